@@ -0,0 +1,76 @@
+#!/bin/bash
+
+# Localnet harness for end-to-end buy/sell tests against dumped pump.fun fixtures
+#
+#   ./localnet.sh dump <MINT>   dump the pump.fun program and accounts for MINT from mainnet
+#   ./localnet.sh test          start solana-test-validator with the fixtures on disk and run the buy/sell test
+#
+# Set LOCALNET_REFRESH=true to re-dump the fixtures from mainnet before the test.
+
+set -e
+
+# Constants
+FIXTURES_DIR="${LOCALNET_FIXTURES_DIR:-fixtures}"
+MAINNET_URL="${LOCALNET_DUMP_URL:-https://api.mainnet-beta.solana.com}"
+PUMP_PROGRAM="6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"
+PUMP_GLOBAL="4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf"
+PUMP_FEE_RECIPIENT="CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM"
+
+# Function to print messages
+function echo_info() {
+    echo "[INFO] $1"
+}
+
+function dump_account() {
+    solana account -u "$MAINNET_URL" "$1" --output json-compact -o "$FIXTURES_DIR/accounts/$1.json" > /dev/null
+    echo_info "Dumped account $1"
+}
+
+function dump() {
+    local mint="$1"
+    if [ -z "$mint" ]; then
+        echo "usage: ./localnet.sh dump <MINT>"
+        exit 1
+    fi
+
+    mkdir -p "$FIXTURES_DIR/accounts"
+
+    echo_info "Dumping pump.fun program..."
+    solana program dump -u "$MAINNET_URL" "$PUMP_PROGRAM" "$FIXTURES_DIR/pump.so"
+
+    local bonding_curve
+    bonding_curve=$(solana find-program-derived-address "$PUMP_PROGRAM" string:bonding-curve pubkey:"$mint" | awk '{print $1}')
+    local associated_bonding_curve
+    associated_bonding_curve=$(spl-token address --token "$mint" --owner "$bonding_curve" --verbose | awk '/Associated token address/ {print $4}')
+
+    dump_account "$PUMP_GLOBAL"
+    dump_account "$PUMP_FEE_RECIPIENT"
+    dump_account "$mint"
+    dump_account "$bonding_curve"
+    dump_account "$associated_bonding_curve"
+
+    echo "$mint" > "$FIXTURES_DIR/mint.txt"
+    echo_info "Fixtures written to $FIXTURES_DIR"
+}
+
+case "$1" in
+    dump)
+        dump "$2"
+        ;;
+    test)
+        if [ ! -f "$FIXTURES_DIR/mint.txt" ]; then
+            echo "No fixtures yet, run ./localnet.sh dump <MINT> first"
+            exit 1
+        fi
+        if [ "${LOCALNET_REFRESH:-false}" = "true" ]; then
+            # the program as deployed now, so a layout change on mainnet fails here first
+            dump "$(cat "$FIXTURES_DIR/mint.txt")"
+        fi
+        echo_info "Running localnet buy/sell test..."
+        cargo run -- --test-localnet
+        ;;
+    *)
+        echo "usage: ./localnet.sh {dump <MINT>|test}"
+        exit 1
+        ;;
+esac
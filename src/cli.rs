@@ -0,0 +1,684 @@
+use std::io::Write;
+use std::sync::Arc;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use serde_json::Value;
+
+use crate::common::arming::{arm_interactively, initialize_arming, ArmingSettings};
+use crate::common::config::{import_signer, Config, LiquidityPool};
+use crate::common::config_export;
+use crate::common::keystore::{read_passphrase, KdfParams, Keystore, KeystoreSettings};
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::common::trade_log::{read_trades, TradeLogEntry};
+use crate::core::position_store::PositionStore;
+use crate::engine::ata_cleanup::{cleanup_empty_accounts, AtaCleanupSettings};
+use crate::engine::panic::panic_sell;
+use crate::engine::shutdown::{restore_positions, save_positions, wait_for_in_flight, ShutdownSettings};
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::api::ApiSettings;
+use crate::tests::{run_dev_wallet_test, run_localnet_test};
+
+/// Pump.fun / PumpSwap sniper and copy trading bot
+#[derive(Debug, Parser)]
+#[command(name = "solana-vntr-sniper", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the bot (default when no subcommand is given)
+    Run,
+    /// Sell the whole balance of every token in the wallet
+    SellAll,
+    /// Sell everything at any price over the fastest route and pause buying
+    ///
+    /// A running bot with the control API enabled is told to panic; otherwise the wallet is sold directly.
+    Panic,
+    /// Sell part or all of one token
+    Sell {
+        /// Token mint address
+        mint: String,
+        /// Share of the balance to sell
+        #[arg(long, default_value_t = 100.0)]
+        percent: f64,
+    },
+    /// Buy a token right away, skipping launch detection
+    Snipe {
+        /// Token mint address
+        mint: String,
+        /// SOL to spend; sized by the position sizer when omitted
+        #[arg(long)]
+        amount: Option<f64>,
+    },
+    /// List open positions with current price and unrealized PnL
+    Positions,
+    /// Show the wallet's SOL and token balances
+    Balance,
+    /// Load and validate the configuration, then exit
+    CheckConfig {
+        /// Also print the resolved configuration, secrets redacted, and what differs from the defaults
+        #[arg(long)]
+        export: bool,
+        #[arg(long, value_enum, default_value_t = ConfigFormat::Toml)]
+        format: ConfigFormat,
+        /// Output file for the export; stdout when omitted
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Close empty token accounts and reclaim their rent
+    CloseAtas,
+    /// Write the confirmed fills of the trade log as CSV, JSON or Parquet
+    ExportTrades {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Output file; stdout when omitted (required for Parquet)
+        #[arg(long, short)]
+        output: Option<String>,
+        /// First day (YYYY-MM-DD) or instant (RFC 3339) to include
+        #[arg(long, value_parser = parse_time_bound)]
+        from: Option<TimeBound>,
+        /// Last day (YYYY-MM-DD, inclusive) or instant (RFC 3339, exclusive) to include
+        #[arg(long, value_parser = parse_time_bound)]
+        to: Option<TimeBound>,
+        /// Also export failed and abandoned transactions
+        #[arg(long)]
+        all: bool,
+    },
+    /// Acknowledge the risk disclaimer and write the signed arming file
+    Arm,
+    /// Generate a new wallet into an encrypted keystore
+    Keygen {
+        /// Keystore file; KEYSTORE_PATH when omitted
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Replace an existing keystore
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt an existing base58 private key into a keystore
+    ImportKey {
+        /// Keystore file; KEYSTORE_PATH when omitted
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Replace an existing keystore
+        #[arg(long)]
+        force: bool,
+        /// Take the key from PRIVATE_KEY instead of prompting for it
+        #[arg(long)]
+        from_env: bool,
+    },
+    /// Run the dev wallet detection test
+    #[command(hide = true)]
+    TestDevWallet,
+    /// Run the localnet buy/sell test
+    #[command(hide = true)]
+    TestLocalnet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+/// A `--from`/`--to` bound of the trade export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeBound {
+    pub at: DateTime<Utc>,
+    /// Given as a whole day, so an upper bound covers all of it
+    pub whole_day: bool,
+}
+
+impl TimeBound {
+    /// First instant after the bound when it is used as the upper one
+    fn end(&self) -> DateTime<Utc> {
+        if self.whole_day { self.at + chrono::Duration::days(1) } else { self.at }
+    }
+}
+
+fn parse_time_bound(value: &str) -> Result<TimeBound, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(TimeBound { at: at.with_timezone(&Utc), whole_day: false });
+    }
+    let day = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("expected YYYY-MM-DD or an RFC 3339 time, got '{}'", value))?;
+    Ok(TimeBound { at: day.and_time(NaiveTime::MIN).and_utc(), whole_day: true })
+}
+
+/// Map the old `--arm`, `--test-dev-wallet` and `--test-localnet` flags to their subcommands
+pub fn normalize_legacy_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    args.into_iter()
+        .enumerate()
+        .map(|(i, arg)| match (i, arg.as_str()) {
+            (1, "--arm") => "arm".to_string(),
+            (1, "--test-dev-wallet") => "test-dev-wallet".to_string(),
+            (1, "--test-localnet") => "test-localnet".to_string(),
+            _ => arg,
+        })
+        .collect()
+}
+
+/// Run a one-shot operational command; `Command::Run` is handled by the caller
+pub async fn run_command(command: Command) -> Result<()> {
+    match command {
+        Command::Run => Err(anyhow!("run is not a one-shot command")),
+        Command::SellAll => sell_all().await,
+        Command::Panic => panic().await,
+        Command::Sell { mint, percent } => sell(&mint, percent).await,
+        Command::Snipe { mint, amount } => snipe(&mint, amount).await,
+        Command::Positions => positions().await,
+        Command::Balance => balance().await,
+        Command::CheckConfig { export, format, output } => check_config(export, format, output.as_deref()).await,
+        Command::CloseAtas => close_atas().await,
+        Command::ExportTrades { format, output, from, to, all } => {
+            export_trades(format, output.as_deref(), from, to, all)
+        }
+        Command::Arm => {
+            let wallet = import_signer().map_err(|e| anyhow!("Failed to load wallet: {}", e))?;
+            arm_interactively(wallet.as_ref(), &ArmingSettings::from_env())
+        }
+        Command::Keygen { output, force } => keygen(output, force),
+        Command::ImportKey { output, force, from_env } => import_key(output, force, from_env),
+        Command::TestDevWallet => {
+            println!("Running dev wallet detection test...");
+            run_dev_wallet_test().await.map_err(|e| anyhow!("Error running dev wallet test: {}", e))?;
+            println!("Dev wallet test completed successfully!");
+            Ok(())
+        }
+        Command::TestLocalnet => {
+            println!("Running localnet buy/sell test...");
+            run_localnet_test().await.map_err(|e| anyhow!("Error running localnet test: {}", e))?;
+            println!("Localnet test completed successfully!");
+            Ok(())
+        }
+    }
+}
+
+/// Executor with the positions saved by the last run
+async fn executor(logger: &Logger) -> Arc<TradeExecutor> {
+    let config = Config::new().await;
+    let executor = Arc::new(TradeExecutor::new(
+        config.app_state.clone(),
+        config.swap_config.clone(),
+        Arc::new(PositionStore::new()),
+        Logger::new("[EXECUTOR] => ".green().bold().to_string()),
+    ));
+    restore_positions(&executor, &ShutdownSettings::from_env(), logger);
+    if let Err(e) = executor.refresh_wsol().await {
+        logger.error(format!("Failed to look up the WSOL account: {}", e));
+    }
+    executor
+}
+
+/// Arm for live transactions the same way the bot does at startup
+async fn require_armed(logger: &Logger) -> Result<()> {
+    let config = Config::new().await;
+    if initialize_arming(&config.mode, config.app_state.wallet.as_ref(), logger) {
+        Ok(())
+    } else {
+        Err(anyhow!("Live trading is not armed, nothing was sent"))
+    }
+}
+
+/// Let sent trades confirm, then write the open positions back to the state file
+async fn settle(executor: &TradeExecutor, logger: &Logger) {
+    let settings = ShutdownSettings::from_env();
+    wait_for_in_flight(executor, &settings, logger).await;
+    let pools: Vec<LiquidityPool> = executor.positions().snapshot();
+    if let Err(e) = save_positions(&settings.state_file, &pools) {
+        logger.error(format!("Failed to save positions to {}: {}", settings.state_file, e));
+    }
+}
+
+async fn sell_all() -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    require_armed(&logger).await?;
+
+    let wsol = spl_token::native_mint::ID.to_string();
+    let held: Vec<String> = executor
+        .token_accounts()
+        .await?
+        .into_iter()
+        .filter(|account| account.amount > 0 && account.mint != wsol)
+        .map(|account| account.mint)
+        .collect();
+    if held.is_empty() {
+        logger.log("No token balances to sell".to_string());
+        return Ok(());
+    }
+
+    logger.log(format!("Selling {} tokens", held.len()).yellow().to_string());
+    let mut failed = 0;
+    for mint in &held {
+        record_decision(Decision::Sell, mint, ReasonCode::ManualSell, "cli sell-all");
+        match executor.sell_all_now(mint).await {
+            Ok(signature) => logger.log(format!("Sold {}: {}", mint, signature)),
+            Err(e) => {
+                failed += 1;
+                logger.error(format!("Sell failed for {}: {}", mint, e));
+            }
+        }
+    }
+    settle(&executor, &logger).await;
+
+    if failed > 0 {
+        return Err(anyhow!("{} of {} sells failed", failed, held.len()));
+    }
+    Ok(())
+}
+
+/// Ask a running bot to panic through its control API, `None` when no bot answers
+async fn panic_running_bot(logger: &Logger) -> Option<Result<Value>> {
+    let settings = ApiSettings::from_env();
+    if !settings.enabled || settings.token.is_empty() {
+        return None;
+    }
+    let host = if settings.bind_address == "0.0.0.0" { "127.0.0.1" } else { settings.bind_address.as_str() };
+    let url = format!("http://{}:{}/panic", host, settings.port);
+    let response = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth(&settings.token)
+        .timeout(std::time::Duration::from_secs(60))
+        .send()
+        .await;
+    match response {
+        Ok(response) => Some(match response.error_for_status() {
+            Ok(response) => response.json::<Value>().await.map_err(Into::into),
+            Err(e) => Err(anyhow!("The running bot refused the panic: {}", e)),
+        }),
+        Err(e) => {
+            logger.log(format!("No bot answering at {} ({}), selling from the wallet directly", url, e));
+            None
+        }
+    }
+}
+
+async fn panic() -> Result<()> {
+    let logger = Logger::new("[CLI] => ".red().bold().to_string());
+    if let Some(result) = panic_running_bot(&logger).await {
+        let report = result?;
+        logger.log(format!("Running bot panicked:\n{}", serde_json::to_string_pretty(&report)?));
+        return Ok(());
+    }
+
+    let executor = executor(&logger).await;
+    require_armed(&logger).await?;
+    let report = panic_sell(&executor, &logger).await;
+    settle(&executor, &logger).await;
+    if report.failed() > 0 {
+        return Err(anyhow!("{} of {} panic sells failed", report.failed(), report.sells.len()));
+    }
+    Ok(())
+}
+
+async fn sell(mint: &str, percent: f64) -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    require_armed(&logger).await?;
+
+    record_decision(Decision::Sell, mint, ReasonCode::ManualSell, "cli");
+    let signature = executor.sell(mint, percent).await?;
+    logger.log(format!("Sold {:.1}% of {}: {}", percent, mint, signature));
+    settle(&executor, &logger).await;
+    Ok(())
+}
+
+async fn snipe(mint: &str, amount: Option<f64>) -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    require_armed(&logger).await?;
+
+    let (sol_amount, signature) = executor.snipe(mint, amount).await?;
+    logger.log(format!("Sniped {} for {:.4} SOL: {}", mint, sol_amount, signature));
+    settle(&executor, &logger).await;
+    logger.log("The position is saved and managed by the exit rules once the bot runs".to_string());
+    Ok(())
+}
+
+async fn positions() -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    let pools: Vec<LiquidityPool> = executor.positions().snapshot();
+    let balances = executor.token_accounts().await?;
+
+    if pools.is_empty() {
+        println!("No open positions");
+        return Ok(());
+    }
+    println!("{:<46} {:>16} {:>14} {:>14} {:>9}", "MINT", "BALANCE", "ENTRY (SOL)", "PRICE (SOL)", "PNL %");
+    for pool in pools {
+        let balance = balances
+            .iter()
+            .find(|account| account.mint == pool.mint)
+            .map(|account| account.ui_amount)
+            .unwrap_or(0.0);
+        let price = executor.current_price(&pool.mint).await.ok();
+        let pnl = match price {
+            Some(price) if pool.buy_price > 0.0 => format!("{:+.1}", (price / pool.buy_price - 1.0) * 100.0),
+            _ => "-".to_string(),
+        };
+        println!(
+            "{:<46} {:>16.2} {:>14.10} {:>14} {:>9}",
+            pool.mint,
+            balance,
+            pool.buy_price,
+            price.map(|price| format!("{:.10}", price)).unwrap_or_else(|| "-".to_string()),
+            pnl
+        );
+    }
+    Ok(())
+}
+
+async fn balance() -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    let sol = executor.wallet_balance().await?;
+    let accounts = executor.token_accounts().await?;
+
+    println!("SOL: {:.6}", sol);
+    let held: Vec<_> = accounts.iter().filter(|account| account.amount > 0).collect();
+    println!("Token accounts: {} ({} empty)", accounts.len(), accounts.len() - held.len());
+    for account in held {
+        println!("  {:<46} {:>16.6}", account.mint, account.ui_amount);
+    }
+    Ok(())
+}
+
+async fn check_config(export: bool, format: ConfigFormat, output: Option<&str>) -> Result<()> {
+    let config = Config::new().await;
+    if export {
+        export_config(&config, format, output)?;
+    }
+    match config.validate() {
+        Ok(()) => {
+            println!("{}", "✅ Configuration is valid".green());
+            Ok(())
+        }
+        Err(errors) => {
+            for error in &errors {
+                println!("{}", format!("   - {}", error).red());
+            }
+            Err(anyhow!("{} configuration errors found", errors.len()))
+        }
+    }
+}
+
+async fn close_atas() -> Result<()> {
+    let logger = Logger::new("[CLI] => ".cyan().bold().to_string());
+    let executor = executor(&logger).await;
+    require_armed(&logger).await?;
+
+    let signatures = cleanup_empty_accounts(&executor, &AtaCleanupSettings::from_env()).await?;
+    if signatures.is_empty() {
+        logger.log("No empty token accounts to close".to_string());
+    }
+    for signature in signatures {
+        logger.log(format!("Closed empty token accounts: {}", signature));
+    }
+    Ok(())
+}
+
+/// Write the resolved configuration, then list the settings that differ from their defaults
+fn export_config(config: &Config, format: ConfigFormat, output: Option<&str>) -> Result<()> {
+    let resolved = config_export::export(config);
+    let content = match format {
+        ConfigFormat::Toml => config_export::to_toml(&resolved),
+        ConfigFormat::Json => serde_json::to_string_pretty(&resolved)? + "\n",
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            eprintln!("Exported the configuration to {}", path);
+        }
+        None => std::io::stdout().write_all(content.as_bytes())?,
+    }
+
+    let changes = config_export::diff(&resolved, &config_export::defaults());
+    eprintln!("{}", format!("{} settings differ from the defaults:", changes.len()).bold());
+    for change in changes {
+        eprintln!("  {:<52} {} (default {})", change.key, change.value, change.default);
+    }
+    Ok(())
+}
+
+/// Trades inside the bounds; only confirmed fills unless `all`
+fn select_trades(trades: Vec<TradeLogEntry>, from: Option<TimeBound>, to: Option<TimeBound>, all: bool) -> Vec<TradeLogEntry> {
+    trades.into_iter()
+        .filter(|trade| all || trade.status == "confirmed")
+        .filter(|trade| from.map(|from| trade.timestamp >= from.at).unwrap_or(true))
+        .filter(|trade| to.map(|to| trade.timestamp < to.end()).unwrap_or(true))
+        .collect()
+}
+
+fn export_trades(format: ExportFormat, output: Option<&str>, from: Option<TimeBound>, to: Option<TimeBound>, all: bool) -> Result<()> {
+    let trades = select_trades(read_trades(), from, to, all);
+    let content = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&trades)? + "\n",
+        ExportFormat::Csv => trades_to_csv(&trades),
+        ExportFormat::Parquet => {
+            let path = output.ok_or_else(|| anyhow!("Parquet export needs an output file (-o)"))?;
+            write_parquet(&trades, path)?;
+            eprintln!("Exported {} trades to {}", trades.len(), path);
+            return Ok(());
+        }
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            eprintln!("Exported {} trades to {}", trades.len(), path);
+        }
+        None => std::io::stdout().write_all(content.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Keystore settings with `output` replacing KEYSTORE_PATH
+fn keystore_settings(output: Option<String>) -> KeystoreSettings {
+    dotenv::dotenv().ok();
+    let mut settings = KeystoreSettings::from_env();
+    if let Some(path) = output {
+        settings.path = path;
+    }
+    settings
+}
+
+fn keygen(output: Option<String>, force: bool) -> Result<()> {
+    let settings = keystore_settings(output);
+    let keypair = Keypair::new();
+    let passphrase = read_passphrase(&settings, true)?;
+    Keystore::encrypt(&keypair, &passphrase, KdfParams::default())?.save(&settings.path, force)?;
+    println!("Created keystore {}", settings.path);
+    println!("Wallet address: {}", keypair.pubkey());
+    Ok(())
+}
+
+fn import_key(output: Option<String>, force: bool, from_env: bool) -> Result<()> {
+    let settings = keystore_settings(output);
+    let private_key = if from_env {
+        std::env::var("PRIVATE_KEY").map_err(|_| anyhow!("PRIVATE_KEY is not set"))?
+    } else {
+        rpassword::prompt_password("Base58 private key: ")?
+    };
+    let bytes = bs58::decode(private_key.trim()).into_vec().map_err(|e| anyhow!("Invalid base58 key: {}", e))?;
+    let keypair = Keypair::from_bytes(&bytes).map_err(|e| anyhow!("Invalid private key: {}", e))?;
+
+    let passphrase = read_passphrase(&settings, true)?;
+    Keystore::encrypt(&keypair, &passphrase, KdfParams::default())?.save(&settings.path, force)?;
+    println!("Imported {} into {}", keypair.pubkey(), settings.path);
+    if from_env {
+        println!("{}", "Remove PRIVATE_KEY from .env now that the keystore is in place".yellow());
+    }
+    Ok(())
+}
+
+fn trades_to_csv(trades: &[TradeLogEntry]) -> String {
+    let mut csv = String::from("timestamp,mint,side,status,signature,sol_amount,token_amount,price,fee_sol,tip_sol,attempts,latency_ms,reason,reason_code\n");
+    for trade in trades {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            trade.timestamp.to_rfc3339(),
+            trade.mint,
+            trade.side,
+            trade.status,
+            trade.signature,
+            trade.sol_amount,
+            trade.token_amount,
+            trade.price,
+            trade.fee_sol,
+            trade.tip_sol,
+            trade.attempts,
+            trade.latency_ms,
+            csv_field(trade.reason.as_deref().unwrap_or("")),
+            trade.reason_code.map(|code| code.to_string()).unwrap_or_default(),
+        ));
+    }
+    csv
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(trades: &[TradeLogEntry], path: &str) -> Result<()> {
+    use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray, UInt64Array};
+    use parquet::arrow::ArrowWriter;
+
+    let text = |field: fn(&TradeLogEntry) -> &str| -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(trades.iter().map(field)))
+    };
+    let number = |field: fn(&TradeLogEntry) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter_values(trades.iter().map(field)))
+    };
+    let batch = RecordBatch::try_from_iter(vec![
+        ("timestamp", Arc::new(
+            TimestampMillisecondArray::from_iter_values(trades.iter().map(|t| t.timestamp.timestamp_millis())).with_timezone("UTC"),
+        ) as ArrayRef),
+        ("mint", text(|t| &t.mint)),
+        ("side", text(|t| &t.side)),
+        ("status", text(|t| &t.status)),
+        ("signature", text(|t| &t.signature)),
+        ("sol_amount", number(|t| t.sol_amount)),
+        ("token_amount", Arc::new(UInt64Array::from_iter_values(trades.iter().map(|t| t.token_amount))) as ArrayRef),
+        ("price", number(|t| t.price)),
+        ("fee_sol", number(|t| t.fee_sol)),
+        ("tip_sol", number(|t| t.tip_sol)),
+        ("reason", Arc::new(StringArray::from_iter(trades.iter().map(|t| t.reason.as_deref()))) as ArrayRef),
+        ("reason_code", Arc::new(StringArray::from_iter(trades.iter().map(|t| t.reason_code.map(|code| code.to_string())))) as ArrayRef),
+    ])?;
+    let mut writer = ArrowWriter::try_new(std::fs::File::create(path)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_trades: &[TradeLogEntry], _path: &str) -> Result<()> {
+    Err(anyhow!("Parquet export needs a build with --features parquet"))
+}
+
+/// Quote a free-text field if it could break the row
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(list: &[&str]) -> Cli {
+        Cli::try_parse_from(normalize_legacy_args(list.iter().map(|s| s.to_string()))).unwrap()
+    }
+
+    fn trade(at: &str, status: &str) -> TradeLogEntry {
+        TradeLogEntry {
+            timestamp: DateTime::parse_from_rfc3339(at).unwrap().with_timezone(&Utc),
+            mint: "mint".to_string(),
+            side: "buy".to_string(),
+            status: status.to_string(),
+            signature: "sig".to_string(),
+            sol_amount: 0.1,
+            token_amount: 1000,
+            price: 0.0001,
+            fee_sol: 0.000105,
+            tip_sol: 0.001,
+            attempts: 1,
+            latency_ms: 400,
+            reason: None,
+            reason_code: None,
+        }
+    }
+
+    fn trades() -> Vec<TradeLogEntry> {
+        vec![
+            trade("2024-12-31T23:59:59Z", "confirmed"),
+            trade("2025-01-01T00:00:00Z", "confirmed"),
+            trade("2025-06-30T12:00:00Z", "failed"),
+            trade("2025-12-31T23:00:00Z", "confirmed"),
+            trade("2026-01-01T00:00:00Z", "confirmed"),
+        ]
+    }
+
+    #[test]
+    fn test_legacy_flags_map_to_subcommands() {
+        assert!(matches!(parse(&["bot", "--arm"]).command, Some(Command::Arm)));
+        assert!(parse(&["bot"]).command.is_none());
+    }
+
+    #[test]
+    fn test_sell_subcommand() {
+        let cli = parse(&["bot", "sell", "Mint111", "--percent", "50"]);
+        assert!(matches!(cli.command, Some(Command::Sell { ref mint, percent }) if mint == "Mint111" && percent == 50.0));
+    }
+
+    #[test]
+    fn test_check_config_export_flags() {
+        let cli = parse(&["bot", "check-config", "--export", "--format", "json"]);
+        assert!(matches!(cli.command, Some(Command::CheckConfig { export: true, format: ConfigFormat::Json, output: None })));
+    }
+
+    #[test]
+    fn test_csv_field_quotes_commas() {
+        assert_eq!(csv_field("slippage, retry"), "\"slippage, retry\"");
+    }
+
+    #[test]
+    fn test_export_date_bounds_cover_whole_days() {
+        let from = parse_time_bound("2025-01-01").ok();
+        let to = parse_time_bound("2025-12-31").ok();
+        assert_eq!(select_trades(trades(), from, to, false).len(), 2);
+    }
+
+    #[test]
+    fn test_export_keeps_failed_trades_on_request() {
+        let from = parse_time_bound("2025-01-01").ok();
+        let to = parse_time_bound("2025-12-31").ok();
+        assert_eq!(select_trades(trades(), from, to, true).len(), 3);
+    }
+
+    #[test]
+    fn test_export_time_bound_is_exclusive() {
+        let from = parse_time_bound("2025-01-01").ok();
+        let to = parse_time_bound("2025-12-31T23:00:00Z").ok();
+        assert_eq!(select_trades(trades(), from, to, false).len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_unknown_date_format() {
+        assert!(parse_time_bound("31/12/2025").is_err());
+    }
+}
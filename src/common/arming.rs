@@ -0,0 +1,186 @@
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use anyhow::{anyhow, Result};
+use anchor_client::solana_sdk::{
+    signature::Signature,
+    signer::Signer,
+};
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::common::config::ModeConfig;
+use crate::common::logger::Logger;
+
+/// Bump this whenever the disclaimer text changes so existing arming files are invalidated
+pub const DISCLAIMER_VERSION: u32 = 1;
+
+/// Phrase the operator must type to arm live trading
+pub const ARMING_PHRASE: &str = "I UNDERSTAND THE RISKS";
+
+pub const RISK_DISCLAIMER: &str = "\
+This bot sends REAL transactions with REAL funds from the configured wallet.
+- Memecoin trading is extremely risky; most tokens go to zero.
+- Bugs, misconfiguration, RPC or relay failures can lose funds.
+- Transactions cannot be reversed once they land.
+- You are solely responsible for every trade the bot makes.
+Only arm live trading with funds you can afford to lose.";
+
+/// Whether live transactions are allowed in this process
+static ARMED: AtomicBool = AtomicBool::new(false);
+
+/// Live trading arming settings
+#[derive(Debug, Clone)]
+pub struct ArmingSettings {
+    /// Operator intent to trade live; without it the bot never sends transactions
+    pub arm_live_trading: bool,
+    /// Path of the signed arming file written by `--arm`
+    pub arming_file: String,
+}
+
+impl ArmingSettings {
+    /// Load arming settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            arm_live_trading: std::env::var("ARM_LIVE_TRADING").unwrap_or_default() == "true",
+            arming_file: std::env::var("ARMING_FILE").unwrap_or_else(|_| "live_trading.armed".to_string()),
+        }
+    }
+}
+
+/// Signed acknowledgment of the risk disclaimer for one wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmingRecord {
+    pub wallet: String,
+    pub disclaimer_version: u32,
+    pub armed_at: String,
+    /// Wallet signature over `arming_message(wallet, version)`
+    pub signature: String,
+}
+
+fn arming_message(wallet: &str, version: u32) -> String {
+    format!("vntr-sniper live trading armed: wallet={} disclaimer=v{}", wallet, version)
+}
+
+/// Whether live transactions are allowed
+pub fn is_armed() -> bool {
+    ARMED.load(Ordering::SeqCst)
+}
+
+/// Fail unless live trading has been armed
+///
+/// Every transaction sender calls this first, so nothing can reach the network
+/// from a default or paper configuration.
+pub fn ensure_armed() -> Result<()> {
+    if is_armed() {
+        Ok(())
+    } else {
+        Err(anyhow!("Live trading is not armed (set LIVE_MODE=true, ARM_LIVE_TRADING=true and run with --arm)"))
+    }
+}
+
+/// Arm without the disclaimer for a throwaway wallet on a local validator
+///
+/// Only for the localnet test: refuses any RPC that is not on this machine.
+pub fn arm_for_localnet(rpc_url: &str) -> Result<()> {
+    let url = url::Url::parse(rpc_url)?;
+    if !matches!(url.host_str(), Some("127.0.0.1") | Some("localhost")) {
+        return Err(anyhow!("Refusing to arm for {}, only a local validator can be armed without the disclaimer", rpc_url));
+    }
+    ARMED.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Show the disclaimer, ask for the arming phrase and write a signed arming file
+pub fn arm_interactively(wallet: &dyn Signer, settings: &ArmingSettings) -> Result<()> {
+    println!("{}", "⚠️  LIVE TRADING RISK DISCLAIMER".red().bold());
+    println!("{}", RISK_DISCLAIMER.yellow());
+    println!("\nWallet: {}", wallet.pubkey());
+    print!("Type \"{}\" to arm live trading: ", ARMING_PHRASE);
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    if input.trim() != ARMING_PHRASE {
+        return Err(anyhow!("Arming phrase did not match, live trading stays disarmed"));
+    }
+
+    let wallet_str = wallet.pubkey().to_string();
+    let signature = wallet.try_sign_message(arming_message(&wallet_str, DISCLAIMER_VERSION).as_bytes())?;
+    let record = ArmingRecord {
+        wallet: wallet_str,
+        disclaimer_version: DISCLAIMER_VERSION,
+        armed_at: Utc::now().to_rfc3339(),
+        signature: signature.to_string(),
+    };
+    std::fs::write(&settings.arming_file, serde_json::to_string_pretty(&record)?)?;
+    println!("{}", format!("✅ Live trading armed, acknowledgment written to {}", settings.arming_file).green());
+    Ok(())
+}
+
+/// Check that the arming file was signed by this wallet for the current disclaimer
+pub fn verify_arming_file(wallet: &dyn Signer, path: &str) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|_| anyhow!("No arming file at {}", path))?;
+    let record: ArmingRecord = serde_json::from_str(&content)?;
+
+    if record.wallet != wallet.pubkey().to_string() {
+        return Err(anyhow!("Arming file was created for a different wallet ({})", record.wallet));
+    }
+    if record.disclaimer_version != DISCLAIMER_VERSION {
+        return Err(anyhow!(
+            "Arming file acknowledges disclaimer v{}, current is v{}",
+            record.disclaimer_version,
+            DISCLAIMER_VERSION
+        ));
+    }
+
+    let signature = Signature::from_str(&record.signature)?;
+    let message = arming_message(&record.wallet, record.disclaimer_version);
+    if !signature.verify(wallet.pubkey().as_ref(), message.as_bytes()) {
+        return Err(anyhow!("Arming file signature is invalid"));
+    }
+    Ok(())
+}
+
+/// Decide at startup whether this process may send live transactions
+///
+/// Live trading needs all of: `LIVE_MODE=true`, `ARM_LIVE_TRADING=true`, and either
+/// a valid signed arming file or an interactive confirmation on a terminal. Anything
+/// else leaves the bot disarmed in paper/observer mode.
+pub fn initialize_arming(mode: &ModeConfig, wallet: &dyn Signer, logger: &Logger) -> bool {
+    let settings = ArmingSettings::from_env();
+
+    let armed = if !mode.live_mode {
+        logger.log("🧪 LIVE_MODE is off - running in paper mode, no transactions will be sent".yellow().to_string());
+        false
+    } else if !settings.arm_live_trading {
+        logger.log("🔒 LIVE_MODE is on but ARM_LIVE_TRADING is not set - staying disarmed".yellow().to_string());
+        false
+    } else {
+        match verify_arming_file(wallet, &settings.arming_file) {
+            Ok(()) => true,
+            Err(e) if std::io::stdin().is_terminal() => {
+                logger.log(format!("Arming required: {}", e).yellow().to_string());
+                match arm_interactively(wallet, &settings) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        logger.error(e.to_string());
+                        false
+                    }
+                }
+            }
+            Err(e) => {
+                logger.error(format!("🔒 Live trading disarmed: {} (run with --arm once)", e));
+                false
+            }
+        }
+    };
+
+    if armed {
+        logger.log("🔴 LIVE TRADING ARMED - transactions will be sent".red().bold().to_string());
+    }
+    ARMED.store(armed, Ordering::SeqCst);
+    armed
+}
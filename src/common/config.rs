@@ -1,5 +1,5 @@
 //! نظام إعدادات شامل لـ Pump Fun Trading Bot
-//! يدعم جميع الـ 96 إعداد المطلوب مع نظام validation متقدم
+//! يدعم جميع الـ 101 إعداد المطلوب مع نظام validation متقدم
 
 use anyhow::{Result, anyhow};
 use bs58;
@@ -8,17 +8,28 @@ use dotenv::dotenv;
 use reqwest::Error;
 use serde::{Deserialize, Serialize};
 use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair, signer::Signer};
-use tokio::sync::{Mutex, OnceCell};
+use arc_swap::ArcSwap;
+use tokio::sync::OnceCell;
 use std::{env, sync::Arc, collections::HashMap};
 use thiserror::Error;
 
 use crate::{
-    common::{constants::INIT_MSG, logger::Logger, blacklist::Blacklist},
-    engine::swap::{SwapDirection, SwapInType},
+    common::{
+        constants::INIT_MSG,
+        logger::Logger,
+        blacklist::Blacklist,
+        keystore::{load_keystore_wallet, KeystoreSettings},
+        signer::{connect_signer, SignerSettings, WalletSigner},
+    },
+    engine::{
+        swap::{SwapDirection, SwapInType},
+        timer::TimerSchedule,
+    },
+    services::market_cap::MarketCapUnit,
 };
 
 // Global configuration instance
-static GLOBAL_CONFIG: OnceCell<Mutex<Config>> = OnceCell::const_new();
+static GLOBAL_CONFIG: OnceCell<ArcSwap<Config>> = OnceCell::const_new();
 
 // Constants
 const HELIUS_PROXY: &str = "HuuaCvCTvpEFT9DfMynCNM4CppCRU6r5oikziF8ZpzMm2Au2eoTjkWgTnQq6TBb6Jpt";
@@ -210,14 +221,14 @@ impl Default for BloxRouteConfig {
     }
 }
 
-/// Advanced filter settings - 14 settings
+/// Advanced filter settings - 17 settings
 /// Comprehensive filtering system for token analysis and selection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedFilterSettings {
-    /// Minimum market cap threshold in USD
+    /// Minimum market cap threshold in `MARKET_CAP_UNIT`, thousands of USD by default
     pub min_market_cap: f64,
 
-    /// Maximum market cap threshold in USD
+    /// Maximum market cap threshold in `MARKET_CAP_UNIT`
     pub max_market_cap: f64,
 
     /// Enable/disable market cap filtering
@@ -258,6 +269,30 @@ pub struct AdvancedFilterSettings {
 
     /// Enable/disable developer buy filtering
     pub dev_buy_enabled: bool,
+
+    /// Minimum bonding curve completion in percent
+    pub min_curve_progress: f64,
+
+    /// Maximum bonding curve completion in percent
+    pub max_curve_progress: f64,
+
+    /// Enable/disable bonding curve progress filtering
+    pub curve_progress_enabled: bool,
+
+    /// Minimum pool liquidity in USD of a graduated copy-trade token
+    pub min_liquidity_usd: f64,
+
+    /// Minimum 24h trading volume in USD of a graduated copy-trade token
+    pub min_volume_24h_usd: f64,
+
+    /// Minimum 1h price change in percent of a graduated copy-trade token
+    pub min_price_change_1h: f64,
+
+    /// Maximum 1h price change in percent of a graduated copy-trade token
+    pub max_price_change_1h: f64,
+
+    /// Enable/disable Birdeye/DexScreener market data filtering of graduated copy-trade tokens
+    pub market_data_enabled: bool,
 }
 
 impl Default for AdvancedFilterSettings {
@@ -278,6 +313,14 @@ impl Default for AdvancedFilterSettings {
             max_launcher_sol_balance: 1.0,
             launcher_sol_enabled: true,
             dev_buy_enabled: true,
+            min_curve_progress: 0.0,
+            max_curve_progress: 100.0,
+            curve_progress_enabled: false,
+            min_liquidity_usd: 10_000.0,
+            min_volume_24h_usd: 0.0,
+            min_price_change_1h: -100.0,
+            max_price_change_1h: 1_000.0,
+            market_data_enabled: false,
         }
     }
 }
@@ -298,10 +341,10 @@ pub struct CopyTradingConfig {
     /// Enable multiple target tracking mode
     pub multi_target_mode: bool,
 
-    /// Market cap threshold to trigger buy operations
+    /// Highest USD market cap a target's buy is copied at, under the full copy filter mode
     pub mc_threshold_to_buy: f64,
 
-    /// Market cap threshold to follow target wallet
+    /// Market cap threshold to follow target wallet, in USD
     pub mc_threshold_to_follow: f64,
 }
 
@@ -318,74 +361,115 @@ impl Default for CopyTradingConfig {
     }
 }
 
-/// Private logic configuration - 15 settings
-/// Multi-stage percentage-based trading strategy with delayed execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PrivateLogicConfig {
-    /// Enable/disable private logic functionality
-    pub enabled: bool,
-
-    /// Stage 1 percentage threshold
-    pub stage_1_percent: f64,
-
-    /// Stage 1 delay in milliseconds
-    pub stage_1_delay: u64,
-
-    /// Stage 2 percentage threshold
-    pub stage_2_percent: f64,
-
-    /// Stage 2 delay in milliseconds
-    pub stage_2_delay: u64,
-
-    /// Stage 3 percentage threshold
-    pub stage_3_percent: f64,
-
-    /// Stage 3 delay in milliseconds
-    pub stage_3_delay: u64,
+/// What a private logic stage does once its delay has passed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageAction {
+    /// Sell `percent` of the remaining position
+    Sell,
+    /// Move the stop up to `percent` below the current price
+    TightenStop,
+    /// Only report that the stage was reached
+    Notify,
+}
 
-    /// Stage 4 percentage threshold
-    pub stage_4_percent: f64,
+impl std::str::FromStr for StageAction {
+    type Err = String;
 
-    /// Stage 4 delay in milliseconds
-    pub stage_4_delay: u64,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sell" => Ok(StageAction::Sell),
+            "tighten_stop" | "stop" => Ok(StageAction::TightenStop),
+            "notify" => Ok(StageAction::Notify),
+            other => Err(format!("Unknown stage action '{}', expected sell, tighten_stop or notify", other)),
+        }
+    }
+}
 
-    /// Stage 5 percentage threshold
-    pub stage_5_percent: f64,
+fn default_stage_action() -> StageAction {
+    StageAction::Sell
+}
 
-    /// Stage 5 delay in milliseconds
-    pub stage_5_delay: u64,
+/// One step of the private logic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivateLogicStage {
+    /// Share to sell for `sell`, stop distance for `tighten_stop`, in percent
+    pub percent: f64,
+    /// Delay after the buy in milliseconds
+    pub delay_ms: u64,
+    #[serde(default = "default_stage_action")]
+    pub action: StageAction,
+}
 
-    /// Stage 6 percentage threshold
-    pub stage_6_percent: f64,
+impl PrivateLogicStage {
+    /// Parse stages from a JSON array, or from `percent:delay_ms[:action]` pairs like `10:1000,20:2000:tighten_stop`
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        let value = value.trim();
+        if value.starts_with('[') {
+            return serde_json::from_str(value).map_err(|e| format!("Invalid stage list: {}", e));
+        }
+        let mut stages = Vec::new();
+        for stage in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let mut parts = stage.split(':');
+            let (Some(percent), Some(delay_ms)) = (parts.next(), parts.next()) else {
+                return Err(format!("Invalid stage '{}', expected percent:delay_ms[:action]", stage));
+            };
+            stages.push(PrivateLogicStage {
+                percent: percent.trim().parse::<f64>().map_err(|_| format!("Invalid percent in '{}'", stage))?,
+                delay_ms: delay_ms.trim().parse::<u64>().map_err(|_| format!("Invalid delay in '{}'", stage))?,
+                action: parts.next().map(str::parse).transpose()?.unwrap_or(StageAction::Sell),
+            });
+        }
+        Ok(stages)
+    }
+}
 
-    /// Stage 6 delay in milliseconds
-    pub stage_6_delay: u64,
+/// Private logic configuration - 15 settings (the flag and a stage list replacing the 7 stage pairs)
+/// Multi-stage percentage-based trading strategy with delayed execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateLogicConfig {
+    /// Enable/disable private logic functionality
+    pub enabled: bool,
 
-    /// Stage 7 percentage threshold
-    pub stage_7_percent: f64,
+    /// Stages in the order they run, any number of them
+    pub stages: Vec<PrivateLogicStage>,
+}
 
-    /// Stage 7 delay in milliseconds
-    pub stage_7_delay: u64,
+impl PrivateLogicConfig {
+    /// Percents in range, delays strictly increasing, and each stop tighter than the one before
+    pub fn validate_stages(&self) -> Result<(), String> {
+        let mut last_delay = None;
+        let mut last_stop: Option<f64> = None;
+        for (index, stage) in self.stages.iter().enumerate() {
+            if stage.percent <= 0.0 || stage.percent > 100.0 {
+                return Err(format!("stage {} percent must be in (0, 100], got {}", index + 1, stage.percent));
+            }
+            if last_delay.is_some_and(|last| stage.delay_ms <= last) {
+                return Err(format!("stage {} delay must be later than the stage before", index + 1));
+            }
+            last_delay = Some(stage.delay_ms);
+            if stage.action == StageAction::TightenStop {
+                if last_stop.is_some_and(|last| stage.percent >= last) {
+                    return Err(format!("stage {} stop must be tighter than the one before", index + 1));
+                }
+                last_stop = Some(stage.percent);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for PrivateLogicConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            stage_1_percent: 10.0,
-            stage_1_delay: 1000,
-            stage_2_percent: 20.0,
-            stage_2_delay: 2000,
-            stage_3_percent: 30.0,
-            stage_3_delay: 3000,
-            stage_4_percent: 40.0,
-            stage_4_delay: 4000,
-            stage_5_percent: 50.0,
-            stage_5_delay: 5000,
-            stage_6_percent: 60.0,
-            stage_6_delay: 6000,
-            stage_7_percent: 70.0,
-            stage_7_delay: 7000,
+            stages: (1..=7)
+                .map(|n| PrivateLogicStage {
+                    percent: n as f64 * 10.0,
+                    delay_ms: n * 1000,
+                    action: StageAction::Sell,
+                })
+                .collect(),
         }
     }
 }
@@ -410,7 +494,7 @@ impl Default for InverseBuyConfig {
     }
 }
 
-/// Timer configuration - 4 settings
+/// Timer configuration - 6 settings
 /// Time-based control for bot operations with scheduled start/stop
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimerConfig {
@@ -425,6 +509,13 @@ pub struct TimerConfig {
 
     /// Automatically sell all positions when stopping
     pub auto_sell_on_stop: bool,
+
+    /// IANA timezone the times are read in, e.g. Europe/Berlin ("local" = server time)
+    pub timezone: String,
+
+    /// Per-weekday windows, e.g. `mon-fri=09:00-12:00,14:00-22:00;sat=10:00-14:00`;
+    /// empty uses start_time-stop_time every day
+    pub schedule: String,
 }
 
 impl Default for TimerConfig {
@@ -434,6 +525,8 @@ impl Default for TimerConfig {
             start_time: "00:00".to_string(),
             stop_time: "23:59".to_string(),
             auto_sell_on_stop: false,
+            timezone: "local".to_string(),
+            schedule: String::new(),
         }
     }
 }
@@ -456,8 +549,8 @@ impl Default for ModeConfig {
     fn default() -> Self {
         Self {
             simulation_mode: false,
-            live_mode: true,
-            paper_trading: false,
+            live_mode: false,
+            paper_trading: true,
         }
     }
 }
@@ -530,7 +623,7 @@ impl std::hash::Hash for LiquidityPool {
 }
 
 /// Trading status enumeration
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Status {
     Bought,
     Buying,
@@ -545,7 +638,10 @@ pub enum Status {
 pub struct AppState {
     pub rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
     pub rpc_nonblocking_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
-    pub wallet: Arc<Keypair>,
+    /// Signs for the primary wallet, locally or through SIGNER_BACKEND
+    pub wallet: WalletSigner,
+    /// Extra trading wallets from EXTRA_PRIVATE_KEYS, used alongside `wallet`
+    pub wallets: Vec<WalletSigner>,
 }
 
 /// Swap configuration container
@@ -569,8 +665,8 @@ struct SolanaData {
     usd: f64,
 }
 
-/// Main configuration structure containing all 96 settings
-/// Total: 96 settings (15 existing + 81 new)
+/// Main configuration structure containing all 101 settings
+/// Total: 101 settings (15 existing + 86 new)
 #[derive(Clone)]
 pub struct Config {
     // ============ EXISTING SETTINGS (15) - PRESERVED AS-IS ============
@@ -584,8 +680,8 @@ pub struct Config {
     pub time_exceed: u64,                           // 6
     pub blacklist: Blacklist,                       // Compound (not counted)
     pub counter_limit: u32,                         // 7 (as counter_limit)
-    pub min_dev_buy: u32,                           // 8
-    pub max_dev_buy: u32,                           // 9
+    pub min_dev_buy: f64,                           // 8
+    pub max_dev_buy: f64,                           // 9
     pub telegram_bot_token: String,                 // 10
     pub telegram_chat_id: String,                   // 11
     pub bundle_check: bool,                         // 12
@@ -593,186 +689,232 @@ pub struct Config {
     pub stop_loss_percent: f64,                     // 14
     pub min_last_time: u64,                         // 15
 
-    // ============ NEW SETTINGS (81) - GROUPED BY CATEGORY ============
+    // ============ NEW SETTINGS (84) - GROUPED BY CATEGORY ============
     pub basic_trading: BasicTradingConfig,          // 12 settings
     pub jito: JitoConfig,                          // 4 settings
     pub zero_slot: ZeroSlotConfig,                 // 2 settings
     pub nozomi: NozomiConfig,                      // 2 settings
     pub blox_route: BloxRouteConfig,               // 4 settings
-    pub advanced_filters: AdvancedFilterSettings,  // 14 settings
+    pub advanced_filters: AdvancedFilterSettings,  // 17 settings
     pub copy_trading: CopyTradingConfig,           // 6 settings
     pub private_logic: PrivateLogicConfig,         // 15 settings
     pub inverse_buy: InverseBuyConfig,             // 2 settings
-    pub timer: TimerConfig,                        // 4 settings
+    pub timer: TimerConfig,                        // 6 settings
     pub mode: ModeConfig,                          // 3 settings
     pub advanced: AdvancedConfig,                  // 8 settings
     // Additional: 5 settings in SwapConfig (slippage, amount_in, swap_direction, in_type, use_jito)
 }
 
 impl Config {
-    /// Create new configuration from environment variables
-    pub async fn new() -> &'static Mutex<Config> {
+    /// Load the configuration once and return a snapshot of it
+    ///
+    /// Snapshots are plain `Arc`s, so readers never lock; `reload` swaps a new
+    /// configuration in atomically while earlier snapshots stay valid.
+    pub async fn new() -> Arc<Config> {
         GLOBAL_CONFIG
-            .get_or_init(|| async {
-                let init_msg = INIT_MSG;
-                println!("{}", init_msg);
-
-                dotenv().ok(); // Load .env file
-
-                let logger = Logger::new("[INIT] => ".blue().bold().to_string());
-
-                // Load existing settings (preserved exactly as they were)
-                let yellowstone_grpc_http = import_env_var("YELLOWSTONE_GRPC_HTTP");
-                let yellowstone_grpc_token = import_env_var("YELLOWSTONE_GRPC_TOKEN");
-
-                let yellowstone_ping_interval = env::var("YELLOWSTONE_PING_INTERVAL")
-                    .unwrap_or_default()
-                    .parse::<u64>()
-                    .unwrap_or(30);
-                let yellowstone_reconnect_delay = env::var("YELLOWSTONE_RECONNECT_DELAY")
-                    .unwrap_or_default()
-                    .parse::<u64>()
-                    .unwrap_or(5);
-                let yellowstone_max_retries = env::var("YELLOWSTONE_MAX_RETRIES")
-                    .unwrap_or_default()
-                    .parse::<u32>()
-                    .unwrap_or(10);
-
-                let slippage_input = env::var("SLIPPAGE")
-                    .unwrap_or_default()
-                    .parse::<u64>()
-                    .unwrap_or(100);
-                let counter_limit = env::var("COUNTER")
-                    .unwrap_or_default()
-                    .parse::<u32>()
-                    .unwrap_or(10);
-                let max_dev_buy = env::var("MAX_DEV_BUY")
-                    .unwrap_or_default()
-                    .parse::<u32>()
-                    .unwrap_or(30);
-                let min_dev_buy = env::var("MIN_DEV_BUY")
-                    .unwrap_or_default()
-                    .parse::<u32>()
-                    .unwrap_or(5);
-                let bundle_check = env::var("BUNDLE_CHECK")
-                    .unwrap_or_default()
-                    .parse::<bool>()
-                    .unwrap_or(true);
-
-                let time_exceed = env::var("TIME_EXCEED")
-                    .unwrap_or_default()
-                    .parse::<u64>()
-                    .unwrap_or(30);
-                let amount_in = env::var("TOKEN_AMOUNT")
-                    .unwrap_or_default()
-                    .parse::<f64>()
-                    .unwrap_or(1.0);
-                let use_jito = env::var("USE_JITO")
-                    .unwrap_or_default()
-                    .parse::<bool>()
-                    .unwrap_or(false);
-
-                let take_profit_percent = env::var("TAKE_PROFIT_PERCENT")
-                    .unwrap_or_else(|_| "50.0".to_string())
-                    .parse::<f64>()
-                    .unwrap_or(50.0);
-
-                let stop_loss_percent = env::var("STOP_LOSS_PERCENT")
-                    .unwrap_or_else(|_| "30.0".to_string())
-                    .parse::<f64>()
-                    .unwrap_or(30.0);
-
-                let min_last_time = env::var("MIN_LAST_TIME")
-                    .unwrap_or_else(|_| "300000".to_string())
-                    .parse::<u64>()
-                    .unwrap_or(300000);
-
-                // Load new settings
-                let basic_trading = Self::load_basic_trading_settings();
-                let jito = Self::load_jito_settings();
-                let zero_slot = Self::load_zero_slot_settings();
-                let nozomi = Self::load_nozomi_settings();
-                let blox_route = Self::load_blox_route_settings();
-                let advanced_filters = Self::load_advanced_filter_settings();
-                let copy_trading = Self::load_copy_trading_settings();
-                let private_logic = Self::load_private_logic_settings();
-                let inverse_buy = Self::load_inverse_buy_settings();
-                let timer = Self::load_timer_settings();
-                let mode = Self::load_mode_settings();
-                let advanced = Self::load_advanced_settings();
-
-                // Validate all settings
-                if let Err(errors) = Self::validate_all_settings(
-                    &basic_trading, &jito, &advanced_filters, &copy_trading,
-                    &private_logic, &timer, &advanced
-                ) {
-                    logger.log("⚠️  Configuration validation errors found:".to_string());
-                    for error in errors {
-                        logger.log(format!("   - {}", error));
-                    }
-                }
-
-                let swap_config = SwapConfig {
-                    swap_direction: SwapDirection::Buy,
-                    in_type: SwapInType::Qty,
-                    amount_in,
-                    slippage: slippage_input,
-                    use_jito,
-                };
-
-                let telegram_bot_token = env::var("TELEGRAM_BOT_TOKEN").unwrap_or_else(|_| "".to_string());
-                let telegram_chat_id = env::var("TELEGRAM_CHAT_ID").unwrap_or_else(|_| "".to_string());
-
-                let app_state = AppState {
-                    rpc_client: create_rpc_client().unwrap(),
-                    rpc_nonblocking_client: Arc::new(
-                        anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new_with_commitment(
-                            env::var("RPC_HTTP").unwrap_or_default(),
-                            CommitmentConfig::processed(),
-                        ),
-                    ),
-                    wallet: Arc::new(import_wallet().unwrap_or_else(|_| Keypair::new())),
-                };
-
-                let config = Config {
-                    yellowstone_grpc_http,
-                    yellowstone_grpc_token,
-                    yellowstone_ping_interval,
-                    yellowstone_reconnect_delay,
-                    yellowstone_max_retries,
-                    app_state,
-                    swap_config,
-                    time_exceed,
-                    blacklist: Blacklist::new(),
-                    counter_limit,
-                    min_dev_buy,
-                    max_dev_buy,
-                    telegram_bot_token,
-                    telegram_chat_id,
-                    bundle_check,
-                    take_profit_percent,
-                    stop_loss_percent,
-                    min_last_time,
-                    basic_trading,
-                    jito,
-                    zero_slot,
-                    nozomi,
-                    blox_route,
-                    advanced_filters,
-                    copy_trading,
-                    private_logic,
-                    inverse_buy,
-                    timer,
-                    mode,
-                    advanced,
-                };
-
-                logger.log("✅ All settings loaded successfully - 96 settings total".to_string());
-                config.print_configuration_summary();
-
-                Mutex::new(config)
-            })
+            .get_or_init(|| async { ArcSwap::from_pointee(Self::load(None).await) })
             .await
+            .load_full()
+    }
+
+    /// Latest configuration, once `new` has loaded it
+    pub fn current() -> Option<Arc<Config>> {
+        GLOBAL_CONFIG.get().map(ArcSwap::load_full)
+    }
+
+    /// Re-read the environment and swap the new configuration in
+    ///
+    /// RPC clients and wallets are kept, so no passphrase or signer connection is asked for again.
+    pub async fn reload() -> Arc<Config> {
+        let Some(current) = GLOBAL_CONFIG.get() else {
+            return Self::new().await;
+        };
+        let config = Arc::new(Self::load(Some(current.load().app_state.clone())).await);
+        current.store(config.clone());
+        config
+    }
+
+    /// Build the configuration from environment variables, reusing `app_state` when given
+    async fn load(app_state: Option<AppState>) -> Config {
+        let init_msg = INIT_MSG;
+        println!("{}", init_msg);
+
+        dotenv().ok(); // Load .env file
+
+        let logger = Logger::new("[INIT] => ".blue().bold().to_string());
+
+        // Preset values go in after .env, so anything set explicitly still wins
+        match crate::common::profile::apply_profile() {
+            Ok(Some((profile, applied))) => logger.log(format!(
+                "Using the {} profile ({} of its {} settings applied, the rest are set explicitly)",
+                profile.name(), applied, profile.preset().len()
+            )),
+            Ok(None) => {}
+            Err(e) => logger.error(e),
+        }
+        match crate::common::network::apply_network() {
+            Ok(network) if !network.is_mainnet() => logger.log(format!(
+                "Running on {}: RPC defaults to {}, relays are disabled",
+                network.name(), env::var("RPC_HTTP").unwrap_or_default()
+            ).yellow().to_string()),
+            Ok(_) => {}
+            Err(e) => logger.error(e),
+        }
+
+        // Load existing settings (preserved exactly as they were)
+        let yellowstone_grpc_http = import_env_var("YELLOWSTONE_GRPC_HTTP");
+        let yellowstone_grpc_token = import_env_var("YELLOWSTONE_GRPC_TOKEN");
+
+        let yellowstone_ping_interval = env::var("YELLOWSTONE_PING_INTERVAL")
+            .unwrap_or_default()
+            .parse::<u64>()
+            .unwrap_or(30);
+        let yellowstone_reconnect_delay = env::var("YELLOWSTONE_RECONNECT_DELAY")
+            .unwrap_or_default()
+            .parse::<u64>()
+            .unwrap_or(5);
+        let yellowstone_max_retries = env::var("YELLOWSTONE_MAX_RETRIES")
+            .unwrap_or_default()
+            .parse::<u32>()
+            .unwrap_or(10);
+
+        let slippage_input = env::var("SLIPPAGE")
+            .unwrap_or_default()
+            .parse::<u64>()
+            .unwrap_or(100);
+        let counter_limit = env::var("COUNTER")
+            .unwrap_or_default()
+            .parse::<u32>()
+            .unwrap_or(10);
+        let max_dev_buy = env::var("MAX_DEV_BUY")
+            .unwrap_or_default()
+            .parse::<f64>()
+            .unwrap_or(30.0);
+        let min_dev_buy = env::var("MIN_DEV_BUY")
+            .unwrap_or_default()
+            .parse::<f64>()
+            .unwrap_or(5.0);
+        let bundle_check = env::var("BUNDLE_CHECK")
+            .unwrap_or_default()
+            .parse::<bool>()
+            .unwrap_or(true);
+
+        let time_exceed = env::var("TIME_EXCEED")
+            .unwrap_or_default()
+            .parse::<u64>()
+            .unwrap_or(30);
+        let amount_in = env::var("TOKEN_AMOUNT")
+            .unwrap_or_default()
+            .parse::<f64>()
+            .unwrap_or(1.0);
+        let use_jito = env::var("USE_JITO")
+            .unwrap_or_default()
+            .parse::<bool>()
+            .unwrap_or(false);
+
+        let take_profit_percent = env::var("TAKE_PROFIT_PERCENT")
+            .unwrap_or_else(|_| "50.0".to_string())
+            .parse::<f64>()
+            .unwrap_or(50.0);
+
+        let stop_loss_percent = env::var("STOP_LOSS_PERCENT")
+            .unwrap_or_else(|_| "30.0".to_string())
+            .parse::<f64>()
+            .unwrap_or(30.0);
+
+        let min_last_time = env::var("MIN_LAST_TIME")
+            .unwrap_or_else(|_| "300000".to_string())
+            .parse::<u64>()
+            .unwrap_or(300000);
+
+        // Load new settings
+        let basic_trading = Self::load_basic_trading_settings();
+        let jito = Self::load_jito_settings();
+        let zero_slot = Self::load_zero_slot_settings();
+        let nozomi = Self::load_nozomi_settings();
+        let blox_route = Self::load_blox_route_settings();
+        let advanced_filters = Self::load_advanced_filter_settings();
+        let copy_trading = Self::load_copy_trading_settings();
+        let private_logic = Self::load_private_logic_settings();
+        let inverse_buy = Self::load_inverse_buy_settings();
+        let timer = Self::load_timer_settings();
+        let mode = Self::load_mode_settings();
+        let advanced = Self::load_advanced_settings();
+
+        // Validate all settings
+        if let Err(errors) = Self::validate_all_settings(
+            &basic_trading, &jito, &advanced_filters, &copy_trading,
+            &private_logic, &timer, &advanced
+        ) {
+            logger.log("⚠️  Configuration validation errors found:".to_string());
+            for error in errors {
+                logger.log(format!("   - {}", error));
+            }
+        }
+
+        let swap_config = SwapConfig {
+            swap_direction: SwapDirection::Buy,
+            in_type: SwapInType::Qty,
+            amount_in,
+            slippage: slippage_input,
+            use_jito,
+        };
+
+        let telegram_bot_token = env::var("TELEGRAM_BOT_TOKEN").unwrap_or_else(|_| "".to_string());
+        let telegram_chat_id = env::var("TELEGRAM_CHAT_ID").unwrap_or_else(|_| "".to_string());
+
+        let app_state = app_state.unwrap_or_else(|| AppState {
+            rpc_client: create_rpc_client().unwrap(),
+            rpc_nonblocking_client: Arc::new(
+                anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new_with_commitment(
+                    env::var("RPC_HTTP").unwrap_or_default(),
+                    CommitmentConfig::processed(),
+                ),
+            ),
+            wallet: import_signer().unwrap_or_else(|e| {
+                eprintln!("Failed to load wallet: {}", e);
+                std::process::exit(1);
+            }),
+            wallets: import_extra_wallets(),
+        });
+
+        let config = Config {
+            yellowstone_grpc_http,
+            yellowstone_grpc_token,
+            yellowstone_ping_interval,
+            yellowstone_reconnect_delay,
+            yellowstone_max_retries,
+            app_state,
+            swap_config,
+            time_exceed,
+            blacklist: Blacklist::new(),
+            counter_limit,
+            min_dev_buy,
+            max_dev_buy,
+            telegram_bot_token,
+            telegram_chat_id,
+            bundle_check,
+            take_profit_percent,
+            stop_loss_percent,
+            min_last_time,
+            basic_trading,
+            jito,
+            zero_slot,
+            nozomi,
+            blox_route,
+            advanced_filters,
+            copy_trading,
+            private_logic,
+            inverse_buy,
+            timer,
+            mode,
+            advanced,
+        };
+
+        logger.log("✅ All settings loaded successfully - 101 settings total".to_string());
+        config.print_configuration_summary();
+
+        config
     }
 
     /// Load basic trading settings from environment
@@ -848,6 +990,14 @@ impl Config {
             max_launcher_sol_balance: parse_f64_env("MAX_LAUNCHER_SOL_BALANCE", AdvancedFilterSettings::default().max_launcher_sol_balance),
             launcher_sol_enabled: parse_bool_env("LAUNCHER_SOL_ENABLED", AdvancedFilterSettings::default().launcher_sol_enabled),
             dev_buy_enabled: parse_bool_env("DEV_BUY_ENABLED", AdvancedFilterSettings::default().dev_buy_enabled),
+            min_curve_progress: parse_f64_env("MIN_CURVE_PROGRESS", AdvancedFilterSettings::default().min_curve_progress),
+            max_curve_progress: parse_f64_env("MAX_CURVE_PROGRESS", AdvancedFilterSettings::default().max_curve_progress),
+            curve_progress_enabled: parse_bool_env("CURVE_PROGRESS_ENABLED", AdvancedFilterSettings::default().curve_progress_enabled),
+            min_liquidity_usd: parse_f64_env("MIN_LIQUIDITY_USD", AdvancedFilterSettings::default().min_liquidity_usd),
+            min_volume_24h_usd: parse_f64_env("MIN_VOLUME_24H_USD", AdvancedFilterSettings::default().min_volume_24h_usd),
+            min_price_change_1h: parse_f64_env("MIN_PRICE_CHANGE_1H", AdvancedFilterSettings::default().min_price_change_1h),
+            max_price_change_1h: parse_f64_env("MAX_PRICE_CHANGE_1H", AdvancedFilterSettings::default().max_price_change_1h),
+            market_data_enabled: parse_bool_env("MARKET_DATA_FILTER_ENABLED", AdvancedFilterSettings::default().market_data_enabled),
         }
     }
 
@@ -871,24 +1021,44 @@ impl Config {
     }
 
     /// Load private logic settings from environment
+    ///
+    /// `PL_STAGES` holds the whole list. Without it, the older `PL_STAGE_<n>_PERCENT` and
+    /// `PL_STAGE_<n>_DELAY` keys override the default stages, and extend them past the seventh.
     fn load_private_logic_settings() -> PrivateLogicConfig {
+        let defaults = PrivateLogicConfig::default();
+        let stages = match env::var("PL_STAGES") {
+            Ok(value) if !value.trim().is_empty() => PrivateLogicStage::parse_list(&value).unwrap_or_else(|e| {
+                eprintln!("Ignoring PL_STAGES: {}", e);
+                defaults.stages.clone()
+            }),
+            _ => Self::load_legacy_private_logic_stages(defaults.stages.clone()),
+        };
         PrivateLogicConfig {
-            enabled: parse_bool_env("PRIVATE_LOGIC_ENABLED", PrivateLogicConfig::default().enabled),
-            stage_1_percent: parse_f64_env_with_validation("PL_STAGE_1_PERCENT", PrivateLogicConfig::default().stage_1_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_1_percent),
-            stage_1_delay: parse_u64_env("PL_STAGE_1_DELAY", PrivateLogicConfig::default().stage_1_delay),
-            stage_2_percent: parse_f64_env_with_validation("PL_STAGE_2_PERCENT", PrivateLogicConfig::default().stage_2_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_2_percent),
-            stage_2_delay: parse_u64_env("PL_STAGE_2_DELAY", PrivateLogicConfig::default().stage_2_delay),
-            stage_3_percent: parse_f64_env_with_validation("PL_STAGE_3_PERCENT", PrivateLogicConfig::default().stage_3_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_3_percent),
-            stage_3_delay: parse_u64_env("PL_STAGE_3_DELAY", PrivateLogicConfig::default().stage_3_delay),
-            stage_4_percent: parse_f64_env_with_validation("PL_STAGE_4_PERCENT", PrivateLogicConfig::default().stage_4_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_4_percent),
-            stage_4_delay: parse_u64_env("PL_STAGE_4_DELAY", PrivateLogicConfig::default().stage_4_delay),
-            stage_5_percent: parse_f64_env_with_validation("PL_STAGE_5_PERCENT", PrivateLogicConfig::default().stage_5_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_5_percent),
-            stage_5_delay: parse_u64_env("PL_STAGE_5_DELAY", PrivateLogicConfig::default().stage_5_delay),
-            stage_6_percent: parse_f64_env_with_validation("PL_STAGE_6_PERCENT", PrivateLogicConfig::default().stage_6_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_6_percent),
-            stage_6_delay: parse_u64_env("PL_STAGE_6_DELAY", PrivateLogicConfig::default().stage_6_delay),
-            stage_7_percent: parse_f64_env_with_validation("PL_STAGE_7_PERCENT", PrivateLogicConfig::default().stage_7_percent, 0.0, 100.0).unwrap_or(PrivateLogicConfig::default().stage_7_percent),
-            stage_7_delay: parse_u64_env("PL_STAGE_7_DELAY", PrivateLogicConfig::default().stage_7_delay),
+            enabled: parse_bool_env("PRIVATE_LOGIC_ENABLED", defaults.enabled),
+            stages,
+        }
+    }
+
+    fn load_legacy_private_logic_stages(mut stages: Vec<PrivateLogicStage>) -> Vec<PrivateLogicStage> {
+        for n in 1.. {
+            let percent = env::var(format!("PL_STAGE_{}_PERCENT", n)).ok().and_then(|v| v.parse::<f64>().ok());
+            let delay_ms = env::var(format!("PL_STAGE_{}_DELAY", n)).ok().and_then(|v| v.parse::<u64>().ok());
+            match stages.get_mut(n - 1) {
+                Some(stage) => {
+                    stage.percent = percent.unwrap_or(stage.percent);
+                    stage.delay_ms = delay_ms.unwrap_or(stage.delay_ms);
+                }
+                None => match percent {
+                    Some(percent) => stages.push(PrivateLogicStage {
+                        percent,
+                        delay_ms: delay_ms.unwrap_or(n as u64 * 1000),
+                        action: StageAction::Sell,
+                    }),
+                    None => break,
+                },
+            }
         }
+        stages
     }
 
     /// Load inverse buy settings from environment
@@ -906,6 +1076,8 @@ impl Config {
             start_time: parse_time_format_env("BOT_START_TIME", &TimerConfig::default().start_time).unwrap_or(TimerConfig::default().start_time),
             stop_time: parse_time_format_env("BOT_STOP_TIME", &TimerConfig::default().stop_time).unwrap_or(TimerConfig::default().stop_time),
             auto_sell_on_stop: parse_bool_env("AUTO_SELL_ON_STOP", TimerConfig::default().auto_sell_on_stop),
+            timezone: env::var("TIMER_TIMEZONE").unwrap_or_else(|_| TimerConfig::default().timezone),
+            schedule: env::var("TIMER_SCHEDULE").unwrap_or_default(),
         }
     }
 
@@ -932,6 +1104,14 @@ impl Config {
         }
     }
 
+    /// Validate the loaded settings, returning every problem found
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        Self::validate_all_settings(
+            &self.basic_trading, &self.jito, &self.advanced_filters, &self.copy_trading,
+            &self.private_logic, &self.timer, &self.advanced
+        )
+    }
+
     /// Comprehensive validation for all settings
     fn validate_all_settings(
         basic_trading: &BasicTradingConfig,
@@ -963,6 +1143,27 @@ impl Config {
             errors.push(ConfigError::ValidationError("VOLUME".to_string(), "min cannot be greater than max".to_string()));
         }
 
+        for (name, value) in [
+            ("MIN_CURVE_PROGRESS", advanced_filters.min_curve_progress),
+            ("MAX_CURVE_PROGRESS", advanced_filters.max_curve_progress),
+        ] {
+            if !(0.0..=100.0).contains(&value) {
+                errors.push(ConfigError::InvalidPercentage(name.to_string(), value));
+            }
+        }
+
+        if advanced_filters.min_curve_progress > advanced_filters.max_curve_progress {
+            errors.push(ConfigError::ValidationError("CURVE_PROGRESS".to_string(), "min cannot be greater than max".to_string()));
+        }
+
+        if advanced_filters.min_price_change_1h > advanced_filters.max_price_change_1h {
+            errors.push(ConfigError::ValidationError("PRICE_CHANGE_1H".to_string(), "min cannot be greater than max".to_string()));
+        }
+
+        if let Err(e) = private_logic.validate_stages() {
+            errors.push(ConfigError::ValidationError("PL_STAGES".to_string(), e));
+        }
+
         // Validate copy trading wallets
         for wallet in &copy_trading.target_wallets {
             if !is_valid_wallet_address(wallet) {
@@ -978,6 +1179,9 @@ impl Config {
             if !Self::is_valid_time_format(&timer.stop_time) {
                 errors.push(ConfigError::InvalidTimeFormat(timer.stop_time.clone()));
             }
+            if let Err(e) = TimerSchedule::parse(&timer.schedule, &timer.start_time, &timer.stop_time, &timer.timezone) {
+                errors.push(ConfigError::ValidationError("TIMER_SCHEDULE".to_string(), e));
+            }
         }
 
         // Validate confidence levels
@@ -1024,12 +1228,18 @@ impl Config {
         println!("├─ ZeroSlot (2 settings): {}", if !self.zero_slot.url.is_empty() { "Configured" } else { "Not configured" });
         println!("├─ Nozomi (2 settings): {}", if !self.nozomi.url.is_empty() { "Configured" } else { "Not configured" });
         println!("├─ BloxRoute (4 settings): {}", if !self.blox_route.auth_header.is_empty() { "Configured" } else { "Not configured" });
-        println!("├─ Advanced Filters (14 settings): MC {:.1}K-{:.1}K",
-                 self.advanced_filters.min_market_cap, self.advanced_filters.max_market_cap);
+        println!("├─ Advanced Filters (17 settings): MC {}-{}, curve {}",
+                 MarketCapUnit::from_env().format(self.advanced_filters.min_market_cap),
+                 MarketCapUnit::from_env().format(self.advanced_filters.max_market_cap),
+                 if self.advanced_filters.curve_progress_enabled {
+                     format!("{:.0}%-{:.0}%", self.advanced_filters.min_curve_progress, self.advanced_filters.max_curve_progress)
+                 } else {
+                     "any".to_string()
+                 });
         println!("├─ Copy Trading (6 settings): {} targets", self.copy_trading.target_wallets.len());
         println!("├─ Private Logic (15 settings): {}", if self.private_logic.enabled { "Enabled" } else { "Disabled" });
         println!("├─ Inverse Buy (2 settings): {}", if self.inverse_buy.enabled { "Enabled" } else { "Disabled" });
-        println!("├─ Timer (4 settings): {}", if self.timer.enabled { format!("{} - {}", self.timer.start_time, self.timer.stop_time) } else { "Disabled".to_string() });
+        println!("├─ Timer (6 settings): {}", if self.timer.enabled && !self.timer.schedule.is_empty() { format!("{} ({})", self.timer.schedule, self.timer.timezone) } else if self.timer.enabled { format!("{} - {} ({})", self.timer.start_time, self.timer.stop_time, self.timer.timezone) } else { "Disabled".to_string() });
         println!("├─ Mode (3 settings): {}", if self.mode.live_mode { "Live" } else if self.mode.simulation_mode { "Simulation" } else { "Paper" });
         println!("├─ Advanced (8 settings): Buy confidence {:.1}%", self.advanced.min_buy_confidence * 100.0);
         println!("└─ Existing preserved (15 settings): Yellowstone, Telegram, etc.");
@@ -1043,11 +1253,11 @@ impl Config {
         let zero_slot_settings = 2;
         let nozomi_settings = 2;
         let blox_route_settings = 4;
-        let advanced_filter_settings = 14;
+        let advanced_filter_settings = 17;
         let copy_trading_settings = 6;
         let private_logic_settings = 15;
         let inverse_buy_settings = 2;
-        let timer_settings = 4;
+        let timer_settings = 6;
         let mode_settings = 3;
         let advanced_settings = 8;
         let additional_swap_settings = 5; // In SwapConfig
@@ -1141,8 +1351,11 @@ pub fn create_rpc_client() -> Result<Arc<anchor_client::solana_client::rpc_clien
     Ok(Arc::new(rpc_client))
 }
 
-/// Import wallet from private key with error handling
+/// Load the trading wallet from the encrypted keystore, falling back to PRIVATE_KEY
 pub fn import_wallet() -> Result<Keypair, Box<dyn std::error::Error>> {
+    if let Some(keypair) = load_keystore_wallet(&KeystoreSettings::from_env())? {
+        return Ok(keypair);
+    }
     let private_key = env::var("PRIVATE_KEY").unwrap_or_default();
     if private_key.is_empty() {
         return Ok(Keypair::new()); // Create new wallet if no key provided
@@ -1152,7 +1365,39 @@ pub fn import_wallet() -> Result<Keypair, Box<dyn std::error::Error>> {
     Ok(keypair)
 }
 
-/// Create CoinGecko price proxy
+/// Signer for the trading wallet: the external signer from SIGNER_BACKEND, or the local keypair
+pub fn import_signer() -> Result<WalletSigner, Box<dyn std::error::Error>> {
+    if let Some(signer) = connect_signer(&SignerSettings::from_env())? {
+        return Ok(signer);
+    }
+    Ok(Arc::new(import_wallet()?))
+}
+
+/// Import the extra trading wallets listed in EXTRA_PRIVATE_KEYS (comma separated, base58)
+///
+/// Keys that fail to decode are skipped with a warning.
+pub fn import_extra_wallets() -> Vec<WalletSigner> {
+    env::var("EXTRA_PRIVATE_KEYS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .enumerate()
+        .filter_map(|(i, key)| {
+            match bs58::decode(key).into_vec().ok().and_then(|bytes| Keypair::from_bytes(&bytes).ok()) {
+                Some(keypair) => Some(Arc::new(keypair) as WalletSigner),
+                None => {
+                    eprintln!("Ignoring EXTRA_PRIVATE_KEYS entry {}: invalid key", i + 1);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Fetch SOL/USD from CoinGecko
+///
+/// Each call hits the API; use `services::price_feed::sol_usd` for the cached price.
 pub async fn create_coingecko_proxy() -> Result<f64, Error> {
     let client = reqwest::Client::new();
     let response = client
@@ -1174,7 +1419,7 @@ mod tests {
     fn test_settings_count() {
         let config = create_test_config();
         let total_count = config.count_all_settings();
-        assert_eq!(total_count, 96, "Total settings count must be exactly 96");
+        assert_eq!(total_count, 101, "Total settings count must be exactly 101");
     }
 
     #[test]
@@ -1220,7 +1465,30 @@ mod tests {
 
         let private_logic = PrivateLogicConfig::default();
         assert!(!private_logic.enabled);
-        assert_eq!(private_logic.stage_1_percent, 10.0);
+        assert_eq!(private_logic.stages.len(), 7);
+        assert_eq!(private_logic.stages[0], PrivateLogicStage { percent: 10.0, delay_ms: 1000, action: StageAction::Sell });
+        assert!(private_logic.validate_stages().is_ok());
+    }
+
+    #[test]
+    fn test_private_logic_stage_lists() {
+        let stages = PrivateLogicStage::parse_list("25:500, 50:1500:notify, 20:3000:tighten_stop").unwrap();
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[1].action, StageAction::Notify);
+        assert_eq!(stages[2], PrivateLogicStage { percent: 20.0, delay_ms: 3000, action: StageAction::TightenStop });
+
+        let json = PrivateLogicStage::parse_list(r#"[{"percent": 25, "delay_ms": 500}, {"percent": 10, "delay_ms": 900, "action": "tighten_stop"}]"#).unwrap();
+        assert_eq!(json[0].action, StageAction::Sell);
+        assert_eq!(json[1].action, StageAction::TightenStop);
+
+        assert!(PrivateLogicStage::parse_list("25").is_err());
+        assert!(PrivateLogicStage::parse_list("25:500:hold").is_err());
+
+        let config = |stages: &str| PrivateLogicConfig { enabled: true, stages: PrivateLogicStage::parse_list(stages).unwrap() };
+        assert!(config("10:1000,20:1000").validate_stages().is_err());
+        assert!(config("20:1000:tighten_stop,25:2000:tighten_stop").validate_stages().is_err());
+        assert!(config("20:1000:tighten_stop,50:1500,10:2000:tighten_stop").validate_stages().is_ok());
+        assert!(config("0:1000").validate_stages().is_err());
     }
 
     #[test]
@@ -1246,6 +1514,29 @@ mod tests {
         assert!(errors.len() >= 1); // Should have at least 1 error
     }
 
+    #[test]
+    fn test_curve_progress_validation() {
+        let mut advanced_filters = AdvancedFilterSettings::default();
+        advanced_filters.min_curve_progress = 40.0;
+        advanced_filters.max_curve_progress = 5.0;
+
+        let result = Config::validate_all_settings(
+            &BasicTradingConfig::default(), &JitoConfig::default(), &advanced_filters,
+            &CopyTradingConfig::default(), &PrivateLogicConfig::default(),
+            &TimerConfig::default(), &AdvancedConfig::default()
+        );
+        assert!(result.is_err());
+
+        advanced_filters.min_curve_progress = 5.0;
+        advanced_filters.max_curve_progress = 140.0;
+        let result = Config::validate_all_settings(
+            &BasicTradingConfig::default(), &JitoConfig::default(), &advanced_filters,
+            &CopyTradingConfig::default(), &PrivateLogicConfig::default(),
+            &TimerConfig::default(), &AdvancedConfig::default()
+        );
+        assert!(result.is_err());
+    }
+
     fn create_test_config() -> Config {
         Config {
             // Existing settings - 15
@@ -1256,8 +1547,8 @@ mod tests {
             yellowstone_max_retries: 10,
             time_exceed: 30,
             counter_limit: 10,
-            min_dev_buy: 5,
-            max_dev_buy: 30,
+            min_dev_buy: 5.0,
+            max_dev_buy: 30.0,
             telegram_bot_token: String::new(),
             telegram_chat_id: String::new(),
             bundle_check: true,
@@ -1288,6 +1579,7 @@ mod tests {
                     anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new("https://api.mainnet-beta.solana.com".to_string())
                 ),
                 wallet: Arc::new(Keypair::new()),
+                wallets: Vec::new(),
             },
             swap_config: SwapConfig {
                 swap_direction: SwapDirection::Buy,
@@ -1321,7 +1613,8 @@ mod tests {
         assert!(copy_trading.enabled);
         assert_eq!(copy_trading.target_wallets.len(), 3);
         assert!(private_logic.enabled);
-        assert_eq!(private_logic.stage_1_percent, 15.0);
+        assert_eq!(private_logic.stages[0].percent, 15.0);
+        assert_eq!(private_logic.stages.len(), 7);
 
         // Clean up environment variables
         env::remove_var("THRESHOLD_SELL");
@@ -1335,12 +1628,12 @@ mod tests {
 
     #[test]
     fn test_comprehensive_config_test() {
-        // This test ensures all 96 settings are properly implemented
+        // This test ensures all 101 settings are properly implemented
         let config = create_test_config();
 
         // Validate that config loads successfully
         let total_settings = config.count_all_settings();
-        assert_eq!(total_settings, 96, "Total settings must be exactly 96");
+        assert_eq!(total_settings, 101, "Total settings must be exactly 101");
 
         // Test validation system
         let basic_trading = BasicTradingConfig::default();
@@ -1358,7 +1651,7 @@ mod tests {
 
         assert!(validation_result.is_ok(), "Default config validation should pass");
 
-        println!("✅ All 96 settings are properly implemented and validated");
+        println!("✅ All 101 settings are properly implemented and validated");
     }
 
     #[test]
@@ -1383,11 +1676,11 @@ mod tests {
         let zero_slot_settings = 2;       // ZeroSlotConfig fields
         let nozomi_settings = 2;          // NozomiConfig fields
         let blox_route_settings = 4;      // BloxRouteConfig fields
-        let advanced_filter_settings = 14; // AdvancedFilterSettings fields
+        let advanced_filter_settings = 17; // AdvancedFilterSettings fields
         let copy_trading_settings = 6;    // CopyTradingConfig fields
         let private_logic_settings = 15;  // PrivateLogicConfig fields
         let inverse_buy_settings = 2;     // InverseBuyConfig fields
-        let timer_settings = 4;           // TimerConfig fields
+        let timer_settings = 6;           // TimerConfig fields
         let mode_settings = 3;            // ModeConfig fields
         let advanced_settings = 8;        // AdvancedConfig fields
         let additional_swap_settings = 5; // SwapConfig fields
@@ -1398,7 +1691,7 @@ mod tests {
             private_logic_settings + inverse_buy_settings + timer_settings +
             mode_settings + advanced_settings + additional_swap_settings;
 
-        assert_eq!(total_expected, 96, "Manual count should equal 96");
-        assert_eq!(config.count_all_settings(), 96, "Config count should equal 96");
+        assert_eq!(total_expected, 101, "Manual count should equal 101");
+        assert_eq!(config.count_all_settings(), 101, "Config count should equal 101");
     }
 }
\ No newline at end of file
@@ -0,0 +1,200 @@
+use serde_json::{json, Map, Value};
+
+use crate::common::config::{
+    AdvancedConfig, AdvancedFilterSettings, BasicTradingConfig, BloxRouteConfig, Config, CopyTradingConfig,
+    InverseBuyConfig, JitoConfig, ModeConfig, NozomiConfig, PrivateLogicConfig, TimerConfig, ZeroSlotConfig,
+};
+
+/// Fields holding keys or tokens, never printed
+const SECRET_FIELDS: &[&str] = &["private_key", "yellowstone_grpc_token", "telegram_bot_token", "auth_header"];
+const REDACTED: &str = "<redacted>";
+
+/// One setting whose resolved value differs from its default
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// `section.field`
+    pub key: String,
+    pub value: Value,
+    pub default: Value,
+}
+
+fn section<T: serde::Serialize>(settings: &T) -> Value {
+    serde_json::to_value(settings).unwrap_or(Value::Null)
+}
+
+/// Hide secret fields and the query strings of URLs, which often carry API keys
+fn redact(value: &mut Value) {
+    let Value::Object(sections) = value else {
+        return;
+    };
+    for fields in sections.values_mut() {
+        let Value::Object(fields) = fields else {
+            continue;
+        };
+        for (name, field) in fields.iter_mut() {
+            let Value::String(text) = field else {
+                continue;
+            };
+            if SECRET_FIELDS.contains(&name.as_str()) {
+                if !text.is_empty() {
+                    *text = REDACTED.to_string();
+                }
+            } else if text.contains("://") {
+                if let Some((url, _)) = text.split_once('?') {
+                    *text = format!("{}?{}", url, REDACTED);
+                }
+            }
+        }
+    }
+}
+
+/// The resolved configuration grouped by section, secrets redacted
+pub fn export(config: &Config) -> Value {
+    let mut value = json!({
+        "core": {
+            "yellowstone_grpc_http": config.yellowstone_grpc_http,
+            "yellowstone_grpc_token": config.yellowstone_grpc_token,
+            "yellowstone_ping_interval": config.yellowstone_ping_interval,
+            "yellowstone_reconnect_delay": config.yellowstone_reconnect_delay,
+            "yellowstone_max_retries": config.yellowstone_max_retries,
+            "time_exceed": config.time_exceed,
+            "counter_limit": config.counter_limit,
+            "min_dev_buy": config.min_dev_buy,
+            "max_dev_buy": config.max_dev_buy,
+            "telegram_bot_token": config.telegram_bot_token,
+            "telegram_chat_id": config.telegram_chat_id,
+            "bundle_check": config.bundle_check,
+            "take_profit_percent": config.take_profit_percent,
+            "stop_loss_percent": config.stop_loss_percent,
+            "min_last_time": config.min_last_time,
+        },
+        "swap": {
+            "amount_in": config.swap_config.amount_in,
+            "slippage": config.swap_config.slippage,
+            "use_jito": config.swap_config.use_jito,
+        },
+        "basic_trading": section(&config.basic_trading),
+        "jito": section(&config.jito),
+        "zero_slot": section(&config.zero_slot),
+        "nozomi": section(&config.nozomi),
+        "blox_route": section(&config.blox_route),
+        "advanced_filters": section(&config.advanced_filters),
+        "copy_trading": section(&config.copy_trading),
+        "private_logic": section(&config.private_logic),
+        "inverse_buy": section(&config.inverse_buy),
+        "timer": section(&config.timer),
+        "mode": section(&config.mode),
+        "advanced": section(&config.advanced),
+    });
+    redact(&mut value);
+    value
+}
+
+/// What `export` returns for an empty environment
+pub fn defaults() -> Value {
+    let mut value = json!({
+        "core": {
+            "yellowstone_grpc_http": "",
+            "yellowstone_grpc_token": "",
+            "yellowstone_ping_interval": 30,
+            "yellowstone_reconnect_delay": 5,
+            "yellowstone_max_retries": 10,
+            "time_exceed": 30,
+            "counter_limit": 10,
+            "min_dev_buy": 5.0,
+            "max_dev_buy": 30.0,
+            "telegram_bot_token": "",
+            "telegram_chat_id": "",
+            "bundle_check": true,
+            "take_profit_percent": 50.0,
+            "stop_loss_percent": 30.0,
+            "min_last_time": 300000,
+        },
+        "swap": {
+            "amount_in": 1.0,
+            "slippage": 100,
+            "use_jito": false,
+        },
+        "basic_trading": section(&BasicTradingConfig::default()),
+        "jito": section(&JitoConfig::default()),
+        "zero_slot": section(&ZeroSlotConfig::default()),
+        "nozomi": section(&NozomiConfig::default()),
+        "blox_route": section(&BloxRouteConfig::default()),
+        "advanced_filters": section(&AdvancedFilterSettings::default()),
+        "copy_trading": section(&CopyTradingConfig::default()),
+        "private_logic": section(&PrivateLogicConfig::default()),
+        "inverse_buy": section(&InverseBuyConfig::default()),
+        "timer": section(&TimerConfig::default()),
+        "mode": section(&ModeConfig::default()),
+        "advanced": section(&AdvancedConfig::default()),
+    });
+    redact(&mut value);
+    value
+}
+
+fn fields(value: &Value) -> impl Iterator<Item = (String, &Value)> {
+    value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(section, fields)| {
+            fields
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(move |(name, field)| (format!("{}.{}", section, name), field))
+        })
+}
+
+/// Settings of `resolved` that differ from `defaults`
+pub fn diff(resolved: &Value, defaults: &Value) -> Vec<ConfigChange> {
+    let defaults: Map<String, Value> = fields(defaults).map(|(key, value)| (key, value.clone())).collect();
+    fields(resolved)
+        .filter_map(|(key, value)| {
+            let default = defaults.get(&key).cloned().unwrap_or(Value::Null);
+            (*value != default).then(|| ConfigChange { key, value: value.clone(), default })
+        })
+        .collect()
+}
+
+/// Render the sectioned export as TOML, one table per section
+///
+/// JSON strings, numbers, booleans and arrays are valid TOML values, so they are written as is.
+pub fn to_toml(value: &Value) -> String {
+    let mut toml = String::new();
+    for (section, fields) in value.as_object().into_iter().flatten() {
+        toml.push_str(&format!("[{}]\n", section));
+        for (name, field) in fields.as_object().into_iter().flatten() {
+            match field {
+                Value::Null => toml.push_str(&format!("# {} is not set\n", name)),
+                field => toml.push_str(&format!("{} = {}\n", name, field)),
+            }
+        }
+        toml.push('\n');
+    }
+    toml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_and_redaction() {
+        let mut resolved = defaults();
+        resolved["basic_trading"]["private_key"] = json!("5Kb8kLf9zgWQnogidDA76Mz");
+        resolved["basic_trading"]["rpc_http"] = json!("https://rpc.example.com/?api-key=secret");
+        resolved["swap"]["slippage"] = json!(300);
+        redact(&mut resolved);
+        assert_eq!(resolved["basic_trading"]["private_key"], json!(REDACTED));
+        assert_eq!(resolved["basic_trading"]["rpc_http"], json!("https://rpc.example.com/?<redacted>"));
+
+        let changes = diff(&resolved, &defaults());
+        assert_eq!(changes.len(), 3);
+        assert!(changes.contains(&ConfigChange { key: "swap.slippage".to_string(), value: json!(300), default: json!(100) }));
+
+        let toml = to_toml(&resolved);
+        assert!(toml.contains("[swap]\namount_in = 1.0\nslippage = 300\n"));
+        assert!(toml.contains("target_wallets = []"));
+    }
+}
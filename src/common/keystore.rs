@@ -0,0 +1,188 @@
+use std::io::IsTerminal;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anyhow::{anyhow, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Current keystore file format
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Keystore settings
+#[derive(Debug, Clone)]
+pub struct KeystoreSettings {
+    /// Encrypted keystore file; used instead of PRIVATE_KEY when it exists
+    pub path: String,
+    /// Passphrase for non-interactive starts; prompted for when empty
+    pub passphrase: Option<String>,
+}
+
+impl KeystoreSettings {
+    /// Load keystore settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            path: std::env::var("KEYSTORE_PATH").unwrap_or_else(|_| "keystore.json".to_string()),
+            passphrase: std::env::var("KEY_PASSPHRASE").ok().filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+/// Argon2id cost parameters, stored in the file so they can be raised later
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+        }
+    }
+}
+
+/// A keypair encrypted with a passphrase (argon2id + chacha20poly1305)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Public key of the stored wallet, readable without the passphrase
+    pub pubkey: String,
+    pub kdf: KdfParams,
+    /// Base64 encoded
+    pub salt: String,
+    /// Base64 encoded
+    pub nonce: String,
+    /// Base64 encoded keypair bytes with the authentication tag
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt `keypair` with `passphrase`
+    pub fn encrypt(keypair: &Keypair, passphrase: &str, kdf: KdfParams) -> Result<Self> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, kdf)?);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), keypair.to_bytes().as_ref())
+            .map_err(|_| anyhow!("Failed to encrypt keypair"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey: keypair.pubkey().to_string(),
+            kdf,
+            salt: base64::encode(salt),
+            nonce: base64::encode(nonce),
+            ciphertext: base64::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the keypair, failing on a wrong passphrase or a tampered file
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(anyhow!("Unsupported keystore version {}", self.version));
+        }
+        let salt = base64::decode(&self.salt)?;
+        let nonce = base64::decode(&self.nonce)?;
+        let ciphertext = base64::decode(&self.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(anyhow!("Invalid keystore nonce"));
+        }
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, self.kdf)?);
+        let bytes = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| anyhow!("Wrong passphrase or corrupted keystore"))?;
+        let keypair = Keypair::from_bytes(&bytes).map_err(|e| anyhow!("Invalid keypair in keystore: {}", e))?;
+        if keypair.pubkey().to_string() != self.pubkey {
+            return Err(anyhow!("Keystore public key does not match the decrypted keypair"));
+        }
+        Ok(keypair)
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| anyhow!("Failed to read keystore {}: {}", path, e))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the keystore, refusing to replace an existing file unless `overwrite` is set
+    pub fn save(&self, path: &str, overwrite: bool) -> Result<()> {
+        if !overwrite && std::path::Path::new(path).exists() {
+            return Err(anyhow!("{} already exists, pass --force to replace it", path));
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Key> {
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(32))
+        .map_err(|e| anyhow!("Invalid keystore KDF parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive keystore key: {}", e))?;
+    Ok(Key::clone_from_slice(&key))
+}
+
+/// Passphrase from KEY_PASSPHRASE, or a hidden prompt on a terminal
+///
+/// With `confirm` the prompt asks twice, for creating a new keystore.
+pub fn read_passphrase(settings: &KeystoreSettings, confirm: bool) -> Result<String> {
+    if let Some(passphrase) = &settings.passphrase {
+        return Ok(passphrase.clone());
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!("Keystore {} needs a passphrase: set KEY_PASSPHRASE or run on a terminal", settings.path));
+    }
+    let passphrase = rpassword::prompt_password("Keystore passphrase: ")?;
+    if confirm {
+        if passphrase.len() < 8 {
+            return Err(anyhow!("Passphrase must be at least 8 characters"));
+        }
+        if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+            return Err(anyhow!("Passphrases do not match"));
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Load the wallet from the keystore file, if there is one
+pub fn load_keystore_wallet(settings: &KeystoreSettings) -> Result<Option<Keypair>> {
+    if !std::path::Path::new(&settings.path).exists() {
+        return Ok(None);
+    }
+    let keystore = Keystore::load(&settings.path)?;
+    let passphrase = read_passphrase(settings, false)?;
+    keystore.decrypt(&passphrase).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_wrong_passphrase() {
+        let kdf = KdfParams { m_cost: 1024, t_cost: 1, p_cost: 1 };
+        let keypair = Keypair::new();
+        let keystore = Keystore::encrypt(&keypair, "correct horse", kdf).unwrap();
+        assert_eq!(keystore.pubkey, keypair.pubkey().to_string());
+
+        let decrypted = keystore.decrypt("correct horse").unwrap();
+        assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+        assert!(keystore.decrypt("wrong").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use chrono::Local;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// When a file is rolled over and how many archives are kept
+#[derive(Debug, Clone)]
+pub struct RotationPolicy {
+    /// Roll over once the active file reaches this size (0 = never by size)
+    pub max_bytes: u64,
+    /// Roll over once the active file is this old (None = never by age)
+    pub max_age: Option<Duration>,
+    /// Number of archived files to keep (0 = keep all)
+    pub retention: usize,
+}
+
+struct Inner {
+    dir: PathBuf,
+    stem: String,
+    extension: String,
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+    policy: RotationPolicy,
+}
+
+/// Append-only file that rotates by size and/or age
+///
+/// The active file is always `<dir>/<stem>.<ext>`. On rotation it is renamed to
+/// `<stem>-<YYYYmmdd-HHMMSS>.<ext>` and the oldest archives beyond the retention
+/// count are deleted.
+#[derive(Clone)]
+pub struct RotatingFile {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl RotatingFile {
+    /// Open (or create) `<dir>/<file_name>` with the given rotation policy
+    pub fn open(dir: impl AsRef<Path>, file_name: &str, policy: RotationPolicy) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let (stem, extension) = match file_name.rsplit_once('.') {
+            Some((stem, ext)) => (stem.to_string(), ext.to_string()),
+            None => (file_name.to_string(), "log".to_string()),
+        };
+        let path = dir.join(format!("{}.{}", stem, extension));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                dir,
+                stem,
+                extension,
+                size: metadata.len(),
+                opened_at: metadata.created().or_else(|_| metadata.modified()).unwrap_or_else(|_| SystemTime::now()),
+                file,
+                policy,
+            })),
+        })
+    }
+
+    /// Path of the active file
+    pub fn path(&self) -> PathBuf {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.active_path()
+    }
+
+    /// Active file followed by every archive, oldest archive first
+    pub fn all_files(&self) -> Vec<PathBuf> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut files = inner.archives();
+        files.push(inner.active_path());
+        files
+    }
+}
+
+impl Inner {
+    fn active_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.{}", self.stem, self.extension))
+    }
+
+    fn archives(&self) -> Vec<PathBuf> {
+        let prefix = format!("{}-", self.stem);
+        let suffix = format!(".{}", self.extension);
+        let mut archives: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|n| n.to_str())
+                            .map(|n| n.starts_with(&prefix) && n.ends_with(&suffix))
+                            .unwrap_or(false)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // timestamps in the name sort chronologically
+        archives.sort();
+        archives
+    }
+
+    fn should_rotate(&self, incoming: usize) -> bool {
+        let by_size = self.policy.max_bytes > 0
+            && self.size > 0
+            && self.size + incoming as u64 > self.policy.max_bytes;
+        let by_age = self
+            .policy
+            .max_age
+            .map(|max_age| self.opened_at.elapsed().map(|age| age >= max_age).unwrap_or(false))
+            .unwrap_or(false);
+        by_size || (by_age && self.size > 0)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let mut archive = self.dir.join(format!(
+            "{}-{}.{}",
+            self.stem,
+            Local::now().format("%Y%m%d-%H%M%S"),
+            self.extension
+        ));
+        let mut n = 1;
+        while archive.exists() {
+            archive = self.dir.join(format!(
+                "{}-{}-{}.{}",
+                self.stem,
+                Local::now().format("%Y%m%d-%H%M%S"),
+                n,
+                self.extension
+            ));
+            n += 1;
+        }
+        fs::rename(self.active_path(), &archive)?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(self.active_path())?;
+        self.size = 0;
+        self.opened_at = SystemTime::now();
+
+        if self.policy.retention > 0 {
+            let archives = self.archives();
+            if archives.len() > self.policy.retention {
+                for old in &archives[..archives.len() - self.policy.retention] {
+                    let _ = fs::remove_file(old);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.should_rotate(buf.len()) {
+            inner.rotate()?;
+        }
+        inner.file.write_all(buf)?;
+        inner.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = RotatingFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_by_size_and_prunes() {
+        let dir = std::env::temp_dir().join(format!("vntr-rotation-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut file = RotatingFile::open(&dir, "bot.log", RotationPolicy {
+            max_bytes: 16,
+            max_age: None,
+            retention: 2,
+        }).unwrap();
+
+        for _ in 0..10 {
+            file.write_all(b"0123456789\n").unwrap();
+        }
+
+        let files = file.all_files();
+        // active file plus at most 2 archives
+        assert!(files.len() <= 3);
+        assert!(fs::metadata(file.path()).unwrap().len() <= 16);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,41 +1,66 @@
 use chrono::Local;
 use colored::*;
+use tracing::Level;
 
-const LOG_LEVEL: &str = "LOG";
+use crate::common::telemetry::{component_from_prefix, strip_ansi, LOG_SETTINGS};
 
+/// Prefix-based logger used throughout the bot
+///
+/// Messages are emitted as `tracing` events with a `component` field derived from
+/// the prefix, so they share the subscriber's format (pretty or JSON) and filters.
+/// Before a subscriber is installed the old colored stdout format is used.
 #[derive(Clone, Debug)]
 pub struct Logger {
     prefix: String,
+    component: String,
     date_format: String,
 }
 
 impl Logger {
     // Constructor function to create a new Logger instance
     pub fn new(prefix: String) -> Self {
+        let component = component_from_prefix(&prefix);
         Logger {
             prefix,
+            component,
             date_format: String::from("%Y-%m-%d %H:%M:%S"),
         }
     }
 
     // Method to log a message with a prefix
     pub fn log(&self, message: String) -> String {
-        let log = format!("{} {}", self.prefix_with_date(), message);
-        println!("{}", log);
-        log
+        self.emit(Level::INFO, None, message)
     }
 
     pub fn debug(&self, message: String) -> String {
-        let log = format!("{} [{}] {}", self.prefix_with_date(), "DEBUG", message);
-        if LogLevel::new().is_debug() {
-            println!("{}", log);
-        }
-        log
+        self.emit(Level::DEBUG, Some("DEBUG"), message)
     }
+
     pub fn error(&self, message: String) -> String {
-        let log = format!("{} [{}] {}", self.prefix_with_date(), "ERROR", message);
-        println!("{}", log);
+        self.emit(Level::ERROR, Some("ERROR"), message)
+    }
+
+    fn emit(&self, level: Level, tag: Option<&str>, message: String) -> String {
+        let log = match tag {
+            Some(tag) => format!("{} [{}] {}", self.prefix_with_date(), tag, message),
+            None => format!("{} {}", self.prefix_with_date(), message),
+        };
 
+        if !LOG_SETTINGS.component_enabled(&self.component, level) {
+            return log;
+        }
+
+        if tracing::dispatcher::has_been_set() {
+            let message = strip_ansi(&message);
+            let component = self.component.as_str();
+            match level {
+                Level::ERROR => tracing::error!(component, "{}", message),
+                Level::DEBUG => tracing::debug!(component, "{}", message),
+                _ => tracing::info!(component, "{}", message),
+            }
+        } else {
+            println!("{}", log);
+        }
         log
     }
 
@@ -47,22 +72,14 @@ impl Logger {
             self.prefix
         )
     }
-    
-    // Method to check if debug logging is enabled
-    pub fn debug_enabled(&self) -> bool {
-        LogLevel::new().is_debug()
-    }
-}
 
-struct LogLevel<'a> {
-    level: &'a str,
-}
-impl LogLevel<'_> {
-    fn new() -> Self {
-        let level = LOG_LEVEL;
-        LogLevel { level }
+    /// Component name used as the `component` field and for per-component levels
+    pub fn component(&self) -> &str {
+        &self.component
     }
-    fn is_debug(&self) -> bool {
-        self.level.to_lowercase().eq("debug")
+
+    // Method to check if debug logging is enabled
+    pub fn debug_enabled(&self) -> bool {
+        LOG_SETTINGS.component_enabled(&self.component, Level::DEBUG)
     }
 }
@@ -1,24 +1,34 @@
+pub mod arming;
 pub mod blacklist;
 pub mod config;
+pub mod config_export;
 pub mod constants;
+pub mod keystore;
+pub mod log_rotation;
 pub mod logger;
+pub mod network;
+pub mod profile;
+pub mod reason_code;
+pub mod signer;
+pub mod telemetry;
+pub mod trade_log;
 pub mod whitelist;
 
 pub use config::{
     Config,
-    TradingSettings,
-    JitoSettings,
-    ZeroSlotSettings,
-    NozomiSettings,
-    BloxRouteSettings,
+    BasicTradingConfig,
+    JitoConfig,
+    ZeroSlotConfig,
+    NozomiConfig,
+    BloxRouteConfig,
     AdvancedFilterSettings,
-    CopyTradingSettings,
-    PrivateLogicSettings,
-    InverseBuySettings,
-    TimerSettings,
-    ModeSettings,
-    AdvancedSettings,
-    ValidationError,
+    CopyTradingConfig,
+    PrivateLogicConfig,
+    InverseBuyConfig,
+    TimerConfig,
+    ModeConfig,
+    AdvancedConfig,
+    ConfigError,
     AppState,
     SwapConfig,
     LiquidityPool,
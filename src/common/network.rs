@@ -0,0 +1,104 @@
+/// Cluster the bot trades on, from `NETWORK_ENV`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkEnv {
+    Mainnet,
+    Devnet,
+    /// A local `solana-test-validator` with the pump.fun programs cloned in
+    Localnet,
+}
+
+/// Relay endpoints cleared off mainnet, none of them accept devnet or localnet transactions
+const RELAY_KEYS: &[&str] = &["JITO_BLOCK_ENGINE_URL", "ZERO_SLOT_URL", "NOZOMI_URL", "AUTH_HEADER"];
+
+impl NetworkEnv {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "" | "mainnet" | "mainnet-beta" => Some(NetworkEnv::Mainnet),
+            "devnet" => Some(NetworkEnv::Devnet),
+            "localnet" | "local" | "localhost" => Some(NetworkEnv::Localnet),
+            _ => None,
+        }
+    }
+
+    /// The network named by `NETWORK_ENV`, mainnet when unset or unknown
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::var("NETWORK_ENV").unwrap_or_default()).unwrap_or(NetworkEnv::Mainnet)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkEnv::Mainnet => "mainnet",
+            NetworkEnv::Devnet => "devnet",
+            NetworkEnv::Localnet => "localnet",
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == NetworkEnv::Mainnet
+    }
+
+    /// Prefix of the per-network program ID overrides, e.g. `DEVNET_PUMP_FUN_PROGRAM_ID`
+    pub fn env_prefix(&self) -> Option<&'static str> {
+        match self {
+            NetworkEnv::Mainnet => None,
+            NetworkEnv::Devnet => Some("DEVNET_"),
+            NetworkEnv::Localnet => Some("LOCALNET_"),
+        }
+    }
+
+    /// Default RPC HTTP and websocket endpoints
+    pub fn default_rpc(&self) -> (&'static str, &'static str) {
+        match self {
+            NetworkEnv::Mainnet => ("https://api.mainnet-beta.solana.com", "wss://api.mainnet-beta.solana.com"),
+            NetworkEnv::Devnet => ("https://api.devnet.solana.com", "wss://api.devnet.solana.com"),
+            NetworkEnv::Localnet => ("http://127.0.0.1:8899", "ws://127.0.0.1:8900"),
+        }
+    }
+
+    /// Environment values this network forces or defaults, `true` when set even if present
+    pub fn preset(&self) -> Vec<(&'static str, &'static str, bool)> {
+        if self.is_mainnet() {
+            return Vec::new();
+        }
+        let (rpc_http, rpc_wss) = self.default_rpc();
+        let mut preset = vec![("RPC_HTTP", rpc_http, false), ("RPC_WSS", rpc_wss, false), ("USE_JITO", "false", true)];
+        preset.extend(RELAY_KEYS.iter().map(|key| (*key, "", true)));
+        preset
+    }
+}
+
+/// Apply `NETWORK_ENV`: RPC defaults for the cluster and, off mainnet, no relays
+///
+/// Explicit RPC endpoints are kept, relay endpoints are cleared even when set so a devnet
+/// run never pays a mainnet tip. Returns the network, `Err` for an unknown name.
+pub fn apply_network() -> Result<NetworkEnv, String> {
+    let name = std::env::var("NETWORK_ENV").unwrap_or_default();
+    let network = NetworkEnv::parse(&name)
+        .ok_or_else(|| format!("Unknown NETWORK_ENV '{}', expected mainnet, devnet or localnet", name))?;
+    for (key, value, force) in network.preset() {
+        let unset = std::env::var(key).map(|v| v.trim().is_empty()).unwrap_or(true);
+        if force || unset {
+            std::env::set_var(key, value);
+        }
+    }
+    Ok(network)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_presets() {
+        assert_eq!(NetworkEnv::parse(""), Some(NetworkEnv::Mainnet));
+        assert_eq!(NetworkEnv::parse(" Devnet "), Some(NetworkEnv::Devnet));
+        assert_eq!(NetworkEnv::parse("testnet"), None);
+        assert!(NetworkEnv::Mainnet.preset().is_empty());
+
+        let preset = NetworkEnv::Localnet.preset();
+        assert!(preset.contains(&("RPC_HTTP", "http://127.0.0.1:8899", false)));
+        assert!(preset.contains(&("USE_JITO", "false", true)));
+        assert!(preset.contains(&("JITO_BLOCK_ENGINE_URL", "", true)));
+        assert_eq!(NetworkEnv::Devnet.env_prefix(), Some("DEVNET_"));
+    }
+}
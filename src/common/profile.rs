@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+/// Named bundle of settings selected with `PROFILE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Conservative,
+    Balanced,
+    Aggressive,
+}
+
+impl Profile {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "conservative" => Some(Self::Conservative),
+            "balanced" => Some(Self::Balanced),
+            "aggressive" | "degen" => Some(Self::Aggressive),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Conservative => "conservative",
+            Self::Balanced => "balanced",
+            Self::Aggressive => "aggressive",
+        }
+    }
+
+    /// Environment values the profile sets when they are not set already
+    pub fn preset(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Conservative => &[
+                ("TOKEN_AMOUNT", "0.05"),
+                ("SLIPPAGE", "50"),
+                ("STOP_LOSS_PERCENT", "15"),
+                ("TP_LADDER", "25:50,50:25"),
+                ("TP_TRAILING_STOP_PERCENT", "10"),
+                ("BREAKEVEN_TRIGGER_PERCENT", "15"),
+                ("MAX_HOLD_TIME_SECS", "900"),
+                ("DAILY_BUY_BUDGET", "1.0"),
+                ("MIN_BUY_CONFIDENCE", "0.8"),
+                ("MAX_OPEN_POSITIONS", "2"),
+                ("MAX_POSITION_SOL", "0.1"),
+                ("POSITION_SIZING_MODE", "fixed"),
+                ("DYNAMIC_SLIPPAGE", "false"),
+                ("MAX_SLIPPAGE_BPS", "500"),
+                ("MAX_PRICE_IMPACT_PERCENT", "3"),
+                ("SIMULATE_BEFORE_SEND", "true"),
+                ("REBUY_WINDOW_SECS", "3600"),
+                ("CIRCUIT_BREAKER_ENABLED", "true"),
+                ("CIRCUIT_BREAKER_COOLDOWN_SECS", "7200"),
+                ("MIN_LIQUIDITY_USD", "20000"),
+                ("MIN_MARKET_CAP", "10.0"),
+                ("MAX_MARKET_CAP", "50.0"),
+            ],
+            Self::Balanced => &[
+                ("TOKEN_AMOUNT", "0.2"),
+                ("SLIPPAGE", "100"),
+                ("STOP_LOSS_PERCENT", "25"),
+                ("TP_LADDER", "50:25,100:25"),
+                ("TP_TRAILING_STOP_PERCENT", "20"),
+                ("BREAKEVEN_TRIGGER_PERCENT", "30"),
+                ("MAX_HOLD_TIME_SECS", "1800"),
+                ("DAILY_BUY_BUDGET", "5.0"),
+                ("MIN_BUY_CONFIDENCE", "0.7"),
+                ("MAX_OPEN_POSITIONS", "5"),
+                ("MAX_POSITION_SOL", "0.5"),
+                ("POSITION_SIZING_MODE", "fixed"),
+                ("DYNAMIC_SLIPPAGE", "true"),
+                ("MAX_SLIPPAGE_BPS", "1500"),
+                ("MAX_PRICE_IMPACT_PERCENT", "8"),
+                ("SIMULATE_BEFORE_SEND", "true"),
+                ("REBUY_WINDOW_SECS", "600"),
+                ("CIRCUIT_BREAKER_ENABLED", "true"),
+                ("CIRCUIT_BREAKER_COOLDOWN_SECS", "3600"),
+                ("MIN_LIQUIDITY_USD", "10000"),
+                ("MIN_MARKET_CAP", "8.0"),
+                ("MAX_MARKET_CAP", "100.0"),
+            ],
+            Self::Aggressive => &[
+                ("TOKEN_AMOUNT", "1.0"),
+                ("SLIPPAGE", "300"),
+                ("STOP_LOSS_PERCENT", "40"),
+                ("TP_LADDER", "100:25,300:25,1000:25"),
+                ("TP_TRAILING_STOP_PERCENT", "30"),
+                ("BREAKEVEN_TRIGGER_PERCENT", "0"),
+                ("MAX_HOLD_TIME_SECS", "0"),
+                ("DAILY_BUY_BUDGET", "20.0"),
+                ("MIN_BUY_CONFIDENCE", "0.5"),
+                ("MAX_OPEN_POSITIONS", "15"),
+                ("MAX_POSITION_SOL", "2.0"),
+                ("POSITION_SIZING_MODE", "fixed"),
+                ("DYNAMIC_SLIPPAGE", "true"),
+                ("MAX_SLIPPAGE_BPS", "3000"),
+                ("MAX_PRICE_IMPACT_PERCENT", "0"),
+                ("SIMULATE_BEFORE_SEND", "false"),
+                ("REBUY_WINDOW_SECS", "120"),
+                ("CIRCUIT_BREAKER_ENABLED", "false"),
+                ("CIRCUIT_BREAKER_COOLDOWN_SECS", "1800"),
+                ("MIN_LIQUIDITY_USD", "3000"),
+                ("MIN_MARKET_CAP", "4.0"),
+                ("MAX_MARKET_CAP", "500.0"),
+            ],
+        }
+    }
+}
+
+/// Keys the last `apply_profile` call filled in, so a reload can replace them
+static APPLIED_KEYS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Fill in the settings of the `PROFILE` preset that are not set in the environment or `.env`
+///
+/// Returns the profile and how many of its values were applied, `Err` for an unknown name.
+pub fn apply_profile() -> Result<Option<(Profile, usize)>, String> {
+    let mut applied = APPLIED_KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    for key in applied.drain() {
+        std::env::remove_var(key);
+    }
+    // values added to `.env` since the last call win over the profile
+    dotenv::dotenv().ok();
+    let name = std::env::var("PROFILE").unwrap_or_default();
+    if name.trim().is_empty() {
+        return Ok(None);
+    }
+    let profile = Profile::parse(&name)
+        .ok_or_else(|| format!("Unknown PROFILE '{}', expected conservative, balanced or aggressive", name))?;
+    for (key, value) in profile.preset() {
+        if std::env::var_os(key).is_none() {
+            std::env::set_var(key, value);
+            applied.insert(key);
+        }
+    }
+    Ok(Some((profile, applied.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        assert_eq!(Profile::parse(" Degen "), Some(Profile::Aggressive));
+        assert_eq!(Profile::parse("yolo"), None);
+        let keys = |profile: Profile| profile.preset().iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        // every profile sets the same settings
+        assert_eq!(keys(Profile::Conservative), keys(Profile::Balanced));
+        assert_eq!(keys(Profile::Balanced), keys(Profile::Aggressive));
+    }
+}
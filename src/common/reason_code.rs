@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::{LazyLock, Mutex};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::common::log_rotation::{RotatingFile, RotationPolicy};
+use crate::common::trade_log::TradeLogSettings;
+
+/// What was decided about a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    Buy,
+    /// Not bought, rejected by a filter
+    Skip,
+    Sell,
+    /// A buy that was decided on but not sent
+    Abort,
+}
+
+/// Machine-readable reason behind a decision, written as its variant name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReasonCode {
+    // buys, by the strategy that made them
+    LaunchSnipe,
+    CopySourceWallet,
+    ChannelCall,
+    ExternalSignal,
+    KingOfTheHill,
+    MomentumEntry,
+    InverseBuy,
+    ManualBuy,
+    StrategyBuy,
+
+    // skips, by the filter that rejected the token
+    FilterMarketCapTooLow,
+    FilterMarketCapTooHigh,
+    FilterMarketCapUnknown,
+    FilterBuySellCount,
+    FilterVolume,
+    FilterLauncherBalance,
+    FilterDevBuy,
+    FilterCurveProgress,
+    FilterTokenAge,
+    FilterBundle,
+    FilterInsiders,
+    FilterCoinPage,
+    FilterMetadata,
+    FilterConfidence,
+    FilterSolInvested,
+    FilterLimitVolume,
+    FilterTokenRule,
+    FilterMarketData,
+    FilterMintSafety,
+    FilterOther,
+
+    // aborted buys
+    TradingPaused,
+    OutsideSchedule,
+    AlreadyHolding,
+    CooldownActive,
+    CircuitBreakerTripped,
+    BalanceTooLow,
+    PositionLimitReached,
+    DuplicateBuy,
+    BudgetExhausted,
+    SubmitFailed,
+
+    // sells
+    StopLossTriggered,
+    BreakEvenStop,
+    ProfitLockHit,
+    TakeProfitHit,
+    MaxHoldTime,
+    TrailingStop,
+    CopySourceSold,
+    StrategyExit,
+    CreatorDump,
+    CreatorActivity,
+    TradingWindowClosed,
+    ShutdownSell,
+    PanicSell,
+    ManualSell,
+}
+
+impl ReasonCode {
+    /// Code of a buy made by `strategy`
+    pub fn for_strategy(strategy: &str) -> Self {
+        match strategy {
+            "snipe" => ReasonCode::LaunchSnipe,
+            "copy" => ReasonCode::CopySourceWallet,
+            "channel" => ReasonCode::ChannelCall,
+            "signal" => ReasonCode::ExternalSignal,
+            "koth" => ReasonCode::KingOfTheHill,
+            "momentum" => ReasonCode::MomentumEntry,
+            "inverse" => ReasonCode::InverseBuy,
+            "manual" => ReasonCode::ManualBuy,
+            _ => ReasonCode::StrategyBuy,
+        }
+    }
+
+    /// Code of a rejection by the filter named `filter` in the filter statistics
+    pub fn for_filter(filter: &str) -> Self {
+        match filter {
+            "market_cap" => ReasonCode::FilterMarketCapUnknown,
+            "buy_sell_count" => ReasonCode::FilterBuySellCount,
+            "volume" => ReasonCode::FilterVolume,
+            "launcher_sol_balance" => ReasonCode::FilterLauncherBalance,
+            "dev_buy" => ReasonCode::FilterDevBuy,
+            "curve_progress" => ReasonCode::FilterCurveProgress,
+            "token_age" => ReasonCode::FilterTokenAge,
+            "bundle" => ReasonCode::FilterBundle,
+            "insiders" => ReasonCode::FilterInsiders,
+            "coin_page" => ReasonCode::FilterCoinPage,
+            "metadata" => ReasonCode::FilterMetadata,
+            "confidence" => ReasonCode::FilterConfidence,
+            "sol_invested" => ReasonCode::FilterSolInvested,
+            "limit_volume" => ReasonCode::FilterLimitVolume,
+            "token_rule" => ReasonCode::FilterTokenRule,
+            "market_data" => ReasonCode::FilterMarketData,
+            "safety" => ReasonCode::FilterMintSafety,
+            _ => ReasonCode::FilterOther,
+        }
+    }
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// One line of decisions.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionEntry {
+    pub timestamp: DateTime<Utc>,
+    pub mint: String,
+    pub decision: Decision,
+    pub code: ReasonCode,
+    /// Free-text detail, the same wording as the log line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// decisions.jsonl next to trades.jsonl, rotated with the same settings
+static DECISION_LOG: LazyLock<Option<RotatingFile>> = LazyLock::new(|| {
+    let settings = TradeLogSettings::from_env();
+    let enabled = std::env::var("DECISION_LOG_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .to_lowercase() == "true";
+    if !enabled {
+        return None;
+    }
+    let policy = RotationPolicy {
+        max_bytes: settings.max_size_mb * 1024 * 1024,
+        max_age: None,
+        retention: settings.retention,
+    };
+    match RotatingFile::open(&settings.dir, "decisions.jsonl", policy) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("Failed to open decision log in {}: {}", settings.dir, e);
+            None
+        }
+    }
+});
+
+/// Code of the latest buy and sell decision per mint, picked up when the trade is logged
+static TRADE_REASONS: LazyLock<Mutex<HashMap<(String, Decision), ReasonCode>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Record a decision about `mint` in decisions.jsonl
+///
+/// Buy and sell decisions also tag the trade they lead to, so its ledger entry and alerts carry `code`.
+pub fn record_decision(decision: Decision, mint: &str, code: ReasonCode, detail: &str) {
+    if matches!(decision, Decision::Buy | Decision::Sell) {
+        TRADE_REASONS.lock().unwrap_or_else(|e| e.into_inner()).insert((mint.to_string(), decision), code);
+    }
+    let Some(file) = DECISION_LOG.as_ref() else {
+        return;
+    };
+    let entry = DecisionEntry {
+        timestamp: Utc::now(),
+        mint: mint.to_string(),
+        decision,
+        code,
+        detail: (!detail.is_empty()).then(|| detail.to_string()),
+    };
+    let line = match serde_json::to_string(&entry) {
+        Ok(line) => line + "\n",
+        Err(e) => {
+            eprintln!("Failed to serialize decision log entry: {}", e);
+            return;
+        }
+    };
+    let mut file = file.clone();
+    if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.flush()) {
+        eprintln!("Failed to write decision log entry: {}", e);
+    }
+}
+
+/// Code of the buy or sell decision behind the latest trade of `mint` on that side
+pub fn trade_reason(mint: &str, decision: Decision) -> Option<ReasonCode> {
+    TRADE_REASONS.lock().unwrap_or_else(|e| e.into_inner()).get(&(mint.to_string(), decision)).copied()
+}
+
+/// Forget the decision behind `mint`'s trades on that side
+pub fn clear_trade_reason(mint: &str, decision: Decision) {
+    TRADE_REASONS.lock().unwrap_or_else(|e| e.into_inner()).remove(&(mint.to_string(), decision));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_for_strategies_and_filters() {
+        assert_eq!(ReasonCode::for_strategy("copy"), ReasonCode::CopySourceWallet);
+        assert_eq!(ReasonCode::for_strategy("unknown"), ReasonCode::StrategyBuy);
+        assert_eq!(ReasonCode::for_filter("safety"), ReasonCode::FilterMintSafety);
+        assert_eq!(ReasonCode::for_filter("new_filter"), ReasonCode::FilterOther);
+    }
+
+    #[test]
+    fn test_codes_serialize_by_name() {
+        assert_eq!(serde_json::to_string(&ReasonCode::FilterMarketCapTooLow).unwrap(), "\"FilterMarketCapTooLow\"");
+        assert_eq!(ReasonCode::BudgetExhausted.to_string(), "BudgetExhausted");
+    }
+
+    #[test]
+    fn test_decision_entry_omits_empty_detail() {
+        let entry = DecisionEntry {
+            timestamp: Utc::now(),
+            mint: "mint".to_string(),
+            decision: Decision::Abort,
+            code: ReasonCode::BudgetExhausted,
+            detail: None,
+        };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["decision"], "abort");
+        assert!(json.get("detail").is_none());
+    }
+
+    #[test]
+    fn test_trade_reason_tagged_per_side_until_cleared() {
+        TRADE_REASONS.lock().unwrap().insert(("tagged".to_string(), Decision::Sell), ReasonCode::StopLossTriggered);
+        assert_eq!(trade_reason("tagged", Decision::Sell), Some(ReasonCode::StopLossTriggered));
+        assert_eq!(trade_reason("tagged", Decision::Buy), None);
+        clear_trade_reason("tagged", Decision::Sell);
+        assert_eq!(trade_reason("tagged", Decision::Sell), None);
+    }
+}
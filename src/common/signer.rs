@@ -0,0 +1,386 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anchor_client::solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError},
+    transaction::{Transaction, VersionedTransaction},
+};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Wallet used to sign trades: a local keypair, a remote signing service or a Ledger
+pub type WalletSigner = Arc<dyn Signer + Send + Sync>;
+
+/// Where transactions get signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerBackend {
+    /// In-process keypair from the keystore or PRIVATE_KEY
+    Local,
+    /// External signing service over a persistent TCP connection
+    Remote,
+    /// Ledger hardware wallet (needs the `ledger` feature)
+    Ledger,
+}
+
+impl SignerBackend {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "local" => Some(Self::Local),
+            "remote" => Some(Self::Remote),
+            "ledger" => Some(Self::Ledger),
+            _ => None,
+        }
+    }
+}
+
+/// Signer settings
+#[derive(Debug, Clone)]
+pub struct SignerSettings {
+    pub backend: SignerBackend,
+    /// host:port of the signing service
+    pub remote_addr: String,
+    /// Shared secret sent with every request, if the service wants one
+    pub remote_token: Option<String>,
+    /// Expected wallet address; startup fails if the service reports another one
+    pub remote_pubkey: Option<Pubkey>,
+    pub remote_timeout_ms: u64,
+    /// Ledger locator, e.g. `usb://ledger`
+    pub ledger_locator: String,
+    /// Ledger derivation key, e.g. `0/0`; the device default when empty
+    pub ledger_key: Option<String>,
+}
+
+impl SignerSettings {
+    /// Load signer settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            backend: std::env::var("SIGNER_BACKEND")
+                .ok()
+                .and_then(|v| SignerBackend::parse(&v))
+                .unwrap_or(SignerBackend::Local),
+            remote_addr: std::env::var("REMOTE_SIGNER_ADDR").unwrap_or_else(|_| "127.0.0.1:7878".to_string()),
+            remote_token: std::env::var("REMOTE_SIGNER_TOKEN").ok().filter(|v| !v.is_empty()),
+            remote_pubkey: std::env::var("REMOTE_SIGNER_PUBKEY")
+                .ok()
+                .and_then(|v| Pubkey::from_str(v.trim()).ok()),
+            remote_timeout_ms: std::env::var("REMOTE_SIGNER_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(500),
+            ledger_locator: std::env::var("LEDGER_LOCATOR").unwrap_or_else(|_| "usb://ledger".to_string()),
+            ledger_key: std::env::var("LEDGER_KEY").ok().filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+/// Connect the configured external signer; `None` means sign with the local keypair
+pub fn connect_signer(settings: &SignerSettings) -> Result<Option<WalletSigner>> {
+    match settings.backend {
+        SignerBackend::Local => Ok(None),
+        SignerBackend::Remote => Ok(Some(Arc::new(RemoteSigner::connect(settings)?))),
+        #[cfg(feature = "ledger")]
+        SignerBackend::Ledger => Ok(Some(Arc::new(ledger::LedgerSigner::connect(
+            &settings.ledger_locator,
+            settings.ledger_key.as_deref(),
+        )?))),
+        #[cfg(not(feature = "ledger"))]
+        SignerBackend::Ledger => Err(anyhow!("SIGNER_BACKEND=ledger needs a build with --features ledger")),
+    }
+}
+
+/// Build and sign a transaction paid for by `payer`
+///
+/// Unlike `Transaction::new_signed_with_payer` this returns an error instead of
+/// panicking when an external signer is unreachable or refuses.
+pub fn signed_transaction(instructions: &[Instruction], payer: &dyn Signer, recent_blockhash: Hash) -> Result<Transaction> {
+    let mut txn = Transaction::new_with_payer(instructions, Some(&payer.try_pubkey()?));
+    txn.try_sign(&vec![payer], recent_blockhash)?;
+    Ok(txn)
+}
+
+/// Build and sign a v0 transaction paid for by `payer`, resolving accounts through `lookup_tables`
+///
+/// Accounts found in a table are referenced by a one-byte index instead of 32 bytes.
+pub fn signed_versioned_transaction(
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    recent_blockhash: Hash,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<VersionedTransaction> {
+    let message = v0::Message::try_compile(&payer.try_pubkey()?, instructions, lookup_tables, recent_blockhash)?;
+    Ok(VersionedTransaction::try_new(VersionedMessage::V0(message), &vec![payer])?)
+}
+
+#[derive(Debug, Serialize)]
+struct SignerRequest<'a> {
+    method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignerResponse {
+    pubkey: Option<String>,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+/// Signs through an external service speaking newline-delimited JSON over TCP
+///
+/// `{"method":"get_pubkey"}` answers `{"pubkey":"<base58>"}` and
+/// `{"method":"sign_message","message":"<base64>"}` answers `{"signature":"<base58>"}`;
+/// failures answer `{"error":"..."}`. The connection is kept open between requests
+/// and reopened once if it breaks, so a signature costs one round trip.
+pub struct RemoteSigner {
+    addr: String,
+    token: Option<String>,
+    timeout: Duration,
+    pubkey: Pubkey,
+    connection: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RemoteSigner {
+    /// Connect and fetch the wallet address from the service
+    pub fn connect(settings: &SignerSettings) -> Result<Self> {
+        let mut signer = Self {
+            addr: settings.remote_addr.clone(),
+            token: settings.remote_token.clone(),
+            timeout: Duration::from_millis(settings.remote_timeout_ms.max(50)),
+            pubkey: Pubkey::default(),
+            connection: Mutex::new(None),
+        };
+        let response = signer
+            .request("get_pubkey", None)
+            .map_err(|e| anyhow!("Remote signer at {}: {}", settings.remote_addr, e))?;
+        let pubkey = response
+            .pubkey
+            .and_then(|pubkey| Pubkey::from_str(&pubkey).ok())
+            .ok_or_else(|| anyhow!("Remote signer at {} returned no valid public key", settings.remote_addr))?;
+        if let Some(expected) = settings.remote_pubkey {
+            if expected != pubkey {
+                return Err(anyhow!("Remote signer holds {}, expected REMOTE_SIGNER_PUBKEY {}", pubkey, expected));
+            }
+        }
+        signer.pubkey = pubkey;
+        Ok(signer)
+    }
+
+    fn open(&self) -> std::io::Result<BufReader<TcpStream>> {
+        let stream = TcpStream::connect(&self.addr)?;
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        Ok(BufReader::new(stream))
+    }
+
+    fn round_trip(connection: &mut BufReader<TcpStream>, line: &str) -> std::io::Result<String> {
+        connection.get_mut().write_all(line.as_bytes())?;
+        let mut response = String::new();
+        if connection.read_line(&mut response)? == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "signer closed the connection"));
+        }
+        Ok(response)
+    }
+
+    fn request(&self, method: &str, message: Option<&[u8]>) -> Result<SignerResponse, SignerError> {
+        let mut line = serde_json::to_string(&SignerRequest {
+            method,
+            message: message.map(base64::encode),
+            token: self.token.as_deref(),
+        })
+        .map_err(|e| SignerError::Custom(e.to_string()))?;
+        line.push('\n');
+
+        let mut connection = self.connection.lock().unwrap_or_else(|e| e.into_inner());
+        let mut last_error = None;
+        // a kept-alive connection may have been dropped by the service, retry once on a new one
+        for _ in 0..2 {
+            if connection.is_none() {
+                *connection = Some(self.open().map_err(|e| SignerError::Connection(e.to_string()))?);
+            }
+            let Some(stream) = connection.as_mut() else {
+                continue;
+            };
+            match Self::round_trip(stream, &line) {
+                Ok(response) => {
+                    let response: SignerResponse =
+                        serde_json::from_str(&response).map_err(|e| SignerError::Protocol(e.to_string()))?;
+                    if let Some(error) = response.error {
+                        return Err(SignerError::Custom(error));
+                    }
+                    return Ok(response);
+                }
+                Err(e) => {
+                    *connection = None;
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(SignerError::Connection(
+            last_error.map(|e| e.to_string()).unwrap_or_else(|| "not connected".to_string()),
+        ))
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let response = self.request("sign_message", Some(message))?;
+        let signature = response
+            .signature
+            .and_then(|signature| Signature::from_str(&signature).ok())
+            .ok_or_else(|| SignerError::Protocol("response has no valid signature".to_string()))?;
+        // never hand out a signature the chain would reject
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::Protocol("signature does not match the signer's public key".to_string()));
+        }
+        Ok(signature)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "ledger")]
+mod ledger {
+    use std::sync::mpsc;
+    use anchor_client::solana_sdk::{
+        derivation_path::DerivationPath,
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{Signer, SignerError},
+    };
+    use anyhow::{anyhow, Result};
+    use solana_remote_wallet::{
+        locator::Locator,
+        remote_keypair::{generate_remote_keypair, RemoteKeypair},
+        remote_wallet::maybe_wallet_manager,
+    };
+
+    type SignRequest = (Vec<u8>, mpsc::Sender<Result<Signature, SignerError>>);
+
+    /// Ledger signer
+    ///
+    /// The device handle is not thread safe, so it lives on a dedicated thread that
+    /// keeps the USB connection open and signs requests one at a time.
+    pub struct LedgerSigner {
+        pubkey: Pubkey,
+        requests: mpsc::Sender<SignRequest>,
+    }
+
+    impl LedgerSigner {
+        pub fn connect(locator: &str, key: Option<&str>) -> Result<Self> {
+            let (ready_tx, ready_rx) = mpsc::channel();
+            let (requests, incoming) = mpsc::channel::<SignRequest>();
+            let locator = locator.to_string();
+            let key = key.map(str::to_string);
+
+            std::thread::spawn(move || {
+                let keypair = match open(&locator, key.as_deref()) {
+                    Ok(keypair) => keypair,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(keypair.pubkey()));
+                for (message, reply) in incoming {
+                    let _ = reply.send(keypair.try_sign_message(&message));
+                }
+            });
+
+            let pubkey = ready_rx.recv().map_err(|_| anyhow!("Ledger thread exited during startup"))??;
+            Ok(Self { pubkey, requests })
+        }
+    }
+
+    fn open(locator: &str, key: Option<&str>) -> Result<RemoteKeypair> {
+        let manager = maybe_wallet_manager()?.ok_or_else(|| anyhow!("No Ledger device found"))?;
+        let locator = Locator::new_from_path(locator)?;
+        let derivation_path = match key {
+            Some(key) => DerivationPath::from_key_str(key)?,
+            None => DerivationPath::default(),
+        };
+        Ok(generate_remote_keypair(locator, derivation_path, &manager, false, "trading wallet")?)
+    }
+
+    impl Signer for LedgerSigner {
+        fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+            Ok(self.pubkey)
+        }
+
+        fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            let (reply, response) = mpsc::channel();
+            self.requests
+                .send((message.to_vec(), reply))
+                .map_err(|_| SignerError::Connection("Ledger thread stopped".to_string()))?;
+            response
+                .recv()
+                .map_err(|_| SignerError::Connection("Ledger thread stopped".to_string()))?
+        }
+
+        fn is_interactive(&self) -> bool {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use anchor_client::solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_remote_signer_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+
+        // minimal signing service answering over one kept-alive connection
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                let response = match request["method"].as_str() {
+                    Some("get_pubkey") => serde_json::json!({ "pubkey": keypair.pubkey().to_string() }),
+                    _ => {
+                        let message = base64::decode(request["message"].as_str().unwrap()).unwrap();
+                        serde_json::json!({ "signature": keypair.sign_message(&message).to_string() })
+                    }
+                };
+                writer.write_all(format!("{}\n", response).as_bytes()).unwrap();
+                line.clear();
+            }
+        });
+
+        let mut settings = SignerSettings::from_env();
+        settings.remote_addr = addr;
+        settings.remote_pubkey = Some(pubkey);
+        settings.remote_timeout_ms = 2_000;
+        let signer = RemoteSigner::connect(&settings).unwrap();
+        assert_eq!(signer.pubkey(), pubkey);
+
+        let signature = signer.try_sign_message(b"message").unwrap();
+        assert!(signature.verify(pubkey.as_ref(), b"message"));
+        assert!(signer.try_sign_message(b"again").is_ok());
+    }
+}
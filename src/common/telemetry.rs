@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::{Level, Span};
+use tracing_subscriber::{fmt, fmt::format::FmtSpan, prelude::*, EnvFilter};
+
+use crate::common::log_rotation::{RotatingFile, RotationPolicy};
+
+/// Output format for log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable, colored output
+    Pretty,
+    /// One JSON object per line, for Loki/Elastic ingestion
+    Json,
+}
+
+/// Logging settings
+#[derive(Debug, Clone)]
+pub struct LogSettings {
+    pub format: LogFormat,
+    /// Default level for everything (trace, debug, info, warn, error)
+    pub level: String,
+    /// Extra `EnvFilter` directives for modules, e.g. `solana_vntr_sniper::core=debug`
+    pub filter: String,
+    /// Per-component levels for `Logger` prefixes, e.g. `telegram=warn,jito=debug`
+    pub component_levels: HashMap<String, Level>,
+    /// Directory for the rotating JSON log file (empty = stdout only)
+    pub file_dir: String,
+    pub file_name: String,
+    /// Rotate the log file once it reaches this size in MB (0 = never by size)
+    pub file_max_size_mb: u64,
+    /// Rotate the log file at least this often in hours (0 = never by age)
+    pub file_rotate_hours: u64,
+    /// Number of rotated log files to keep (0 = keep all)
+    pub file_retention: usize,
+}
+
+impl LogSettings {
+    /// Load logging settings from environment variables
+    pub fn from_env() -> Self {
+        let format = match std::env::var("LOG_FORMAT").unwrap_or_default().to_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Pretty,
+        };
+
+        let component_levels = std::env::var("LOG_COMPONENT_LEVELS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|pair| {
+                let (component, level) = pair.split_once('=')?;
+                Some((component.trim().to_lowercase(), level.trim().parse::<Level>().ok()?))
+            })
+            .collect();
+
+        Self {
+            format,
+            level: std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
+            filter: std::env::var("LOG_FILTER").unwrap_or_default(),
+            component_levels,
+            file_dir: std::env::var("LOG_FILE_DIR").unwrap_or_default(),
+            file_name: std::env::var("LOG_FILE_NAME").unwrap_or_else(|_| "bot.log".to_string()),
+            file_max_size_mb: std::env::var("LOG_FILE_MAX_SIZE_MB")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(100),
+            file_rotate_hours: std::env::var("LOG_FILE_ROTATE_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(24),
+            file_retention: std::env::var("LOG_FILE_RETENTION")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(7),
+        }
+    }
+
+    /// Rotation policy for the log file
+    pub fn file_policy(&self) -> RotationPolicy {
+        RotationPolicy {
+            max_bytes: self.file_max_size_mb * 1024 * 1024,
+            max_age: if self.file_rotate_hours > 0 {
+                Some(Duration::from_secs(self.file_rotate_hours * 3600))
+            } else {
+                None
+            },
+            retention: self.file_retention,
+        }
+    }
+
+    /// Whether a `Logger` component should emit events at `level`
+    pub fn component_enabled(&self, component: &str, level: Level) -> bool {
+        let max = self
+            .component_levels
+            .get(component)
+            .copied()
+            .or_else(|| self.level.parse::<Level>().ok())
+            .unwrap_or(Level::INFO);
+        level <= max
+    }
+}
+
+/// Settings resolved once per process
+pub static LOG_SETTINGS: LazyLock<LogSettings> = LazyLock::new(LogSettings::from_env);
+
+/// Install the global tracing subscriber
+///
+/// Spans are reported when they close, so the `detect`, `filter`, `build` and `send`
+/// stages of a trade each log their own duration under the parent `pipeline` span.
+/// When `LOG_FILE_DIR` is set, every event is also written as JSON to a rotating file.
+pub fn init_tracing() {
+    let settings = &*LOG_SETTINGS;
+
+    let mut directives = settings.level.clone();
+    if !settings.filter.is_empty() {
+        directives = format!("{},{}", directives, settings.filter);
+    }
+    let filter = EnvFilter::try_new(&directives).unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let stdout_layer = match settings.format {
+        LogFormat::Json => {
+            // colored output would leak escape codes into the JSON fields
+            colored::control::set_override(false);
+            fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_events(FmtSpan::CLOSE)
+                .boxed()
+        }
+        LogFormat::Pretty => fmt::layer()
+            .with_span_events(FmtSpan::CLOSE)
+            .with_target(true)
+            .boxed(),
+    };
+
+    let file_layer = if settings.file_dir.is_empty() {
+        None
+    } else {
+        match RotatingFile::open(&settings.file_dir, &settings.file_name, settings.file_policy()) {
+            Ok(writer) => Some(
+                fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_ansi(false)
+                    .with_writer(writer)
+                    .boxed(),
+            ),
+            Err(e) => {
+                eprintln!("Failed to open log file in {}: {}", settings.file_dir, e);
+                None
+            }
+        }
+    };
+
+    let result = tracing_subscriber::registry()
+        .with(filter)
+        .with(stdout_layer)
+        .with(file_layer)
+        .try_init();
+
+    if let Err(e) = result {
+        eprintln!("Failed to initialize tracing: {}", e);
+    }
+}
+
+/// Root span covering one token from detection to submission
+pub fn pipeline_span(mint: &str) -> Span {
+    tracing::info_span!("pipeline", mint = %mint)
+}
+
+/// Child span for one pipeline stage (`detect`, `filter`, `build`, `send`)
+pub fn stage_span(parent: &Span, stage: &'static str) -> Span {
+    tracing::info_span!(parent: parent, "stage", stage = stage)
+}
+
+/// Remove ANSI color escape sequences from a message
+pub fn strip_ansi(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Component name from a `Logger` prefix like `"[TELEGRAM] => "`
+pub fn component_from_prefix(prefix: &str) -> String {
+    strip_ansi(prefix)
+        .trim()
+        .trim_end_matches("=>")
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim()
+        .to_lowercase()
+        .replace(' ', "_")
+}
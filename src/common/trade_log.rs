@@ -0,0 +1,122 @@
+use std::io::{BufRead, BufReader, Write};
+use std::sync::LazyLock;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::common::log_rotation::{RotatingFile, RotationPolicy};
+use crate::common::reason_code::ReasonCode;
+
+/// Trade log settings
+#[derive(Debug, Clone)]
+pub struct TradeLogSettings {
+    /// Directory holding trades.jsonl and its archives
+    pub dir: String,
+    /// Archive trades.jsonl once it reaches this size in MB (0 = never)
+    pub max_size_mb: u64,
+    /// Number of archived trade files to keep (0 = keep all)
+    pub retention: usize,
+}
+
+impl TradeLogSettings {
+    /// Load trade log settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            dir: std::env::var("TRADE_LOG_DIR").unwrap_or_else(|_| "logs".to_string()),
+            max_size_mb: std::env::var("TRADE_LOG_MAX_SIZE_MB")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(50),
+            retention: std::env::var("TRADE_LOG_RETENTION")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// One line of trades.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub mint: String,
+    /// "buy" or "sell"
+    pub side: String,
+    /// confirmed, failed or abandoned
+    pub status: String,
+    pub signature: String,
+    pub sol_amount: f64,
+    pub token_amount: u64,
+    /// Price per whole token in SOL at submission
+    pub price: f64,
+    /// Network and priority fee paid, in SOL (0 when it never landed)
+    #[serde(default)]
+    pub fee_sol: f64,
+    /// Jito tip paid, in SOL
+    #[serde(default)]
+    pub tip_sol: f64,
+    pub attempts: u32,
+    pub latency_ms: u64,
+    /// Failure reason, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Why the trade was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
+}
+
+static TRADE_LOG: LazyLock<Option<RotatingFile>> = LazyLock::new(|| {
+    let settings = TradeLogSettings::from_env();
+    let policy = RotationPolicy {
+        max_bytes: settings.max_size_mb * 1024 * 1024,
+        max_age: None,
+        retention: settings.retention,
+    };
+    match RotatingFile::open(&settings.dir, "trades.jsonl", policy) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("Failed to open trade log in {}: {}", settings.dir, e);
+            None
+        }
+    }
+});
+
+/// Append a trade to trades.jsonl
+///
+/// Each record is written and flushed as a single line, so the file stays valid
+/// JSONL across crashes and restarts.
+pub fn append_trade(entry: &TradeLogEntry) {
+    let Some(file) = TRADE_LOG.as_ref() else {
+        return;
+    };
+    let line = match serde_json::to_string(entry) {
+        Ok(line) => line + "\n",
+        Err(e) => {
+            eprintln!("Failed to serialize trade log entry: {}", e);
+            return;
+        }
+    };
+    // a single write keeps concurrent appends from interleaving
+    let mut file = file.clone();
+    if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.flush()) {
+        eprintln!("Failed to write trade log entry: {}", e);
+    }
+}
+
+/// Read every trade from trades.jsonl and its archives, oldest first
+pub fn read_trades() -> Vec<TradeLogEntry> {
+    let Some(file) = TRADE_LOG.as_ref() else {
+        return Vec::new();
+    };
+    let mut trades = Vec::new();
+    for path in file.all_files() {
+        let Ok(handle) = std::fs::File::open(&path) else {
+            continue;
+        };
+        for line in BufReader::new(handle).lines().map_while(Result::ok) {
+            if let Ok(entry) = serde_json::from_str::<TradeLogEntry>(&line) {
+                trades.push(entry);
+            }
+        }
+    }
+    trades
+}
@@ -0,0 +1,631 @@
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use colored::Colorize;
+use futures_util::StreamExt;
+use tokio::time::Instant;
+use anchor_client::solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_config::RpcSignatureSubscribeConfig,
+    rpc_response::RpcSignatureResult,
+};
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+};
+
+use crate::common::config::Status;
+use crate::common::{arming::ensure_armed, logger::Logger, signer::WalletSigner};
+use crate::common::reason_code::{clear_trade_reason, trade_reason, Decision};
+use crate::common::trade_log::{append_trade, TradeLogEntry};
+use crate::core::position_store::PositionStore;
+use crate::core::tx;
+use crate::dex::pump_fun::get_token_creator;
+use crate::engine::budget::budget_manager;
+use crate::engine::circuit_breaker::circuit_breaker;
+use crate::engine::cooldown::cooldowns;
+use crate::engine::fee_ledger::{fee_alerts, fee_ledger};
+use crate::engine::latency::latency_tracer;
+use crate::engine::position_marks::position_marks;
+use crate::engine::slot_sync::{slot_sync, SlotDistance};
+use crate::engine::strategy_ledger::strategy_ledger;
+use crate::engine::swap::SwapDirection;
+use crate::services::alerts::{alert, Alert};
+use crate::services::health::stream_health;
+use crate::services::metrics::metrics;
+use crate::services::webhook::{webhook, WebhookEvent};
+
+/// Time after a submission by which its blockhash has expired, when the cluster cannot be asked
+const BLOCKHASH_DEADLINE: Duration = Duration::from_secs(90);
+
+/// Settings controlling how long we wait for a signature and how often we resubmit
+#[derive(Debug, Clone)]
+pub struct ConfirmationSettings {
+    /// How long to wait for a single submission to confirm before treating it as dropped
+    pub timeout_ms: u64,
+    /// Polling interval used when no websocket endpoint is available
+    pub poll_interval_ms: u64,
+    /// Number of times an expired transaction is rebuilt with a fresh blockhash and resent
+    pub max_resubmits: u32,
+}
+
+impl Default for ConfirmationSettings {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 15_000,
+            poll_interval_ms: 400,
+            max_resubmits: 2,
+        }
+    }
+}
+
+impl ConfirmationSettings {
+    /// Load confirmation settings from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            timeout_ms: std::env::var("CONFIRM_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(defaults.timeout_ms),
+            poll_interval_ms: std::env::var("CONFIRM_POLL_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(defaults.poll_interval_ms),
+            max_resubmits: std::env::var("CONFIRM_MAX_RESUBMITS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(defaults.max_resubmits),
+        }
+    }
+}
+
+/// A submitted transaction that still needs to be confirmed
+#[derive(Clone)]
+pub struct PendingTransaction {
+    /// Token mint the trade is for
+    pub mint: String,
+    /// Wallet that signed the transaction, used again for resubmits
+    pub wallet: WalletSigner,
+    /// Whether this was a buy or a sell
+    pub direction: SwapDirection,
+    /// Instructions handed to the sender, which adds the compute budget and tip again on a resubmit
+    pub instructions: Vec<Instruction>,
+    /// Signature of the most recent submission
+    pub signature: Signature,
+    /// Blockhash the most recent submission was built with
+    pub recent_blockhash: Hash,
+    /// When the most recent submission went out
+    pub submitted_at: Instant,
+    /// Last block height at which that blockhash is still accepted
+    pub last_valid_block_height: u64,
+    /// Number of submissions made so far (including the first one)
+    pub attempts: u32,
+    /// SOL spent (buy) or expected back (sell)
+    pub sol_amount: f64,
+    /// Tokens bought or sold, in base units
+    pub token_amount: u64,
+    /// Price per whole token in SOL at submission
+    pub price: f64,
+    /// Network and priority fee of one submission, in SOL
+    pub fee_sol: f64,
+    /// Jito tip sent with the transaction, in SOL
+    pub tip_sol: f64,
+    /// Path the transaction was submitted through ("spam" or a relay name), used again for resubmits
+    pub relay: String,
+    /// Fee boost the transaction was sent under, kept for resubmits
+    pub fee_boost: f64,
+    /// Whether a sell closes the whole position; partial sells settle back to `Bought`
+    pub full_exit: bool,
+}
+
+/// Final result of tracking a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmationOutcome {
+    /// Transaction landed successfully
+    Confirmed { signature: Signature, attempts: u32 },
+    /// Transaction landed but the program returned an error
+    Failed { signature: Signature, reason: String },
+    /// Transaction expired unlanded and all resubmits were used up
+    Abandoned { signature: Signature, attempts: u32 },
+}
+
+/// Status observed for a single signature within one wait window
+enum SignatureState {
+    /// Landed, in this slot
+    Landed(u64),
+    Errored(String),
+    /// Not seen landing within the wait window
+    Expired,
+}
+
+/// Watches submitted transactions and keeps the position state machine consistent
+///
+/// On confirmation the pool moves to `Bought`/`Sold`. On a program error or after
+/// the last resubmit expires, the pending state is rolled back so the token is not
+/// left stuck in `Buying`/`Selling`.
+///
+/// A transaction is only resubmitted once its blockhash has expired, and every earlier
+/// submission stays watched until then, so a slow landing never turns into a double buy
+/// or sell.
+pub struct ConfirmationTracker {
+    rpc_nonblocking_client: Arc<RpcClient>,
+    rpc_wss: String,
+    positions: Arc<PositionStore>,
+    settings: ConfirmationSettings,
+    logger: Logger,
+}
+
+impl ConfirmationTracker {
+    /// Create a new confirmation tracker
+    pub fn new(
+        rpc_nonblocking_client: Arc<RpcClient>,
+        positions: Arc<PositionStore>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            rpc_nonblocking_client,
+            rpc_wss: std::env::var("RPC_WSS").unwrap_or_default(),
+            positions,
+            settings: ConfirmationSettings::from_env(),
+            logger,
+        }
+    }
+
+    /// Track a pending transaction in the background
+    pub fn spawn(self: &Arc<Self>, pending: PendingTransaction) -> tokio::task::JoinHandle<ConfirmationOutcome> {
+        let tracker = self.clone();
+        tokio::spawn(async move { tracker.track(pending).await })
+    }
+
+    /// Wait for a pending transaction to land, resubmitting or rolling back as needed
+    pub async fn track(&self, mut pending: PendingTransaction) -> ConfirmationOutcome {
+        let start_time = Instant::now();
+        let mut submissions = vec![pending.signature];
+        loop {
+            let mut state = self.wait_for_signature(&pending.signature).await;
+            if matches!(state, SignatureState::Expired) {
+                match self.watched_status(&submissions).await {
+                    // an earlier submission is the one that landed or is landing
+                    Some((signature, status)) => {
+                        pending.signature = signature;
+                        match status {
+                            Some(status) => state = status,
+                            None => continue,
+                        }
+                    }
+                    None if !self.blockhash_expired(&pending).await => continue,
+                    None => {}
+                }
+            }
+
+            match state {
+                SignatureState::Landed(slot) => {
+                    tracing::info!(
+                        mint = %pending.mint,
+                        direction = ?pending.direction,
+                        signature = %pending.signature,
+                        attempts = pending.attempts,
+                        latency_ms = start_time.elapsed().as_millis() as u64,
+                        "transaction confirmed"
+                    );
+                    // charged before the sell PnL, which ends the strategy's hold of a closed position
+                    self.record_costs(&pending);
+                    if pending.direction == SwapDirection::Sell {
+                        self.record_realized_pnl(&pending);
+                    } else {
+                        self.resolve_creator(&pending.mint);
+                    }
+                    self.commit(&pending);
+                    if pending.direction == SwapDirection::Buy {
+                        self.record_copy_slot(&pending.mint, slot);
+                    }
+                    metrics().landing_latency_seconds.observe(start_time.elapsed().as_secs_f64());
+                    self.record_result(&pending, "confirmed", None, start_time);
+                    return ConfirmationOutcome::Confirmed {
+                        signature: pending.signature,
+                        attempts: pending.attempts,
+                    };
+                }
+                SignatureState::Errored(reason) => {
+                    tracing::error!(
+                        mint = %pending.mint,
+                        direction = ?pending.direction,
+                        signature = %pending.signature,
+                        reason = %reason,
+                        latency_ms = start_time.elapsed().as_millis() as u64,
+                        "transaction failed"
+                    );
+                    self.record_costs(&pending);
+                    self.rollback(&pending.mint, &pending.direction);
+                    if pending.direction == SwapDirection::Buy {
+                        slot_sync().copy_landed(&pending.mint, None);
+                    }
+                    self.record_result(&pending, "failed", Some(reason.clone()), start_time);
+                    return ConfirmationOutcome::Failed {
+                        signature: pending.signature,
+                        reason,
+                    };
+                }
+                // the blockhash of the latest submission, and so of every earlier one, has expired
+                SignatureState::Expired => {
+                    if pending.attempts > self.settings.max_resubmits {
+                        self.logger.log(format!(
+                            "[TX ABANDONED] => {} {:?} expired after {} attempts, rolling back",
+                            pending.mint, pending.direction, pending.attempts
+                        ).red().bold().to_string());
+                        self.rollback(&pending.mint, &pending.direction);
+                        if pending.direction == SwapDirection::Buy {
+                            slot_sync().copy_landed(&pending.mint, None);
+                        }
+                        self.record_result(&pending, "abandoned", None, start_time);
+                        return ConfirmationOutcome::Abandoned {
+                            signature: pending.signature,
+                            attempts: pending.attempts,
+                        };
+                    }
+
+                    match self.resubmit(&pending).await {
+                        Ok((signature, recent_blockhash, last_valid_block_height)) => {
+                            self.logger.log(format!(
+                                "[RESUBMIT] => {} {:?} {} -> {} (attempt {})",
+                                pending.mint, pending.direction, pending.signature, signature, pending.attempts + 1
+                            ).yellow().to_string());
+                            submissions.push(signature);
+                            pending.signature = signature;
+                            pending.recent_blockhash = recent_blockhash;
+                            pending.submitted_at = Instant::now();
+                            pending.last_valid_block_height = last_valid_block_height;
+                            pending.attempts += 1;
+                        }
+                        Err(e) => {
+                            self.logger.error(format!("Resubmit failed for {}: {}", pending.mint, e));
+                            pending.attempts += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait for one signature until it lands, errors or the timeout expires
+    async fn wait_for_signature(&self, signature: &Signature) -> SignatureState {
+        let timeout = Duration::from_millis(self.settings.timeout_ms);
+
+        if !self.rpc_wss.is_empty() {
+            match tokio::time::timeout(timeout, self.subscribe_signature(signature)).await {
+                Ok(Ok(state)) => return state,
+                Ok(Err(e)) => {
+                    self.logger.debug(format!("Signature subscription failed, falling back to polling: {}", e));
+                }
+                Err(_) => return self.final_status_check(signature).await,
+            }
+        }
+
+        self.poll_signature(signature, timeout).await
+    }
+
+    /// Watch a signature via the websocket `signatureSubscribe` notification
+    async fn subscribe_signature(&self, signature: &Signature) -> Result<SignatureState> {
+        let pubsub = PubsubClient::new(&self.rpc_wss).await?;
+        let (mut stream, unsubscribe) = pubsub
+            .signature_subscribe(
+                signature,
+                Some(RpcSignatureSubscribeConfig {
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    enable_received_notification: Some(false),
+                }),
+            )
+            .await?;
+
+        let state = match stream.next().await {
+            Some(response) => match response.value {
+                RpcSignatureResult::ProcessedSignature(result) => match result.err {
+                    Some(err) => SignatureState::Errored(err.to_string()),
+                    None => SignatureState::Landed(response.context.slot),
+                },
+                RpcSignatureResult::ReceivedSignature(_) => SignatureState::Expired,
+            },
+            None => return Err(anyhow!("signature subscription closed")),
+        };
+
+        drop(stream);
+        unsubscribe().await;
+        Ok(state)
+    }
+
+    /// Poll `getSignatureStatuses` until the signature lands or the timeout expires
+    async fn poll_signature(&self, signature: &Signature, timeout: Duration) -> SignatureState {
+        let start_time = Instant::now();
+        let interval = Duration::from_millis(self.settings.poll_interval_ms);
+
+        while start_time.elapsed() < timeout {
+            if let Some(state) = self.signature_status(signature).await {
+                return state;
+            }
+            tokio::time::sleep(interval).await;
+        }
+
+        self.final_status_check(signature).await
+    }
+
+    /// One last status lookup so a late landing is not mistaken for a drop
+    async fn final_status_check(&self, signature: &Signature) -> SignatureState {
+        self.signature_status(signature).await.unwrap_or(SignatureState::Expired)
+    }
+
+    async fn signature_status(&self, signature: &Signature) -> Option<SignatureState> {
+        let statuses = self.rpc_nonblocking_client
+            .get_signature_statuses(&[*signature])
+            .await
+            .ok()?;
+
+        let status = statuses.value.into_iter().next().flatten()?;
+        if let Some(err) = status.err {
+            return Some(SignatureState::Errored(err.to_string()));
+        }
+        if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+            return Some(SignatureState::Landed(status.slot));
+        }
+        None
+    }
+
+    /// First of `signatures` the cluster has seen, with its final state once it has one
+    async fn watched_status(&self, signatures: &[Signature]) -> Option<(Signature, Option<SignatureState>)> {
+        let statuses = self.rpc_nonblocking_client
+            .get_signature_statuses(signatures)
+            .await
+            .ok()?;
+
+        signatures.iter().zip(statuses.value).find_map(|(signature, status)| {
+            let status = status?;
+            let state = if let Some(err) = status.err {
+                Some(SignatureState::Errored(err.to_string()))
+            } else if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                Some(SignatureState::Landed(status.slot))
+            } else {
+                None
+            };
+            Some((*signature, state))
+        })
+    }
+
+    /// Whether the latest submission's blockhash can no longer land, so resending is safe
+    ///
+    /// Falls back to the block height when the validity check fails. A blockhash is past its
+    /// validity once `BLOCKHASH_DEADLINE` has passed since the submission, even when neither
+    /// can be read.
+    async fn blockhash_expired(&self, pending: &PendingTransaction) -> bool {
+        if pending.submitted_at.elapsed() >= BLOCKHASH_DEADLINE {
+            return true;
+        }
+        match self.rpc_nonblocking_client
+            .is_blockhash_valid(&pending.recent_blockhash, CommitmentConfig::processed())
+            .await
+        {
+            Ok(valid) => !valid,
+            Err(_) => match self.rpc_nonblocking_client.get_block_height().await {
+                Ok(height) => height > pending.last_valid_block_height,
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// Rebuild the transaction with a fresh blockhash and send it again through its path
+    async fn resubmit(&self, pending: &PendingTransaction) -> Result<(Signature, Hash, u64)> {
+        ensure_armed()?;
+        let (recent_blockhash, last_valid_block_height) = self.rpc_nonblocking_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        let sent = tx::with_fee_boost(pending.fee_boost, tx::new_signed_and_send_route(
+            &pending.relay,
+            &self.rpc_nonblocking_client,
+            recent_blockhash,
+            pending.wallet.as_ref(),
+            pending.instructions.clone(),
+            None,
+            &self.logger,
+        )).await?;
+
+        let signature = sent
+            .first()
+            .ok_or_else(|| anyhow!("Sender returned no signature"))?
+            .parse::<Signature>()?;
+        Ok((signature, recent_blockhash, last_valid_block_height))
+    }
+
+    /// Move a pool from its pending state to the settled state
+    fn commit(&self, pending: &PendingTransaction) {
+        // a sell may settle after another partial sell of the mint already returned it to `Bought`
+        let (from, to): (&[Status], Status) = match pending.direction {
+            SwapDirection::Buy => (&[Status::Buying], Status::Bought),
+            SwapDirection::Sell if pending.full_exit => (&[Status::Selling, Status::Bought], Status::Sold),
+            SwapDirection::Sell => (&[Status::Selling, Status::Bought], Status::Bought),
+        };
+        if !self.positions.transition(&pending.mint, from, to) {
+            self.logger.log(format!("No pending position of {} to settle as {:?}", pending.mint, to).yellow().to_string());
+        }
+    }
+
+    /// Charge the fee and tip of a landed transaction to its relay and strategy
+    ///
+    /// Alerts when the last day's fees and tips outweigh the configured share of gross profit.
+    fn record_costs(&self, pending: &PendingTransaction) {
+        strategy_ledger().record_costs(&pending.mint, pending.fee_sol, pending.tip_sol);
+        let alert = fee_ledger().record(&pending.relay, pending.fee_sol, pending.tip_sol);
+        let Some(message) = alert else {
+            return;
+        };
+        self.logger.log(format!("[FEES] => {}", message).red().bold().to_string());
+        if let Some((telegram, chat_id)) = fee_alerts() {
+            let logger = self.logger.clone();
+            tokio::spawn(async move {
+                if let Err(e) = telegram.send_message(&chat_id, &message, "HTML").await {
+                    logger.error(format!("Failed to send fee alert: {}", e));
+                }
+            });
+        }
+    }
+
+    /// Look up the creator of a bought token in the background, so a losing exit can cool it down
+    fn resolve_creator(&self, mint: &str) {
+        let known = {
+            let cooldowns = cooldowns();
+            cooldowns.settings().creator_loss_mins == 0 || cooldowns.creator_of(mint).is_some()
+        };
+        if known {
+            return;
+        }
+        let Ok(mint_pubkey) = mint.parse::<Pubkey>() else {
+            return;
+        };
+        let (rpc_client, logger, mint) = (self.rpc_nonblocking_client.clone(), self.logger.clone(), mint.to_string());
+        tokio::spawn(async move {
+            match get_token_creator(rpc_client, &mint_pubkey).await {
+                Ok(creator) => cooldowns().remember_creator(&mint, &creator.to_string()),
+                Err(e) => logger.debug(format!("Failed to resolve creator of {}: {}", mint, e)),
+            }
+        });
+    }
+
+    /// Feed the PnL of a confirmed sell to the circuit breaker and the strategy ledger
+    ///
+    /// The cost of the tokens sold is derived from the entry price, so partial
+    /// sells are counted proportionally.
+    fn record_realized_pnl(&self, pending: &PendingTransaction) {
+        let buy_price = self.positions.get(&pending.mint).map(|pool| pool.buy_price);
+        let Some(buy_price) = buy_price.filter(|price| *price > 0.0) else {
+            return;
+        };
+        if pending.price <= 0.0 {
+            return;
+        }
+
+        let pnl_sol = pending.sol_amount * (1.0 - buy_price / pending.price);
+        strategy_ledger().record_pnl(&pending.mint, pnl_sol, pending.full_exit);
+        fee_ledger().record_profit(pnl_sol);
+        metrics().realized_pnl_sol.add(pnl_sol);
+        let cooled_creator = cooldowns().record_sell(&pending.mint, pnl_sol, pending.full_exit, Instant::now());
+        if let Some(creator) = cooled_creator {
+            self.logger.log(format!(
+                "[COOLDOWN] => Not buying tokens of creator {} after a loss on {}",
+                creator, pending.mint
+            ).yellow().to_string());
+        }
+        if let Some(reason) = circuit_breaker().record_trade(pnl_sol) {
+            self.logger.log(format!(
+                "[CIRCUIT BREAKER] => Buying paused: {}",
+                reason
+            ).red().bold().to_string());
+        }
+    }
+
+    /// Undo the pending state after a failed or dropped transaction
+    ///
+    /// A failed buy removes the pool entirely since we never held the token. A failed
+    /// sell returns the pool to `Bought` so the exit logic will try again.
+    fn rollback(&self, mint: &str, direction: &SwapDirection) {
+        match direction {
+            SwapDirection::Buy => {
+                self.positions.remove(mint);
+            }
+            SwapDirection::Sell => {
+                self.positions.transition(mint, &[Status::Selling], Status::Bought);
+            }
+        }
+    }
+
+    /// Report how close a same-block copy landed to its target's trade
+    fn record_copy_slot(&self, mint: &str, slot: u64) {
+        let (distance, same_block_percent) = {
+            let mut sync = slot_sync();
+            (sync.copy_landed(mint, Some(slot)), sync.stats().same_block_percent())
+        };
+        let Some(distance) = distance else {
+            return;
+        };
+        let landed = match distance {
+            SlotDistance::Same => "in the target's block".to_string(),
+            SlotDistance::Next => "one block after the target".to_string(),
+            SlotDistance::Later(slots) => format!("{} blocks after the target", slots),
+        };
+        self.logger.log(format!(
+            "[SAME BLOCK] => Copy of {} landed {} ({:.0}% same-block so far)",
+            mint, landed, same_block_percent
+        ));
+    }
+
+    fn record_result(&self, pending: &PendingTransaction, result: &str, reason: Option<String>, start_time: Instant) {
+        let (counter, side, decision) = match pending.direction {
+            SwapDirection::Buy => (&metrics().buys, "buy", Decision::Buy),
+            SwapDirection::Sell => (&metrics().sells, "sell", Decision::Sell),
+        };
+        counter.with_label_values(&[result]).inc();
+        // read before a sell closes the mark
+        let entry_price = position_marks().get(&pending.mint).map(|mark| mark.entry_price).filter(|price| *price > 0.0);
+        if result == "confirmed" {
+            stream_health().trade_confirmed(Instant::now());
+            match pending.direction {
+                SwapDirection::Buy => position_marks().bought(&pending.mint, pending.sol_amount, pending.token_amount, pending.price, Instant::now()),
+                SwapDirection::Sell => position_marks().sold(&pending.mint, pending.token_amount, pending.full_exit),
+            }
+        }
+
+        if pending.direction == SwapDirection::Buy {
+            match result {
+                "confirmed" => latency_tracer().finish(&pending.mint),
+                // the transaction landed, so its fees were still paid
+                "failed" => {
+                    latency_tracer().discard(&pending.mint);
+                    budget_manager().refund_amount(&pending.mint);
+                    strategy_ledger().refund_amount(&pending.mint);
+                    cooldowns().mint_failed(&pending.mint, "a failed buy", Instant::now());
+                }
+                _ => {
+                    cooldowns().mint_failed(&pending.mint, "a dropped buy", Instant::now());
+                    latency_tracer().discard(&pending.mint);
+                    budget_manager().release(&pending.mint);
+                    strategy_ledger().release(&pending.mint);
+                }
+            }
+        }
+
+        let entry = TradeLogEntry {
+            timestamp: Utc::now(),
+            mint: pending.mint.clone(),
+            side: side.to_string(),
+            status: result.to_string(),
+            signature: pending.signature.to_string(),
+            sol_amount: pending.sol_amount,
+            token_amount: pending.token_amount,
+            price: pending.price,
+            // an abandoned transaction never landed, so it cost nothing
+            fee_sol: if result == "abandoned" { 0.0 } else { pending.fee_sol },
+            tip_sol: if result == "abandoned" { 0.0 } else { pending.tip_sol },
+            attempts: pending.attempts,
+            latency_ms: start_time.elapsed().as_millis() as u64,
+            reason,
+            reason_code: trade_reason(&pending.mint, decision),
+        };
+        // a closed or never opened position has no more trades to explain
+        match (result, decision) {
+            ("confirmed", Decision::Sell) if pending.full_exit => {
+                clear_trade_reason(&pending.mint, Decision::Buy);
+                clear_trade_reason(&pending.mint, Decision::Sell);
+            }
+            (result, Decision::Buy) if result != "confirmed" => clear_trade_reason(&pending.mint, Decision::Buy),
+            _ => {}
+        }
+        append_trade(&entry);
+        webhook().notify(WebhookEvent::from_trade(&entry));
+        let pnl_percent = match pending.direction {
+            SwapDirection::Sell => entry_price.map(|entry| (pending.price / entry - 1.0) * 100.0),
+            SwapDirection::Buy => None,
+        };
+        alert(Alert::trade(&entry, pnl_percent));
+    }
+}
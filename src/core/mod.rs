@@ -1,2 +1,5 @@
 pub mod token;
 pub mod tx;
+pub mod simulation;
+pub mod confirmation;
+pub mod position_store;
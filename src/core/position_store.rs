@@ -0,0 +1,221 @@
+use std::sync::RwLock;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+
+use crate::common::config::{LiquidityPool, Status};
+
+/// Positions keyed by mint, shared by the executor, confirmation tracker and exit systems
+///
+/// Entries live in a sharded map, so price updates and exits of different mints never
+/// contend, and status changes of one mint are atomic. Writers share a gate that
+/// `snapshot` takes exclusively, which makes snapshots consistent across mints.
+#[derive(Default)]
+pub struct PositionStore {
+    positions: DashMap<String, LiquidityPool>,
+    gate: RwLock<()>,
+}
+
+/// The mint is held or a trade of it is in flight
+fn is_active(pool: &LiquidityPool) -> bool {
+    pool.status != Status::Sold && pool.status != Status::Failure
+}
+
+impl PositionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn write<T>(&self, write: impl FnOnce(&DashMap<String, LiquidityPool>) -> T) -> T {
+        let _gate = self.gate.read().unwrap_or_else(|e| e.into_inner());
+        write(&self.positions)
+    }
+
+    pub fn get(&self, mint: &str) -> Option<LiquidityPool> {
+        self.positions.get(mint).map(|pool| pool.clone())
+    }
+
+    /// Store `pool`, replacing any entry of the same mint
+    pub fn insert(&self, pool: LiquidityPool) {
+        self.write(|positions| positions.insert(pool.mint.clone(), pool));
+    }
+
+    /// Store `pool` unless its mint is already held or being traded
+    pub fn try_open(&self, pool: LiquidityPool) -> bool {
+        self.write(|positions| match positions.entry(pool.mint.clone()) {
+            Entry::Occupied(entry) if is_active(entry.get()) => false,
+            Entry::Occupied(mut entry) => {
+                entry.insert(pool);
+                true
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(pool);
+                true
+            }
+        })
+    }
+
+    /// Apply `update` to the position of `mint`, returning whether it exists
+    pub fn update(&self, mint: &str, update: impl FnOnce(&mut LiquidityPool)) -> bool {
+        self.write(|positions| match positions.get_mut(mint) {
+            Some(mut pool) => {
+                update(&mut pool);
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Move `mint` to `to` only if its status is one of `from`
+    pub fn transition(&self, mint: &str, from: &[Status], to: Status) -> bool {
+        self.write(|positions| match positions.get_mut(mint) {
+            Some(mut pool) if from.contains(&pool.status) => {
+                pool.status = to;
+                true
+            }
+            _ => false,
+        })
+    }
+
+    pub fn remove(&self, mint: &str) -> Option<LiquidityPool> {
+        self.write(|positions| positions.remove(mint).map(|(_, pool)| pool))
+    }
+
+    pub fn extend(&self, pools: impl IntoIterator<Item = LiquidityPool>) {
+        self.write(|positions| {
+            for pool in pools {
+                positions.insert(pool.mint.clone(), pool);
+            }
+        });
+    }
+
+    /// The mint is held or a trade of it is in flight
+    pub fn is_active(&self, mint: &str) -> bool {
+        self.positions.get(mint).map(|pool| is_active(&pool)).unwrap_or(false)
+    }
+
+    /// Positions matching `filter`, without a consistent cut across mints
+    pub fn count(&self, filter: impl Fn(&LiquidityPool) -> bool) -> usize {
+        self.positions.iter().filter(|pool| filter(pool.value())).count()
+    }
+
+    /// Mints whose position has `status`
+    pub fn mints_with(&self, status: Status) -> Vec<String> {
+        self.positions
+            .iter()
+            .filter(|pool| pool.status == status)
+            .map(|pool| pool.key().clone())
+            .collect()
+    }
+
+    /// Every position at one point in time, sorted by mint, for reports and persistence
+    pub fn snapshot(&self) -> Vec<LiquidityPool> {
+        let _gate = self.gate.write().unwrap_or_else(|e| e.into_inner());
+        let mut pools: Vec<LiquidityPool> = self.positions.iter().map(|pool| pool.value().clone()).collect();
+        pools.sort_by(|a, b| a.mint.cmp(&b.mint));
+        pools
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+
+    fn pool(mint: &str, status: Status) -> LiquidityPool {
+        LiquidityPool {
+            mint: mint.to_string(),
+            buy_price: 1.0,
+            sell_price: 0.0,
+            status,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_try_open_refuses_active_mint() {
+        let store = PositionStore::new();
+        assert!(store.try_open(pool("a", Status::Buying)));
+        assert!(!store.try_open(pool("a", Status::Buying)));
+        assert!(store.is_active("a"));
+    }
+
+    #[test]
+    fn test_try_open_replaces_closed_position() {
+        let store = PositionStore::new();
+        store.insert(pool("b", Status::Sold));
+        assert!(store.try_open(pool("b", Status::Buying)));
+        assert_eq!(store.count(|pool| pool.status == Status::Buying), 1);
+    }
+
+    #[test]
+    fn test_transition_requires_a_listed_status() {
+        let store = PositionStore::new();
+        store.insert(pool("a", Status::Buying));
+        assert!(!store.transition("a", &[Status::Bought], Status::Selling));
+        assert!(store.transition("a", &[Status::Buying], Status::Bought));
+        assert_eq!(store.mints_with(Status::Bought), vec!["a".to_string()]);
+        assert!(!store.transition("c", &[Status::Bought], Status::Selling));
+    }
+
+    #[test]
+    fn test_transition_keeps_the_entry() {
+        let store = PositionStore::new();
+        let opened = tokio::time::Instant::now();
+        store.insert(LiquidityPool { timestamp: Some(opened), ..pool("a", Status::Bought) });
+        assert!(store.transition("a", &[Status::Bought], Status::Selling));
+        let pool = store.get("a").unwrap();
+        assert_eq!((pool.status, pool.timestamp, pool.buy_price), (Status::Selling, Some(opened), 1.0));
+    }
+
+    #[test]
+    fn test_transition_lets_one_racing_caller_win() {
+        let store = Arc::new(PositionStore::new());
+        store.insert(pool("a", Status::Bought));
+        let barrier = Arc::new(Barrier::new(8));
+        let racers: Vec<_> = (0..8)
+            .map(|_| {
+                let (store, barrier) = (store.clone(), barrier.clone());
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    store.transition("a", &[Status::Bought], Status::Selling)
+                })
+            })
+            .collect();
+        let winners = racers.into_iter().filter_map(|racer| racer.join().ok()).filter(|won| *won).count();
+        assert_eq!(winners, 1);
+    }
+
+    #[test]
+    fn test_update_only_touches_existing_positions() {
+        let store = PositionStore::new();
+        store.insert(pool("b", Status::Bought));
+        assert!(store.update("b", |pool| pool.buy_price = 2.0));
+        assert!(!store.update("c", |pool| pool.buy_price = 2.0));
+        assert_eq!(store.get("b").unwrap().buy_price, 2.0);
+    }
+
+    #[test]
+    fn test_snapshot_is_sorted_by_mint() {
+        let store = PositionStore::new();
+        store.insert(pool("b", Status::Bought));
+        store.insert(pool("a", Status::Buying));
+        let snapshot = store.snapshot();
+        assert_eq!(snapshot.iter().map(|pool| pool.mint.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_remove_returns_the_position() {
+        let store = PositionStore::new();
+        store.insert(pool("a", Status::Bought));
+        assert_eq!(store.remove("a").map(|pool| pool.mint), Some("a".to_string()));
+        assert!(store.is_empty());
+    }
+}
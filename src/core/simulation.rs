@@ -0,0 +1,114 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSimulateTransactionConfig;
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, transaction::VersionedTransaction};
+
+use crate::common::logger::Logger;
+use crate::services::metrics::metrics;
+
+/// Pre-send simulation settings
+#[derive(Debug, Clone)]
+pub struct SimulationSettings {
+    /// Run `simulateTransaction` on every built swap before it is submitted
+    pub enabled: bool,
+    /// Strategies whose buys go out unsimulated, to keep launch snipes at full speed
+    pub skip_strategies: Vec<String>,
+}
+
+impl SimulationSettings {
+    /// Load pre-send simulation settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("SIMULATE_BEFORE_SEND")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            skip_strategies: std::env::var("SIMULATE_SKIP_STRATEGIES")
+                .unwrap_or_else(|_| "snipe".to_string())
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether a buy for `strategy` is simulated first
+    pub fn simulates_buy(&self, strategy: &str) -> bool {
+        self.enabled && !self.skip_strategies.iter().any(|skipped| skipped.eq_ignore_ascii_case(strategy))
+    }
+
+    /// Whether sells and other wallet transactions are simulated first
+    pub fn simulates_sell(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// The lines of a simulation log that explain a failure: program errors and failed invocations
+pub fn failure_lines(logs: &[String]) -> Vec<&str> {
+    logs.iter()
+        .map(String::as_str)
+        .filter(|line| {
+            line.starts_with("Program log: Error")
+                || line.starts_with("Program log: AnchorError")
+                || line.contains(" failed: ")
+        })
+        .collect()
+}
+
+/// Simulate the signed transaction exactly as it will be sent, failing if it would not succeed
+///
+/// The program error and its log lines are logged, so predictably failing swaps are dropped
+/// before they pay fees or a tip.
+pub async fn simulate(rpc_client: &RpcClient, txn: &VersionedTransaction, logger: &Logger) -> Result<()> {
+    let response = rpc_client
+        .simulate_transaction_with_config(
+            txn,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: false,
+                commitment: Some(CommitmentConfig::processed()),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| anyhow!("Simulation request failed: {}", e))?;
+    let Some(err) = response.value.err else {
+        return Ok(());
+    };
+    let logs = response.value.logs.unwrap_or_default();
+    let lines = failure_lines(&logs);
+    logger.log(format!("[SIMULATION] => Aborting {}: {}", txn.signatures[0], err).red().to_string());
+    for line in &lines {
+        logger.log(format!("[SIMULATION] =>   {}", line).red().to_string());
+    }
+    metrics().simulation_aborts.inc();
+    match lines.last() {
+        Some(line) => Err(anyhow!("Simulation failed: {} ({})", err, line)),
+        None => Err(anyhow!("Simulation failed: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_and_failure_lines() {
+        let settings = SimulationSettings { enabled: true, skip_strategies: vec!["snipe".to_string()] };
+        assert!(!settings.simulates_buy("Snipe"));
+        assert!(settings.simulates_buy("copy"));
+        assert!(settings.simulates_sell());
+        let disabled = SimulationSettings { enabled: false, skip_strategies: Vec::new() };
+        assert!(!disabled.simulates_buy("copy"));
+
+        let logs = vec![
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]".to_string(),
+            "Program log: Instruction: Buy".to_string(),
+            "Program log: AnchorError thrown in programs/pump/src/lib.rs:639. Error Code: TooMuchSolRequired. Error Number: 6002.".to_string(),
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P failed: custom program error: 0x1772".to_string(),
+        ];
+        let lines = failure_lines(&logs);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("TooMuchSolRequired"));
+    }
+}
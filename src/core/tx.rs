@@ -2,53 +2,48 @@ use std::{sync::Arc, time::Duration};
 use std::{str::FromStr, env};
 use anyhow::Result;
 use colored::Colorize;
-use anchor_client::solana_client::rpc_client::RpcClient;
 use anchor_client::solana_sdk::{
-    hash::Hash,
     instruction::Instruction,
-    signature::Keypair,
     signer::Signer,
-    system_instruction, system_transaction,
-    transaction::{Transaction, VersionedTransaction},
+    system_instruction,
+    transaction::VersionedTransaction,
 };
 use spl_token::ui_amount_to_amount;
 
-use jito_json_rpc_client::jsonrpc_client::rpc_client::RpcClient as JitoRpcClient;
 use tokio::time::Instant;
 
-use crate::common::logger::Logger;
+use crate::common::{arming::ensure_armed, logger::Logger, signer::{signed_versioned_transaction, WalletSigner}};
+use crate::core::simulation;
 use crate::{
+    engine::relay_selector::{Relay, RelaySelector},
     services::{
-        jito::{self, JitoClient},
-        nozomi,
+        bloxroute::{self, BloxrouteClient},
+        jito::{self, BundleBuilder, BundleOutcome, JitoClient},
+        jito_leaders,
+        lookup_table::lookup_tables,
+        nozomi::{self, NozomiClient},
+        spam::{spam_sender, SpamSender},
+        tpu,
         zeroslot::{self, ZeroSlotClient},
     },
 };
 
-pub async fn jito_confirm(
-    client: &RpcClient,
-    keypair: &Keypair,
-    version_tx: VersionedTransaction,
-    recent_block_hash: &Hash,
-    logger: &Logger,
-) -> Result<Vec<String>> {
-   
-      
-}
-
+/// Sign and send through Jito, simulating the signed transaction first when `simulate_with` is given
 pub async fn new_signed_and_send(
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
-    keypair: &Keypair,
+    keypair: &dyn Signer,
     mut instructions: Vec<Instruction>,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    ensure_armed()?;
     let start_time = Instant::now();
 
     let mut txs = vec![];
     let (tip_account, tip1_account) = jito::get_tip_account()?;
 
     // jito tip, the upper limit is 0.1
-    let tip = jito::get_tip_value().await?;
+    let tip = boosted(jito::get_tip_value().await?);
     let fee = jito::get_priority_fee().await?;
     let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
     let fee_lamports = ui_amount_to_amount(fee, spl_token::native_mint::DECIMALS);
@@ -78,26 +73,14 @@ pub async fn new_signed_and_send(
     // instructions.push(jito_tip2_instruction);
 
     // send init tx
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
-        recent_blockhash,
-    );
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
 
-    // let simulate_result = client.simulate_transaction(&txn)?;
-    // logger.log("Tx Stimulate".to_string());
-    // if let Some(logs) = simulate_result.value.logs {
-    //     for log in logs {
-    //         logger.log(log.to_string());
-    //     }
-    // }
-    // if let Some(err) = simulate_result.value.err {
-    //     return Err(anyhow::anyhow!("{}", err));
-    // };
+    if let Some(rpc_client) = simulate_with {
+        simulation::simulate(rpc_client, &txn, logger).await?;
+    }
 
     let jito_client = Arc::new(JitoClient::new(
-        format!("{}/api/v1/transactions", *jito::BLOCK_ENGINE_URL).as_str(),
+        format!("{}/api/v1/transactions", jito_leaders::block_engine_url()).as_str(),
     ));
     let sig = match jito_client.send_transaction(&txn).await {
         Ok(signature) => signature,
@@ -121,17 +104,18 @@ pub async fn new_signed_and_send(
 
 pub async fn new_signed_and_send_zeroslot(
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
-    keypair: &Keypair,
+    keypair: &dyn Signer,
     mut instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    ensure_armed()?;
     let start_time = Instant::now();
 
     let mut txs = vec![];
     let tip_account = zeroslot::get_tip_account()?;
 
     // zeroslot tip, the upper limit is 0.1
-    let tip = zeroslot::get_tip_value().await?;
+    let tip = boosted(zeroslot::get_tip_value().await?);
     let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
 
     let zeroslot_tip_instruction =
@@ -139,12 +123,7 @@ pub async fn new_signed_and_send_zeroslot(
     instructions.insert(0, zeroslot_tip_instruction);
 
     // send init tx
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
-        recent_blockhash,
-    );
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
 
     // let simulate_result = client.simulate_transaction(&txn)?;
     // logger.log("Tx Stimulate".to_string());
@@ -177,12 +156,56 @@ pub async fn new_signed_and_send_zeroslot(
     Ok(txs)
 }
 
+/// Sign a v0 transaction, compiled against the wallet's lookup table once one is loaded
+pub fn build_transaction(
+    instructions: &[Instruction],
+    keypair: &dyn Signer,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+) -> Result<VersionedTransaction> {
+    signed_versioned_transaction(instructions, keypair, recent_blockhash, &lookup_tables())
+}
+
 // prioritization fee = UNIT_PRICE * UNIT_LIMIT
+tokio::task_local! {
+    /// Multiplier on the priority fee and tips of transactions sent within `with_fee_boost`
+    static FEE_BOOST: f64;
+}
+
+/// Run `future` with its transactions' priority fee and tips multiplied by `multiplier`
+///
+/// Scoped to the task, so trades sent elsewhere at the same time keep the configured fees.
+pub async fn with_fee_boost<F: std::future::Future>(multiplier: f64, future: F) -> F::Output {
+    FEE_BOOST.scope(multiplier.max(1.0), future).await
+}
+
+/// Multiplier in effect for the current task, 1 outside `with_fee_boost`
+pub fn fee_boost() -> f64 {
+    FEE_BOOST.try_with(|boost| *boost).unwrap_or(1.0)
+}
+
+/// Spawn `future` on its own task under the fee boost of the calling task
+///
+/// A task-local does not follow `tokio::spawn`, so the boost is read here and scoped again.
+fn spawn_boosted<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let boost = fee_boost();
+    tokio::task::spawn(FEE_BOOST.scope(boost, future))
+}
+
+/// `tip` raised by the fee boost in effect
+fn boosted(tip: f64) -> f64 {
+    tip * fee_boost()
+}
+
 fn get_unit_price() -> u64 {
-    env::var("UNIT_PRICE")
+    let unit_price = env::var("UNIT_PRICE")
         .ok()
         .and_then(|v| u64::from_str(&v).ok())
-        .unwrap_or(20000)
+        .unwrap_or(20000);
+    (unit_price as f64 * fee_boost()) as u64
 }
 
 fn get_unit_limit() -> u32 {
@@ -192,26 +215,60 @@ fn get_unit_limit() -> u32 {
         .unwrap_or(200_000)
 }
 
+/// Base fee plus the configured priority fee for one transaction, in lamports
+pub fn estimated_fee_lamports() -> u64 {
+    // unit price is in micro-lamports per compute unit
+    5_000 + get_unit_price() * get_unit_limit() as u64 / 1_000_000
+}
+
 pub async fn new_signed_and_send_nozomi(
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
-    keypair: &Keypair,
+    keypair: &dyn Signer,
     mut instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    ensure_armed()?;
     let start_time = Instant::now();
 
- 
+    let mut txs = vec![];
+    let tip_account = nozomi::get_tip_account()?;
+
+    // nozomi tip, the upper limit is 0.1
+    let tip = boosted(nozomi::get_tip_value().await?);
+    let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
+
+    let nozomi_tip_instruction =
+        system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
+    instructions.insert(0, nozomi_tip_instruction);
+
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
+
+    let nozomi_client = Arc::new(NozomiClient::new((*nozomi::NOZOMI_URL).as_str()));
+    let sig = match nozomi_client.send_transaction(&txn).await {
+        Ok(signature) => signature,
+        Err(e) => {
+            return Err(anyhow::anyhow!(format!("Nozomi send failed: {}", e)
+                .red()
+                .italic()
+                .to_string()));
+        }
+    };
+    txs.push(sig.to_string());
+    logger.log(
+        format!("[TXN-ELLAPSED(NOZOMI)]: {:?}", start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
 
     Ok(txs)
 }
 
 pub async fn new_signed_and_send_spam(
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
-    keypair: &std::sync::Arc<Keypair>,
+    keypair: &WalletSigner,
     instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
-    // Assuming keypair is already defined as Arc<Keypair>
     let logger_clone = logger.clone();
     let keypair_clone = Arc::clone(&keypair); // Clone the Arc for the first future
 
@@ -226,39 +283,59 @@ pub async fn new_signed_and_send_spam(
     let instructions_clone_for_nozomi = instructions.clone();
     let instructions_clone_for_zeroslot = instructions.clone();
 
-    // Create the futures for both transaction sending methods
-    let jito_future = tokio::task::spawn(async move {
-        new_signed_and_send(
-            recent_blockhash,
-            &keypair_clone,
-            instructions_clone_for_jito,
-            &logger_clone,
-        )
-        .await
-    });
 
-    let nozomi_future = tokio::task::spawn(async move {
+    let nozomi_future = spawn_boosted(async move {
         new_signed_and_send_nozomi(
             recent_blockhash,
-            &keypair_clone1,
+            keypair_clone1.as_ref(),
             instructions_clone_for_nozomi,
             &logger_clone1,
         )
         .await
     });
 
-    let zeroslot_future = tokio::task::spawn(async move {
+    let zeroslot_future = spawn_boosted(async move {
         new_signed_and_send_zeroslot(
             recent_blockhash,
-            &keypair_clone2,
+            keypair_clone2.as_ref(),
             instructions_clone_for_zeroslot,
             &logger_clone2,
         )
         .await
     });
 
-    // Await both futures
-    let results = futures::future::join_all(vec![jito_future, nozomi_future, zeroslot_future]).await;
+    let mut futures = vec![nozomi_future, zeroslot_future];
+    // Jito only races while a Jito validator is about to lead, otherwise the tip buys nothing
+    if jito_leaders::jito_leader_upcoming() {
+        futures.push(spawn_boosted(async move {
+            new_signed_and_send(
+                recent_blockhash,
+                keypair_clone.as_ref(),
+                instructions_clone_for_jito,
+                None,
+                &logger_clone,
+            )
+            .await
+        }));
+    }
+    // the TPU path joins the race once its client is connected
+    if tpu::is_running() {
+        let logger_clone3 = logger.clone();
+        let keypair_clone3 = Arc::clone(&keypair);
+        futures.push(spawn_boosted(async move {
+            new_signed_and_send_tpu(
+                recent_blockhash,
+                keypair_clone3.as_ref(),
+                instructions,
+                None,
+                &logger_clone3,
+            )
+            .await
+        }));
+    }
+
+    // Await all futures
+    let results = futures::future::join_all(futures).await;
 
     let mut successful_results = Vec::new();
     let mut errors: Vec<String> = Vec::new();
@@ -285,3 +362,384 @@ pub async fn new_signed_and_send_spam(
 
     Ok(successful_results)
 }
+
+/// Sign once and broadcast through every spam-send path, simulating first when `simulate_with` is given
+pub async fn new_signed_and_send_broadcast(
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    mut instructions: Vec<Instruction>,
+    spam_sender: &SpamSender,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    ensure_armed()?;
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+
+    let modify_compute_units =
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(
+            unit_limit,
+        );
+    let add_priority_fee =
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+            unit_price,
+        );
+    instructions.insert(0, modify_compute_units);
+    instructions.insert(1, add_priority_fee);
+
+    // sign once so every path carries the same signature
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
+    if let Some(rpc_client) = simulate_with {
+        simulation::simulate(rpc_client, &txn, logger).await?;
+    }
+
+    let (signature, results) = spam_sender.broadcast(&txn).await?;
+    for result in results.iter().filter(|r| !r.accepted) {
+        logger.debug(format!(
+            "Spam path {} rejected {}: {}",
+            result.path,
+            signature,
+            result.error.clone().unwrap_or_default()
+        ));
+    }
+
+    Ok(vec![signature.to_string()])
+}
+
+/// Send `instructions` as a Jito bundle with a trailing tip transaction
+///
+/// With `simulate_with` the swap is simulated first, unless a setup transaction has to run
+/// ahead of it. Returns once the block engine accepts the bundle: its outcome is logged in
+/// the background, and the confirmation tracker settles the trade from the signatures.
+pub async fn new_signed_and_send_bundle(
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    setup_instructions: Option<Vec<Instruction>>,
+    mut instructions: Vec<Instruction>,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    ensure_armed()?;
+    let start_time = Instant::now();
+
+    let tip = boosted(jito::get_tip_value().await?);
+    let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    instructions.insert(
+        0,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(
+            unit_limit,
+        ),
+    );
+    instructions.insert(
+        1,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+            unit_price,
+        ),
+    );
+
+    // ata create (optional) -> swap -> tip, all or nothing
+    let has_setup = setup_instructions.as_ref().is_some_and(|setup| !setup.is_empty());
+    let bundle = BundleBuilder::new(keypair, recent_blockhash)
+        .with_setup(setup_instructions.unwrap_or_default())
+        .add_transaction(instructions)
+        .with_tip(tip_lamports)
+        .build()?;
+    let signatures: Vec<String> = bundle
+        .iter()
+        .map(|txn| txn.signatures[0].to_string())
+        .collect();
+    if let (Some(rpc_client), false) = (simulate_with, has_setup) {
+        simulation::simulate(rpc_client, &bundle[0], logger).await?;
+    }
+
+    let jito_client = JitoClient::new(
+        format!("{}/api/v1/bundles", jito_leaders::block_engine_url()).as_str(),
+    );
+    let bundle_id = jito_client
+        .send_bundle(&bundle)
+        .await
+        .map_err(|e| anyhow::anyhow!("Bundle submission failed: {}", e))?;
+    logger.log(
+        format!("[BUNDLE-SENT(JITO)]: {} ({} txs) in {:?}", bundle_id, bundle.len(), start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+
+    let (watched, logger) = (signatures.clone(), logger.clone());
+    tokio::spawn(async move {
+        match jito_client
+            .wait_for_bundle_outcome(
+                &bundle_id,
+                watched,
+                Duration::from_millis(500),
+                Duration::from_secs(30),
+                &logger,
+            )
+            .await
+        {
+            BundleOutcome::Landed { slot, .. } => logger.log(
+                format!("[BUNDLE-LANDED(JITO)]: {} at slot {} in {:?}", bundle_id, slot, start_time.elapsed())
+                    .green()
+                    .to_string(),
+            ),
+            BundleOutcome::Failed { reason, .. } => logger.error(format!("Bundle {} failed: {}", bundle_id, reason)),
+            // it may still land before its blockhash expires, which the confirmation tracker watches for
+            BundleOutcome::TimedOut { .. } => logger.log(
+                format!("[BUNDLE-PENDING(JITO)]: {} has no final status after {:?}", bundle_id, start_time.elapsed())
+                    .yellow()
+                    .to_string(),
+            ),
+        }
+    });
+
+    Ok(signatures)
+}
+
+pub async fn new_signed_and_send_bloxroute(
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    mut instructions: Vec<Instruction>,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    ensure_armed()?;
+    let start_time = Instant::now();
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    instructions.insert(
+        0,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(
+            unit_limit,
+        ),
+    );
+    instructions.insert(
+        1,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+            unit_price,
+        ),
+    );
+
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
+
+    let bloxroute_client = BloxrouteClient::new(&bloxroute::BLOXROUTE_URL, &bloxroute::BLOXROUTE_AUTH_HEADER);
+    let sig = bloxroute_client.send_transaction(&txn).await?;
+    logger.log(
+        format!("[TXN-ELLAPSED(BLOXROUTE)]: {:?}", start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+
+    Ok(vec![sig.to_string()])
+}
+
+/// Sign and push straight to the current and upcoming leaders over QUIC, skipping RPC `sendTransaction`
+pub async fn new_signed_and_send_tpu(
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    mut instructions: Vec<Instruction>,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    ensure_armed()?;
+    let start_time = Instant::now();
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    instructions.insert(
+        0,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(
+            unit_limit,
+        ),
+    );
+    instructions.insert(
+        1,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+            unit_price,
+        ),
+    );
+
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
+    if let Some(rpc_client) = simulate_with {
+        simulation::simulate(rpc_client, &txn, logger).await?;
+    }
+    tpu::send_transaction(&txn).await?;
+    logger.log(
+        format!("[TXN-ELLAPSED(TPU)]: {:?}", start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+
+    Ok(vec![txn.signatures[0].to_string()])
+}
+
+/// Sign and send over RPC, simulating the signed transaction first when `simulate` is set
+pub async fn new_signed_and_send_rpc(
+    rpc_nonblocking_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    mut instructions: Vec<Instruction>,
+    simulate: bool,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    ensure_armed()?;
+    let start_time = Instant::now();
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    instructions.insert(
+        0,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(
+            unit_limit,
+        ),
+    );
+    instructions.insert(
+        1,
+        anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(
+            unit_price,
+        ),
+    );
+
+    let txn = build_transaction(&instructions, keypair, recent_blockhash)?;
+    // preflight is skipped on send, so a requested simulation is the only check
+    if simulate {
+        simulation::simulate(rpc_nonblocking_client, &txn, logger).await?;
+    }
+
+    let sig = rpc_nonblocking_client
+        .send_transaction_with_config(
+            &txn,
+            anchor_client::solana_client::rpc_config::RpcSendTransactionConfig {
+                skip_preflight: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+    logger.log(
+        format!("[TXN-ELLAPSED(RPC)]: {:?}", start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+
+    Ok(vec![sig.to_string()])
+}
+
+/// Send through the relay picked by the selector, returning the relay along with the result
+///
+/// With `simulate_with` the instructions are simulated before they go to a relay that cannot
+/// simulate its own signed transaction.
+pub async fn new_signed_and_send_selected(
+    relay_selector: &std::sync::Mutex<RelaySelector>,
+    rpc_nonblocking_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    instructions: Vec<Instruction>,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    logger: &Logger,
+) -> (Relay, Result<Vec<String>>) {
+    let relay = relay_selector
+        .lock()
+        .map(|mut selector| selector.select())
+        .unwrap_or(Relay::Rpc);
+    let relay = if relay == Relay::Jito && !jito_leaders::jito_leader_upcoming() {
+        logger.debug("No Jito leader in the next slots, sending over RPC instead".to_string());
+        Relay::Rpc
+    } else {
+        relay
+    };
+
+    if let (Some(rpc_client), Relay::ZeroSlot | Relay::Nozomi | Relay::BloXroute) = (simulate_with, relay) {
+        let simulated = match build_transaction(&instructions, keypair, recent_blockhash) {
+            Ok(txn) => simulation::simulate(rpc_client, &txn, logger).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = simulated {
+            return (relay, Err(e));
+        }
+    }
+
+    let result = new_signed_and_send_route(&relay.to_string(), rpc_nonblocking_client, recent_blockhash, keypair, instructions, simulate_with, logger).await;
+    (relay, result)
+}
+
+/// Send through one path: "spam" or a relay name, with anything else going over RPC
+///
+/// A resubmit goes out the same way as the first send, so it carries the same compute
+/// budget, priority fee and tip.
+pub async fn new_signed_and_send_route(
+    route: &str,
+    rpc_nonblocking_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &dyn Signer,
+    instructions: Vec<Instruction>,
+    simulate_with: Option<&anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    if route == "spam" {
+        let spam_sender = spam_sender().ok_or_else(|| anyhow::anyhow!("Spam-send is not running (set SPAM_SEND_ENABLED=true)"))?;
+        return new_signed_and_send_broadcast(recent_blockhash, keypair, instructions, &spam_sender, simulate_with, logger).await;
+    }
+    match route.parse().unwrap_or(Relay::Rpc) {
+        Relay::Jito => new_signed_and_send_bundle(recent_blockhash, keypair, None, instructions, simulate_with, logger).await,
+        Relay::ZeroSlot => new_signed_and_send_zeroslot(recent_blockhash, keypair, instructions, logger).await,
+        Relay::Nozomi => new_signed_and_send_nozomi(recent_blockhash, keypair, instructions, logger).await,
+        Relay::BloXroute => new_signed_and_send_bloxroute(recent_blockhash, keypair, instructions, logger).await,
+        Relay::Tpu => new_signed_and_send_tpu(recent_blockhash, keypair, instructions, simulate_with, logger).await,
+        Relay::Rpc => new_signed_and_send_rpc(rpc_nonblocking_client, recent_blockhash, keypair, instructions, simulate_with.is_some(), logger).await,
+    }
+}
+
+/// Tip in SOL a transaction sent through `relay` pays, raised by the fee boost in effect
+pub async fn relay_tip_sol(relay: &str) -> f64 {
+    let tip = match relay {
+        "jito" => jito::get_tip_value().await,
+        "zeroslot" => zeroslot::get_tip_value().await,
+        "nozomi" => nozomi::get_tip_value().await,
+        _ => return 0.0,
+    };
+    boosted(tip.unwrap_or(0.0))
+}
+
+/// Poll a signature until it is confirmed, returning the slot it landed in
+pub async fn wait_for_landed_slot(
+    rpc_nonblocking_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    signature: &anchor_client::solana_sdk::signature::Signature,
+    timeout: Duration,
+) -> Option<u64> {
+    let start_time = Instant::now();
+    while start_time.elapsed() < timeout {
+        if let Ok(statuses) = rpc_nonblocking_client.get_signature_statuses(&[*signature]).await {
+            if let Some(status) = statuses.value.into_iter().next().flatten() {
+                if status.err.is_some() {
+                    return None;
+                }
+                if status.satisfies_commitment(anchor_client::solana_sdk::commitment_config::CommitmentConfig::confirmed()) {
+                    return Some(status.slot);
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(400)).await;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fee_boost_survives_spawned_sends() {
+        assert_eq!(fee_boost(), 1.0);
+        let (spawned, plain) = with_fee_boost(4.0, async {
+            let spawned = spawn_boosted(async { fee_boost() }).await.unwrap();
+            let plain = tokio::task::spawn(async { fee_boost() }).await.unwrap();
+            (spawned, plain)
+        }).await;
+        assert_eq!(spawned, 4.0);
+        // what a bare spawn would have sent at
+        assert_eq!(plain, 1.0);
+        assert_eq!(spawn_boosted(async { boosted(0.001) }).await.unwrap(), 0.001);
+    }
+}
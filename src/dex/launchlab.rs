@@ -0,0 +1,519 @@
+//! Raydium LaunchLab bonding curves, the program behind letsbonk.fun
+//!
+//! Curves are quoted in WSOL and trade through `buy_exact_in`/`sell_exact_in`;
+//! a curve that finished fundraising migrates to Raydium and is no longer traded here.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
+
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::decoder::{program_instructions, EVENT_IX_TAG};
+use crate::dex::pump_fun::{BondingCurveReserves, TradeEvent, TEN_THOUSAND, TRADE_EVENT_DISCRIMINATOR};
+use crate::dex::reader::Reader;
+use crate::engine::swap::{min_out, Dex, SwapDirection};
+
+/// Anchor instruction discriminator of `buy_exact_in`
+pub const BUY_EXACT_IN_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+/// Anchor instruction discriminator of `sell_exact_in`
+pub const SELL_EXACT_IN_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+/// Anchor instruction discriminator of `initialize`
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+/// Anchor instruction discriminator of `initialize_v2`
+pub const INITIALIZE_V2_DISCRIMINATOR: [u8; 8] = [67, 153, 175, 39, 218, 16, 38, 32];
+/// Anchor account discriminator of a LaunchLab `PoolState`
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+
+/// Pool status while the curve is still raising funds and open to trades
+const STATUS_FUNDRAISING: u8 = 0;
+
+/// A LaunchLab curve with the state needed to quote and build swaps
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchLabPool {
+    pub address: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    /// 0 while fundraising, then migrating and migrated
+    pub status: u8,
+    /// Protocol and platform fees taken from the quote side, in basis points
+    pub fee_bps: u64,
+}
+
+/// A token launched on LaunchLab, decoded from its `initialize` instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchLabLaunch {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: Pubkey,
+    pub platform_config: Pubkey,
+    /// SOL the creator spent buying in the launch transaction, in lamports
+    pub dev_buy_lamports: u64,
+}
+
+/// Curve of `base_mint` against WSOL
+pub fn pool_address(base_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"pool", base_mint.as_ref(), spl_token::native_mint::ID.as_ref()],
+        &program_ids().launchlab_program,
+    ).0
+}
+
+fn vault_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"vault_auth_seed"], &program_ids().launchlab_program).0
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &program_ids().launchlab_program).0
+}
+
+/// Pool state fields the bot trades on; later fields added by the program are ignored
+fn parse_pool_state(address: Pubkey, data: &[u8]) -> Option<LaunchLabPool> {
+    let mut reader = Reader::new(data.strip_prefix(&POOL_STATE_DISCRIMINATOR[..])?);
+    let _epoch = reader.u64()?;
+    let _auth_bump = reader.u8()?;
+    let status = reader.u8()?;
+    // base decimals, quote decimals, migrate type
+    reader.take(3)?;
+    let _supply = reader.u64()?;
+    let _total_base_sell = reader.u64()?;
+    let virtual_base = reader.u64()?;
+    let virtual_quote = reader.u64()?;
+    let real_base = reader.u64()?;
+    let real_quote = reader.u64()?;
+    // fundraising target, protocol, platform and migrate fees, vesting schedule
+    reader.take(8 * 4 + 8 * 5)?;
+    Some(LaunchLabPool {
+        address,
+        global_config: reader.pubkey()?,
+        platform_config: reader.pubkey()?,
+        base_mint: reader.pubkey()?,
+        quote_mint: reader.pubkey()?,
+        base_vault: reader.pubkey()?,
+        quote_vault: reader.pubkey()?,
+        creator: reader.pubkey()?,
+        virtual_base,
+        virtual_quote,
+        real_base,
+        real_quote,
+        status,
+        fee_bps: 0,
+    })
+}
+
+impl LaunchLabPool {
+    /// Load the WSOL curve of `mint`, charging `fee_bps` on quotes
+    pub async fn fetch(
+        rpc_client: &Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+        mint: &Pubkey,
+        fee_bps: u64,
+    ) -> Result<Self> {
+        let address = pool_address(mint);
+        let account = rpc_client
+            .get_account(&address)
+            .await
+            .map_err(|_| anyhow!("No LaunchLab curve for {}", mint))?;
+        let mut pool = parse_pool_state(address, &account.data)
+            .ok_or_else(|| anyhow!("Failed to decode LaunchLab curve {}", address))?;
+        pool.fee_bps = fee_bps;
+        Ok(pool)
+    }
+
+    /// The curve is still open to trades
+    pub fn is_trading(&self) -> bool {
+        self.status == STATUS_FUNDRAISING
+    }
+
+    /// Effective constant-product reserves in the shape the price helpers take
+    pub fn reserves(&self) -> BondingCurveReserves {
+        BondingCurveReserves {
+            virtual_token_reserves: self.virtual_base.saturating_sub(self.real_base),
+            virtual_sol_reserves: self.virtual_quote + self.real_quote,
+        }
+    }
+
+    /// Tokens received for spending `quote_in`, fees included
+    pub fn quote_buy(&self, quote_in: u64) -> u64 {
+        let reserves = self.reserves();
+        let effective = quote_in as u128 - quote_in as u128 * self.fee_bps as u128 / TEN_THOUSAND as u128;
+        (reserves.virtual_token_reserves as u128 * effective
+            / (reserves.virtual_sol_reserves as u128 + effective)) as u64
+    }
+
+    /// Quote received for selling `base_in`, after fees
+    pub fn quote_sell(&self, base_in: u64) -> u64 {
+        let reserves = self.reserves();
+        let gross = reserves.virtual_sol_reserves as u128 * base_in as u128
+            / (reserves.virtual_token_reserves as u128 + base_in as u128);
+        (gross - gross * self.fee_bps as u128 / TEN_THOUSAND as u128) as u64
+    }
+
+    fn swap_instruction(&self, discriminator: [u8; 8], user: &Pubkey, amount_in: u64, minimum_amount_out: u64) -> Instruction {
+        let ids = program_ids();
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        // no share fee is paid to a referrer
+        data.extend_from_slice(&0u64.to_le_bytes());
+        Instruction {
+            program_id: ids.launchlab_program,
+            accounts: vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new_readonly(vault_authority(), false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.platform_config, false),
+                AccountMeta::new(self.address, false),
+                AccountMeta::new(get_associated_token_address(user, &self.base_mint), false),
+                AccountMeta::new(get_associated_token_address(user, &self.quote_mint), false),
+                AccountMeta::new(self.base_vault, false),
+                AccountMeta::new(self.quote_vault, false),
+                AccountMeta::new_readonly(self.base_mint, false),
+                AccountMeta::new_readonly(self.quote_mint, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(event_authority(), false),
+                AccountMeta::new_readonly(ids.launchlab_program, false),
+            ],
+            data,
+        }
+    }
+
+    /// LaunchLab buy spending exactly `quote_in`, receiving at least `min_base_out`
+    pub fn buy_instruction(&self, user: &Pubkey, quote_in: u64, min_base_out: u64) -> Instruction {
+        self.swap_instruction(BUY_EXACT_IN_DISCRIMINATOR, user, quote_in, min_base_out)
+    }
+
+    /// LaunchLab sell of exactly `base_in`, receiving at least `min_quote_out`
+    pub fn sell_instruction(&self, user: &Pubkey, base_in: u64, min_quote_out: u64) -> Instruction {
+        self.swap_instruction(SELL_EXACT_IN_DISCRIMINATOR, user, base_in, min_quote_out)
+    }
+
+    /// Full buy: wrap the SOL spent, swap, and close the WSOL account
+    pub fn buy_instructions(&self, user: &Pubkey, quote_in: u64, min_base_out: u64) -> Result<Vec<Instruction>> {
+        let wsol = get_associated_token_address(user, &self.quote_mint);
+        Ok(vec![
+            create_associated_token_account_idempotent(user, user, &self.base_mint, &spl_token::ID),
+            create_associated_token_account_idempotent(user, user, &self.quote_mint, &spl_token::ID),
+            system_instruction::transfer(user, &wsol, quote_in),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol)?,
+            self.buy_instruction(user, quote_in, min_base_out),
+            spl_token::instruction::close_account(&spl_token::ID, &wsol, user, user, &[user])?,
+        ])
+    }
+
+    /// Full sell: receive WSOL and close the account so the proceeds arrive as native SOL
+    pub fn sell_instructions(&self, user: &Pubkey, base_in: u64, min_quote_out: u64) -> Result<Vec<Instruction>> {
+        let wsol = get_associated_token_address(user, &self.quote_mint);
+        Ok(vec![
+            create_associated_token_account_idempotent(user, user, &self.quote_mint, &spl_token::ID),
+            self.sell_instruction(user, base_in, min_quote_out),
+            spl_token::instruction::close_account(&spl_token::ID, &wsol, user, user, &[user])?,
+        ])
+    }
+}
+
+impl Dex for LaunchLabPool {
+    fn name(&self) -> &'static str {
+        "Raydium LaunchLab"
+    }
+
+    fn address(&self) -> Pubkey {
+        self.address
+    }
+
+    fn reserves(&self) -> BondingCurveReserves {
+        LaunchLabPool::reserves(self)
+    }
+
+    fn quote(&self, direction: SwapDirection, amount_in: u64) -> u64 {
+        match direction {
+            SwapDirection::Buy => self.quote_buy(amount_in),
+            SwapDirection::Sell => self.quote_sell(amount_in),
+        }
+    }
+
+    /// LaunchLab spends exactly `sol_in`, so slippage bounds the tokens received instead
+    fn buy_args(&self, sol_in: u64, slippage_bps: u64) -> (u64, u64) {
+        (sol_in, min_out(self.quote_buy(sol_in), slippage_bps))
+    }
+
+    fn build_buy(&self, user: &Pubkey, sol_in: u64, slippage_bps: u64) -> Result<(u64, Vec<Instruction>)> {
+        let (_, min_tokens_out) = self.buy_args(sol_in, slippage_bps);
+        Ok((self.quote_buy(sol_in), self.buy_instructions(user, sol_in, min_tokens_out)?))
+    }
+
+    fn build_sell(&self, user: &Pubkey, token_amount: u64, min_sol_out: u64) -> Result<Vec<Instruction>> {
+        self.sell_instructions(user, token_amount, min_sol_out)
+    }
+
+    fn decode_event(&self, info: &SubscribeUpdateTransactionInfo) -> Vec<TradeEvent> {
+        trades(info, &self.address, &self.base_mint)
+    }
+}
+
+/// Decode a LaunchLab `TradeEvent` into a trade of `mint`; later fields added by the program are ignored
+///
+/// The event carries no trader and no timestamp, those are filled in by the caller.
+fn parse_trade_event(data: &[u8], pool: &Pubkey, mint: &Pubkey) -> Option<TradeEvent> {
+    let mut reader = Reader::new(data.strip_prefix(&TRADE_EVENT_DISCRIMINATOR[..])?);
+    if reader.pubkey()? != *pool {
+        return None;
+    }
+    let _total_base_sell = reader.u64()?;
+    let virtual_base = reader.u64()?;
+    let virtual_quote = reader.u64()?;
+    // real base and quote before the trade
+    reader.take(16)?;
+    let real_base_after = reader.u64()?;
+    let real_quote_after = reader.u64()?;
+    let amount_in = reader.u64()?;
+    let amount_out = reader.u64()?;
+    // protocol, platform and share fees
+    reader.take(24)?;
+    let is_buy = reader.u8()? == 0;
+    let (sol_amount, token_amount) = if is_buy { (amount_in, amount_out) } else { (amount_out, amount_in) };
+    Some(TradeEvent {
+        mint: *mint,
+        sol_amount,
+        token_amount,
+        is_buy,
+        user: Pubkey::default(),
+        timestamp: 0,
+        virtual_sol_reserves: virtual_quote + real_quote_after,
+        virtual_token_reserves: virtual_base.saturating_sub(real_base_after),
+    })
+}
+
+/// Swaps on the curve `pool` of `mint` in a streamed transaction, in the shape of pump.fun trades
+///
+/// Each event is paired with the swap instruction before it for the trader.
+pub fn trades(info: &SubscribeUpdateTransactionInfo, pool: &Pubkey, mint: &Pubkey) -> Vec<TradeEvent> {
+    let mut trades = Vec::new();
+    let mut trader = None;
+    // payer, authority, global config, platform config, pool state, ...
+    for (accounts, data) in program_instructions(info, &program_ids().launchlab_program) {
+        let discriminator = data.get(..8);
+        if discriminator == Some(&BUY_EXACT_IN_DISCRIMINATOR[..]) || discriminator == Some(&SELL_EXACT_IN_DISCRIMINATOR[..]) {
+            trader = (accounts.len() > 4 && accounts[4] == *pool).then(|| accounts[0]);
+            continue;
+        }
+        let event = data.strip_prefix(&EVENT_IX_TAG[..]).and_then(|event| parse_trade_event(event, pool, mint));
+        if let (Some(mut trade), Some(user)) = (event, trader.take()) {
+            trade.user = user;
+            trades.push(trade);
+        }
+    }
+    trades
+}
+
+/// WSOL-quoted LaunchLab launches in a streamed transaction, with the creator's buy in the same transaction
+pub fn launches(info: &SubscribeUpdateTransactionInfo) -> Vec<LaunchLabLaunch> {
+    let instructions = program_instructions(info, &program_ids().launchlab_program);
+    let mut launches = Vec::new();
+    for (accounts, data) in &instructions {
+        let Some(discriminator) = data.get(..8) else {
+            continue;
+        };
+        if discriminator != INITIALIZE_DISCRIMINATOR && discriminator != INITIALIZE_V2_DISCRIMINATOR {
+            continue;
+        }
+        // payer, creator, global config, platform config, authority, pool state, base mint, quote mint, ...
+        if accounts.len() < 8 || accounts[7] != spl_token::native_mint::ID {
+            continue;
+        }
+        // mint params: decimals, name, symbol, uri
+        let mut reader = Reader::new(&data[8..]);
+        let metadata = reader.u8().and_then(|_| Some((reader.string()?, reader.string()?, reader.string()?)));
+        let Some((name, symbol, uri)) = metadata else {
+            continue;
+        };
+        launches.push(LaunchLabLaunch {
+            mint: accounts[6],
+            pool: accounts[5],
+            name,
+            symbol,
+            uri,
+            creator: accounts[1],
+            platform_config: accounts[3],
+            dev_buy_lamports: 0,
+        });
+    }
+
+    // payer, authority, global config, platform config, pool state, ...
+    for (accounts, data) in &instructions {
+        if data.get(..8) != Some(&BUY_EXACT_IN_DISCRIMINATOR[..]) || accounts.len() < 5 {
+            continue;
+        }
+        let Some(amount_in) = Reader::new(&data[8..]).u64() else {
+            continue;
+        };
+        if let Some(launch) = launches.iter_mut().find(|launch| launch.pool == accounts[4] && launch.creator == accounts[0]) {
+            launch.dev_buy_lamports += amount_in;
+        }
+    }
+    launches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{CompiledInstruction, Message, Transaction};
+
+    fn pool_state(status: u8, virtual_base: u64, virtual_quote: u64, real_base: u64, real_quote: u64, base_mint: &Pubkey) -> Vec<u8> {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&[255, status, 6, 9, 0]);
+        for value in [1_000_000u64, 800_000, virtual_base, virtual_quote, real_base, real_quote] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0u8; 8 * 9]);
+        for key in [Pubkey::new_unique(), Pubkey::new_unique(), *base_mint, spl_token::native_mint::ID] {
+            data.extend_from_slice(key.as_ref());
+        }
+        for _ in 0..3 {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn test_pool_state_decoding_and_quotes() {
+        let base_mint = Pubkey::new_unique();
+        let data = pool_state(0, 1_100_000, 900_000, 100_000, 100_000, &base_mint);
+        let mut pool = parse_pool_state(pool_address(&base_mint), &data).unwrap();
+        assert!(pool.is_trading());
+        assert_eq!(pool.base_mint, base_mint);
+        assert_eq!(pool.reserves(), BondingCurveReserves { virtual_token_reserves: 1_000_000, virtual_sol_reserves: 1_000_000 });
+
+        assert_eq!(pool.quote_buy(1_000), 999);
+        pool.fee_bps = 125;
+        assert_eq!(pool.quote_buy(101_266), 90_909);
+        assert_eq!(pool.quote_sell(100_000), 89_773);
+
+        let buy = pool.buy_instruction(&Pubkey::new_unique(), 10, 20);
+        assert_eq!(buy.accounts.len(), 15);
+        assert_eq!(&buy.data[..8], &BUY_EXACT_IN_DISCRIMINATOR);
+
+        let migrated = parse_pool_state(Pubkey::new_unique(), &pool_state(2, 1, 1, 0, 0, &base_mint)).unwrap();
+        assert!(!migrated.is_trading());
+    }
+
+    #[test]
+    fn test_trade_event_decoded_with_trader() {
+        let ids = program_ids();
+        let (user, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = pool_state(0, 1_100_000, 900_000, 100_000, 100_000, &mint);
+        let pool = parse_pool_state(pool_address(&mint), &data).unwrap();
+        let keys = [user, vault_authority(), ids.launchlab_global_config, ids.launchlab_platform_config, pool.address, ids.launchlab_program];
+
+        let mut buy = BUY_EXACT_IN_DISCRIMINATOR.to_vec();
+        for value in [1_000u64, 900, 0] {
+            buy.extend_from_slice(&value.to_le_bytes());
+        }
+        let mut event = EVENT_IX_TAG.to_vec();
+        event.extend_from_slice(&TRADE_EVENT_DISCRIMINATOR);
+        event.extend_from_slice(pool.address.as_ref());
+        for value in [800_000u64, 1_100_000, 900_000, 100_000, 100_000, 100_999, 101_000, 1_000, 999, 0, 0, 0] {
+            event.extend_from_slice(&value.to_le_bytes());
+        }
+        event.extend_from_slice(&[0, 0]);
+
+        let info = SubscribeUpdateTransactionInfo {
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    account_keys: keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![
+                        CompiledInstruction { program_id_index: 5, accounts: vec![0, 1, 2, 3, 4], data: buy },
+                        CompiledInstruction { program_id_index: 5, accounts: vec![], data: event },
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let trades = pool.decode_event(&info);
+        assert_eq!(trades.len(), 1);
+        assert_eq!((trades[0].user, trades[0].mint, trades[0].is_buy), (user, mint, true));
+        assert_eq!((trades[0].sol_amount, trades[0].token_amount), (1_000, 999));
+        assert_eq!(trades[0].virtual_token_reserves, 999_001);
+        // another pool's curve sees nothing
+        let other = LaunchLabPool { address: Pubkey::new_unique(), ..pool };
+        assert!(other.decode_event(&info).is_empty());
+    }
+
+    #[test]
+    fn test_launch_decoded_with_creator_buy() {
+        let ids = program_ids();
+        let (creator, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool = pool_address(&mint);
+        let keys = [
+            creator,
+            ids.launchlab_global_config,
+            ids.launchlab_platform_config,
+            vault_authority(),
+            pool,
+            mint,
+            spl_token::native_mint::ID,
+            ids.launchlab_program,
+        ];
+
+        let mut initialize = INITIALIZE_DISCRIMINATOR.to_vec();
+        initialize.push(6);
+        for text in ["Bonk Dog", "BDOG", "https://example.com/bdog.json"] {
+            initialize.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            initialize.extend_from_slice(text.as_bytes());
+        }
+        let mut buy = BUY_EXACT_IN_DISCRIMINATOR.to_vec();
+        for value in [2_000_000_000u64, 0, 0] {
+            buy.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let info = SubscribeUpdateTransactionInfo {
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    account_keys: keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![
+                        CompiledInstruction { program_id_index: 7, accounts: vec![0, 0, 1, 2, 3, 4, 5, 6], data: initialize },
+                        CompiledInstruction { program_id_index: 7, accounts: vec![0, 3, 1, 2, 4], data: buy },
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(launches(&info), vec![LaunchLabLaunch {
+            mint,
+            pool,
+            name: "Bonk Dog".to_string(),
+            symbol: "BDOG".to_string(),
+            uri: "https://example.com/bdog.json".to_string(),
+            creator,
+            platform_config: ids.launchlab_platform_config,
+            dev_buy_lamports: 2_000_000_000,
+        }]);
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// A platform new tokens are launched on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Launchpad {
+    /// pump.fun bonding curves
+    PumpFun,
+    /// Raydium LaunchLab curves of letsbonk.fun
+    LaunchLab,
+}
+
+impl Launchpad {
+    /// Parse a launchpad name, accepting the platform and program names
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "pumpfun" | "pump.fun" | "pump" => Some(Launchpad::PumpFun),
+            "launchlab" | "letsbonk" | "letsbonk.fun" | "bonk" => Some(Launchpad::LaunchLab),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Launchpad::PumpFun => "pump.fun",
+            Launchpad::LaunchLab => "letsbonk.fun",
+        }
+    }
+}
+
+/// Parse a launchpad list like `pumpfun,letsbonk`
+pub fn parse_launchpads(value: &str) -> Result<Vec<Launchpad>, String> {
+    let mut launchpads = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let launchpad = Launchpad::parse(name).ok_or_else(|| format!("Unknown launchpad '{}'", name))?;
+        if !launchpads.contains(&launchpad) {
+            launchpads.push(launchpad);
+        }
+    }
+    Ok(launchpads)
+}
+
+/// Launchpad settings
+#[derive(Debug, Clone)]
+pub struct LaunchpadSettings {
+    /// Platforms whose launches are sniped
+    pub launchpads: Vec<Launchpad>,
+    /// Protocol and platform fees charged by LaunchLab curves, in basis points
+    pub launchlab_fee_bps: u64,
+}
+
+impl LaunchpadSettings {
+    /// Load launchpad settings from environment variables
+    pub fn from_env() -> Self {
+        let launchpads = std::env::var("LAUNCHPADS").unwrap_or_else(|_| "pumpfun".to_string());
+        Self {
+            launchpads: parse_launchpads(&launchpads).unwrap_or_else(|e| {
+                eprintln!("Ignoring LAUNCHPADS: {}", e);
+                vec![Launchpad::PumpFun]
+            }),
+            launchlab_fee_bps: std::env::var("LAUNCHLAB_FEE_BPS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(125),
+        }
+    }
+
+    pub fn is_enabled(&self, launchpad: Launchpad) -> bool {
+        self.launchpads.contains(&launchpad)
+    }
+}
+
+static LAUNCHED_ON: LazyLock<Mutex<HashMap<String, Launchpad>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Record where a detected launch happened, so its trades go through the right program
+pub fn remember_launch(mint: &str, launchpad: Launchpad) {
+    LAUNCHED_ON.lock().unwrap_or_else(|e| e.into_inner()).insert(mint.to_string(), launchpad);
+}
+
+/// Launchpad of a mint seen launching since startup
+pub fn launchpad_of(mint: &str) -> Option<Launchpad> {
+    LAUNCHED_ON.lock().unwrap_or_else(|e| e.into_inner()).get(mint).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launchpads() {
+        assert_eq!(parse_launchpads("pumpfun, letsbonk,pump").unwrap(), vec![Launchpad::PumpFun, Launchpad::LaunchLab]);
+        assert_eq!(parse_launchpads("").unwrap(), Vec::new());
+        assert!(parse_launchpads("pumpfun,moonshot").is_err());
+    }
+}
@@ -1 +1,6 @@
 pub mod pump_fun;
+pub mod pump_swap;
+pub mod launchlab;
+pub mod launchpad;
+pub mod program_ids;
+pub(crate) mod reader;
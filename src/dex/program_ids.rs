@@ -0,0 +1,147 @@
+use std::str::FromStr;
+use std::sync::LazyLock;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use colored::Colorize;
+
+use crate::common::network::NetworkEnv;
+use crate::dex::pump_fun::{PUMP_ACCOUNT, PUMP_FEE_RECIPIENT, PUMP_GLOBAL, PUMP_PROGRAM};
+
+pub const PUMP_SWAP_PROGRAM: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+pub const PUMP_SWAP_GLOBAL_CONFIG: &str = "ADyA8hdefvWN2dbGGWFotbzWxrAvLW83WG6QCVXvJKqw";
+pub const PUMP_SWAP_EVENT_AUTHORITY: &str = "GS4CU59F31iL7aR2Q8zVS8DRrcRnXX1yjQ66TqNVQnaR";
+pub const RAYDIUM_AMM_V4_PROGRAM: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+pub const RAYDIUM_AMM_AUTHORITY: &str = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+pub const LAUNCHLAB_PROGRAM: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+pub const LAUNCHLAB_GLOBAL_CONFIG: &str = "6s1xP3hpbAfFoNtUNF8mfHsjr2Bd97JxFJRWLbL6aHuX";
+pub const LETSBONK_PLATFORM_CONFIG: &str = "FfYek5vEz23cMkWsdJwG2oa6EphsvXSHrGpdALN4g6W1";
+
+/// Program IDs and well-known accounts for every supported platform
+///
+/// Each value defaults to the mainnet address and can be overridden from the
+/// environment, so a program migration or a devnet/localnet fork only needs a
+/// config change instead of a rebuild.
+#[derive(Debug, Clone)]
+pub struct ProgramIds {
+    /// Pump.fun bonding curve program
+    pub pump_fun_program: Pubkey,
+    /// Pump.fun global state account
+    pub pump_fun_global: Pubkey,
+    /// Pump.fun protocol fee recipient
+    pub pump_fun_fee_recipient: Pubkey,
+    /// Pump.fun event authority PDA
+    pub pump_fun_event_authority: Pubkey,
+    /// PumpSwap AMM program
+    pub pump_swap_program: Pubkey,
+    /// PumpSwap global config account
+    pub pump_swap_global_config: Pubkey,
+    /// PumpSwap event authority PDA
+    pub pump_swap_event_authority: Pubkey,
+    /// Raydium AMM v4 program
+    pub raydium_amm_program: Pubkey,
+    /// Raydium AMM v4 authority
+    pub raydium_amm_authority: Pubkey,
+    /// Raydium LaunchLab bonding curve program
+    pub launchlab_program: Pubkey,
+    /// LaunchLab global config for SOL-quoted curves
+    pub launchlab_global_config: Pubkey,
+    /// LaunchLab platform config of letsbonk.fun
+    pub launchlab_platform_config: Pubkey,
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        Self {
+            pump_fun_program: Pubkey::from_str(PUMP_PROGRAM).unwrap(),
+            pump_fun_global: Pubkey::from_str(PUMP_GLOBAL).unwrap(),
+            pump_fun_fee_recipient: Pubkey::from_str(PUMP_FEE_RECIPIENT).unwrap(),
+            pump_fun_event_authority: Pubkey::from_str(PUMP_ACCOUNT).unwrap(),
+            pump_swap_program: Pubkey::from_str(PUMP_SWAP_PROGRAM).unwrap(),
+            pump_swap_global_config: Pubkey::from_str(PUMP_SWAP_GLOBAL_CONFIG).unwrap(),
+            pump_swap_event_authority: Pubkey::from_str(PUMP_SWAP_EVENT_AUTHORITY).unwrap(),
+            raydium_amm_program: Pubkey::from_str(RAYDIUM_AMM_V4_PROGRAM).unwrap(),
+            raydium_amm_authority: Pubkey::from_str(RAYDIUM_AMM_AUTHORITY).unwrap(),
+            launchlab_program: Pubkey::from_str(LAUNCHLAB_PROGRAM).unwrap(),
+            launchlab_global_config: Pubkey::from_str(LAUNCHLAB_GLOBAL_CONFIG).unwrap(),
+            launchlab_platform_config: Pubkey::from_str(LETSBONK_PLATFORM_CONFIG).unwrap(),
+        }
+    }
+}
+
+impl ProgramIds {
+    /// Load program IDs from environment variables, falling back to mainnet defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            pump_fun_program: parse_pubkey_env("PUMP_FUN_PROGRAM_ID", defaults.pump_fun_program),
+            pump_fun_global: parse_pubkey_env("PUMP_FUN_GLOBAL", defaults.pump_fun_global),
+            pump_fun_fee_recipient: parse_pubkey_env("PUMP_FUN_FEE_RECIPIENT", defaults.pump_fun_fee_recipient),
+            pump_fun_event_authority: parse_pubkey_env("PUMP_FUN_EVENT_AUTHORITY", defaults.pump_fun_event_authority),
+            pump_swap_program: parse_pubkey_env("PUMP_SWAP_PROGRAM_ID", defaults.pump_swap_program),
+            pump_swap_global_config: parse_pubkey_env("PUMP_SWAP_GLOBAL_CONFIG", defaults.pump_swap_global_config),
+            pump_swap_event_authority: parse_pubkey_env("PUMP_SWAP_EVENT_AUTHORITY", defaults.pump_swap_event_authority),
+            raydium_amm_program: parse_pubkey_env("RAYDIUM_AMM_PROGRAM_ID", defaults.raydium_amm_program),
+            raydium_amm_authority: parse_pubkey_env("RAYDIUM_AMM_AUTHORITY", defaults.raydium_amm_authority),
+            launchlab_program: parse_pubkey_env("LAUNCHLAB_PROGRAM_ID", defaults.launchlab_program),
+            launchlab_global_config: parse_pubkey_env("LAUNCHLAB_GLOBAL_CONFIG", defaults.launchlab_global_config),
+            launchlab_platform_config: parse_pubkey_env("LAUNCHLAB_PLATFORM_CONFIG", defaults.launchlab_platform_config),
+        }
+    }
+
+    /// Whether any value differs from the mainnet defaults
+    pub fn is_overridden(&self) -> bool {
+        let defaults = Self::default();
+        self.pump_fun_program != defaults.pump_fun_program
+            || self.pump_fun_global != defaults.pump_fun_global
+            || self.pump_fun_fee_recipient != defaults.pump_fun_fee_recipient
+            || self.pump_fun_event_authority != defaults.pump_fun_event_authority
+            || self.pump_swap_program != defaults.pump_swap_program
+            || self.pump_swap_global_config != defaults.pump_swap_global_config
+            || self.pump_swap_event_authority != defaults.pump_swap_event_authority
+            || self.raydium_amm_program != defaults.raydium_amm_program
+            || self.raydium_amm_authority != defaults.raydium_amm_authority
+            || self.launchlab_program != defaults.launchlab_program
+            || self.launchlab_global_config != defaults.launchlab_global_config
+            || self.launchlab_platform_config != defaults.launchlab_platform_config
+    }
+
+    /// Bonding curve PDA for a mint under the configured pump.fun program
+    pub fn bonding_curve_pda(&self, mint: &Pubkey) -> Pubkey {
+        let seeds = [b"bonding-curve".as_ref(), mint.as_ref()];
+        Pubkey::find_program_address(&seeds, &self.pump_fun_program).0
+    }
+}
+
+/// Program IDs resolved once at startup
+pub static PROGRAM_IDS: LazyLock<ProgramIds> = LazyLock::new(|| {
+    let ids = ProgramIds::from_env();
+    if ids.is_overridden() {
+        println!("{}", format!("⚠️  Using overridden program IDs: {:?}", ids).yellow());
+    }
+    ids
+});
+
+/// Get the program IDs in effect for this process
+pub fn program_ids() -> &'static ProgramIds {
+    &PROGRAM_IDS
+}
+
+/// Parse a pubkey from the environment, keeping the default on a missing or invalid value
+///
+/// Off mainnet a `DEVNET_`/`LOCALNET_` prefixed key wins over the plain one.
+fn parse_pubkey_env(key: &str, default: Pubkey) -> Pubkey {
+    let prefixed = NetworkEnv::from_env().env_prefix().map(|prefix| format!("{}{}", prefix, key));
+    let key = match prefixed {
+        Some(prefixed) if std::env::var(&prefixed).map(|v| !v.trim().is_empty()).unwrap_or(false) => prefixed,
+        _ => key.to_string(),
+    };
+    match std::env::var(&key) {
+        Ok(value) if !value.trim().is_empty() => match Pubkey::from_str(value.trim()) {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                eprintln!("{}", format!("⚠️  Invalid pubkey in {}: {} ({}), using default", key, value, e).red());
+                default
+            }
+        },
+        _ => default,
+    }
+}
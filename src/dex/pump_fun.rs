@@ -1,30 +1,36 @@
-use std::{str::FromStr, sync::Arc, time::Duration};
+use std::{collections::HashMap, str::FromStr, sync::{Arc, LazyLock, Mutex}, time::Duration};
 
 use anyhow::{anyhow, Result};
-use borsh::from_slice;
+use borsh::BorshDeserialize as _;
 use borsh_derive::{BorshDeserialize, BorshSerialize};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use anchor_client::solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    signature::Keypair,
     signer::Signer,
     system_program,
 };
 use spl_associated_token_account::{
-    get_associated_token_address, instruction::create_associated_token_account,
+    get_associated_token_address,
+    instruction::create_associated_token_account_idempotent,
 };
 use spl_token::{amount_to_ui_amount, ui_amount_to_amount};
 use spl_token_client::token::TokenError;
 use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
 
 use crate::{
-    common::{config::SwapConfig, logger::Logger},
+    common::{config::SwapConfig, logger::Logger, signer::WalletSigner},
     core::token,
-    engine::{monitor::BondingCurveInfo, swap::{SwapDirection, SwapInType}},
+    dex::program_ids::program_ids,
+    engine::{monitor::BondingCurveInfo, swap::{max_in, Dex, SwapDirection, SwapInType}},
 };
 
+pub mod decoder;
+
+pub use decoder::{parse_trade_event, trade_events, PumpEventKind, TradeEvent, TRADE_EVENT_DISCRIMINATOR};
+
 pub const TEN_THOUSAND: u64 = 10000;
 pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 pub const RENT_PROGRAM: &str = "SysvarRent111111111111111111111111111111111";
@@ -32,7 +38,8 @@ pub const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTN
 pub const PUMP_GLOBAL: &str = "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf";
 pub const PUMP_FEE_RECIPIENT: &str = "CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM";
 pub const PUMP_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
-// pub const PUMP_FUN_MINT_AUTHORITY: &str = "TSLvdd1pWpHVjahSpsvCXUbgwsL3JAcvokwaKt1eokM";
+/// Mint authority of every pump.fun token; only `create` instructions reference it
+pub const PUMP_FUN_MINT_AUTHORITY: &str = "TSLvdd1pWpHVjahSpsvCXUbgwsL3JAcvokwaKt1eokM";
 pub const PUMP_ACCOUNT: &str = "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1";
 pub const PUMP_BUY_METHOD: u64 = 16927863322537952870;
 pub const PUMP_SELL_METHOD: u64 = 12502976635542562355;
@@ -40,11 +47,32 @@ pub const PUMP_FUN_CREATE_IX_DISCRIMINATOR: &[u8] = &[24, 30, 200, 40, 5, 28, 7,
 pub const INITIAL_VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
 pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
 pub const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+/// Tokens sold off the curve before it completes and migrates
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+/// On-chain layout of a pump.fun bonding curve account
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct BondingCurveAccount {
+    pub discriminator: u64,
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+}
+
+/// Virtual reserves used for price and quote calculations
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BondingCurveReserves {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+}
 
 #[derive(Clone)]
 pub struct Pump {
     pub rpc_nonblocking_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
-    pub keypair: Arc<Keypair>,
+    pub keypair: WalletSigner,
     pub rpc_client: Option<Arc<anchor_client::solana_client::rpc_client::RpcClient>>,
 }
 
@@ -52,7 +80,7 @@ impl Pump {
     pub fn new(
         rpc_nonblocking_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
         rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
-        keypair: Arc<Keypair>,
+        keypair: WalletSigner,
     ) -> Self {
         Self {
             rpc_nonblocking_client,
@@ -70,6 +98,40 @@ pub async fn get_bonding_curve_account(
 ) -> Result<(Pubkey, Pubkey, BondingCurveReserves)> {
     let bonding_curve = get_pda(&mint, &program_id)?;
     let associated_bonding_curve = get_associated_token_address(&bonding_curve, &mint);
+    let bonding_curve_account = read_bonding_curve(&rpc_client, &bonding_curve).await?;
+    let bonding_curve_reserves = BondingCurveReserves 
+        { 
+            virtual_token_reserves: bonding_curve_account.virtual_token_reserves, 
+            virtual_sol_reserves: bonding_curve_account.virtual_sol_reserves 
+        };
+    Ok((
+        bonding_curve,
+        associated_bonding_curve,
+        bonding_curve_reserves,
+    ))
+}
+
+/// Reserves of the token's bonding curve and whether it has completed
+pub async fn get_bonding_curve_state(
+    rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
+    mint: Pubkey,
+    program_id: Pubkey,
+) -> Result<(BondingCurveReserves, bool)> {
+    let account = read_bonding_curve(&rpc_client, &get_pda(&mint, &program_id)?).await?;
+    Ok((
+        BondingCurveReserves {
+            virtual_token_reserves: account.virtual_token_reserves,
+            virtual_sol_reserves: account.virtual_sol_reserves,
+        },
+        account.complete,
+    ))
+}
+
+/// Read a bonding curve account, retrying briefly since it may not be visible right after launch
+async fn read_bonding_curve(
+    rpc_client: &anchor_client::solana_client::rpc_client::RpcClient,
+    bonding_curve: &Pubkey,
+) -> Result<BondingCurveAccount> {
     let start_time = Instant::now();
     // println!("Start: {:?}", start_time.elapsed());
 
@@ -78,7 +140,7 @@ pub async fn get_bonding_curve_account(
     let timeout = Duration::from_millis(time_exceed);
     let mut retry_count = 0;
     let bonding_curve_data = loop {
-        match rpc_client.get_account_data(&bonding_curve) {
+        match rpc_client.get_account_data(bonding_curve) {
             Ok(data) => {
                 // println!("Done: {:?}", start_time.elapsed());
                 break data;
@@ -105,23 +167,203 @@ pub async fn get_bonding_curve_account(
         }
     };
 
-    let bonding_curve_account =
-        from_slice::<BondingCurveAccount>(&bonding_curve_data).map_err(|e| {
-            anyhow!(
-                "Failed to deserialize bonding curve account: {}",
-                e.to_string()
-            )
-        })?;
-    let bonding_curve_reserves = BondingCurveReserves 
-        { 
-            virtual_token_reserves: bonding_curve_account.virtual_token_reserves, 
-            virtual_sol_reserves: bonding_curve_account.virtual_sol_reserves 
+    // the account is padded past the fields we read, so don't require an exact length
+    BondingCurveAccount::deserialize(&mut &bonding_curve_data[..]).map_err(|e| {
+        anyhow!(
+            "Failed to deserialize bonding curve account: {}",
+            e.to_string()
+        )
+    })
+}
+
+/// Whether the token's bonding curve has completed and the token migrated to PumpSwap
+pub fn is_bonding_curve_complete(
+    rpc_client: &anchor_client::solana_client::rpc_client::RpcClient,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<bool> {
+    let bonding_curve = get_pda(mint, program_id)?;
+    let data = rpc_client
+        .get_account_data(&bonding_curve)
+        .map_err(|e| anyhow!("No pump.fun bonding curve for {}: {}", mint, e))?;
+    let account = BondingCurveAccount::deserialize(&mut &data[..])
+        .map_err(|e| anyhow!("Failed to deserialize bonding curve account: {}", e))?;
+    Ok(account.complete)
+}
+
+/// Build a pump.fun buy instruction for an exact token amount
+pub fn buy_instruction(
+    user: &Pubkey,
+    mint: &Pubkey,
+    creator: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+) -> Result<Instruction> {
+    let mut data = PUMP_BUY_METHOD.to_le_bytes().to_vec();
+    data.extend_from_slice(&token_amount.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: program_ids().pump_fun_program,
+        accounts: swap_accounts(user, mint, creator, true)?,
+        data,
+    })
+}
+
+/// Build a pump.fun sell instruction for an exact token amount
+pub fn sell_instruction(
+    user: &Pubkey,
+    mint: &Pubkey,
+    creator: &Pubkey,
+    token_amount: u64,
+    min_sol_output: u64,
+) -> Result<Instruction> {
+    let mut data = PUMP_SELL_METHOD.to_le_bytes().to_vec();
+    data.extend_from_slice(&token_amount.to_le_bytes());
+    data.extend_from_slice(&min_sol_output.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: program_ids().pump_fun_program,
+        accounts: swap_accounts(user, mint, creator, false)?,
+        data,
+    })
+}
+
+/// Vault collecting the creator fee of every trade of `creator`'s tokens
+pub fn creator_vault_pda(creator: &Pubkey) -> Pubkey {
+    let seeds = [b"creator-vault".as_ref(), creator.as_ref()];
+    Pubkey::find_program_address(&seeds, &program_ids().pump_fun_program).0
+}
+
+/// Account list shared by the buy and sell instructions
+///
+/// Both end with the token's creator vault next to the token program, in opposite
+/// order: buys pass the token program first, sells the creator vault.
+fn swap_accounts(user: &Pubkey, mint: &Pubkey, creator: &Pubkey, is_buy: bool) -> Result<Vec<AccountMeta>> {
+    let ids = program_ids();
+    let bonding_curve = get_pda(mint, &ids.pump_fun_program)?;
+    let associated_bonding_curve = get_associated_token_address(&bonding_curve, mint);
+    let associated_user = get_associated_token_address(user, mint);
+    let token_program = AccountMeta::new_readonly(Pubkey::from_str(TOKEN_PROGRAM)?, false);
+    let creator_vault = AccountMeta::new(creator_vault_pda(creator), false);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(ids.pump_fun_global, false),
+        AccountMeta::new(ids.pump_fun_fee_recipient, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(bonding_curve, false),
+        AccountMeta::new(associated_bonding_curve, false),
+        AccountMeta::new(associated_user, false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if is_buy {
+        accounts.extend([token_program, creator_vault]);
+    } else {
+        accounts.extend([creator_vault, token_program]);
+    }
+    accounts.extend([
+        AccountMeta::new_readonly(ids.pump_fun_event_authority, false),
+        AccountMeta::new_readonly(ids.pump_fun_program, false),
+    ]);
+    Ok(accounts)
+}
+
+/// A pump.fun bonding curve that is still open to trades
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PumpFunCurve {
+    pub mint: Pubkey,
+    /// Creator of the token, whose vault every trade pays the creator fee into
+    pub creator: Pubkey,
+    pub reserves: BondingCurveReserves,
+}
+
+impl Dex for PumpFunCurve {
+    fn name(&self) -> &'static str {
+        "pump.fun"
+    }
+
+    fn address(&self) -> Pubkey {
+        program_ids().bonding_curve_pda(&self.mint)
+    }
+
+    fn reserves(&self) -> BondingCurveReserves {
+        self.reserves
+    }
+
+    fn quote(&self, direction: SwapDirection, amount_in: u64) -> u64 {
+        let (reserve_in, reserve_out) = match direction {
+            SwapDirection::Buy => (self.reserves.virtual_sol_reserves, self.reserves.virtual_token_reserves),
+            SwapDirection::Sell => (self.reserves.virtual_token_reserves, self.reserves.virtual_sol_reserves),
         };
-    Ok((
-        bonding_curve,
-        associated_bonding_curve,
-        bonding_curve_reserves,
-    ))
+        (amount_in as u128 * reserve_out as u128 / (reserve_in as u128 + amount_in as u128)) as u64
+    }
+
+    /// Buys the quoted token amount, spending at most `sol_in` plus slippage
+    fn buy_args(&self, sol_in: u64, slippage_bps: u64) -> (u64, u64) {
+        (self.quote(SwapDirection::Buy, sol_in), max_in(sol_in, slippage_bps))
+    }
+
+    fn build_buy(&self, user: &Pubkey, sol_in: u64, slippage_bps: u64) -> Result<(u64, Vec<Instruction>)> {
+        let (token_amount, max_sol_cost) = self.buy_args(sol_in, slippage_bps);
+        Ok((token_amount, vec![
+            create_associated_token_account_idempotent(user, user, &self.mint, &spl_token::ID),
+            buy_instruction(user, &self.mint, &self.creator, token_amount, max_sol_cost)?,
+        ]))
+    }
+
+    fn build_sell(&self, user: &Pubkey, token_amount: u64, min_sol_out: u64) -> Result<Vec<Instruction>> {
+        Ok(vec![sell_instruction(user, &self.mint, &self.creator, token_amount, min_sol_out)?])
+    }
+
+    fn decode_event(&self, info: &SubscribeUpdateTransactionInfo) -> Vec<TradeEvent> {
+        decoder::decode_transaction(info).trades().filter(|trade| trade.mint == self.mint).copied().collect()
+    }
+
+    /// Curve trades pay and receive native SOL
+    fn closes_wsol(&self) -> bool {
+        false
+    }
+}
+
+/// How far the bonding curve is toward completion, in percent (0-100)
+///
+/// Measured from the tokens bought off the virtual reserves since launch.
+pub fn curve_progress_percent(reserves: &BondingCurveReserves) -> f64 {
+    let sold = INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(reserves.virtual_token_reserves);
+    (sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0).clamp(0.0, 100.0)
+}
+
+/// Byte offset of the creator pubkey in the bonding curve account, right after `complete`
+const BONDING_CURVE_CREATOR_OFFSET: usize = 8 + 8 * 5 + 1;
+
+/// Creators already read, since every trade of a token needs its creator vault
+static TOKEN_CREATORS: LazyLock<Mutex<HashMap<Pubkey, Pubkey>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Remember the creator named in a token's launch, so its first trade skips the curve read
+pub fn remember_token_creator(mint: Pubkey, creator: Pubkey) {
+    TOKEN_CREATORS.lock().unwrap_or_else(|e| e.into_inner()).insert(mint, creator);
+}
+
+/// Creator wallet of a pump.fun token, read from its bonding curve account once
+pub async fn get_token_creator(
+    rpc_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    mint: &Pubkey,
+) -> Result<Pubkey> {
+    if let Some(creator) = TOKEN_CREATORS.lock().unwrap_or_else(|e| e.into_inner()).get(mint) {
+        return Ok(*creator);
+    }
+    let bonding_curve = get_pda(mint, &program_ids().pump_fun_program)?;
+    let data = rpc_client.get_account_data(&bonding_curve).await?;
+    let creator = data
+        .get(BONDING_CURVE_CREATOR_OFFSET..BONDING_CURVE_CREATOR_OFFSET + 32)
+        .ok_or_else(|| anyhow!("Bonding curve of {} has no creator field", mint))?;
+    let creator = Pubkey::try_from(creator).map_err(|_| anyhow!("Invalid creator in bonding curve of {}", mint))?;
+    if creator == Pubkey::default() {
+        return Err(anyhow!("Bonding curve of {} has no creator set", mint));
+    }
+    TOKEN_CREATORS.lock().unwrap_or_else(|e| e.into_inner()).insert(*mint, creator);
+    Ok(creator)
 }
 
 pub fn get_pda(mint: &Pubkey, program_id: &Pubkey) -> Result<Pubkey> {
@@ -168,3 +410,27 @@ pub struct PoolInfo {
     /// Virtual token reserves (from bonding curve)
     pub virtual_token_reserves: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_accounts_pass_the_creator_vault() {
+        let (user, mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = creator_vault_pda(&creator);
+        let token_program = Pubkey::from_str(TOKEN_PROGRAM).unwrap();
+
+        let buy = buy_instruction(&user, &mint, &creator, 10, 20).unwrap();
+        let keys: Vec<Pubkey> = buy.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys.len(), 12);
+        assert_eq!(keys[7..], [system_program::id(), token_program, vault, program_ids().pump_fun_event_authority, program_ids().pump_fun_program]);
+        assert!(buy.accounts[9].is_writable);
+
+        let sell = sell_instruction(&user, &mint, &creator, 10, 20).unwrap();
+        let keys: Vec<Pubkey> = sell.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys[7..], [system_program::id(), vault, token_program, program_ids().pump_fun_event_authority, program_ids().pump_fun_program]);
+        assert!(sell.accounts[8].is_writable);
+        assert_eq!(&sell.data[..8], &PUMP_SELL_METHOD.to_le_bytes());
+    }
+}
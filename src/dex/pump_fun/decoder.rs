@@ -0,0 +1,524 @@
+//! Typed decoding of pump.fun instructions and events
+//!
+//! Instructions are read from a transaction's top-level and inner instructions.
+//! Events are read from the program's self-CPI (`emit_cpi`) inner instructions,
+//! with the `Program data:` log lines as a fallback when there are none.
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
+
+use super::{PUMP_BUY_METHOD, PUMP_FUN_CREATE_IX_DISCRIMINATOR, PUMP_SELL_METHOD};
+use crate::dex::program_ids::program_ids;
+use crate::dex::reader::Reader;
+
+/// Anchor event discriminator of the pump.fun `TradeEvent`
+pub const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+/// Anchor event discriminator of the pump.fun `CreateEvent`
+pub const CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+/// Anchor event discriminator of the pump.fun `CompleteEvent`
+pub const COMPLETE_EVENT_DISCRIMINATOR: [u8; 8] = [95, 114, 97, 156, 212, 46, 152, 8];
+/// Prefix of Anchor `emit_cpi` instruction data, ahead of the event discriminator
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// Log line prefix of Anchor events
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Largest event the decoder reads; longer `Program data:` lines are skipped
+const MAX_EVENT_LEN: usize = 512;
+
+/// Kind of a pump.fun event, told apart by its Anchor discriminator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PumpEventKind {
+    Create,
+    Trade,
+    Complete,
+}
+
+impl PumpEventKind {
+    /// Base64 of the first six discriminator bytes, which every encoded event of the kind starts with
+    const fn log_prefix(self) -> &'static str {
+        match self {
+            PumpEventKind::Create => "G3KpTd7r",
+            PumpEventKind::Trade => "vdt/007m",
+            PumpEventKind::Complete => "X3JhnNQu",
+        }
+    }
+
+    /// Kind of the event in a log line, read from the prefix without decoding it
+    pub fn from_log(line: &str) -> Option<Self> {
+        let data = line.strip_prefix(PROGRAM_DATA_PREFIX)?;
+        [PumpEventKind::Trade, PumpEventKind::Create, PumpEventKind::Complete]
+            .into_iter()
+            .find(|kind| data.starts_with(kind.log_prefix()))
+    }
+
+    pub fn from_discriminator(discriminator: &[u8]) -> Option<Self> {
+        match discriminator.get(..8)? {
+            d if d == TRADE_EVENT_DISCRIMINATOR => Some(PumpEventKind::Trade),
+            d if d == CREATE_EVENT_DISCRIMINATOR => Some(PumpEventKind::Create),
+            d if d == COMPLETE_EVENT_DISCRIMINATOR => Some(PumpEventKind::Complete),
+            _ => None,
+        }
+    }
+}
+
+/// A buy or sell emitted by the pump.fun program
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeEvent {
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub is_buy: bool,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+/// A token launch emitted by the pump.fun program
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub user: Pubkey,
+    /// Coin creator, only emitted by newer program versions
+    pub creator: Option<Pubkey>,
+}
+
+/// A bonding curve reaching completion, emitted by the pump.fun program
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompleteEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Any decoded pump.fun event
+#[derive(Debug, Clone, PartialEq)]
+pub enum PumpEvent {
+    Create(CreateEvent),
+    Trade(TradeEvent),
+    Complete(CompleteEvent),
+}
+
+/// Decode a `TradeEvent` from event data; later fields added by the program are ignored
+pub fn parse_trade_event(data: &[u8]) -> Option<TradeEvent> {
+    let mut reader = Reader::new(data.strip_prefix(&TRADE_EVENT_DISCRIMINATOR[..])?);
+    Some(TradeEvent {
+        mint: reader.pubkey()?,
+        sol_amount: reader.u64()?,
+        token_amount: reader.u64()?,
+        is_buy: reader.bool()?,
+        user: reader.pubkey()?,
+        timestamp: reader.i64()?,
+        virtual_sol_reserves: reader.u64()?,
+        virtual_token_reserves: reader.u64()?,
+    })
+}
+
+fn parse_create_event(data: &[u8]) -> Option<CreateEvent> {
+    let mut reader = Reader::new(data.strip_prefix(&CREATE_EVENT_DISCRIMINATOR[..])?);
+    Some(CreateEvent {
+        name: reader.string()?,
+        symbol: reader.string()?,
+        uri: reader.string()?,
+        mint: reader.pubkey()?,
+        bonding_curve: reader.pubkey()?,
+        user: reader.pubkey()?,
+        creator: if reader.is_empty() { None } else { reader.pubkey() },
+    })
+}
+
+fn parse_complete_event(data: &[u8]) -> Option<CompleteEvent> {
+    let mut reader = Reader::new(data.strip_prefix(&COMPLETE_EVENT_DISCRIMINATOR[..])?);
+    Some(CompleteEvent {
+        user: reader.pubkey()?,
+        mint: reader.pubkey()?,
+        bonding_curve: reader.pubkey()?,
+        timestamp: reader.i64()?,
+    })
+}
+
+/// Decode event data starting at its discriminator
+pub fn decode_event(data: &[u8]) -> Option<PumpEvent> {
+    match PumpEventKind::from_discriminator(data)? {
+        PumpEventKind::Trade => parse_trade_event(data).map(PumpEvent::Trade),
+        PumpEventKind::Create => parse_create_event(data).map(PumpEvent::Create),
+        PumpEventKind::Complete => parse_complete_event(data).map(PumpEvent::Complete),
+    }
+}
+
+/// Base64-decode event data into `buffer`
+fn decode_event_data<'a>(data: &str, buffer: &'a mut [u8; MAX_EVENT_LEN]) -> Option<&'a [u8]> {
+    // decode_config_slice panics when the output is too small, so bound the input first
+    if data.len().div_ceil(4) * 3 > MAX_EVENT_LEN {
+        return None;
+    }
+    let len = base64::decode_config_slice(data, base64::STANDARD, buffer).ok()?;
+    Some(&buffer[..len])
+}
+
+/// Trade events in a transaction's log messages
+///
+/// Other lines are skipped by their prefix and events are decoded into a stack
+/// buffer, so nothing is allocated per line.
+pub fn trade_events(logs: &[String]) -> impl Iterator<Item = TradeEvent> + '_ {
+    logs.iter().filter_map(|line| {
+        if PumpEventKind::from_log(line)? != PumpEventKind::Trade {
+            return None;
+        }
+        let mut buffer = [0u8; MAX_EVENT_LEN];
+        parse_trade_event(decode_event_data(&line[PROGRAM_DATA_PREFIX.len()..], &mut buffer)?)
+    })
+}
+
+/// All pump.fun events in a transaction's log messages
+pub fn events_from_logs(logs: &[String]) -> Vec<PumpEvent> {
+    logs.iter()
+        .filter(|line| PumpEventKind::from_log(line).is_some())
+        .filter_map(|line| {
+            let mut buffer = [0u8; MAX_EVENT_LEN];
+            decode_event(decode_event_data(&line[PROGRAM_DATA_PREFIX.len()..], &mut buffer)?)
+        })
+        .collect()
+}
+
+/// The curve accounts an instruction operates on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveAccounts {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub associated_bonding_curve: Pubkey,
+}
+
+/// A pump.fun `create` instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub accounts: CurveAccounts,
+    /// Signer paying for the launch
+    pub user: Pubkey,
+    /// Coin creator argument, only sent to newer program versions
+    pub creator: Option<Pubkey>,
+}
+
+/// A pump.fun `buy` or `sell` instruction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapInstruction {
+    pub accounts: CurveAccounts,
+    pub user: Pubkey,
+    /// Exact token amount bought or sold
+    pub token_amount: u64,
+    /// Max SOL cost of a buy or min SOL output of a sell, in lamports
+    pub sol_limit: u64,
+}
+
+/// Any decoded pump.fun instruction
+#[derive(Debug, Clone, PartialEq)]
+pub enum PumpInstruction {
+    Create(CreateInstruction),
+    Buy(SwapInstruction),
+    Sell(SwapInstruction),
+}
+
+impl PumpInstruction {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            PumpInstruction::Create(create) => create.accounts.mint,
+            PumpInstruction::Buy(swap) | PumpInstruction::Sell(swap) => swap.accounts.mint,
+        }
+    }
+}
+
+/// Decode a pump.fun instruction from its data and resolved account list
+pub fn decode_instruction(data: &[u8], accounts: &[Pubkey]) -> Option<PumpInstruction> {
+    let (discriminator, args) = (data.get(..8)?, &data[8..]);
+    let mut reader = Reader::new(args);
+    if discriminator == PUMP_FUN_CREATE_IX_DISCRIMINATOR {
+        // mint, mint authority, bonding curve, associated bonding curve, global, metadata program, metadata, user
+        return Some(PumpInstruction::Create(CreateInstruction {
+            name: reader.string()?,
+            symbol: reader.string()?,
+            uri: reader.string()?,
+            creator: if reader.is_empty() { None } else { reader.pubkey() },
+            accounts: CurveAccounts {
+                mint: *accounts.first()?,
+                bonding_curve: *accounts.get(2)?,
+                associated_bonding_curve: *accounts.get(3)?,
+            },
+            user: *accounts.get(7)?,
+        }));
+    }
+
+    let is_buy = match u64::from_le_bytes(discriminator.try_into().ok()?) {
+        PUMP_BUY_METHOD => true,
+        PUMP_SELL_METHOD => false,
+        _ => return None,
+    };
+    // global, fee recipient, mint, bonding curve, associated bonding curve, user token account, user
+    let swap = SwapInstruction {
+        accounts: CurveAccounts {
+            mint: *accounts.get(2)?,
+            bonding_curve: *accounts.get(3)?,
+            associated_bonding_curve: *accounts.get(4)?,
+        },
+        user: *accounts.get(6)?,
+        token_amount: reader.u64()?,
+        sol_limit: reader.u64()?,
+    };
+    Some(if is_buy { PumpInstruction::Buy(swap) } else { PumpInstruction::Sell(swap) })
+}
+
+/// What a creator bought of its own token in the launch transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DevBuy {
+    /// SOL paid into the curve, in lamports
+    pub sol_lamports: u64,
+    /// Tokens received, in raw units
+    pub token_amount: u64,
+}
+
+/// Everything pump.fun did in one transaction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodedTransaction {
+    pub instructions: Vec<PumpInstruction>,
+    pub events: Vec<PumpEvent>,
+}
+
+impl DecodedTransaction {
+    pub fn trades(&self) -> impl Iterator<Item = &TradeEvent> {
+        self.events.iter().filter_map(|event| match event {
+            PumpEvent::Trade(trade) => Some(trade),
+            _ => None,
+        })
+    }
+
+    /// Buys of `mint` by any of `wallets` in this transaction, summed from their trade events
+    ///
+    /// Trade events carry the exact amounts the curve settled, so no rounding is involved.
+    pub fn dev_buy(&self, mint: &Pubkey, wallets: &[Pubkey]) -> DevBuy {
+        self.trades()
+            .filter(|trade| trade.is_buy && trade.mint == *mint && wallets.contains(&trade.user))
+            .fold(DevBuy::default(), |total, trade| DevBuy {
+                sol_lamports: total.sol_lamports.saturating_add(trade.sol_amount),
+                token_amount: total.token_amount.saturating_add(trade.token_amount),
+            })
+    }
+}
+
+/// Account keys of a streamed transaction: the static keys followed by the
+/// addresses loaded from lookup tables, as the runtime orders them
+///
+/// Instruction account indexes and the balances in the meta use this order.
+pub fn account_keys(info: &SubscribeUpdateTransactionInfo) -> Vec<Pubkey> {
+    let Some(message) = info.transaction.as_ref().and_then(|tx| tx.message.as_ref()) else {
+        return Vec::new();
+    };
+    let meta = info.meta.as_ref();
+    message.account_keys
+        .iter()
+        .chain(meta.into_iter().flat_map(|meta| meta.loaded_writable_addresses.iter()))
+        .chain(meta.into_iter().flat_map(|meta| meta.loaded_readonly_addresses.iter()))
+        .map(|key| Pubkey::try_from(key.as_slice()).unwrap_or_default())
+        .collect()
+}
+
+/// Top-level and inner instructions of a streamed transaction that invoke `program`,
+/// in execution order, as their resolved accounts and data
+pub fn program_instructions<'a>(info: &'a SubscribeUpdateTransactionInfo, program: &Pubkey) -> Vec<(Vec<Pubkey>, &'a [u8])> {
+    let Some(message) = info.transaction.as_ref().and_then(|tx| tx.message.as_ref()) else {
+        return Vec::new();
+    };
+    let meta = info.meta.as_ref();
+    let keys = account_keys(info);
+
+    let mut found = Vec::new();
+    let mut visit = |program_index: u32, accounts: &[u8], data: &'a [u8]| {
+        if keys.get(program_index as usize) != Some(program) {
+            return;
+        }
+        let accounts = accounts
+            .iter()
+            .filter_map(|index| keys.get(*index as usize).copied())
+            .collect();
+        found.push((accounts, data));
+    };
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        visit(instruction.program_id_index, &instruction.accounts, &instruction.data);
+        let inner = meta
+            .into_iter()
+            .flat_map(|meta| meta.inner_instructions.iter())
+            .filter(|inner| inner.index as usize == index)
+            .flat_map(|inner| inner.instructions.iter());
+        for instruction in inner {
+            visit(instruction.program_id_index, &instruction.accounts, &instruction.data);
+        }
+    }
+    found
+}
+
+/// Decode the pump.fun instructions and events of a streamed transaction
+pub fn decode_transaction(info: &SubscribeUpdateTransactionInfo) -> DecodedTransaction {
+    let mut decoded = DecodedTransaction::default();
+    for (accounts, data) in program_instructions(info, &program_ids().pump_fun_program) {
+        if let Some(event_data) = data.strip_prefix(&EVENT_IX_TAG[..]) {
+            decoded.events.extend(decode_event(event_data));
+        } else if let Some(instruction) = decode_instruction(data, &accounts) {
+            decoded.instructions.push(instruction);
+        }
+    }
+
+    if decoded.events.is_empty() {
+        if let Some(meta) = info.meta.as_ref() {
+            decoded.events = events_from_logs(&meta.log_messages);
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::pump_fun::{INITIAL_VIRTUAL_SOL_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES};
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, Message, Transaction, TransactionStatusMeta,
+    };
+
+    fn trade_event_data(mint: &Pubkey, user: &Pubkey) -> Vec<u8> {
+        let mut data = TRADE_EVENT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&1_500_000_000u64.to_le_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(user.as_ref());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&INITIAL_VIRTUAL_SOL_RESERVES.to_le_bytes());
+        data.extend_from_slice(&INITIAL_VIRTUAL_TOKEN_RESERVES.to_le_bytes());
+        // newer program versions append more fields
+        data.extend_from_slice(&[0u8; 16]);
+        data
+    }
+
+    fn borsh_string(data: &mut Vec<u8>, value: &str) {
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn test_parse_trade_event() {
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let data = trade_event_data(&mint, &user);
+
+        let logs = vec![
+            "Program log: Instruction: Buy".to_string(),
+            format!("Program data: {}", base64::encode(&data)),
+        ];
+        assert_eq!(PumpEventKind::from_log(&logs[1]), Some(PumpEventKind::Trade));
+        assert_eq!(PumpEventKind::from_log(&logs[0]), None);
+        let events: Vec<TradeEvent> = trade_events(&logs).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].mint, mint);
+        assert_eq!(events[0].user, user);
+        assert_eq!(events[0].sol_amount, 1_500_000_000);
+        assert!(events[0].is_buy);
+        assert_eq!(events[0].virtual_token_reserves, INITIAL_VIRTUAL_TOKEN_RESERVES);
+
+        assert!(parse_trade_event(&data[..60]).is_none());
+        assert!(parse_trade_event(&[0u8; 130]).is_none());
+    }
+
+    #[test]
+    fn test_decode_fixture_transaction() {
+        let ids = program_ids();
+        let user = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let curve = Pubkey::new_unique();
+        let associated_curve = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        // static keys: user, mint, curve, associated curve, program; the rest are filler
+        let mut keys = vec![user, mint, curve, associated_curve, ids.pump_fun_program];
+        keys.extend((0..4).map(|_| Pubkey::new_unique()));
+        let key_bytes = keys.iter().map(|key| key.to_bytes().to_vec()).collect();
+
+        let mut create = PUMP_FUN_CREATE_IX_DISCRIMINATOR.to_vec();
+        borsh_string(&mut create, "Token");
+        borsh_string(&mut create, "TKN");
+        borsh_string(&mut create, "https://example.com/token.json");
+        create.extend_from_slice(creator.as_ref());
+
+        let mut buy = PUMP_BUY_METHOD.to_le_bytes().to_vec();
+        buy.extend_from_slice(&1_000u64.to_le_bytes());
+        buy.extend_from_slice(&2_000_000u64.to_le_bytes());
+
+        let mut complete = EVENT_IX_TAG.to_vec();
+        complete.extend_from_slice(&COMPLETE_EVENT_DISCRIMINATOR);
+        complete.extend_from_slice(user.as_ref());
+        complete.extend_from_slice(mint.as_ref());
+        complete.extend_from_slice(curve.as_ref());
+        complete.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+
+        let mut trade = EVENT_IX_TAG.to_vec();
+        trade.extend_from_slice(&trade_event_data(&mint, &user));
+
+        let info = SubscribeUpdateTransactionInfo {
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    account_keys: key_bytes,
+                    instructions: vec![
+                        CompiledInstruction { program_id_index: 4, accounts: vec![1, 5, 2, 3, 6, 7, 8, 0], data: create },
+                        CompiledInstruction { program_id_index: 4, accounts: vec![5, 6, 1, 2, 3, 7, 0], data: buy },
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(TransactionStatusMeta {
+                inner_instructions: vec![InnerInstructions {
+                    index: 1,
+                    instructions: vec![
+                        InnerInstruction { program_id_index: 4, accounts: vec![8], data: trade, stack_height: Some(2) },
+                        InnerInstruction { program_id_index: 4, accounts: vec![8], data: complete, stack_height: Some(2) },
+                    ],
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let decoded = decode_transaction(&info);
+        let curve_accounts = CurveAccounts { mint, bonding_curve: curve, associated_bonding_curve: associated_curve };
+        assert_eq!(decoded.instructions, vec![
+            PumpInstruction::Create(CreateInstruction {
+                name: "Token".to_string(),
+                symbol: "TKN".to_string(),
+                uri: "https://example.com/token.json".to_string(),
+                accounts: curve_accounts,
+                user,
+                creator: Some(creator),
+            }),
+            PumpInstruction::Buy(SwapInstruction {
+                accounts: curve_accounts,
+                user,
+                token_amount: 1_000,
+                sol_limit: 2_000_000,
+            }),
+        ]);
+        assert_eq!(decoded.events.len(), 2);
+        assert_eq!(decoded.trades().next().map(|trade| trade.sol_amount), Some(1_500_000_000));
+        assert_eq!(decoded.dev_buy(&mint, &[user, creator]), DevBuy { sol_lamports: 1_500_000_000, token_amount: 42 });
+        assert_eq!(decoded.dev_buy(&mint, &[creator]), DevBuy::default());
+        assert_eq!(decoded.events[1], PumpEvent::Complete(CompleteEvent {
+            user,
+            mint,
+            bonding_curve: curve,
+            timestamp: 1_700_000_000,
+        }));
+    }
+}
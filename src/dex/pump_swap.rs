@@ -0,0 +1,328 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction, system_program,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
+
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{BondingCurveReserves, TradeEvent, PUMP_BUY_METHOD, PUMP_SELL_METHOD, TEN_THOUSAND};
+use crate::dex::reader::Reader;
+use crate::engine::swap::{max_in, Dex, SwapDirection};
+
+pub mod decoder;
+
+/// Anchor account discriminator of a PumpSwap `Pool`
+pub const POOL_ACCOUNT_DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+/// Anchor account discriminator of the PumpSwap `GlobalConfig`
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+
+/// Trading fees of a PumpSwap pool, in basis points of the quote amount
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PumpSwapFees {
+    pub lp_fee_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub coin_creator_fee_bps: u64,
+}
+
+impl PumpSwapFees {
+    pub fn total_bps(&self) -> u64 {
+        self.lp_fee_bps + self.protocol_fee_bps + self.coin_creator_fee_bps
+    }
+}
+
+/// A PumpSwap pool with the state needed to quote and build swaps
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpSwapPool {
+    pub address: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    /// Creator paid the coin creator fee; default for pools created before the fee existed
+    pub coin_creator: Pubkey,
+    pub base_reserves: u64,
+    pub quote_reserves: u64,
+    pub fees: PumpSwapFees,
+    pub protocol_fee_recipient: Pubkey,
+}
+
+/// Pool the pump.fun program migrates a completed curve into
+///
+/// It is created by the mint's pool authority at index 0, paired with WSOL.
+pub fn canonical_pool_address(mint: &Pubkey) -> Pubkey {
+    let ids = program_ids();
+    let (authority, _) = Pubkey::find_program_address(&[b"pool-authority", mint.as_ref()], &ids.pump_fun_program);
+    let (pool, _) = Pubkey::find_program_address(
+        &[
+            b"pool",
+            &0u16.to_le_bytes(),
+            authority.as_ref(),
+            mint.as_ref(),
+            spl_token::native_mint::ID.as_ref(),
+        ],
+        &ids.pump_swap_program,
+    );
+    pool
+}
+
+fn coin_creator_vault_authority(coin_creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"creator_vault", coin_creator.as_ref()], &program_ids().pump_swap_program).0
+}
+
+/// Pool account fields, without reserves, fees and the fee recipient
+fn parse_pool_account(address: Pubkey, data: &[u8]) -> Option<PumpSwapPool> {
+    let mut reader = Reader::new(data.strip_prefix(&POOL_ACCOUNT_DISCRIMINATOR[..])?);
+    let _bump = reader.u8()?;
+    let _index = reader.u16()?;
+    let _creator = reader.pubkey()?;
+    let base_mint = reader.pubkey()?;
+    let quote_mint = reader.pubkey()?;
+    let _lp_mint = reader.pubkey()?;
+    let pool_base_token_account = reader.pubkey()?;
+    let pool_quote_token_account = reader.pubkey()?;
+    let _lp_supply = reader.u64()?;
+    let coin_creator = if reader.is_empty() { Pubkey::default() } else { reader.pubkey()? };
+    Some(PumpSwapPool {
+        address,
+        base_mint,
+        quote_mint,
+        pool_base_token_account,
+        pool_quote_token_account,
+        coin_creator,
+        base_reserves: 0,
+        quote_reserves: 0,
+        fees: PumpSwapFees::default(),
+        protocol_fee_recipient: Pubkey::default(),
+    })
+}
+
+/// Fees and the first protocol fee recipient from the global config
+fn parse_global_config(data: &[u8]) -> Option<(PumpSwapFees, Pubkey)> {
+    let mut reader = Reader::new(data.strip_prefix(&GLOBAL_CONFIG_DISCRIMINATOR[..])?);
+    let _admin = reader.pubkey()?;
+    let lp_fee_bps = reader.u64()?;
+    let protocol_fee_bps = reader.u64()?;
+    let _disable_flags = reader.u8()?;
+    let recipient = reader.pubkey()?;
+    reader.take(32 * 7)?;
+    let coin_creator_fee_bps = if reader.is_empty() { 0 } else { reader.u64()? };
+    Some((PumpSwapFees { lp_fee_bps, protocol_fee_bps, coin_creator_fee_bps }, recipient))
+}
+
+/// Amount of an SPL token account
+fn token_account_amount(data: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(64..72)?.try_into().ok()?))
+}
+
+impl PumpSwapPool {
+    /// Load the canonical pool of `mint` with its current reserves and fees
+    pub async fn fetch(
+        rpc_client: &Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+        mint: &Pubkey,
+    ) -> Result<Self> {
+        let address = canonical_pool_address(mint);
+        let accounts = rpc_client
+            .get_multiple_accounts(&[address, program_ids().pump_swap_global_config])
+            .await?;
+        let mut pool = accounts[0]
+            .as_ref()
+            .and_then(|account| parse_pool_account(address, &account.data))
+            .ok_or_else(|| anyhow!("No PumpSwap pool for {}", mint))?;
+        let (fees, recipient) = accounts[1]
+            .as_ref()
+            .and_then(|account| parse_global_config(&account.data))
+            .ok_or_else(|| anyhow!("Failed to read the PumpSwap global config"))?;
+        pool.fees = fees;
+        pool.protocol_fee_recipient = recipient;
+
+        let vaults = rpc_client
+            .get_multiple_accounts(&[pool.pool_base_token_account, pool.pool_quote_token_account])
+            .await?;
+        let amount = |index: usize| vaults[index].as_ref().and_then(|account| token_account_amount(&account.data));
+        pool.base_reserves = amount(0).ok_or_else(|| anyhow!("Missing base vault of pool {}", address))?;
+        pool.quote_reserves = amount(1).ok_or_else(|| anyhow!("Missing quote vault of pool {}", address))?;
+        Ok(pool)
+    }
+
+    /// Pool reserves in the shape the price helpers take
+    pub fn reserves(&self) -> BondingCurveReserves {
+        BondingCurveReserves {
+            virtual_token_reserves: self.base_reserves,
+            virtual_sol_reserves: self.quote_reserves,
+        }
+    }
+
+    /// Base tokens received for spending `quote_in`, fees included
+    pub fn quote_buy(&self, quote_in: u64) -> u64 {
+        let effective = quote_in as u128 * TEN_THOUSAND as u128 / (TEN_THOUSAND + self.fees.total_bps()) as u128;
+        (self.base_reserves as u128 * effective / (self.quote_reserves as u128 + effective)) as u64
+    }
+
+    /// Quote received for selling `base_in`, after fees
+    pub fn quote_sell(&self, base_in: u64) -> u64 {
+        let gross = self.quote_reserves as u128 * base_in as u128 / (self.base_reserves as u128 + base_in as u128);
+        (gross - gross * self.fees.total_bps() as u128 / TEN_THOUSAND as u128) as u64
+    }
+
+    fn swap_accounts(&self, user: &Pubkey) -> Vec<AccountMeta> {
+        let ids = program_ids();
+        let quote_vault_authority = coin_creator_vault_authority(&self.coin_creator);
+        vec![
+            AccountMeta::new_readonly(self.address, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(ids.pump_swap_global_config, false),
+            AccountMeta::new_readonly(self.base_mint, false),
+            AccountMeta::new_readonly(self.quote_mint, false),
+            AccountMeta::new(get_associated_token_address(user, &self.base_mint), false),
+            AccountMeta::new(get_associated_token_address(user, &self.quote_mint), false),
+            AccountMeta::new(self.pool_base_token_account, false),
+            AccountMeta::new(self.pool_quote_token_account, false),
+            AccountMeta::new_readonly(self.protocol_fee_recipient, false),
+            AccountMeta::new(get_associated_token_address(&self.protocol_fee_recipient, &self.quote_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(ids.pump_swap_event_authority, false),
+            AccountMeta::new_readonly(ids.pump_swap_program, false),
+            AccountMeta::new(get_associated_token_address(&quote_vault_authority, &self.quote_mint), false),
+            AccountMeta::new_readonly(quote_vault_authority, false),
+        ]
+    }
+
+    /// PumpSwap buy of exactly `base_amount_out`, paying at most `max_quote_amount_in`
+    pub fn buy_instruction(&self, user: &Pubkey, base_amount_out: u64, max_quote_amount_in: u64) -> Instruction {
+        let mut data = PUMP_BUY_METHOD.to_le_bytes().to_vec();
+        data.extend_from_slice(&base_amount_out.to_le_bytes());
+        data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
+        Instruction {
+            program_id: program_ids().pump_swap_program,
+            accounts: self.swap_accounts(user),
+            data,
+        }
+    }
+
+    /// PumpSwap sell of exactly `base_amount_in`, receiving at least `min_quote_amount_out`
+    pub fn sell_instruction(&self, user: &Pubkey, base_amount_in: u64, min_quote_amount_out: u64) -> Instruction {
+        let mut data = PUMP_SELL_METHOD.to_le_bytes().to_vec();
+        data.extend_from_slice(&base_amount_in.to_le_bytes());
+        data.extend_from_slice(&min_quote_amount_out.to_le_bytes());
+        Instruction {
+            program_id: program_ids().pump_swap_program,
+            accounts: self.swap_accounts(user),
+            data,
+        }
+    }
+
+    /// Full buy: wrap the SOL budget, swap, and close the WSOL account to return the rest
+    pub fn buy_instructions(&self, user: &Pubkey, base_amount_out: u64, max_quote_amount_in: u64) -> Result<Vec<Instruction>> {
+        let wsol = get_associated_token_address(user, &self.quote_mint);
+        Ok(vec![
+            create_associated_token_account_idempotent(user, user, &self.base_mint, &spl_token::ID),
+            create_associated_token_account_idempotent(user, user, &self.quote_mint, &spl_token::ID),
+            system_instruction::transfer(user, &wsol, max_quote_amount_in),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol)?,
+            self.buy_instruction(user, base_amount_out, max_quote_amount_in),
+            spl_token::instruction::close_account(&spl_token::ID, &wsol, user, user, &[user])?,
+        ])
+    }
+
+    /// Full sell: receive WSOL and close the account so the proceeds arrive as native SOL
+    pub fn sell_instructions(&self, user: &Pubkey, base_amount_in: u64, min_quote_amount_out: u64) -> Result<Vec<Instruction>> {
+        let wsol = get_associated_token_address(user, &self.quote_mint);
+        Ok(vec![
+            create_associated_token_account_idempotent(user, user, &self.quote_mint, &spl_token::ID),
+            self.sell_instruction(user, base_amount_in, min_quote_amount_out),
+            spl_token::instruction::close_account(&spl_token::ID, &wsol, user, user, &[user])?,
+        ])
+    }
+}
+
+impl Dex for PumpSwapPool {
+    fn name(&self) -> &'static str {
+        "PumpSwap"
+    }
+
+    fn address(&self) -> Pubkey {
+        self.address
+    }
+
+    fn reserves(&self) -> BondingCurveReserves {
+        PumpSwapPool::reserves(self)
+    }
+
+    fn quote(&self, direction: SwapDirection, amount_in: u64) -> u64 {
+        match direction {
+            SwapDirection::Buy => self.quote_buy(amount_in),
+            SwapDirection::Sell => self.quote_sell(amount_in),
+        }
+    }
+
+    /// Buys the quoted token amount, wrapping at most `sol_in` plus slippage
+    fn buy_args(&self, sol_in: u64, slippage_bps: u64) -> (u64, u64) {
+        (self.quote_buy(sol_in), max_in(sol_in, slippage_bps))
+    }
+
+    fn build_buy(&self, user: &Pubkey, sol_in: u64, slippage_bps: u64) -> Result<(u64, Vec<Instruction>)> {
+        let (token_amount, max_quote_in) = self.buy_args(sol_in, slippage_bps);
+        Ok((token_amount, self.buy_instructions(user, token_amount, max_quote_in)?))
+    }
+
+    fn build_sell(&self, user: &Pubkey, token_amount: u64, min_sol_out: u64) -> Result<Vec<Instruction>> {
+        self.sell_instructions(user, token_amount, min_sol_out)
+    }
+
+    fn decode_event(&self, info: &SubscribeUpdateTransactionInfo) -> Vec<TradeEvent> {
+        decoder::swap_trades(info).into_iter().filter(|trade| trade.mint == self.base_mint).collect()
+    }
+
+    fn is_graduated(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_decoding_and_quotes_with_fees() {
+        let (base_mint, base_vault, quote_vault, coin_creator) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = POOL_ACCOUNT_DISCRIMINATOR.to_vec();
+        data.push(255);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(base_mint.as_ref());
+        data.extend_from_slice(spl_token::native_mint::ID.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(base_vault.as_ref());
+        data.extend_from_slice(quote_vault.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(coin_creator.as_ref());
+
+        let mut pool = parse_pool_account(Pubkey::new_unique(), &data).unwrap();
+        assert_eq!(pool.base_mint, base_mint);
+        assert_eq!(pool.pool_quote_token_account, quote_vault);
+        assert_eq!(pool.coin_creator, coin_creator);
+
+        pool.base_reserves = 1_000_000;
+        pool.quote_reserves = 1_000_000;
+        assert_eq!(pool.quote_buy(1_000), 999);
+        pool.fees = PumpSwapFees { lp_fee_bps: 20, protocol_fee_bps: 5, coin_creator_fee_bps: 5 };
+        // 0.3% of the input goes to fees before the curve is applied
+        assert_eq!(pool.quote_buy(100_300), 90_909);
+        assert_eq!(pool.quote_sell(100_000), 90_637);
+
+        let buy = pool.buy_instruction(&Pubkey::new_unique(), 10, 20);
+        assert_eq!(buy.accounts.len(), 19);
+        assert_eq!(&buy.data[..8], &PUMP_BUY_METHOD.to_le_bytes());
+    }
+}
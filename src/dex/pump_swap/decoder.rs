@@ -0,0 +1,267 @@
+//! Typed decoding of PumpSwap pool creation and swap events
+//!
+//! Swap events only name the pool, so each one is paired with the buy or sell
+//! instruction that emitted it to learn the traded mint.
+
+use std::collections::HashMap;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
+
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::decoder::{program_instructions, EVENT_IX_TAG};
+use crate::dex::pump_fun::{TradeEvent, PUMP_BUY_METHOD, PUMP_SELL_METHOD};
+use crate::dex::reader::Reader;
+
+/// Anchor event discriminator of the PumpSwap `BuyEvent`
+pub const BUY_EVENT_DISCRIMINATOR: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+/// Anchor event discriminator of the PumpSwap `SellEvent`
+pub const SELL_EVENT_DISCRIMINATOR: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+/// Anchor event discriminator of the PumpSwap `CreatePoolEvent`
+pub const CREATE_POOL_EVENT_DISCRIMINATOR: [u8; 8] = [177, 49, 12, 210, 160, 118, 167, 116];
+
+/// A PumpSwap buy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuyEvent {
+    pub timestamp: i64,
+    pub base_amount_out: u64,
+    /// Pool reserves before the swap
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    /// Quote added to the pool, LP fee included
+    pub quote_amount_in_with_lp_fee: u64,
+    /// Quote paid by the user, all fees included
+    pub user_quote_amount_in: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+}
+
+/// A PumpSwap sell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SellEvent {
+    pub timestamp: i64,
+    pub base_amount_in: u64,
+    /// Pool reserves before the swap
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    /// Quote taken from the pool, LP fee excluded
+    pub quote_amount_out_without_lp_fee: u64,
+    /// Quote received by the user, all fees deducted
+    pub user_quote_amount_out: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+}
+
+/// A new PumpSwap pool, emitted when a pump.fun curve migrates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CreatePoolEvent {
+    pub timestamp: i64,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_amount: u64,
+    pub pool_quote_amount: u64,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+/// Any decoded PumpSwap event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PumpSwapEvent {
+    Buy(BuyEvent),
+    Sell(SellEvent),
+    CreatePool(CreatePoolEvent),
+}
+
+fn parse_buy_event(data: &[u8]) -> Option<BuyEvent> {
+    let mut reader = Reader::new(data);
+    let timestamp = reader.i64()?;
+    let base_amount_out = reader.u64()?;
+    let _max_quote_amount_in = reader.u64()?;
+    let _user_base_token_reserves = reader.u64()?;
+    let _user_quote_token_reserves = reader.u64()?;
+    let pool_base_token_reserves = reader.u64()?;
+    let pool_quote_token_reserves = reader.u64()?;
+    // quote amount in, lp fee bps, lp fee, protocol fee bps, protocol fee
+    reader.take(8 * 5)?;
+    Some(BuyEvent {
+        timestamp,
+        base_amount_out,
+        pool_base_token_reserves,
+        pool_quote_token_reserves,
+        quote_amount_in_with_lp_fee: reader.u64()?,
+        user_quote_amount_in: reader.u64()?,
+        pool: reader.pubkey()?,
+        user: reader.pubkey()?,
+    })
+}
+
+fn parse_sell_event(data: &[u8]) -> Option<SellEvent> {
+    let mut reader = Reader::new(data);
+    let timestamp = reader.i64()?;
+    let base_amount_in = reader.u64()?;
+    let _min_quote_amount_out = reader.u64()?;
+    let _user_base_token_reserves = reader.u64()?;
+    let _user_quote_token_reserves = reader.u64()?;
+    let pool_base_token_reserves = reader.u64()?;
+    let pool_quote_token_reserves = reader.u64()?;
+    // quote amount out, lp fee bps, lp fee, protocol fee bps, protocol fee
+    reader.take(8 * 5)?;
+    Some(SellEvent {
+        timestamp,
+        base_amount_in,
+        pool_base_token_reserves,
+        pool_quote_token_reserves,
+        quote_amount_out_without_lp_fee: reader.u64()?,
+        user_quote_amount_out: reader.u64()?,
+        pool: reader.pubkey()?,
+        user: reader.pubkey()?,
+    })
+}
+
+fn parse_create_pool_event(data: &[u8]) -> Option<CreatePoolEvent> {
+    let mut reader = Reader::new(data);
+    let timestamp = reader.i64()?;
+    let _index = reader.u16()?;
+    let creator = reader.pubkey()?;
+    let base_mint = reader.pubkey()?;
+    let quote_mint = reader.pubkey()?;
+    // base and quote decimals, base amount in, quote amount in
+    reader.take(2 + 8 * 2)?;
+    let pool_base_amount = reader.u64()?;
+    let pool_quote_amount = reader.u64()?;
+    // minimum liquidity, initial liquidity, lp tokens out, pool bump
+    reader.take(8 * 3 + 1)?;
+    Some(CreatePoolEvent {
+        timestamp,
+        creator,
+        base_mint,
+        quote_mint,
+        pool_base_amount,
+        pool_quote_amount,
+        pool: reader.pubkey()?,
+        lp_mint: reader.pubkey()?,
+    })
+}
+
+/// Decode event data starting at its discriminator; later fields added by the program are ignored
+pub fn decode_event(data: &[u8]) -> Option<PumpSwapEvent> {
+    let (discriminator, body) = (data.get(..8)?, &data[8..]);
+    match discriminator {
+        d if d == BUY_EVENT_DISCRIMINATOR => parse_buy_event(body).map(PumpSwapEvent::Buy),
+        d if d == SELL_EVENT_DISCRIMINATOR => parse_sell_event(body).map(PumpSwapEvent::Sell),
+        d if d == CREATE_POOL_EVENT_DISCRIMINATOR => parse_create_pool_event(body).map(PumpSwapEvent::CreatePool),
+        _ => None,
+    }
+}
+
+/// PumpSwap events of a streamed transaction, from the program's self-CPI instructions
+pub fn decode_events(info: &SubscribeUpdateTransactionInfo) -> Vec<PumpSwapEvent> {
+    program_instructions(info, &program_ids().pump_swap_program)
+        .into_iter()
+        .filter_map(|(_, data)| decode_event(data.strip_prefix(&EVENT_IX_TAG[..])?))
+        .collect()
+}
+
+/// PumpSwap swaps against WSOL in a streamed transaction, in the shape of pump.fun trades
+///
+/// Reserves are the pool's after the swap, so prices read the same way as curve trades.
+pub fn swap_trades(info: &SubscribeUpdateTransactionInfo) -> Vec<TradeEvent> {
+    let instructions = program_instructions(info, &program_ids().pump_swap_program);
+    // pool, user, global config, base mint, quote mint, ...
+    let mut mints: HashMap<Pubkey, Pubkey> = HashMap::new();
+    for (accounts, data) in &instructions {
+        let is_swap = data.get(..8).is_some_and(|discriminator| {
+            discriminator == PUMP_BUY_METHOD.to_le_bytes() || discriminator == PUMP_SELL_METHOD.to_le_bytes()
+        });
+        if is_swap && accounts.len() > 4 && accounts[4] == spl_token::native_mint::ID {
+            mints.insert(accounts[0], accounts[3]);
+        }
+    }
+
+    instructions
+        .iter()
+        .filter_map(|(_, data)| decode_event(data.strip_prefix(&EVENT_IX_TAG[..])?))
+        .filter_map(|event| match event {
+            PumpSwapEvent::Buy(buy) => Some(TradeEvent {
+                mint: *mints.get(&buy.pool)?,
+                sol_amount: buy.user_quote_amount_in,
+                token_amount: buy.base_amount_out,
+                is_buy: true,
+                user: buy.user,
+                timestamp: buy.timestamp,
+                virtual_sol_reserves: buy.pool_quote_token_reserves + buy.quote_amount_in_with_lp_fee,
+                virtual_token_reserves: buy.pool_base_token_reserves.saturating_sub(buy.base_amount_out),
+            }),
+            PumpSwapEvent::Sell(sell) => Some(TradeEvent {
+                mint: *mints.get(&sell.pool)?,
+                sol_amount: sell.user_quote_amount_out,
+                token_amount: sell.base_amount_in,
+                is_buy: false,
+                user: sell.user,
+                timestamp: sell.timestamp,
+                virtual_sol_reserves: sell.pool_quote_token_reserves.saturating_sub(sell.quote_amount_out_without_lp_fee),
+                virtual_token_reserves: sell.pool_base_token_reserves + sell.base_amount_in,
+            }),
+            PumpSwapEvent::CreatePool(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, Message, Transaction, TransactionStatusMeta,
+    };
+
+    #[test]
+    fn test_swap_event_paired_with_instruction_mint() {
+        let ids = program_ids();
+        let (pool, user, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let keys = [pool, user, ids.pump_swap_global_config, mint, spl_token::native_mint::ID, ids.pump_swap_program];
+
+        let mut sell = PUMP_SELL_METHOD.to_le_bytes().to_vec();
+        sell.extend_from_slice(&500u64.to_le_bytes());
+        sell.extend_from_slice(&0u64.to_le_bytes());
+
+        let mut event = EVENT_IX_TAG.to_vec();
+        event.extend_from_slice(&SELL_EVENT_DISCRIMINATOR);
+        for value in [1_700_000_000u64, 500, 0, 0, 0, 10_000, 2_000, 90, 20, 1, 5, 1, 89, 87] {
+            event.extend_from_slice(&value.to_le_bytes());
+        }
+        event.extend_from_slice(pool.as_ref());
+        event.extend_from_slice(user.as_ref());
+
+        let info = SubscribeUpdateTransactionInfo {
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    account_keys: keys.iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    instructions: vec![CompiledInstruction { program_id_index: 5, accounts: vec![0, 1, 2, 3, 4], data: sell }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(TransactionStatusMeta {
+                inner_instructions: vec![InnerInstructions {
+                    index: 0,
+                    instructions: vec![InnerInstruction { program_id_index: 5, accounts: vec![], data: event, stack_height: Some(2) }],
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(matches!(decode_events(&info)[..], [PumpSwapEvent::Sell(SellEvent { user_quote_amount_out: 87, .. })]));
+        let trades = swap_trades(&info);
+        assert_eq!(trades, vec![TradeEvent {
+            mint,
+            sol_amount: 87,
+            token_amount: 500,
+            is_buy: false,
+            user,
+            timestamp: 1_700_000_000,
+            virtual_sol_reserves: 2_000 - 89,
+            virtual_token_reserves: 10_500,
+        }]);
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+/// Little-endian reader over borsh-encoded instruction and event data
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Some(head)
+    }
+
+    pub fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    pub fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    pub fn i64(&mut self) -> Option<i64> {
+        Some(i64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    pub fn bool(&mut self) -> Option<bool> {
+        Some(self.u8()? != 0)
+    }
+
+    pub fn pubkey(&mut self) -> Option<Pubkey> {
+        Pubkey::try_from(self.take(32)?).ok()
+    }
+
+    pub fn string(&mut self) -> Option<String> {
+        let len = u32::from_le_bytes(self.take(4)?.try_into().ok()?) as usize;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    /// Fields appended by newer program versions are optional
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
@@ -10,4 +10,13 @@
 
 // Position Sizing: Applies the Kelly criterion to determine optimal position size based on confidence and portfolio value, with safety caps.
 
-// Trade Evaluation: Evaluates tokens for entry signals, checks if trading is active, and decides position size before logging the trade decision.
\ No newline at end of file
+// Trade Evaluation: Evaluates tokens for entry signals, checks if trading is active, and decides position size before logging the trade decision.
+
+/// Risk category of a token, from its market cap, volume, buy/sell ratio and age
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskProfile {
+    Low,
+    Medium,
+    High,
+    VeryHigh,
+}
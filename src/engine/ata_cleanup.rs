@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use colored::Colorize;
+use tokio::time::Instant;
+
+use crate::common::config::{LiquidityPool, Status};
+use crate::common::logger::Logger;
+use crate::engine::ata_precreate::ata_cache;
+use crate::engine::execution_queue::{submit, Priority};
+use crate::engine::trade_executor::{TradeExecutor, WalletTokenAccount};
+
+/// Empty token account cleanup settings
+#[derive(Debug, Clone)]
+pub struct AtaCleanupSettings {
+    /// Close empty token accounts periodically while the bot runs
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// Close instructions packed into one transaction
+    pub batch_size: usize,
+    /// Keep accounts of mints sold less than this long ago, in case they are bought again
+    pub rebuy_grace_secs: u64,
+    /// Mints whose accounts are never closed
+    pub safelist: HashSet<String>,
+}
+
+impl AtaCleanupSettings {
+    /// Load cleanup settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("ATA_CLEANUP_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            interval_secs: std::env::var("ATA_CLEANUP_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(600),
+            batch_size: std::env::var("ATA_CLEANUP_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(10)
+                .clamp(1, 20),
+            rebuy_grace_secs: std::env::var("ATA_CLEANUP_REBUY_GRACE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+            safelist: std::env::var("ATA_CLEANUP_SAFELIST")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|mint| !mint.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Empty accounts that are safe to close
+///
+/// Skips WSOL (handled by the WSOL manager), safelisted mints, mints with an open or
+/// in-flight position, and mints sold within the rebuy grace period.
+pub fn closable_accounts(
+    accounts: &[WalletTokenAccount],
+    pools: &[LiquidityPool],
+    settings: &AtaCleanupSettings,
+    now: Instant,
+) -> Vec<Pubkey> {
+    let wsol = spl_token::native_mint::ID.to_string();
+    let grace = Duration::from_secs(settings.rebuy_grace_secs);
+    accounts
+        .iter()
+        .filter(|account| account.amount == 0 && account.mint != wsol && !settings.safelist.contains(&account.mint))
+        .filter(|account| {
+            pools.iter().filter(|pool| pool.mint == account.mint).all(|pool| match pool.status {
+                Status::Sold | Status::Failure => pool
+                    .timestamp
+                    .map(|at| now.saturating_duration_since(at) >= grace)
+                    .unwrap_or(true),
+                _ => false,
+            })
+        })
+        .map(|account| account.address)
+        .collect()
+}
+
+/// Close every closable empty account of the wallet, returning the close signatures
+pub async fn cleanup_empty_accounts(executor: &TradeExecutor, settings: &AtaCleanupSettings) -> Result<Vec<String>> {
+    let pending = ata_cache().pending_mints(Instant::now());
+    let mut accounts = executor.token_accounts().await?;
+    // pre-created accounts are empty until their buy lands
+    accounts.retain(|account| !pending.contains(&account.mint));
+    let pools: Vec<LiquidityPool> = executor.positions().snapshot();
+    let closable = closable_accounts(&accounts, &pools, settings, Instant::now());
+    executor.close_token_accounts(&closable, settings.batch_size).await
+}
+
+/// Periodically close empty token accounts to reclaim their rent (~0.002 SOL each)
+pub async fn start_ata_cleanup_system(executor: Arc<TradeExecutor>, settings: AtaCleanupSettings, logger: Logger) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.interval_secs.max(60)));
+        loop {
+            interval.tick().await;
+            let (executor, settings, logger) = (executor.clone(), settings.clone(), logger.clone());
+            // closing accounts can wait behind every trade
+            submit(Priority::Background, "token account cleanup", async move {
+                match cleanup_empty_accounts(&executor, &settings).await {
+                    Ok(signatures) if !signatures.is_empty() => logger.log(
+                        format!("Closed empty token accounts in {} transactions: {}", signatures.len(), signatures.join(", "))
+                            .green()
+                            .to_string(),
+                    ),
+                    Ok(_) => {}
+                    Err(e) => logger.error(format!("Token account cleanup failed: {}", e)),
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closable_accounts_respects_positions_safelist_and_grace() {
+        let account = |mint: &str, amount: u64| WalletTokenAccount {
+            address: Pubkey::new_unique(),
+            mint: mint.to_string(),
+            amount,
+            ui_amount: amount as f64,
+        };
+        let pool = |mint: &str, status: Status, at: Instant| LiquidityPool {
+            mint: mint.to_string(),
+            buy_price: 0.0,
+            sell_price: 0.0,
+            status,
+            timestamp: Some(at),
+        };
+        let settings = AtaCleanupSettings {
+            enabled: true,
+            interval_secs: 600,
+            batch_size: 10,
+            rebuy_grace_secs: 300,
+            safelist: HashSet::from(["safe".to_string()]),
+        };
+        let now = Instant::now() + Duration::from_secs(1_000);
+
+        let accounts = vec![
+            account("old", 0),
+            account("recent", 0),
+            account("held", 0),
+            account("safe", 0),
+            account("balance", 5),
+            account(&spl_token::native_mint::ID.to_string(), 0),
+        ];
+        let pools = vec![
+            pool("old", Status::Sold, now - Duration::from_secs(600)),
+            pool("recent", Status::Sold, now - Duration::from_secs(10)),
+            pool("held", Status::Buying, now),
+        ];
+        assert_eq!(closable_accounts(&accounts, &pools, &settings, now), vec![accounts[0].address]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+
+/// Token account pre-creation settings
+#[derive(Debug, Clone)]
+pub struct AtaPrecreateSettings {
+    /// Create the token account of a launch while its filters still run
+    pub enabled: bool,
+    /// Pre-creations sent at most at once, each locks ~0.002 SOL of rent until the account is closed
+    pub max_in_flight: usize,
+    /// A pre-created account unused for this long is left to the token account cleanup
+    pub ttl_secs: u64,
+}
+
+impl AtaPrecreateSettings {
+    /// Load pre-creation settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("ATA_PRECREATE_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            max_in_flight: std::env::var("ATA_PRECREATE_MAX_IN_FLIGHT")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(4),
+            ttl_secs: std::env::var("ATA_PRECREATE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+        }
+    }
+}
+
+/// Token accounts known to exist because they were pre-created, until a buy uses them
+#[derive(Debug)]
+pub struct AtaCache {
+    settings: AtaPrecreateSettings,
+    created: HashMap<(Pubkey, Pubkey), Instant>,
+}
+
+impl AtaCache {
+    pub fn new(settings: AtaPrecreateSettings) -> Self {
+        Self { settings, created: HashMap::new() }
+    }
+
+    pub fn settings(&self) -> &AtaPrecreateSettings {
+        &self.settings
+    }
+
+    pub fn created(&mut self, wallet: Pubkey, mint: Pubkey, now: Instant) {
+        let ttl = Duration::from_secs(self.settings.ttl_secs);
+        self.created.retain(|_, at| now.saturating_duration_since(*at) < ttl);
+        self.created.insert((wallet, mint), now);
+    }
+
+    pub fn contains(&self, wallet: &Pubkey, mint: &Pubkey, now: Instant) -> bool {
+        self.created
+            .get(&(*wallet, *mint))
+            .is_some_and(|at| now.saturating_duration_since(*at) < Duration::from_secs(self.settings.ttl_secs))
+    }
+
+    /// Whether the account exists, forgetting it so only one buy skips the creation
+    pub fn take(&mut self, wallet: &Pubkey, mint: &Pubkey, now: Instant) -> bool {
+        let exists = self.contains(wallet, mint, now);
+        self.created.remove(&(*wallet, *mint));
+        exists
+    }
+
+    /// Mints with a pre-created account still waiting for its buy, kept from the cleanup
+    pub fn pending_mints(&self, now: Instant) -> Vec<String> {
+        self.created
+            .iter()
+            .filter(|(_, at)| now.saturating_duration_since(**at) < Duration::from_secs(self.settings.ttl_secs))
+            .map(|((_, mint), _)| mint.to_string())
+            .collect()
+    }
+}
+
+static ATA_CACHE: LazyLock<Mutex<AtaCache>> = LazyLock::new(|| Mutex::new(AtaCache::new(AtaPrecreateSettings::from_env())));
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn ata_cache() -> MutexGuard<'static, AtaCache> {
+    ATA_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// `instructions` without the creation of `wallet`'s account for `mint`
+pub fn without_ata_creation(instructions: Vec<Instruction>, wallet: &Pubkey, mint: &Pubkey) -> Vec<Instruction> {
+    let ata = get_associated_token_address(wallet, mint);
+    instructions
+        .into_iter()
+        .filter(|ix| !(ix.program_id == spl_associated_token_account::ID && ix.accounts.get(1).is_some_and(|meta| meta.pubkey == ata)))
+        .collect()
+}
+
+/// Create the primary wallet's account for `mint` in the background, while the launch is screened
+///
+/// Skipped when pre-creation is off, the account is already known, or too many are in flight.
+pub fn precreate(executor: &Arc<TradeExecutor>, mint: &str, logger: &Logger) {
+    let Ok(mint_pubkey) = mint.parse::<Pubkey>() else {
+        return;
+    };
+    let wallet = executor.wallet_pool().primary().pubkey();
+    {
+        let cache = ata_cache();
+        if !cache.settings().enabled || cache.contains(&wallet, &mint_pubkey, Instant::now()) {
+            return;
+        }
+        if IN_FLIGHT.fetch_add(1, Ordering::SeqCst) >= cache.settings().max_in_flight {
+            IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+            return;
+        }
+    }
+    let (executor, mint, logger) = (executor.clone(), mint.to_string(), logger.clone());
+    tokio::spawn(async move {
+        match executor.create_token_account(&mint_pubkey).await {
+            Ok(signature) => {
+                ata_cache().created(wallet, mint_pubkey, Instant::now());
+                logger.debug(format!("[ATA] => Pre-created token account for {}: {}", mint, signature));
+            }
+            Err(e) => logger.debug(format!("[ATA] => Pre-creating token account for {} failed: {}", mint, e)),
+        }
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+    #[test]
+    fn test_cache_and_instruction_filter() {
+        let (wallet, mint, wsol) = (Pubkey::new_unique(), Pubkey::new_unique(), spl_token::native_mint::ID);
+        let mut cache = AtaCache::new(AtaPrecreateSettings { enabled: true, max_in_flight: 4, ttl_secs: 60 });
+        let now = Instant::now();
+        cache.created(wallet, mint, now);
+        assert_eq!(cache.pending_mints(now), vec![mint.to_string()]);
+        assert!(!cache.contains(&wallet, &mint, now + Duration::from_secs(61)));
+        assert!(cache.take(&wallet, &mint, now));
+        assert!(!cache.take(&wallet, &mint, now));
+
+        let instructions = vec![
+            create_associated_token_account_idempotent(&wallet, &wallet, &mint, &spl_token::ID),
+            create_associated_token_account_idempotent(&wallet, &wallet, &wsol, &spl_token::ID),
+        ];
+        let kept = without_ata_creation(instructions, &wallet, &mint);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].accounts[1].pubkey, get_associated_token_address(&wallet, &wsol));
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::telegram::TelegramService;
+
+/// Wallet balance monitor settings
+#[derive(Debug, Clone)]
+pub struct BalanceMonitorSettings {
+    pub enabled: bool,
+    pub check_interval_secs: u64,
+    /// Alert once when the balance drops below this, in SOL
+    pub alert_threshold_sol: f64,
+    /// Pause new buys below this, keeping enough for sells and fees, in SOL
+    pub pause_floor_sol: f64,
+}
+
+impl BalanceMonitorSettings {
+    /// Load balance monitor settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("BALANCE_MONITOR_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            check_interval_secs: std::env::var("BALANCE_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(30),
+            alert_threshold_sol: std::env::var("LOW_BALANCE_ALERT_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.5),
+            pause_floor_sol: std::env::var("BALANCE_PAUSE_FLOOR_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.1),
+        }
+    }
+}
+
+/// Transitions worth telling the operator about
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BalanceEvent {
+    /// Dropped below the alert threshold
+    Low(f64),
+    /// Dropped below the floor, buys paused
+    Paused(f64),
+    /// Back above the floor, buys resumed
+    Resumed(f64),
+    /// Back above the alert threshold
+    Recovered(f64),
+}
+
+impl BalanceEvent {
+    pub fn to_telegram_message(&self) -> String {
+        match self {
+            BalanceEvent::Low(balance) => format!("<b>⚠️ Low wallet balance</b>\n\n{:.4} SOL left, top up soon.", balance),
+            BalanceEvent::Paused(balance) => format!(
+                "<b>⏸️ Buying paused</b>\n\nWallet balance {:.4} SOL is below the floor. Sells keep working; buying resumes after a top-up.",
+                balance
+            ),
+            BalanceEvent::Resumed(balance) => format!("<b>▶️ Buying resumed</b>\n\nWallet balance is back to {:.4} SOL.", balance),
+            BalanceEvent::Recovered(balance) => format!("<b>✅ Wallet balance recovered</b>\n\n{:.4} SOL available.", balance),
+        }
+    }
+}
+
+/// Balance guard state for status output
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceGuardStatus {
+    pub balance_sol: Option<f64>,
+    pub low: bool,
+    pub paused: bool,
+}
+
+/// Pauses buys while the wallet is below the floor and resumes after a top-up
+pub struct BalanceGuard {
+    settings: BalanceMonitorSettings,
+    balance_sol: Option<f64>,
+    low: bool,
+    paused: bool,
+}
+
+impl BalanceGuard {
+    /// Create a new balance guard
+    pub fn new(settings: BalanceMonitorSettings) -> Self {
+        Self {
+            settings,
+            balance_sol: None,
+            low: false,
+            paused: false,
+        }
+    }
+
+    /// Record a fresh balance reading, returning the transitions it caused
+    pub fn update(&mut self, balance_sol: f64) -> Vec<BalanceEvent> {
+        self.balance_sol = Some(balance_sol);
+        let mut events = Vec::new();
+
+        let low = balance_sol < self.settings.alert_threshold_sol;
+        if low && !self.low {
+            events.push(BalanceEvent::Low(balance_sol));
+        }
+        let paused = balance_sol < self.settings.pause_floor_sol;
+        if paused && !self.paused {
+            events.push(BalanceEvent::Paused(balance_sol));
+        } else if !paused && self.paused {
+            events.push(BalanceEvent::Resumed(balance_sol));
+        }
+        if !low && self.low {
+            events.push(BalanceEvent::Recovered(balance_sol));
+        }
+
+        self.low = low;
+        self.paused = paused;
+        events
+    }
+
+    /// Fail if buying is paused for a low balance
+    pub fn check(&self) -> Result<()> {
+        if self.settings.enabled && self.paused {
+            return Err(anyhow!(
+                "Wallet balance {:.4} SOL is below the {:.4} SOL floor, buying paused",
+                self.balance_sol.unwrap_or(0.0),
+                self.settings.pause_floor_sol
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn status(&self) -> BalanceGuardStatus {
+        BalanceGuardStatus {
+            balance_sol: self.balance_sol,
+            low: self.low,
+            paused: self.paused,
+        }
+    }
+}
+
+static BALANCE_GUARD: LazyLock<Mutex<BalanceGuard>> =
+    LazyLock::new(|| Mutex::new(BalanceGuard::new(BalanceMonitorSettings::from_env())));
+
+/// Shared balance guard checked before every buy
+pub fn balance_guard() -> MutexGuard<'static, BalanceGuard> {
+    BALANCE_GUARD.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Poll the wallet balance, alerting on low balance and pausing buys below the floor
+pub async fn start_balance_monitor_system(
+    executor: Arc<TradeExecutor>,
+    settings: BalanceMonitorSettings,
+    telegram_service: Option<Arc<TelegramService>>,
+    telegram_chat_id: String,
+    logger: Logger,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.check_interval_secs.max(5)));
+        loop {
+            interval.tick().await;
+            let balance = match executor.wallet_balance().await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    logger.debug(format!("Failed to fetch wallet balance: {}", e));
+                    continue;
+                }
+            };
+
+            let events = balance_guard().update(balance);
+            for event in events {
+                match event {
+                    BalanceEvent::Low(sol) => logger.log(format!("Low wallet balance: {:.4} SOL", sol).yellow().to_string()),
+                    BalanceEvent::Paused(sol) => logger.log(
+                        format!("Balance {:.4} SOL below the {:.4} SOL floor, pausing buys", sol, settings.pause_floor_sol)
+                            .red()
+                            .bold()
+                            .to_string(),
+                    ),
+                    BalanceEvent::Resumed(sol) => logger.log(format!("Balance back to {:.4} SOL, resuming buys", sol).green().to_string()),
+                    BalanceEvent::Recovered(sol) => logger.log(format!("Balance recovered to {:.4} SOL", sol).green().to_string()),
+                }
+                if let Some(telegram) = &telegram_service {
+                    if let Err(e) = telegram.send_message(&telegram_chat_id, &event.to_telegram_message(), "HTML").await {
+                        logger.error(format!("Failed to send balance alert: {}", e));
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alerts_pause_and_resume_once_per_transition() {
+        let mut guard = BalanceGuard::new(BalanceMonitorSettings {
+            enabled: true,
+            check_interval_secs: 30,
+            alert_threshold_sol: 0.5,
+            pause_floor_sol: 0.1,
+        });
+        assert!(guard.update(1.0).is_empty());
+        assert_eq!(guard.update(0.4), vec![BalanceEvent::Low(0.4)]);
+        assert!(guard.update(0.3).is_empty());
+        assert_eq!(guard.update(0.05), vec![BalanceEvent::Paused(0.05)]);
+        assert!(guard.check().is_err());
+
+        assert_eq!(guard.update(2.0), vec![BalanceEvent::Resumed(2.0), BalanceEvent::Recovered(2.0)]);
+        assert!(guard.check().is_ok());
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::VecDeque;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::common::config::AdvancedConfig;
+
+/// Budget settings
+#[derive(Debug, Clone)]
+pub struct BudgetSettings {
+    /// Maximum SOL spent on buys (including fees and tips) per window; 0 disables the limit
+    pub daily_budget_sol: f64,
+    /// Daily UTC time at which spending is reset, in addition to the rolling 24h window
+    pub reset_time: Option<NaiveTime>,
+}
+
+impl BudgetSettings {
+    /// Load budget settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            daily_budget_sol: std::env::var("DAILY_BUY_BUDGET")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(AdvancedConfig::default().daily_buy_budget),
+            reset_time: std::env::var("BUDGET_RESET_TIME")
+                .ok()
+                .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok()),
+        }
+    }
+}
+
+/// One buy counted against the budget
+#[derive(Debug, Clone, Serialize)]
+pub struct Spend {
+    pub at: DateTime<Utc>,
+    pub mint: String,
+    pub amount_sol: f64,
+    /// Network fee, priority fee and relay tip
+    pub fees_sol: f64,
+}
+
+impl Spend {
+    pub fn total(&self) -> f64 {
+        self.amount_sol + self.fees_sol
+    }
+}
+
+/// Budget usage for status output
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub budget_sol: f64,
+    pub spent_sol: f64,
+    pub fees_sol: f64,
+    pub remaining_sol: f64,
+    pub buys: usize,
+    pub next_reset: Option<DateTime<Utc>>,
+}
+
+/// Tracks SOL spent on buys and blocks new buys once the daily budget is used up
+///
+/// Spends older than 24h drop out of the window. When a reset time is configured
+/// the window is also cleared once a day at that time.
+pub struct BudgetManager {
+    settings: BudgetSettings,
+    spends: VecDeque<Spend>,
+    last_reset: DateTime<Utc>,
+}
+
+impl BudgetManager {
+    /// Create a new budget manager
+    pub fn new(settings: BudgetSettings) -> Self {
+        Self {
+            settings,
+            spends: VecDeque::new(),
+            last_reset: Utc::now(),
+        }
+    }
+
+    /// Count a buy against the budget, failing if it would exceed it
+    pub fn reserve(&mut self, mint: &str, amount_sol: f64, fees_sol: f64) -> Result<()> {
+        self.reserve_at(Utc::now(), mint, amount_sol, fees_sol)
+    }
+
+    pub fn reserve_at(&mut self, now: DateTime<Utc>, mint: &str, amount_sol: f64, fees_sol: f64) -> Result<()> {
+        self.roll(now);
+        if self.settings.daily_budget_sol > 0.0 {
+            let spent = self.spent();
+            if spent + amount_sol + fees_sol > self.settings.daily_budget_sol {
+                return Err(anyhow!(
+                    "Daily buy budget reached: {:.4}/{:.4} SOL spent, buy needs {:.4} SOL",
+                    spent,
+                    self.settings.daily_budget_sol,
+                    amount_sol + fees_sol
+                ));
+            }
+        }
+        self.spends.push_back(Spend {
+            at: now,
+            mint: mint.to_string(),
+            amount_sol,
+            fees_sol,
+        });
+        Ok(())
+    }
+
+    /// Give back the most recent reservation for a buy that never landed
+    pub fn release(&mut self, mint: &str) {
+        if let Some(index) = self.spends.iter().rposition(|spend| spend.mint == mint) {
+            self.spends.remove(index);
+        }
+    }
+
+    /// Keep only the fees of a buy that landed but failed on chain
+    pub fn refund_amount(&mut self, mint: &str) {
+        if let Some(spend) = self.spends.iter_mut().rev().find(|spend| spend.mint == mint) {
+            spend.amount_sol = 0.0;
+        }
+    }
+
+    /// SOL spent in the current window, fees included
+    pub fn spent(&self) -> f64 {
+        self.spends.iter().map(Spend::total).sum()
+    }
+
+    /// Whether the budget has no room left for `amount_sol`
+    pub fn is_exhausted(&mut self, amount_sol: f64) -> bool {
+        self.roll(Utc::now());
+        self.settings.daily_budget_sol > 0.0 && self.spent() + amount_sol > self.settings.daily_budget_sol
+    }
+
+    /// Current usage for status output
+    pub fn status(&mut self) -> BudgetStatus {
+        self.status_at(Utc::now())
+    }
+
+    pub fn status_at(&mut self, now: DateTime<Utc>) -> BudgetStatus {
+        self.roll(now);
+        let spent = self.spent();
+        BudgetStatus {
+            budget_sol: self.settings.daily_budget_sol,
+            spent_sol: spent,
+            fees_sol: self.spends.iter().map(|spend| spend.fees_sol).sum(),
+            remaining_sol: (self.settings.daily_budget_sol - spent).max(0.0),
+            buys: self.spends.len(),
+            next_reset: self.next_reset(now),
+        }
+    }
+
+    /// Telegram-friendly one-line summary
+    pub fn summary(&mut self) -> String {
+        let status = self.status();
+        if status.budget_sol <= 0.0 {
+            return format!("{:.4} SOL spent (no daily limit)", status.spent_sol);
+        }
+        format!(
+            "{:.4}/{:.4} SOL spent, {:.4} SOL remaining ({} buys)",
+            status.spent_sol, status.budget_sol, status.remaining_sol, status.buys
+        )
+    }
+
+    fn next_reset(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let reset_time = self.settings.reset_time?;
+        let today = Utc.from_utc_datetime(&now.date_naive().and_time(reset_time));
+        Some(if today > now { today } else { today + Duration::days(1) })
+    }
+
+    fn roll(&mut self, now: DateTime<Utc>) {
+        if let Some(reset_time) = self.settings.reset_time {
+            let today = Utc.from_utc_datetime(&now.date_naive().and_time(reset_time));
+            if self.last_reset < today && now >= today {
+                self.spends.clear();
+            }
+        }
+        self.last_reset = now;
+
+        let cutoff = now - Duration::hours(24);
+        while self.spends.front().map(|spend| spend.at <= cutoff).unwrap_or(false) {
+            self.spends.pop_front();
+        }
+    }
+}
+
+static BUDGET: LazyLock<Mutex<BudgetManager>> = LazyLock::new(|| Mutex::new(BudgetManager::new(BudgetSettings::from_env())));
+
+/// Shared budget used by the executor and status output
+pub fn budget_manager() -> MutexGuard<'static, BudgetManager> {
+    BUDGET.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(budget: f64, reset: Option<&str>) -> BudgetManager {
+        let mut manager = BudgetManager::new(BudgetSettings {
+            daily_budget_sol: budget,
+            reset_time: reset.map(|t| NaiveTime::parse_from_str(t, "%H:%M").unwrap()),
+        });
+        manager.last_reset = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        manager
+    }
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_blocks_buys_over_budget_including_fees() {
+        let mut budget = manager(1.0, None);
+        assert!(budget.reserve_at(noon(), "a", 0.5, 0.01).is_ok());
+        assert!(budget.reserve_at(noon(), "b", 0.47, 0.01).is_ok());
+        // 0.99 spent, another 0.01 plus its fee goes over
+        assert!(budget.reserve_at(noon(), "c", 0.01, 0.01).is_err());
+    }
+
+    #[test]
+    fn test_released_reservation_frees_budget() {
+        let mut budget = manager(1.0, None);
+        budget.reserve_at(noon(), "a", 0.5, 0.01).unwrap();
+        budget.reserve_at(noon(), "b", 0.47, 0.01).unwrap();
+
+        budget.release("b");
+        assert!(budget.reserve_at(noon(), "c", 0.01, 0.01).is_ok());
+    }
+
+    #[test]
+    fn test_rolling_window_frees_spend_after_24_hours() {
+        let mut budget = manager(1.0, None);
+        budget.reserve_at(noon(), "a", 1.0, 0.0).unwrap();
+        assert!(budget.reserve_at(noon() + Duration::hours(23), "b", 0.1, 0.0).is_err());
+        assert!(budget.reserve_at(noon() + Duration::hours(24), "b", 0.1, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_reset_time_frees_spend_at_that_time() {
+        let mut budget = manager(1.0, Some("18:00"));
+        budget.reserve_at(noon(), "a", 1.0, 0.0).unwrap();
+        assert!(budget.reserve_at(noon() + Duration::hours(5), "b", 0.1, 0.0).is_err());
+        assert!(budget.reserve_at(noon() + Duration::hours(6), "b", 0.1, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_status_after_reset_points_at_next_day() {
+        let mut budget = manager(1.0, Some("18:00"));
+        budget.reserve_at(noon(), "a", 1.0, 0.0).unwrap();
+        let status = budget.status_at(noon() + Duration::hours(6));
+        assert_eq!(status.spent_sol, 0.0);
+        assert_eq!(status.next_reset, Some(Utc.with_ymd_and_hms(2024, 1, 2, 18, 0, 0).unwrap()));
+    }
+}
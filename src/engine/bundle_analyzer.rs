@@ -0,0 +1,229 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use anyhow::Result;
+use serde::Serialize;
+use anchor_client::solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::dex::pump_fun::TOKEN_TOTAL_SUPPLY;
+use crate::engine::dev_watch::BalanceChange;
+
+/// Bundle analyzer settings (the check itself is toggled by `BUNDLE_CHECK`)
+#[derive(Debug, Clone)]
+pub struct BundleSettings {
+    /// Reject launches where bundled wallets hold more than this % of the supply
+    pub max_bundled_percent: f64,
+    /// Buys landing within this many slots of the creation count as part of its bundle
+    pub slot_window: u64,
+    /// Minimum number of non-creator wallets in the window before it is treated as a bundle
+    pub min_wallets: usize,
+    /// Wallets with at most this many prior transactions count as fresh
+    pub fresh_wallet_max_txs: usize,
+}
+
+impl BundleSettings {
+    /// Load bundle analyzer settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            max_bundled_percent: std::env::var("MAX_BUNDLED_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            slot_window: std::env::var("BUNDLE_SLOT_WINDOW")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+            min_wallets: std::env::var("BUNDLE_MIN_WALLETS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(2),
+            fresh_wallet_max_txs: std::env::var("FRESH_WALLET_MAX_TXS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(3),
+        }
+    }
+}
+
+/// A buy of a freshly launched token
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchBuy {
+    pub wallet: String,
+    pub slot: u64,
+    /// Raw token amount received
+    pub token_amount: u64,
+}
+
+/// Buys in a transaction, taken from the token balances that grew for `mint`
+///
+/// The bonding curve's own token account shrinks on a buy, so it never shows up here.
+pub fn launch_buys(mint: &str, slot: u64, changes: &[BalanceChange<'_>]) -> Vec<LaunchBuy> {
+    changes
+        .iter()
+        .filter(|change| change.mint == mint && change.post > change.pre)
+        .map(|change| LaunchBuy {
+            wallet: change.owner.to_string(),
+            slot,
+            token_amount: change.post - change.pre,
+        })
+        .collect()
+}
+
+/// Result of analyzing the buys around a launch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BundleReport {
+    /// Non-creator wallets that bought inside the creation window
+    pub bundled_wallets: usize,
+    /// How many of those wallets are fresh
+    pub fresh_wallets: usize,
+    /// Share of the total supply bought inside the window, creator included, in percent
+    pub bundled_percent: f64,
+    /// Whether enough wallets bought in the window to call it a bundle
+    pub is_bundle: bool,
+}
+
+/// Detects coordinated sniper clusters bundled with a token's creation
+pub struct BundleAnalyzer {
+    settings: BundleSettings,
+}
+
+impl BundleAnalyzer {
+    /// Create a new bundle analyzer
+    pub fn new(settings: BundleSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Wallets bundled with the creation, excluding the creator
+    pub fn bundled_wallets(&self, creator: &str, creation_slot: u64, buys: &[LaunchBuy]) -> Vec<String> {
+        let mut wallets: Vec<String> = buys
+            .iter()
+            .filter(|buy| self.in_window(creation_slot, buy) && buy.wallet != creator)
+            .map(|buy| buy.wallet.clone())
+            .collect();
+        wallets.sort();
+        wallets.dedup();
+        wallets
+    }
+
+    /// Analyze the buys around a launch given which of the bundled wallets are fresh
+    pub fn analyze(
+        &self,
+        creator: &str,
+        creation_slot: u64,
+        buys: &[LaunchBuy],
+        fresh: &HashSet<String>,
+    ) -> BundleReport {
+        let wallets = self.bundled_wallets(creator, creation_slot, buys);
+        if wallets.len() < self.settings.min_wallets.max(1) {
+            return BundleReport {
+                bundled_wallets: wallets.len(),
+                ..Default::default()
+            };
+        }
+
+        let mut per_wallet: HashMap<&str, u64> = HashMap::new();
+        for buy in buys.iter().filter(|buy| self.in_window(creation_slot, buy)) {
+            *per_wallet.entry(buy.wallet.as_str()).or_default() += buy.token_amount;
+        }
+        let bundled_tokens: u64 = per_wallet.values().sum();
+
+        BundleReport {
+            bundled_wallets: wallets.len(),
+            fresh_wallets: wallets.iter().filter(|wallet| fresh.contains(*wallet)).count(),
+            bundled_percent: bundled_tokens as f64 / TOKEN_TOTAL_SUPPLY as f64 * 100.0,
+            is_bundle: true,
+        }
+    }
+
+    /// Reason to skip the launch, if the bundle holds too much of the supply
+    pub fn rejection(&self, report: &BundleReport) -> Option<String> {
+        (report.is_bundle && report.bundled_percent > self.settings.max_bundled_percent).then(|| format!(
+            "{} bundled wallets ({} fresh) hold {:.1}% of supply, max is {}%",
+            report.bundled_wallets, report.fresh_wallets, report.bundled_percent, self.settings.max_bundled_percent
+        ))
+    }
+
+    /// Analyze a launch, looking up wallet freshness over RPC
+    pub async fn analyze_launch(
+        &self,
+        rpc_client: Arc<RpcClient>,
+        creator: &str,
+        creation_slot: u64,
+        buys: &[LaunchBuy],
+    ) -> BundleReport {
+        let mut fresh = HashSet::new();
+        for wallet in self.bundled_wallets(creator, creation_slot, buys) {
+            if is_fresh_wallet(&rpc_client, &wallet, self.settings.fresh_wallet_max_txs).await.unwrap_or(false) {
+                fresh.insert(wallet);
+            }
+        }
+        self.analyze(creator, creation_slot, buys, &fresh)
+    }
+
+    fn in_window(&self, creation_slot: u64, buy: &LaunchBuy) -> bool {
+        buy.slot >= creation_slot && buy.slot <= creation_slot + self.settings.slot_window
+    }
+}
+
+/// Whether a wallet has at most `max_txs` transactions in its history
+pub async fn is_fresh_wallet(rpc_client: &RpcClient, wallet: &str, max_txs: usize) -> Result<bool> {
+    let signatures = rpc_client
+        .get_signatures_for_address_with_config(
+            &Pubkey::from_str(wallet)?,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(max_txs + 1),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(signatures.len() <= max_txs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzer() -> BundleAnalyzer {
+        BundleAnalyzer::new(BundleSettings {
+            max_bundled_percent: 20.0,
+            slot_window: 1,
+            min_wallets: 2,
+            fresh_wallet_max_txs: 3,
+        })
+    }
+
+    fn buy(wallet: &str, slot: u64, percent: u64) -> LaunchBuy {
+        LaunchBuy { wallet: wallet.to_string(), slot, token_amount: TOKEN_TOTAL_SUPPLY / 100 * percent }
+    }
+
+    #[test]
+    fn test_bundle_supply_and_rejection() {
+        let buys = vec![
+            buy("dev", 100, 5),
+            buy("a", 100, 8),
+            buy("b", 101, 9),
+            buy("c", 105, 30),
+        ];
+        let fresh: HashSet<String> = ["a".to_string()].into_iter().collect();
+        let report = analyzer().analyze("dev", 100, &buys, &fresh);
+        assert!(report.is_bundle);
+        assert_eq!(report.bundled_wallets, 2);
+        assert_eq!(report.fresh_wallets, 1);
+        assert!((report.bundled_percent - 22.0).abs() < 1e-9);
+        assert!(analyzer().rejection(&report).is_some());
+    }
+
+    #[test]
+    fn test_lone_dev_buy_is_not_a_bundle() {
+        let buys = vec![buy("dev", 100, 40), buy("a", 100, 1)];
+        let report = analyzer().analyze("dev", 100, &buys, &HashSet::new());
+        assert!(!report.is_bundle);
+        assert!(analyzer().rejection(&report).is_none());
+
+        let changes = vec![BalanceChange { mint: "mint", owner: "a", pre: 0, post: 50 }];
+        assert_eq!(launch_buys("mint", 7, &changes), vec![LaunchBuy { wallet: "a".to_string(), slot: 7, token_amount: 50 }]);
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// Circuit breaker settings
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerSettings {
+    pub enabled: bool,
+    /// Trip after this many losing trades in a row (0 = disabled)
+    pub max_consecutive_losses: u32,
+    /// Trip once realized losses in the window exceed this % of the starting balance (0 = disabled)
+    pub max_drawdown_percent: f64,
+    /// Window for the drawdown check, in seconds
+    pub drawdown_window_secs: u64,
+    /// How long buying stays paused before resuming automatically, in seconds
+    pub cooldown_secs: u64,
+}
+
+impl CircuitBreakerSettings {
+    /// Load circuit breaker settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("CIRCUIT_BREAKER_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            max_consecutive_losses: std::env::var("MAX_CONSECUTIVE_LOSSES")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(5),
+            max_drawdown_percent: std::env::var("MAX_DRAWDOWN_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            drawdown_window_secs: std::env::var("DRAWDOWN_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(86400),
+            cooldown_secs: std::env::var("CIRCUIT_BREAKER_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3600),
+        }
+    }
+}
+
+/// Circuit breaker state for status output
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub tripped: bool,
+    pub reason: Option<String>,
+    pub resumes_at: Option<DateTime<Utc>>,
+    pub consecutive_losses: u32,
+    pub window_pnl_sol: f64,
+    pub starting_balance_sol: f64,
+}
+
+/// Pauses buying after a losing streak or a drawdown, and resumes after a cooldown
+///
+/// Fed with the realized PnL of every closed trade. While tripped, every buy is
+/// refused until the cooldown passes or the breaker is reset by hand.
+pub struct CircuitBreaker {
+    settings: CircuitBreakerSettings,
+    starting_balance_sol: f64,
+    consecutive_losses: u32,
+    results: VecDeque<(DateTime<Utc>, f64)>,
+    tripped_until: Option<DateTime<Utc>>,
+    reason: Option<String>,
+}
+
+impl CircuitBreaker {
+    /// Create a new circuit breaker
+    pub fn new(settings: CircuitBreakerSettings) -> Self {
+        Self {
+            settings,
+            starting_balance_sol: 0.0,
+            consecutive_losses: 0,
+            results: VecDeque::new(),
+            tripped_until: None,
+            reason: None,
+        }
+    }
+
+    /// Balance the drawdown percentage is measured against
+    pub fn set_starting_balance(&mut self, balance_sol: f64) {
+        self.starting_balance_sol = balance_sol;
+    }
+
+    /// Record a closed trade; returns the reason if this trade tripped the breaker
+    pub fn record_trade(&mut self, pnl_sol: f64) -> Option<String> {
+        self.record_trade_at(Utc::now(), pnl_sol)
+    }
+
+    pub fn record_trade_at(&mut self, now: DateTime<Utc>, pnl_sol: f64) -> Option<String> {
+        if pnl_sol < 0.0 {
+            self.consecutive_losses += 1;
+        } else {
+            self.consecutive_losses = 0;
+        }
+        self.results.push_back((now, pnl_sol));
+        self.prune(now);
+
+        if !self.settings.enabled || self.is_tripped_at(now) {
+            return None;
+        }
+
+        let reason = if self.settings.max_consecutive_losses > 0
+            && self.consecutive_losses >= self.settings.max_consecutive_losses
+        {
+            Some(format!("{} consecutive losing trades", self.consecutive_losses))
+        } else if let Some(drawdown) = self.drawdown_percent() {
+            (self.settings.max_drawdown_percent > 0.0 && drawdown >= self.settings.max_drawdown_percent)
+                .then(|| format!(
+                    "{:.1}% drawdown in the last {}h",
+                    drawdown,
+                    self.settings.drawdown_window_secs / 3600
+                ))
+        } else {
+            None
+        };
+
+        if let Some(reason) = &reason {
+            self.tripped_until = Some(now + Duration::seconds(self.settings.cooldown_secs as i64));
+            self.reason = Some(reason.clone());
+        }
+        reason
+    }
+
+    /// Fail if buying is currently paused by the breaker
+    pub fn check(&mut self) -> Result<()> {
+        if self.is_tripped_at(Utc::now()) {
+            return Err(anyhow!(
+                "Circuit breaker tripped ({}), buying paused",
+                self.reason.clone().unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the breaker is tripped, resuming automatically once the cooldown passed
+    pub fn is_tripped_at(&mut self, now: DateTime<Utc>) -> bool {
+        match self.tripped_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                self.clear();
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Manually resume buying
+    pub fn reset(&mut self) {
+        self.clear();
+        self.results.clear();
+    }
+
+    pub fn status(&mut self) -> CircuitBreakerStatus {
+        let now = Utc::now();
+        self.prune(now);
+        CircuitBreakerStatus {
+            tripped: self.is_tripped_at(now),
+            reason: self.reason.clone(),
+            resumes_at: self.tripped_until,
+            consecutive_losses: self.consecutive_losses,
+            window_pnl_sol: self.results.iter().map(|(_, pnl)| pnl).sum(),
+            starting_balance_sol: self.starting_balance_sol,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.tripped_until = None;
+        self.reason = None;
+        self.consecutive_losses = 0;
+    }
+
+    fn drawdown_percent(&self) -> Option<f64> {
+        if self.starting_balance_sol <= 0.0 {
+            return None;
+        }
+        let pnl: f64 = self.results.iter().map(|(_, pnl)| pnl).sum();
+        Some((-pnl / self.starting_balance_sol * 100.0).max(0.0))
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::seconds(self.settings.drawdown_window_secs as i64);
+        while self.results.front().map(|(at, _)| *at < cutoff).unwrap_or(false) {
+            self.results.pop_front();
+        }
+    }
+}
+
+static CIRCUIT_BREAKER: LazyLock<Mutex<CircuitBreaker>> =
+    LazyLock::new(|| Mutex::new(CircuitBreaker::new(CircuitBreakerSettings::from_env())));
+
+/// Shared circuit breaker used by the executor, confirmation tracker and control commands
+pub fn circuit_breaker() -> MutexGuard<'static, CircuitBreaker> {
+    CIRCUIT_BREAKER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        let mut breaker = CircuitBreaker::new(CircuitBreakerSettings {
+            enabled: true,
+            max_consecutive_losses: 3,
+            max_drawdown_percent: 10.0,
+            drawdown_window_secs: 3600,
+            cooldown_secs: 600,
+        });
+        breaker.set_starting_balance(10.0);
+        breaker
+    }
+
+    #[test]
+    fn test_trips_on_loss_streak() {
+        let mut breaker = breaker();
+        let now = Utc::now();
+        assert!(breaker.record_trade_at(now, -0.1).is_none());
+        assert!(breaker.record_trade_at(now, -0.1).is_none());
+        assert!(breaker.record_trade_at(now, -0.1).is_some());
+        assert!(breaker.is_tripped_at(now));
+    }
+
+    #[test]
+    fn test_win_breaks_loss_streak() {
+        let mut breaker = breaker();
+        let now = Utc::now();
+        breaker.record_trade_at(now, -0.1);
+        breaker.record_trade_at(now, -0.1);
+        assert!(breaker.record_trade_at(now, 0.05).is_none());
+        assert!(breaker.record_trade_at(now, -0.1).is_none());
+        assert!(breaker.record_trade_at(now, -0.1).is_none());
+        assert!(!breaker.is_tripped_at(now));
+    }
+
+    #[test]
+    fn test_resumes_after_cooldown() {
+        let mut breaker = breaker();
+        let now = Utc::now();
+        for _ in 0..3 {
+            breaker.record_trade_at(now, -0.1);
+        }
+        assert!(breaker.is_tripped_at(now + Duration::seconds(599)));
+        assert!(!breaker.is_tripped_at(now + Duration::seconds(600)));
+    }
+
+    #[test]
+    fn test_trips_on_drawdown() {
+        let mut breaker = breaker();
+        let now = Utc::now();
+        assert!(breaker.record_trade_at(now, -0.6).is_none());
+        assert!(breaker.record_trade_at(now, 0.1).is_none());
+        // 1.1 SOL down from the 10 SOL start, over the 10% limit
+        let reason = breaker.record_trade_at(now, -0.6).unwrap();
+        assert!(reason.contains("drawdown"));
+    }
+
+    #[test]
+    fn test_manual_reset_resumes_trading() {
+        let mut breaker = breaker();
+        let now = Utc::now();
+        breaker.record_trade_at(now, -1.2).unwrap();
+
+        breaker.reset();
+        assert!(!breaker.is_tripped_at(now));
+        assert!(breaker.record_trade_at(now, -0.1).is_none());
+    }
+}
@@ -0,0 +1,244 @@
+use serde::Serialize;
+
+use crate::services::telegram::TokenInfo;
+
+/// Confidence scoring settings
+///
+/// Weights are relative; components without data are left out and the rest re-normalized.
+#[derive(Debug, Clone)]
+pub struct ConfidenceSettings {
+    pub enabled: bool,
+    /// Skip buys scoring below this (0.0-1.0), shared with `AdvancedConfig::min_buy_confidence`
+    pub min_buy_confidence: f64,
+    pub market_cap_weight: f64,
+    pub holders_weight: f64,
+    pub creator_weight: f64,
+    pub bundle_weight: f64,
+    pub momentum_weight: f64,
+    /// Market cap range the market cap component scores inside of, in `MARKET_CAP_UNIT`
+    pub min_market_cap: f64,
+    pub max_market_cap: f64,
+    /// Holder count that scores a full 1.0
+    pub target_holders: u32,
+    /// Bundled share of supply that scores 0.0
+    pub max_bundled_percent: f64,
+    /// Insider share of early buyers that scores 0.0
+    pub max_insider_percent: f64,
+}
+
+impl ConfidenceSettings {
+    /// Load confidence settings from environment variables
+    pub fn from_env() -> Self {
+        let weight = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0)
+                .max(0.0)
+        };
+        Self {
+            enabled: std::env::var("CONFIDENCE_GATE_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            min_buy_confidence: std::env::var("MIN_BUY_CONFIDENCE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.7),
+            market_cap_weight: weight("CONFIDENCE_WEIGHT_MARKET_CAP"),
+            holders_weight: weight("CONFIDENCE_WEIGHT_HOLDERS"),
+            creator_weight: weight("CONFIDENCE_WEIGHT_CREATOR"),
+            bundle_weight: weight("CONFIDENCE_WEIGHT_BUNDLE"),
+            momentum_weight: weight("CONFIDENCE_WEIGHT_MOMENTUM"),
+            min_market_cap: std::env::var("MIN_MARKET_CAP")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(8.0),
+            max_market_cap: std::env::var("MAX_MARKET_CAP")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(15.0),
+            target_holders: std::env::var("CONFIDENCE_TARGET_HOLDERS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(100),
+            max_bundled_percent: std::env::var("MAX_BUNDLED_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            max_insider_percent: std::env::var("MAX_INSIDER_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(50.0),
+        }
+    }
+}
+
+/// Filter outputs the score is built from; `None` means no data
+#[derive(Debug, Clone, Default)]
+pub struct ConfidenceInputs {
+    pub market_cap: Option<f64>,
+    pub holders: Option<u32>,
+    /// Creator reputation, 0.0 (known rugger) to 1.0 (trusted)
+    pub creator_score: Option<f64>,
+    pub bundled_percent: Option<f64>,
+    pub insider_percent: Option<f64>,
+    /// Buy pressure, 0.0 (none) to 1.0 (strong)
+    pub momentum: Option<f64>,
+}
+
+impl ConfidenceInputs {
+    /// Inputs available on a token alert
+    pub fn from_token_info(token: &TokenInfo) -> Self {
+        Self {
+            market_cap: token.market_cap,
+            bundled_percent: token.bundled_percent,
+            insider_percent: token.insider_percent,
+            // a 50/50 buy ratio is no momentum, all buys is full momentum
+            momentum: token.buy_ratio.map(|ratio| ((ratio as f64 - 50.0) / 50.0).clamp(0.0, 1.0)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Combined score and the components that went into it
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfidenceScore {
+    /// Weighted score, 0.0-1.0
+    pub score: f64,
+    /// `(component, score)` for each component with data
+    pub components: Vec<(&'static str, f64)>,
+}
+
+impl ConfidenceScore {
+    pub fn describe(&self) -> String {
+        self.components
+            .iter()
+            .map(|(name, score)| format!("{} {:.2}", name, score))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Combines filter outputs into a single 0.0-1.0 buy confidence
+pub struct ConfidenceScorer {
+    settings: ConfidenceSettings,
+}
+
+impl ConfidenceScorer {
+    /// Create a new confidence scorer
+    pub fn new(settings: ConfidenceSettings) -> Self {
+        Self { settings }
+    }
+
+    pub fn settings(&self) -> &ConfidenceSettings {
+        &self.settings
+    }
+
+    /// Score the inputs; `None` when no component has data
+    pub fn score(&self, inputs: &ConfidenceInputs) -> Option<ConfidenceScore> {
+        let settings = &self.settings;
+        let mut components = Vec::new();
+
+        if let Some(market_cap) = inputs.market_cap {
+            components.push(("market_cap", settings.market_cap_weight, self.market_cap_score(market_cap)));
+        }
+        if let Some(holders) = inputs.holders {
+            let score = (holders as f64 / settings.target_holders.max(1) as f64).min(1.0);
+            components.push(("holders", settings.holders_weight, score));
+        }
+        if let Some(creator) = inputs.creator_score {
+            components.push(("creator", settings.creator_weight, creator.clamp(0.0, 1.0)));
+        }
+        let bundle = inputs.bundled_percent.map(|p| shrinking_score(p, settings.max_bundled_percent));
+        let insider = inputs.insider_percent.map(|p| shrinking_score(p, settings.max_insider_percent));
+        if let Some(score) = match (bundle, insider) {
+            (Some(bundle), Some(insider)) => Some(bundle.min(insider)),
+            (bundle, insider) => bundle.or(insider),
+        } {
+            components.push(("bundle", settings.bundle_weight, score));
+        }
+        if let Some(momentum) = inputs.momentum {
+            components.push(("momentum", settings.momentum_weight, momentum.clamp(0.0, 1.0)));
+        }
+
+        let total_weight: f64 = components.iter().map(|(_, weight, _)| weight).sum();
+        if total_weight <= 0.0 {
+            return None;
+        }
+        Some(ConfidenceScore {
+            score: components.iter().map(|(_, weight, score)| weight * score).sum::<f64>() / total_weight,
+            components: components.into_iter().map(|(name, _, score)| (name, score)).collect(),
+        })
+    }
+
+    /// Reason to skip the buy, if the score is below `min_buy_confidence`
+    pub fn rejection(&self, score: &ConfidenceScore) -> Option<String> {
+        (score.score < self.settings.min_buy_confidence).then(|| format!(
+            "confidence {:.2} below {:.2} ({})",
+            score.score, self.settings.min_buy_confidence, score.describe()
+        ))
+    }
+
+    /// Cheaper entries inside the range score higher; outside the range scores 0.0
+    fn market_cap_score(&self, market_cap: f64) -> f64 {
+        let (min, max) = (self.settings.min_market_cap, self.settings.max_market_cap);
+        if market_cap < min || market_cap > max {
+            return 0.0;
+        }
+        if max <= min {
+            return 1.0;
+        }
+        1.0 - (market_cap - min) / (max - min) * 0.5
+    }
+}
+
+/// 1.0 at zero, falling linearly to 0.0 at `limit`
+fn shrinking_score(value: f64, limit: f64) -> f64 {
+    if limit <= 0.0 {
+        return if value > 0.0 { 0.0 } else { 1.0 };
+    }
+    (1.0 - value / limit).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scorer() -> ConfidenceScorer {
+        ConfidenceScorer::new(ConfidenceSettings {
+            enabled: true,
+            min_buy_confidence: 0.7,
+            market_cap_weight: 1.0,
+            holders_weight: 1.0,
+            creator_weight: 2.0,
+            bundle_weight: 1.0,
+            momentum_weight: 0.0,
+            min_market_cap: 10.0,
+            max_market_cap: 20.0,
+            target_holders: 100,
+            max_bundled_percent: 20.0,
+            max_insider_percent: 50.0,
+        })
+    }
+
+    #[test]
+    fn test_weighted_score_skips_missing_components() {
+        assert!(scorer().score(&ConfidenceInputs::default()).is_none());
+
+        let inputs = ConfidenceInputs {
+            market_cap: Some(10.0),
+            holders: Some(50),
+            creator_score: Some(1.0),
+            bundled_percent: Some(5.0),
+            insider_percent: Some(25.0),
+            momentum: Some(0.0),
+        };
+        let score = scorer().score(&inputs).unwrap();
+        // (1.0 + 0.5 + 2 * 1.0 + 0.5) / 5, momentum has no weight
+        assert!((score.score - 0.8).abs() < 1e-9);
+        assert!(scorer().rejection(&score).is_none());
+
+        let rugger = ConfidenceInputs { creator_score: Some(0.0), ..inputs };
+        assert!(scorer().rejection(&scorer().score(&rugger).unwrap()).is_some());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Cooldown settings after failed buys and losing tokens
+#[derive(Debug, Clone)]
+pub struct CooldownSettings {
+    /// A mint whose buy failed or was dropped is not retried for this long, in minutes (0 = off)
+    pub mint_failure_mins: u64,
+    /// A creator whose token was closed at a loss is not bought from for this long, in minutes (0 = off)
+    pub creator_loss_mins: u64,
+    /// Strategies the cooldowns do not apply to
+    pub allow_strategies: Vec<String>,
+}
+
+impl CooldownSettings {
+    /// Load cooldown settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            mint_failure_mins: std::env::var("MINT_FAILURE_COOLDOWN_MINS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(30),
+            creator_loss_mins: std::env::var("CREATOR_LOSS_COOLDOWN_MINS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60),
+            allow_strategies: std::env::var("COOLDOWN_ALLOWED_STRATEGIES")
+                .unwrap_or_else(|_| "manual".to_string())
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Mints and creators that are not bought again for a while
+pub struct Cooldowns {
+    settings: CooldownSettings,
+    /// Mint -> when its cooldown ends and why
+    mints: HashMap<String, (Instant, String)>,
+    /// Creator -> when its cooldown ends and why
+    creators: HashMap<String, (Instant, String)>,
+    /// Creator of each bought mint, once resolved
+    creator_of: HashMap<String, String>,
+    /// Realized PnL of each open position so far, in SOL
+    pnl: HashMap<String, f64>,
+}
+
+impl Cooldowns {
+    pub fn new(settings: CooldownSettings) -> Self {
+        Self {
+            settings,
+            mints: HashMap::new(),
+            creators: HashMap::new(),
+            creator_of: HashMap::new(),
+            pnl: HashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &CooldownSettings {
+        &self.settings
+    }
+
+    fn exempt(&self, strategy: &str) -> bool {
+        self.settings.allow_strategies.iter().any(|name| name.eq_ignore_ascii_case(strategy))
+    }
+
+    /// Whether a buy by `strategy` has to know the creator to be checked
+    pub fn needs_creator(&self, strategy: &str, mint: &str) -> bool {
+        !self.exempt(strategy) && !self.creators.is_empty() && !self.creator_of.contains_key(mint)
+    }
+
+    /// Creator of `mint`, if one was recorded
+    pub fn creator_of(&self, mint: &str) -> Option<String> {
+        self.creator_of.get(mint).cloned()
+    }
+
+    pub fn remember_creator(&mut self, mint: &str, creator: &str) {
+        self.creator_of.insert(mint.to_string(), creator.to_string());
+    }
+
+    /// A buy of `mint` failed on chain or was dropped
+    pub fn mint_failed(&mut self, mint: &str, reason: &str, now: Instant) {
+        if self.settings.mint_failure_mins > 0 {
+            let until = now + Duration::from_secs(self.settings.mint_failure_mins * 60);
+            self.mints.insert(mint.to_string(), (until, reason.to_string()));
+        }
+        self.creator_of.remove(mint);
+    }
+
+    /// Count a confirmed sell; once the position is closed at a loss its creator cools down
+    ///
+    /// Returns the creator when its cooldown starts.
+    pub fn record_sell(&mut self, mint: &str, pnl_sol: f64, full_exit: bool, now: Instant) -> Option<String> {
+        *self.pnl.entry(mint.to_string()).or_default() += pnl_sol;
+        if !full_exit {
+            return None;
+        }
+        let pnl = self.pnl.remove(mint).unwrap_or(0.0);
+        let creator = self.creator_of.remove(mint)?;
+        if pnl >= 0.0 || self.settings.creator_loss_mins == 0 {
+            return None;
+        }
+        let until = now + Duration::from_secs(self.settings.creator_loss_mins * 60);
+        self.creators.insert(creator.clone(), (until, format!("lost {:.4} SOL on {}", -pnl, mint)));
+        Some(creator)
+    }
+
+    /// Whether `strategy` may buy `mint` of `creator` now, or which cooldown blocks it
+    pub fn check(&mut self, mint: &str, creator: Option<&str>, strategy: &str, now: Instant) -> Result<(), String> {
+        self.mints.retain(|_, (until, _)| *until > now);
+        self.creators.retain(|_, (until, _)| *until > now);
+        if self.exempt(strategy) {
+            return Ok(());
+        }
+        if let Some((until, reason)) = self.mints.get(mint) {
+            return Err(format!("{} cooling down for {}s after {}", mint, until.duration_since(now).as_secs(), reason));
+        }
+        let creator = creator.map(str::to_string).or_else(|| self.creator_of(mint));
+        let Some(creator) = creator else {
+            return Ok(());
+        };
+        match self.creators.get(&creator) {
+            Some((until, reason)) => Err(format!(
+                "creator {} cooling down for {}s after it {}",
+                creator, until.duration_since(now).as_secs(), reason
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+static COOLDOWNS: LazyLock<Mutex<Cooldowns>> = LazyLock::new(|| Mutex::new(Cooldowns::new(CooldownSettings::from_env())));
+
+/// Shared cooldowns, fed by the confirmation tracker and checked by the executor
+pub fn cooldowns() -> MutexGuard<'static, Cooldowns> {
+    COOLDOWNS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cooldowns() -> Cooldowns {
+        Cooldowns::new(CooldownSettings {
+            mint_failure_mins: 10,
+            creator_loss_mins: 60,
+            allow_strategies: vec!["manual".to_string()],
+        })
+    }
+
+    #[test]
+    fn test_failed_buy_cools_down_the_mint() {
+        let mut cooldowns = cooldowns();
+        let now = Instant::now();
+        cooldowns.mint_failed("mint", "a failed buy", now);
+        assert!(cooldowns.check("mint", None, "snipe", now).is_err());
+        assert!(cooldowns.check("mint", None, "snipe", now + Duration::from_secs(601)).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_strategies_bypass_cooldowns() {
+        let mut cooldowns = cooldowns();
+        let now = Instant::now();
+        cooldowns.mint_failed("mint", "a failed buy", now);
+        assert!(cooldowns.check("mint", None, "manual", now).is_ok());
+    }
+
+    #[test]
+    fn test_losing_exit_cools_down_the_creator() {
+        let mut cooldowns = cooldowns();
+        let now = Instant::now();
+        cooldowns.remember_creator("a", "dev");
+        // a partial sell starts nothing
+        assert_eq!(cooldowns.record_sell("a", 0.2, false, now), None);
+        assert_eq!(cooldowns.record_sell("a", -0.3, true, now), Some("dev".to_string()));
+        assert!(cooldowns.needs_creator("snipe", "b"));
+        assert!(cooldowns.check("b", Some("dev"), "snipe", now).is_err());
+        assert!(cooldowns.check("b", Some("other"), "snipe", now).is_ok());
+        assert!(cooldowns.check("b", Some("dev"), "snipe", now + Duration::from_secs(3601)).is_ok());
+    }
+
+    #[test]
+    fn test_winning_exit_starts_no_cooldown() {
+        let mut cooldowns = cooldowns();
+        cooldowns.remember_creator("c", "dev2");
+        assert_eq!(cooldowns.record_sell("c", 0.1, true, Instant::now()), None);
+    }
+}
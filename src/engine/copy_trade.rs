@@ -0,0 +1,534 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anchor_client::solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use serde::Serialize;
+use spl_token_2022::extension::{
+    default_account_state::DefaultAccountState, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_token_2022::state::{AccountState, Mint};
+use tokio::time::Instant;
+
+use crate::common::config::CopyTradingConfig;
+use crate::dex::pump_fun::TradeEvent;
+use crate::engine::strategy::{Strategy, StrategyAction};
+use crate::services::alerts::{alert, Alert};
+use crate::services::leaderboard::imported_targets;
+
+/// Target wallet heartbeat settings
+#[derive(Debug, Clone)]
+pub struct TargetWatchSettings {
+    /// Alert when a target made no trade for this long, in hours (0 = off)
+    pub silence_hours: f64,
+    /// Alert when a trade is this many times the target's average trade size (0 = off)
+    pub size_jump_multiplier: f64,
+    /// Trades seen before the average is trusted for the size check
+    pub size_baseline_trades: u64,
+    /// Stop copying the buys of a target that raised an alert, until it is resumed
+    pub auto_pause: bool,
+}
+
+impl TargetWatchSettings {
+    /// Load heartbeat settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            silence_hours: std::env::var("TARGET_SILENCE_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(12.0),
+            size_jump_multiplier: std::env::var("TARGET_SIZE_JUMP_MULTIPLIER")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(10.0),
+            size_baseline_trades: std::env::var("TARGET_SIZE_BASELINE_TRADES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(5),
+            auto_pause: std::env::var("TARGET_AUTO_PAUSE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+}
+
+/// Something unusual about a target wallet
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetAnomaly {
+    /// No trade for `hours`
+    Silent { hours: f64 },
+    /// A trade of `sol` against an average of `average_sol`
+    SizeJump { sol: f64, average_sol: f64 },
+}
+
+impl TargetAnomaly {
+    pub fn describe(&self) -> String {
+        match self {
+            TargetAnomaly::Silent { hours } => format!("made no trade for {:.1}h", hours),
+            TargetAnomaly::SizeJump { sol, average_sol } => format!(
+                "traded {:.4} SOL, {:.1}x its average of {:.4} SOL",
+                sol,
+                sol / average_sol,
+                average_sol
+            ),
+        }
+    }
+}
+
+/// What has been seen of one target
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TargetActivity {
+    #[serde(skip)]
+    pub last_trade: Option<Instant>,
+    pub trades: u64,
+    /// Mean SOL amount of its trades
+    pub average_sol: f64,
+    /// A silence alert was sent and no trade came since
+    pub silent: bool,
+}
+
+/// Per-wallet activity of the copy-trading targets, and the targets paused after an anomaly
+#[derive(Debug)]
+pub struct TargetWatch {
+    settings: TargetWatchSettings,
+    /// Counted from for targets that have not traded yet
+    started: Instant,
+    activity: HashMap<String, TargetActivity>,
+    paused: HashSet<String>,
+}
+
+impl TargetWatch {
+    pub fn new(settings: TargetWatchSettings, now: Instant) -> Self {
+        Self { settings, started: now, activity: HashMap::new(), paused: HashSet::new() }
+    }
+
+    /// Record a trade of `wallet`, with the anomaly it shows
+    pub fn observe(&mut self, wallet: &str, sol: f64, now: Instant) -> Option<TargetAnomaly> {
+        let settings = &self.settings;
+        let activity = self.activity.entry(wallet.to_string()).or_default();
+        let anomaly = (settings.size_jump_multiplier > 0.0
+            && activity.trades >= settings.size_baseline_trades.max(1)
+            && activity.average_sol > 0.0
+            && sol >= activity.average_sol * settings.size_jump_multiplier)
+            .then_some(TargetAnomaly::SizeJump { sol, average_sol: activity.average_sol });
+        activity.trades += 1;
+        activity.average_sol += (sol - activity.average_sol) / activity.trades as f64;
+        activity.last_trade = Some(now);
+        activity.silent = false;
+        if anomaly.is_some() && settings.auto_pause {
+            self.paused.insert(wallet.to_string());
+        }
+        anomaly
+    }
+
+    /// Targets that went quiet since the last check, each reported once per silence
+    pub fn check_silence(&mut self, targets: &[String], now: Instant) -> Vec<(String, TargetAnomaly)> {
+        if self.settings.silence_hours <= 0.0 {
+            return Vec::new();
+        }
+        let limit = Duration::from_secs_f64(self.settings.silence_hours * 3600.0);
+        let mut silent = Vec::new();
+        for target in targets {
+            let activity = self.activity.entry(target.clone()).or_default();
+            let quiet = now.saturating_duration_since(activity.last_trade.unwrap_or(self.started));
+            if activity.silent || quiet < limit {
+                continue;
+            }
+            activity.silent = true;
+            silent.push((target.clone(), TargetAnomaly::Silent { hours: quiet.as_secs_f64() / 3600.0 }));
+            if self.settings.auto_pause {
+                self.paused.insert(target.clone());
+            }
+        }
+        silent
+    }
+
+    pub fn is_paused(&self, wallet: &str) -> bool {
+        self.paused.contains(wallet)
+    }
+
+    /// Copy the buys of `wallet` again, `false` when it was not paused
+    pub fn resume(&mut self, wallet: &str) -> bool {
+        self.paused.remove(wallet)
+    }
+
+    pub fn paused(&self) -> Vec<String> {
+        self.paused.iter().cloned().collect()
+    }
+
+    /// Activity per target, with how long ago each last traded
+    pub fn activity(&self, now: Instant) -> Vec<(String, TargetActivity, Option<Duration>)> {
+        self.activity
+            .iter()
+            .map(|(wallet, activity)| {
+                let ago = activity.last_trade.map(|at| now.saturating_duration_since(at));
+                (wallet.clone(), activity.clone(), ago)
+            })
+            .collect()
+    }
+}
+
+static TARGET_WATCH: LazyLock<Mutex<TargetWatch>> =
+    LazyLock::new(|| Mutex::new(TargetWatch::new(TargetWatchSettings::from_env(), Instant::now())));
+
+pub fn target_watch() -> MutexGuard<'static, TargetWatch> {
+    TARGET_WATCH.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// How often the targets are checked for silence
+const SILENCE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Checks a copied buy goes through before it is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFilterMode {
+    /// The safety checks, the `MC_THRESHOLD_TO_BUY` market cap cap and the `AdvancedFilterSettings` market data filters
+    Full,
+    /// Only the safety checks: freeze authority and token extensions that can block sells
+    Safety,
+    /// Copied unconditionally
+    None,
+}
+
+impl FromStr for CopyFilterMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "full" => Ok(CopyFilterMode::Full),
+            "safety" => Ok(CopyFilterMode::Safety),
+            "none" | "off" => Ok(CopyFilterMode::None),
+            other => Err(format!("unknown copy filter mode '{}'", other)),
+        }
+    }
+}
+
+/// Filter mode of copied buys, with overrides per target wallet
+#[derive(Debug, Clone)]
+pub struct CopyFilterSettings {
+    pub default_mode: CopyFilterMode,
+    pub per_target: HashMap<String, CopyFilterMode>,
+}
+
+impl CopyFilterSettings {
+    /// Load copy filter settings from environment variables
+    ///
+    /// `COPY_FILTER_TARGETS` is a comma separated list of `wallet:mode`; entries that do not
+    /// parse are skipped.
+    pub fn from_env() -> Self {
+        Self {
+            default_mode: std::env::var("COPY_FILTER_MODE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(CopyFilterMode::Full),
+            per_target: std::env::var("COPY_FILTER_TARGETS")
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|entry| {
+                    let (wallet, mode) = entry.trim().split_once(':')?;
+                    Some((wallet.trim().to_string(), mode.parse().ok()?))
+                })
+                .collect(),
+        }
+    }
+
+    /// Mode for a buy copied from `target`, the default when the target is unknown
+    pub fn mode_for(&self, target: Option<&str>) -> CopyFilterMode {
+        target
+            .and_then(|target| self.per_target.get(target))
+            .copied()
+            .unwrap_or(self.default_mode)
+    }
+}
+
+/// Why a token with this mint account could keep us from selling, `None` when nothing was found
+///
+/// Reads SPL Token and Token-2022 mints alike; for Token-2022 the extensions a honeypot would
+/// use are checked too.
+pub fn mint_safety_rejection(owner: &Pubkey, data: &[u8]) -> Option<String> {
+    if *owner != spl_token::ID && *owner != spl_token_2022::ID {
+        return Some(format!("mint is owned by {}, not a token program", owner));
+    }
+    let Ok(mint) = StateWithExtensions::<Mint>::unpack(data) else {
+        return Some("mint account could not be read".to_string());
+    };
+    if mint.base.freeze_authority.is_some() {
+        return Some("freeze authority is set".to_string());
+    }
+    for extension in mint.get_extension_types().unwrap_or_default() {
+        let reason = match extension {
+            ExtensionType::TransferHook => "transfer hook can block sells",
+            ExtensionType::NonTransferable => "token is non-transferable",
+            ExtensionType::PermanentDelegate => "permanent delegate can move holders' tokens",
+            ExtensionType::DefaultAccountState => {
+                let frozen = mint
+                    .get_extension::<DefaultAccountState>()
+                    .is_ok_and(|default| default.state == AccountState::Frozen as u8);
+                if !frozen {
+                    continue;
+                }
+                "new token accounts start frozen"
+            }
+            _ => continue,
+        };
+        return Some(reason.to_string());
+    }
+    None
+}
+
+static COPIED_FROM: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Record the target whose buy of `mint` is being copied, so its filter mode applies
+pub fn remember_copy_source(mint: &str, target: &str) {
+    COPIED_FROM.lock().unwrap_or_else(|e| e.into_inner()).insert(mint.to_string(), target.to_string());
+}
+
+/// Target the latest copied buy of `mint` came from
+pub fn copy_source(mint: &str) -> Option<String> {
+    COPIED_FROM.lock().unwrap_or_else(|e| e.into_inner()).get(mint).cloned()
+}
+
+/// Mirrors the pump.fun and PumpSwap buys and sells of target wallets
+///
+/// Buys are copied at `BUY_SELL_PERCENT` of the target's SOL amount. A target's sell is
+/// copied as the same share of our position as the share of its own bag it sold. Wallets
+/// imported from the leaderboard are followed like configured targets.
+///
+/// Every target trade feeds the `TargetWatch`; the buys of a paused target are not
+/// copied, its sells still are so open positions can exit.
+pub struct CopyTradeStrategy {
+    targets: HashSet<String>,
+    buy_sell_percent: f64,
+    /// Tokens each target holds per mint, as far as seen on the stream
+    target_holdings: HashMap<(String, String), u64>,
+    /// Mints bought by copying
+    copied: HashSet<String>,
+    last_silence_check: Option<Instant>,
+}
+
+impl CopyTradeStrategy {
+    pub fn new(config: &CopyTradingConfig) -> Self {
+        // without multi-target mode only the first wallet is followed
+        let targets = if config.multi_target_mode {
+            config.target_wallets.iter().cloned().collect()
+        } else {
+            config.target_wallets.iter().take(1).cloned().collect()
+        };
+        Self {
+            targets,
+            buy_sell_percent: config.buy_sell_percent,
+            target_holdings: HashMap::new(),
+            copied: HashSet::new(),
+            last_silence_check: None,
+        }
+    }
+}
+
+impl Strategy for CopyTradeStrategy {
+    fn name(&self) -> &'static str {
+        "copy"
+    }
+
+    fn stream_accounts(&self) -> Option<Vec<String>> {
+        let mut accounts: Vec<String> = self.targets.iter().cloned().collect();
+        accounts.extend(imported_targets().into_iter().filter(|wallet| !self.targets.contains(wallet)));
+        Some(accounts)
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Vec<StrategyAction> {
+        if self.last_silence_check.is_some_and(|at| now.saturating_duration_since(at) < SILENCE_CHECK_INTERVAL) {
+            return Vec::new();
+        }
+        self.last_silence_check = Some(now);
+        let targets = self.stream_accounts().unwrap_or_default();
+        for (wallet, anomaly) in target_watch().check_silence(&targets, now) {
+            alert(Alert::target(&wallet, &format!("Target {} {}", wallet, anomaly.describe())));
+        }
+        Vec::new()
+    }
+
+    fn on_trade_event(&mut self, event: &TradeEvent, at: Instant) -> Vec<StrategyAction> {
+        let user = event.user.to_string();
+        if !self.targets.contains(&user) && !imported_targets().contains(&user) {
+            return Vec::new();
+        }
+        let sol = event.sol_amount as f64 / LAMPORTS_PER_SOL as f64;
+        let paused = {
+            let mut watch = target_watch();
+            if let Some(anomaly) = watch.observe(&user, sol, at) {
+                let paused = if watch.is_paused(&user) { ", copying its buys is paused" } else { "" };
+                alert(Alert::target(&user, &format!("Target {} {}{}", user, anomaly.describe(), paused)));
+            }
+            watch.is_paused(&user)
+        };
+        let mint = event.mint.to_string();
+        let held = self.target_holdings.entry((user.clone(), mint.clone())).or_default();
+
+        if event.is_buy {
+            *held += event.token_amount;
+            if paused {
+                return Vec::new();
+            }
+            self.copied.insert(mint.clone());
+            remember_copy_source(&mint, &user);
+            return vec![StrategyAction::Buy {
+                mint,
+                sol_amount: Some(sol * self.buy_sell_percent / 100.0),
+                confidence: None,
+                reason: format!("copying buy of {}", user),
+            }];
+        }
+
+        // an unknown bag is treated as a full exit
+        let percent = if *held > 0 {
+            (event.token_amount as f64 / *held as f64 * 100.0).min(100.0)
+        } else {
+            100.0
+        };
+        *held = held.saturating_sub(event.token_amount);
+        if !self.copied.contains(&mint) {
+            return Vec::new();
+        }
+        if *held == 0 {
+            self.copied.remove(&mint);
+        }
+        vec![StrategyAction::Sell {
+            mint,
+            percent,
+            reason: format!("copying sell of {}", user),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program_pack::Pack;
+    use spl_token::solana_program::program_option::COption;
+
+    fn event(mint: Pubkey, user: Pubkey, is_buy: bool, sol_amount: u64, token_amount: u64) -> TradeEvent {
+        TradeEvent {
+            mint,
+            sol_amount,
+            token_amount,
+            is_buy,
+            user,
+            timestamp: 0,
+            virtual_sol_reserves: 0,
+            virtual_token_reserves: 0,
+        }
+    }
+
+    fn strategy(target: Pubkey) -> CopyTradeStrategy {
+        CopyTradeStrategy::new(&CopyTradingConfig {
+            enabled: true,
+            buy_sell_percent: 50.0,
+            target_wallets: vec![target.to_string()],
+            ..Default::default()
+        })
+    }
+
+    fn mint_account(freeze_authority: COption<Pubkey>) -> Vec<u8> {
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(
+            spl_token::state::Mint { supply: 1_000, decimals: 6, is_initialized: true, freeze_authority, ..Default::default() },
+            &mut data,
+        )
+        .unwrap();
+        data
+    }
+
+    fn watch(start: Instant) -> TargetWatch {
+        let settings = TargetWatchSettings { silence_hours: 1.0, size_jump_multiplier: 10.0, size_baseline_trades: 3, auto_pause: true };
+        TargetWatch::new(settings, start)
+    }
+
+    #[test]
+    fn test_copies_buys_scaled_by_percent() {
+        let (target, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut strategy = strategy(target);
+        let buy = strategy.on_trade_event(&event(mint, target, true, 2 * LAMPORTS_PER_SOL, 1000), Instant::now());
+        assert!(matches!(&buy[0], StrategyAction::Buy { sol_amount: Some(amount), .. } if (*amount - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_copies_partial_sells_as_percent_of_target_holding() {
+        let (target, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut strategy = strategy(target);
+        strategy.on_trade_event(&event(mint, target, true, 2 * LAMPORTS_PER_SOL, 1000), Instant::now());
+        let sell = strategy.on_trade_event(&event(mint, target, false, 0, 250), Instant::now());
+        assert!(matches!(&sell[0], StrategyAction::Sell { percent, .. } if (*percent - 25.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_ignores_other_wallets() {
+        let (target, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut strategy = strategy(target);
+        let other = Pubkey::new_unique();
+        assert!(strategy.on_trade_event(&event(mint, other, true, LAMPORTS_PER_SOL, 1), Instant::now()).is_empty());
+    }
+
+    #[test]
+    fn test_copy_filter_modes_per_target() {
+        let wallet = Pubkey::new_unique().to_string();
+        std::env::set_var("COPY_FILTER_TARGETS", format!("{}:none, bad:entry", wallet));
+        let settings = CopyFilterSettings::from_env();
+        assert_eq!(settings.mode_for(Some(&wallet)), CopyFilterMode::None);
+        assert_eq!(settings.mode_for(Some("other")), settings.default_mode);
+        // the unknown mode is skipped
+        assert_eq!(settings.per_target.len(), 1);
+    }
+
+    #[test]
+    fn test_mint_safety_rejects_freeze_authority() {
+        assert_eq!(mint_safety_rejection(&spl_token::ID, &mint_account(COption::None)), None);
+        assert_eq!(
+            mint_safety_rejection(&spl_token::ID, &mint_account(COption::Some(Pubkey::new_unique()))),
+            Some("freeze authority is set".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mint_safety_rejects_unknown_token_program() {
+        assert!(mint_safety_rejection(&Pubkey::new_unique(), &mint_account(COption::None)).is_some());
+    }
+
+    #[test]
+    fn test_target_watch_flags_silence_once_and_pauses() {
+        let start = Instant::now();
+        let mut watch = watch(start);
+        let targets = vec!["quiet".to_string(), "busy".to_string()];
+        watch.observe("busy", 0.5, start + Duration::from_secs(3000));
+
+        let later = start + Duration::from_secs(3600);
+        let silent = watch.check_silence(&targets, later);
+        assert_eq!(silent.len(), 1);
+        assert_eq!(silent[0].0, "quiet");
+        assert!(watch.check_silence(&targets, later + Duration::from_secs(60)).is_empty());
+        assert!(watch.is_paused("quiet"));
+    }
+
+    #[test]
+    fn test_target_watch_flags_size_jumps() {
+        let start = Instant::now();
+        let mut watch = watch(start);
+        for minute in 0..3 {
+            assert_eq!(watch.observe("busy", 0.5, start + Duration::from_secs(minute * 60)), None);
+        }
+        let jump = watch.observe("busy", 6.0, start + Duration::from_secs(600));
+        assert!(matches!(jump, Some(TargetAnomaly::SizeJump { average_sol, .. }) if (average_sol - 0.5).abs() < 1e-9));
+        assert!(watch.is_paused("busy"));
+    }
+
+    #[test]
+    fn test_paused_target_resumes_by_hand() {
+        let start = Instant::now();
+        let mut watch = watch(start);
+        for minute in 0..3 {
+            watch.observe("busy", 0.5, start + Duration::from_secs(minute * 60));
+        }
+        watch.observe("busy", 6.0, start + Duration::from_secs(600));
+        assert!(watch.resume("busy"));
+        assert!(!watch.is_paused("busy"));
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeRequest};
+
+use crate::common::logger::Logger;
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{get_pda, BondingCurveReserves};
+use crate::engine::position_marks::position_marks;
+use crate::engine::trade_executor::{price_from_reserves, TradeExecutor};
+use crate::services::geyser::{wait_to_reconnect, GeyserSource, GeyserSubscription};
+use crate::services::subscription::{ping_request, SubscriptionBuilder, CURVE_DATA_SLICE_LENGTH};
+
+/// Curve feed settings
+#[derive(Debug, Clone)]
+pub struct CurveFeedSettings {
+    pub enabled: bool,
+    /// Streamed reserves older than this are not trusted and the curve is read over RPC
+    pub max_age_ms: u64,
+}
+
+impl CurveFeedSettings {
+    /// Load curve feed settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("CURVE_FEED_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            max_age_ms: std::env::var("CURVE_FEED_MAX_AGE_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(5000),
+        }
+    }
+}
+
+/// Last streamed state of one bonding curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveState {
+    pub reserves: BondingCurveReserves,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    /// The curve has completed and the token migrated to PumpSwap
+    pub complete: bool,
+    pub slot: u64,
+    pub updated_at: Instant,
+}
+
+impl CurveState {
+    /// Bonding curve price of one whole token in SOL
+    pub fn price(&self) -> f64 {
+        price_from_reserves(&self.reserves)
+    }
+}
+
+/// Decode the reserve fields of a bonding curve account
+///
+/// Accepts both the sliced data sent by the stream (starting after the discriminator)
+/// and the full account data.
+pub fn decode_curve(data: &[u8]) -> Option<(BondingCurveReserves, u64, u64, bool)> {
+    let length = CURVE_DATA_SLICE_LENGTH as usize;
+    let fields = match data.len() {
+        n if n == length => data,
+        n if n >= length + 8 => &data[8..8 + length],
+        _ => return None,
+    };
+    let read = |index: usize| u64::from_le_bytes(fields[index * 8..index * 8 + 8].try_into().unwrap());
+    Some((
+        BondingCurveReserves {
+            virtual_token_reserves: read(0),
+            virtual_sol_reserves: read(1),
+        },
+        read(2),
+        read(3),
+        fields[40] != 0,
+    ))
+}
+
+/// Bonding curve states of held mints, kept current by account updates from the stream
+pub struct CurveFeed {
+    settings: CurveFeedSettings,
+    curves: HashMap<String, CurveState>,
+    /// Curve account -> mint
+    accounts: HashMap<String, String>,
+}
+
+impl CurveFeed {
+    /// Create a new curve feed
+    pub fn new(settings: CurveFeedSettings) -> Self {
+        Self {
+            settings,
+            curves: HashMap::new(),
+            accounts: HashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &CurveFeedSettings {
+        &self.settings
+    }
+
+    /// Track the curves of `mints` and forget the rest, returning the curve accounts to subscribe to
+    pub fn track(&mut self, mints: &[String]) -> Vec<String> {
+        self.accounts.retain(|_, mint| mints.contains(mint));
+        self.curves.retain(|mint, _| mints.contains(mint));
+        for mint in mints {
+            if self.accounts.values().any(|tracked| tracked == mint) {
+                continue;
+            }
+            let curve = Pubkey::from_str(mint)
+                .ok()
+                .and_then(|pubkey| get_pda(&pubkey, &program_ids().pump_fun_program).ok());
+            if let Some(curve) = curve {
+                self.accounts.insert(curve.to_string(), mint.clone());
+            }
+        }
+        let mut accounts: Vec<String> = self.accounts.keys().cloned().collect();
+        accounts.sort();
+        accounts
+    }
+
+    /// Apply an account update, returning the mint when its curve changed
+    ///
+    /// Updates from an older slot than the one already seen are ignored.
+    pub fn apply(&mut self, account: &str, data: &[u8], slot: u64, at: Instant) -> Option<String> {
+        let mint = self.accounts.get(account)?.clone();
+        let (reserves, real_token_reserves, real_sol_reserves, complete) = decode_curve(data)?;
+        if let Some(previous) = self.curves.get(&mint) {
+            if previous.slot > slot {
+                return None;
+            }
+        }
+        self.curves.insert(mint.clone(), CurveState {
+            reserves,
+            real_token_reserves,
+            real_sol_reserves,
+            complete,
+            slot,
+            updated_at: at,
+        });
+        Some(mint)
+    }
+
+    /// Streamed state of `mint`, if it is recent enough to trade on
+    pub fn fresh(&self, mint: &str) -> Option<CurveState> {
+        if !self.settings.enabled {
+            return None;
+        }
+        let state = self.curves.get(mint)?;
+        (state.updated_at.elapsed() <= Duration::from_millis(self.settings.max_age_ms)).then_some(*state)
+    }
+}
+
+static CURVE_FEED: LazyLock<Mutex<CurveFeed>> =
+    LazyLock::new(|| Mutex::new(CurveFeed::new(CurveFeedSettings::from_env())));
+
+static CURVE_UPDATED: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+/// Shared feed written by the curve stream and read by the executor
+pub fn curve_feed() -> std::sync::MutexGuard<'static, CurveFeed> {
+    CURVE_FEED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Wait for the next curve update of a held mint
+pub async fn curve_updated() {
+    CURVE_UPDATED.notified().await
+}
+
+/// Account updates of the held mints' curves, trimmed to the reserve fields
+fn subscribe_request(curves: &[String]) -> SubscribeRequest {
+    SubscriptionBuilder::new().curve_accounts("curves", curves).build()
+}
+
+/// Stream curve updates of held mints into the feed until the connection drops
+async fn run_stream(
+    executor: &Arc<TradeExecutor>,
+    source: &dyn GeyserSource,
+    logger: &Logger,
+) -> Result<()> {
+    let GeyserSubscription { sink: mut subscribe_tx, mut stream } = source.subscribe().await?;
+
+    let mut subscribed: Option<Vec<String>> = None;
+    let mut sync = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        tokio::select! {
+            _ = sync.tick() => {
+                let mut held = executor.open_positions();
+                held.sort();
+                let curves = source.limit_accounts(&curve_feed().track(&held));
+                // a new request replaces the previous filters on the same stream
+                if subscribed.as_ref() != Some(&curves) {
+                    subscribe_tx.send(subscribe_request(&curves)).await?;
+                    logger.debug(format!("Streaming {} bonding curves", curves.len()));
+                    subscribed = Some(curves);
+                }
+            }
+            message = stream.next() => {
+                let update = match message {
+                    Some(Ok(update)) => update,
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(anyhow!("Stream closed")),
+                };
+                match update.update_oneof {
+                    Some(UpdateOneof::Account(update)) => {
+                        let Some(account) = update.account else {
+                            continue;
+                        };
+                        let Ok(pubkey) = Pubkey::try_from(account.pubkey.as_slice()) else {
+                            continue;
+                        };
+                        let changed = curve_feed().apply(&pubkey.to_string(), &account.data, update.slot, Instant::now());
+                        let Some(mint) = changed else {
+                            continue;
+                        };
+                        let state = curve_feed().fresh(&mint);
+                        match state {
+                            Some(state) if state.complete => logger.log(format!("Bonding curve of {} completed", mint)),
+                            Some(state) => position_marks().mark(&mint, state.price(), state.updated_at),
+                            None => {}
+                        }
+                        CURVE_UPDATED.notify_one();
+                    }
+                    Some(UpdateOneof::Ping(_)) => {
+                        subscribe_tx.send(ping_request()).await?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Start streaming bonding curve updates for held mints
+///
+/// The executor prices held mints from the feed instead of RPC while it is fresh,
+/// and the position manager re-checks exits as soon as an update arrives.
+pub async fn start_curve_feed_system(
+    executor: Arc<TradeExecutor>,
+    source: Arc<dyn GeyserSource>,
+    logger: Logger,
+) {
+    tokio::spawn(async move {
+        loop {
+            let result = run_stream(&executor, source.as_ref(), &logger).await;
+            wait_to_reconnect(source.as_ref(), "Curve", result, &logger).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve_data(virtual_token: u64, virtual_sol: u64, complete: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [virtual_token, virtual_sol, 1, 2, 3] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(complete as u8);
+        data
+    }
+
+    #[test]
+    fn test_updates_are_decoded_and_stale_slots_ignored() {
+        let mint = Pubkey::new_unique().to_string();
+        let mut feed = CurveFeed::new(CurveFeedSettings { enabled: true, max_age_ms: 5000 });
+        let curves = feed.track(std::slice::from_ref(&mint));
+        assert_eq!(curves.len(), 1);
+
+        let now = Instant::now();
+        assert_eq!(feed.apply(&curves[0], &curve_data(1_000, 30, false), 10, now), Some(mint.clone()));
+        assert_eq!(feed.apply(&curves[0], &curve_data(2_000, 60, true), 9, now), None);
+        assert_eq!(feed.apply("unknown", &curve_data(1_000, 30, false), 11, now), None);
+
+        let state = feed.fresh(&mint).unwrap();
+        assert_eq!(state.reserves.virtual_token_reserves, 1_000);
+        assert_eq!(state.real_token_reserves, 1);
+        assert!(!state.complete);
+
+        assert!(feed.track(&[]).is_empty());
+        assert!(feed.fresh(&mint).is_none());
+    }
+}
@@ -0,0 +1,602 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use futures_util::{SinkExt, StreamExt};
+use tokio::time::Instant;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::native_token::LAMPORTS_PER_SOL;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeUpdateTransactionInfo, TokenBalance, TransactionStatusMeta,
+};
+
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::dex::launchlab::launches;
+use crate::dex::pump_fun::decoder::{account_keys, decode_transaction, PumpEvent};
+use crate::dex::pump_fun::get_token_creator;
+use crate::engine::execution_queue::{submit, Priority};
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::dedup::{DedupSettings, SignatureCache};
+use crate::services::geyser::{wait_to_reconnect, GeyserSource, GeyserSubscription};
+use crate::services::subscription::{ping_request, SubscriptionBuilder};
+use crate::services::telegram::{sol_to_lamports, TelegramService};
+
+/// Wormhole core and token bridge, deBridge DLN source and Allbridge Core
+const DEFAULT_BRIDGE_PROGRAMS: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth,wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb,src5qyZHqTqecJV4aY6Cb6zDZLMDzrDKKezs22MPHr4,BrdgN2RPzEMWF96ZbnnJaUtQDQx7VRXYaHHbYCBvceWB";
+
+/// Dev-wallet dump watch settings
+#[derive(Debug, Clone)]
+pub struct DevWatchSettings {
+    pub enabled: bool,
+    /// Exit once the creator has sold or moved this % of its peak holdings
+    pub dump_threshold_percent: f64,
+    /// How often the watched creator set is re-synced with open positions, in milliseconds
+    pub sync_interval_ms: u64,
+    /// Alert when a creator bridges SOL out, funds new wallets or launches another token
+    pub activity_alerts: bool,
+    /// Also sell the creator's held tokens on such activity
+    pub activity_exit: bool,
+    /// Smallest transfer into an empty wallet that counts as funding it, in SOL
+    pub funding_min_sol: f64,
+    /// Programs whose use counts as bridging funds out
+    pub bridge_programs: Vec<String>,
+}
+
+impl DevWatchSettings {
+    /// Load dev watch settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("DEV_DUMP_WATCH_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            dump_threshold_percent: std::env::var("DEV_DUMP_THRESHOLD_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            sync_interval_ms: std::env::var("DEV_WATCH_SYNC_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1000),
+            activity_alerts: std::env::var("DEV_ACTIVITY_ALERTS")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            activity_exit: std::env::var("DEV_ACTIVITY_EXIT")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            funding_min_sol: std::env::var("DEV_FUNDING_MIN_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.1),
+            bridge_programs: std::env::var("DEV_BRIDGE_PROGRAMS")
+                .ok()
+                .filter(|programs| !programs.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_BRIDGE_PROGRAMS.to_string())
+                .split(',')
+                .map(|program| program.trim().to_string())
+                .filter(|program| !program.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Token balance of one owner before and after a transaction, borrowed from the update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceChange<'a> {
+    pub mint: &'a str,
+    pub owner: &'a str,
+    pub pre: u64,
+    pub post: u64,
+}
+
+/// Pair up pre and post token balances of a transaction by account index
+pub fn token_balance_changes(meta: &TransactionStatusMeta) -> Vec<BalanceChange<'_>> {
+    fn amount(balance: &TokenBalance) -> u64 {
+        balance.ui_token_amount
+            .as_ref()
+            .and_then(|amount| amount.amount.parse::<u64>().ok())
+            .unwrap_or(0)
+    }
+
+    // a transaction touches a handful of token accounts, so a linear scan beats hashing
+    let mut indexes: Vec<u32> = Vec::with_capacity(meta.pre_token_balances.len());
+    let mut changes: Vec<BalanceChange> = Vec::with_capacity(meta.pre_token_balances.len());
+    for balance in &meta.pre_token_balances {
+        indexes.push(balance.account_index);
+        changes.push(BalanceChange {
+            mint: &balance.mint,
+            owner: &balance.owner,
+            pre: amount(balance),
+            post: 0,
+        });
+    }
+    for balance in &meta.post_token_balances {
+        match indexes.iter().position(|index| *index == balance.account_index) {
+            Some(position) => changes[position].post = amount(balance),
+            None => {
+                indexes.push(balance.account_index);
+                changes.push(BalanceChange {
+                    mint: &balance.mint,
+                    owner: &balance.owner,
+                    pre: 0,
+                    post: amount(balance),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// A creator selling or moving enough of its tokens to trigger an exit
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevDump {
+    pub mint: String,
+    pub creator: String,
+    /// Share of the creator's peak holdings that is gone, in percent
+    pub dumped_percent: f64,
+}
+
+/// Something a watched creator did with its wallet besides selling
+#[derive(Debug, Clone, PartialEq)]
+pub enum DevActivity {
+    /// Sent a transaction through a bridge program
+    Bridge { program: String, lamports_out: u64 },
+    /// Sent SOL to wallets that held none before
+    FundedWallets { wallets: Vec<String>, lamports: u64 },
+    /// Launched another token
+    NewToken { mint: String },
+}
+
+impl DevActivity {
+    pub fn describe(&self) -> String {
+        let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL as f64;
+        match self {
+            Self::Bridge { program, lamports_out } => format!("bridged out through {} ({:.4} SOL left the wallet)", program, sol(*lamports_out)),
+            Self::FundedWallets { wallets, lamports } => format!("funded {} new wallets with {:.4} SOL", wallets.len(), sol(*lamports)),
+            Self::NewToken { mint } => format!("launched another token {}", mint),
+        }
+    }
+}
+
+/// What `creator` did in a transaction it signed
+///
+/// Failed transactions and ones the creator only appears in, e.g. as a receiver, report nothing.
+pub fn creator_activity(info: &SubscribeUpdateTransactionInfo, creator: &Pubkey, settings: &DevWatchSettings) -> Vec<DevActivity> {
+    let (Some(message), Some(meta)) = (info.transaction.as_ref().and_then(|tx| tx.message.as_ref()), info.meta.as_ref()) else {
+        return Vec::new();
+    };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+    let keys = account_keys(info);
+    let signers = message.header.as_ref().map(|header| header.num_required_signatures as usize).unwrap_or(1);
+    let Some(index) = keys.iter().take(signers).position(|key| key == creator) else {
+        return Vec::new();
+    };
+    let balance = |balances: &[u64], index: usize| balances.get(index).copied().unwrap_or(0);
+
+    let mut activity = Vec::new();
+    if let Some(program) = keys.iter().find(|key| settings.bridge_programs.contains(&key.to_string())) {
+        let lamports_out = balance(&meta.pre_balances, index).saturating_sub(balance(&meta.post_balances, index));
+        activity.push(DevActivity::Bridge { program: program.to_string(), lamports_out });
+    }
+
+    // new token accounts are funded with rent too, so they are left out
+    let token_accounts: Vec<usize> = meta.post_token_balances.iter().map(|balance| balance.account_index as usize).collect();
+    let min_funding = sol_to_lamports(settings.funding_min_sol).max(1);
+    let funded: Vec<(String, u64)> = (0..keys.len())
+        .filter(|other| *other != index && !token_accounts.contains(other))
+        .filter(|other| balance(&meta.pre_balances, *other) == 0)
+        .map(|other| (keys[other].to_string(), balance(&meta.post_balances, other)))
+        .filter(|(_, lamports)| *lamports >= min_funding)
+        .collect();
+    if !funded.is_empty() {
+        activity.push(DevActivity::FundedWallets {
+            lamports: funded.iter().map(|(_, lamports)| lamports).sum(),
+            wallets: funded.into_iter().map(|(wallet, _)| wallet).collect(),
+        });
+    }
+
+    for event in decode_transaction(info).events {
+        if let PumpEvent::Create(create) = event {
+            if create.user == *creator || create.creator == Some(*creator) {
+                activity.push(DevActivity::NewToken { mint: create.mint.to_string() });
+            }
+        }
+    }
+    for launch in launches(info).into_iter().filter(|launch| launch.creator == *creator) {
+        activity.push(DevActivity::NewToken { mint: launch.mint.to_string() });
+    }
+    activity
+}
+
+/// Activity of a watched creator and the held tokens it concerns
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatorAlert {
+    pub creator: String,
+    pub activity: DevActivity,
+    /// Held mints of the creator
+    pub mints: Vec<String>,
+    /// Held mints to sell now; empty unless `DEV_ACTIVITY_EXIT` is on
+    pub exits: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct WatchedCreator {
+    creator: String,
+    /// Highest balance seen for the creator, the base the dump is measured against
+    peak_balance: u64,
+}
+
+/// Tracks the creator wallets of held tokens and flags when one of them dumps
+pub struct DevWatcher {
+    settings: DevWatchSettings,
+    watched: HashMap<String, WatchedCreator>,
+    /// Mints whose exit already fired, so one dump triggers one sell
+    triggered: HashSet<String>,
+}
+
+impl DevWatcher {
+    /// Create a new dev watcher
+    pub fn new(settings: DevWatchSettings) -> Self {
+        Self {
+            settings,
+            watched: HashMap::new(),
+            triggered: HashSet::new(),
+        }
+    }
+
+    /// Start watching `creator` for `mint`
+    pub fn watch(&mut self, mint: &str, creator: &str) {
+        self.watched.entry(mint.to_string()).or_insert_with(|| WatchedCreator {
+            creator: creator.to_string(),
+            peak_balance: 0,
+        });
+    }
+
+    /// Stop watching mints that are no longer held
+    pub fn retain(&mut self, held: &[String]) {
+        self.watched.retain(|mint, _| held.contains(mint));
+        self.triggered.retain(|mint| held.contains(mint));
+    }
+
+    pub fn is_watched(&self, mint: &str) -> bool {
+        self.watched.contains_key(mint)
+    }
+
+    /// Creator wallets to subscribe to, sorted so changes are easy to detect
+    pub fn creators(&self) -> Vec<String> {
+        let mut creators: Vec<String> = self.watched.values().map(|w| w.creator.clone()).collect();
+        creators.sort();
+        creators.dedup();
+        creators
+    }
+
+    /// Held mints launched by `creator`
+    fn mints_of(&self, creator: &str) -> Vec<String> {
+        let mut mints: Vec<String> = self.watched
+            .iter()
+            .filter(|(_, watched)| watched.creator == creator)
+            .map(|(mint, _)| mint.clone())
+            .collect();
+        mints.sort();
+        mints
+    }
+
+    /// Feed a transaction of the watched creators; returns what each creator that signed it did
+    ///
+    /// A mint is only sold once, whether for a dump or for activity.
+    pub fn observe_activity(&mut self, info: &SubscribeUpdateTransactionInfo) -> Vec<CreatorAlert> {
+        if !self.settings.activity_alerts {
+            return Vec::new();
+        }
+        let mut alerts = Vec::new();
+        for creator in self.creators() {
+            let Ok(creator_pubkey) = Pubkey::from_str(&creator) else {
+                continue;
+            };
+            let mints = self.mints_of(&creator);
+            for activity in creator_activity(info, &creator_pubkey, &self.settings) {
+                if matches!(&activity, DevActivity::NewToken { mint } if mints.contains(mint)) {
+                    continue;
+                }
+                let exits: Vec<String> = match self.settings.activity_exit {
+                    true => mints.iter().filter(|mint| self.triggered.insert(mint.to_string())).cloned().collect(),
+                    false => Vec::new(),
+                };
+                alerts.push(CreatorAlert { creator: creator.clone(), activity, mints: mints.clone(), exits });
+            }
+        }
+        alerts
+    }
+
+    /// Feed the balance changes of a transaction; returns the dumps that crossed the threshold
+    pub fn observe(&mut self, changes: &[BalanceChange<'_>]) -> Vec<DevDump> {
+        let mut dumps = Vec::new();
+        for change in changes {
+            let Some(watched) = self.watched.get_mut(change.mint) else {
+                continue;
+            };
+            if watched.creator != change.owner {
+                continue;
+            }
+            watched.peak_balance = watched.peak_balance.max(change.pre).max(change.post);
+            if watched.peak_balance == 0 || change.post >= change.pre || self.triggered.contains(change.mint) {
+                continue;
+            }
+
+            let dumped_percent = (watched.peak_balance - change.post) as f64 / watched.peak_balance as f64 * 100.0;
+            if dumped_percent >= self.settings.dump_threshold_percent {
+                self.triggered.insert(change.mint.to_string());
+                dumps.push(DevDump {
+                    mint: change.mint.to_string(),
+                    creator: change.owner.to_string(),
+                    dumped_percent,
+                });
+            }
+        }
+        dumps
+    }
+}
+
+/// Any transaction of the watched creators: dumps can be sells or plain transfers
+fn subscribe_request(creators: &[String]) -> SubscribeRequest {
+    SubscriptionBuilder::new()
+        .transactions("dev_watch", creators, &[])
+        .build()
+}
+
+/// Resolve creators for newly held mints and drop the ones that were sold
+async fn sync_watched(executor: &TradeExecutor, watcher: &Mutex<DevWatcher>, logger: &Logger) {
+    let held = executor.open_positions();
+    let new_mints: Vec<String> = match watcher.lock() {
+        Ok(mut watcher) => {
+            watcher.retain(&held);
+            held.iter().filter(|mint| !watcher.is_watched(mint)).cloned().collect()
+        }
+        Err(_) => return,
+    };
+
+    for mint in new_mints {
+        let Ok(mint_pubkey) = Pubkey::from_str(&mint) else {
+            continue;
+        };
+        match get_token_creator(executor.rpc_client(), &mint_pubkey).await {
+            Ok(creator) => {
+                logger.debug(format!("Watching creator {} of {}", creator, mint));
+                if let Ok(mut watcher) = watcher.lock() {
+                    watcher.watch(&mint, &creator.to_string());
+                }
+            }
+            Err(e) => logger.debug(format!("Failed to resolve creator of {}: {}", mint, e)),
+        }
+    }
+}
+
+/// Stream creator transactions until the connection drops
+async fn run_stream(
+    executor: &Arc<TradeExecutor>,
+    watcher: &Arc<Mutex<DevWatcher>>,
+    source: &dyn GeyserSource,
+    sync_interval: Duration,
+    seen: &mut SignatureCache,
+    alerts: &Option<(Arc<TelegramService>, String)>,
+    logger: &Logger,
+) -> Result<()> {
+    let GeyserSubscription { sink: mut subscribe_tx, mut stream } = source.subscribe().await?;
+
+    let mut subscribed: Option<Vec<String>> = None;
+    let mut sync = tokio::time::interval(sync_interval);
+    loop {
+        tokio::select! {
+            _ = sync.tick() => {
+                sync_watched(executor, watcher, logger).await;
+                let creators = match watcher.lock() {
+                    Ok(watcher) => source.limit_accounts(&watcher.creators()),
+                    Err(_) => continue,
+                };
+                // a new request replaces the previous filters on the same stream
+                if subscribed.as_ref() != Some(&creators) {
+                    subscribe_tx.send(subscribe_request(&creators)).await?;
+                    logger.debug(format!("Subscribed to {} creator wallets", creators.len()));
+                    subscribed = Some(creators);
+                }
+            }
+            message = stream.next() => {
+                let update = match message {
+                    Some(Ok(update)) => update,
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(anyhow!("Stream closed")),
+                };
+                match update.update_oneof {
+                    Some(UpdateOneof::Transaction(tx)) => {
+                        let Some(info) = tx.transaction else {
+                            continue;
+                        };
+                        if !seen.first_seen(&info.signature, Instant::now()) {
+                            continue;
+                        }
+                        let Some(meta) = info.meta.as_ref() else {
+                            continue;
+                        };
+                        let (dumps, activity) = match watcher.lock() {
+                            Ok(mut watcher) => (watcher.observe(&token_balance_changes(meta)), watcher.observe_activity(&info)),
+                            Err(_) => continue,
+                        };
+                        for alert in activity {
+                            let message = format!(
+                                "Creator {} of {} {}",
+                                alert.creator, alert.mints.join(", "), alert.activity.describe()
+                            );
+                            logger.log(format!("[DEV ACTIVITY] => {}", message).yellow().bold().to_string());
+                            for mint in alert.exits {
+                                record_decision(Decision::Sell, &mint, ReasonCode::CreatorActivity, &message);
+                                let executor = executor.clone();
+                                let logger = logger.clone();
+                                submit(Priority::Exit, "dev activity exit", async move {
+                                    if let Err(e) = executor.sell_all_now(&mint).await {
+                                        logger.error(format!("Dev activity exit failed for {}: {}", mint, e));
+                                    }
+                                });
+                            }
+                            if let Some((telegram, chat_id)) = alerts {
+                                let (telegram, chat_id, logger) = (telegram.clone(), chat_id.clone(), logger.clone());
+                                tokio::spawn(async move {
+                                    if let Err(e) = telegram.send_message(&chat_id, &format!("⚠️ {}", message), "HTML").await {
+                                        logger.error(format!("Failed to send dev activity alert: {}", e));
+                                    }
+                                });
+                            }
+                        }
+                        for dump in dumps {
+                            logger.log(format!(
+                                "[DEV DUMP] => Creator {} of {} dumped {:.1}% of its tokens, exiting now",
+                                dump.creator, dump.mint, dump.dumped_percent
+                            ).red().bold().to_string());
+                            record_decision(
+                                Decision::Sell,
+                                &dump.mint,
+                                ReasonCode::CreatorDump,
+                                &format!("creator dumped {:.1}% of its tokens", dump.dumped_percent),
+                            );
+                            let executor = executor.clone();
+                            let logger = logger.clone();
+                            submit(Priority::Exit, "dev dump exit", async move {
+                                if let Err(e) = executor.sell_all_now(&dump.mint).await {
+                                    logger.error(format!("Dev dump exit failed for {}: {}", dump.mint, e));
+                                }
+                            });
+                        }
+                    }
+                    Some(UpdateOneof::Ping(_)) => {
+                        subscribe_tx.send(ping_request()).await?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Start watching the creators of held tokens over the gRPC stream
+///
+/// When a creator sells or transfers `DEV_DUMP_THRESHOLD_PERCENT` of its tokens, the
+/// whole position is sold in one transaction, bypassing the position manager.
+/// Bridging, funding new wallets and new launches by the creator are alerted on,
+/// and sold on with `DEV_ACTIVITY_EXIT`.
+pub async fn start_dev_watch_system(
+    executor: Arc<TradeExecutor>,
+    source: Arc<dyn GeyserSource>,
+    telegram: Option<Arc<TelegramService>>,
+    telegram_chat_id: String,
+    logger: Logger,
+) -> Arc<Mutex<DevWatcher>> {
+    let settings = DevWatchSettings::from_env();
+    let sync_interval = Duration::from_millis(settings.sync_interval_ms.max(100));
+    let watcher = Arc::new(Mutex::new(DevWatcher::new(settings)));
+
+    let watcher_clone = watcher.clone();
+    tokio::spawn(async move {
+        let mut seen = SignatureCache::new(DedupSettings::from_env());
+        let alerts = telegram.map(|telegram| (telegram, telegram_chat_id));
+        loop {
+            let result = run_stream(&executor, &watcher_clone, source.as_ref(), sync_interval, &mut seen, &alerts, &logger).await;
+            wait_to_reconnect(source.as_ref(), "Dev watch", result, &logger).await;
+        }
+    });
+
+    watcher
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{Message, MessageHeader, Transaction, UiTokenAmount};
+
+    fn settings() -> DevWatchSettings {
+        DevWatchSettings {
+            enabled: true,
+            dump_threshold_percent: 20.0,
+            sync_interval_ms: 1000,
+            activity_alerts: true,
+            activity_exit: true,
+            funding_min_sol: 0.1,
+            bridge_programs: vec![],
+        }
+    }
+
+    fn watcher() -> DevWatcher {
+        let mut watcher = DevWatcher::new(settings());
+        watcher.watch("mint", "dev");
+        watcher
+    }
+
+    fn change(owner: &str, pre: u64, post: u64) -> BalanceChange<'_> {
+        BalanceChange { mint: "mint", owner, pre, post }
+    }
+
+    #[test]
+    fn test_dump_measured_against_peak_and_fires_once() {
+        let mut watcher = watcher();
+        assert!(watcher.observe(&[change("other", 1000, 0)]).is_empty());
+        assert!(watcher.observe(&[change("dev", 1000, 900)]).is_empty());
+        // small sells add up against the peak balance
+        let dumps = watcher.observe(&[change("dev", 900, 750)]);
+        assert_eq!(dumps.len(), 1);
+        assert!((dumps[0].dumped_percent - 25.0).abs() < 1e-9);
+        assert!(watcher.observe(&[change("dev", 750, 0)]).is_empty());
+
+        watcher.retain(&[]);
+        assert!(watcher.creators().is_empty());
+    }
+
+    #[test]
+    fn test_creator_funding_new_wallets() {
+        let (creator, fresh, known) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let info = SubscribeUpdateTransactionInfo {
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                    account_keys: [creator, fresh, known].iter().map(|key| key.to_bytes().to_vec()).collect(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(TransactionStatusMeta {
+                pre_balances: vec![10_000_000_000, 0, 1_000_000_000],
+                post_balances: vec![7_000_000_000, 2_000_000_000, 2_000_000_000],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut watcher = DevWatcher::new(settings());
+        watcher.watch("mint", &creator.to_string());
+        let alerts = watcher.observe_activity(&info);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].activity, DevActivity::FundedWallets { wallets: vec![fresh.to_string()], lamports: 2_000_000_000 });
+        assert_eq!(alerts[0].exits, vec!["mint".to_string()]);
+        // the position is only sold once
+        assert!(watcher.observe_activity(&info)[0].exits.is_empty());
+        // a receiver that did not sign reports nothing
+        assert!(creator_activity(&info, &fresh, &settings()).is_empty());
+    }
+
+    #[test]
+    fn test_token_balance_changes() {
+        let balance = |index: u32, amount: &str| TokenBalance {
+            account_index: index,
+            mint: "mint".to_string(),
+            owner: "dev".to_string(),
+            ui_token_amount: Some(UiTokenAmount { amount: amount.to_string(), ..Default::default() }),
+            ..Default::default()
+        };
+        let meta = TransactionStatusMeta {
+            pre_token_balances: vec![balance(1, "500")],
+            post_token_balances: vec![balance(1, "100")],
+            ..Default::default()
+        };
+        assert_eq!(token_balance_changes(&meta), vec![change("dev", 500, 100)]);
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::BinaryHeap;
+use std::cmp::Ordering;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+use colored::Colorize;
+use tokio::sync::Notify;
+
+use crate::common::logger::Logger;
+use crate::services::metrics::metrics;
+
+/// How urgent a queued trade task is, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Housekeeping that can wait, like closing accounts
+    Background,
+    /// Strategy buys of tokens that are already trading
+    Buy,
+    /// Strategy sells, like copy exits and take-profit or stop-loss hits
+    ///
+    /// Above buys, so a burst of buys never sheds the exit of a held position.
+    Sell,
+    /// Exits forced by a risk signal, like a creator dumping
+    Exit,
+    /// Buys of a token in its launch transaction
+    LaunchBuy,
+}
+
+impl Priority {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Background => "background",
+            Priority::Sell => "sell",
+            Priority::Buy => "buy",
+            Priority::Exit => "exit",
+            Priority::LaunchBuy => "launch_buy",
+        }
+    }
+}
+
+/// What to do with a task that arrives while the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShedPolicy {
+    /// Drop the newest task of the lowest queued priority if the new task outranks it
+    DropLowest,
+    /// Always turn the new task away
+    RejectNew,
+}
+
+impl ShedPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "drop_lowest" => Some(ShedPolicy::DropLowest),
+            "reject_new" => Some(ShedPolicy::RejectNew),
+            _ => None,
+        }
+    }
+}
+
+/// Execution queue settings
+#[derive(Debug, Clone)]
+pub struct ExecutionQueueSettings {
+    /// Tasks waiting at most; beyond it the shed policy applies
+    pub capacity: usize,
+    /// Tasks running at once
+    pub workers: usize,
+    pub shed_policy: ShedPolicy,
+}
+
+impl ExecutionQueueSettings {
+    /// Load execution queue settings from environment variables
+    pub fn from_env() -> Self {
+        let shed_policy = std::env::var("EXEC_QUEUE_SHED_POLICY").unwrap_or_else(|_| "drop_lowest".to_string());
+        Self {
+            capacity: std::env::var("EXEC_QUEUE_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(256),
+            workers: std::env::var("EXEC_QUEUE_WORKERS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(8),
+            shed_policy: ShedPolicy::parse(&shed_policy).unwrap_or_else(|| {
+                eprintln!("Ignoring EXEC_QUEUE_SHED_POLICY: unknown policy '{}'", shed_policy);
+                ShedPolicy::DropLowest
+            }),
+        }
+    }
+}
+
+struct Entry<T> {
+    priority: Priority,
+    seq: u64,
+    item: T,
+}
+
+// higher priority first, then first in first out
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+/// Outcome of pushing onto a full or non-full queue
+#[derive(Debug, PartialEq)]
+pub enum Push<T> {
+    Queued,
+    /// Queued after dropping this lower priority task
+    Shed(Priority, T),
+    /// Not queued
+    Rejected(T),
+}
+
+/// Bounded priority queue, FIFO within a priority
+pub struct BoundedQueue<T> {
+    heap: BinaryHeap<Entry<T>>,
+    capacity: usize,
+    policy: ShedPolicy,
+    seq: u64,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize, policy: ShedPolicy) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            capacity: capacity.max(1),
+            policy,
+            seq: 0,
+        }
+    }
+
+    pub fn push(&mut self, priority: Priority, item: T) -> Push<T> {
+        self.seq += 1;
+        let entry = Entry { priority, seq: self.seq, item };
+        if self.heap.len() < self.capacity {
+            self.heap.push(entry);
+            return Push::Queued;
+        }
+        if self.policy == ShedPolicy::RejectNew {
+            return Push::Rejected(entry.item);
+        }
+        // the full queue is small, so finding its lowest entry linearly is fine
+        let mut entries = std::mem::take(&mut self.heap).into_vec();
+        let lowest = entries.iter().enumerate().min_by(|(_, a), (_, b)| {
+            a.priority.cmp(&b.priority).then_with(|| b.seq.cmp(&a.seq))
+        }).map(|(index, _)| index);
+        let result = match lowest {
+            Some(index) if entries[index].priority < priority => {
+                let shed = entries.swap_remove(index);
+                entries.push(entry);
+                Push::Shed(shed.priority, shed.item)
+            }
+            _ => Push::Rejected(entry.item),
+        };
+        self.heap = entries.into();
+        result
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|entry| entry.item)
+    }
+
+    /// Drop every queued task of the given priorities, returning how many were dropped
+    pub fn drop_priorities(&mut self, priorities: &[Priority]) -> usize {
+        let before = self.heap.len();
+        self.heap.retain(|entry| !priorities.contains(&entry.priority));
+        before - self.heap.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Trade tasks waiting for one of a fixed number of workers
+pub struct ExecutionQueue {
+    queue: Mutex<BoundedQueue<(&'static str, Task)>>,
+    ready: Notify,
+    logger: Logger,
+}
+
+impl ExecutionQueue {
+    fn submit(&self, priority: Priority, label: &'static str, task: Task) {
+        let pushed = self.queue.lock().unwrap_or_else(|e| e.into_inner()).push(priority, (label, task));
+        match pushed {
+            Push::Queued => {}
+            Push::Shed(shed, (shed_label, _)) => {
+                metrics().execution_shed.with_label_values(&[shed.name()]).inc();
+                self.logger.log(format!(
+                    "Queue full, dropped queued {} task ({}) for a {} task",
+                    shed.name(), shed_label, priority.name()
+                ).yellow().to_string());
+            }
+            Push::Rejected(_) => {
+                metrics().execution_shed.with_label_values(&[priority.name()]).inc();
+                self.logger.log(format!("Queue full, dropped {} task ({})", priority.name(), label).yellow().to_string());
+                return;
+            }
+        }
+        self.ready.notify_one();
+    }
+
+    async fn next(&self) -> Task {
+        loop {
+            if let Some((_, task)) = self.queue.lock().unwrap_or_else(|e| e.into_inner()).pop() {
+                return task;
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    fn drop_priorities(&self, priorities: &[Priority]) -> usize {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner()).drop_priorities(priorities)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+static EXECUTION_QUEUE: OnceLock<Arc<ExecutionQueue>> = OnceLock::new();
+
+/// Start the workers draining the execution queue
+pub fn start_execution_queue(settings: ExecutionQueueSettings, logger: Logger) {
+    let queue = Arc::new(ExecutionQueue {
+        queue: Mutex::new(BoundedQueue::new(settings.capacity, settings.shed_policy)),
+        ready: Notify::new(),
+        logger: logger.clone(),
+    });
+    if EXECUTION_QUEUE.set(queue.clone()).is_err() {
+        return;
+    }
+    logger.log(format!(
+        "Executing trades on {} workers, up to {} queued ({:?} when full)",
+        settings.workers.max(1), settings.capacity.max(1), settings.shed_policy
+    ));
+    for _ in 0..settings.workers.max(1) {
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            loop {
+                queue.next().await.await;
+            }
+        });
+    }
+}
+
+/// Run a trade task through the execution queue
+///
+/// Without a started queue (CLI commands, tests) the task is spawned right away.
+pub fn submit(priority: Priority, label: &'static str, task: impl Future<Output = ()> + Send + 'static) {
+    match EXECUTION_QUEUE.get() {
+        Some(queue) => queue.submit(priority, label, Box::pin(task)),
+        None => {
+            tokio::spawn(task);
+        }
+    }
+}
+
+/// Tasks waiting in the execution queue
+pub fn queued_tasks() -> usize {
+    EXECUTION_QUEUE.get().map(|queue| queue.len()).unwrap_or(0)
+}
+
+/// Drop the buys still waiting in the execution queue, returning how many were dropped
+///
+/// Buys already picked up by a worker are not stopped.
+pub fn cancel_queued_buys() -> usize {
+    EXECUTION_QUEUE
+        .get()
+        .map(|queue| queue.drop_priorities(&[Priority::Buy, Priority::LaunchBuy]))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<T>(queue: &mut BoundedQueue<T>) -> Vec<T> {
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    #[test]
+    fn test_pops_highest_priority_first() {
+        let mut queue = BoundedQueue::new(5, ShedPolicy::DropLowest);
+        queue.push(Priority::Background, "cleanup");
+        queue.push(Priority::Buy, "buy");
+        queue.push(Priority::Sell, "sell");
+        queue.push(Priority::LaunchBuy, "launch");
+        queue.push(Priority::Exit, "exit");
+        assert_eq!(drain(&mut queue), vec!["launch", "exit", "sell", "buy", "cleanup"]);
+    }
+
+    #[test]
+    fn test_fifo_within_a_priority() {
+        let mut queue = BoundedQueue::new(3, ShedPolicy::DropLowest);
+        queue.push(Priority::Sell, "first");
+        queue.push(Priority::Sell, "second");
+        queue.push(Priority::Sell, "third");
+        assert_eq!(drain(&mut queue), vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn test_drop_lowest_sheds_for_higher_priority() {
+        let mut queue = BoundedQueue::new(3, ShedPolicy::DropLowest);
+        assert_eq!(queue.push(Priority::Sell, "sell"), Push::Queued);
+        assert_eq!(queue.push(Priority::Background, "cleanup"), Push::Queued);
+        assert_eq!(queue.push(Priority::Buy, "buy"), Push::Queued);
+        assert_eq!(queue.push(Priority::LaunchBuy, "launch"), Push::Shed(Priority::Background, "cleanup"));
+        assert_eq!(queue.push(Priority::Exit, "exit"), Push::Shed(Priority::Buy, "buy"));
+        assert_eq!(drain(&mut queue), vec!["launch", "exit", "sell"]);
+    }
+
+    #[test]
+    fn test_drop_lowest_rejects_when_nothing_lower() {
+        let mut queue = BoundedQueue::new(2, ShedPolicy::DropLowest);
+        queue.push(Priority::Sell, "sell");
+        queue.push(Priority::Buy, "buy");
+        assert_eq!(queue.push(Priority::Buy, "buy 2"), Push::Rejected("buy 2"));
+    }
+
+    #[test]
+    fn test_reject_new_keeps_the_queue() {
+        let mut queue = BoundedQueue::new(1, ShedPolicy::RejectNew);
+        assert_eq!(queue.push(Priority::Sell, 1), Push::Queued);
+        assert_eq!(queue.push(Priority::LaunchBuy, 2), Push::Rejected(2));
+        assert_eq!(queue.push(Priority::Sell, 3), Push::Rejected(3));
+        assert_eq!(queue.pop(), Some(1));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_drop_priorities_removes_only_those() {
+        let mut queue = BoundedQueue::new(4, ShedPolicy::DropLowest);
+        queue.push(Priority::Buy, "buy");
+        queue.push(Priority::Exit, "exit");
+        queue.push(Priority::LaunchBuy, "launch");
+        queue.push(Priority::Sell, "sell");
+        assert_eq!(queue.drop_priorities(&[Priority::Buy, Priority::LaunchBuy]), 2);
+        assert_eq!(drain(&mut queue), vec!["exit", "sell"]);
+    }
+
+    #[test]
+    fn test_full_queue_keeps_sells_over_incoming_buys() {
+        let mut queue = BoundedQueue::new(2, ShedPolicy::DropLowest);
+        assert_eq!(queue.push(Priority::Sell, "take profit"), Push::Queued);
+        assert_eq!(queue.push(Priority::Sell, "copy exit"), Push::Queued);
+        assert_eq!(queue.push(Priority::Buy, "buy"), Push::Rejected("buy"));
+    }
+
+    #[test]
+    fn test_queued_buy_is_shed_for_incoming_sell() {
+        let mut queue = BoundedQueue::new(2, ShedPolicy::DropLowest);
+        queue.push(Priority::Sell, "stop loss");
+        queue.push(Priority::Buy, "buy");
+        assert_eq!(queue.push(Priority::Sell, "copy exit"), Push::Shed(Priority::Buy, "buy"));
+        assert_eq!(drain(&mut queue), vec!["stop loss", "copy exit"]);
+    }
+}
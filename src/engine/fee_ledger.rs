@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::services::telegram::TelegramService;
+
+/// Fee and tip accounting settings
+#[derive(Debug, Clone)]
+pub struct FeeLedgerSettings {
+    /// Alert when fees and tips of the last 24h exceed this fraction of gross profit (0 = off)
+    pub alert_profit_share: f64,
+    /// Fees and tips the last 24h must reach before alerting, in SOL
+    pub alert_min_sol: f64,
+}
+
+impl FeeLedgerSettings {
+    /// Load fee accounting settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            alert_profit_share: std::env::var("FEE_ALERT_PROFIT_SHARE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0),
+            alert_min_sol: std::env::var("FEE_ALERT_MIN_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.05),
+        }
+    }
+}
+
+/// Fees and tips paid through one relay since startup
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RelaySpend {
+    pub relay: String,
+    /// Landed transactions, which are the ones that pay
+    pub transactions: usize,
+    pub fees_sol: f64,
+    pub tips_sol: f64,
+}
+
+/// Day totals the fee alert compares
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DailyCosts {
+    pub costs_sol: f64,
+    pub gross_pnl_sol: f64,
+}
+
+/// Totals priority fees and tips per relay, and weighs the last day's spend against gross profit
+pub struct FeeLedger {
+    settings: FeeLedgerSettings,
+    relays: BTreeMap<String, RelaySpend>,
+    costs: VecDeque<(DateTime<Utc>, f64)>,
+    profits: VecDeque<(DateTime<Utc>, f64)>,
+    /// Last alert, so one goes out per day at most
+    alerted_at: Option<DateTime<Utc>>,
+}
+
+impl FeeLedger {
+    pub fn new(settings: FeeLedgerSettings) -> Self {
+        Self {
+            settings,
+            relays: BTreeMap::new(),
+            costs: VecDeque::new(),
+            profits: VecDeque::new(),
+            alerted_at: None,
+        }
+    }
+
+    /// Count the fee and tip of a landed transaction, returning an alert when spend runs too high
+    pub fn record(&mut self, relay: &str, fee_sol: f64, tip_sol: f64) -> Option<String> {
+        self.record_at(Utc::now(), relay, fee_sol, tip_sol)
+    }
+
+    pub fn record_at(&mut self, now: DateTime<Utc>, relay: &str, fee_sol: f64, tip_sol: f64) -> Option<String> {
+        let spend = self.relays.entry(relay.to_string()).or_insert_with(|| RelaySpend {
+            relay: relay.to_string(),
+            ..Default::default()
+        });
+        spend.transactions += 1;
+        spend.fees_sol += fee_sol;
+        spend.tips_sol += tip_sol;
+        self.costs.push_back((now, fee_sol + tip_sol));
+        self.check_at(now)
+    }
+
+    /// Count the gross PnL of a confirmed sell
+    pub fn record_profit(&mut self, pnl_sol: f64) {
+        self.record_profit_at(Utc::now(), pnl_sol);
+    }
+
+    pub fn record_profit_at(&mut self, now: DateTime<Utc>, pnl_sol: f64) {
+        self.profits.push_back((now, pnl_sol));
+    }
+
+    /// Fees and tips against gross PnL over the last 24h
+    pub fn daily_at(&mut self, now: DateTime<Utc>) -> DailyCosts {
+        let cutoff = now - Duration::hours(24);
+        while self.costs.front().map(|(at, _)| *at <= cutoff).unwrap_or(false) {
+            self.costs.pop_front();
+        }
+        while self.profits.front().map(|(at, _)| *at <= cutoff).unwrap_or(false) {
+            self.profits.pop_front();
+        }
+        DailyCosts {
+            costs_sol: self.costs.iter().map(|(_, sol)| sol).sum(),
+            gross_pnl_sol: self.profits.iter().map(|(_, sol)| sol).sum(),
+        }
+    }
+
+    fn check_at(&mut self, now: DateTime<Utc>) -> Option<String> {
+        let share = self.settings.alert_profit_share;
+        if share <= 0.0 || self.alerted_at.map(|at| now - at < Duration::hours(24)).unwrap_or(false) {
+            return None;
+        }
+        let daily = self.daily_at(now);
+        if daily.costs_sol < self.settings.alert_min_sol || daily.costs_sol <= share * daily.gross_pnl_sol.max(0.0) {
+            return None;
+        }
+        self.alerted_at = Some(now);
+        Some(format!(
+            "⚠️ Fees and tips of {:.4} SOL in the last 24h exceed {:.0}% of the {:+.4} SOL gross profit",
+            daily.costs_sol, share * 100.0, daily.gross_pnl_sol
+        ))
+    }
+
+    /// Spend per relay, most expensive first
+    pub fn report(&self) -> Vec<RelaySpend> {
+        let mut report: Vec<RelaySpend> = self.relays.values().cloned().collect();
+        report.sort_by(|a, b| (b.fees_sol + b.tips_sol).total_cmp(&(a.fees_sol + a.tips_sol)));
+        report
+    }
+
+    /// Telegram-friendly summary, one line per relay
+    pub fn summary(&self) -> String {
+        let report = self.report();
+        if report.is_empty() {
+            return "no fees paid yet".to_string();
+        }
+        report
+            .iter()
+            .map(|spend| format!(
+                "{}: {:.4} SOL fees + {:.4} SOL tips over {} txs",
+                spend.relay, spend.fees_sol, spend.tips_sol, spend.transactions
+            ))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+static FEE_LEDGER: LazyLock<Mutex<FeeLedger>> =
+    LazyLock::new(|| Mutex::new(FeeLedger::new(FeeLedgerSettings::from_env())));
+
+/// Shared fee ledger fed by the confirmation tracker
+pub fn fee_ledger() -> MutexGuard<'static, FeeLedger> {
+    FEE_LEDGER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+static FEE_ALERTS: LazyLock<Mutex<Option<(Arc<TelegramService>, String)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Send fee spend alerts to `chat_id`
+pub fn set_fee_alerts(telegram: Arc<TelegramService>, chat_id: String) {
+    *FEE_ALERTS.lock().unwrap_or_else(|e| e.into_inner()) = Some((telegram, chat_id));
+}
+
+/// Telegram service and chat fee alerts go to, if set
+pub fn fee_alerts() -> Option<(Arc<TelegramService>, String)> {
+    FEE_ALERTS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_relay_totals_and_daily_alert() {
+        let mut ledger = FeeLedger::new(FeeLedgerSettings { alert_profit_share: 0.5, alert_min_sol: 0.01 });
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        ledger.record_profit_at(now, 0.1);
+        assert_eq!(ledger.record_at(now, "jito", 0.001, 0.009), None);
+        assert_eq!(ledger.record_at(now, "rpc", 0.001, 0.0), None);
+        assert_eq!(ledger.report()[0].relay, "jito");
+        assert_eq!(ledger.report()[0].transactions, 1);
+
+        // 0.06 SOL spent against 0.1 SOL gross profit
+        assert!(ledger.record_at(now, "jito", 0.001, 0.048).is_some());
+        // one alert per day
+        assert_eq!(ledger.record_at(now + Duration::hours(1), "jito", 0.001, 0.05), None);
+        // the profit dropped out of the window
+        assert!(ledger.record_at(now + Duration::hours(25), "jito", 0.001, 0.01).is_some());
+        assert_eq!(ledger.daily_at(now + Duration::hours(25)).gross_pnl_sol, 0.0);
+    }
+}
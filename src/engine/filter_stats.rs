@@ -0,0 +1,353 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::timer::TimerZone;
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::metrics::metrics;
+use crate::services::telegram::TelegramService;
+
+/// Hourly buckets kept, one day's worth
+const HOURS_KEPT: usize = 24;
+/// Price lookups per follow-up tick, so a launch storm does not flood the RPC
+const LOOKUPS_PER_TICK: usize = 20;
+
+/// Filter rejection statistics settings
+#[derive(Debug, Clone)]
+pub struct FilterStatsSettings {
+    /// How long after a rejection the token's price is checked again, in seconds (0 = no follow-up)
+    pub follow_up_secs: u64,
+    /// Rejected tokens waiting for a follow-up at most, later rejections are only counted
+    pub max_tracked: usize,
+    /// A rejected token up this much at its follow-up counts as a missed winner, in percent
+    pub winner_percent: f64,
+    /// Post the tuning report every day at this time, in `TIMER_TIMEZONE` (None = off)
+    pub daily_at: Option<NaiveTime>,
+    pub timezone: String,
+}
+
+impl FilterStatsSettings {
+    /// Load filter statistics settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            follow_up_secs: std::env::var("FILTER_STATS_FOLLOW_UP_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1800),
+            max_tracked: std::env::var("FILTER_STATS_MAX_TRACKED")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(200),
+            winner_percent: std::env::var("FILTER_STATS_WINNER_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(100.0),
+            daily_at: std::env::var("FILTER_REPORT_DAILY_AT")
+                .ok()
+                .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok()),
+            timezone: std::env::var("TIMER_TIMEZONE").unwrap_or_else(|_| "local".to_string()),
+        }
+    }
+}
+
+/// A rejected token waiting to be priced again
+#[derive(Debug, Clone)]
+struct FollowUp {
+    filter: String,
+    rejected_at: DateTime<Utc>,
+    /// Price shortly after the rejection, looked up off the stream
+    price: Option<f64>,
+}
+
+/// How the tokens a filter rejected did by their follow-up
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Outcome {
+    followed: u64,
+    total_change_percent: f64,
+    winners: u64,
+}
+
+/// One filter's line in the tuning report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilterRow {
+    pub filter: String,
+    pub last_hour: u64,
+    pub last_day: u64,
+    pub total: u64,
+    /// Rejected tokens priced again at their follow-up
+    pub followed: u64,
+    /// Average price change of those tokens from rejection to follow-up
+    pub avg_change_percent: Option<f64>,
+    /// Those up at least `FILTER_STATS_WINNER_PERCENT`
+    pub missed_winners: u64,
+}
+
+/// Rejections per filter and hour, and how the rejected tokens performed afterwards
+#[derive(Debug)]
+pub struct FilterStats {
+    settings: FilterStatsSettings,
+    /// Rejections per filter in each hour since the epoch, oldest first
+    hours: VecDeque<(i64, HashMap<String, u64>)>,
+    totals: HashMap<String, u64>,
+    pending: HashMap<String, FollowUp>,
+    outcomes: HashMap<String, Outcome>,
+}
+
+impl FilterStats {
+    pub fn new(settings: FilterStatsSettings) -> Self {
+        Self {
+            settings,
+            hours: VecDeque::new(),
+            totals: HashMap::new(),
+            pending: HashMap::new(),
+            outcomes: HashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &FilterStatsSettings {
+        &self.settings
+    }
+
+    /// `filter` rejected `mint`
+    pub fn rejected(&mut self, filter: &str, mint: &str, now: DateTime<Utc>) {
+        let hour = now.timestamp().div_euclid(3600);
+        if self.hours.back().map(|(last, _)| *last != hour).unwrap_or(true) {
+            self.hours.push_back((hour, HashMap::new()));
+        }
+        while self.hours.front().map(|(first, _)| hour - first >= HOURS_KEPT as i64).unwrap_or(false) {
+            self.hours.pop_front();
+        }
+        if let Some((_, counts)) = self.hours.back_mut() {
+            *counts.entry(filter.to_string()).or_insert(0) += 1;
+        }
+        *self.totals.entry(filter.to_string()).or_insert(0) += 1;
+
+        let track = self.settings.follow_up_secs > 0
+            && self.pending.len() < self.settings.max_tracked
+            && !self.pending.contains_key(mint);
+        if track {
+            self.pending.insert(mint.to_string(), FollowUp { filter: filter.to_string(), rejected_at: now, price: None });
+        }
+    }
+
+    /// Rejected mints still without a starting price, up to `limit`
+    pub fn unpriced(&self, limit: usize) -> Vec<String> {
+        self.pending.iter().filter(|(_, follow_up)| follow_up.price.is_none()).map(|(mint, _)| mint.clone()).take(limit).collect()
+    }
+
+    pub fn priced(&mut self, mint: &str, price: f64) {
+        if let Some(follow_up) = self.pending.get_mut(mint) {
+            follow_up.price = Some(price);
+        }
+    }
+
+    /// Rejected mints whose follow-up is due, up to `limit`
+    pub fn due(&self, now: DateTime<Utc>, limit: usize) -> Vec<String> {
+        let after = chrono::Duration::seconds(self.settings.follow_up_secs as i64);
+        self.pending
+            .iter()
+            .filter(|(_, follow_up)| follow_up.price.is_some() && now - follow_up.rejected_at >= after)
+            .map(|(mint, _)| mint.clone())
+            .take(limit)
+            .collect()
+    }
+
+    /// Record the follow-up price of `mint`, `None` when it could not be priced
+    pub fn followed_up(&mut self, mint: &str, price: Option<f64>) {
+        let Some(follow_up) = self.pending.remove(mint) else {
+            return;
+        };
+        let (Some(start), Some(price)) = (follow_up.price.filter(|start| *start > 0.0), price) else {
+            return;
+        };
+        let change = (price / start - 1.0) * 100.0;
+        let outcome = self.outcomes.entry(follow_up.filter).or_default();
+        outcome.followed += 1;
+        outcome.total_change_percent += change;
+        if change >= self.settings.winner_percent {
+            outcome.winners += 1;
+        }
+    }
+
+    /// One row per filter, the most rejecting over the last day first
+    pub fn report(&self, now: DateTime<Utc>) -> Vec<FilterRow> {
+        let hour = now.timestamp().div_euclid(3600);
+        let mut rows: Vec<FilterRow> = self.totals
+            .iter()
+            .map(|(filter, total)| {
+                let in_hours = |hours: i64| self.hours
+                    .iter()
+                    .filter(|(at, _)| hour - at < hours)
+                    .filter_map(|(_, counts)| counts.get(filter))
+                    .sum::<u64>();
+                let outcome = self.outcomes.get(filter).copied().unwrap_or_default();
+                FilterRow {
+                    filter: filter.clone(),
+                    last_hour: in_hours(1),
+                    last_day: in_hours(HOURS_KEPT as i64),
+                    total: *total,
+                    followed: outcome.followed,
+                    avg_change_percent: (outcome.followed > 0).then(|| outcome.total_change_percent / outcome.followed as f64),
+                    missed_winners: outcome.winners,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| b.last_day.cmp(&a.last_day).then_with(|| a.filter.cmp(&b.filter)));
+        rows
+    }
+}
+
+/// Telegram message of the tuning report
+pub fn report_message(rows: &[FilterRow], winner_percent: f64) -> String {
+    if rows.is_empty() {
+        return "<b>🧪 FILTER REPORT</b>\n\nNo token was rejected yet".to_string();
+    }
+    let mut msg = "<b>🧪 FILTER REPORT</b>\nRejections in the last hour / day / total".to_string();
+    for row in rows {
+        msg.push_str(&format!("\n\n<b>{}</b>: {} / {} / {}", row.filter, row.last_hour, row.last_day, row.total));
+        if let Some(change) = row.avg_change_percent {
+            msg.push_str(&format!(
+                "\n└ {} followed up: {:+.1}% on average, {} up {:.0}% or more",
+                row.followed, change, row.missed_winners, winner_percent
+            ));
+        }
+    }
+    msg.push_str("\n\nA filter whose rejected tokens often went on to rise may be too tight.");
+    msg
+}
+
+static FILTER_STATS: LazyLock<Mutex<FilterStats>> =
+    LazyLock::new(|| Mutex::new(FilterStats::new(FilterStatsSettings::from_env())));
+static FILTER_REPORTS: LazyLock<Mutex<Option<(Arc<TelegramService>, String)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Shared filter statistics, fed wherever a filter rejects a token
+pub fn filter_stats() -> MutexGuard<'static, FilterStats> {
+    FILTER_STATS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Count a rejection of `mint` by `filter`, in the metrics and the tuning report, and log the skip
+pub fn record_rejection(filter: &str, mint: &str) {
+    record_rejection_as(filter, mint, ReasonCode::for_filter(filter));
+}
+
+/// `record_rejection` with a more specific reason code than the filter's own
+pub fn record_rejection_as(filter: &str, mint: &str, code: ReasonCode) {
+    metrics().filters_rejected.with_label_values(&[filter]).inc();
+    filter_stats().rejected(filter, mint, Utc::now());
+    record_decision(Decision::Skip, mint, code, filter);
+}
+
+/// Send the daily filter report to `chat_id`
+pub fn set_filter_reports(telegram: Arc<TelegramService>, chat_id: String) {
+    *FILTER_REPORTS.lock().unwrap_or_else(|e| e.into_inner()) = Some((telegram, chat_id));
+}
+
+/// Price rejected tokens at rejection and again at their follow-up, and post the daily report
+pub async fn start_filter_stats_system(executor: Arc<TradeExecutor>, logger: Logger) {
+    let settings = filter_stats().settings().clone();
+    if settings.follow_up_secs > 0 {
+        let logger = logger.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                let unpriced = filter_stats().unpriced(LOOKUPS_PER_TICK);
+                for mint in unpriced {
+                    match executor.current_price(&mint).await {
+                        Ok(price) => filter_stats().priced(&mint, price),
+                        Err(e) => {
+                            logger.debug(format!("No price for rejected {}: {}", mint, e));
+                            filter_stats().followed_up(&mint, None);
+                        }
+                    }
+                }
+                let due = filter_stats().due(Utc::now(), LOOKUPS_PER_TICK);
+                for mint in due {
+                    let price = executor.current_price(&mint).await.ok();
+                    filter_stats().followed_up(&mint, price);
+                }
+            }
+        });
+    }
+
+    let Some(daily_at) = settings.daily_at else {
+        return;
+    };
+    let zone = match TimerZone::parse(&settings.timezone) {
+        Ok(zone) => zone,
+        Err(e) => {
+            logger.error(format!("Daily filter report disabled: {}", e));
+            return;
+        }
+    };
+    logger.log(format!("Daily filter report at {} ({})", daily_at.format("%H:%M"), settings.timezone));
+    tokio::spawn(async move {
+        let now = zone.local(Utc::now());
+        let mut last_posted: Option<NaiveDate> = (now.time() >= daily_at).then(|| now.date());
+        let mut interval = tokio::time::interval(Duration::from_secs(30));
+        loop {
+            interval.tick().await;
+            let now = zone.local(Utc::now());
+            if now.time() < daily_at || last_posted == Some(now.date()) {
+                continue;
+            }
+            last_posted = Some(now.date());
+            let message = report_message(&filter_stats().report(Utc::now()), settings.winner_percent);
+            let target = FILTER_REPORTS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            if let Some((telegram, chat_id)) = target {
+                if let Err(e) = telegram.send_message(&chat_id, &message, "HTML").await {
+                    logger.error(format!("Failed to send filter report: {}", e));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_rejections_and_follow_ups() {
+        let settings = FilterStatsSettings {
+            follow_up_secs: 600,
+            max_tracked: 2,
+            winner_percent: 100.0,
+            daily_at: None,
+            timezone: "UTC".to_string(),
+        };
+        let mut stats = FilterStats::new(settings);
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        stats.rejected("market_cap", "a", start);
+        stats.rejected("market_cap", "b", start + chrono::Duration::minutes(70));
+        stats.rejected("dev_buy", "c", start + chrono::Duration::minutes(71));
+        // only two are tracked
+        assert_eq!(stats.unpriced(10).len(), 2);
+
+        stats.priced("a", 1.0);
+        stats.priced("b", 1.0);
+        let later = start + chrono::Duration::minutes(75);
+        assert_eq!(stats.due(later, 10), vec!["a".to_string()]);
+        stats.followed_up("a", Some(3.0));
+        stats.followed_up("b", Some(0.5));
+
+        let rows = stats.report(later);
+        assert_eq!(rows[0].filter, "market_cap");
+        assert_eq!((rows[0].last_hour, rows[0].last_day, rows[0].total), (1, 2, 2));
+        assert_eq!(rows[0].followed, 2);
+        assert_eq!(rows[0].avg_change_percent, Some(75.0));
+        assert_eq!(rows[0].missed_winners, 1);
+        assert_eq!(rows[1].avg_change_percent, None);
+
+        // a day later the buckets have rolled off, the totals stay
+        stats.rejected("dev_buy", "d", start + chrono::Duration::hours(30));
+        let rows = stats.report(start + chrono::Duration::hours(30));
+        let market_cap = rows.iter().find(|row| row.filter == "market_cap").unwrap();
+        assert_eq!((market_cap.last_day, market_cap.total), (0, 2));
+    }
+}
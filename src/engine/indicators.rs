@@ -0,0 +1,491 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::str::FromStr;
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeRequest};
+
+use crate::common::logger::Logger;
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{trade_events, BondingCurveReserves};
+use crate::engine::trade_executor::{price_from_reserves, TradeExecutor};
+use crate::services::geyser::{wait_to_reconnect, GeyserSource, GeyserSubscription};
+use crate::services::subscription::{ping_request, SubscriptionBuilder};
+
+/// Closed candles kept per series
+const MAX_CANDLES: usize = 120;
+
+/// Indicator exit settings
+#[derive(Debug, Clone)]
+pub struct IndicatorSettings {
+    pub enabled: bool,
+    /// Candle size the exit rules read, in seconds (1 or 5)
+    pub candle_secs: u64,
+    pub ema_fast_period: usize,
+    pub ema_slow_period: usize,
+    pub rsi_period: usize,
+    /// Sell when RSI is above this and the price is below the fast EMA (0 = off)
+    pub rsi_exit_level: f64,
+    /// Sell when the fast EMA crosses below the slow EMA
+    pub ema_cross_exit: bool,
+}
+
+impl IndicatorSettings {
+    /// Load indicator settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("INDICATOR_EXITS_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            candle_secs: std::env::var("INDICATOR_CANDLE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs == 1 || *secs == 5)
+                .unwrap_or(1),
+            ema_fast_period: std::env::var("EMA_FAST_PERIOD")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(9),
+            ema_slow_period: std::env::var("EMA_SLOW_PERIOD")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(21),
+            rsi_period: std::env::var("RSI_PERIOD")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(14),
+            rsi_exit_level: std::env::var("RSI_EXIT_LEVEL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(80.0),
+            ema_cross_exit: std::env::var("EMA_CROSS_EXIT")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+}
+
+/// OHLC of the trades in one interval
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// Exponential moving average, seeded with the first value
+#[derive(Debug, Clone)]
+pub struct Ema {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self {
+            alpha: 2.0 / (period.max(1) as f64 + 1.0),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, x: f64) -> f64 {
+        let next = match self.value {
+            Some(prev) => prev + self.alpha * (x - prev),
+            None => x,
+        };
+        self.value = Some(next);
+        next
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Wilder's RSI; `None` until `period` changes were seen
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    period: usize,
+    prev: Option<f64>,
+    avg_gain: f64,
+    avg_loss: f64,
+    samples: usize,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev: None,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+            samples: 0,
+        }
+    }
+
+    pub fn update(&mut self, x: f64) -> Option<f64> {
+        if let Some(prev) = self.prev.replace(x) {
+            let change = x - prev;
+            let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+            self.samples += 1;
+            // simple average while warming up, smoothed afterwards
+            let n = self.samples.min(self.period) as f64;
+            self.avg_gain += (gain - self.avg_gain) / n;
+            self.avg_loss += (loss - self.avg_loss) / n;
+        }
+        self.value()
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        if self.samples < self.period {
+            return None;
+        }
+        if self.avg_loss == 0.0 {
+            return Some(if self.avg_gain == 0.0 { 50.0 } else { 100.0 });
+        }
+        Some(100.0 - 100.0 / (1.0 + self.avg_gain / self.avg_loss))
+    }
+}
+
+/// Direction of the latest fast/slow EMA cross
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Cross {
+    Bullish,
+    Bearish,
+}
+
+/// Indicator values of one candle series
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndicatorSnapshot {
+    pub last_close: Option<f64>,
+    pub fast_ema: Option<f64>,
+    pub slow_ema: Option<f64>,
+    pub rsi: Option<f64>,
+    /// Cross on the most recent closed candle, if any
+    pub cross: Option<Cross>,
+    pub candles: usize,
+}
+
+/// Fixed-interval candles for one mint with indicators updated on every close
+///
+/// Intervals without trades produce no candle, so a quiet token's indicators simply stop moving.
+#[derive(Debug, Clone)]
+pub struct CandleSeries {
+    interval: Duration,
+    current: Option<(Instant, Candle)>,
+    closed: VecDeque<Candle>,
+    fast: Ema,
+    slow: Ema,
+    rsi: Rsi,
+    cross: Option<Cross>,
+}
+
+impl CandleSeries {
+    pub fn new(interval: Duration, settings: &IndicatorSettings) -> Self {
+        Self {
+            interval,
+            current: None,
+            closed: VecDeque::new(),
+            fast: Ema::new(settings.ema_fast_period),
+            slow: Ema::new(settings.ema_slow_period),
+            rsi: Rsi::new(settings.rsi_period),
+            cross: None,
+        }
+    }
+
+    /// Add a traded price, closing the open candle when its interval has passed
+    pub fn push(&mut self, price: f64, at: Instant) {
+        match self.current.as_mut() {
+            Some((start, candle)) if at.duration_since(*start) < self.interval => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+            }
+            _ => {
+                if let Some((_, candle)) = self.current.take() {
+                    self.close(candle);
+                }
+                self.current = Some((at, Candle { open: price, high: price, low: price, close: price }));
+            }
+        }
+    }
+
+    fn close(&mut self, candle: Candle) {
+        let was_above = match (self.fast.value(), self.slow.value()) {
+            (Some(fast), Some(slow)) => Some(fast > slow),
+            _ => None,
+        };
+        let fast = self.fast.update(candle.close);
+        let slow = self.slow.update(candle.close);
+        self.rsi.update(candle.close);
+        self.cross = match (was_above, fast > slow) {
+            (Some(false), true) => Some(Cross::Bullish),
+            (Some(true), false) => Some(Cross::Bearish),
+            _ => None,
+        };
+
+        self.closed.push_back(candle);
+        if self.closed.len() > MAX_CANDLES {
+            self.closed.pop_front();
+        }
+    }
+
+    pub fn candles(&self) -> Vec<Candle> {
+        self.closed.iter().copied().collect()
+    }
+
+    pub fn snapshot(&self) -> IndicatorSnapshot {
+        IndicatorSnapshot {
+            last_close: self.closed.back().map(|candle| candle.close),
+            fast_ema: self.fast.value(),
+            slow_ema: self.slow.value(),
+            rsi: self.rsi.value(),
+            cross: self.cross,
+            candles: self.closed.len(),
+        }
+    }
+}
+
+/// 1s and 5s candle series for one mint
+#[derive(Debug, Clone)]
+pub struct MintIndicators {
+    pub one_sec: CandleSeries,
+    pub five_sec: CandleSeries,
+}
+
+/// Builds candles and indicators for held mints from observed trades
+pub struct IndicatorEngine {
+    settings: IndicatorSettings,
+    mints: HashMap<String, MintIndicators>,
+}
+
+impl IndicatorEngine {
+    /// Create a new indicator engine
+    pub fn new(settings: IndicatorSettings) -> Self {
+        Self {
+            settings,
+            mints: HashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &IndicatorSettings {
+        &self.settings
+    }
+
+    /// Record a traded price for `mint`
+    pub fn record(&mut self, mint: &str, price: f64, at: Instant) {
+        if price <= 0.0 {
+            return;
+        }
+        let settings = &self.settings;
+        let series = self.mints.entry(mint.to_string()).or_insert_with(|| MintIndicators {
+            one_sec: CandleSeries::new(Duration::from_secs(1), settings),
+            five_sec: CandleSeries::new(Duration::from_secs(5), settings),
+        });
+        series.one_sec.push(price, at);
+        series.five_sec.push(price, at);
+    }
+
+    /// Forget mints that are no longer held
+    pub fn retain(&mut self, held: &[String]) {
+        self.mints.retain(|mint, _| held.contains(mint));
+    }
+
+    pub fn is_tracked(&self, mint: &str) -> bool {
+        self.mints.contains_key(mint)
+    }
+
+    /// Indicators of `mint` on the `candle_secs` series (1 or 5)
+    pub fn snapshot(&self, mint: &str, candle_secs: u64) -> Option<IndicatorSnapshot> {
+        let series = self.mints.get(mint)?;
+        Some(if candle_secs >= 5 { series.five_sec.snapshot() } else { series.one_sec.snapshot() })
+    }
+
+    /// Reason to exit `mint` at `price` from its indicators, if an indicator rule fires
+    pub fn exit_signal(&self, mint: &str, price: f64) -> Option<String> {
+        let snapshot = self.snapshot(mint, self.settings.candle_secs)?;
+        let fast = snapshot.fast_ema?;
+
+        if self.settings.rsi_exit_level > 0.0 {
+            if let Some(rsi) = snapshot.rsi {
+                if rsi > self.settings.rsi_exit_level && price < fast {
+                    return Some(format!(
+                        "RSI {:.0} above {:.0} with price below fast EMA ({}s candles)",
+                        rsi, self.settings.rsi_exit_level, self.settings.candle_secs
+                    ));
+                }
+            }
+        }
+        if self.settings.ema_cross_exit && snapshot.cross == Some(Cross::Bearish) {
+            return Some(format!(
+                "EMA {} crossed below EMA {} ({}s candles)",
+                self.settings.ema_fast_period, self.settings.ema_slow_period, self.settings.candle_secs
+            ));
+        }
+        None
+    }
+}
+
+static INDICATOR_ENGINE: LazyLock<Mutex<IndicatorEngine>> =
+    LazyLock::new(|| Mutex::new(IndicatorEngine::new(IndicatorSettings::from_env())));
+
+/// Shared engine fed by the trade stream and read by the position manager
+pub fn indicator_engine() -> std::sync::MutexGuard<'static, IndicatorEngine> {
+    INDICATOR_ENGINE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// pump.fun trades of the held mints only
+fn subscribe_request(mints: &[String]) -> SubscribeRequest {
+    if mints.is_empty() {
+        return SubscriptionBuilder::new().build();
+    }
+    SubscriptionBuilder::new()
+        .transactions("indicators", mints, &[program_ids().pump_fun_program])
+        .build()
+}
+
+/// Stream trades of held mints into the engine until the connection drops
+async fn run_stream(
+    executor: &Arc<TradeExecutor>,
+    source: &dyn GeyserSource,
+    logger: &Logger,
+) -> Result<()> {
+    let GeyserSubscription { sink: mut subscribe_tx, mut stream } = source.subscribe().await?;
+
+    let mut subscribed: Option<Vec<String>> = None;
+    // parsed once per change so events are matched without formatting their mint
+    let mut held_keys: Vec<Pubkey> = Vec::new();
+    let mut sync = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        tokio::select! {
+            _ = sync.tick() => {
+                let mut held = executor.open_positions();
+                held.sort();
+                let held = source.limit_accounts(&held);
+                indicator_engine().retain(&held);
+                // a new request replaces the previous filters on the same stream
+                if subscribed.as_ref() != Some(&held) {
+                    subscribe_tx.send(subscribe_request(&held)).await?;
+                    logger.debug(format!("Building candles for {} held mints", held.len()));
+                    held_keys = held.iter().filter_map(|mint| Pubkey::from_str(mint).ok()).collect();
+                    subscribed = Some(held);
+                }
+            }
+            message = stream.next() => {
+                let update = match message {
+                    Some(Ok(update)) => update,
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(anyhow!("Stream closed")),
+                };
+                match update.update_oneof {
+                    Some(UpdateOneof::Transaction(tx)) => {
+                        let Some(meta) = tx.transaction.and_then(|info| info.meta) else {
+                            continue;
+                        };
+                        let now = Instant::now();
+                        for event in trade_events(&meta.log_messages) {
+                            if !held_keys.contains(&event.mint) {
+                                continue;
+                            }
+                            let price = price_from_reserves(&BondingCurveReserves {
+                                virtual_token_reserves: event.virtual_token_reserves,
+                                virtual_sol_reserves: event.virtual_sol_reserves,
+                            });
+                            indicator_engine().record(&event.mint.to_string(), price, now);
+                        }
+                    }
+                    Some(UpdateOneof::Ping(_)) => {
+                        subscribe_tx.send(ping_request()).await?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Start building candles for held mints from the gRPC trade stream
+///
+/// The position manager reads the resulting indicators through `indicator_engine()`.
+pub async fn start_indicator_system(
+    executor: Arc<TradeExecutor>,
+    source: Arc<dyn GeyserSource>,
+    logger: Logger,
+) {
+    tokio::spawn(async move {
+        loop {
+            let result = run_stream(&executor, source.as_ref(), &logger).await;
+            wait_to_reconnect(source.as_ref(), "Indicator", result, &logger).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> IndicatorSettings {
+        IndicatorSettings {
+            enabled: true,
+            candle_secs: 1,
+            ema_fast_period: 2,
+            ema_slow_period: 4,
+            rsi_period: 3,
+            rsi_exit_level: 80.0,
+            ema_cross_exit: true,
+        }
+    }
+
+    #[test]
+    fn test_ema_and_rsi() {
+        let mut ema = Ema::new(3);
+        assert_eq!(ema.update(10.0), 10.0);
+        assert_eq!(ema.update(20.0), 15.0);
+
+        let mut rsi = Rsi::new(3);
+        assert_eq!(rsi.update(1.0), None);
+        rsi.update(2.0);
+        rsi.update(3.0);
+        assert_eq!(rsi.update(4.0), Some(100.0));
+        let after_drop = rsi.update(3.0).unwrap();
+        assert!(after_drop < 100.0 && after_drop > 50.0);
+    }
+
+    #[test]
+    fn test_candles_close_on_interval() {
+        let mut series = CandleSeries::new(Duration::from_secs(1), &settings());
+        let start = Instant::now();
+        series.push(1.0, start);
+        series.push(3.0, start + Duration::from_millis(300));
+        series.push(2.0, start + Duration::from_millis(600));
+        assert!(series.candles().is_empty());
+
+        series.push(2.5, start + Duration::from_millis(1100));
+        assert_eq!(series.candles(), vec![Candle { open: 1.0, high: 3.0, low: 1.0, close: 2.0 }]);
+    }
+
+    #[test]
+    fn test_overbought_pullback_and_bearish_cross_exit() {
+        let mut engine = IndicatorEngine::new(settings());
+        let start = Instant::now();
+        for (i, price) in [1.0, 1.2, 1.5, 1.9, 2.4, 3.0].iter().enumerate() {
+            engine.record("mint", *price, start + Duration::from_secs(i as u64));
+        }
+        let snapshot = engine.snapshot("mint", 1).unwrap();
+        assert!(snapshot.rsi.unwrap() > 80.0);
+        assert!(engine.exit_signal("mint", 2.0).unwrap().contains("RSI"));
+        // still above the fast EMA
+        assert!(engine.exit_signal("mint", 3.1).is_none());
+
+        for (i, price) in [1.5, 1.0].iter().enumerate() {
+            engine.record("mint", *price, start + Duration::from_secs(6 + i as u64));
+        }
+        assert_eq!(engine.snapshot("mint", 1).unwrap().cross, Some(Cross::Bearish));
+        assert!(engine.exit_signal("mint", 0.8).unwrap().contains("crossed below"));
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use anchor_client::solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig,
+};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::engine::bundle_analyzer::LaunchBuy;
+
+/// Insider detection settings
+#[derive(Debug, Clone)]
+pub struct InsiderSettings {
+    pub enabled: bool,
+    /// Number of first buyers inspected
+    pub early_buys: usize,
+    /// Wallets funded at most this long before the launch count as freshly funded, in seconds
+    pub funding_window_secs: i64,
+    /// Skip the token when insiders make up at least this % of the early buyers
+    pub max_insider_percent: f64,
+    /// Wallets with more history than this are treated as established and not traced
+    pub funding_lookup_max_txs: usize,
+    /// Mixer and CEX hot wallets; anything freshly funded from one of them is flagged
+    pub known_funders: HashSet<String>,
+}
+
+impl InsiderSettings {
+    /// Load insider detection settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("INSIDER_CHECK_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            early_buys: std::env::var("INSIDER_EARLY_BUYS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(10),
+            funding_window_secs: std::env::var("INSIDER_FUNDING_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(600),
+            max_insider_percent: std::env::var("MAX_INSIDER_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(50.0),
+            funding_lookup_max_txs: std::env::var("FUNDING_LOOKUP_MAX_TXS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(20),
+            known_funders: std::env::var("KNOWN_FUNDER_WALLETS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|wallet| wallet.trim().to_string())
+                .filter(|wallet| !wallet.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Where and when a wallet got its first SOL
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingInfo {
+    pub funder: String,
+    /// Unix timestamp of the funding transaction
+    pub funded_at: i64,
+}
+
+/// Result of inspecting the early buyers of a launch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InsiderReport {
+    pub early_buyers: usize,
+    pub insiders: Vec<String>,
+    /// Funders shared by several freshly funded early buyers
+    pub shared_funders: Vec<String>,
+    /// Share of the early buyers flagged as insiders, in percent
+    pub insider_percent: f64,
+}
+
+/// Flags early buyers that look like insiders from how and when they were funded
+pub struct InsiderAnalyzer {
+    settings: InsiderSettings,
+}
+
+impl InsiderAnalyzer {
+    /// Create a new insider analyzer
+    pub fn new(settings: InsiderSettings) -> Self {
+        Self { settings }
+    }
+
+    /// First `early_buys` distinct buyers in slot order, excluding the creator
+    pub fn early_buyers(&self, creator: &str, buys: &[LaunchBuy]) -> Vec<String> {
+        let mut ordered: Vec<&LaunchBuy> = buys.iter().collect();
+        ordered.sort_by_key(|buy| buy.slot);
+
+        let mut buyers = Vec::new();
+        for buy in ordered {
+            if buy.wallet != creator && !buyers.contains(&buy.wallet) {
+                buyers.push(buy.wallet.clone());
+            }
+            if buyers.len() >= self.settings.early_buys {
+                break;
+            }
+        }
+        buyers
+    }
+
+    /// Analyze the early buyers given the funding of each one that could be traced
+    pub fn analyze(
+        &self,
+        creator: &str,
+        buys: &[LaunchBuy],
+        funding: &HashMap<String, FundingInfo>,
+        launch_time: i64,
+    ) -> InsiderReport {
+        let buyers = self.early_buyers(creator, buys);
+
+        let mut by_funder: HashMap<&str, Vec<&str>> = HashMap::new();
+        for buyer in &buyers {
+            let Some(info) = funding.get(buyer) else {
+                continue;
+            };
+            if launch_time - info.funded_at <= self.settings.funding_window_secs {
+                by_funder.entry(info.funder.as_str()).or_default().push(buyer.as_str());
+            }
+        }
+
+        let mut insiders = Vec::new();
+        let mut shared_funders = Vec::new();
+        for (funder, wallets) in by_funder {
+            let shared = wallets.len() >= 2;
+            if shared {
+                shared_funders.push(funder.to_string());
+            }
+            if shared || self.settings.known_funders.contains(funder) || funder == creator {
+                insiders.extend(wallets.into_iter().map(str::to_string));
+            }
+        }
+        insiders.sort();
+        shared_funders.sort();
+
+        let insider_percent = if buyers.is_empty() {
+            0.0
+        } else {
+            insiders.len() as f64 / buyers.len() as f64 * 100.0
+        };
+        InsiderReport {
+            early_buyers: buyers.len(),
+            insiders,
+            shared_funders,
+            insider_percent,
+        }
+    }
+
+    /// Reason to skip the launch, if insiders dominate the early buys
+    pub fn rejection(&self, report: &InsiderReport) -> Option<String> {
+        (!report.insiders.is_empty() && report.insider_percent >= self.settings.max_insider_percent).then(|| format!(
+            "{} of the first {} buyers look like insiders ({:.0}%), max is {}%",
+            report.insiders.len(), report.early_buyers, report.insider_percent, self.settings.max_insider_percent
+        ))
+    }
+
+    /// Analyze a launch, tracing the funding of each early buyer over RPC
+    pub async fn analyze_launch(
+        &self,
+        rpc_client: Arc<RpcClient>,
+        creator: &str,
+        buys: &[LaunchBuy],
+        launch_time: i64,
+    ) -> InsiderReport {
+        let mut funding = HashMap::new();
+        for buyer in self.early_buyers(creator, buys) {
+            if let Ok(Some(info)) = funding_source(&rpc_client, &buyer, self.settings.funding_lookup_max_txs).await {
+                funding.insert(buyer, info);
+            }
+        }
+        self.analyze(creator, buys, &funding, launch_time)
+    }
+}
+
+/// Trace who funded a fresh wallet from its oldest transaction
+///
+/// Returns `None` when the wallet has more than `max_txs` transactions, since an
+/// established wallet's first funding says nothing about this launch.
+pub async fn funding_source(rpc_client: &RpcClient, wallet: &str, max_txs: usize) -> Result<Option<FundingInfo>> {
+    let wallet_pubkey = Pubkey::from_str(wallet)?;
+    let signatures = rpc_client
+        .get_signatures_for_address_with_config(
+            &wallet_pubkey,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(max_txs + 1),
+                ..Default::default()
+            },
+        )
+        .await?;
+    if signatures.len() > max_txs {
+        return Ok(None);
+    }
+    // signatures come newest first
+    let Some(oldest) = signatures.last() else {
+        return Ok(None);
+    };
+
+    let transaction = rpc_client
+        .get_transaction_with_config(
+            &Signature::from_str(&oldest.signature)?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                max_supported_transaction_version: Some(0),
+                ..Default::default()
+            },
+        )
+        .await?;
+    let meta = transaction.transaction.meta.ok_or_else(|| anyhow!("Funding transaction has no meta"))?;
+    let decoded = transaction.transaction.transaction
+        .decode()
+        .ok_or_else(|| anyhow!("Failed to decode funding transaction"))?;
+    let keys = decoded.message.static_account_keys();
+
+    // the funder is whoever lost the most SOL in the transaction
+    let funder = keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+        .filter(|(key, _)| **key != wallet_pubkey)
+        .filter(|(_, (pre, post))| post < pre)
+        .max_by_key(|(_, (pre, post))| *pre - *post)
+        .map(|(key, _)| key.to_string());
+
+    Ok(funder.map(|funder| FundingInfo {
+        funder,
+        funded_at: oldest.block_time.or(transaction.block_time).unwrap_or_default(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzer() -> InsiderAnalyzer {
+        InsiderAnalyzer::new(InsiderSettings {
+            enabled: true,
+            early_buys: 4,
+            funding_window_secs: 600,
+            max_insider_percent: 50.0,
+            funding_lookup_max_txs: 20,
+            known_funders: ["mixer".to_string()].into_iter().collect(),
+        })
+    }
+
+    fn buy(wallet: &str, slot: u64) -> LaunchBuy {
+        LaunchBuy { wallet: wallet.to_string(), slot, token_amount: 1 }
+    }
+
+    fn funded(funder: &str, funded_at: i64) -> FundingInfo {
+        FundingInfo { funder: funder.to_string(), funded_at }
+    }
+
+    #[test]
+    fn test_shared_and_known_funders_are_insiders() {
+        let buys = vec![buy("dev", 1), buy("a", 2), buy("b", 2), buy("c", 3), buy("d", 4), buy("late", 9)];
+        let funding: HashMap<String, FundingInfo> = [
+            ("a".to_string(), funded("source", 900)),
+            ("b".to_string(), funded("source", 950)),
+            ("c".to_string(), funded("mixer", 990)),
+            ("d".to_string(), funded("source", 0)),
+        ].into_iter().collect();
+
+        let report = analyzer().analyze("dev", &buys, &funding, 1000);
+        assert_eq!(report.early_buyers, 4);
+        assert_eq!(report.insiders, vec!["a", "b", "c"]);
+        assert_eq!(report.shared_funders, vec!["source"]);
+        assert!(analyzer().rejection(&report).is_some());
+    }
+
+    #[test]
+    fn test_unrelated_buyers_pass() {
+        let buys = vec![buy("a", 1), buy("b", 2)];
+        let funding: HashMap<String, FundingInfo> = [
+            ("a".to_string(), funded("x", 900)),
+            ("b".to_string(), funded("y", 900)),
+        ].into_iter().collect();
+        let report = analyzer().analyze("dev", &buys, &funding, 1000);
+        assert!(report.insiders.is_empty());
+        assert!(analyzer().rejection(&report).is_none());
+    }
+}
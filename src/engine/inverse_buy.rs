@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use anchor_client::solana_sdk::native_token::LAMPORTS_PER_SOL;
+use tokio::time::Instant;
+
+use crate::common::config::InverseBuyConfig;
+use crate::dex::pump_fun::TradeEvent;
+use crate::engine::strategy::{Strategy, StrategyAction};
+use crate::services::telegram::TokenInfo;
+
+/// Buys tokens that passed the filters when someone dumps a large bag into them
+pub struct InverseBuyStrategy {
+    buy_amount: f64,
+    /// A single sell of at least this much SOL triggers the buy
+    min_sell_sol: f64,
+    /// How long a filtered token stays watched
+    watch_for: Duration,
+    watched: HashMap<String, Instant>,
+    bought: HashSet<String>,
+}
+
+impl InverseBuyStrategy {
+    pub fn new(config: &InverseBuyConfig) -> Self {
+        Self {
+            buy_amount: config.buy_amount,
+            min_sell_sol: std::env::var("INVERSE_BUY_MIN_SELL_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0),
+            watch_for: Duration::from_secs(
+                std::env::var("INVERSE_BUY_WATCH_SECS")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(600),
+            ),
+            watched: HashMap::new(),
+            bought: HashSet::new(),
+        }
+    }
+}
+
+impl Strategy for InverseBuyStrategy {
+    fn name(&self) -> &'static str {
+        "inverse"
+    }
+
+    /// Only dumps into the watched tokens matter
+    fn stream_accounts(&self) -> Option<Vec<String>> {
+        Some(self.watched.keys().cloned().collect())
+    }
+
+    fn on_new_token(&mut self, token: &TokenInfo) -> Vec<StrategyAction> {
+        self.watched.insert(token.address.clone(), Instant::now());
+        Vec::new()
+    }
+
+    fn on_trade_event(&mut self, event: &TradeEvent, _at: Instant) -> Vec<StrategyAction> {
+        let sold = event.sol_amount as f64 / LAMPORTS_PER_SOL as f64;
+        if event.is_buy || sold < self.min_sell_sol {
+            return Vec::new();
+        }
+        let mint = event.mint.to_string();
+        if !self.watched.contains_key(&mint) || !self.bought.insert(mint.clone()) {
+            return Vec::new();
+        }
+        vec![StrategyAction::Buy {
+            mint,
+            sol_amount: Some(self.buy_amount),
+            confidence: None,
+            reason: format!("{} sold {:.2} SOL", event.user, sold),
+        }]
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Vec<StrategyAction> {
+        let watch_for = self.watch_for;
+        self.watched.retain(|_, since| now.duration_since(*since) < watch_for);
+        self.bought.retain(|mint| self.watched.contains_key(mint));
+        Vec::new()
+    }
+}
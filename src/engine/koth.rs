@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use colored::Colorize;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::dex::pump_fun::{curve_progress_percent, BondingCurveReserves, TradeEvent};
+use crate::engine::strategy::{PositionUpdate, Strategy, StrategyAction};
+use crate::services::pump_api::pump_api;
+
+/// How long a mint seen below the progress threshold is remembered without trades
+const BELOW_TTL_SECS: u64 = 600;
+
+/// King-of-the-Hill entry settings
+#[derive(Debug, Clone)]
+pub struct KothSettings {
+    pub enabled: bool,
+    /// Fixed buy size, independent of the launch sniping size
+    pub buy_sol: f64,
+    /// Bonding curve progress treated as reaching King of the Hill on-chain (0 = off)
+    pub curve_progress: f64,
+    /// How often the pump.fun API is asked for the current King of the Hill (0 = off)
+    pub poll_secs: u64,
+    /// Only trigger on these mints; empty triggers on any token
+    pub watchlist: Vec<String>,
+    pub take_profit_percent: f64,
+    pub stop_loss_percent: f64,
+    /// Sell whatever is left after this long, in seconds (0 = no limit)
+    pub max_hold_secs: u64,
+}
+
+impl KothSettings {
+    /// Load King-of-the-Hill settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("KOTH_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            buy_sol: std::env::var("KOTH_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.1),
+            curve_progress: std::env::var("KOTH_CURVE_PROGRESS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(45.0),
+            poll_secs: std::env::var("KOTH_POLL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(5),
+            watchlist: std::env::var("KOTH_WATCHLIST")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|mint| !mint.is_empty())
+                .map(str::to_string)
+                .collect(),
+            take_profit_percent: std::env::var("KOTH_TAKE_PROFIT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(50.0),
+            stop_loss_percent: std::env::var("KOTH_STOP_LOSS_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            max_hold_secs: std::env::var("KOTH_MAX_HOLD_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(900),
+        }
+    }
+}
+
+/// Mints the API poller saw crowned, waiting for the strategy's next tick
+static KOTH_SIGNALS: LazyLock<Mutex<VecDeque<String>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+fn push_signal(mint: String) {
+    KOTH_SIGNALS.lock().unwrap_or_else(|e| e.into_inner()).push_back(mint);
+}
+
+fn take_signals() -> Vec<String> {
+    KOTH_SIGNALS.lock().unwrap_or_else(|e| e.into_inner()).drain(..).collect()
+}
+
+/// Poll the pump.fun API for the current King of the Hill, signalling each new one
+pub fn start_koth_poller(settings: KothSettings, logger: Logger) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.poll_secs.max(1)));
+        // the coin crowned before startup has held the spot for an unknown time, so it is not signalled
+        let mut current: Option<String> = None;
+        let mut first = true;
+        let mut failing = false;
+        loop {
+            interval.tick().await;
+            match pump_api().king_of_the_hill().await {
+                Ok(Some(coin)) => {
+                    failing = false;
+                    if current.as_deref() == Some(coin.mint.as_str()) {
+                        continue;
+                    }
+                    current = Some(coin.mint.clone());
+                    if first {
+                        first = false;
+                        logger.log(format!("Current King of the Hill: {} ({})", coin.symbol, coin.mint));
+                        continue;
+                    }
+                    if coin.complete {
+                        continue;
+                    }
+                    logger.log(format!("New King of the Hill: {} ({})", coin.symbol, coin.mint).green().to_string());
+                    push_signal(coin.mint);
+                }
+                Ok(None) => {
+                    failing = false;
+                    first = false;
+                }
+                // log once per outage
+                Err(e) if !failing => {
+                    failing = true;
+                    logger.error(format!("King of the Hill lookup failed: {}", e));
+                }
+                Err(_) => {}
+            }
+        }
+    });
+}
+
+/// Buys tokens reaching King of the Hill, with their own size and exits
+pub struct KothStrategy {
+    settings: KothSettings,
+    /// Mints last seen below the progress threshold and when
+    below: HashMap<String, Instant>,
+    /// Mints already bought or signalled once
+    triggered: HashSet<String>,
+    /// Mints whose positions this strategy exits
+    bought: HashSet<String>,
+}
+
+impl KothStrategy {
+    pub fn new(settings: KothSettings) -> Self {
+        Self {
+            settings,
+            below: HashMap::new(),
+            triggered: HashSet::new(),
+            bought: HashSet::new(),
+        }
+    }
+
+    fn watches(&self, mint: &str) -> bool {
+        self.settings.watchlist.is_empty() || self.settings.watchlist.iter().any(|watched| watched == mint)
+    }
+
+    fn buy(&mut self, mint: String, reason: String) -> Vec<StrategyAction> {
+        if !self.watches(&mint) || !self.triggered.insert(mint.clone()) {
+            return Vec::new();
+        }
+        self.bought.insert(mint.clone());
+        vec![StrategyAction::Buy {
+            mint,
+            sol_amount: Some(self.settings.buy_sol),
+            confidence: None,
+            reason,
+        }]
+    }
+}
+
+impl Strategy for KothStrategy {
+    fn name(&self) -> &'static str {
+        "koth"
+    }
+
+    fn on_trade_event(&mut self, event: &TradeEvent, at: Instant) -> Vec<StrategyAction> {
+        if self.settings.curve_progress <= 0.0 {
+            return Vec::new();
+        }
+        let mint = event.mint.to_string();
+        if self.triggered.contains(&mint) {
+            return Vec::new();
+        }
+        let progress = curve_progress_percent(&BondingCurveReserves {
+            virtual_token_reserves: event.virtual_token_reserves,
+            virtual_sol_reserves: event.virtual_sol_reserves,
+        });
+        if progress < self.settings.curve_progress {
+            self.below.insert(mint, at);
+            return Vec::new();
+        }
+        // only a crossing counts; tokens first seen above the threshold were already there
+        if self.below.remove(&mint).is_none() {
+            return Vec::new();
+        }
+        self.buy(mint, format!("curve {:.1}% crossed {:.0}%", progress, self.settings.curve_progress))
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Vec<StrategyAction> {
+        let ttl = Duration::from_secs(BELOW_TTL_SECS);
+        self.below.retain(|_, seen| now.duration_since(*seen) < ttl);
+        let mut actions = Vec::new();
+        for mint in take_signals() {
+            actions.extend(self.buy(mint, "crowned King of the Hill".to_string()));
+        }
+        actions
+    }
+
+    fn on_position_update(&mut self, update: &PositionUpdate) -> Vec<StrategyAction> {
+        if !self.bought.contains(&update.mint) {
+            return Vec::new();
+        }
+        let pnl = update.pnl_percent();
+        let held_too_long = self.settings.max_hold_secs > 0 && update.opened_at
+            .map(|opened| opened.elapsed() >= Duration::from_secs(self.settings.max_hold_secs))
+            .unwrap_or(false);
+        let reason = if pnl >= self.settings.take_profit_percent {
+            format!("take profit at {:.1}%", pnl)
+        } else if pnl <= -self.settings.stop_loss_percent {
+            format!("stop loss at {:.1}%", pnl)
+        } else if held_too_long {
+            format!("held over {}s at {:.1}%", self.settings.max_hold_secs, pnl)
+        } else {
+            return Vec::new();
+        };
+        self.bought.remove(&update.mint);
+        vec![StrategyAction::Sell { mint: update.mint.clone(), percent: 100.0, reason }]
+    }
+
+    fn stream_accounts(&self) -> Option<Vec<String>> {
+        (!self.settings.watchlist.is_empty()).then(|| self.settings.watchlist.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use crate::dex::pump_fun::INITIAL_VIRTUAL_TOKEN_RESERVES;
+
+    fn trade(mint: Pubkey, tokens_sold: u64) -> TradeEvent {
+        TradeEvent {
+            mint,
+            sol_amount: 1_000_000_000,
+            token_amount: 1,
+            is_buy: true,
+            user: Pubkey::default(),
+            timestamp: 0,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: INITIAL_VIRTUAL_TOKEN_RESERVES - tokens_sold,
+        }
+    }
+
+    #[test]
+    fn test_buys_on_crossing_and_exits_once() {
+        let mut strategy = KothStrategy::new(KothSettings {
+            enabled: true,
+            buy_sol: 0.2,
+            curve_progress: 50.0,
+            poll_secs: 0,
+            watchlist: Vec::new(),
+            take_profit_percent: 40.0,
+            stop_loss_percent: 15.0,
+            max_hold_secs: 0,
+        });
+        let now = Instant::now();
+        let mint = Pubkey::new_unique();
+        // first seen above the threshold, no crossing
+        let above = Pubkey::new_unique();
+        assert!(strategy.on_trade_event(&trade(above, 500_000_000_000_000), now).is_empty());
+
+        assert!(strategy.on_trade_event(&trade(mint, 100_000_000_000_000), now).is_empty());
+        let actions = strategy.on_trade_event(&trade(mint, 500_000_000_000_000), now);
+        assert!(matches!(&actions[..], [StrategyAction::Buy { sol_amount: Some(sol), .. }] if *sol == 0.2));
+        assert!(strategy.on_trade_event(&trade(mint, 100_000_000_000_000), now).is_empty());
+        assert!(strategy.on_trade_event(&trade(mint, 600_000_000_000_000), now).is_empty());
+
+        let mut update = PositionUpdate { mint: mint.to_string(), entry_price: 1.0, price: 1.2, opened_at: Some(now) };
+        assert!(strategy.on_position_update(&update).is_empty());
+        update.price = 0.8;
+        assert_eq!(strategy.on_position_update(&update).len(), 1);
+        assert!(strategy.on_position_update(&update).is_empty());
+    }
+}
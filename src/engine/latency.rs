@@ -0,0 +1,287 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use serde::Serialize;
+use tokio::time::Instant;
+
+/// Number of completed trades kept for percentile calculations
+const DEFAULT_WINDOW: usize = 500;
+
+/// In-flight traces older than this are dropped (the trade never landed)
+const STALE_TRACE: Duration = Duration::from_secs(300);
+
+/// Pipeline stages of a snipe, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Transaction update received from the gRPC stream
+    GrpcReceive,
+    /// Create/trade instruction decoded
+    Decode,
+    /// Token passed the filters
+    Filter,
+    /// Swap instructions built
+    Build,
+    /// Blockhash fetched and transaction ready to sign
+    Sign,
+    /// Relay accepted the transaction
+    Submit,
+    /// Transaction confirmed on chain
+    Land,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 7] = [
+        Stage::GrpcReceive,
+        Stage::Decode,
+        Stage::Filter,
+        Stage::Build,
+        Stage::Sign,
+        Stage::Submit,
+        Stage::Land,
+    ];
+}
+
+/// Timestamps for a single trade
+#[derive(Debug, Clone)]
+struct Trace {
+    started_at: Instant,
+    last_mark: Instant,
+    relay: Option<String>,
+    stages: Vec<(Stage, Duration)>,
+}
+
+/// Per-stage durations of one finished trade
+#[derive(Debug, Clone)]
+struct CompletedTrace {
+    relay: String,
+    stages: HashMap<Stage, Duration>,
+    total: Duration,
+}
+
+/// p50/p95/p99 for one stage, in milliseconds
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LatencyPercentiles {
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+impl LatencyPercentiles {
+    fn from_durations(mut durations: Vec<Duration>) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+        durations.sort();
+        let pick = |p: f64| {
+            let idx = ((durations.len() as f64 * p).ceil() as usize).clamp(1, durations.len()) - 1;
+            durations[idx].as_secs_f64() * 1000.0
+        };
+        Self {
+            count: durations.len(),
+            p50_ms: pick(0.50),
+            p95_ms: pick(0.95),
+            p99_ms: pick(0.99),
+        }
+    }
+}
+
+/// Latency report for the status output
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LatencyReport {
+    /// Time spent in each stage (since the previous stage)
+    pub stages: Vec<StageLatency>,
+    /// First timestamp to landing, across all relays
+    pub total: LatencyPercentiles,
+    /// First timestamp to landing, per relay
+    pub by_relay: Vec<RelayLatency>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StageLatency {
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub latency: LatencyPercentiles,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RelayLatency {
+    pub relay: String,
+    #[serde(flatten)]
+    pub latency: LatencyPercentiles,
+}
+
+/// Timestamps each stage of every snipe and keeps a rolling window of finished trades
+///
+/// A trade is keyed by mint. Each `mark` records the time since the previous mark,
+/// so the per-stage numbers show exactly where the milliseconds go.
+pub struct LatencyTracer {
+    in_flight: HashMap<String, Trace>,
+    completed: VecDeque<CompletedTrace>,
+    window: usize,
+}
+
+impl LatencyTracer {
+    /// Create a tracer keeping the last `window` completed trades
+    pub fn new(window: usize) -> Self {
+        Self {
+            in_flight: HashMap::new(),
+            completed: VecDeque::new(),
+            window: window.max(1),
+        }
+    }
+
+    /// Start tracing a trade at `at` (usually when the gRPC update was received)
+    pub fn begin(&mut self, mint: &str, at: Instant) {
+        self.in_flight.insert(mint.to_string(), Trace {
+            started_at: at,
+            last_mark: at,
+            relay: None,
+            stages: vec![(Stage::GrpcReceive, Duration::ZERO)],
+        });
+    }
+
+    /// Record that `stage` finished now
+    ///
+    /// Trades that were not started with `begin` (e.g. manual buys) start here.
+    pub fn mark(&mut self, mint: &str, stage: Stage) {
+        self.mark_at(mint, stage, Instant::now());
+    }
+
+    pub fn mark_at(&mut self, mint: &str, stage: Stage, at: Instant) {
+        let trace = self.in_flight.entry(mint.to_string()).or_insert_with(|| Trace {
+            started_at: at,
+            last_mark: at,
+            relay: None,
+            stages: Vec::new(),
+        });
+        let elapsed = at.saturating_duration_since(trace.last_mark);
+        trace.stages.push((stage, elapsed));
+        trace.last_mark = at;
+    }
+
+    /// Record which relay the trade was sent through
+    pub fn set_relay(&mut self, mint: &str, relay: &str) {
+        if let Some(trace) = self.in_flight.get_mut(mint) {
+            trace.relay = Some(relay.to_string());
+        }
+    }
+
+    /// Mark the trade as landed and move it into the completed window
+    pub fn finish(&mut self, mint: &str) {
+        self.mark(mint, Stage::Land);
+        if let Some(trace) = self.in_flight.remove(mint) {
+            self.completed.push_back(CompletedTrace {
+                relay: trace.relay.unwrap_or_else(|| "unknown".to_string()),
+                total: trace.last_mark.saturating_duration_since(trace.started_at),
+                stages: trace.stages.into_iter().collect(),
+            });
+            while self.completed.len() > self.window {
+                self.completed.pop_front();
+            }
+        }
+        self.prune_stale();
+    }
+
+    /// Drop a trade that failed or was abandoned
+    pub fn discard(&mut self, mint: &str) {
+        self.in_flight.remove(mint);
+        self.prune_stale();
+    }
+
+    /// Percentiles for every stage, the total and each relay
+    pub fn report(&self) -> LatencyReport {
+        let stages = Stage::ALL
+            .iter()
+            .map(|stage| {
+                let durations = self.completed.iter().filter_map(|t| t.stages.get(stage).copied()).collect();
+                StageLatency {
+                    stage: *stage,
+                    latency: LatencyPercentiles::from_durations(durations),
+                }
+            })
+            .collect();
+
+        let total = LatencyPercentiles::from_durations(self.completed.iter().map(|t| t.total).collect());
+
+        let mut relays: Vec<String> = self.completed.iter().map(|t| t.relay.clone()).collect();
+        relays.sort();
+        relays.dedup();
+        let by_relay = relays
+            .into_iter()
+            .map(|relay| {
+                let durations = self.completed.iter().filter(|t| t.relay == relay).map(|t| t.total).collect();
+                RelayLatency {
+                    latency: LatencyPercentiles::from_durations(durations),
+                    relay,
+                }
+            })
+            .collect();
+
+        LatencyReport { stages, total, by_relay }
+    }
+
+    fn prune_stale(&mut self) {
+        self.in_flight.retain(|_, trace| trace.started_at.elapsed() < STALE_TRACE);
+    }
+}
+
+static LATENCY_TRACER: LazyLock<Mutex<LatencyTracer>> = LazyLock::new(|| {
+    let window = std::env::var("LATENCY_WINDOW")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_WINDOW);
+    Mutex::new(LatencyTracer::new(window))
+});
+
+/// Shared tracer used by the monitor, executor and confirmation tracker
+pub fn latency_tracer() -> std::sync::MutexGuard<'static, LatencyTracer> {
+    LATENCY_TRACER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_durations_and_percentiles() {
+        let mut tracer = LatencyTracer::new(10);
+        let start = Instant::now();
+        for i in 0..4u64 {
+            let mint = format!("mint{}", i);
+            tracer.begin(&mint, start);
+            tracer.mark_at(&mint, Stage::Build, start + Duration::from_millis(10));
+            tracer.mark_at(&mint, Stage::Submit, start + Duration::from_millis(10 + 5 * (i + 1)));
+            tracer.set_relay(&mint, "jito");
+            tracer.finish(&mint);
+        }
+
+        let report = tracer.report();
+        let build = &report.stages.iter().find(|s| s.stage == Stage::Build).unwrap().latency;
+        assert_eq!(build.count, 4);
+        assert!((build.p50_ms - 10.0).abs() < 0.001);
+
+        let submit = &report.stages.iter().find(|s| s.stage == Stage::Submit).unwrap().latency;
+        assert!((submit.p50_ms - 10.0).abs() < 0.001);
+        assert!((submit.p99_ms - 20.0).abs() < 0.001);
+
+        assert_eq!(report.by_relay.len(), 1);
+        assert_eq!(report.by_relay[0].relay, "jito");
+    }
+
+    #[test]
+    fn test_window_and_discard() {
+        let mut tracer = LatencyTracer::new(2);
+        for i in 0..5 {
+            let mint = format!("mint{}", i);
+            tracer.mark(&mint, Stage::Build);
+            tracer.finish(&mint);
+        }
+        tracer.mark("dropped", Stage::Build);
+        tracer.discard("dropped");
+
+        assert_eq!(tracer.report().total.count, 2);
+        assert!(tracer.in_flight.is_empty());
+    }
+}
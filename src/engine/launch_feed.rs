@@ -0,0 +1,422 @@
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use anchor_client::solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use futures_util::{SinkExt, StreamExt};
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeRequest, SubscribeUpdate, SubscribeUpdateTransactionInfo};
+
+use crate::common::logger::Logger;
+use crate::dex::launchlab::launches;
+use crate::dex::launchpad::{remember_launch, Launchpad, LaunchpadSettings};
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::decoder::{decode_transaction, PumpEvent};
+use crate::dex::pump_fun::{remember_token_creator, PUMP_FUN_MINT_AUTHORITY};
+use crate::engine::ata_precreate::precreate;
+use crate::engine::confidence::{ConfidenceInputs, ConfidenceScorer, ConfidenceSettings};
+use crate::engine::execution_queue::Priority;
+use crate::engine::filter_stats::record_rejection;
+use crate::engine::observer::{observe_launch, observing, ObservedDecision};
+use crate::engine::shadow::{paper_buy, shadow_book, shadow_flag, shadow_var};
+use crate::engine::strategy::{spawn_actions, StrategyAction, StrategyRegistry};
+use crate::engine::trade_executor::{price_from_reserves, TradeExecutor};
+use crate::engine::tx_cache::prebuild;
+use crate::services::alerts::{alert, Alert};
+use crate::services::backpressure::{buffered, BackpressureSettings, EventClass};
+use crate::services::dedup::{DedupSettings, SignatureCache};
+use crate::services::geyser::{wait_to_reconnect, GeyserSource, GeyserSubscription};
+use crate::services::market_cap::{launch_reserves, MarketCap};
+use crate::services::metrics::metrics;
+use crate::services::price_feed::sol_usd;
+use crate::services::pump_api::{pump_api, CoinPageFilter};
+use crate::services::subscription::{ping_request, SubscriptionBuilder};
+use crate::services::telegram::{FilterRange, TokenInfo};
+use crate::services::token_metadata::MetadataFilter;
+
+/// Launch transactions of the enabled launchpads
+///
+/// pump.fun creates are the only instructions touching the shared mint authority;
+/// LaunchLab transactions are narrowed to the configured platform.
+fn subscribe_request(settings: &LaunchpadSettings) -> SubscribeRequest {
+    let ids = program_ids();
+    let mut builder = SubscriptionBuilder::new();
+    if settings.is_enabled(Launchpad::PumpFun) {
+        let mint_authority = Pubkey::from_str(PUMP_FUN_MINT_AUTHORITY).unwrap();
+        builder = builder.transactions("launches_pumpfun", &[], &[ids.pump_fun_program, mint_authority]);
+    }
+    if settings.is_enabled(Launchpad::LaunchLab) {
+        builder = builder.transactions("launches_launchlab", &[], &[ids.launchlab_program, ids.launchlab_platform_config]);
+    }
+    builder.build()
+}
+
+/// A launch that waited behind a storm is too old to snipe, so launches may be shed
+fn classify(update: &SubscribeUpdate) -> EventClass {
+    match update.update_oneof {
+        Some(UpdateOneof::Transaction(_)) => EventClass::Bulk,
+        _ => EventClass::Critical,
+    }
+}
+
+/// Tokens launched in a transaction on the enabled launchpads
+fn new_tokens(info: &SubscribeUpdateTransactionInfo, settings: &LaunchpadSettings) -> Vec<(Launchpad, TokenInfo)> {
+    let mut tokens = Vec::new();
+    if settings.is_enabled(Launchpad::PumpFun) {
+        let decoded = decode_transaction(info);
+        for event in &decoded.events {
+            let PumpEvent::Create(create) = event else {
+                continue;
+            };
+            // the curve's creator, whose vault the buys pay into
+            if let Some(creator) = create.creator {
+                remember_token_creator(create.mint, creator);
+            }
+            // the payer and the named creator both count as the dev
+            let dev_buy = decoded.dev_buy(&create.mint, &[create.user, create.creator.unwrap_or(create.user)]);
+            tokens.push((Launchpad::PumpFun, TokenInfo {
+                address: create.mint.to_string(),
+                name: Some(create.name.clone()),
+                symbol: Some(create.symbol.clone()),
+                uri: Some(create.uri.clone()),
+                dev_buy_amount: Some(dev_buy.sol_lamports as f64 / LAMPORTS_PER_SOL as f64),
+                dev_buy_lamports: Some(dev_buy.sol_lamports),
+                dev_buy_tokens: Some(dev_buy.token_amount),
+                // priced off the curve as the dev buy left it
+                market_cap: MarketCap::from_reserves(&launch_reserves(dev_buy.sol_lamports, dev_buy.token_amount), sol_usd())
+                    .in_configured_unit(),
+                dev_wallet: Some(create.creator.unwrap_or(create.user).to_string()),
+                token_age_secs: Some(0),
+                ..Default::default()
+            }));
+        }
+    }
+    if settings.is_enabled(Launchpad::LaunchLab) {
+        let platform = program_ids().launchlab_platform_config;
+        for launch in launches(info).into_iter().filter(|launch| launch.platform_config == platform) {
+            tokens.push((Launchpad::LaunchLab, TokenInfo {
+                address: launch.mint.to_string(),
+                name: Some(launch.name),
+                symbol: Some(launch.symbol),
+                uri: Some(launch.uri),
+                dev_buy_amount: Some(launch.dev_buy_lamports as f64 / LAMPORTS_PER_SOL as f64),
+                dev_buy_lamports: Some(launch.dev_buy_lamports),
+                dev_wallet: Some(launch.creator.to_string()),
+                token_age_secs: Some(0),
+                ..Default::default()
+            }));
+        }
+    }
+    tokens
+}
+
+/// Hand a launch to the strategies
+fn dispatch(executor: &Arc<TradeExecutor>, registry: &Arc<Mutex<StrategyRegistry>>, token: &TokenInfo, logger: &Logger) {
+    let actions = match registry.lock() {
+        Ok(mut registry) => registry.on_new_token(token),
+        Err(_) => return,
+    };
+    // the buys are observed once they pass the strategy filters, launches nobody buys here
+    let bought = actions
+        .iter()
+        .any(|tagged| matches!(&tagged.action, StrategyAction::Buy { mint, .. } if *mint == token.address));
+    if !bought {
+        observe_skip(token, "no strategy buy".to_string());
+    }
+    spawn_actions(executor, actions, Priority::LaunchBuy, logger);
+}
+
+/// Record a launch the filters or strategies passed on, when observing
+///
+/// The launch is priced off the pump.fun curve as the dev buy left it.
+fn observe_skip(token: &TokenInfo, reason: String) {
+    if !observing() {
+        return;
+    }
+    let price = price_from_reserves(&launch_reserves(
+        token.dev_buy_lamports.unwrap_or(0),
+        token.dev_buy_tokens.unwrap_or(0),
+    ));
+    let score = ConfidenceScorer::new(ConfidenceSettings::from_env())
+        .score(&ConfidenceInputs::from_token_info(token))
+        .map(|score| score.score)
+        .unwrap_or(0.0);
+    observe_launch(&token.address, price, score, ObservedDecision::Skip(reason));
+}
+
+/// Dev buy range launches must fall in, checked straight from the launch transaction
+#[derive(Clone)]
+struct DevBuyFilter {
+    enabled: bool,
+    /// Bounds in SOL, compared in lamports
+    range: FilterRange<f64>,
+}
+
+impl DevBuyFilter {
+    fn from_env() -> Self {
+        let sol = |name: &str, default: f64| std::env::var(name).ok().and_then(|v| v.parse::<f64>().ok()).unwrap_or(default);
+        Self {
+            enabled: std::env::var("LAUNCH_DEV_BUY_FILTER")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            range: FilterRange {
+                min: sol("MIN_DEV_BUY", 5.0),
+                max: sol("MAX_DEV_BUY", 30.0),
+            },
+        }
+    }
+
+    fn rejection(&self, token: &TokenInfo) -> Option<String> {
+        let lamports = token.dev_buy_lamports.filter(|_| self.enabled)?;
+        (!self.range.contains_lamports(lamports)).then(|| format!(
+            "dev bought {:.9} SOL, outside {}-{} SOL",
+            lamports as f64 / LAMPORTS_PER_SOL as f64,
+            self.range.min,
+            self.range.max
+        ))
+    }
+}
+
+/// Filters that need an HTTP lookup before a launch is handed to the strategies
+#[derive(Clone)]
+struct LaunchScreen {
+    coin_page: CoinPageFilter,
+    metadata: MetadataFilter,
+    client: reqwest::Client,
+}
+
+impl LaunchScreen {
+    fn from_env() -> Self {
+        Self {
+            coin_page: CoinPageFilter::from_env(),
+            metadata: MetadataFilter::from_env(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Coin pages only exist for pump.fun launches
+    fn applies_to(&self, launchpad: Launchpad) -> bool {
+        self.metadata.is_enabled() || (launchpad == Launchpad::PumpFun && self.coin_page.is_enabled())
+    }
+
+    /// Look the launch up, returning the failed filter and why
+    async fn rejection(&self, launchpad: Launchpad, token: &mut TokenInfo) -> Option<(&'static str, String)> {
+        if self.metadata.is_enabled() {
+            // a launch screened twice, e.g. by the shadow filters, is only looked up once
+            if token.metadata.is_none() {
+                let uri = token.uri.clone().unwrap_or_default();
+                token.metadata = self.metadata.fetch(&self.client, &uri).await.ok();
+            }
+            if let Some(reason) = self.metadata.rejection(token.metadata.as_ref()) {
+                return Some(("metadata", reason));
+            }
+        }
+        if launchpad == Launchpad::PumpFun && self.coin_page.is_enabled() {
+            let coin = match token.pump_coin.clone() {
+                Some(coin) => Ok(Some(coin)),
+                None => pump_api().coin_when_indexed(&token.address).await,
+            };
+            let reason = match coin {
+                Ok(Some(coin)) => {
+                    let reason = self.coin_page.rejection(&coin);
+                    token.pump_coin = Some(coin);
+                    reason
+                }
+                Ok(None) => Some("coin page not found".to_string()),
+                Err(e) => Some(format!("coin page lookup failed: {}", e)),
+            };
+            if let Some(reason) = reason {
+                return Some(("coin_page", reason));
+            }
+        }
+        None
+    }
+}
+
+/// The alternative filter configuration, the live filters with every `SHADOW_` override applied
+#[derive(Clone)]
+struct ShadowScreen {
+    dev_buy: DevBuyFilter,
+    screen: LaunchScreen,
+    live_scorer: Arc<ConfidenceScorer>,
+    scorer: Arc<ConfidenceScorer>,
+}
+
+impl ShadowScreen {
+    fn from_env() -> Self {
+        let live_dev_buy = DevBuyFilter::from_env();
+        let mut screen = LaunchScreen::from_env();
+        let coin_page = &mut screen.coin_page;
+        coin_page.require_socials = shadow_flag("REQUIRE_SOCIALS", coin_page.require_socials);
+        coin_page.min_reply_count = shadow_var("MIN_REPLY_COUNT", coin_page.min_reply_count);
+        coin_page.require_image = shadow_flag("REQUIRE_IMAGE", coin_page.require_image);
+        let metadata = &mut screen.metadata;
+        metadata.require_metadata = shadow_flag("REQUIRE_METADATA", metadata.require_metadata);
+        metadata.require_twitter = shadow_flag("REQUIRE_TWITTER", metadata.require_twitter);
+        metadata.require_telegram = shadow_flag("REQUIRE_TELEGRAM", metadata.require_telegram);
+        metadata.require_website = shadow_flag("REQUIRE_WEBSITE", metadata.require_website);
+
+        let live_confidence = ConfidenceSettings::from_env();
+        let mut confidence = live_confidence.clone();
+        confidence.enabled = shadow_flag("CONFIDENCE_GATE_ENABLED", confidence.enabled);
+        confidence.min_buy_confidence = shadow_var("MIN_BUY_CONFIDENCE", confidence.min_buy_confidence);
+        Self {
+            dev_buy: DevBuyFilter {
+                enabled: shadow_flag("LAUNCH_DEV_BUY_FILTER", live_dev_buy.enabled),
+                range: FilterRange {
+                    min: shadow_var("MIN_DEV_BUY", live_dev_buy.range.min),
+                    max: shadow_var("MAX_DEV_BUY", live_dev_buy.range.max),
+                },
+            },
+            screen,
+            live_scorer: Arc::new(ConfidenceScorer::new(live_confidence)),
+            scorer: Arc::new(ConfidenceScorer::new(confidence)),
+        }
+    }
+
+    /// Whether the confidence gate of `scorer` lets the launch through, as the strategy buy would
+    fn confident(scorer: &ConfidenceScorer, token: &TokenInfo) -> bool {
+        if !scorer.settings().enabled {
+            return true;
+        }
+        match scorer.score(&ConfidenceInputs::from_token_info(token)) {
+            Some(score) => score.score >= scorer.settings().min_buy_confidence,
+            None => true,
+        }
+    }
+
+    /// Judge a launch the live filters passed or rejected, and paper-buy it for each that would buy
+    fn judge(&self, executor: &Arc<TradeExecutor>, launchpad: Launchpad, mut token: TokenInfo, live_passed: bool) {
+        let shadow = self.clone();
+        let executor = executor.clone();
+        tokio::spawn(async move {
+            let live = live_passed && Self::confident(&shadow.live_scorer, &token);
+            let passed = shadow.dev_buy.rejection(&token).is_none()
+                && shadow.screen.rejection(launchpad, &mut token).await.is_none();
+            let shadow_buys = passed && Self::confident(&shadow.scorer, &token);
+            paper_buy(&executor, &token.address, live, shadow_buys).await;
+        });
+    }
+}
+
+/// Screen a launch and dispatch it if it passes
+async fn screen_launch(
+    executor: Arc<TradeExecutor>,
+    registry: Arc<Mutex<StrategyRegistry>>,
+    screen: LaunchScreen,
+    shadow: Option<ShadowScreen>,
+    launchpad: Launchpad,
+    mut token: TokenInfo,
+    logger: Logger,
+) {
+    let rejection = screen.rejection(launchpad, &mut token).await;
+    if let Some((filter, reason)) = &rejection {
+        logger.log(format!("Skipping {}: {}", token.address, reason).yellow().to_string());
+        record_rejection(filter, &token.address);
+        alert(Alert::filter(&token.address, filter, reason));
+        observe_skip(&token, reason.clone());
+    } else {
+        dispatch(&executor, &registry, &token, &logger);
+    }
+    if let Some(shadow) = shadow {
+        shadow.judge(&executor, launchpad, token, rejection.is_none());
+    }
+}
+
+/// Stream launches into the registry until the connection drops
+async fn run_stream(
+    executor: &Arc<TradeExecutor>,
+    registry: &Arc<Mutex<StrategyRegistry>>,
+    settings: &LaunchpadSettings,
+    source: &dyn GeyserSource,
+    seen: &mut SignatureCache,
+    logger: &Logger,
+) -> Result<()> {
+    let GeyserSubscription { sink: mut subscribe_tx, stream } = source.subscribe().await?;
+    let mut stream = buffered("launches", stream, &BackpressureSettings::from_env(), classify);
+    subscribe_tx.send(subscribe_request(settings)).await?;
+    let screen = LaunchScreen::from_env();
+    let dev_buy = DevBuyFilter::from_env();
+    let shadow = shadow_book().settings().enabled.then(ShadowScreen::from_env);
+
+    while let Some(message) = stream.next().await {
+        match message?.update_oneof {
+            Some(UpdateOneof::Transaction(tx)) => {
+                let Some(info) = tx.transaction else {
+                    continue;
+                };
+                if !seen.first_seen(&info.signature, Instant::now()) {
+                    continue;
+                }
+                for (launchpad, token) in new_tokens(&info, settings) {
+                    metrics().events_processed.inc();
+                    remember_launch(&token.address, launchpad);
+                    logger.log(format!(
+                        "New {} launch: {} ({})",
+                        launchpad.name(),
+                        token.address,
+                        token.symbol.as_deref().unwrap_or("?"),
+                    ));
+                    if let Some(reason) = dev_buy.rejection(&token) {
+                        logger.log(format!("Skipping {}: {}", token.address, reason).yellow().to_string());
+                        record_rejection("dev_buy", &token.address);
+                        observe_skip(&token, reason);
+                        if let Some(shadow) = &shadow {
+                            shadow.judge(executor, launchpad, token, false);
+                        }
+                        continue;
+                    }
+                    // metadata and coin page lookups take a while, so they run off the stream
+                    if screen.applies_to(launchpad) {
+                        // and the token account and swap templates can be prepared in the meantime
+                        if !observing() {
+                            precreate(executor, &token.address, logger);
+                            prebuild(executor, &token.address, logger);
+                        }
+                        tokio::spawn(screen_launch(
+                            executor.clone(),
+                            registry.clone(),
+                            screen.clone(),
+                            shadow.clone(),
+                            launchpad,
+                            token,
+                            logger.clone(),
+                        ));
+                        continue;
+                    }
+                    dispatch(executor, registry, &token, logger);
+                    if let Some(shadow) = &shadow {
+                        shadow.judge(executor, launchpad, token, true);
+                    }
+                }
+            }
+            Some(UpdateOneof::Ping(_)) => {
+                subscribe_tx.send(ping_request()).await?;
+            }
+            _ => {}
+        }
+    }
+    Err(anyhow!("Stream closed"))
+}
+
+/// Start detecting launches on the platforms listed in `LAUNCHPADS`
+///
+/// Each launch is recorded with its launchpad, so the executor routes its trades
+/// through the right program, and handed to the strategies' `on_new_token`.
+pub async fn start_launch_feed_system(
+    executor: Arc<TradeExecutor>,
+    registry: Arc<Mutex<StrategyRegistry>>,
+    settings: LaunchpadSettings,
+    source: Arc<dyn GeyserSource>,
+    logger: Logger,
+) {
+    let names: Vec<&str> = settings.launchpads.iter().map(Launchpad::name).collect();
+    logger.log(format!("Watching launches on {}", names.join(", ")));
+    tokio::spawn(async move {
+        let mut seen = SignatureCache::new(DedupSettings::from_env());
+        loop {
+            let result = run_stream(&executor, &registry, &settings, source.as_ref(), &mut seen, &logger).await;
+            wait_to_reconnect(source.as_ref(), "Launch", result, &logger).await;
+        }
+    });
+}
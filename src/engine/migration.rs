@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use colored::Colorize;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::alerts::{alert, Alert};
+
+/// Migration watch settings
+#[derive(Debug, Clone)]
+pub struct MigrationSettings {
+    pub enabled: bool,
+    /// How often held mints are checked for a completed curve, in seconds
+    pub check_interval_secs: u64,
+    /// A completed curve whose pool is still missing after this long is reported, in seconds
+    pub pending_alert_secs: u64,
+}
+
+impl MigrationSettings {
+    /// Load migration watch settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("MIGRATION_WATCH_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            check_interval_secs: std::env::var("MIGRATION_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(5),
+            pending_alert_secs: std::env::var("MIGRATION_PENDING_ALERT_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+        }
+    }
+}
+
+/// Where a held mint stands after its curve completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// The curve completed and the pool it migrates into is not live yet
+    Pending { since: Instant, alerted: bool },
+    /// The mint trades on `pool` of `venue`
+    Migrated { pool: Pubkey, venue: &'static str },
+}
+
+/// Held mints that left their bonding curve
+#[derive(Debug, Default)]
+pub struct Migrations {
+    mints: HashMap<String, MigrationState>,
+}
+
+impl Migrations {
+    /// The curve of `mint` completed, returning whether this is new
+    pub fn completed(&mut self, mint: &str, now: Instant) -> bool {
+        if self.mints.contains_key(mint) {
+            return false;
+        }
+        self.mints.insert(mint.to_string(), MigrationState::Pending { since: now, alerted: false });
+        true
+    }
+
+    /// `mint` now trades on `pool`, returning whether the position was re-bound to it
+    pub fn migrated(&mut self, mint: &str, pool: Pubkey, venue: &'static str) -> bool {
+        let migrated = MigrationState::Migrated { pool, venue };
+        self.mints.insert(mint.to_string(), migrated) != Some(migrated)
+    }
+
+    pub fn state(&self, mint: &str) -> Option<MigrationState> {
+        self.mints.get(mint).copied()
+    }
+
+    /// Pool a migrated `mint` was re-bound to
+    pub fn pool_of(&self, mint: &str) -> Option<Pubkey> {
+        match self.mints.get(mint)? {
+            MigrationState::Migrated { pool, .. } => Some(*pool),
+            MigrationState::Pending { .. } => None,
+        }
+    }
+
+    /// Pending mints waiting longer than `after`, each returned once
+    pub fn overdue(&mut self, after: Duration, now: Instant) -> Vec<(String, Duration)> {
+        let mut overdue = Vec::new();
+        for (mint, state) in self.mints.iter_mut() {
+            if let MigrationState::Pending { since, alerted } = state {
+                let waited = now.duration_since(*since);
+                if !*alerted && waited >= after {
+                    *alerted = true;
+                    overdue.push((mint.clone(), waited));
+                }
+            }
+        }
+        overdue
+    }
+
+    /// Forget mints no longer held
+    pub fn retain(&mut self, held: &[String]) {
+        self.mints.retain(|mint, _| held.contains(mint));
+    }
+}
+
+static MIGRATIONS: LazyLock<Mutex<Migrations>> = LazyLock::new(|| Mutex::new(Migrations::default()));
+
+/// Shared migration state, written by the executor and the watch
+pub fn migrations() -> MutexGuard<'static, Migrations> {
+    MIGRATIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Watch held mints for a completed curve and re-bind their positions to the new pool
+///
+/// Resolving the venue of a held mint records a completed curve and, once the pool is live,
+/// the pool it migrated into. Later buys, sells and price checks of the mint go straight to
+/// that pool. Entry price, take-profit progress and token rules are kept by mint and carry over.
+pub async fn start_migration_watch(executor: Arc<TradeExecutor>, settings: MigrationSettings, logger: Logger) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.check_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            let held = executor.open_positions();
+            migrations().retain(&held);
+            for mint in &held {
+                if migrations().pool_of(mint).is_some() {
+                    continue;
+                }
+                if let Err(e) = executor.rebind_if_migrated(mint).await {
+                    logger.debug(format!("{}: {}", mint, e));
+                }
+            }
+            let overdue = migrations().overdue(Duration::from_secs(settings.pending_alert_secs), Instant::now());
+            for (mint, waited) in overdue {
+                let message = format!(
+                    "{} completed its curve {}s ago and its pool is still not live, the position cannot be sold yet",
+                    mint, waited.as_secs()
+                );
+                logger.error(message.red().to_string());
+                alert(Alert::debug(&mint, &message));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_states() {
+        let mut migrations = Migrations::default();
+        let now = Instant::now();
+        assert!(migrations.completed("mint", now));
+        assert!(!migrations.completed("mint", now));
+        assert_eq!(migrations.pool_of("mint"), None);
+        assert!(migrations.overdue(Duration::from_secs(60), now + Duration::from_secs(30)).is_empty());
+        assert_eq!(migrations.overdue(Duration::from_secs(60), now + Duration::from_secs(61)).len(), 1);
+        // reported once
+        assert!(migrations.overdue(Duration::from_secs(60), now + Duration::from_secs(120)).is_empty());
+
+        let pool = Pubkey::new_unique();
+        assert!(migrations.migrated("mint", pool, "PumpSwap"));
+        assert!(!migrations.migrated("mint", pool, "PumpSwap"));
+        assert_eq!(migrations.pool_of("mint"), Some(pool));
+
+        migrations.retain(&[]);
+        assert_eq!(migrations.state("mint"), None);
+    }
+}
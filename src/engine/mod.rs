@@ -4,8 +4,56 @@ pub mod token_tracker;
 pub mod token_selling;
 pub mod token_buying;
 pub mod advanced_trading;
+pub mod ata_cleanup;
+pub mod ata_precreate;
+pub mod balance_monitor;
 pub mod bonding_curve;
+pub mod budget;
+pub mod bundle_analyzer;
+pub mod circuit_breaker;
+pub mod confidence;
+pub mod cooldown;
+pub mod copy_trade;
+pub mod curve_feed;
+pub mod dev_watch;
+pub mod execution_queue;
+pub mod fee_ledger;
+pub mod filter_stats;
 pub mod risk_management;
 pub mod enhanced_monitor;
 pub mod token_list_manager;
 pub mod enhanced_token_trader;
+pub mod indicators;
+pub mod insider_analyzer;
+pub mod inverse_buy;
+pub mod koth;
+pub mod latency;
+pub mod migration;
+pub mod launch_feed;
+pub mod momentum;
+pub mod observer;
+pub mod panic;
+pub mod position_limits;
+pub mod position_manager;
+pub mod position_marks;
+pub mod position_sizer;
+pub mod profit_sweep;
+pub mod recent_buys;
+pub mod relay_selector;
+pub mod session_report;
+pub mod shadow;
+pub mod shutdown;
+pub mod slippage;
+pub mod slot_sync;
+pub mod snipe;
+pub mod split_exit;
+pub mod stop_loss;
+pub mod strategy;
+pub mod strategy_ledger;
+pub mod timer;
+pub mod token_age;
+pub mod token_rules;
+pub mod trade_executor;
+pub mod tx_cache;
+pub mod wallet_pool;
+pub mod wsol;
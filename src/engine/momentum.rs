@@ -0,0 +1,234 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use anchor_client::solana_sdk::native_token::LAMPORTS_PER_SOL;
+use tokio::time::Instant;
+
+use crate::dex::pump_fun::TradeEvent;
+use crate::engine::strategy::{Strategy, StrategyAction};
+
+/// Momentum entry settings
+#[derive(Debug, Clone)]
+pub struct MomentumSettings {
+    pub enabled: bool,
+    /// Rolling window the velocity is measured over, in seconds
+    pub window_secs: u64,
+    /// Buys needed inside the window
+    pub min_buys: usize,
+    /// SOL bought inside the window
+    pub min_volume_sol: f64,
+    /// Share of buys among all trades in the window (0.0-1.0)
+    pub min_buy_ratio: f64,
+    /// After a signal, ignore the mint for this long, in seconds
+    pub cooldown_secs: u64,
+}
+
+impl MomentumSettings {
+    /// Load momentum settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("MOMENTUM_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            window_secs: std::env::var("MOMENTUM_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(10),
+            min_buys: std::env::var("MOMENTUM_MIN_BUYS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(15),
+            min_volume_sol: std::env::var("MOMENTUM_MIN_VOLUME_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(5.0),
+            min_buy_ratio: std::env::var("MOMENTUM_MIN_BUY_RATIO")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.7),
+            cooldown_secs: std::env::var("MOMENTUM_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+        }
+    }
+}
+
+/// A mint whose buying velocity crossed the thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct MomentumSignal {
+    pub mint: String,
+    pub buys: usize,
+    pub sells: usize,
+    /// SOL bought inside the window
+    pub volume_sol: f64,
+    pub buys_per_sec: f64,
+    /// How far past the thresholds the window is, 0.0-1.0
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tick {
+    at: Instant,
+    is_buy: bool,
+    sol: f64,
+}
+
+/// Rolling per-mint windows of buys, sells and SOL volume
+pub struct MomentumTracker {
+    settings: MomentumSettings,
+    windows: HashMap<String, VecDeque<Tick>>,
+    /// Mints that signalled recently and when
+    cooldowns: HashMap<String, Instant>,
+}
+
+impl MomentumTracker {
+    /// Create a new momentum tracker
+    pub fn new(settings: MomentumSettings) -> Self {
+        Self {
+            settings,
+            windows: HashMap::new(),
+            cooldowns: HashMap::new(),
+        }
+    }
+
+    /// Record a trade; returns a signal when the mint's window crosses the thresholds
+    pub fn record(&mut self, mint: &str, is_buy: bool, sol: f64, at: Instant) -> Option<MomentumSignal> {
+        let window = Duration::from_secs(self.settings.window_secs.max(1));
+        let ticks = self.windows.entry(mint.to_string()).or_default();
+        ticks.push_back(Tick { at, is_buy, sol });
+        while ticks.front().map(|tick| at.duration_since(tick.at) > window).unwrap_or(false) {
+            ticks.pop_front();
+        }
+
+        if let Some(since) = self.cooldowns.get(mint) {
+            if at.duration_since(*since) < Duration::from_secs(self.settings.cooldown_secs) {
+                return None;
+            }
+        }
+
+        let buys = ticks.iter().filter(|tick| tick.is_buy).count();
+        let sells = ticks.len() - buys;
+        let volume_sol: f64 = ticks.iter().filter(|tick| tick.is_buy).map(|tick| tick.sol).sum();
+        let buy_ratio = buys as f64 / ticks.len() as f64;
+        if buys < self.settings.min_buys.max(1)
+            || volume_sol < self.settings.min_volume_sol
+            || buy_ratio < self.settings.min_buy_ratio
+        {
+            return None;
+        }
+
+        self.cooldowns.insert(mint.to_string(), at);
+        let overshoot = (buys as f64 / self.settings.min_buys.max(1) as f64)
+            .min(volume_sol / self.settings.min_volume_sol.max(f64::EPSILON));
+        Some(MomentumSignal {
+            mint: mint.to_string(),
+            buys,
+            sells,
+            volume_sol,
+            buys_per_sec: buys as f64 / window.as_secs_f64(),
+            // exactly at the thresholds is 0.5, twice the thresholds or more is 1.0
+            confidence: (overshoot / 2.0).clamp(0.5, 1.0),
+        })
+    }
+
+    /// Drop windows that have gone quiet and expired cooldowns
+    pub fn prune(&mut self, now: Instant) {
+        let window = Duration::from_secs(self.settings.window_secs.max(1));
+        self.windows.retain(|_, ticks| {
+            ticks.back().map(|tick| now.duration_since(tick.at) <= window).unwrap_or(false)
+        });
+        let cooldown = Duration::from_secs(self.settings.cooldown_secs);
+        self.cooldowns.retain(|_, since| now.duration_since(*since) < cooldown);
+    }
+
+    pub fn tracked_mints(&self) -> usize {
+        self.windows.len()
+    }
+}
+
+/// Momentum entries as a pluggable strategy
+pub struct MomentumStrategy {
+    tracker: MomentumTracker,
+}
+
+impl MomentumStrategy {
+    pub fn new(settings: MomentumSettings) -> Self {
+        Self { tracker: MomentumTracker::new(settings) }
+    }
+}
+
+impl Strategy for MomentumStrategy {
+    fn name(&self) -> &'static str {
+        "momentum"
+    }
+
+    fn on_trade_event(&mut self, event: &TradeEvent, at: Instant) -> Vec<StrategyAction> {
+        let sol = event.sol_amount as f64 / LAMPORTS_PER_SOL as f64;
+        let Some(signal) = self.tracker.record(&event.mint.to_string(), event.is_buy, sol, at) else {
+            return Vec::new();
+        };
+        vec![StrategyAction::Buy {
+            mint: signal.mint.clone(),
+            sol_amount: None,
+            confidence: Some(signal.confidence),
+            reason: format!(
+                "{} buys / {} sells, {:.2} SOL in window ({:.1} buys/s)",
+                signal.buys, signal.sells, signal.volume_sol, signal.buys_per_sec
+            ),
+        }]
+    }
+
+    fn on_tick(&mut self, now: Instant) -> Vec<StrategyAction> {
+        self.tracker.prune(now);
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> MomentumTracker {
+        MomentumTracker::new(MomentumSettings {
+            enabled: true,
+            window_secs: 10,
+            min_buys: 3,
+            min_volume_sol: 1.0,
+            min_buy_ratio: 0.6,
+            cooldown_secs: 60,
+        })
+    }
+
+    #[test]
+    fn test_signal_on_velocity_then_cooldown() {
+        let mut tracker = tracker();
+        let start = Instant::now();
+        assert!(tracker.record("mint", true, 0.4, start).is_none());
+        assert!(tracker.record("mint", true, 0.4, start + Duration::from_secs(1)).is_none());
+        let signal = tracker.record("mint", true, 0.4, start + Duration::from_secs(2)).unwrap();
+        assert_eq!(signal.buys, 3);
+        assert!((signal.volume_sol - 1.2).abs() < 1e-9);
+        assert!(signal.confidence >= 0.5 && signal.confidence <= 1.0);
+
+        // cooldown suppresses repeats
+        assert!(tracker.record("mint", true, 5.0, start + Duration::from_secs(3)).is_none());
+    }
+
+    #[test]
+    fn test_old_ticks_and_sell_pressure_block_signal() {
+        let mut tracker = tracker();
+        let start = Instant::now();
+        tracker.record("mint", true, 1.0, start);
+        tracker.record("mint", true, 1.0, start + Duration::from_secs(1));
+        // the first two buys fall out of the window
+        assert!(tracker.record("mint", true, 1.0, start + Duration::from_secs(20)).is_none());
+
+        let mut selling = self::tracker();
+        for i in 0..3 {
+            selling.record("other", false, 1.0, start + Duration::from_millis(i));
+        }
+        for i in 0..3 {
+            assert!(selling.record("other", true, 1.0, start + Duration::from_millis(10 + i)).is_none());
+        }
+    }
+}
@@ -15,7 +15,7 @@ use std::str::FromStr;
 use base64;
 use anchor_client::solana_client::rpc_client::RpcClient;
 use spl_token::state::Mint;
-use crate::dex::pump_fun::{get_pda, PUMP_PROGRAM};
+use crate::dex::pump_fun::get_pda;
 // PumpFun constants
 pub const PUMPFUN_CREATE_DATA_PREFIX: &str = "Program data: G3KpTd7rY3Y";
 pub const PUMP_FUN_BUY_OR_SELL_PROGRAM_DATA_PREFIX: &str = "Program data: vdt/007mYe";
@@ -32,7 +32,6 @@ pub struct BondingCurveInfo {
     pub new_virtual_token_reserve: u64,
 }
 
-#[derive(Clone, Debug)]
 pub async fn new_token_trader_pumpfun(
     _yellowstone_grpc_http: String,
     _yellowstone_grpc_token: String,
@@ -44,8 +43,8 @@ pub async fn new_token_trader_pumpfun(
     _blacklist: Blacklist,
     _time_exceed: u64,
     _counter_limit: u64,
-    _min_dev_buy: u64,
-    _max_dev_buy: u64,
+    _min_dev_buy: f64,
+    _max_dev_buy: f64,
     _telegram_bot_token: String,
     _telegram_chat_id: String,
     _bundle_check: bool,
@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use tokio::time;
+
+use crate::common::logger::Logger;
+use crate::services::telegram::TelegramService;
+
+/// Observer mode settings
+#[derive(Debug, Clone)]
+pub struct ObserverSettings {
+    /// Run the full pipeline on every launch but never send a transaction
+    pub enabled: bool,
+    /// How long to follow a launch before classifying its outcome (seconds)
+    pub outcome_window_secs: u64,
+    /// Peak gain from first-seen price that counts as a winner (%)
+    pub win_threshold_percent: f64,
+    /// Drawdown from first-seen price that counts as a loser (%)
+    pub loss_threshold_percent: f64,
+    /// Directory where finalized observations are appended as JSON lines
+    pub output_dir: String,
+}
+
+impl ObserverSettings {
+    /// Load observer settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("OBSERVER_MODE").unwrap_or_default() == "true",
+            outcome_window_secs: std::env::var("OBSERVER_OUTCOME_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1800),
+            win_threshold_percent: std::env::var("OBSERVER_WIN_THRESHOLD_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(100.0),
+            loss_threshold_percent: std::env::var("OBSERVER_LOSS_THRESHOLD_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(50.0),
+            output_dir: std::env::var("OBSERVER_OUTPUT_DIR").unwrap_or_else(|_| "observer".to_string()),
+        }
+    }
+}
+
+/// What the bot would have done with a launch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ObservedDecision {
+    /// All filters passed, the bot would have bought
+    WouldBuy,
+    /// A filter rejected the launch
+    Skip(String),
+}
+
+/// How the launch performed after we first saw it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LaunchOutcome {
+    /// Peak price reached the win threshold
+    Winner,
+    /// Price fell through the loss threshold without reaching the win threshold
+    Loser,
+    /// Neither threshold was hit within the window
+    Flat,
+}
+
+/// A single launch followed by the observer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservedLaunch {
+    pub mint: String,
+    pub first_seen: DateTime<Utc>,
+    pub entry_price: f64,
+    pub score: f64,
+    pub decision: ObservedDecision,
+    pub peak_price: f64,
+    pub min_price: f64,
+    pub last_price: f64,
+    pub outcome: Option<LaunchOutcome>,
+}
+
+impl ObservedLaunch {
+    /// Highest gain seen relative to the entry price (%)
+    pub fn peak_gain_percent(&self) -> f64 {
+        if self.entry_price <= 0.0 {
+            return 0.0;
+        }
+        (self.peak_price / self.entry_price - 1.0) * 100.0
+    }
+
+    /// Deepest drawdown seen relative to the entry price (%)
+    pub fn max_drawdown_percent(&self) -> f64 {
+        if self.entry_price <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - self.min_price / self.entry_price) * 100.0
+    }
+
+    fn classify(&self, settings: &ObserverSettings) -> LaunchOutcome {
+        if self.peak_gain_percent() >= settings.win_threshold_percent {
+            LaunchOutcome::Winner
+        } else if self.max_drawdown_percent() >= settings.loss_threshold_percent {
+            LaunchOutcome::Loser
+        } else {
+            LaunchOutcome::Flat
+        }
+    }
+}
+
+/// Daily summary of observed launches
+#[derive(Debug, Clone, Default)]
+pub struct ObserverReport {
+    pub date: String,
+    pub launches: usize,
+    pub would_buy: usize,
+    pub bought_winners: Vec<(String, f64)>,
+    pub bought_losers: Vec<(String, f64)>,
+    pub missed_winners: Vec<(String, f64)>,
+    pub avoided_losers: Vec<(String, f64)>,
+}
+
+impl ObserverReport {
+    /// Format the report as a Telegram HTML message
+    pub fn to_telegram_message(&self) -> String {
+        let top = |items: &Vec<(String, f64)>| -> String {
+            if items.is_empty() {
+                return "└ none\n".to_string();
+            }
+            let mut lines = String::new();
+            for (i, (mint, percent)) in items.iter().take(5).enumerate() {
+                let branch = if i + 1 == items.len().min(5) { "└" } else { "├" };
+                lines.push_str(&format!("{} <code>{}</code> {:+.1}%\n", branch, mint, percent));
+            }
+            lines
+        };
+
+        format!(
+            "<b>👀 OBSERVER REPORT</b> - {}\n\n\
+            <b>Launches scored:</b> {}\n\
+            <b>Would have bought:</b> {}\n\n\
+            <b>✅ Bought winners ({}):</b>\n{}\n\
+            <b>❌ Bought losers ({}):</b>\n{}\n\
+            <b>😬 Missed winners ({}):</b>\n{}\n\
+            <b>🛡️ Avoided losers ({}):</b>\n{}",
+            self.date,
+            self.launches,
+            self.would_buy,
+            self.bought_winners.len(), top(&self.bought_winners),
+            self.bought_losers.len(), top(&self.bought_losers),
+            self.missed_winners.len(), top(&self.missed_winners),
+            self.avoided_losers.len(), top(&self.avoided_losers),
+        )
+    }
+}
+
+/// Scores every launch and records what would have happened, without trading
+pub struct Observer {
+    logger: Logger,
+    settings: ObserverSettings,
+    /// Launches still inside their outcome window
+    active: HashMap<String, ObservedLaunch>,
+    /// Launches classified today
+    finalized: Vec<ObservedLaunch>,
+    /// Start of the current report day
+    current_day_start: DateTime<Utc>,
+}
+
+impl Observer {
+    /// Create a new observer
+    pub fn new(logger: Logger, settings: ObserverSettings) -> Self {
+        Self {
+            logger,
+            settings,
+            active: HashMap::new(),
+            finalized: Vec::new(),
+            current_day_start: Utc::now(),
+        }
+    }
+
+    /// Record the pipeline's decision for a newly detected launch
+    pub fn record_launch(&mut self, mint: &str, price: f64, score: f64, decision: ObservedDecision) {
+        if self.active.contains_key(mint) {
+            return;
+        }
+        self.logger.log(format!(
+            "[OBSERVE] => {} score {:.2} - {}",
+            mint,
+            score,
+            match &decision {
+                ObservedDecision::WouldBuy => "WOULD BUY".green().to_string(),
+                ObservedDecision::Skip(reason) => format!("skip ({})", reason).yellow().to_string(),
+            }
+        ));
+
+        self.active.insert(mint.to_string(), ObservedLaunch {
+            mint: mint.to_string(),
+            first_seen: Utc::now(),
+            entry_price: price,
+            score,
+            decision,
+            peak_price: price,
+            min_price: price,
+            last_price: price,
+            outcome: None,
+        });
+    }
+
+    /// Mints still inside their outcome window, whose trades the strategy stream follows
+    pub fn followed(&self) -> Vec<String> {
+        self.active.keys().cloned().collect()
+    }
+
+    /// Update the price of a followed launch
+    pub fn record_price(&mut self, mint: &str, price: f64) {
+        if let Some(launch) = self.active.get_mut(mint) {
+            launch.last_price = price;
+            launch.peak_price = launch.peak_price.max(price);
+            launch.min_price = launch.min_price.min(price);
+        }
+    }
+
+    /// Classify launches whose outcome window has passed
+    pub fn finalize_expired(&mut self) {
+        let window = chrono::Duration::seconds(self.settings.outcome_window_secs as i64);
+        let now = Utc::now();
+        let expired: Vec<String> = self.active
+            .iter()
+            .filter(|(_, launch)| now.signed_duration_since(launch.first_seen) >= window)
+            .map(|(mint, _)| mint.clone())
+            .collect();
+
+        for mint in expired {
+            if let Some(mut launch) = self.active.remove(&mint) {
+                launch.outcome = Some(launch.classify(&self.settings));
+                if let Err(e) = self.append_record(&launch) {
+                    self.logger.error(format!("Failed to write observer record: {}", e));
+                }
+                self.finalized.push(launch);
+            }
+        }
+    }
+
+    /// Build the report for everything finalized so far today
+    pub fn build_report(&self) -> ObserverReport {
+        let mut report = ObserverReport {
+            date: self.current_day_start.format("%Y-%m-%d").to_string(),
+            launches: self.finalized.len(),
+            ..Default::default()
+        };
+
+        for launch in &self.finalized {
+            let would_buy = launch.decision == ObservedDecision::WouldBuy;
+            if would_buy {
+                report.would_buy += 1;
+            }
+            match (launch.outcome, would_buy) {
+                (Some(LaunchOutcome::Winner), true) => report.bought_winners.push((launch.mint.clone(), launch.peak_gain_percent())),
+                (Some(LaunchOutcome::Winner), false) => report.missed_winners.push((launch.mint.clone(), launch.peak_gain_percent())),
+                (Some(LaunchOutcome::Loser), true) => report.bought_losers.push((launch.mint.clone(), -launch.max_drawdown_percent())),
+                (Some(LaunchOutcome::Loser), false) => report.avoided_losers.push((launch.mint.clone(), -launch.max_drawdown_percent())),
+                _ => {}
+            }
+        }
+
+        report.missed_winners.sort_by(|a, b| b.1.total_cmp(&a.1));
+        report.bought_winners.sort_by(|a, b| b.1.total_cmp(&a.1));
+        report.avoided_losers.sort_by(|a, b| a.1.total_cmp(&b.1));
+        report.bought_losers.sort_by(|a, b| a.1.total_cmp(&b.1));
+        report
+    }
+
+    /// Close out the current day and return its report
+    pub fn roll_day(&mut self) -> ObserverReport {
+        let report = self.build_report();
+        self.finalized.clear();
+        self.current_day_start = Utc::now();
+        report
+    }
+
+    fn append_record(&self, launch: &ObservedLaunch) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.settings.output_dir)?;
+        let path = format!(
+            "{}/observer_{}.jsonl",
+            self.settings.output_dir,
+            launch.first_seen.format("%Y-%m-%d")
+        );
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(launch)?)?;
+        Ok(())
+    }
+}
+
+static OBSERVER: OnceLock<Arc<Mutex<Observer>>> = OnceLock::new();
+
+/// Running observer, if observer mode started one
+pub fn observer() -> Option<Arc<Mutex<Observer>>> {
+    OBSERVER.get().cloned()
+}
+
+/// Whether decisions go to the observer instead of the executor
+pub fn observing() -> bool {
+    OBSERVER.get().is_some()
+}
+
+/// Record a decision the strategies or launch filters made, when observing
+pub fn observe_launch(mint: &str, price: f64, score: f64, decision: ObservedDecision) {
+    if let Some(observer) = observer() {
+        observer.lock().unwrap_or_else(|e| e.into_inner()).record_launch(mint, price, score, decision);
+    }
+}
+
+/// Feed a traded price to the observer, which ignores mints it is not following
+pub fn observe_price(mint: &str, price: f64) {
+    if let Some(observer) = observer() {
+        observer.lock().unwrap_or_else(|e| e.into_inner()).record_price(mint, price);
+    }
+}
+
+/// Start the observer with its background finalize and daily report task
+///
+/// Once started, the strategies and the launch feed hand their decisions here instead of to the executor.
+pub async fn start_observer_system(
+    logger: Logger,
+    settings: ObserverSettings,
+    telegram_service: Option<Arc<TelegramService>>,
+    telegram_chat_id: String,
+) -> Arc<Mutex<Observer>> {
+    logger.log("👀 OBSERVER MODE: scoring every launch, no transactions will be sent".yellow().bold().to_string());
+
+    let observer = Arc::new(Mutex::new(Observer::new(logger.clone(), settings)));
+    let _ = OBSERVER.set(observer.clone());
+
+    let observer_clone = observer.clone();
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+
+            let report = {
+                let mut observer = match observer_clone.lock() {
+                    Ok(observer) => observer,
+                    Err(_) => continue,
+                };
+                observer.finalize_expired();
+                if Utc::now().date_naive() > observer.current_day_start.date_naive() {
+                    Some(observer.roll_day())
+                } else {
+                    None
+                }
+            };
+
+            if let Some(report) = report {
+                logger.log(format!(
+                    "Observer report {}: {} launches, {} would-buy, {} missed winners, {} avoided losers",
+                    report.date,
+                    report.launches,
+                    report.would_buy,
+                    report.missed_winners.len(),
+                    report.avoided_losers.len()
+                ).cyan().to_string());
+
+                if let Some(telegram) = &telegram_service {
+                    if let Err(e) = telegram.send_message(&telegram_chat_id, &report.to_telegram_message(), "HTML").await {
+                        logger.error(format!("Failed to send observer report: {}", e));
+                    }
+                }
+            }
+        }
+    });
+
+    observer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(output_dir: &std::path::Path) -> ObserverSettings {
+        ObserverSettings {
+            enabled: true,
+            outcome_window_secs: 0,
+            win_threshold_percent: 100.0,
+            loss_threshold_percent: 50.0,
+            output_dir: output_dir.to_string_lossy().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_classification() {
+        let dir = std::env::temp_dir().join(format!("observer_test_{}", std::process::id()));
+        let logger = Logger::new("[TEST] => ".to_string());
+        let mut observer = Observer::new(logger, settings(&dir));
+
+        observer.record_launch("winner_bought", 1.0, 0.9, ObservedDecision::WouldBuy);
+        observer.record_launch("winner_missed", 1.0, 0.2, ObservedDecision::Skip("market cap".to_string()));
+        observer.record_launch("loser_avoided", 1.0, 0.1, ObservedDecision::Skip("volume".to_string()));
+        observer.record_launch("flat", 1.0, 0.5, ObservedDecision::WouldBuy);
+
+        observer.record_price("winner_bought", 2.5);
+        observer.record_price("winner_missed", 3.0);
+        observer.record_price("loser_avoided", 0.4);
+        observer.record_price("flat", 1.2);
+        observer.finalize_expired();
+
+        let report = observer.build_report();
+        assert_eq!(report.launches, 4);
+        assert_eq!(report.would_buy, 2);
+        assert_eq!(report.bought_winners.len(), 1);
+        assert_eq!(report.missed_winners[0].0, "winner_missed");
+        assert_eq!(report.avoided_losers[0].0, "loser_avoided");
+        assert!(report.bought_losers.is_empty());
+        assert!(observer.followed().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+use std::io::IsTerminal;
+use std::sync::Arc;
+use colored::Colorize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::core::tx::with_fee_boost;
+use crate::engine::execution_queue::cancel_queued_buys;
+use crate::common::config::Status;
+use crate::engine::trade_executor::{pause_trading, TradeExecutor};
+
+/// Panic sell settings
+#[derive(Debug, Clone)]
+pub struct PanicSettings {
+    /// Slippage of the panic sells, 10000 selling at any price
+    pub slippage_bps: u64,
+    /// Multiplier on the priority fee and tips of the panic sells
+    pub fee_multiplier: f64,
+    /// Line typed on the bot's terminal that triggers a panic sell, empty to disable
+    pub hotkey: String,
+}
+
+impl PanicSettings {
+    /// Load panic sell settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            slippage_bps: std::env::var("PANIC_SLIPPAGE_BPS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(10_000)
+                .min(10_000),
+            fee_multiplier: std::env::var("PANIC_FEE_MULTIPLIER")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(4.0)
+                .max(1.0),
+            hotkey: std::env::var("PANIC_HOTKEY")
+                .unwrap_or_else(|_| "panic".to_string())
+                .trim()
+                .to_string(),
+        }
+    }
+
+    /// Whether a line typed on the terminal is the panic hotkey
+    pub fn is_hotkey(&self, line: &str) -> bool {
+        !self.hotkey.is_empty() && line.trim().eq_ignore_ascii_case(&self.hotkey)
+    }
+}
+
+/// Outcome of the panic sell of one token
+#[derive(Debug, Clone, Serialize)]
+pub struct PanicSell {
+    pub mint: String,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
+
+/// What a panic sell did
+#[derive(Debug, Clone, Serialize)]
+pub struct PanicReport {
+    /// Queued buys dropped before they were sent
+    pub cancelled_buys: usize,
+    pub route: &'static str,
+    pub sells: Vec<PanicSell>,
+    /// Wallet tokens the bot holds no position in, left unsold
+    pub untracked: Vec<String>,
+}
+
+impl PanicReport {
+    pub fn failed(&self) -> usize {
+        self.sells.iter().filter(|sell| sell.error.is_some()).count()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Trading paused, {} queued buys cancelled, {} of {} sells sent via {}",
+            self.cancelled_buys,
+            self.sells.len() - self.failed(),
+            self.sells.len(),
+            self.route
+        );
+        if !self.untracked.is_empty() {
+            summary.push_str(&format!(", {} untracked tokens left in the wallet", self.untracked.len()));
+        }
+        summary
+    }
+}
+
+/// Tokens to get out of: the positions held or already being sold
+fn tracked_mints(executor: &TradeExecutor) -> BTreeSet<String> {
+    let positions = executor.positions();
+    positions
+        .mints_with(Status::Bought)
+        .into_iter()
+        .chain(positions.mints_with(Status::Selling))
+        .collect()
+}
+
+/// Mints with a balance in `wallet` that are not among the `tracked` positions, WSOL aside
+fn untracked_mints<'a>(tracked: &BTreeSet<String>, wallet: impl IntoIterator<Item = (&'a str, u64)>) -> Vec<String> {
+    let wsol = spl_token::native_mint::ID.to_string();
+    let untracked: BTreeSet<String> = wallet
+        .into_iter()
+        .filter(|(mint, amount)| *amount > 0 && *mint != wsol && !tracked.contains(*mint))
+        .map(|(mint, _)| mint.to_string())
+        .collect();
+    untracked.into_iter().collect()
+}
+
+/// Other tokens in the primary wallet, reported but never sold, since the bot did not buy them
+async fn untracked_balances(executor: &TradeExecutor, tracked: &BTreeSet<String>, logger: &Logger) -> Vec<String> {
+    match executor.token_accounts().await {
+        Ok(accounts) => untracked_mints(tracked, accounts.iter().map(|account| (account.mint.as_str(), account.amount))),
+        Err(e) => {
+            logger.error(format!("Failed to list wallet tokens for untracked balances: {}", e));
+            Vec::new()
+        }
+    }
+}
+
+/// Pause buying, drop queued buys and sell every tracked position at once
+///
+/// The sells go out together at `PANIC_SLIPPAGE_BPS` over the fastest route, with fees raised
+/// `PANIC_FEE_MULTIPLIER` times. Other wallet tokens are only reported. Trading stays paused until resumed.
+pub async fn panic_sell(executor: &TradeExecutor, logger: &Logger) -> PanicReport {
+    let settings = PanicSettings::from_env();
+    pause_trading();
+    let cancelled_buys = cancel_queued_buys();
+    let route = executor.fastest_route();
+    logger.log(format!(
+        "Panic sell: trading paused, {} queued buys cancelled, selling every position via {}",
+        cancelled_buys, route
+    ).red().bold().to_string());
+
+    let tracked = tracked_mints(executor);
+    let untracked = untracked_balances(executor, &tracked, logger).await;
+    for mint in &untracked {
+        logger.log(format!("Not selling {}: held in the wallet without a tracked position", mint).yellow().to_string());
+    }
+    let mints: Vec<String> = tracked.into_iter().collect();
+    for mint in &mints {
+        record_decision(Decision::Sell, mint, ReasonCode::PanicSell, route);
+    }
+    let sells = futures::future::join_all(mints.iter().map(|mint| {
+        with_fee_boost(settings.fee_multiplier, executor.sell_escalated(mint, settings.slippage_bps, Some(route)))
+    }))
+    .await;
+
+    let sells = mints
+        .into_iter()
+        .zip(sells)
+        .map(|(mint, result)| match result {
+            Ok(signature) => {
+                logger.log(format!("Panic sold {}: {}", mint, signature).red().to_string());
+                PanicSell { mint, signature: Some(signature), error: None }
+            }
+            Err(e) => {
+                logger.error(format!("Panic sell of {} failed: {}", mint, e));
+                PanicSell { mint, signature: None, error: Some(e.to_string()) }
+            }
+        })
+        .collect();
+    let report = PanicReport { cancelled_buys, route, sells, untracked };
+    logger.log(format!("Panic sell done: {}", report.summary()).red().bold().to_string());
+    report
+}
+
+/// Panic sell when `PANIC_HOTKEY` is typed on the bot's terminal
+pub fn start_panic_hotkey(executor: Arc<TradeExecutor>, logger: Logger) {
+    let settings = PanicSettings::from_env();
+    if settings.hotkey.is_empty() || !std::io::stdin().is_terminal() {
+        return;
+    }
+    logger.log(format!("Type '{}' and press Enter to sell everything and pause trading", settings.hotkey));
+    tokio::spawn(async move {
+        let mut lines = tokio::io::BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if settings.is_hotkey(&line) {
+                panic_sell(&executor, &logger).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(hotkey: &str) -> PanicSettings {
+        PanicSettings { slippage_bps: 10_000, fee_multiplier: 4.0, hotkey: hotkey.to_string() }
+    }
+
+    #[test]
+    fn test_hotkey_ignores_case_and_whitespace() {
+        let settings = settings("panic");
+        assert!(settings.is_hotkey("  PANIC \n"));
+        assert!(!settings.is_hotkey("pan"));
+    }
+
+    #[test]
+    fn test_empty_hotkey_is_disabled() {
+        assert!(!settings("").is_hotkey(""));
+    }
+
+    #[test]
+    fn test_report_counts_failed_sells() {
+        let report = PanicReport {
+            cancelled_buys: 2,
+            route: "jito",
+            sells: vec![
+                PanicSell { mint: "a".to_string(), signature: Some("sig".to_string()), error: None },
+                PanicSell { mint: "b".to_string(), signature: None, error: Some("no balance".to_string()) },
+            ],
+            untracked: Vec::new(),
+        };
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.summary(), "Trading paused, 2 queued buys cancelled, 1 of 2 sells sent via jito");
+    }
+
+    #[test]
+    fn test_untracked_mints_skip_wsol_and_empty_balances() {
+        let tracked: BTreeSet<String> = ["bought".to_string(), "selling".to_string()].into_iter().collect();
+        let wsol = spl_token::native_mint::ID.to_string();
+        let wallet = vec![("bought", 10), ("airdrop", 5), ("dust", 0), (wsol.as_str(), 1_000), ("gift", 1)];
+        assert_eq!(untracked_mints(&tracked, wallet), vec!["airdrop".to_string(), "gift".to_string()]);
+    }
+
+    #[test]
+    fn test_report_lists_untracked_tokens() {
+        let report = PanicReport {
+            cancelled_buys: 0,
+            route: "rpc",
+            sells: vec![PanicSell { mint: "bought".to_string(), signature: Some("sig".to_string()), error: None }],
+            untracked: vec!["airdrop".to_string(), "gift".to_string()],
+        };
+        assert_eq!(
+            report.summary(),
+            "Trading paused, 0 queued buys cancelled, 1 of 1 sells sent via rpc, 2 untracked tokens left in the wallet"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+/// Engine-level limits that keep the bot from over-committing the wallet
+#[derive(Debug, Clone)]
+pub struct PositionLimits {
+    /// Maximum number of positions held or being bought at once (0 = unlimited)
+    pub max_open_positions: usize,
+    /// Maximum SOL put into a single mint (0 = unlimited)
+    pub max_position_sol: f64,
+    /// SOL that always stays in the wallet for fees and sells
+    pub min_wallet_reserve_sol: f64,
+    /// Shrink buys that break a size limit instead of skipping them
+    pub scale_down: bool,
+}
+
+/// Result of checking a buy against the limits
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitDecision {
+    /// Buy the requested amount
+    Allow(f64),
+    /// Buy a smaller amount
+    ScaleDown { amount_sol: f64, reason: String },
+    /// Do not buy
+    Skip(String),
+}
+
+impl PositionLimits {
+    /// Load position limits from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            max_open_positions: std::env::var("MAX_OPEN_POSITIONS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(5),
+            max_position_sol: std::env::var("MAX_POSITION_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0),
+            min_wallet_reserve_sol: std::env::var("MIN_WALLET_RESERVE_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.05),
+            scale_down: std::env::var("SCALE_DOWN_OVERSIZED_BUYS")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+
+    /// Check a buy of `amount_sol` given the current open positions and wallet balance
+    pub fn check(&self, amount_sol: f64, open_positions: usize, wallet_balance_sol: f64) -> LimitDecision {
+        if self.max_open_positions > 0 && open_positions >= self.max_open_positions {
+            return LimitDecision::Skip(format!(
+                "{} open positions, limit is {}",
+                open_positions, self.max_open_positions
+            ));
+        }
+
+        let mut allowed = amount_sol;
+        let mut reasons = Vec::new();
+
+        if self.max_position_sol > 0.0 && allowed > self.max_position_sol {
+            allowed = self.max_position_sol;
+            reasons.push(format!("MAX_POSITION_SOL is {} SOL", self.max_position_sol));
+        }
+
+        let spendable = (wallet_balance_sol - self.min_wallet_reserve_sol).max(0.0);
+        if allowed > spendable {
+            allowed = spendable;
+            reasons.push(format!(
+                "wallet has {:.4} SOL and must keep {} SOL in reserve",
+                wallet_balance_sol, self.min_wallet_reserve_sol
+            ));
+        }
+
+        if reasons.is_empty() {
+            return LimitDecision::Allow(amount_sol);
+        }
+        let reason = reasons.join(", ");
+        if !self.scale_down || allowed <= 0.0 {
+            return LimitDecision::Skip(reason);
+        }
+        LimitDecision::ScaleDown { amount_sol: allowed, reason }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(scale_down: bool) -> PositionLimits {
+        PositionLimits {
+            max_open_positions: 2,
+            max_position_sol: 0.5,
+            min_wallet_reserve_sol: 0.1,
+            scale_down,
+        }
+    }
+
+    #[test]
+    fn test_position_count_and_size_limits() {
+        assert_eq!(limits(true).check(0.2, 1, 10.0), LimitDecision::Allow(0.2));
+        assert!(matches!(limits(true).check(0.2, 2, 10.0), LimitDecision::Skip(_)));
+        assert!(matches!(
+            limits(true).check(1.0, 0, 10.0),
+            LimitDecision::ScaleDown { amount_sol, .. } if amount_sol == 0.5
+        ));
+        assert!(matches!(limits(false).check(1.0, 0, 10.0), LimitDecision::Skip(_)));
+    }
+
+    #[test]
+    fn test_wallet_reserve() {
+        assert!(matches!(
+            limits(true).check(0.4, 0, 0.3),
+            LimitDecision::ScaleDown { amount_sol, .. } if (amount_sol - 0.2).abs() < 1e-9
+        ));
+        assert!(matches!(limits(true).check(0.4, 0, 0.1), LimitDecision::Skip(_)));
+    }
+}
@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use colored::Colorize;
+use tokio::time::{self, Instant};
+
+use crate::common::config::{LiquidityPool, Status};
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::curve_feed::curve_updated;
+use crate::engine::indicators::indicator_engine;
+use crate::engine::position_marks::position_marks;
+use crate::engine::stop_loss::{is_escalating, stop_loss_sell};
+use crate::engine::token_rules::{position_rule, retain_position_rules};
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::alerts::{alert, Alert};
+use crate::services::webhook::{webhook, WebhookEvent};
+
+/// One step of the take-profit ladder
+#[derive(Debug, Clone, PartialEq)]
+pub struct LadderRung {
+    /// Gain over entry that triggers this rung, in percent
+    pub trigger_percent: f64,
+    /// Share of the original position to sell, in percent
+    pub sell_percent: f64,
+}
+
+/// Parse a ladder like `50:25,100:25` into rungs sorted by trigger
+pub fn parse_ladder(value: &str) -> Result<Vec<LadderRung>, String> {
+    let mut rungs = Vec::new();
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (trigger, sell) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid ladder rung '{}', expected trigger:sell", pair))?;
+        let trigger_percent = trigger.trim().parse::<f64>().map_err(|_| format!("Invalid trigger in '{}'", pair))?;
+        let sell_percent = sell.trim().parse::<f64>().map_err(|_| format!("Invalid sell percent in '{}'", pair))?;
+        if sell_percent <= 0.0 || sell_percent > 100.0 {
+            return Err(format!("Sell percent in '{}' must be in (0, 100]", pair));
+        }
+        rungs.push(LadderRung { trigger_percent, sell_percent });
+    }
+    rungs.sort_by(|a, b| a.trigger_percent.total_cmp(&b.trigger_percent));
+
+    let total: f64 = rungs.iter().map(|r| r.sell_percent).sum();
+    if total > 100.0 {
+        return Err(format!("Ladder sells {}% of the position, more than 100%", total));
+    }
+    Ok(rungs)
+}
+
+/// Parse profit locks like `100:50,200:120` into `(milestone, locked)` pairs
+pub fn parse_profit_locks(value: &str) -> Result<Vec<(f64, f64)>, String> {
+    let mut locks = Vec::new();
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (milestone, locked) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid profit lock '{}', expected milestone:locked", pair))?;
+        let milestone = milestone.trim().parse::<f64>().map_err(|_| format!("Invalid milestone in '{}'", pair))?;
+        let locked = locked.trim().parse::<f64>().map_err(|_| format!("Invalid locked gain in '{}'", pair))?;
+        if locked >= milestone {
+            return Err(format!("Locked gain in '{}' must be below its milestone", pair));
+        }
+        locks.push((milestone, locked));
+    }
+    locks.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(locks)
+}
+
+/// Position manager settings
+#[derive(Debug, Clone)]
+pub struct PositionManagerSettings {
+    pub enabled: bool,
+    /// Take-profit ladder, sorted by trigger
+    pub ladder: Vec<LadderRung>,
+    /// Trailing stop for whatever is left after the last rung, in percent from the peak (0 = off)
+    pub trailing_stop_percent: f64,
+    /// Sell everything once the position is down this much, in percent (0 = off)
+    pub stop_loss_percent: f64,
+    /// Move the stop to entry once the position is up this much, in percent (0 = off)
+    pub breakeven_trigger_percent: f64,
+    /// `(milestone_percent, locked_percent)` pairs: once the peak gain reaches the
+    /// milestone, the stop is raised to lock in the given gain
+    pub profit_locks: Vec<(f64, f64)>,
+    /// Sell a position held this long without another exit firing (0 = off)
+    pub max_hold_secs: u64,
+    /// Only apply the hold-time exit when the gain is at least this, in percent
+    pub max_hold_min_pnl_percent: Option<f64>,
+    /// Only apply the hold-time exit when the gain is at most this, in percent
+    pub max_hold_max_pnl_percent: Option<f64>,
+    /// How often positions are re-priced
+    pub check_interval_ms: u64,
+}
+
+impl PositionManagerSettings {
+    /// Load position manager settings from environment variables
+    pub fn from_env() -> Self {
+        let ladder = std::env::var("TP_LADDER").unwrap_or_else(|_| "50:25,100:25".to_string());
+        Self {
+            enabled: std::env::var("POSITION_MANAGER_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            ladder: parse_ladder(&ladder).unwrap_or_else(|e| {
+                eprintln!("Ignoring TP_LADDER: {}", e);
+                Vec::new()
+            }),
+            trailing_stop_percent: std::env::var("TP_TRAILING_STOP_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(20.0),
+            stop_loss_percent: std::env::var("STOP_LOSS_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(30.0),
+            breakeven_trigger_percent: std::env::var("BREAKEVEN_TRIGGER_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(30.0),
+            profit_locks: parse_profit_locks(&std::env::var("PROFIT_LOCK_LEVELS").unwrap_or_default())
+                .unwrap_or_else(|e| {
+                    eprintln!("Ignoring PROFIT_LOCK_LEVELS: {}", e);
+                    Vec::new()
+                }),
+            max_hold_secs: std::env::var("MAX_HOLD_TIME_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+            max_hold_min_pnl_percent: std::env::var("MAX_HOLD_MIN_PNL_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok()),
+            max_hold_max_pnl_percent: std::env::var("MAX_HOLD_MAX_PNL_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok()),
+            check_interval_ms: std::env::var("POSITION_CHECK_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1000),
+        }
+    }
+}
+
+/// How much of a position to sell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SellAmount {
+    /// Share of the tokens still held, in percent
+    Percent(f64),
+    /// Token base units, capped at the balance
+    Tokens(u64),
+    All,
+}
+
+impl SellAmount {
+    /// Parse `all`, `25` or `25%` (percent of the balance) or `1000000 tokens`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim().to_lowercase();
+        if value == "all" || value == "max" {
+            return Ok(SellAmount::All);
+        }
+        if let Some(tokens) = value.strip_suffix("tokens") {
+            return match tokens.trim().parse::<u64>() {
+                Ok(0) | Err(_) => Err(format!("Invalid token amount '{}'", tokens.trim())),
+                Ok(tokens) => Ok(SellAmount::Tokens(tokens)),
+            };
+        }
+        let percent = value
+            .trim_end_matches('%')
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid sell amount '{}', expected a percent, a token amount or all", value))?;
+        Self::percent(percent)
+    }
+
+    /// `Percent`, or `All` for 100%
+    pub fn percent(percent: f64) -> Result<Self, String> {
+        if percent <= 0.0 || percent > 100.0 {
+            return Err(format!("Sell percent must be in (0, 100], got {}", percent));
+        }
+        Ok(if percent >= 100.0 { SellAmount::All } else { SellAmount::Percent(percent) })
+    }
+}
+
+impl std::fmt::Display for SellAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SellAmount::Percent(percent) => write!(f, "{:.1}%", percent),
+            SellAmount::Tokens(tokens) => write!(f, "{} tokens", tokens),
+            SellAmount::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Sell `amount` of a held token; every exit path goes through here
+pub async fn sell(executor: &TradeExecutor, mint: &str, amount: SellAmount) -> anyhow::Result<String> {
+    match amount {
+        SellAmount::Percent(percent) => executor.sell(mint, percent).await,
+        SellAmount::Tokens(tokens) => executor.sell_token_amount(mint, tokens, None).await,
+        SellAmount::All => executor.sell(mint, 100.0).await,
+    }
+}
+
+/// A sell the manager wants to make
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitAction {
+    /// Share of the *remaining* position to sell, in percent
+    pub sell_percent: f64,
+    pub reason: String,
+    /// Rungs consumed by this sell
+    pub rungs: usize,
+}
+
+impl ExitAction {
+    /// Whether the fixed stop loss fired, as opposed to a trailing stop or profit lock
+    pub fn is_stop_loss(&self) -> bool {
+        self.reason.starts_with("stop loss")
+    }
+
+    /// Reason code of the sell, from the rule that fired
+    pub fn code(&self) -> ReasonCode {
+        match self.reason.as_str() {
+            reason if reason.starts_with("stop loss") => ReasonCode::StopLossTriggered,
+            reason if reason.starts_with("break-even stop") => ReasonCode::BreakEvenStop,
+            reason if reason.starts_with("profit lock") => ReasonCode::ProfitLockHit,
+            reason if reason.starts_with("take profit") => ReasonCode::TakeProfitHit,
+            reason if reason.starts_with("held ") => ReasonCode::MaxHoldTime,
+            reason if reason.starts_with("trailing stop") => ReasonCode::TrailingStop,
+            _ => ReasonCode::StrategyExit,
+        }
+    }
+
+    pub fn amount(&self) -> SellAmount {
+        if self.sell_percent >= 100.0 { SellAmount::All } else { SellAmount::Percent(self.sell_percent) }
+    }
+}
+
+/// Exit state for one held token
+#[derive(Debug, Clone)]
+pub struct PositionState {
+    pub mint: String,
+    pub entry_price: f64,
+    pub peak_price: f64,
+    pub opened_at: Instant,
+    /// Share of the original position already sold, in percent
+    pub sold_percent: f64,
+    /// Index of the next ladder rung
+    pub next_rung: usize,
+    /// Effective stop as a gain over entry in percent, once break-even or a profit lock kicked in
+    pub stop_percent: Option<f64>,
+}
+
+impl PositionState {
+    pub fn new(mint: &str, entry_price: f64, opened_at: Instant) -> Self {
+        Self {
+            mint: mint.to_string(),
+            entry_price,
+            peak_price: entry_price,
+            opened_at,
+            sold_percent: 0.0,
+            next_rung: 0,
+            stop_percent: None,
+        }
+    }
+
+    /// Gain over entry at `price`, in percent
+    pub fn pnl_percent(&self, price: f64) -> f64 {
+        if self.entry_price <= 0.0 {
+            return 0.0;
+        }
+        (price / self.entry_price - 1.0) * 100.0
+    }
+
+    fn remaining_percent(&self) -> f64 {
+        (100.0 - self.sold_percent).max(0.0)
+    }
+
+    /// Decide whether to sell at `price`
+    pub fn evaluate(&mut self, price: f64, settings: &PositionManagerSettings) -> Option<ExitAction> {
+        self.peak_price = self.peak_price.max(price);
+        let pnl = self.pnl_percent(price);
+        let remaining = self.remaining_percent();
+        if remaining <= 0.0 {
+            return None;
+        }
+
+        if settings.stop_loss_percent > 0.0 && pnl <= -settings.stop_loss_percent {
+            return Some(ExitAction {
+                sell_percent: 100.0,
+                reason: format!("stop loss at {:.1}%", pnl),
+                rungs: 0,
+            });
+        }
+
+        self.ratchet_stop(settings);
+        if let Some(stop) = self.stop_percent {
+            if pnl <= stop {
+                let kind = if stop <= 0.0 { "break-even stop" } else { "profit lock" };
+                return Some(ExitAction {
+                    sell_percent: 100.0,
+                    reason: format!("{} at {:+.0}% hit ({:.1}%)", kind, stop, pnl),
+                    rungs: 0,
+                });
+            }
+        }
+
+        // sell every rung crossed since the last check in one transaction
+        let crossed: Vec<&LadderRung> = settings.ladder[self.next_rung.min(settings.ladder.len())..]
+            .iter()
+            .take_while(|rung| pnl >= rung.trigger_percent)
+            .collect();
+        if !crossed.is_empty() {
+            let of_original: f64 = crossed.iter().map(|rung| rung.sell_percent).sum();
+            return Some(ExitAction {
+                sell_percent: (of_original / remaining * 100.0).min(100.0),
+                reason: format!("take profit +{:.0}% at {:.1}%", crossed.last().unwrap().trigger_percent, pnl),
+                rungs: crossed.len(),
+            });
+        }
+
+        if settings.max_hold_secs > 0 && self.opened_at.elapsed() >= Duration::from_secs(settings.max_hold_secs) {
+            let above_min = settings.max_hold_min_pnl_percent.map(|min| pnl >= min).unwrap_or(true);
+            let below_max = settings.max_hold_max_pnl_percent.map(|max| pnl <= max).unwrap_or(true);
+            if above_min && below_max {
+                return Some(ExitAction {
+                    sell_percent: 100.0,
+                    reason: format!(
+                        "held {}s (max {}s) at {:.1}%",
+                        self.opened_at.elapsed().as_secs(), settings.max_hold_secs, pnl
+                    ),
+                    rungs: 0,
+                });
+            }
+        }
+
+        let ladder_done = self.next_rung >= settings.ladder.len();
+        if ladder_done && settings.trailing_stop_percent > 0.0 && self.peak_price > self.entry_price {
+            let drop = (1.0 - price / self.peak_price) * 100.0;
+            if drop >= settings.trailing_stop_percent {
+                return Some(ExitAction {
+                    sell_percent: 100.0,
+                    reason: format!("trailing stop {:.1}% below peak", drop),
+                    rungs: 0,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Raise the stop from the peak gain; it never moves down
+    fn ratchet_stop(&mut self, settings: &PositionManagerSettings) {
+        let peak_pnl = self.pnl_percent(self.peak_price);
+        let mut stop = self.stop_percent;
+        if settings.breakeven_trigger_percent > 0.0 && peak_pnl >= settings.breakeven_trigger_percent {
+            stop = Some(stop.unwrap_or(0.0).max(0.0));
+        }
+        for (milestone, locked) in &settings.profit_locks {
+            if peak_pnl >= *milestone {
+                stop = Some(stop.unwrap_or(*locked).max(*locked));
+            }
+        }
+        self.stop_percent = stop;
+    }
+
+    /// Record that `action` was submitted
+    pub fn apply(&mut self, action: &ExitAction) {
+        let remaining = self.remaining_percent();
+        self.sold_percent += remaining * action.sell_percent / 100.0;
+        self.next_rung += action.rungs;
+    }
+}
+
+/// Watches held tokens and sells them according to the exit rules
+pub struct PositionManager {
+    settings: PositionManagerSettings,
+    positions: HashMap<String, PositionState>,
+}
+
+impl PositionManager {
+    /// Create a new position manager
+    pub fn new(settings: PositionManagerSettings) -> Self {
+        Self {
+            settings,
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &PositionManagerSettings {
+        &self.settings
+    }
+
+    /// Exit state for every managed position
+    pub fn positions(&self) -> Vec<PositionState> {
+        self.positions.values().cloned().collect()
+    }
+
+    /// Start tracking new positions and forget closed ones
+    ///
+    /// `held` is every pool the executor knows; the pool timestamp is used as the open time.
+    pub fn sync(&mut self, held: &[LiquidityPool]) {
+        for pool in held {
+            if pool.status != Status::Bought || pool.buy_price <= 0.0 {
+                continue;
+            }
+            match self.positions.get_mut(&pool.mint) {
+                // a full exit was submitted but the pool is back to `Bought`, so it never landed
+                Some(position) if position.remaining_percent() <= 0.0 => {
+                    position.sold_percent = self.settings.ladder[..position.next_rung.min(self.settings.ladder.len())]
+                        .iter()
+                        .map(|rung| rung.sell_percent)
+                        .sum();
+                }
+                Some(_) => {}
+                None => {
+                    let opened_at = pool.timestamp.unwrap_or_else(Instant::now);
+                    self.positions.insert(pool.mint.clone(), PositionState::new(&pool.mint, pool.buy_price, opened_at));
+                }
+            }
+        }
+        self.positions.retain(|mint, _| {
+            held.iter().any(|pool| {
+                pool.mint == *mint && (pool.status == Status::Bought || pool.status == Status::Selling)
+            })
+        });
+        let open: Vec<String> = held
+            .iter()
+            .filter(|pool| matches!(pool.status, Status::Buying | Status::Bought | Status::Selling))
+            .map(|pool| pool.mint.clone())
+            .collect();
+        retain_position_rules(&open);
+        let mut marks = position_marks();
+        marks.retain(&open);
+        for pool in held.iter().filter(|pool| pool.status == Status::Bought && pool.buy_price > 0.0) {
+            marks.restore(&pool.mint, pool.buy_price, Instant::now());
+        }
+    }
+
+    /// Decide whether to sell `mint` at `price`
+    ///
+    /// A token rule the position was bought under replaces the stop loss and hold-time settings.
+    pub fn evaluate(&mut self, mint: &str, price: f64) -> Option<ExitAction> {
+        let position = self.positions.get_mut(mint)?;
+        match position_rule(mint) {
+            Some(rule) => position.evaluate(price, &rule.exit_settings(&self.settings, position.opened_at.elapsed())),
+            None => position.evaluate(price, &self.settings),
+        }
+    }
+
+    /// Record a submitted sell
+    pub fn apply(&mut self, mint: &str, action: &ExitAction) {
+        if let Some(position) = self.positions.get_mut(mint) {
+            position.apply(action);
+        }
+    }
+}
+
+/// Start the position manager
+///
+/// Every check interval, and as soon as the curve feed streams a new curve state,
+/// each `Bought` position is re-priced from its bonding curve and sold through the
+/// executor when an exit rule fires. With `INDICATOR_EXITS_ENABLED`,
+/// the RSI/EMA rules from the indicator engine are checked after the percentage rules.
+pub async fn start_position_manager_system(
+    executor: Arc<TradeExecutor>,
+    logger: Logger,
+) -> Arc<Mutex<PositionManager>> {
+    let settings = PositionManagerSettings::from_env();
+    let interval_ms = settings.check_interval_ms.max(100);
+    let manager = Arc::new(Mutex::new(PositionManager::new(settings)));
+
+    let manager_clone = manager.clone();
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_millis(interval_ms));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = curve_updated() => {}
+            }
+
+            let held: Vec<LiquidityPool> = executor.positions().snapshot();
+            let to_check: Vec<String> = match manager_clone.lock() {
+                Ok(mut manager) => {
+                    manager.sync(&held);
+                    held.iter()
+                        .filter(|pool| pool.status == Status::Bought && !is_escalating(&pool.mint))
+                        .map(|pool| pool.mint.clone())
+                        .collect()
+                }
+                Err(_) => continue,
+            };
+
+            for mint in to_check {
+                let price = match executor.current_price(&mint).await {
+                    Ok(price) => {
+                        position_marks().mark(&mint, price, Instant::now());
+                        price
+                    }
+                    Err(e) => {
+                        logger.debug(format!("Failed to price {}: {}", mint, e));
+                        continue;
+                    }
+                };
+                let action = match manager_clone.lock() {
+                    Ok(mut manager) => manager.evaluate(&mint, price),
+                    Err(_) => None,
+                };
+                // indicator exits only apply when none of the percentage rules fired
+                let action = action.or_else(|| {
+                    let engine = indicator_engine();
+                    if !engine.settings().enabled {
+                        return None;
+                    }
+                    engine.exit_signal(&mint, price).map(|reason| ExitAction {
+                        sell_percent: 100.0,
+                        reason,
+                        rungs: 0,
+                    })
+                });
+                let Some(action) = action else {
+                    continue;
+                };
+
+                logger.log(format!(
+                    "[EXIT] => {} selling {} of remaining: {}",
+                    mint, action.amount(), action.reason
+                ).yellow().bold().to_string());
+                record_decision(Decision::Sell, &mint, action.code(), &action.reason);
+                if action.is_stop_loss() {
+                    webhook().notify(WebhookEvent::stop_loss(&mint, &action.reason));
+                    alert(Alert::stop_loss(&mint, &action.reason));
+                    // escalation waits for each sell to land, so it runs beside the other positions
+                    if let Ok(mut manager) = manager_clone.lock() {
+                        manager.apply(&mint, &action);
+                    }
+                    let (executor, logger) = (executor.clone(), logger.clone());
+                    tokio::spawn(async move {
+                        if let Err(e) = stop_loss_sell(&executor, &mint, &logger).await {
+                            logger.error(format!("Stop-loss sell failed for {}: {}", mint, e));
+                        }
+                    });
+                    continue;
+                }
+                match sell(&executor, &mint, action.amount()).await {
+                    Ok(_) => {
+                        if let Ok(mut manager) = manager_clone.lock() {
+                            manager.apply(&mint, &action);
+                        }
+                    }
+                    Err(e) => logger.error(format!("Exit sell failed for {}: {}", mint, e)),
+                }
+            }
+        }
+    });
+
+    manager
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(ladder: &str) -> PositionManagerSettings {
+        PositionManagerSettings {
+            enabled: true,
+            ladder: parse_ladder(ladder).unwrap(),
+            trailing_stop_percent: 20.0,
+            stop_loss_percent: 30.0,
+            breakeven_trigger_percent: 0.0,
+            profit_locks: Vec::new(),
+            max_hold_secs: 0,
+            max_hold_min_pnl_percent: None,
+            max_hold_max_pnl_percent: None,
+            check_interval_ms: 1000,
+        }
+    }
+
+    #[test]
+    fn test_parse_ladder() {
+        let ladder = parse_ladder("100:25, 50:25").unwrap();
+        assert_eq!(ladder[0], LadderRung { trigger_percent: 50.0, sell_percent: 25.0 });
+        assert_eq!(ladder[1].trigger_percent, 100.0);
+        assert!(parse_ladder("50:60,100:60").is_err());
+        assert!(parse_ladder("50").is_err());
+        assert!(parse_ladder("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_sell_amount() {
+        assert_eq!(SellAmount::parse("25%"), Ok(SellAmount::Percent(25.0)));
+        assert_eq!(SellAmount::parse("25"), Ok(SellAmount::Percent(25.0)));
+        assert_eq!(SellAmount::parse("100"), Ok(SellAmount::All));
+        assert_eq!(SellAmount::parse(" ALL "), Ok(SellAmount::All));
+        assert_eq!(SellAmount::parse("1500000 tokens"), Ok(SellAmount::Tokens(1_500_000)));
+        assert!(SellAmount::parse("0 tokens").is_err());
+        assert!(SellAmount::parse("150%").is_err());
+        assert!(SellAmount::parse("half").is_err());
+    }
+
+    #[test]
+    fn test_ladder_then_trailing_stop() {
+        let settings = settings("50:25,100:25");
+        let mut position = PositionState::new("mint", 1.0, Instant::now());
+
+        assert!(position.evaluate(1.2, &settings).is_none());
+
+        let first = position.evaluate(1.5, &settings).unwrap();
+        assert_eq!(first.sell_percent, 25.0);
+        position.apply(&first);
+
+        // 25% of the original is a third of the remaining 75%
+        let second = position.evaluate(2.1, &settings).unwrap();
+        assert!((second.sell_percent - 100.0 / 3.0).abs() < 1e-9);
+        position.apply(&second);
+        assert!((position.sold_percent - 50.0).abs() < 1e-9);
+
+        assert!(position.evaluate(3.0, &settings).is_none());
+        let trailing = position.evaluate(2.3, &settings).unwrap();
+        assert_eq!(trailing.sell_percent, 100.0);
+        assert!(trailing.reason.contains("trailing"));
+    }
+
+    #[test]
+    fn test_gap_through_several_rungs_and_stop_loss() {
+        let settings = settings("50:25,100:25");
+        let mut position = PositionState::new("mint", 1.0, Instant::now());
+        let action = position.evaluate(2.5, &settings).unwrap();
+        assert_eq!(action.rungs, 2);
+        assert_eq!(action.sell_percent, 50.0);
+
+        let mut losing = PositionState::new("mint", 1.0, Instant::now());
+        assert_eq!(losing.evaluate(0.65, &settings).unwrap().sell_percent, 100.0);
+    }
+
+    #[test]
+    fn test_breakeven_and_profit_lock_ratchet() {
+        let mut settings = settings("");
+        settings.trailing_stop_percent = 0.0;
+        settings.breakeven_trigger_percent = 30.0;
+        settings.profit_locks = parse_profit_locks("100:50,200:120").unwrap();
+
+        let mut position = PositionState::new("mint", 1.0, Instant::now());
+        assert!(position.evaluate(1.2, &settings).is_none());
+        assert_eq!(position.stop_percent, None);
+
+        assert!(position.evaluate(1.35, &settings).is_none());
+        assert_eq!(position.stop_percent, Some(0.0));
+
+        assert!(position.evaluate(2.1, &settings).is_none());
+        assert_eq!(position.stop_percent, Some(50.0));
+
+        // the stop does not move back down when the price falls
+        assert!(position.evaluate(1.6, &settings).is_none());
+        let exit = position.evaluate(1.45, &settings).unwrap();
+        assert_eq!(exit.sell_percent, 100.0);
+        assert!(exit.reason.contains("profit lock"));
+
+        let mut breakeven = PositionState::new("mint", 1.0, Instant::now());
+        breakeven.evaluate(1.4, &settings);
+        assert!(breakeven.evaluate(0.99, &settings).unwrap().reason.contains("break-even"));
+        assert!(parse_profit_locks("100:150").is_err());
+    }
+
+    #[test]
+    fn test_max_hold_time_with_pnl_threshold() {
+        let mut settings = settings("");
+        settings.max_hold_secs = 60;
+        let opened = Instant::now() - Duration::from_secs(120);
+
+        let mut stale = PositionState::new("mint", 1.0, opened);
+        assert!(stale.evaluate(1.05, &settings).unwrap().reason.contains("held"));
+
+        let fresh = Instant::now();
+        assert!(PositionState::new("mint", 1.0, fresh).evaluate(1.05, &settings).is_none());
+
+        // only exit stale positions that are not in profit
+        settings.max_hold_max_pnl_percent = Some(0.0);
+        assert!(PositionState::new("mint", 1.0, opened).evaluate(1.05, &settings).is_none());
+        assert!(PositionState::new("mint", 1.0, opened).evaluate(0.95, &settings).is_some());
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use tokio::time::Instant;
+
+use crate::engine::trade_executor::PUMP_TOKEN_DECIMALS;
+
+/// An open position marked to the latest observed price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionMark {
+    /// Average price paid per whole token in SOL
+    pub entry_price: f64,
+    pub last_price: f64,
+    /// Highest price seen since entry, the entry price included
+    pub peak_price: f64,
+    /// Largest drop from a peak since entry, in percent
+    pub max_drawdown_percent: f64,
+    /// Tokens still held, in base units
+    pub tokens: u64,
+    /// SOL paid for the tokens still held
+    pub cost_sol: f64,
+    pub updated_at: Instant,
+}
+
+impl PositionMark {
+    /// SOL the held tokens are worth at the last price
+    pub fn value_sol(&self) -> f64 {
+        self.tokens as f64 / 10f64.powi(PUMP_TOKEN_DECIMALS as i32) * self.last_price
+    }
+
+    /// Unrealized PnL in SOL, `None` when the position size is unknown
+    pub fn unrealized_pnl_sol(&self) -> Option<f64> {
+        (self.tokens > 0).then(|| self.value_sol() - self.cost_sol)
+    }
+
+    pub fn unrealized_pnl_percent(&self) -> f64 {
+        if self.entry_price <= 0.0 {
+            return 0.0;
+        }
+        (self.last_price / self.entry_price - 1.0) * 100.0
+    }
+
+    fn mark(&mut self, price: f64, now: Instant) {
+        self.last_price = price;
+        self.peak_price = self.peak_price.max(price);
+        if self.peak_price > 0.0 {
+            let drawdown = (1.0 - price / self.peak_price) * 100.0;
+            self.max_drawdown_percent = self.max_drawdown_percent.max(drawdown);
+        }
+        self.updated_at = now;
+    }
+}
+
+/// Unrealized PnL and drawdown of every open position
+#[derive(Debug, Default)]
+pub struct PositionMarks {
+    marks: HashMap<String, PositionMark>,
+}
+
+impl PositionMarks {
+    /// A confirmed buy of `tokens` for `sol_amount`, averaged into the position
+    pub fn bought(&mut self, mint: &str, sol_amount: f64, tokens: u64, price: f64, now: Instant) {
+        let mark = self.marks.entry(mint.to_string()).or_insert(PositionMark {
+            entry_price: price,
+            last_price: price,
+            peak_price: price,
+            max_drawdown_percent: 0.0,
+            tokens: 0,
+            cost_sol: 0.0,
+            updated_at: now,
+        });
+        mark.tokens += tokens;
+        mark.cost_sol += sol_amount;
+        if mark.tokens > 0 {
+            mark.entry_price = mark.cost_sol / (mark.tokens as f64 / 10f64.powi(PUMP_TOKEN_DECIMALS as i32));
+        }
+    }
+
+    /// A confirmed sell of `tokens`; the cost of the tokens sold leaves the position with them
+    pub fn sold(&mut self, mint: &str, tokens: u64, full_exit: bool) {
+        if full_exit {
+            self.marks.remove(mint);
+            return;
+        }
+        if let Some(mark) = self.marks.get_mut(mint) {
+            let sold = tokens.min(mark.tokens);
+            if mark.tokens > 0 {
+                mark.cost_sol *= (mark.tokens - sold) as f64 / mark.tokens as f64;
+            }
+            mark.tokens -= sold;
+        }
+    }
+
+    /// Track a position whose buy was not seen, e.g. one restored after a restart
+    ///
+    /// Its size is unknown, so only the percentages are marked.
+    pub fn restore(&mut self, mint: &str, entry_price: f64, now: Instant) {
+        self.marks.entry(mint.to_string()).or_insert(PositionMark {
+            entry_price,
+            last_price: entry_price,
+            peak_price: entry_price,
+            max_drawdown_percent: 0.0,
+            tokens: 0,
+            cost_sol: 0.0,
+            updated_at: now,
+        });
+    }
+
+    /// Mark `mint` to `price`, for held mints only
+    pub fn mark(&mut self, mint: &str, price: f64, now: Instant) {
+        if price <= 0.0 {
+            return;
+        }
+        if let Some(mark) = self.marks.get_mut(mint) {
+            mark.mark(price, now);
+        }
+    }
+
+    pub fn get(&self, mint: &str) -> Option<PositionMark> {
+        self.marks.get(mint).copied()
+    }
+
+    /// Every marked position, deepest in the red first
+    pub fn snapshot(&self) -> Vec<(String, PositionMark)> {
+        let mut marks: Vec<(String, PositionMark)> = self.marks.iter().map(|(mint, mark)| (mint.clone(), *mark)).collect();
+        marks.sort_by(|a, b| a.1.unrealized_pnl_percent().total_cmp(&b.1.unrealized_pnl_percent()));
+        marks
+    }
+
+    /// Forget positions that are closed
+    pub fn retain(&mut self, held: &[String]) {
+        self.marks.retain(|mint, _| held.contains(mint));
+    }
+}
+
+static POSITION_MARKS: LazyLock<Mutex<PositionMarks>> = LazyLock::new(|| Mutex::new(PositionMarks::default()));
+
+/// Shared marks, fed by confirmed trades, the curve feed and the position manager
+pub fn position_marks() -> MutexGuard<'static, PositionMarks> {
+    POSITION_MARKS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1M whole tokens for 0.1 SOL, marked up to 1.5x and back down to 0.9x
+    fn marked(now: Instant) -> PositionMarks {
+        let mut marks = PositionMarks::default();
+        marks.bought("mint", 0.1, 1_000_000_000_000, 0.0000001, now);
+        marks.mark("mint", 0.00000015, now);
+        marks.mark("mint", 0.00000009, now);
+        marks
+    }
+
+    #[test]
+    fn test_unknown_mints_are_not_marked() {
+        let mut marks = PositionMarks::default();
+        marks.mark("other", 1.0, Instant::now());
+        assert!(marks.get("other").is_none());
+    }
+
+    #[test]
+    fn test_marks_track_pnl_and_drawdown() {
+        let marks = marked(Instant::now());
+        let mark = marks.get("mint").unwrap();
+        assert!((mark.unrealized_pnl_percent() + 10.0).abs() < 1e-9);
+        assert!((mark.unrealized_pnl_sol().unwrap() + 0.01).abs() < 1e-9);
+        assert!((mark.max_drawdown_percent - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partial_sell_takes_its_share_of_cost() {
+        let mut marks = marked(Instant::now());
+        marks.sold("mint", 500_000_000_000, false);
+        let mark = marks.get("mint").unwrap();
+        assert!((mark.cost_sol - 0.05).abs() < 1e-12);
+        assert!((mark.unrealized_pnl_sol().unwrap() + 0.005).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_full_exit_drops_the_mark() {
+        let mut marks = marked(Instant::now());
+        marks.sold("mint", 1_000_000_000_000, true);
+        assert!(marks.get("mint").is_none());
+    }
+
+    #[test]
+    fn test_restored_mark_has_no_cost() {
+        let mut marks = PositionMarks::default();
+        let now = Instant::now();
+        marks.restore("restored", 0.0000001, now);
+        marks.mark("restored", 0.0000002, now);
+        let mark = marks.get("restored").unwrap();
+        assert_eq!(mark.unrealized_pnl_sol(), None);
+        assert!((mark.unrealized_pnl_percent() - 100.0).abs() < 1e-9);
+    }
+}
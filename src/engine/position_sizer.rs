@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+/// How the base allocation is computed before confidence and volatility scaling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingMode {
+    /// Always the configured TOKEN_AMOUNT
+    Fixed,
+    /// A fixed percentage of the wallet balance
+    Fractional,
+    /// Half-Kelly fraction of the wallet balance from the expected win rate and payoff
+    Kelly,
+}
+
+impl FromStr for SizingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fixed" => Ok(SizingMode::Fixed),
+            "fractional" => Ok(SizingMode::Fractional),
+            "kelly" => Ok(SizingMode::Kelly),
+            other => Err(format!("Unknown position sizing mode: {}", other)),
+        }
+    }
+}
+
+/// Position sizing settings
+#[derive(Debug, Clone)]
+pub struct PositionSizerSettings {
+    pub mode: SizingMode,
+    /// Percentage of the wallet balance per buy in fractional mode
+    pub risk_fraction_percent: f64,
+    /// Expected share of winning trades (0.0-1.0) for Kelly mode
+    pub kelly_win_rate: f64,
+    /// Average win divided by average loss for Kelly mode
+    pub kelly_win_loss_ratio: f64,
+    /// Volatility at which no reduction is applied; buys shrink above it (0 = ignore volatility)
+    pub target_volatility: f64,
+    pub min_buy_sol: f64,
+    pub max_buy_sol: f64,
+}
+
+impl PositionSizerSettings {
+    /// Load position sizing settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            mode: std::env::var("POSITION_SIZING_MODE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(SizingMode::Fixed),
+            risk_fraction_percent: std::env::var("POSITION_RISK_FRACTION_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(2.0),
+            kelly_win_rate: std::env::var("KELLY_WIN_RATE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.4),
+            kelly_win_loss_ratio: std::env::var("KELLY_WIN_LOSS_RATIO")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(2.0),
+            target_volatility: std::env::var("POSITION_TARGET_VOLATILITY")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0),
+            min_buy_sol: std::env::var("MIN_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.01),
+            max_buy_sol: std::env::var("MAX_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+/// Sizes each buy from the wallet balance, signal confidence and recent volatility
+///
+/// The base allocation comes from the sizing mode. It is multiplied by the signal
+/// confidence (0.0-1.0) and shrunk when volatility is above the target, then
+/// clamped to `[min_buy_sol, max_buy_sol]`.
+#[derive(Debug, Clone)]
+pub struct PositionSizer {
+    settings: PositionSizerSettings,
+    /// Fixed buy amount used in `Fixed` mode
+    fixed_amount_sol: f64,
+}
+
+impl PositionSizer {
+    /// Create a new position sizer
+    pub fn new(settings: PositionSizerSettings, fixed_amount_sol: f64) -> Self {
+        Self { settings, fixed_amount_sol }
+    }
+
+    /// SOL to spend on a buy
+    ///
+    /// `confidence` and `volatility` are optional; missing values leave the size unchanged.
+    pub fn size(&self, wallet_balance_sol: f64, confidence: Option<f64>, volatility: Option<f64>) -> f64 {
+        let base = match self.settings.mode {
+            SizingMode::Fixed => return self.fixed_amount_sol,
+            SizingMode::Fractional => wallet_balance_sol * self.settings.risk_fraction_percent / 100.0,
+            SizingMode::Kelly => wallet_balance_sol * self.kelly_fraction(),
+        };
+
+        let confidence_factor = confidence.map(|c| c.clamp(0.0, 1.0)).unwrap_or(1.0);
+        let volatility_factor = match volatility {
+            Some(vol) if self.settings.target_volatility > 0.0 && vol > self.settings.target_volatility => {
+                self.settings.target_volatility / vol
+            }
+            _ => 1.0,
+        };
+
+        (base * confidence_factor * volatility_factor)
+            .clamp(self.settings.min_buy_sol, self.settings.max_buy_sol.max(self.settings.min_buy_sol))
+    }
+
+    /// Half-Kelly fraction, capped at 25% of the balance
+    fn kelly_fraction(&self) -> f64 {
+        let p = self.settings.kelly_win_rate;
+        let b = self.settings.kelly_win_loss_ratio;
+        if p <= 0.0 || b <= 0.0 {
+            return 0.0;
+        }
+        let kelly = (p * b - (1.0 - p)) / b;
+        (kelly * 0.5).clamp(0.0, 0.25)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizer(mode: SizingMode) -> PositionSizer {
+        PositionSizer::new(PositionSizerSettings {
+            mode,
+            risk_fraction_percent: 2.0,
+            kelly_win_rate: 0.5,
+            kelly_win_loss_ratio: 2.0,
+            target_volatility: 0.5,
+            min_buy_sol: 0.01,
+            max_buy_sol: 1.0,
+        }, 0.1)
+    }
+
+    #[test]
+    fn test_fixed_ignores_balance() {
+        assert_eq!(sizer(SizingMode::Fixed).size(100.0, Some(0.2), Some(2.0)), 0.1);
+    }
+
+    #[test]
+    fn test_fractional_scales_with_balance_confidence_and_volatility() {
+        let sizer = sizer(SizingMode::Fractional);
+        assert!((sizer.size(10.0, None, None) - 0.2).abs() < 1e-9);
+        assert!((sizer.size(20.0, None, None) - 0.4).abs() < 1e-9);
+        assert!((sizer.size(10.0, Some(0.5), None) - 0.1).abs() < 1e-9);
+        assert!((sizer.size(10.0, None, Some(1.0)) - 0.1).abs() < 1e-9);
+        // clamps
+        assert_eq!(sizer.size(0.1, None, None), 0.01);
+        assert_eq!(sizer.size(1000.0, None, None), 1.0);
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        // full Kelly = (0.5 * 2 - 0.5) / 2 = 0.25, half = 0.125
+        assert!((sizer(SizingMode::Kelly).size(4.0, None, None) - 0.5).abs() < 1e-9);
+    }
+}
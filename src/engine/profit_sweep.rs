@@ -0,0 +1,150 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use colored::Colorize;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::telegram::TelegramService;
+
+/// Profit sweep settings
+#[derive(Debug, Clone)]
+pub struct ProfitSweepSettings {
+    pub enabled: bool,
+    /// Address the excess is sent to; only its public key is configured
+    pub cold_wallet: Option<Pubkey>,
+    /// Sweep once the balance goes above this, in SOL
+    pub ceiling_sol: f64,
+    /// Balance left in the hot wallet after a sweep, in SOL
+    pub working_capital_sol: f64,
+    /// Skip sweeps smaller than this, in SOL
+    pub min_sweep_sol: f64,
+    pub check_interval_secs: u64,
+}
+
+impl ProfitSweepSettings {
+    /// Load profit sweep settings from environment variables
+    pub fn from_env() -> Self {
+        let cold_wallet = match std::env::var("COLD_WALLET_ADDRESS") {
+            Ok(address) if !address.trim().is_empty() => match Pubkey::from_str(address.trim()) {
+                Ok(pubkey) => Some(pubkey),
+                Err(_) => {
+                    eprintln!("Ignoring COLD_WALLET_ADDRESS: invalid address {}", address);
+                    None
+                }
+            },
+            _ => None,
+        };
+        Self {
+            enabled: std::env::var("PROFIT_SWEEP_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            cold_wallet,
+            ceiling_sol: std::env::var("SWEEP_CEILING_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(10.0),
+            working_capital_sol: std::env::var("WORKING_CAPITAL_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(5.0),
+            min_sweep_sol: std::env::var("SWEEP_MIN_AMOUNT_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.1),
+            check_interval_secs: std::env::var("SWEEP_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+        }
+    }
+
+    /// SOL to sweep at `balance_sol`, if the ceiling is crossed
+    pub fn sweep_amount(&self, balance_sol: f64) -> Option<f64> {
+        if balance_sol <= self.ceiling_sol {
+            return None;
+        }
+        let amount = balance_sol - self.working_capital_sol.min(self.ceiling_sol);
+        (amount >= self.min_sweep_sol).then_some(amount)
+    }
+}
+
+/// Move profits above the ceiling to the cold wallet
+///
+/// Sweeps wait while trades are in flight so a pending buy never finds the wallet emptied.
+pub async fn start_profit_sweep_system(
+    executor: Arc<TradeExecutor>,
+    settings: ProfitSweepSettings,
+    telegram_service: Option<Arc<TelegramService>>,
+    telegram_chat_id: String,
+    logger: Logger,
+) {
+    let Some(cold_wallet) = settings.cold_wallet else {
+        logger.error("PROFIT_SWEEP_ENABLED is set but COLD_WALLET_ADDRESS is missing, sweeps disabled".to_string());
+        return;
+    };
+    logger.log(format!(
+        "Sweeping balance above {} SOL down to {} SOL into {}",
+        settings.ceiling_sol, settings.working_capital_sol, cold_wallet
+    ));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.check_interval_secs.max(30)));
+        loop {
+            interval.tick().await;
+            if executor.in_flight() > 0 {
+                continue;
+            }
+            let balance = match executor.wallet_balance().await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    logger.debug(format!("Failed to fetch wallet balance: {}", e));
+                    continue;
+                }
+            };
+            let Some(amount) = settings.sweep_amount(balance) else {
+                continue;
+            };
+
+            match executor.transfer_sol(&cold_wallet, amount).await {
+                Ok(signature) => {
+                    logger.log(format!("Swept {:.4} SOL to {}: {}", amount, cold_wallet, signature).green().bold().to_string());
+                    if let Some(telegram) = &telegram_service {
+                        let msg = format!(
+                            "<b>💸 Profit sweep</b>\n\n{:.4} SOL sent to <code>{}</code>\nBalance was {:.4} SOL\n🔗 <a href=\"https://solscan.io/tx/{}\">View Transaction</a>",
+                            amount, cold_wallet, balance, signature
+                        );
+                        if let Err(e) = telegram.send_message(&telegram_chat_id, &msg, "HTML").await {
+                            logger.error(format!("Failed to send sweep confirmation: {}", e));
+                        }
+                    }
+                }
+                Err(e) => logger.error(format!("Profit sweep of {:.4} SOL failed: {}", amount, e)),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_amount_keeps_working_capital() {
+        let settings = ProfitSweepSettings {
+            enabled: true,
+            cold_wallet: None,
+            ceiling_sol: 10.0,
+            working_capital_sol: 5.0,
+            min_sweep_sol: 0.1,
+            check_interval_secs: 300,
+        };
+        assert_eq!(settings.sweep_amount(10.0), None);
+        assert_eq!(settings.sweep_amount(12.5), Some(7.5));
+
+        // a working capital above the ceiling still sweeps down to the ceiling
+        let high = ProfitSweepSettings { working_capital_sol: 20.0, ..settings };
+        assert_eq!(high.sweep_amount(11.0), Some(1.0));
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Duplicate-buy prevention settings
+#[derive(Debug, Clone)]
+pub struct RecentBuySettings {
+    /// A mint bought or attempted is not bought again for this long, in seconds (0 = off)
+    pub window_secs: u64,
+    /// Strategies allowed to buy a mint again inside the window
+    pub allow_strategies: Vec<String>,
+}
+
+impl RecentBuySettings {
+    /// Load duplicate-buy prevention settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            window_secs: std::env::var("REBUY_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(600),
+            allow_strategies: std::env::var("REBUY_ALLOWED_STRATEGIES")
+                .unwrap_or_else(|_| "manual".to_string())
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Mints bought or attempted recently, so retries, replayed stream events and
+/// overlapping strategies cannot buy the same token twice
+pub struct RecentBuys {
+    settings: RecentBuySettings,
+    /// Mint -> when it was last claimed and by which strategy
+    claimed: HashMap<String, (Instant, String)>,
+}
+
+impl RecentBuys {
+    pub fn new(settings: RecentBuySettings) -> Self {
+        Self { settings, claimed: HashMap::new() }
+    }
+
+    /// Record a buy of `mint` by `strategy`, or explain why it is a duplicate
+    pub fn try_claim(&mut self, mint: &str, strategy: &str, now: Instant) -> Result<(), String> {
+        let window = Duration::from_secs(self.settings.window_secs);
+        if window.is_zero() {
+            return Ok(());
+        }
+        self.claimed.retain(|_, (at, _)| now.duration_since(*at) < window);
+        let allowed = self.settings.allow_strategies.iter().any(|name| name.eq_ignore_ascii_case(strategy));
+        if let Some((at, by)) = self.claimed.get(mint) {
+            if !allowed {
+                return Err(format!(
+                    "{} bought or attempted by {} {}s ago, REBUY_WINDOW_SECS is {}",
+                    mint, by, now.duration_since(*at).as_secs(), self.settings.window_secs
+                ));
+            }
+        }
+        self.claimed.insert(mint.to_string(), (now, strategy.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_rebuys_inside_window() {
+        let mut recent = RecentBuys::new(RecentBuySettings {
+            window_secs: 60,
+            allow_strategies: vec!["manual".to_string()],
+        });
+        let now = Instant::now();
+        assert!(recent.try_claim("mint", "snipe", now).is_ok());
+        assert!(recent.try_claim("mint", "copy", now + Duration::from_secs(5)).is_err());
+        assert!(recent.try_claim("other", "copy", now + Duration::from_secs(5)).is_ok());
+        assert!(recent.try_claim("mint", "manual", now + Duration::from_secs(10)).is_ok());
+        // the manual buy restarted the window
+        assert!(recent.try_claim("mint", "snipe", now + Duration::from_secs(65)).is_err());
+        assert!(recent.try_claim("mint", "snipe", now + Duration::from_secs(71)).is_ok());
+
+        let mut off = RecentBuys::new(RecentBuySettings { window_secs: 0, allow_strategies: Vec::new() });
+        assert!(off.try_claim("mint", "snipe", now).is_ok());
+        assert!(off.try_claim("mint", "snipe", now).is_ok());
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use colored::Colorize;
+use tokio::time;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::signature::Signature;
+
+use crate::common::logger::Logger;
+use crate::core::tx::wait_for_landed_slot;
+use crate::services::metrics::metrics;
+use crate::services::{bloxroute, jito, nozomi, tpu, zeroslot};
+
+/// A transaction submission path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relay {
+    Jito,
+    ZeroSlot,
+    Nozomi,
+    BloXroute,
+    /// Straight to the current and upcoming leaders over QUIC
+    Tpu,
+    Rpc,
+}
+
+impl Relay {
+    pub const ALL: [Relay; 6] = [Relay::Jito, Relay::ZeroSlot, Relay::Nozomi, Relay::BloXroute, Relay::Tpu, Relay::Rpc];
+
+    /// Whether the endpoint for this relay is configured
+    pub fn is_configured(&self) -> bool {
+        match self {
+            Relay::Jito => !jito::BLOCK_ENGINE_URL.is_empty(),
+            Relay::ZeroSlot => !zeroslot::ZERO_SLOT_URL.is_empty(),
+            Relay::Nozomi => !nozomi::NOZOMI_URL.is_empty(),
+            Relay::BloXroute => bloxroute::is_configured(),
+            Relay::Tpu => tpu::is_running(),
+            Relay::Rpc => true,
+        }
+    }
+}
+
+impl fmt::Display for Relay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Relay::Jito => "jito",
+            Relay::ZeroSlot => "zeroslot",
+            Relay::Nozomi => "nozomi",
+            Relay::BloXroute => "bloxroute",
+            Relay::Tpu => "tpu",
+            Relay::Rpc => "rpc",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for Relay {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jito" => Ok(Relay::Jito),
+            "zeroslot" => Ok(Relay::ZeroSlot),
+            "nozomi" => Ok(Relay::Nozomi),
+            "bloxroute" => Ok(Relay::BloXroute),
+            "tpu" => Ok(Relay::Tpu),
+            "rpc" => Ok(Relay::Rpc),
+            _ => Err(anyhow::anyhow!("Unknown relay: {}", s)),
+        }
+    }
+}
+
+/// One recorded submission through a relay
+#[derive(Debug, Clone)]
+pub struct RelaySubmission {
+    pub relay: Relay,
+    pub landed: bool,
+    /// Slots between submission and confirmation, when it landed
+    pub slots_to_land: Option<u64>,
+    /// Tip paid to the relay in lamports
+    pub tip_lamports: u64,
+}
+
+/// Rolling landing statistics for one relay
+#[derive(Debug, Clone, Default)]
+pub struct RelayStats {
+    pub submissions: usize,
+    pub landed: usize,
+    pub avg_slots_to_land: f64,
+    pub avg_tip_lamports: f64,
+    pub score: f64,
+}
+
+impl RelayStats {
+    pub fn land_rate(&self) -> f64 {
+        if self.submissions == 0 {
+            return 0.0;
+        }
+        self.landed as f64 / self.submissions as f64
+    }
+}
+
+/// Relay selection settings
+#[derive(Debug, Clone)]
+pub struct RelaySelectorSettings {
+    /// Route trades to the best ranked relay instead of the fixed one
+    pub auto_select: bool,
+    /// Relay used when auto-selection is off or there is no data yet
+    pub default_relay: Relay,
+    /// Number of recent submissions kept per relay
+    pub window: usize,
+    /// Submissions a relay needs before its stats are trusted
+    pub min_samples: usize,
+    /// How often relays are re-ranked (seconds)
+    pub rerank_interval_secs: u64,
+    /// Score penalty per SOL of average tip
+    pub cost_weight: f64,
+}
+
+impl RelaySelectorSettings {
+    /// Load relay selection settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            auto_select: std::env::var("RELAY_AUTO_SELECT").unwrap_or_default() == "true",
+            default_relay: std::env::var("RELAY_DEFAULT")
+                .ok()
+                .and_then(|v| v.parse::<Relay>().ok())
+                .unwrap_or(Relay::Jito),
+            window: std::env::var("RELAY_STATS_WINDOW")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(50),
+            min_samples: std::env::var("RELAY_MIN_SAMPLES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(5),
+            rerank_interval_secs: std::env::var("RELAY_RERANK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60),
+            cost_weight: std::env::var("RELAY_COST_WEIGHT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(10.0),
+        }
+    }
+}
+
+/// Records per-relay landing results and picks the relay for new trades
+///
+/// Relays are scored by land rate divided by (1 + average slots to land), minus a
+/// penalty for the average tip, so a relay that lands fast and cheap wins. Relays
+/// without enough samples yet are tried round-robin so every configured relay keeps
+/// getting data.
+pub struct RelaySelector {
+    logger: Logger,
+    settings: RelaySelectorSettings,
+    history: HashMap<Relay, VecDeque<RelaySubmission>>,
+    ranking: Vec<(Relay, RelayStats)>,
+    explore_index: usize,
+}
+
+impl RelaySelector {
+    pub fn new(logger: Logger, settings: RelaySelectorSettings) -> Self {
+        Self {
+            logger,
+            settings,
+            history: HashMap::new(),
+            ranking: Vec::new(),
+            explore_index: 0,
+        }
+    }
+
+    /// Record the result of a submission
+    pub fn record(&mut self, submission: RelaySubmission) {
+        let entries = self.history.entry(submission.relay).or_default();
+        entries.push_back(submission);
+        while entries.len() > self.settings.window {
+            entries.pop_front();
+        }
+    }
+
+    /// Current statistics for a relay
+    pub fn stats(&self, relay: Relay) -> RelayStats {
+        let entries = match self.history.get(&relay) {
+            Some(entries) if !entries.is_empty() => entries,
+            _ => return RelayStats::default(),
+        };
+
+        let landed: Vec<&RelaySubmission> = entries.iter().filter(|s| s.landed).collect();
+        let avg_slots_to_land = if landed.is_empty() {
+            0.0
+        } else {
+            landed.iter().filter_map(|s| s.slots_to_land).sum::<u64>() as f64 / landed.len() as f64
+        };
+        let avg_tip_lamports = entries.iter().map(|s| s.tip_lamports).sum::<u64>() as f64 / entries.len() as f64;
+
+        let mut stats = RelayStats {
+            submissions: entries.len(),
+            landed: landed.len(),
+            avg_slots_to_land,
+            avg_tip_lamports,
+            score: 0.0,
+        };
+        stats.score = stats.land_rate() / (1.0 + avg_slots_to_land)
+            - self.settings.cost_weight * avg_tip_lamports / 1_000_000_000.0;
+        stats
+    }
+
+    /// Recompute the ranking of all configured relays
+    pub fn rerank(&mut self) {
+        let mut ranking: Vec<(Relay, RelayStats)> = Relay::ALL
+            .iter()
+            .filter(|relay| relay.is_configured())
+            .map(|relay| (*relay, self.stats(*relay)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+        self.ranking = ranking;
+    }
+
+    /// Current ranking, best first
+    pub fn ranking(&self) -> &[(Relay, RelayStats)] {
+        &self.ranking
+    }
+
+    /// Relay to use for the next trade
+    pub fn select(&mut self) -> Relay {
+        if !self.settings.auto_select {
+            return self.settings.default_relay;
+        }
+
+        let unexplored: Vec<Relay> = self.ranking
+            .iter()
+            .filter(|(_, stats)| stats.submissions < self.settings.min_samples)
+            .map(|(relay, _)| *relay)
+            .collect();
+        if !unexplored.is_empty() {
+            self.explore_index = (self.explore_index + 1) % unexplored.len();
+            return unexplored[self.explore_index];
+        }
+
+        self.ranking
+            .first()
+            .map(|(relay, _)| *relay)
+            .unwrap_or(self.settings.default_relay)
+    }
+
+    fn log_ranking(&self) {
+        for (i, (relay, stats)) in self.ranking.iter().enumerate() {
+            self.logger.log(format!(
+                "#{} {} - landed {}/{} ({:.0}%), avg {:.1} slots, avg tip {:.0} lamports, score {:.4}",
+                i + 1,
+                relay,
+                stats.landed,
+                stats.submissions,
+                stats.land_rate() * 100.0,
+                stats.avg_slots_to_land,
+                stats.avg_tip_lamports,
+                stats.score
+            ));
+        }
+    }
+}
+
+static RELAY_SELECTOR: OnceLock<Arc<Mutex<RelaySelector>>> = OnceLock::new();
+
+/// Running relay selector, if auto-selection was started
+pub fn relay_selector() -> Option<Arc<Mutex<RelaySelector>>> {
+    RELAY_SELECTOR.get().cloned()
+}
+
+/// Watch a submission through `relay` in the background and record whether and how fast it landed
+///
+/// `signature` is `None` when the relay rejected the transaction. Every submission is
+/// counted in `relay_submissions`; paths that are not a relay of their own, like
+/// spam-send, are not ranked.
+pub fn track_submission(rpc_client: Arc<RpcClient>, relay: String, signature: Option<String>, tip_lamports: u64) {
+    tokio::spawn(async move {
+        let submit_slot = rpc_client.get_slot().await.unwrap_or(0);
+        let landed_slot = match signature.as_deref().and_then(|s| Signature::from_str(s).ok()) {
+            Some(signature) => wait_for_landed_slot(&rpc_client, &signature, Duration::from_secs(30)).await,
+            None => None,
+        };
+        let slots_to_land = landed_slot.map(|slot| slot.saturating_sub(submit_slot));
+        metrics()
+            .relay_submissions
+            .with_label_values(&[&relay, if landed_slot.is_some() { "true" } else { "false" }])
+            .inc();
+
+        if let (Ok(relay), Some(selector)) = (relay.parse::<Relay>(), relay_selector()) {
+            if let Ok(mut selector) = selector.lock() {
+                selector.record(RelaySubmission {
+                    relay,
+                    landed: landed_slot.is_some(),
+                    slots_to_land,
+                    tip_lamports,
+                });
+            }
+        }
+        tracing::info!(
+            relay = %relay,
+            signature = signature.as_deref().unwrap_or_default(),
+            submit_slot,
+            slot = landed_slot,
+            slots_to_land,
+            landed = landed_slot.is_some(),
+            "relay submission"
+        );
+    });
+}
+
+/// Start the relay selector with periodic re-ranking
+pub async fn start_relay_selection_system(logger: Logger) -> Arc<Mutex<RelaySelector>> {
+    let settings = RelaySelectorSettings::from_env();
+    logger.log(format!(
+        "Relay selection: {} (default {})",
+        if settings.auto_select { "auto" } else { "fixed" },
+        settings.default_relay
+    ).green().to_string());
+
+    let interval_secs = settings.rerank_interval_secs;
+    let selector = Arc::new(Mutex::new(RelaySelector::new(logger, settings)));
+    if let Ok(mut selector) = selector.lock() {
+        selector.rerank();
+    }
+    let _ = RELAY_SELECTOR.set(selector.clone());
+
+    let selector_clone = selector.clone();
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            if let Ok(mut selector) = selector_clone.lock() {
+                selector.rerank();
+                selector.log_ranking();
+            }
+        }
+    });
+
+    selector
+}
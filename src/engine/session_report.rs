@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+use crate::common::logger::Logger;
+use crate::common::trade_log::{read_trades, TradeLogEntry};
+use crate::engine::timer::TimerZone;
+use crate::services::price_feed::sol_usd;
+use crate::services::telegram::TelegramService;
+
+/// Session report settings
+#[derive(Debug, Clone)]
+pub struct SessionReportSettings {
+    /// Post a report when the timer closes a trading window
+    pub on_timer_stop: bool,
+    /// Also post one every day at this time, in `TIMER_TIMEZONE` (None = off)
+    pub daily_at: Option<NaiveTime>,
+    pub timezone: String,
+}
+
+impl SessionReportSettings {
+    /// Load session report settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            on_timer_stop: std::env::var("SESSION_REPORT_ON_STOP")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            daily_at: std::env::var("SESSION_REPORT_DAILY_AT")
+                .ok()
+                .and_then(|v| NaiveTime::parse_from_str(v.trim(), "%H:%M").ok()),
+            timezone: std::env::var("TIMER_TIMEZONE").unwrap_or_else(|_| "local".to_string()),
+        }
+    }
+}
+
+/// Results of the trades made since a session started
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionReport {
+    pub started_at: Option<DateTime<Utc>>,
+    /// Confirmed buys and sells
+    pub trades: usize,
+    /// Tokens with at least one confirmed sell in the session
+    pub closed: usize,
+    pub wins: usize,
+    /// Sells minus buys of the closed tokens, before fees and tips
+    pub gross_pnl_sol: f64,
+    /// Fees and tips of every landed transaction in the session
+    pub fees_sol: f64,
+    pub net_pnl_sol: f64,
+    pub best: Option<(String, f64)>,
+    pub worst: Option<(String, f64)>,
+}
+
+impl SessionReport {
+    /// Summarize the trades logged at or after `since`
+    pub fn build(trades: &[TradeLogEntry], since: DateTime<Utc>) -> Self {
+        let session: Vec<&TradeLogEntry> = trades.iter().filter(|trade| trade.timestamp >= since).collect();
+        let mut report = Self { started_at: Some(since), ..Default::default() };
+
+        // mint -> (SOL in, SOL out, sold)
+        let mut tokens: HashMap<&str, (f64, f64, bool)> = HashMap::new();
+        for trade in &session {
+            if trade.status != "abandoned" {
+                report.fees_sol += trade.fee_sol + trade.tip_sol;
+            }
+            if trade.status != "confirmed" {
+                continue;
+            }
+            report.trades += 1;
+            let token = tokens.entry(trade.mint.as_str()).or_default();
+            match trade.side.as_str() {
+                "buy" => token.0 += trade.sol_amount,
+                _ => {
+                    token.1 += trade.sol_amount;
+                    token.2 = true;
+                }
+            }
+        }
+
+        let mut closed: Vec<(String, f64)> = tokens
+            .into_iter()
+            .filter(|(_, (_, _, sold))| *sold)
+            .map(|(mint, (spent, received, _))| (mint.to_string(), received - spent))
+            .collect();
+        closed.sort_by(|a, b| b.1.total_cmp(&a.1));
+        report.closed = closed.len();
+        report.wins = closed.iter().filter(|(_, pnl)| *pnl > 0.0).count();
+        report.gross_pnl_sol = closed.iter().map(|(_, pnl)| pnl).sum();
+        report.net_pnl_sol = report.gross_pnl_sol - report.fees_sol;
+        report.best = closed.first().cloned();
+        report.worst = closed.last().cloned().filter(|_| closed.len() > 1);
+        report
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.closed == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.closed as f64 * 100.0
+    }
+
+    /// Telegram message, with USD amounts when a SOL/USD price is known
+    pub fn to_telegram_message(&self, sol_usd: Option<f64>) -> String {
+        let amount = |sol: f64| match sol_usd {
+            Some(usd) => format!("{:+.4} SOL (${:+.2})", sol, sol * usd),
+            None => format!("{:+.4} SOL", sol),
+        };
+        let token = |entry: &Option<(String, f64)>| match entry {
+            Some((mint, pnl)) => format!("{} {}", mint, amount(*pnl)),
+            None => "-".to_string(),
+        };
+        format!(
+            "<b>📒 SESSION REPORT</b>\n\
+            Since {}\n\n\
+            ├ Trades: {}\n\
+            ├ Closed tokens: {} ({:.0}% win rate)\n\
+            ├ Gross PnL: {}\n\
+            ├ Fees and tips: {}\n\
+            ├ Net PnL: {}\n\
+            ├ Best: {}\n\
+            └ Worst: {}",
+            self.started_at.map(|at| at.format("%Y-%m-%d %H:%M UTC").to_string()).unwrap_or_default(),
+            self.trades,
+            self.closed,
+            self.win_rate(),
+            amount(self.gross_pnl_sol),
+            amount(-self.fees_sol),
+            amount(self.net_pnl_sol),
+            token(&self.best),
+            token(&self.worst),
+        )
+    }
+}
+
+/// Start of the current session: startup, or the last report
+static SESSION_START: LazyLock<Mutex<DateTime<Utc>>> = LazyLock::new(|| Mutex::new(Utc::now()));
+static SESSION_REPORTS: LazyLock<Mutex<Option<(Arc<TelegramService>, String)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Send session reports to `chat_id`
+pub fn set_session_reports(telegram: Arc<TelegramService>, chat_id: String) {
+    *SESSION_REPORTS.lock().unwrap_or_else(|e| e.into_inner()) = Some((telegram, chat_id));
+}
+
+/// Start a new session now
+pub fn begin_session() {
+    *SESSION_START.lock().unwrap_or_else(|e| e.into_inner()) = Utc::now();
+}
+
+/// Post the report of the session so far to Telegram and start a new session
+pub async fn post_session_report(logger: &Logger) {
+    let since = std::mem::replace(&mut *SESSION_START.lock().unwrap_or_else(|e| e.into_inner()), Utc::now());
+    let report = SessionReport::build(&read_trades(), since);
+    logger.log(format!(
+        "Session report: {} trades, {}/{} wins, {:+.4} SOL gross, {:+.4} SOL net",
+        report.trades, report.wins, report.closed, report.gross_pnl_sol, report.net_pnl_sol
+    ));
+    let target = SESSION_REPORTS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some((telegram, chat_id)) = target {
+        if let Err(e) = telegram.send_message(&chat_id, &report.to_telegram_message(sol_usd()), "HTML").await {
+            logger.error(format!("Failed to send session report: {}", e));
+        }
+    }
+}
+
+/// Post a session report every day at `daily_at`
+pub async fn start_session_report_system(settings: SessionReportSettings, logger: Logger) {
+    let Some(daily_at) = settings.daily_at else {
+        return;
+    };
+    let zone = match TimerZone::parse(&settings.timezone) {
+        Ok(zone) => zone,
+        Err(e) => {
+            logger.error(format!("Daily session report disabled: {}", e));
+            return;
+        }
+    };
+    logger.log(format!("Daily session report at {} ({})", daily_at.format("%H:%M"), settings.timezone));
+    tokio::spawn(async move {
+        // a start after today's cutoff waits for tomorrow's
+        let now = zone.local(Utc::now());
+        let mut last_posted: Option<NaiveDate> = (now.time() >= daily_at).then(|| now.date());
+        let mut interval = tokio::time::interval(Duration::from_secs(30));
+        loop {
+            interval.tick().await;
+            let now = zone.local(Utc::now());
+            if now.time() >= daily_at && last_posted != Some(now.date()) {
+                last_posted = Some(now.date());
+                post_session_report(&logger).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn trade(minute: u32, mint: &str, side: &str, status: &str, sol_amount: f64) -> TradeLogEntry {
+        TradeLogEntry {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, minute, 0).unwrap(),
+            mint: mint.to_string(),
+            side: side.to_string(),
+            status: status.to_string(),
+            signature: String::new(),
+            sol_amount,
+            token_amount: 0,
+            price: 0.0,
+            fee_sol: 0.001,
+            tip_sol: 0.0,
+            attempts: 1,
+            latency_ms: 0,
+            reason: None,
+            reason_code: None,
+        }
+    }
+
+    #[test]
+    fn test_session_report() {
+        let trades = vec![
+            trade(0, "old", "buy", "confirmed", 5.0),
+            trade(10, "a", "buy", "confirmed", 1.0),
+            trade(11, "a", "sell", "confirmed", 1.5),
+            trade(12, "b", "buy", "confirmed", 1.0),
+            trade(13, "b", "sell", "confirmed", 0.8),
+            trade(14, "c", "buy", "confirmed", 1.0),
+            trade(15, "d", "buy", "abandoned", 1.0),
+            trade(16, "d", "buy", "failed", 1.0),
+        ];
+        let report = SessionReport::build(&trades, Utc.with_ymd_and_hms(2024, 1, 1, 12, 5, 0).unwrap());
+        assert_eq!(report.trades, 5);
+        assert_eq!((report.closed, report.wins), (2, 1));
+        assert!((report.gross_pnl_sol - 0.3).abs() < 1e-9);
+        assert!((report.fees_sol - 0.006).abs() < 1e-9);
+        assert!((report.net_pnl_sol - 0.294).abs() < 1e-9);
+        assert_eq!(report.best.as_ref().map(|(mint, _)| mint.as_str()), Some("a"));
+        assert_eq!(report.worst.as_ref().map(|(mint, _)| mint.as_str()), Some("b"));
+        assert_eq!(report.win_rate(), 50.0);
+        assert!(report.to_telegram_message(Some(100.0)).contains("$+29.40"));
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+
+/// Settings of the shadow run itself; every other `SHADOW_` key overrides a live filter
+const SETTING_KEYS: &[&str] = &[
+    "SHADOW_ENABLED",
+    "SHADOW_BUY_SOL",
+    "SHADOW_TAKE_PROFIT_PERCENT",
+    "SHADOW_STOP_LOSS_PERCENT",
+    "SHADOW_MAX_HOLD_SECS",
+    "SHADOW_CHECK_INTERVAL_SECS",
+    "SHADOW_MAX_OPEN",
+    "SHADOW_OUTPUT_DIR",
+];
+
+/// Shadow filter evaluation settings
+#[derive(Debug, Clone)]
+pub struct ShadowSettings {
+    pub enabled: bool,
+    /// Paper size of every simulated buy, in SOL
+    pub buy_sol: f64,
+    /// Paper positions close at this gain, in percent
+    pub take_profit_percent: f64,
+    /// Paper positions close at this loss, in percent
+    pub stop_loss_percent: f64,
+    /// Paper positions close at the last price after this long, in seconds
+    pub max_hold_secs: u64,
+    /// How often open paper positions are priced, in seconds
+    pub check_interval_secs: u64,
+    /// Paper positions held at most per configuration, later buys are not simulated
+    pub max_open: usize,
+    /// Directory closed paper trades are appended to as JSON lines
+    pub output_dir: String,
+}
+
+impl ShadowSettings {
+    /// Load shadow settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("SHADOW_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            buy_sol: std::env::var("SHADOW_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.1),
+            take_profit_percent: std::env::var("SHADOW_TAKE_PROFIT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(100.0),
+            stop_loss_percent: std::env::var("SHADOW_STOP_LOSS_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(30.0),
+            max_hold_secs: std::env::var("SHADOW_MAX_HOLD_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1800),
+            check_interval_secs: std::env::var("SHADOW_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(10),
+            max_open: std::env::var("SHADOW_MAX_OPEN")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(50),
+            output_dir: std::env::var("SHADOW_OUTPUT_DIR").unwrap_or_else(|_| "shadow".to_string()),
+        }
+    }
+}
+
+/// `SHADOW_{key}` when set and valid, otherwise the live value
+pub fn shadow_var<T: FromStr>(key: &str, live: T) -> T {
+    std::env::var(format!("SHADOW_{}", key))
+        .ok()
+        .and_then(|v| v.trim().parse::<T>().ok())
+        .unwrap_or(live)
+}
+
+/// Boolean `SHADOW_{key}`, `true` or `false` in any case
+pub fn shadow_flag(key: &str, live: bool) -> bool {
+    match std::env::var(format!("SHADOW_{}", key)) {
+        Ok(value) if !value.trim().is_empty() => value.trim().eq_ignore_ascii_case("true"),
+        _ => live,
+    }
+}
+
+/// Filter keys the shadow configuration overrides, with their values
+pub fn shadow_overrides() -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = std::env::vars()
+        .filter(|(key, value)| key.starts_with("SHADOW_") && !SETTING_KEYS.contains(&key.as_str()) && !value.trim().is_empty())
+        .collect();
+    overrides.sort();
+    overrides
+}
+
+/// The filter configuration a paper trade was made under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterConfig {
+    Live,
+    Shadow,
+}
+
+impl FilterConfig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FilterConfig::Live => "live",
+            FilterConfig::Shadow => "shadow",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PaperPosition {
+    entry_price: f64,
+    last_price: f64,
+    opened_at: Instant,
+    opened_at_utc: DateTime<Utc>,
+}
+
+/// A closed paper position
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PaperTrade {
+    pub config: FilterConfig,
+    pub mint: String,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: DateTime<Utc>,
+    pub pnl_percent: f64,
+    pub pnl_sol: f64,
+    pub exit_reason: String,
+}
+
+/// Paper results of one configuration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ConfigStats {
+    /// Launches the configuration would have bought
+    pub would_buy: u64,
+    pub closed: u64,
+    pub wins: u64,
+    pub realized_pnl_sol: f64,
+    pub open: usize,
+}
+
+/// How often the two configurations agreed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Agreement {
+    pub both: u64,
+    pub live_only: u64,
+    pub shadow_only: u64,
+    pub neither: u64,
+}
+
+/// Paper positions of the live and the shadow filter configuration, exited by the same rules
+#[derive(Debug)]
+pub struct ShadowBook {
+    settings: ShadowSettings,
+    open: HashMap<(FilterConfig, String), PaperPosition>,
+    stats: HashMap<FilterConfig, ConfigStats>,
+    agreement: Agreement,
+}
+
+impl ShadowBook {
+    pub fn new(settings: ShadowSettings) -> Self {
+        Self {
+            settings,
+            open: HashMap::new(),
+            stats: HashMap::new(),
+            agreement: Agreement::default(),
+        }
+    }
+
+    pub fn settings(&self) -> &ShadowSettings {
+        &self.settings
+    }
+
+    /// Both configurations judged a launch
+    pub fn decided(&mut self, live: bool, shadow: bool) {
+        match (live, shadow) {
+            (true, true) => self.agreement.both += 1,
+            (true, false) => self.agreement.live_only += 1,
+            (false, true) => self.agreement.shadow_only += 1,
+            (false, false) => self.agreement.neither += 1,
+        }
+    }
+
+    /// Paper buy of `mint` at `price`, returning false when the configuration is at its limit
+    pub fn open(&mut self, config: FilterConfig, mint: &str, price: f64, now: Instant) -> bool {
+        let held = self.open.keys().filter(|(held, _)| *held == config).count();
+        if price <= 0.0 || held >= self.settings.max_open || self.open.contains_key(&(config, mint.to_string())) {
+            return false;
+        }
+        self.open.insert((config, mint.to_string()), PaperPosition {
+            entry_price: price,
+            last_price: price,
+            opened_at: now,
+            opened_at_utc: Utc::now(),
+        });
+        self.stats.entry(config).or_default().would_buy += 1;
+        true
+    }
+
+    /// Mints with an open paper position in either configuration
+    pub fn open_mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self.open.keys().map(|(_, mint)| mint.clone()).collect();
+        mints.sort();
+        mints.dedup();
+        mints
+    }
+
+    /// Price `mint`, closing the paper positions that hit an exit; `None` when it could not be priced
+    pub fn mark(&mut self, mint: &str, price: Option<f64>, now: Instant) -> Vec<PaperTrade> {
+        let max_hold = Duration::from_secs(self.settings.max_hold_secs);
+        let mut closed = Vec::new();
+        for config in [FilterConfig::Live, FilterConfig::Shadow] {
+            let key = (config, mint.to_string());
+            let Some(position) = self.open.get_mut(&key) else {
+                continue;
+            };
+            if let Some(price) = price.filter(|price| *price > 0.0) {
+                position.last_price = price;
+            }
+            let change = (position.last_price / position.entry_price - 1.0) * 100.0;
+            let reason = if change >= self.settings.take_profit_percent {
+                "take profit"
+            } else if change <= -self.settings.stop_loss_percent {
+                "stop loss"
+            } else if now.duration_since(position.opened_at) >= max_hold {
+                "max hold"
+            } else {
+                continue;
+            };
+            let Some(position) = self.open.remove(&key) else {
+                continue;
+            };
+            let trade = PaperTrade {
+                config,
+                mint: mint.to_string(),
+                entry_price: position.entry_price,
+                exit_price: position.last_price,
+                opened_at: position.opened_at_utc,
+                closed_at: Utc::now(),
+                pnl_percent: change,
+                pnl_sol: self.settings.buy_sol * change / 100.0,
+                exit_reason: reason.to_string(),
+            };
+            let stats = self.stats.entry(config).or_default();
+            stats.closed += 1;
+            stats.realized_pnl_sol += trade.pnl_sol;
+            if trade.pnl_sol > 0.0 {
+                stats.wins += 1;
+            }
+            closed.push(trade);
+        }
+        closed
+    }
+
+    pub fn stats(&self, config: FilterConfig) -> ConfigStats {
+        let mut stats = self.stats.get(&config).copied().unwrap_or_default();
+        stats.open = self.open.keys().filter(|(held, _)| *held == config).count();
+        stats
+    }
+
+    pub fn agreement(&self) -> Agreement {
+        self.agreement
+    }
+}
+
+static SHADOW_BOOK: LazyLock<Mutex<ShadowBook>> = LazyLock::new(|| Mutex::new(ShadowBook::new(ShadowSettings::from_env())));
+
+/// Shared paper book, fed by the launch feed
+pub fn shadow_book() -> MutexGuard<'static, ShadowBook> {
+    SHADOW_BOOK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Paper-buy a launch for each configuration that would have bought it
+pub async fn paper_buy(executor: &TradeExecutor, mint: &str, live: bool, shadow: bool) {
+    shadow_book().decided(live, shadow);
+    if !live && !shadow {
+        return;
+    }
+    let Ok(price) = executor.current_price(mint).await else {
+        return;
+    };
+    let now = Instant::now();
+    let mut book = shadow_book();
+    for (config, bought) in [(FilterConfig::Live, live), (FilterConfig::Shadow, shadow)] {
+        if bought {
+            book.open(config, mint, price, now);
+        }
+    }
+}
+
+fn append_trade(dir: &str, trade: &PaperTrade) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(format!("{}/shadow_trades.jsonl", dir))?;
+    writeln!(file, "{}", serde_json::to_string(trade)?)
+}
+
+/// Price open paper positions and record the ones that close
+pub async fn start_shadow_system(executor: Arc<TradeExecutor>, logger: Logger) {
+    let settings = shadow_book().settings().clone();
+    if !settings.enabled {
+        return;
+    }
+    let overrides = shadow_overrides();
+    if overrides.is_empty() {
+        logger.log("Shadow mode is on without SHADOW_ filter overrides, both configurations are the same".yellow().to_string());
+    } else {
+        let list: Vec<String> = overrides.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+        logger.log(format!("👥 Shadow filters: {}", list.join(", ")).cyan().to_string());
+    }
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.check_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            let mints = shadow_book().open_mints();
+            for mint in mints {
+                let price = executor.current_price(&mint).await.ok();
+                let closed = shadow_book().mark(&mint, price, Instant::now());
+                for trade in closed {
+                    logger.log(format!(
+                        "[{}] => Paper {} of {}: {:+.1}% ({:+.4} SOL)",
+                        trade.config.name(), trade.exit_reason, trade.mint, trade.pnl_percent, trade.pnl_sol
+                    ));
+                    if let Err(e) = append_trade(&settings.output_dir, &trade) {
+                        logger.error(format!("Failed to record paper trade: {}", e));
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paper_book() {
+        let settings = ShadowSettings {
+            enabled: true,
+            buy_sol: 0.1,
+            take_profit_percent: 100.0,
+            stop_loss_percent: 30.0,
+            max_hold_secs: 600,
+            check_interval_secs: 10,
+            max_open: 2,
+            output_dir: String::new(),
+        };
+        let mut book = ShadowBook::new(settings);
+        let now = Instant::now();
+        book.decided(true, true);
+        book.decided(false, true);
+        assert!(book.open(FilterConfig::Live, "a", 1.0, now));
+        assert!(book.open(FilterConfig::Shadow, "a", 1.0, now));
+        assert!(book.open(FilterConfig::Shadow, "b", 1.0, now));
+        // the shadow configuration is at its limit
+        assert!(!book.open(FilterConfig::Shadow, "c", 1.0, now));
+
+        assert!(book.mark("a", Some(1.5), now).is_empty());
+        let closed = book.mark("a", Some(2.0), now);
+        assert_eq!(closed.len(), 2);
+        assert!((closed[0].pnl_sol - 0.1).abs() < 1e-9);
+
+        // unpriced, closed at the last price once held too long
+        assert!(book.mark("b", Some(0.9), now).is_empty());
+        let closed = book.mark("b", None, now + Duration::from_secs(600));
+        assert_eq!(closed[0].exit_reason, "max hold");
+
+        let shadow = book.stats(FilterConfig::Shadow);
+        assert_eq!((shadow.would_buy, shadow.closed, shadow.wins, shadow.open), (2, 2, 1, 0));
+        assert!((shadow.realized_pnl_sol - 0.09).abs() < 1e-9);
+        assert_eq!(book.agreement(), Agreement { both: 1, live_only: 0, shadow_only: 1, neither: 0 });
+    }
+}
@@ -0,0 +1,217 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::common::config::{LiquidityPool, Status};
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::position_manager::{sell, SellAmount};
+use crate::engine::trade_executor::{pause_trading, TradeExecutor};
+
+/// Set once a shutdown signal has been received
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the bot is shutting down; streams should stop taking new events
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Shutdown settings
+#[derive(Debug, Clone)]
+pub struct ShutdownSettings {
+    /// Sell every open position before exiting
+    pub sell_all: bool,
+    /// How long to wait for in-flight transactions to settle
+    pub grace_period_secs: u64,
+    /// Where open positions are saved on exit and restored on start
+    pub state_file: String,
+}
+
+impl ShutdownSettings {
+    /// Load shutdown settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            sell_all: std::env::var("SELL_ALL_ON_SHUTDOWN")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            grace_period_secs: std::env::var("SHUTDOWN_GRACE_PERIOD_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(30),
+            state_file: std::env::var("POSITIONS_STATE_FILE")
+                .unwrap_or_else(|_| "positions_state.json".to_string()),
+        }
+    }
+}
+
+/// Open position as written to the state file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedPosition {
+    pub mint: String,
+    pub buy_price: f64,
+    pub sell_price: f64,
+    pub status: Status,
+}
+
+/// Wait for SIGINT (Ctrl+C) or, on unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Save open positions so they are tracked again after a restart
+pub fn save_positions(path: &str, pools: &[LiquidityPool]) -> Result<usize> {
+    let positions: Vec<PersistedPosition> = pools
+        .iter()
+        .filter(|pool| pool.status == Status::Bought)
+        .map(|pool| PersistedPosition {
+            mint: pool.mint.clone(),
+            buy_price: pool.buy_price,
+            sell_price: pool.sell_price,
+            status: pool.status.clone(),
+        })
+        .collect();
+    std::fs::write(path, serde_json::to_string_pretty(&positions)?)?;
+    Ok(positions.len())
+}
+
+/// Load positions saved by a previous run
+pub fn load_positions(path: &str) -> Result<Vec<LiquidityPool>> {
+    if !std::path::Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+    let positions: Vec<PersistedPosition> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    Ok(positions
+        .into_iter()
+        .map(|position| LiquidityPool {
+            mint: position.mint,
+            buy_price: position.buy_price,
+            sell_price: position.sell_price,
+            status: position.status,
+            timestamp: Some(tokio::time::Instant::now()),
+        })
+        .collect())
+}
+
+/// Restore saved positions into the executor's position store
+pub fn restore_positions(executor: &TradeExecutor, settings: &ShutdownSettings, logger: &Logger) {
+    match load_positions(&settings.state_file) {
+        Ok(positions) if !positions.is_empty() => {
+            let count = positions.len();
+            executor.positions().extend(positions);
+            logger.log(format!("Restored {} open positions from {}", count, settings.state_file).green().to_string());
+        }
+        Ok(_) => {}
+        Err(e) => logger.error(format!("Failed to load {}: {}", settings.state_file, e)),
+    }
+}
+
+/// Stop trading and exit cleanly
+///
+/// New buys are paused, in-flight transactions get up to the grace period to settle,
+/// open positions are optionally sold, WSOL is unwrapped and the remaining positions
+/// are written to the state file.
+pub async fn graceful_shutdown(executor: Arc<TradeExecutor>, settings: ShutdownSettings, logger: Logger) {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    pause_trading();
+    logger.log("Shutdown signal received, no new buys will be started".yellow().bold().to_string());
+
+    wait_for_in_flight(&executor, &settings, &logger).await;
+
+    if settings.sell_all {
+        let open = executor.open_positions();
+        logger.log(format!("SELL_ALL_ON_SHUTDOWN=true, liquidating {} positions", open.len()).yellow().to_string());
+        for mint in open {
+            record_decision(Decision::Sell, &mint, ReasonCode::ShutdownSell, "SELL_ALL_ON_SHUTDOWN");
+            match sell(&executor, &mint, SellAmount::All).await {
+                Ok(signature) => logger.log(format!("Emergency sell {}: {}", mint, signature)),
+                Err(e) => logger.error(format!("Emergency sell failed for {}: {}", mint, e)),
+            }
+        }
+        wait_for_in_flight(&executor, &settings, &logger).await;
+    }
+
+    match executor.unwrap_wsol().await {
+        Ok(Some(signature)) => logger.log(format!("Unwrapped WSOL: {}", signature)),
+        Ok(None) => {}
+        Err(e) => logger.error(format!("Failed to unwrap WSOL: {}", e)),
+    }
+
+    let pools: Vec<LiquidityPool> = executor.positions().snapshot();
+    match save_positions(&settings.state_file, &pools) {
+        Ok(count) => logger.log(format!("Saved {} open positions to {}", count, settings.state_file)),
+        Err(e) => logger.error(format!("Failed to save positions to {}: {}", settings.state_file, e)),
+    }
+
+    logger.log("Shutdown complete".green().bold().to_string());
+}
+
+/// Wait until no trade is pending confirmation, or the grace period runs out
+pub async fn wait_for_in_flight(executor: &TradeExecutor, settings: &ShutdownSettings, logger: &Logger) {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(settings.grace_period_secs);
+    loop {
+        let pending = executor.in_flight();
+        if pending == 0 {
+            return;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            logger.error(format!("{} transactions still pending after {}s grace period", pending, settings.grace_period_secs));
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_positions_keeps_only_bought() {
+        let path = std::env::temp_dir().join(format!("vntr-positions-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let pool = |mint: &str, status: Status| LiquidityPool {
+            mint: mint.to_string(),
+            buy_price: 0.001,
+            sell_price: 0.0,
+            status,
+            timestamp: None,
+        };
+
+        let saved = save_positions(path, &[
+            pool("held", Status::Bought),
+            pool("sold", Status::Sold),
+            pool("failed", Status::Failure),
+        ]).unwrap();
+        assert_eq!(saved, 1);
+
+        let loaded = load_positions(path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].mint, "held");
+        assert_eq!(loaded[0].status, Status::Bought);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::dex::pump_fun::BondingCurveReserves;
+
+/// Most prices kept per mint, enough for the busiest launches
+const MAX_SAMPLES: usize = 256;
+
+/// Dynamic slippage and price impact settings
+#[derive(Debug, Clone)]
+pub struct SlippageSettings {
+    /// Widen the configured `SLIPPAGE` while a token's price swings
+    pub dynamic: bool,
+    /// Never widen slippage beyond this, in basis points
+    pub max_slippage_bps: u64,
+    /// Slippage in basis points per percent of recent price range
+    pub volatility_factor: f64,
+    /// How far back the recent price range is measured, in seconds
+    pub volatility_window_secs: u64,
+    /// Skip buys whose own price impact is above this percent (0 = no limit)
+    pub max_price_impact_percent: f64,
+}
+
+impl SlippageSettings {
+    /// Load dynamic slippage settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            dynamic: std::env::var("DYNAMIC_SLIPPAGE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            max_slippage_bps: std::env::var("MAX_SLIPPAGE_BPS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3000),
+            volatility_factor: std::env::var("SLIPPAGE_VOLATILITY_FACTOR")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(50.0),
+            volatility_window_secs: std::env::var("SLIPPAGE_VOLATILITY_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(10),
+            max_price_impact_percent: std::env::var("MAX_PRICE_IMPACT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0),
+        }
+    }
+
+    /// Slippage for a trade: `base_bps`, widened by the recent price range when dynamic
+    ///
+    /// The result never drops below `base_bps` and is only widened up to `max_slippage_bps`.
+    pub fn slippage_bps(&self, base_bps: u64, volatility_percent: Option<f64>) -> u64 {
+        let Some(volatility) = volatility_percent.filter(|_| self.dynamic) else {
+            return base_bps;
+        };
+        let widened = (volatility * self.volatility_factor).round().max(0.0) as u64;
+        widened.min(self.max_slippage_bps).max(base_bps)
+    }
+
+    /// Reason to skip a buy moving the price by `impact_percent`, if it is too large
+    pub fn impact_rejection(&self, impact_percent: f64) -> Option<String> {
+        if self.max_price_impact_percent > 0.0 && impact_percent > self.max_price_impact_percent {
+            return Some(format!(
+                "price impact {:.2}% above MAX_PRICE_IMPACT_PERCENT {}",
+                impact_percent, self.max_price_impact_percent
+            ));
+        }
+        None
+    }
+}
+
+/// Percent the average fill price of a `sol_in` lamport buy lies above the current price
+///
+/// On a constant product curve this is `sol_in / virtual_sol_reserves`, fees aside.
+pub fn price_impact_percent(reserves: &BondingCurveReserves, sol_in: u64) -> f64 {
+    if reserves.virtual_sol_reserves == 0 {
+        return 100.0;
+    }
+    sol_in as f64 / reserves.virtual_sol_reserves as f64 * 100.0
+}
+
+/// Recent trade prices per mint, fed from the trade stream
+pub struct VolatilityTracker {
+    window: Duration,
+    prices: HashMap<String, VecDeque<(Instant, f64)>>,
+}
+
+impl VolatilityTracker {
+    pub fn new(window: Duration) -> Self {
+        Self { window, prices: HashMap::new() }
+    }
+
+    /// Record a trade price of `mint`
+    pub fn record(&mut self, mint: &str, price: f64, at: Instant) {
+        if price <= 0.0 || !price.is_finite() {
+            return;
+        }
+        let samples = self.prices.entry(mint.to_string()).or_default();
+        samples.push_back((at, price));
+        while samples.len() > MAX_SAMPLES {
+            samples.pop_front();
+        }
+    }
+
+    /// Range of `mint`'s prices in the window as a percent of the lowest, `None` without two prices
+    pub fn volatility_percent(&self, mint: &str, now: Instant) -> Option<f64> {
+        let samples = self.prices.get(mint)?;
+        let recent = samples.iter().filter(|(at, _)| now.duration_since(*at) <= self.window);
+        let (count, low, high) = recent.fold((0, f64::MAX, 0.0f64), |(count, low, high), (_, price)| {
+            (count + 1, low.min(*price), high.max(*price))
+        });
+        (count >= 2).then(|| (high - low) / low * 100.0)
+    }
+
+    /// Forget prices older than the window
+    pub fn prune(&mut self, now: Instant) {
+        let window = self.window;
+        self.prices.retain(|_, samples| {
+            while samples.front().map(|(at, _)| now.duration_since(*at) > window).unwrap_or(false) {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+    }
+}
+
+static VOLATILITY: LazyLock<Mutex<VolatilityTracker>> = LazyLock::new(|| {
+    Mutex::new(VolatilityTracker::new(Duration::from_secs(SlippageSettings::from_env().volatility_window_secs.max(1))))
+});
+
+/// Shared tracker written by the trade stream and read when sizing slippage
+pub fn volatility() -> std::sync::MutexGuard<'static, VolatilityTracker> {
+    VOLATILITY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> SlippageSettings {
+        SlippageSettings {
+            dynamic: true,
+            max_slippage_bps: 2000,
+            volatility_factor: 50.0,
+            volatility_window_secs: 10,
+            max_price_impact_percent: 5.0,
+        }
+    }
+
+    #[test]
+    fn test_dynamic_slippage_scales_with_volatility() {
+        let settings = settings();
+        assert_eq!(settings.slippage_bps(500, None), 500);
+        // the base slippage is a floor
+        assert_eq!(settings.slippage_bps(500, Some(4.0)), 500);
+        assert_eq!(settings.slippage_bps(500, Some(20.0)), 1000);
+        assert_eq!(settings.slippage_bps(500, Some(80.0)), 2000);
+    }
+
+    #[test]
+    fn test_static_slippage_ignores_volatility() {
+        assert_eq!(SlippageSettings { dynamic: false, ..settings() }.slippage_bps(500, Some(80.0)), 500);
+    }
+
+    #[test]
+    fn test_price_impact_rejection() {
+        let reserves = BondingCurveReserves { virtual_token_reserves: 1_073_000_000_000_000, virtual_sol_reserves: 30_000_000_000 };
+        assert!((price_impact_percent(&reserves, 1_500_000_000) - 5.0).abs() < 1e-9);
+        assert_eq!(settings().impact_rejection(4.9), None);
+        assert!(settings().impact_rejection(6.0).is_some());
+    }
+
+    #[test]
+    fn test_volatility_over_the_window() {
+        let mut tracker = VolatilityTracker::new(Duration::from_secs(10));
+        let now = Instant::now();
+        tracker.record("mint", 1.0, now);
+        assert_eq!(tracker.volatility_percent("mint", now), None);
+        tracker.record("mint", 1.25, now + Duration::from_secs(2));
+        tracker.record("mint", 1.1, now + Duration::from_secs(4));
+        assert!((tracker.volatility_percent("mint", now + Duration::from_secs(5)).unwrap() - 25.0).abs() < 1e-9);
+        // the low fell out of the window
+        assert!((tracker.volatility_percent("mint", now + Duration::from_secs(11)).unwrap() - 100.0 * 0.15 / 1.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_prune_forgets_stale_mints() {
+        let mut tracker = VolatilityTracker::new(Duration::from_secs(10));
+        let now = Instant::now();
+        tracker.record("mint", 1.0, now);
+        tracker.record("mint", 1.25, now + Duration::from_secs(2));
+        tracker.prune(now + Duration::from_secs(30));
+        assert_eq!(tracker.volatility_percent("mint", now + Duration::from_secs(30)), None);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use serde::Serialize;
+
+/// Same-block copy settings
+#[derive(Debug, Clone)]
+pub struct SlotSyncSettings {
+    /// Targets whose buys are copied ahead of every other queued buy, aiming for their block
+    pub targets: HashSet<String>,
+    /// A target trade seen more slots than this behind the stream tip is copied as usual
+    pub max_lag_slots: u64,
+}
+
+impl SlotSyncSettings {
+    /// Load same-block copy settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            targets: std::env::var("SAME_BLOCK_TARGETS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|wallet| wallet.trim().to_string())
+                .filter(|wallet| !wallet.is_empty())
+                .collect(),
+            max_lag_slots: std::env::var("SAME_BLOCK_MAX_LAG_SLOTS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1),
+        }
+    }
+}
+
+/// How many blocks after the target's a copy landed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotDistance {
+    Same,
+    Next,
+    Later(u64),
+}
+
+impl SlotDistance {
+    pub fn between(target_slot: u64, landed_slot: u64) -> Self {
+        match landed_slot.saturating_sub(target_slot) {
+            0 => SlotDistance::Same,
+            1 => SlotDistance::Next,
+            slots => SlotDistance::Later(slots),
+        }
+    }
+}
+
+/// Landing record of same-block copies
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SlotSyncStats {
+    pub same_block: u64,
+    pub next_block: u64,
+    pub later: u64,
+    /// Copies that failed or never landed
+    pub missed: u64,
+    /// Target trades seen too far behind the tip to try
+    pub too_late: u64,
+}
+
+impl SlotSyncStats {
+    /// Share of landed copies in the target's block, in percent
+    pub fn same_block_percent(&self) -> f64 {
+        let landed = self.same_block + self.next_block + self.later;
+        if landed == 0 {
+            return 0.0;
+        }
+        self.same_block as f64 / landed as f64 * 100.0
+    }
+}
+
+/// Slots of the target trades being copied, and how the copies landed
+#[derive(Debug)]
+pub struct SlotSync {
+    settings: SlotSyncSettings,
+    /// Target slot per mint with a same-block copy in flight
+    pending: HashMap<String, u64>,
+    stats: SlotSyncStats,
+}
+
+impl SlotSync {
+    pub fn new(settings: SlotSyncSettings) -> Self {
+        Self { settings, pending: HashMap::new(), stats: SlotSyncStats::default() }
+    }
+
+    pub fn is_target(&self, wallet: &str) -> bool {
+        self.settings.targets.contains(wallet)
+    }
+
+    /// Note a target trade of `mint` in `slot`, `true` when it is fresh enough to copy in the same block
+    pub fn target_trade(&mut self, mint: &str, slot: u64, tip_slot: u64) -> bool {
+        if tip_slot.saturating_sub(slot) > self.settings.max_lag_slots {
+            self.stats.too_late += 1;
+            return false;
+        }
+        self.pending.insert(mint.to_string(), slot);
+        true
+    }
+
+    /// Record where our copy of `mint` landed, `None` when it failed or was dropped
+    ///
+    /// Returns the distance to the target's block, or `None` for mints without a same-block copy.
+    pub fn copy_landed(&mut self, mint: &str, landed_slot: Option<u64>) -> Option<SlotDistance> {
+        let target_slot = self.pending.remove(mint)?;
+        let Some(landed_slot) = landed_slot else {
+            self.stats.missed += 1;
+            return None;
+        };
+        let distance = SlotDistance::between(target_slot, landed_slot);
+        match distance {
+            SlotDistance::Same => self.stats.same_block += 1,
+            SlotDistance::Next => self.stats.next_block += 1,
+            SlotDistance::Later(_) => self.stats.later += 1,
+        }
+        Some(distance)
+    }
+
+    /// Forget the copy of `mint` when it was not sent at all
+    pub fn discard(&mut self, mint: &str) {
+        self.pending.remove(mint);
+    }
+
+    pub fn stats(&self) -> &SlotSyncStats {
+        &self.stats
+    }
+}
+
+static SLOT_SYNC: LazyLock<Mutex<SlotSync>> = LazyLock::new(|| Mutex::new(SlotSync::new(SlotSyncSettings::from_env())));
+
+pub fn slot_sync() -> MutexGuard<'static, SlotSync> {
+    SLOT_SYNC.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_block_landings() {
+        let settings = SlotSyncSettings { targets: HashSet::from(["target".to_string()]), max_lag_slots: 1 };
+        let mut sync = SlotSync::new(settings);
+        assert!(sync.is_target("target"));
+
+        assert!(!sync.target_trade("late", 100, 102));
+        assert!(sync.target_trade("a", 100, 101));
+        assert!(sync.target_trade("b", 100, 100));
+        assert!(sync.target_trade("c", 100, 100));
+        assert_eq!(sync.copy_landed("a", Some(100)), Some(SlotDistance::Same));
+        assert_eq!(sync.copy_landed("b", Some(103)), Some(SlotDistance::Later(3)));
+        assert_eq!(sync.copy_landed("c", None), None);
+        assert_eq!(sync.copy_landed("unknown", Some(100)), None);
+
+        let stats = sync.stats();
+        assert_eq!((stats.same_block, stats.later, stats.missed, stats.too_late), (1, 1, 1, 1));
+        assert!((stats.same_block_percent() - 50.0).abs() < 1e-9);
+    }
+}
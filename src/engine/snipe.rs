@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use crate::engine::confidence::{ConfidenceInputs, ConfidenceScorer};
+use crate::engine::strategy::{Strategy, StrategyAction};
+use crate::services::telegram::TokenInfo;
+
+/// Launch sniping settings
+#[derive(Debug, Clone)]
+pub struct SnipeSettings {
+    pub enabled: bool,
+}
+
+impl SnipeSettings {
+    /// Load sniping settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("SNIPE_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+}
+
+/// Buys new launches that passed the filters, sized by their confidence score
+pub struct SnipeStrategy {
+    scorer: ConfidenceScorer,
+    /// Mints already bought, so a token re-announced by the pipeline is not bought twice
+    seen: HashSet<String>,
+}
+
+impl SnipeStrategy {
+    pub fn new(scorer: ConfidenceScorer) -> Self {
+        Self {
+            scorer,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl Strategy for SnipeStrategy {
+    fn name(&self) -> &'static str {
+        "snipe"
+    }
+
+    /// Entries come from the launch pipeline, not the trade stream
+    fn stream_accounts(&self) -> Option<Vec<String>> {
+        Some(Vec::new())
+    }
+
+    fn on_new_token(&mut self, token: &TokenInfo) -> Vec<StrategyAction> {
+        if !self.seen.insert(token.address.clone()) {
+            return Vec::new();
+        }
+        let score = self.scorer.score(&ConfidenceInputs::from_token_info(token));
+        vec![StrategyAction::Buy {
+            mint: token.address.clone(),
+            sol_amount: None,
+            confidence: score.as_ref().map(|score| score.score),
+            reason: match score {
+                Some(score) => format!("new launch ({})", score.describe()),
+                None => "new launch".to_string(),
+            },
+        }]
+    }
+}
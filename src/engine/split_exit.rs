@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+/// How sells are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SellExecutionMode {
+    /// One transaction for the whole amount
+    Market,
+    /// Split into equal chunks sent over time
+    Twap,
+}
+
+impl FromStr for SellExecutionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "market" => Ok(SellExecutionMode::Market),
+            "twap" | "split" => Ok(SellExecutionMode::Twap),
+            other => Err(format!("Unknown sell execution mode: {}", other)),
+        }
+    }
+}
+
+/// Split exit settings
+#[derive(Debug, Clone)]
+pub struct SplitExitSettings {
+    pub mode: SellExecutionMode,
+    /// Number of chunks a sell is split into
+    pub chunks: u32,
+    /// Delay between chunks in milliseconds (400ms is roughly one slot)
+    pub interval_ms: u64,
+    /// If the price falls this much below the first chunk's price, sell the rest at once (0 = never)
+    pub abort_drop_percent: f64,
+    /// Sells smaller than this many SOL are always sent in one transaction
+    pub min_split_sol: f64,
+}
+
+impl SplitExitSettings {
+    /// Load split exit settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            mode: std::env::var("SELL_EXECUTION_MODE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(SellExecutionMode::Market),
+            chunks: std::env::var("TWAP_CHUNKS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(4),
+            interval_ms: std::env::var("TWAP_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(1200),
+            abort_drop_percent: std::env::var("TWAP_ABORT_DROP_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(25.0),
+            min_split_sol: std::env::var("TWAP_MIN_SPLIT_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.5),
+        }
+    }
+
+    /// Whether a sell worth `sol_value` should be split
+    pub fn should_split(&self, sol_value: f64) -> bool {
+        self.mode == SellExecutionMode::Twap && self.chunks > 1 && sol_value >= self.min_split_sol
+    }
+
+    /// Whether the price has collapsed enough since `start_price` to stop splitting
+    pub fn is_collapse(&self, start_price: f64, price: f64) -> bool {
+        self.abort_drop_percent > 0.0
+            && start_price > 0.0
+            && (1.0 - price / start_price) * 100.0 >= self.abort_drop_percent
+    }
+}
+
+/// Split `total` tokens into `chunks` near-equal amounts; the last chunk takes the remainder
+pub fn plan_chunks(total: u64, chunks: u32) -> Vec<u64> {
+    let chunks = chunks.max(1) as u64;
+    let size = total / chunks;
+    if size == 0 {
+        return vec![total];
+    }
+    let mut plan = vec![size; chunks as usize - 1];
+    plan.push(total - size * (chunks - 1));
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_chunks() {
+        assert_eq!(plan_chunks(100, 4), vec![25, 25, 25, 25]);
+        assert_eq!(plan_chunks(10, 3), vec![3, 3, 4]);
+        assert_eq!(plan_chunks(2, 4), vec![2]);
+        assert_eq!(plan_chunks(7, 0), vec![7]);
+    }
+
+    #[test]
+    fn test_collapse_and_split_threshold() {
+        let settings = SplitExitSettings {
+            mode: SellExecutionMode::Twap,
+            chunks: 4,
+            interval_ms: 400,
+            abort_drop_percent: 25.0,
+            min_split_sol: 0.5,
+        };
+        assert!(!settings.is_collapse(1.0, 0.8));
+        assert!(settings.is_collapse(1.0, 0.75));
+        assert!(settings.should_split(1.0));
+        assert!(!settings.should_split(0.1));
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+
+use crate::common::logger::Logger;
+use crate::core::tx::with_fee_boost;
+use crate::engine::position_manager::{sell, SellAmount};
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::alerts::{alert, Alert};
+
+/// One retry of a stop-loss sell that did not land
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscalationStep {
+    pub slippage_bps: u64,
+    /// Multiplier on the priority fee and tips
+    pub fee_multiplier: f64,
+    /// Submission path, the usual one when `None`
+    pub route: Option<&'static str>,
+}
+
+impl EscalationStep {
+    /// Parse `slippage_bps:fee_multiplier[:route]`, route being `rpc`, `jito` or `tpu`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = value.trim().split(':').map(str::trim).collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(format!("expected slippage_bps:fee_multiplier[:route], got '{}'", value));
+        }
+        let slippage_bps = parts[0].parse::<u64>().map_err(|_| format!("invalid slippage '{}'", parts[0]))?;
+        if slippage_bps > 10_000 {
+            return Err(format!("slippage {} is above 10000 bps", slippage_bps));
+        }
+        let fee_multiplier = parts[1].parse::<f64>().map_err(|_| format!("invalid fee multiplier '{}'", parts[1]))?;
+        if fee_multiplier < 1.0 {
+            return Err(format!("fee multiplier {} is below 1", fee_multiplier));
+        }
+        let route = match parts.get(2).map(|route| route.to_lowercase()) {
+            None => None,
+            Some(route) => Some(match route.as_str() {
+                "rpc" => "rpc",
+                "jito" => "jito",
+                "tpu" => "tpu",
+                other => return Err(format!("unknown route '{}'", other)),
+            }),
+        };
+        Ok(Self { slippage_bps, fee_multiplier, route })
+    }
+
+    /// Parse a comma separated list of steps
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        value.split(',').filter(|step| !step.trim().is_empty()).map(Self::parse).collect()
+    }
+
+    fn describe(&self) -> String {
+        let price = if self.slippage_bps >= 10_000 {
+            "at any price".to_string()
+        } else {
+            format!("{} bps slippage", self.slippage_bps)
+        };
+        format!("{}, fees x{}, via {}", price, self.fee_multiplier, self.route.unwrap_or("the usual route"))
+    }
+}
+
+const DEFAULT_STEPS: &str = "1500:1,3000:2,5000:4:rpc,10000:8";
+
+/// Stop-loss escalation settings
+#[derive(Debug, Clone)]
+pub struct StopLossEscalationSettings {
+    pub enabled: bool,
+    /// Tried in order after the first sell, the last one usually dumping at any price
+    pub steps: Vec<EscalationStep>,
+    /// How long a sell gets to land before the next step, in milliseconds
+    pub wait_ms: u64,
+}
+
+impl StopLossEscalationSettings {
+    /// Load escalation settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("SL_ESCALATION_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            steps: std::env::var("SL_ESCALATION_STEPS")
+                .ok()
+                .and_then(|v| EscalationStep::parse_list(&v).ok())
+                .unwrap_or_else(|| EscalationStep::parse_list(DEFAULT_STEPS).unwrap_or_default()),
+            wait_ms: std::env::var("SL_ESCALATION_WAIT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(4000),
+        }
+    }
+}
+
+/// Mints with a stop-loss sell being escalated, left alone by the position manager meanwhile
+static ESCALATING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+pub fn is_escalating(mint: &str) -> bool {
+    ESCALATING.lock().unwrap_or_else(|e| e.into_inner()).contains(mint)
+}
+
+/// Whether a full sell of `mint` was confirmed, or the tokens are gone anyway
+async fn position_closed(executor: &TradeExecutor, mint: &str) -> bool {
+    !executor.has_position(mint) || executor.held_balance(mint).await.is_ok_and(|balance| balance == 0)
+}
+
+/// Whether the position in `mint` is closed after waiting up to `wait`
+async fn sold_within(executor: &TradeExecutor, mint: &str, wait: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + wait;
+    loop {
+        if position_closed(executor, mint).await {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+/// Sell the whole position in `mint` for a stop loss, escalating while the sell does not land
+///
+/// The first sell goes out as usual. Each step after it resends the remaining balance with its
+/// slippage, fee multiplier and route, and is logged and alerted.
+pub async fn stop_loss_sell(executor: &TradeExecutor, mint: &str, logger: &Logger) -> Result<String> {
+    let settings = StopLossEscalationSettings::from_env();
+    if !settings.enabled {
+        return sell(executor, mint, SellAmount::All).await;
+    }
+    if !ESCALATING.lock().unwrap_or_else(|e| e.into_inner()).insert(mint.to_string()) {
+        return Err(anyhow!("A stop-loss sell of {} is already escalating", mint));
+    }
+    let result = escalate(executor, mint, &settings, logger).await;
+    ESCALATING.lock().unwrap_or_else(|e| e.into_inner()).remove(mint);
+    result
+}
+
+async fn escalate(executor: &TradeExecutor, mint: &str, settings: &StopLossEscalationSettings, logger: &Logger) -> Result<String> {
+    let wait = Duration::from_millis(settings.wait_ms);
+    let first = sell(executor, mint, SellAmount::All).await;
+    match &first {
+        Ok(_) if sold_within(executor, mint, wait).await => return first,
+        Ok(signature) => logger.log(format!("[SL ESCALATION] => {} stop-loss sell {} has not landed", mint, signature).red().to_string()),
+        Err(e) => logger.log(format!("[SL ESCALATION] => {} stop-loss sell failed: {}", mint, e).red().to_string()),
+    }
+
+    let count = settings.steps.len();
+    for (i, step) in settings.steps.iter().enumerate() {
+        let message = format!("Stop loss of {} escalating, step {}/{}: {}", mint, i + 1, count, step.describe());
+        logger.log(format!("[SL ESCALATION] => {}", message).red().bold().to_string());
+        alert(Alert::stop_loss(mint, &message));
+        let result = with_fee_boost(step.fee_multiplier, executor.sell_escalated(mint, step.slippage_bps, step.route)).await;
+        match &result {
+            Ok(_) if sold_within(executor, mint, wait).await => return result,
+            Ok(signature) => logger.log(format!("[SL ESCALATION] => {} step {} sell {} has not landed", mint, i + 1, signature).red().to_string()),
+            // an earlier sell may have landed late and taken the balance
+            Err(_) if position_closed(executor, mint).await => return Ok(String::new()),
+            Err(e) => logger.log(format!("[SL ESCALATION] => {} step {} failed: {}", mint, i + 1, e).red().to_string()),
+        }
+    }
+
+    let message = format!("Stop loss of {} did not land after {} escalation steps, the position is still open", mint, count);
+    logger.error(message.clone());
+    alert(Alert::stop_loss(mint, &message));
+    Err(anyhow!(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_escalation_steps() {
+        let steps = EscalationStep::parse_list(DEFAULT_STEPS).unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[2], EscalationStep { slippage_bps: 5000, fee_multiplier: 4.0, route: Some("rpc") });
+    }
+
+    #[test]
+    fn test_last_step_sells_at_any_price() {
+        let steps = EscalationStep::parse_list(DEFAULT_STEPS).unwrap();
+        assert_eq!(steps[3].route, None);
+        assert!(steps[3].describe().contains("at any price"));
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_steps() {
+        // slippage over 100%
+        assert!(EscalationStep::parse("20000:2").is_err());
+        // a fee multiplier below 1 would lower the fee
+        assert!(EscalationStep::parse("500:0.5").is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_route() {
+        assert!(EscalationStep::parse("500:2:carrier-pigeon").is_err());
+    }
+
+    #[test]
+    fn test_rejects_missing_fee_multiplier() {
+        assert!(EscalationStep::parse("500").is_err());
+    }
+}
@@ -0,0 +1,653 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use futures_util::{SinkExt, StreamExt};
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeRequest, SubscribeUpdate};
+
+use crate::common::config::{Config, LiquidityPool, Status};
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{trade_events, BondingCurveReserves, TradeEvent};
+use crate::dex::pump_swap::decoder::swap_trades;
+use crate::engine::confidence::{ConfidenceScorer, ConfidenceSettings};
+use crate::engine::copy_trade::{copy_source, mint_safety_rejection, CopyFilterMode, CopyFilterSettings, CopyTradeStrategy};
+use crate::engine::execution_queue::{submit, Priority};
+use crate::engine::filter_stats::{record_rejection, record_rejection_as};
+use crate::engine::inverse_buy::InverseBuyStrategy;
+use crate::engine::koth::{KothSettings, KothStrategy};
+use crate::engine::momentum::{MomentumSettings, MomentumStrategy};
+use crate::engine::observer::{observe_launch, observe_price, observer, observing, ObservedDecision};
+use crate::engine::slippage::volatility;
+use crate::engine::slot_sync::slot_sync;
+use crate::engine::snipe::{SnipeSettings, SnipeStrategy};
+use crate::engine::timer::trading_window_open;
+use crate::engine::token_rules::{remember_position_rule, rule_for};
+use crate::engine::trade_executor::{price_from_reserves, TradeExecutor};
+use crate::services::alerts::{alert, Alert};
+use crate::services::backpressure::{buffered, BackpressureSettings, EventClass};
+use crate::services::dedup::{DedupSettings, SignatureCache};
+use crate::services::geyser::{wait_to_reconnect, GeyserSource, GeyserSubscription};
+use crate::services::health::stream_health;
+use crate::services::market_cap::{fetch_market_cap, MarketCapUnit};
+use crate::services::market_data::market_data;
+use crate::services::metrics::metrics;
+use crate::services::subscription::{ping_request, SubscriptionBuilder};
+use crate::services::telegram::TokenInfo;
+
+/// Something a strategy wants the executor to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrategyAction {
+    Buy {
+        mint: String,
+        /// Fixed amount; `None` lets the position sizer decide
+        sol_amount: Option<f64>,
+        /// Signal confidence (0.0-1.0), gated on `MIN_BUY_CONFIDENCE` and used for sizing
+        confidence: Option<f64>,
+        reason: String,
+    },
+    Sell {
+        mint: String,
+        percent: f64,
+        reason: String,
+    },
+}
+
+impl StrategyAction {
+    pub fn mint(&self) -> &str {
+        match self {
+            StrategyAction::Buy { mint, .. } | StrategyAction::Sell { mint, .. } => mint,
+        }
+    }
+}
+
+/// An action with the name of the strategy that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedAction {
+    pub strategy: &'static str,
+    pub action: StrategyAction,
+}
+
+/// Price update for a held position
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionUpdate {
+    pub mint: String,
+    pub entry_price: f64,
+    pub price: f64,
+    pub opened_at: Option<Instant>,
+}
+
+impl PositionUpdate {
+    /// Gain over entry, in percent
+    pub fn pnl_percent(&self) -> f64 {
+        if self.entry_price <= 0.0 {
+            return 0.0;
+        }
+        (self.price / self.entry_price - 1.0) * 100.0
+    }
+}
+
+/// An entry or exit strategy plugged into the engine
+///
+/// Hooks are synchronous and return the actions to take; the runner executes them
+/// through the `TradeExecutor`, so every strategy shares the same budget, limits and
+/// circuit breaker. All hooks default to doing nothing.
+pub trait Strategy: Send {
+    /// Short name used in logs and attribution
+    fn name(&self) -> &'static str;
+
+    /// A new token passed the launch filters
+    fn on_new_token(&mut self, _token: &TokenInfo) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    /// A pump.fun buy or sell was seen on the stream
+    fn on_trade_event(&mut self, _event: &TradeEvent, _at: Instant) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    /// Called about once a second
+    fn on_tick(&mut self, _now: Instant) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    /// A held position was re-priced
+    fn on_position_update(&mut self, _update: &PositionUpdate) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    /// Wallets or mints whose pump.fun trades `on_trade_event` needs
+    ///
+    /// `None` (the default) asks for every pump.fun trade; narrower answers let the
+    /// stream filter on the server.
+    fn stream_accounts(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// Enabled strategies, dispatched in registration order
+#[derive(Default)]
+pub struct StrategyRegistry {
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in strategies enabled in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        let mut registry = Self::new();
+        if SnipeSettings::from_env().enabled {
+            registry.register(Box::new(SnipeStrategy::new(ConfidenceScorer::new(ConfidenceSettings::from_env()))));
+        }
+        if config.copy_trading.enabled {
+            registry.register(Box::new(CopyTradeStrategy::new(&config.copy_trading)));
+        }
+        if config.inverse_buy.enabled {
+            registry.register(Box::new(InverseBuyStrategy::new(&config.inverse_buy)));
+        }
+        let momentum = MomentumSettings::from_env();
+        if momentum.enabled {
+            registry.register(Box::new(MomentumStrategy::new(momentum)));
+        }
+        let koth = KothSettings::from_env();
+        if koth.enabled {
+            registry.register(Box::new(KothStrategy::new(koth)));
+        }
+        registry
+    }
+
+    /// Add a strategy; custom strategies are registered the same way as the built-ins
+    pub fn register(&mut self, strategy: Box<dyn Strategy>) {
+        self.strategies.push(strategy);
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.strategies.iter().map(|strategy| strategy.name()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+
+    /// Accounts every strategy's trades can be narrowed to, `None` if one needs the whole program
+    pub fn stream_accounts(&self) -> Option<Vec<String>> {
+        let mut accounts = Vec::new();
+        for strategy in &self.strategies {
+            accounts.extend(strategy.stream_accounts()?);
+        }
+        accounts.sort();
+        accounts.dedup();
+        Some(accounts)
+    }
+
+    pub fn on_new_token(&mut self, token: &TokenInfo) -> Vec<TaggedAction> {
+        self.dispatch(|strategy| strategy.on_new_token(token))
+    }
+
+    pub fn on_trade_event(&mut self, event: &TradeEvent, at: Instant) -> Vec<TaggedAction> {
+        self.dispatch(|strategy| strategy.on_trade_event(event, at))
+    }
+
+    pub fn on_tick(&mut self, now: Instant) -> Vec<TaggedAction> {
+        self.dispatch(|strategy| strategy.on_tick(now))
+    }
+
+    pub fn on_position_update(&mut self, update: &PositionUpdate) -> Vec<TaggedAction> {
+        self.dispatch(|strategy| strategy.on_position_update(update))
+    }
+
+    fn dispatch(&mut self, mut hook: impl FnMut(&mut dyn Strategy) -> Vec<StrategyAction>) -> Vec<TaggedAction> {
+        let mut actions = Vec::new();
+        for strategy in self.strategies.iter_mut() {
+            let name = strategy.name();
+            actions.extend(hook(strategy.as_mut()).into_iter().map(|action| TaggedAction { strategy: name, action }));
+        }
+        actions
+    }
+}
+
+/// Check a graduated token copied from a target against the Birdeye/DexScreener filters
+///
+/// Tokens still on their curve and lookups while the filter is off always pass.
+async fn check_market_data(executor: &TradeExecutor, mint: &str) -> Result<(), String> {
+    let Some(filters) = Config::current().map(|config| config.advanced_filters.clone()) else {
+        return Ok(());
+    };
+    if !filters.market_data_enabled || !executor.is_graduated(mint).await.unwrap_or(false) {
+        return Ok(());
+    }
+    let provider = market_data().provider().name();
+    match market_data().get(mint).await {
+        Ok(Some(data)) => data.check(&filters),
+        Ok(None) => Err(format!("not listed on {}", provider)),
+        Err(e) => Err(format!("{} lookup failed: {}", provider, e)),
+    }
+}
+
+/// Check a copied buy's USD market cap is at most `MC_THRESHOLD_TO_BUY`
+///
+/// Read from the token's curve or pool and priced with the cached SOL/USD feed.
+async fn check_copy_market_cap(executor: &TradeExecutor, mint: &str) -> Result<(), (ReasonCode, String)> {
+    let Some(copy_trading) = Config::current().map(|config| config.copy_trading.clone()) else {
+        return Ok(());
+    };
+    let usd = fetch_market_cap(executor, mint).await.ok().and_then(|market_cap| market_cap.usd);
+    match MarketCapUnit::Usd.rejection(usd, 0.0, copy_trading.mc_threshold_to_buy) {
+        Some(rejection) => Err(rejection),
+        None => Ok(()),
+    }
+}
+
+/// Check a copied buy against the filter mode of the target it came from
+///
+/// Returns the rejecting filter, its reason code and the reason.
+async fn check_copy_buy(executor: &TradeExecutor, mint: &str) -> Result<(), (&'static str, ReasonCode, String)> {
+    let mode = CopyFilterSettings::from_env().mode_for(copy_source(mint).as_deref());
+    if mode == CopyFilterMode::None {
+        return Ok(());
+    }
+    let unsafe_mint = |reason: String| ("safety", ReasonCode::FilterMintSafety, reason);
+    let pubkey = mint.parse::<Pubkey>().map_err(|e| unsafe_mint(format!("invalid mint: {}", e)))?;
+    let account = executor
+        .rpc_client()
+        .get_account(&pubkey)
+        .await
+        .map_err(|e| unsafe_mint(format!("mint lookup failed: {}", e)))?;
+    if let Some(reason) = mint_safety_rejection(&account.owner, &account.data) {
+        return Err(unsafe_mint(reason));
+    }
+    if mode == CopyFilterMode::Full {
+        check_copy_market_cap(executor, mint).await.map_err(|(code, reason)| ("market_cap", code, reason))?;
+        check_market_data(executor, mint).await.map_err(|reason| ("market_data", ReasonCode::FilterMarketData, reason))?;
+    }
+    Ok(())
+}
+
+/// Execute a strategy action through the executor
+pub async fn execute_action(executor: Arc<TradeExecutor>, tagged: TaggedAction, logger: Logger) {
+    match tagged.action {
+        StrategyAction::Buy { mint, sol_amount, confidence, reason } => {
+            let scorer = ConfidenceScorer::new(ConfidenceSettings::from_env());
+            if let Some(confidence) = confidence {
+                if scorer.settings().enabled && confidence < scorer.settings().min_buy_confidence {
+                    logger.log(format!(
+                        "[{}] => Skipping buy of {}: confidence {:.2} below {:.2}",
+                        tagged.strategy, mint, confidence, scorer.settings().min_buy_confidence
+                    ).yellow().to_string());
+                    record_decision(Decision::Skip, &mint, ReasonCode::FilterConfidence, &format!("{}: confidence {:.2}", tagged.strategy, confidence));
+                    slot_sync().discard(&mint);
+                    observe_skip(&executor, &mint, Some(confidence), format!("confidence {:.2}", confidence)).await;
+                    return;
+                }
+            }
+
+            // tokens picked up from other traders or channels skip the launch filters
+            let checked = match tagged.strategy {
+                "copy" => check_copy_buy(&executor, &mint).await,
+                "channel" => check_market_data(&executor, &mint).await.map_err(|reason| ("market_data", ReasonCode::FilterMarketData, reason)),
+                _ => Ok(()),
+            };
+            if let Err((filter, code, reason)) = checked {
+                logger.log(format!(
+                    "[{}] => Skipping buy of {}: {}",
+                    tagged.strategy, mint, reason
+                ).yellow().to_string());
+                record_rejection_as(filter, &mint, code);
+                alert(Alert::filter(&mint, filter, &reason));
+                slot_sync().discard(&mint);
+                observe_skip(&executor, &mint, confidence, reason).await;
+                return;
+            }
+
+            // per-token rules come after the generic filters
+            let rule = rule_for(&executor, &mint).await;
+            if let Some(rule) = rule.as_ref().filter(|rule| rule.skip) {
+                logger.log(format!(
+                    "[{}] => Skipping buy of {}: rule '{}'",
+                    tagged.strategy, mint, rule.name.as_deref().unwrap_or("skip")
+                ).yellow().to_string());
+                record_rejection("token_rule", &mint);
+                alert(Alert::filter(&mint, "token_rule", &format!("rule '{}'", rule.name.as_deref().unwrap_or("skip"))));
+                slot_sync().discard(&mint);
+                observe_skip(&executor, &mint, confidence, "token rule".to_string()).await;
+                return;
+            }
+
+            // in observer mode the buy that passed every filter is only recorded
+            if observing() {
+                slot_sync().discard(&mint);
+                match executor.current_price(&mint).await {
+                    Ok(price) => observe_launch(&mint, price, confidence.unwrap_or(0.0), ObservedDecision::WouldBuy),
+                    Err(e) => logger.debug(format!("[{}] => No price to observe {} at: {}", tagged.strategy, mint, e)),
+                }
+                return;
+            }
+
+            logger.log(format!("[{}] => Buying {}: {}", tagged.strategy, mint, reason).magenta().bold().to_string());
+            let amount = match sol_amount {
+                Some(amount) => amount,
+                None => match executor.size_buy(confidence, None).await {
+                    Ok(amount) => amount,
+                    Err(e) => {
+                        logger.error(format!("[{}] => Failed to size buy of {}: {}", tagged.strategy, mint, e));
+                        slot_sync().discard(&mint);
+                        return;
+                    }
+                },
+            };
+            let amount = match rule.as_ref() {
+                Some(rule) => {
+                    let adjusted = rule.buy_amount(amount);
+                    logger.log(format!("[{}] => Rule for {} sizes the buy {:.4} -> {:.4} SOL", tagged.strategy, mint, amount, adjusted));
+                    adjusted
+                }
+                None => amount,
+            };
+            match executor.buy_as(tagged.strategy, &mint, amount).await {
+                Ok(_) => {
+                    if let Some(rule) = rule {
+                        remember_position_rule(&mint, rule);
+                    }
+                }
+                Err(e) => {
+                    slot_sync().discard(&mint);
+                    logger.debug(format!("[{}] => Buy of {} not sent: {}", tagged.strategy, mint, e));
+                }
+            }
+        }
+        StrategyAction::Sell { mint, percent, reason } => {
+            if observing() {
+                logger.debug(format!("[{}] => Observer mode, not selling {}: {}", tagged.strategy, mint, reason));
+                return;
+            }
+            logger.log(format!(
+                "[{}] => Selling {:.1}% of {}: {}",
+                tagged.strategy, percent, mint, reason
+            ).yellow().bold().to_string());
+            let code = if tagged.strategy == "copy" { ReasonCode::CopySourceSold } else { ReasonCode::StrategyExit };
+            record_decision(Decision::Sell, &mint, code, &format!("{}: {}", tagged.strategy, reason));
+            if let Err(e) = executor.sell(&mint, percent).await {
+                logger.error(format!("[{}] => Sell of {} failed: {}", tagged.strategy, mint, e));
+            }
+        }
+    }
+}
+
+/// Record a strategy buy the filters rejected, priced now, when observing
+async fn observe_skip(executor: &TradeExecutor, mint: &str, confidence: Option<f64>, reason: String) {
+    if !observing() {
+        return;
+    }
+    if let Ok(price) = executor.current_price(mint).await {
+        observe_launch(mint, price, confidence.unwrap_or(0.0), ObservedDecision::Skip(reason));
+    }
+}
+
+/// Queue strategy actions for execution, buys at `buy_priority` and sells ahead of plain buys
+///
+/// Buys outside the trading schedule are dropped here instead of queuing.
+pub(crate) fn spawn_actions(executor: &Arc<TradeExecutor>, actions: Vec<TaggedAction>, buy_priority: Priority, logger: &Logger) {
+    let window_open = trading_window_open();
+    for action in actions {
+        let (priority, label) = match action.action {
+            StrategyAction::Buy { ref mint, .. } if !window_open => {
+                logger.debug(format!("[{}] => Buy of {} dropped outside the trading schedule", action.strategy, mint));
+                continue;
+            }
+            StrategyAction::Buy { .. } => (buy_priority, "strategy buy"),
+            StrategyAction::Sell { .. } => (Priority::Sell, "strategy sell"),
+        };
+        submit(priority, label, execute_action(executor.clone(), action, logger.clone()));
+    }
+}
+
+/// Split off the copies of same-block targets' buys, which skip ahead of the buy queue
+///
+/// A copy only goes ahead when the target's trade in `slot` is still near the stream tip.
+fn same_block_copies(actions: Vec<TaggedAction>, events: &[TradeEvent], slot: u64) -> (Vec<TaggedAction>, Vec<TaggedAction>) {
+    let tip = stream_health().last_slot().max(slot);
+    let mut sync = slot_sync();
+    actions.into_iter().partition(|tagged| match &tagged.action {
+        StrategyAction::Buy { mint, .. } if tagged.strategy == "copy" => {
+            let by_target = events
+                .iter()
+                .any(|event| event.is_buy && event.mint.to_string() == *mint && sync.is_target(&event.user.to_string()));
+            by_target && sync.target_trade(mint, slot, tip)
+        }
+        _ => false,
+    })
+}
+
+/// Position updates for held tokens, priced from the latest trade seen on the stream
+fn position_updates(held: &[LiquidityPool], last_prices: &HashMap<String, f64>) -> Vec<PositionUpdate> {
+    held.iter()
+        .filter(|pool| pool.status == Status::Bought)
+        .filter_map(|pool| last_prices.get(&pool.mint).map(|price| PositionUpdate {
+            mint: pool.mint.clone(),
+            entry_price: pool.buy_price,
+            price: *price,
+            opened_at: pool.timestamp,
+        }))
+        .collect()
+}
+
+/// Filter of the whole pump.fun program, the only one whose trades may be shed under load
+const PROGRAM_WIDE_FILTER: &str = "strategies_all";
+
+/// pump.fun trades the strategies and held positions need
+///
+/// When every strategy names its accounts, only their pump.fun and PumpSwap trades
+/// and those of held mints are streamed instead of the whole program. Held mints keep
+/// their own filters either way, which marks their trades as critical.
+fn subscribe_request(accounts: Option<&[String]>, held: &[String]) -> SubscribeRequest {
+    let program = program_ids().pump_fun_program;
+    // migrated tokens and PumpSwap trades of target wallets come from the AMM
+    let swap_program = program_ids().pump_swap_program;
+    let mut builder = SubscriptionBuilder::new();
+    match accounts {
+        None => builder = builder.program_transactions(PROGRAM_WIDE_FILTER, &program),
+        Some(accounts) if !accounts.is_empty() => {
+            builder = builder
+                .transactions("strategies", accounts, &[program])
+                .transactions("strategies_swap", accounts, &[swap_program]);
+        }
+        Some(_) => {}
+    }
+    if !held.is_empty() {
+        builder = builder
+            .transactions("positions", held, &[program])
+            .transactions("positions_swap", held, &[swap_program]);
+    }
+    builder.build()
+}
+
+/// Trades matched only by the program-wide filter may be dropped when the strategies fall behind
+fn classify(update: &SubscribeUpdate) -> EventClass {
+    match update.update_oneof {
+        Some(UpdateOneof::Transaction(_)) if update.filters.iter().all(|filter| filter == PROGRAM_WIDE_FILTER) => EventClass::Bulk,
+        _ => EventClass::Critical,
+    }
+}
+
+/// Stream pump.fun and PumpSwap trades into the registry until the connection drops
+async fn run_stream(
+    executor: &Arc<TradeExecutor>,
+    registry: &Arc<Mutex<StrategyRegistry>>,
+    source: &dyn GeyserSource,
+    seen: &mut SignatureCache,
+    logger: &Logger,
+) -> Result<()> {
+    let GeyserSubscription { sink: mut subscribe_tx, stream } = source.subscribe().await?;
+    let mut stream = buffered("strategies", stream, &BackpressureSettings::from_env(), classify);
+
+    let mut subscribed: Option<(Option<Vec<String>>, Vec<String>)> = None;
+    let mut last_prices: HashMap<String, f64> = HashMap::new();
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {
+                volatility().prune(Instant::now());
+                let held: Vec<LiquidityPool> = executor.positions().snapshot();
+                last_prices.retain(|mint, _| held.iter().any(|pool| pool.mint == *mint));
+
+                // a new request replaces the previous filters on the same stream
+                let accounts = match registry.lock() {
+                    Ok(registry) => registry.stream_accounts().map(|accounts| source.limit_accounts(&accounts)),
+                    Err(_) => continue,
+                };
+                let held_mints = {
+                    let mut mints: Vec<String> = held.iter().map(|pool| pool.mint.clone()).collect();
+                    // launches the observer follows are priced from their trades like positions
+                    if let Some(observer) = observer() {
+                        mints.extend(observer.lock().unwrap_or_else(|e| e.into_inner()).followed());
+                    }
+                    mints.sort();
+                    mints.dedup();
+                    source.limit_accounts(&mints)
+                };
+                let wanted = (accounts, held_mints);
+                if subscribed.as_ref() != Some(&wanted) {
+                    subscribe_tx.send(subscribe_request(wanted.0.as_deref(), &wanted.1)).await?;
+                    subscribed = Some(wanted);
+                }
+                let actions = match registry.lock() {
+                    Ok(mut registry) => {
+                        let mut actions = registry.on_tick(Instant::now());
+                        for update in position_updates(&held, &last_prices) {
+                            actions.extend(registry.on_position_update(&update));
+                        }
+                        actions
+                    }
+                    Err(_) => continue,
+                };
+                spawn_actions(executor, actions, Priority::Buy, logger);
+            }
+            message = stream.next() => {
+                let update = match message {
+                    Some(Ok(update)) => update,
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(anyhow!("Stream closed")),
+                };
+                match update.update_oneof {
+                    Some(UpdateOneof::Transaction(tx)) => {
+                        let Some(info) = tx.transaction else {
+                            continue;
+                        };
+                        // a transaction seen twice must not trigger the strategies twice
+                        if !seen.first_seen(&info.signature, Instant::now()) {
+                            continue;
+                        }
+                        let Some(meta) = info.meta.as_ref() else {
+                            continue;
+                        };
+                        let mut events: Vec<TradeEvent> = trade_events(&meta.log_messages).collect();
+                        if events.is_empty() {
+                            events = swap_trades(&info);
+                        }
+                        if events.is_empty() {
+                            continue;
+                        }
+                        metrics().events_processed.inc_by(events.len() as u64);
+                        let now = Instant::now();
+                        for event in &events {
+                            let mint = event.mint.to_string();
+                            let price = price_from_reserves(&BondingCurveReserves {
+                                virtual_token_reserves: event.virtual_token_reserves,
+                                virtual_sol_reserves: event.virtual_sol_reserves,
+                            });
+                            volatility().record(&mint, price, now);
+                            observe_price(&mint, price);
+                            if executor.has_position(&mint) {
+                                last_prices.insert(mint, price);
+                            }
+                        }
+                        let actions = match registry.lock() {
+                            Ok(mut registry) => events
+                                .iter()
+                                .flat_map(|event| registry.on_trade_event(event, now))
+                                .collect(),
+                            Err(_) => continue,
+                        };
+                        let (same_block, actions) = same_block_copies(actions, &events, tx.slot);
+                        spawn_actions(executor, same_block, Priority::LaunchBuy, logger);
+                        spawn_actions(executor, actions, Priority::Buy, logger);
+                    }
+                    Some(UpdateOneof::Ping(_)) => {
+                        subscribe_tx.send(ping_request()).await?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Start running the registered strategies on the pump.fun trade stream
+///
+/// The returned registry is shared with the launch pipeline, which feeds it
+/// tokens that passed the filters through `on_new_token`.
+pub async fn start_strategy_system(
+    executor: Arc<TradeExecutor>,
+    registry: StrategyRegistry,
+    source: Arc<dyn GeyserSource>,
+    logger: Logger,
+) -> Arc<Mutex<StrategyRegistry>> {
+    logger.log(format!("Running strategies: {}", registry.names().join(", ")));
+    let registry = Arc::new(Mutex::new(registry));
+
+    let registry_clone = registry.clone();
+    tokio::spawn(async move {
+        // kept across reconnects, which can replay transactions already handled
+        let mut seen = SignatureCache::new(DedupSettings::from_env());
+        loop {
+            let result = run_stream(&executor, &registry_clone, source.as_ref(), &mut seen, &logger).await;
+            wait_to_reconnect(source.as_ref(), "Strategy", result, &logger).await;
+        }
+    });
+
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Strategy for Echo {
+        fn name(&self) -> &'static str {
+            "echo"
+        }
+
+        fn on_position_update(&mut self, update: &PositionUpdate) -> Vec<StrategyAction> {
+            vec![StrategyAction::Sell {
+                mint: update.mint.clone(),
+                percent: 100.0,
+                reason: format!("{:.0}%", update.pnl_percent()),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatch_tags_actions() {
+        let mut registry = StrategyRegistry::new();
+        assert!(registry.is_empty());
+        registry.register(Box::new(Echo));
+        assert_eq!(registry.names(), vec!["echo"]);
+        assert!(registry.on_tick(Instant::now()).is_empty());
+
+        let update = PositionUpdate { mint: "mint".to_string(), entry_price: 1.0, price: 1.5, opened_at: None };
+        let actions = registry.on_position_update(&update);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].strategy, "echo");
+        assert_eq!(actions[0].action.mint(), "mint");
+        assert_eq!(actions[0].action, StrategyAction::Sell { mint: "mint".to_string(), percent: 100.0, reason: "50%".to_string() });
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// Strategy name used for buys that did not come from a strategy (API, Telegram)
+pub const MANUAL_STRATEGY: &str = "manual";
+
+/// Parse budgets like `snipe:2,copy:5` into SOL per strategy
+pub fn parse_strategy_budgets(value: &str) -> Result<HashMap<String, f64>, String> {
+    let mut budgets = HashMap::new();
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (strategy, budget) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid strategy budget '{}', expected strategy:sol", pair))?;
+        let budget = budget.trim().parse::<f64>().map_err(|_| format!("Invalid budget in '{}'", pair))?;
+        if budget < 0.0 {
+            return Err(format!("Budget in '{}' must not be negative", pair));
+        }
+        budgets.insert(strategy.trim().to_string(), budget);
+    }
+    Ok(budgets)
+}
+
+/// Per-strategy budget settings
+#[derive(Debug, Clone, Default)]
+pub struct StrategyLedgerSettings {
+    /// SOL each strategy may spend on buys (including fees) per rolling 24h; missing means no cap
+    pub budgets: HashMap<String, f64>,
+}
+
+impl StrategyLedgerSettings {
+    /// Load per-strategy budgets from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            budgets: parse_strategy_budgets(&std::env::var("STRATEGY_BUDGETS").unwrap_or_default())
+                .unwrap_or_else(|e| {
+                    eprintln!("Ignoring STRATEGY_BUDGETS: {}", e);
+                    HashMap::new()
+                }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StrategySpend {
+    at: DateTime<Utc>,
+    mint: String,
+    amount_sol: f64,
+    fees_sol: f64,
+}
+
+/// Spending and realized PnL of one strategy
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StrategyStats {
+    pub strategy: String,
+    /// Cap per rolling 24h, if one is set
+    pub budget_sol: Option<f64>,
+    /// SOL spent in the last 24h, fees included
+    pub spent_sol: f64,
+    pub buys: usize,
+    /// Realized PnL since startup, before fees and tips
+    pub realized_pnl_sol: f64,
+    /// Priority and network fees of landed transactions since startup
+    pub fees_sol: f64,
+    pub tips_sol: f64,
+    /// Realized PnL after fees and tips
+    pub net_pnl_sol: f64,
+    pub wins: usize,
+    pub losses: usize,
+}
+
+/// Attributes spending and realized PnL to the strategy that opened each position
+pub struct StrategyLedger {
+    settings: StrategyLedgerSettings,
+    spends: HashMap<String, VecDeque<StrategySpend>>,
+    /// Strategy that opened each held mint
+    owners: HashMap<String, String>,
+    /// Realized PnL of each open position so far, to count it as a win or loss on close
+    position_pnl: HashMap<String, f64>,
+    stats: BTreeMap<String, StrategyStats>,
+}
+
+impl StrategyLedger {
+    /// Create a new strategy ledger
+    pub fn new(settings: StrategyLedgerSettings) -> Self {
+        Self {
+            settings,
+            spends: HashMap::new(),
+            owners: HashMap::new(),
+            position_pnl: HashMap::new(),
+            stats: BTreeMap::new(),
+        }
+    }
+
+    /// Count a buy against the strategy's budget, failing if it would exceed it
+    pub fn reserve(&mut self, strategy: &str, mint: &str, amount_sol: f64, fees_sol: f64) -> Result<()> {
+        self.reserve_at(Utc::now(), strategy, mint, amount_sol, fees_sol)
+    }
+
+    pub fn reserve_at(&mut self, now: DateTime<Utc>, strategy: &str, mint: &str, amount_sol: f64, fees_sol: f64) -> Result<()> {
+        let spent = self.spent_at(now, strategy);
+        if let Some(budget) = self.settings.budgets.get(strategy) {
+            if spent + amount_sol + fees_sol > *budget {
+                return Err(anyhow!(
+                    "Budget of strategy {} reached: {:.4}/{:.4} SOL spent, buy needs {:.4} SOL",
+                    strategy, spent, budget, amount_sol + fees_sol
+                ));
+            }
+        }
+        self.spends.entry(strategy.to_string()).or_default().push_back(StrategySpend {
+            at: now,
+            mint: mint.to_string(),
+            amount_sol,
+            fees_sol,
+        });
+        self.owners.insert(mint.to_string(), strategy.to_string());
+        self.stats_mut(strategy).buys += 1;
+        Ok(())
+    }
+
+    /// Give back the reservation of a buy that never landed
+    pub fn release(&mut self, mint: &str) {
+        let Some(strategy) = self.owners.remove(mint) else {
+            return;
+        };
+        if let Some(spends) = self.spends.get_mut(&strategy) {
+            if let Some(index) = spends.iter().rposition(|spend| spend.mint == mint) {
+                spends.remove(index);
+            }
+        }
+        let stats = self.stats_mut(&strategy);
+        stats.buys = stats.buys.saturating_sub(1);
+    }
+
+    /// Keep only the fees of a buy that landed but failed on chain
+    pub fn refund_amount(&mut self, mint: &str) {
+        let Some(strategy) = self.owners.remove(mint) else {
+            return;
+        };
+        if let Some(spend) = self.spends.get_mut(&strategy).and_then(|spends| spends.iter_mut().rev().find(|spend| spend.mint == mint)) {
+            spend.amount_sol = 0.0;
+        }
+    }
+
+    /// Attribute the PnL of a confirmed sell to the strategy that bought the token
+    pub fn record_pnl(&mut self, mint: &str, pnl_sol: f64, closed: bool) {
+        let strategy = if closed {
+            self.owners.remove(mint)
+        } else {
+            self.owners.get(mint).cloned()
+        }
+        .unwrap_or_else(|| MANUAL_STRATEGY.to_string());
+
+        *self.position_pnl.entry(mint.to_string()).or_default() += pnl_sol;
+        let position_pnl = if closed { self.position_pnl.remove(mint) } else { None };
+
+        let stats = self.stats_mut(&strategy);
+        stats.realized_pnl_sol += pnl_sol;
+        match position_pnl {
+            Some(pnl) if pnl >= 0.0 => stats.wins += 1,
+            Some(_) => stats.losses += 1,
+            None => {}
+        }
+    }
+
+    /// Charge the fee and tip of a landed transaction to the strategy holding `mint`
+    pub fn record_costs(&mut self, mint: &str, fee_sol: f64, tip_sol: f64) {
+        let strategy = self.owners.get(mint).cloned().unwrap_or_else(|| MANUAL_STRATEGY.to_string());
+        let stats = self.stats_mut(&strategy);
+        stats.fees_sol += fee_sol;
+        stats.tips_sol += tip_sol;
+    }
+
+    /// Strategy that opened `mint`, if it is still held
+    pub fn owner(&self, mint: &str) -> Option<&str> {
+        self.owners.get(mint).map(String::as_str)
+    }
+
+    /// Stats for every strategy that traded or has a budget
+    pub fn report(&mut self) -> Vec<StrategyStats> {
+        self.report_at(Utc::now())
+    }
+
+    pub fn report_at(&mut self, now: DateTime<Utc>) -> Vec<StrategyStats> {
+        let budgeted: Vec<String> = self.settings.budgets.keys().cloned().collect();
+        for strategy in budgeted {
+            self.stats_mut(&strategy);
+        }
+        let names: Vec<String> = self.stats.keys().cloned().collect();
+        names
+            .into_iter()
+            .map(|strategy| {
+                let spent_sol = self.spent_at(now, &strategy);
+                let stats = &self.stats[&strategy];
+                StrategyStats {
+                    budget_sol: self.settings.budgets.get(&strategy).copied(),
+                    spent_sol,
+                    net_pnl_sol: stats.realized_pnl_sol - stats.fees_sol - stats.tips_sol,
+                    ..stats.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Telegram-friendly summary, one line per strategy
+    pub fn summary(&mut self) -> String {
+        let report = self.report();
+        if report.is_empty() {
+            return "no strategy trades yet".to_string();
+        }
+        report
+            .iter()
+            .map(|stats| {
+                let budget = stats.budget_sol
+                    .map(|budget| format!("{:.2}/{:.2} SOL", stats.spent_sol, budget))
+                    .unwrap_or_else(|| format!("{:.2} SOL", stats.spent_sol));
+                format!(
+                    "{}: {:+.4} SOL gross, {:+.4} SOL net after {:.4} SOL fees and tips ({}W/{}L), spent {}",
+                    stats.strategy,
+                    stats.realized_pnl_sol,
+                    stats.net_pnl_sol,
+                    stats.fees_sol + stats.tips_sol,
+                    stats.wins,
+                    stats.losses,
+                    budget
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn spent_at(&mut self, now: DateTime<Utc>, strategy: &str) -> f64 {
+        let Some(spends) = self.spends.get_mut(strategy) else {
+            return 0.0;
+        };
+        let cutoff = now - Duration::hours(24);
+        while spends.front().map(|spend| spend.at <= cutoff).unwrap_or(false) {
+            spends.pop_front();
+        }
+        spends.iter().map(|spend| spend.amount_sol + spend.fees_sol).sum()
+    }
+
+    fn stats_mut(&mut self, strategy: &str) -> &mut StrategyStats {
+        self.stats.entry(strategy.to_string()).or_insert_with(|| StrategyStats {
+            strategy: strategy.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+static STRATEGY_LEDGER: LazyLock<Mutex<StrategyLedger>> =
+    LazyLock::new(|| Mutex::new(StrategyLedger::new(StrategyLedgerSettings::from_env())));
+
+/// Shared ledger used by the executor, confirmation tracker and status output
+pub fn strategy_ledger() -> MutexGuard<'static, StrategyLedger> {
+    STRATEGY_LEDGER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ledger() -> StrategyLedger {
+        StrategyLedger::new(StrategyLedgerSettings {
+            budgets: parse_strategy_budgets("snipe:1, copy:5").unwrap(),
+        })
+    }
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn stats<'a>(report: &'a [StrategyStats], strategy: &str) -> &'a StrategyStats {
+        report.iter().find(|stats| stats.strategy == strategy).unwrap()
+    }
+
+    #[test]
+    fn test_independent_caps() {
+        let mut ledger = ledger();
+        assert!(ledger.reserve_at(noon(), "snipe", "a", 0.9, 0.01).is_ok());
+        assert!(ledger.reserve_at(noon(), "snipe", "b", 0.2, 0.0).is_err());
+        assert!(ledger.reserve_at(noon(), "copy", "b", 2.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_strategy_without_budget_is_uncapped() {
+        let mut ledger = ledger();
+        assert!(ledger.reserve_at(noon(), "momentum", "c", 50.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_release_and_rolling_window_free_the_cap() {
+        let mut ledger = ledger();
+        ledger.reserve_at(noon(), "snipe", "a", 0.9, 0.01).unwrap();
+        ledger.release("a");
+        assert!(ledger.reserve_at(noon(), "snipe", "b", 0.2, 0.0).is_ok());
+        assert!(ledger.reserve_at(noon() + Duration::hours(24), "snipe", "d", 1.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_parse_budgets_needs_an_amount() {
+        assert!(parse_strategy_budgets("snipe").is_err());
+    }
+
+    #[test]
+    fn test_pnl_attributed_to_buying_strategy() {
+        let mut ledger = ledger();
+        ledger.reserve_at(noon(), "copy", "a", 1.0, 0.0).unwrap();
+        ledger.reserve_at(noon(), "snipe", "b", 0.5, 0.0).unwrap();
+
+        ledger.record_pnl("a", 1.5, false);
+        ledger.record_pnl("a", 0.5, true);
+        ledger.record_pnl("b", -0.5, true);
+        // a full exit ends the ownership
+        assert_eq!(ledger.owner("a"), None);
+
+        let report = ledger.report_at(noon());
+        let (copy, snipe) = (stats(&report, "copy"), stats(&report, "snipe"));
+        assert!((copy.realized_pnl_sol - 2.0).abs() < 1e-9);
+        assert_eq!((copy.wins, copy.losses), (1, 0));
+        assert!((snipe.realized_pnl_sol + 0.5).abs() < 1e-9);
+        assert_eq!((snipe.wins, snipe.losses), (0, 1));
+        assert_eq!(snipe.budget_sol, Some(1.0));
+    }
+
+    #[test]
+    fn test_costs_reduce_net_pnl() {
+        let mut ledger = ledger();
+        ledger.reserve_at(noon(), "copy", "a", 1.0, 0.0).unwrap();
+        ledger.record_pnl("a", 2.0, true);
+        ledger.reserve_at(noon(), "copy", "c", 1.0, 0.0).unwrap();
+        ledger.record_costs("c", 0.001, 0.01);
+        let report = ledger.report_at(noon());
+        assert!((stats(&report, "copy").net_pnl_sol - (2.0 - 0.011)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_costs_of_unowned_mints_go_to_manual() {
+        let mut ledger = ledger();
+        ledger.record_costs("unknown", 0.001, 0.0);
+        let report = ledger.report_at(noon());
+        assert!(stats(&report, MANUAL_STRATEGY).fees_sol > 0.0);
+    }
+}
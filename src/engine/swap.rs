@@ -2,6 +2,10 @@ use clap::ValueEnum;
 use serde::Deserialize;
 use std::str::FromStr;
 use anyhow::{Error, Result};
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo;
+
+use crate::dex::pump_fun::{BondingCurveReserves, TradeEvent, TEN_THOUSAND};
 
 #[derive(ValueEnum, Debug, Clone, Deserialize, PartialEq)]
 pub enum SwapDirection {
@@ -27,6 +31,9 @@ pub enum SwapInType {
     /// Percentage
     #[serde(rename = "pct")]
     Pct,
+    /// Target output: tokens received on a buy, SOL proceeds on a sell
+    #[serde(rename = "out")]
+    ExactOut,
 }
 
 impl FromStr for SwapDirection {
@@ -48,7 +55,112 @@ impl FromStr for SwapInType {
         match s.to_lowercase().as_str() {
             "qty" => Ok(SwapInType::Qty),
             "pct" => Ok(SwapInType::Pct),
+            "out" | "exact_out" => Ok(SwapInType::ExactOut),
             _ => Err(anyhow::anyhow!("Invalid swap in type: {}", s)),
         }
     }
 }
+
+/// A venue a token trades on: the pump.fun curve, its PumpSwap pool or a Raydium LaunchLab curve
+///
+/// The executor loads the venue a mint currently trades on and builds every order through
+/// this trait, so supporting another exchange means implementing it, not adding branches.
+pub trait Dex: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Curve or pool account the swaps trade against
+    fn address(&self) -> Pubkey;
+
+    /// Effective constant-product reserves, for prices and price impact
+    fn reserves(&self) -> BondingCurveReserves;
+
+    /// Output of swapping `amount_in`: tokens for lamports on a buy, lamports for tokens on a sell
+    fn quote(&self, direction: SwapDirection, amount_in: u64) -> u64;
+
+    /// Least input whose quote reaches `amount_out`, `None` when the reserves cannot supply it
+    ///
+    /// Searched over `quote` itself, so each venue's fees and rounding are accounted for.
+    fn quote_in(&self, direction: SwapDirection, amount_out: u64) -> Option<u64> {
+        input_for(amount_out, |amount_in| self.quote(direction.clone(), amount_in))
+    }
+
+    /// The two amounts of the swap instruction `build_buy` makes, in instruction order
+    fn buy_args(&self, sol_in: u64, slippage_bps: u64) -> (u64, u64);
+
+    /// Instructions spending `sol_in` lamports within `slippage_bps`, and the tokens expected
+    fn build_buy(&self, user: &Pubkey, sol_in: u64, slippage_bps: u64) -> Result<(u64, Vec<Instruction>)>;
+
+    /// Instructions selling `token_amount` for at least `min_sol_out` lamports
+    fn build_sell(&self, user: &Pubkey, token_amount: u64, min_sol_out: u64) -> Result<Vec<Instruction>>;
+
+    /// Trades of this venue's token in a streamed transaction, in the shape of pump.fun trades
+    fn decode_event(&self, info: &SubscribeUpdateTransactionInfo) -> Vec<TradeEvent>;
+
+    /// Swaps wrap SOL and close the wallet's WSOL account when done
+    fn closes_wsol(&self) -> bool {
+        true
+    }
+
+    /// The token left its launch curve for an AMM pool
+    fn is_graduated(&self) -> bool {
+        false
+    }
+}
+
+/// `amount` less `slippage_bps`, the least a swap may return
+pub fn min_out(amount: u64, slippage_bps: u64) -> u64 {
+    amount - amount * slippage_bps.min(TEN_THOUSAND) / TEN_THOUSAND
+}
+
+/// `amount` plus `slippage_bps`, the most a swap may spend
+pub fn max_in(amount: u64, slippage_bps: u64) -> u64 {
+    amount + amount * slippage_bps / TEN_THOUSAND
+}
+
+/// Least `amount_in` for which `quote(amount_in) >= amount_out`, by bisection over a non-decreasing quote
+pub fn input_for(amount_out: u64, quote: impl Fn(u64) -> u64) -> Option<u64> {
+    if amount_out == 0 {
+        return Some(0);
+    }
+    if quote(u64::MAX) < amount_out {
+        return None;
+    }
+    let (mut low, mut high) = (0u64, u64::MAX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if quote(mid) >= amount_out {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Some(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_for_inverts_quotes() {
+        // 30 SOL against 1.073B tokens, as a fresh pump.fun curve
+        let (sol, tokens) = (30_000_000_000u128, 1_073_000_000_000_000u128);
+        let buy = |amount_in: u64| (amount_in as u128 * tokens / (sol + amount_in as u128)) as u64;
+        let sol_in = input_for(1_000_000_000_000, buy).unwrap();
+        assert!(buy(sol_in) >= 1_000_000_000_000);
+        assert!(buy(sol_in - 1) < 1_000_000_000_000);
+
+        // a 1% fee taken from the proceeds
+        let sell = |amount_in: u64| {
+            let gross = sol * amount_in as u128 / (tokens + amount_in as u128);
+            (gross - gross / 100) as u64
+        };
+        let tokens_in = input_for(100_000_000, sell).unwrap();
+        assert!(sell(tokens_in) >= 100_000_000 && sell(tokens_in - 1) < 100_000_000);
+
+        // the whole reserve can never be bought out
+        assert_eq!(input_for(tokens as u64, buy), None);
+        assert_eq!(input_for(0, buy), Some(0));
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use colored::Colorize;
+use tokio::time::Instant;
+
+use crate::common::config::TimerConfig;
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::position_manager::{sell, SellAmount};
+use crate::engine::session_report::{begin_session, post_session_report, SessionReportSettings};
+use crate::engine::trade_executor::TradeExecutor;
+
+/// Sell attempts per position after a window closes before it is left alone
+const MAX_EXIT_ATTEMPTS: u32 = 3;
+/// Longest the session report waits for exit sells to confirm
+const REPORT_SETTLE_TIME: Duration = Duration::from_secs(60);
+
+/// One trading window of a day; an `end` before `start` runs past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    fn parse(value: &str) -> Result<Self, String> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid window '{}', expected HH:MM-HH:MM", value))?;
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| format!("Invalid time '{}', expected HH:MM", value.trim()))
+        };
+        Ok(Self { start: time(start)?, end: time(end)? })
+    }
+
+    fn wraps(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value.trim().to_lowercase().as_str() {
+        "mon" => Ok(Weekday::Mon),
+        "tue" => Ok(Weekday::Tue),
+        "wed" => Ok(Weekday::Wed),
+        "thu" => Ok(Weekday::Thu),
+        "fri" => Ok(Weekday::Fri),
+        "sat" => Ok(Weekday::Sat),
+        "sun" => Ok(Weekday::Sun),
+        other => Err(format!("Unknown weekday '{}', expected mon..sun", other)),
+    }
+}
+
+/// Days named by `mon`, `mon-fri`, `fri-mon`, `sat,sun` or `daily`
+fn parse_days(value: &str) -> Result<Vec<Weekday>, String> {
+    let mut days = Vec::new();
+    for part in value.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        if part.eq_ignore_ascii_case("daily") || part == "*" {
+            return Ok(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun]);
+        }
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (mut day, last) = (parse_weekday(first)?, parse_weekday(last)?);
+                days.push(day);
+                while day != last {
+                    day = day.succ();
+                    days.push(day);
+                }
+            }
+            None => days.push(parse_weekday(part)?),
+        }
+    }
+    Ok(days)
+}
+
+/// Timezone the schedule is read in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimerZone {
+    /// The server's local time
+    Local,
+    Named(Tz),
+}
+
+impl TimerZone {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "" | "local" => Ok(Self::Local),
+            name => name.parse::<Tz>().map(Self::Named).map_err(|_| format!("Unknown timezone '{}'", name)),
+        }
+    }
+
+    /// Wall clock time of `now` in this zone
+    pub fn local(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => now.with_timezone(&Local).naive_local(),
+            Self::Named(tz) => now.with_timezone(tz).naive_local(),
+        }
+    }
+
+    fn weekday_and_time(&self, now: DateTime<Utc>) -> (Weekday, NaiveTime) {
+        let local = self.local(now);
+        (local.weekday(), local.time())
+    }
+}
+
+/// When trading is allowed: windows per weekday in one timezone
+#[derive(Debug, Clone)]
+pub struct TimerSchedule {
+    zone: TimerZone,
+    windows: HashMap<Weekday, Vec<TimeWindow>>,
+}
+
+impl TimerSchedule {
+    /// Parse `TIMER_SCHEDULE`, e.g. `mon-fri=09:00-12:00,14:00-22:00;sat=10:00-02:00`
+    ///
+    /// Days without an entry are closed. An empty schedule opens `start`-`stop` every day.
+    pub fn parse(schedule: &str, start: &str, stop: &str, timezone: &str) -> Result<Self, String> {
+        let zone = TimerZone::parse(timezone)?;
+        let mut windows: HashMap<Weekday, Vec<TimeWindow>> = HashMap::new();
+        if schedule.trim().is_empty() {
+            let window = TimeWindow::parse(&format!("{}-{}", start, stop))?;
+            for day in parse_days("daily")? {
+                windows.insert(day, vec![window]);
+            }
+            return Ok(Self { zone, windows });
+        }
+        for entry in schedule.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (days, day_windows) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid schedule entry '{}', expected days=HH:MM-HH:MM", entry))?;
+            let day_windows = day_windows
+                .split(',')
+                .map(str::trim)
+                .filter(|window| !window.is_empty())
+                .map(TimeWindow::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            for day in parse_days(days)? {
+                windows.entry(day).or_default().extend(day_windows.iter().copied());
+            }
+        }
+        Ok(Self { zone, windows })
+    }
+
+    /// Whether `now` falls inside a window, counting windows of the day before that run past midnight
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        let (day, time) = self.zone.weekday_and_time(now);
+        let today = self.windows.get(&day).into_iter().flatten().any(|window| match window.wraps() {
+            true => time >= window.start,
+            false => window.start <= time && time < window.end,
+        });
+        let from_yesterday = self.windows.get(&day.pred()).into_iter().flatten().any(|window| window.wraps() && time < window.end);
+        today || from_yesterday
+    }
+}
+
+static WINDOW_OPEN: AtomicBool = AtomicBool::new(true);
+
+/// Whether strategies may buy now; always true while the timer is off
+pub fn trading_window_open() -> bool {
+    WINDOW_OPEN.load(Ordering::SeqCst)
+}
+
+/// Enforce the trading schedule: pause strategy buys outside its windows and, with
+/// `AUTO_SELL_ON_STOP`, sell every open position when a window closes
+///
+/// Exit sells that fail are retried on the next checks while the window stays closed.
+/// Each close posts a session report once the exits settle, and each open starts a new session.
+pub async fn start_timer_system(executor: Arc<TradeExecutor>, timer: TimerConfig, logger: Logger) {
+    let schedule = match TimerSchedule::parse(&timer.schedule, &timer.start_time, &timer.stop_time, &timer.timezone) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            logger.error(format!("Timer disabled, invalid schedule: {}", e));
+            return;
+        }
+    };
+    let open = schedule.is_open(Utc::now());
+    WINDOW_OPEN.store(open, Ordering::SeqCst);
+    logger.log(format!(
+        "Trading schedule active ({}), window {}",
+        if timer.schedule.trim().is_empty() { format!("{}-{} daily", timer.start_time, timer.stop_time) } else { timer.schedule.clone() },
+        if open { "open" } else { "closed, strategy buys paused" }
+    ).green().to_string());
+
+    let report_on_stop = SessionReportSettings::from_env().on_timer_stop;
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        let mut exit_attempts: Option<HashMap<String, u32>> = None;
+        let mut report_deadline: Option<Instant> = None;
+        loop {
+            interval.tick().await;
+            let open = schedule.is_open(Utc::now());
+            let was_open = WINDOW_OPEN.swap(open, Ordering::SeqCst);
+            if open && !was_open {
+                exit_attempts = None;
+                if report_deadline.take().is_some() {
+                    post_session_report(&logger).await;
+                }
+                begin_session();
+                logger.log("Trading window opened, strategy buys resumed".green().bold().to_string());
+            } else if !open && was_open {
+                logger.log("Trading window closed, strategy buys paused".yellow().bold().to_string());
+                if timer.auto_sell_on_stop {
+                    exit_attempts = Some(HashMap::new());
+                }
+                if report_on_stop {
+                    report_deadline = Some(Instant::now() + REPORT_SETTLE_TIME);
+                }
+            }
+
+            if let Some(attempts) = exit_attempts.as_mut() {
+                let open_positions: Vec<String> = executor
+                    .open_positions()
+                    .into_iter()
+                    .filter(|mint| attempts.get(mint).copied().unwrap_or(0) < MAX_EXIT_ATTEMPTS)
+                    .collect();
+                if !open_positions.is_empty() {
+                    logger.log(format!("AUTO_SELL_ON_STOP=true, selling {} positions", open_positions.len()).yellow().to_string());
+                }
+                for mint in open_positions {
+                    let attempt = attempts.entry(mint.clone()).or_default();
+                    *attempt += 1;
+                    record_decision(Decision::Sell, &mint, ReasonCode::TradingWindowClosed, "AUTO_SELL_ON_STOP");
+                    match sell(&executor, &mint, SellAmount::All).await {
+                        Ok(signature) => logger.log(format!("Sold {} at window close: {}", mint, signature)),
+                        Err(e) => logger.error(format!("Sell of {} at window close failed (attempt {}): {}", mint, attempt, e)),
+                    }
+                }
+            }
+
+            // the report waits for the exit sells to settle
+            if let Some(deadline) = report_deadline {
+                let settled = executor.in_flight() == 0 && (exit_attempts.is_none() || executor.open_positions().is_empty());
+                if settled || Instant::now() >= deadline {
+                    report_deadline = None;
+                    post_session_report(&logger).await;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_schedule_windows() {
+        // 2024-01-01 is a Monday
+        let at = |day: u32, hour: u32, minute: u32| Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap();
+        let schedule = TimerSchedule::parse("mon-fri=09:00-12:00,22:00-02:00;sun=10:00-11:00", "", "", "UTC").unwrap();
+        assert!(schedule.is_open(at(1, 9, 0)));
+        assert!(!schedule.is_open(at(1, 12, 0)));
+        assert!(schedule.is_open(at(1, 23, 30)));
+        assert!(schedule.is_open(at(2, 1, 59)));
+        // friday's late window runs into saturday, which has no windows of its own
+        assert!(schedule.is_open(at(6, 1, 0)));
+        assert!(!schedule.is_open(at(6, 9, 30)));
+        assert!(schedule.is_open(at(7, 10, 30)));
+        // monday's early hours follow sunday, which has no late window
+        assert!(!schedule.is_open(at(1, 1, 0)));
+
+        let daily = TimerSchedule::parse("", "08:00", "20:00", "America/New_York").unwrap();
+        assert!(daily.is_open(at(1, 14, 0)));
+        assert!(!daily.is_open(at(1, 2, 0)));
+
+        assert_eq!(parse_days("fri-mon").unwrap(), vec![Weekday::Fri, Weekday::Sat, Weekday::Sun, Weekday::Mon]);
+        assert!(TimerSchedule::parse("mon=9-17", "", "", "UTC").is_err());
+        assert!(TimerZone::parse("Mars/Olympus").is_err());
+    }
+}
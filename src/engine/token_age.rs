@@ -0,0 +1,109 @@
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use anchor_client::solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+/// Signature pages walked back when looking for a mint's creation
+const MAX_SIGNATURE_PAGES: usize = 10;
+
+/// Token age filter settings
+#[derive(Debug, Clone)]
+pub struct TokenAgeFilter {
+    /// Skip tokens younger than this, in seconds (0 = no minimum)
+    pub min_age_secs: u64,
+    /// Skip tokens older than this, in seconds (0 = no maximum)
+    pub max_age_secs: u64,
+}
+
+impl TokenAgeFilter {
+    /// Load token age filter settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            min_age_secs: std::env::var("MIN_TOKEN_AGE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+            max_age_secs: std::env::var("MAX_TOKEN_AGE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.min_age_secs > 0 || self.max_age_secs > 0
+    }
+
+    /// Reason to skip a token of the given age, if it is outside the window
+    pub fn rejection(&self, age_secs: u64) -> Option<String> {
+        if self.min_age_secs > 0 && age_secs < self.min_age_secs {
+            return Some(format!("{}s old, minimum is {}s", age_secs, self.min_age_secs));
+        }
+        if self.max_age_secs > 0 && age_secs > self.max_age_secs {
+            return Some(format!("{}s old, maximum is {}s", age_secs, self.max_age_secs));
+        }
+        None
+    }
+}
+
+/// Seconds since `created_at` (unix seconds), zero if the clock is behind
+pub fn age_secs(created_at: i64, now: i64) -> u64 {
+    (now - created_at).max(0) as u64
+}
+
+/// Block time of the slot a mint was created in
+pub async fn creation_time_from_slot(rpc_client: &RpcClient, slot: u64) -> Result<i64> {
+    Ok(rpc_client.get_block_time(slot).await?)
+}
+
+/// Creation slot and block time of a mint, from the oldest transaction touching it
+///
+/// Used when the creation was not seen on the stream (e.g. copy trades of older tokens).
+/// Mints with more history than a few thousand transactions are reported as an error.
+pub async fn find_creation(rpc_client: &RpcClient, mint: &str) -> Result<(u64, Option<i64>)> {
+    let mint = Pubkey::from_str(mint)?;
+    let mut before: Option<Signature> = None;
+    let mut oldest_seen = None;
+    for _ in 0..MAX_SIGNATURE_PAGES {
+        let page = rpc_client
+            .get_signatures_for_address_with_config(
+                &mint,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    limit: Some(1000),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        // a short or empty page means the start of the history was reached
+        let complete = page.len() < 1000;
+        if let Some(oldest) = page.last() {
+            oldest_seen = Some((oldest.slot, oldest.block_time));
+            before = Some(Signature::from_str(&oldest.signature)?);
+        }
+        if complete {
+            return oldest_seen.ok_or_else(|| anyhow!("No transactions found for {}", mint));
+        }
+    }
+    Err(anyhow!("No creation transaction found for {} within {} pages", mint, MAX_SIGNATURE_PAGES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_window() {
+        let filter = TokenAgeFilter { min_age_secs: 60, max_age_secs: 600 };
+        assert!(filter.rejection(30).is_some());
+        assert!(filter.rejection(60).is_none());
+        assert!(filter.rejection(601).is_some());
+
+        let new_only = TokenAgeFilter { min_age_secs: 0, max_age_secs: 10 };
+        assert!(new_only.rejection(0).is_none());
+        assert!(!TokenAgeFilter { min_age_secs: 0, max_age_secs: 0 }.is_enabled());
+        assert_eq!(age_secs(100, 90), 0);
+    }
+}
@@ -1,20 +1,6 @@
-use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use anyhow::{Result, anyhow};
-use colored::Colorize;
-use tokio::time;
 use tokio::time::Instant;
 
-use crate::common::logger::Logger;
-use crate::dex::pump_fun::Pump;
-use crate::engine::swap::{SwapDirection, SwapInType};
-use crate::common::config::{SwapConfig, Status, LiquidityPool};
-use crate::core::tx;
-use crate::services::telegram::TelegramService;
-use crate::engine::token_tracker::{TokenTracker, ExtendedTokenInfo};
-use crate::engine::advanced_trading::{RiskProfile, AdvancedTradingManager};
-use crate::engine::monitor::BondingCurveInfo;
+use crate::engine::advanced_trading::RiskProfile;
 
 /// Defines a strategy for token buying with configurable parameters
 #[derive(Clone, Debug)]
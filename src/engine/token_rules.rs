@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+
+use crate::common::logger::Logger;
+use crate::dex::pump_fun::get_token_creator;
+use crate::engine::position_manager::PositionManagerSettings;
+use crate::engine::trade_executor::TradeExecutor;
+
+/// Token rule settings
+#[derive(Debug, Clone)]
+pub struct TokenRuleSettings {
+    /// JSON file with the rules; a missing file means no rules
+    pub file: String,
+}
+
+impl TokenRuleSettings {
+    /// Load token rule settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            file: std::env::var("TOKEN_RULES_FILE").unwrap_or_else(|_| "token_rules.json".to_string()),
+        }
+    }
+}
+
+/// Custom behavior for one mint or for every token of one creator
+///
+/// Unset fields keep the generic behavior.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenRule {
+    /// Label used in logs
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub mint: Option<String>,
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// Never buy matching tokens
+    #[serde(default)]
+    pub skip: bool,
+    /// Spend exactly this much SOL instead of the sized amount
+    #[serde(default)]
+    pub sol_amount: Option<f64>,
+    /// Multiply the sized amount, e.g. 2.0 buys twice the usual size
+    #[serde(default)]
+    pub size_multiplier: Option<f64>,
+    /// Stop loss for the position in percent (0 = none)
+    #[serde(default)]
+    pub stop_loss_percent: Option<f64>,
+    /// No stop loss until the position is this old, in seconds
+    #[serde(default)]
+    pub stop_loss_grace_secs: Option<u64>,
+    /// Hold-time exit for the position in seconds (0 = none)
+    #[serde(default)]
+    pub max_hold_secs: Option<u64>,
+}
+
+impl TokenRule {
+    fn label(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| self.mint.as_ref().map(|mint| format!("mint {}", mint)))
+            .or_else(|| self.creator.as_ref().map(|creator| format!("creator {}", creator)))
+            .unwrap_or_else(|| "unnamed rule".to_string())
+    }
+
+    /// Fill the fields this rule leaves unset from `other`
+    fn or(self, other: &TokenRule) -> TokenRule {
+        TokenRule {
+            name: Some(format!("{} + {}", self.label(), other.label())),
+            mint: self.mint,
+            creator: self.creator.or_else(|| other.creator.clone()),
+            skip: self.skip || other.skip,
+            sol_amount: self.sol_amount.or(other.sol_amount),
+            size_multiplier: self.size_multiplier.or(other.size_multiplier),
+            stop_loss_percent: self.stop_loss_percent.or(other.stop_loss_percent),
+            stop_loss_grace_secs: self.stop_loss_grace_secs.or(other.stop_loss_grace_secs),
+            max_hold_secs: self.max_hold_secs.or(other.max_hold_secs),
+        }
+    }
+
+    /// SOL to spend given the generically sized `amount`
+    pub fn buy_amount(&self, amount: f64) -> f64 {
+        self.sol_amount.unwrap_or(amount * self.size_multiplier.unwrap_or(1.0))
+    }
+
+    /// Exit settings for a position of this token held for `held_for`
+    pub fn exit_settings(&self, settings: &PositionManagerSettings, held_for: Duration) -> PositionManagerSettings {
+        let mut settings = settings.clone();
+        if let Some(stop_loss) = self.stop_loss_percent {
+            settings.stop_loss_percent = stop_loss;
+        }
+        if held_for < Duration::from_secs(self.stop_loss_grace_secs.unwrap_or(0)) {
+            settings.stop_loss_percent = 0.0;
+        }
+        if let Some(max_hold) = self.max_hold_secs {
+            settings.max_hold_secs = max_hold;
+        }
+        settings
+    }
+}
+
+/// Rules checked after the generic filters, mint rules before creator rules
+#[derive(Debug, Clone, Default)]
+pub struct TokenRules {
+    rules: Vec<TokenRule>,
+}
+
+impl TokenRules {
+    pub fn new(rules: Vec<TokenRule>) -> Result<Self, String> {
+        for rule in &rules {
+            if rule.mint.is_none() && rule.creator.is_none() {
+                return Err(format!("Rule '{}' needs a mint or a creator", rule.label()));
+            }
+            if rule.size_multiplier.map(|multiplier| multiplier <= 0.0).unwrap_or(false)
+                || rule.sol_amount.map(|amount| amount <= 0.0).unwrap_or(false)
+            {
+                return Err(format!("Rule '{}' needs a positive size", rule.label()));
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// Load the rules file, which holds a JSON array of rules
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path, e)),
+        };
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let rules: Vec<TokenRule> = serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+        Self::new(rules)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether matching needs the token's creator
+    pub fn has_creator_rules(&self) -> bool {
+        self.rules.iter().any(|rule| rule.creator.is_some())
+    }
+
+    /// The rule for `mint`, its fields merged over those of its creator's rule
+    pub fn matching(&self, mint: &str, creator: Option<&str>) -> Option<TokenRule> {
+        let by_mint = self.rules.iter().find(|rule| rule.mint.as_deref() == Some(mint));
+        let by_creator = creator.and_then(|creator| {
+            self.rules.iter().find(|rule| rule.mint.is_none() && rule.creator.as_deref() == Some(creator))
+        });
+        match (by_mint, by_creator) {
+            (Some(by_mint), Some(by_creator)) => Some(by_mint.clone().or(by_creator)),
+            (by_mint, by_creator) => by_mint.or(by_creator).cloned(),
+        }
+    }
+}
+
+static TOKEN_RULES: LazyLock<RwLock<TokenRules>> = LazyLock::new(|| RwLock::new(TokenRules::default()));
+/// Rule each bought mint was bought under, for its exits
+static POSITION_RULES: LazyLock<Mutex<HashMap<String, TokenRule>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Load `TOKEN_RULES_FILE`, keeping the current rules when it is invalid
+pub fn load_token_rules(settings: &TokenRuleSettings, logger: &Logger) {
+    match TokenRules::load(&settings.file) {
+        Ok(rules) => {
+            if !rules.is_empty() {
+                logger.log(format!("Loaded {} token rules from {}", rules.len(), settings.file));
+            }
+            *TOKEN_RULES.write().unwrap_or_else(|e| e.into_inner()) = rules;
+        }
+        Err(e) => logger.error(format!("Token rules not loaded: {}", e)),
+    }
+}
+
+/// The rule for `mint`, looking its creator up only when a creator rule exists
+pub async fn rule_for(executor: &TradeExecutor, mint: &str) -> Option<TokenRule> {
+    let rules = TOKEN_RULES.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if rules.is_empty() {
+        return None;
+    }
+    let creator = match (rules.has_creator_rules(), Pubkey::from_str(mint)) {
+        (true, Ok(mint)) => get_token_creator(executor.rpc_client(), &mint).await.ok().map(|creator| creator.to_string()),
+        _ => None,
+    };
+    rules.matching(mint, creator.as_deref())
+}
+
+/// Apply `rule` to the exits of `mint` once it is bought
+pub fn remember_position_rule(mint: &str, rule: TokenRule) {
+    POSITION_RULES.lock().unwrap_or_else(|e| e.into_inner()).insert(mint.to_string(), rule);
+}
+
+/// Rule the position in `mint` was bought under
+pub fn position_rule(mint: &str) -> Option<TokenRule> {
+    POSITION_RULES.lock().unwrap_or_else(|e| e.into_inner()).get(mint).cloned()
+}
+
+/// Forget the rules of positions that are closed
+pub fn retain_position_rules(held: &[String]) {
+    POSITION_RULES.lock().unwrap_or_else(|e| e.into_inner()).retain(|mint, _| held.contains(mint));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> TokenRules {
+        let rules: Vec<TokenRule> = serde_json::from_str(r#"[
+            {"name": "trusted dev", "creator": "Dev1", "size_multiplier": 2.0, "stop_loss_grace_secs": 300},
+            {"mint": "Mint1", "sol_amount": 0.5},
+            {"mint": "Mint2", "skip": true}
+        ]"#).unwrap();
+        TokenRules::new(rules).unwrap()
+    }
+
+    #[test]
+    fn test_rule_matching_by_mint() {
+        let rules = rules();
+        assert_eq!(rules.matching("Other", None), None);
+        assert!(rules.matching("Mint2", None).unwrap().skip);
+    }
+
+    #[test]
+    fn test_rule_matching_by_creator() {
+        let rules = rules();
+        assert!(rules.has_creator_rules());
+        let rule = rules.matching("Other", Some("Dev1")).unwrap();
+        assert_eq!(rule.buy_amount(0.1), 0.2);
+    }
+
+    #[test]
+    fn test_mint_rule_overrides_creator_rule() {
+        // the mint rule wins, the creator rule fills in the rest
+        let rule = rules().matching("Mint1", Some("Dev1")).unwrap();
+        assert_eq!(rule.buy_amount(0.1), 0.5);
+        assert_eq!(rule.stop_loss_grace_secs, Some(300));
+    }
+
+    #[test]
+    fn test_stop_loss_grace_period() {
+        let rule = rules().matching("Mint1", Some("Dev1")).unwrap();
+        let settings = PositionManagerSettings { stop_loss_percent: 30.0, ..PositionManagerSettings::from_env() };
+        assert_eq!(rule.exit_settings(&settings, Duration::from_secs(60)).stop_loss_percent, 0.0);
+        assert_eq!(rule.exit_settings(&settings, Duration::from_secs(301)).stop_loss_percent, 30.0);
+    }
+
+    #[test]
+    fn test_rejects_rules_without_target_or_with_unknown_fields() {
+        assert!(TokenRules::new(vec![TokenRule { size_multiplier: Some(2.0), ..Default::default() }]).is_err());
+        assert!(serde_json::from_str::<Vec<TokenRule>>(r#"[{"mint": "M", "size": 2}]"#).is_err());
+    }
+}
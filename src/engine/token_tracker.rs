@@ -9,7 +9,11 @@ use anyhow::{Result, anyhow};
 use spl_token::solana_program::native_token::lamports_to_sol;
 
 use crate::common::logger::Logger;
-use crate::engine::monitor::TransactionType;
+use crate::dex::pump_fun::{curve_progress_percent, BondingCurveReserves, TOKEN_TOTAL_SUPPLY};
+use crate::engine::bundle_analyzer::BundleReport;
+use crate::engine::insider_analyzer::InsiderReport;
+use crate::engine::token_age::age_secs;
+use crate::services::market_cap::{market_cap, whole_tokens};
 
 /// Represents detailed information about a token
 #[derive(Debug)]
@@ -20,15 +24,29 @@ pub struct ExtendedTokenInfo {
     pub current_token_price: f64,
     pub max_token_price: f64,
     pub initial_price: f64,
+    /// Minted supply in raw base units
     pub total_supply: u64,
     pub buy_tx_num: u32,
     pub sell_tx_num: u32,
     pub token_mint_timestamp: Instant,
     pub launcher_sol_balance: Option<f64>,
+    /// In `MARKET_CAP_UNIT`, `None` until the SOL/USD price is known for USD units
     pub market_cap: Option<f64>,
     pub volume: Option<f64>,
     pub dev_buy_amount: Option<f64>,
     pub bundle_check: Option<bool>,
+    /// Share of the supply bought in the creation bundle, once analyzed
+    pub bundled_percent: Option<f64>,
+    /// Whether the early buyers passed insider detection, once analyzed
+    pub insider_check: Option<bool>,
+    /// Share of the early buyers flagged as insiders
+    pub insider_percent: Option<f64>,
+    /// Bonding curve completion in percent, from the latest reserves
+    pub curve_progress: Option<f64>,
+    /// Slot the mint was created in
+    pub creation_slot: Option<u64>,
+    /// Block time of the creation, unix seconds
+    pub created_at: Option<i64>,
     pub active_task: Option<JoinHandle<()>>,
     pub is_monitored: bool,
     pub dev_wallet: Option<String>,
@@ -59,10 +77,16 @@ impl ExtendedTokenInfo {
             sell_tx_num: 0,
             token_mint_timestamp: Instant::now(),
             launcher_sol_balance,
-            market_cap: Some(current_price * (total_supply as f64)),
+            market_cap: market_cap(current_price, whole_tokens(total_supply)),
             volume: dev_buy_amount,
             dev_buy_amount,
             bundle_check,
+            bundled_percent: None,
+            insider_check: None,
+            insider_percent: None,
+            curve_progress: None,
+            creation_slot: None,
+            created_at: None,
             active_task: None,
             is_monitored: false,
             dev_wallet,
@@ -86,9 +110,9 @@ impl ExtendedTokenInfo {
             self.sell_tx_num += 1;
         }
         
-        // Update market cap based on new price
-        if let Some(mc) = self.market_cap.as_mut() {
-            *mc = new_price * (self.total_supply as f64);
+        // Update market cap based on new price, keeping the last one while SOL/USD is unknown
+        if let Some(mc) = market_cap(new_price, whole_tokens(self.total_supply)) {
+            self.market_cap = Some(mc);
         }
         
         // Update volume metrics
@@ -97,6 +121,29 @@ impl ExtendedTokenInfo {
         }
     }
     
+    /// Record the bundle analysis; the bundle check fails when the analyzer rejected the launch
+    pub fn apply_bundle_report(&mut self, report: &BundleReport, rejection: Option<&str>) {
+        self.bundle_check = Some(rejection.is_none());
+        self.bundled_percent = Some(report.bundled_percent);
+    }
+    
+    /// Record the insider analysis; the check fails when the analyzer rejected the launch
+    pub fn apply_insider_report(&mut self, report: &InsiderReport, rejection: Option<&str>) {
+        self.insider_check = Some(rejection.is_none());
+        self.insider_percent = Some(report.insider_percent);
+    }
+    
+    /// Record the bonding curve completion from the latest reserves
+    pub fn update_curve_progress(&mut self, reserves: &BondingCurveReserves) {
+        self.curve_progress = Some(curve_progress_percent(reserves));
+    }
+    
+    /// Record when the mint was created, used by the token age filter
+    pub fn set_creation(&mut self, slot: u64, created_at: Option<i64>) {
+        self.creation_slot = Some(slot);
+        self.created_at = created_at;
+    }
+    
     /// Calculate price delta percentage from max price
     pub fn price_delta_percent(&self) -> f64 {
         if self.max_token_price == 0.0 {
@@ -158,6 +205,11 @@ impl ExtendedTokenInfo {
             dev_buy_amount: self.dev_buy_amount,
             launcher_sol_balance: self.launcher_sol_balance,
             bundle_check: self.bundle_check,
+            bundled_percent: self.bundled_percent,
+            insider_check: self.insider_check,
+            insider_percent: self.insider_percent,
+            curve_progress: self.curve_progress,
+            token_age_secs: self.created_at.map(|created_at| age_secs(created_at, chrono::Utc::now().timestamp())),
             // ATH is equivalent to max_token_price in this context
             ath: Some(self.max_token_price),
             // Add new fields from screenshot
@@ -169,6 +221,9 @@ impl ExtendedTokenInfo {
             token_price,
             dev_wallet: self.dev_wallet.clone(),
             sol_balance,
+            pump_coin: None,
+            uri: None,
+            metadata: None,
         }
     }
     
@@ -190,6 +245,12 @@ impl ExtendedTokenInfo {
             volume: self.volume,
             dev_buy_amount: self.dev_buy_amount,
             bundle_check: self.bundle_check,
+            bundled_percent: self.bundled_percent,
+            insider_check: self.insider_check,
+            insider_percent: self.insider_percent,
+            curve_progress: self.curve_progress,
+            creation_slot: self.creation_slot,
+            created_at: self.created_at,
             active_task: None, // Don't clone the task
             is_monitored: self.is_monitored,
             dev_wallet: self.dev_wallet.clone(), // Include the dev wallet in the clone
@@ -202,6 +263,7 @@ impl ExtendedTokenInfo {
 pub struct TokenFilter {
     pub min_launcher_sol: f64,
     pub max_launcher_sol: f64,
+    /// Market cap range in `MARKET_CAP_UNIT`
     pub min_market_cap: f64,
     pub max_market_cap: f64,
     pub min_volume: f64,
@@ -235,6 +297,7 @@ pub struct TrackedTokenInfo {
     pub token_name: Option<String>,
     pub token_symbol: Option<String>,
     pub price: f64,
+    /// In `MARKET_CAP_UNIT`, 0.0 until it can be priced
     pub market_cap: f64,
     pub volume: f64,
     pub buy_count: u32,
@@ -255,7 +318,7 @@ impl TrackedTokenInfo {
             token_name,
             token_symbol,
             price,
-            market_cap: price,
+            market_cap: market_cap(price, whole_tokens(TOKEN_TOTAL_SUPPLY)).unwrap_or(0.0),
             volume: price,
             buy_count: 1,
             sell_count: 0,
@@ -271,6 +334,9 @@ impl TrackedTokenInfo {
     pub fn update_price(&mut self, new_price: f64) {
         let now = Instant::now();
         self.price = new_price;
+        if let Some(mc) = market_cap(new_price, whole_tokens(TOKEN_TOTAL_SUPPLY)) {
+            self.market_cap = mc;
+        }
         self.last_updated = now;
         self.price_history.push((now, new_price));
         
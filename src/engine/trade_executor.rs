@@ -0,0 +1,1117 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use tracing::Instrument;
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+};
+use anchor_client::solana_client::rpc_request::TokenAccountsFilter;
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+
+use crate::common::config::{AppState, LiquidityPool, Status, SwapConfig};
+use crate::common::logger::Logger;
+use crate::common::reason_code::{clear_trade_reason, record_decision, Decision, ReasonCode};
+use crate::common::signer::WalletSigner;
+use crate::common::telemetry::{pipeline_span, stage_span};
+use crate::core::confirmation::{ConfirmationTracker, PendingTransaction};
+use crate::core::position_store::PositionStore;
+use crate::core::simulation::SimulationSettings;
+use crate::core::tx;
+use crate::dex::launchlab::LaunchLabPool;
+use crate::dex::launchpad::{launchpad_of, Launchpad, LaunchpadSettings};
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{
+    get_bonding_curve_state, get_token_creator, BondingCurveReserves, PumpFunCurve,
+};
+use crate::dex::pump_swap::PumpSwapPool;
+use crate::engine::ata_precreate::{ata_cache, without_ata_creation};
+use crate::engine::balance_monitor::balance_guard;
+use crate::engine::budget::budget_manager;
+use crate::engine::circuit_breaker::circuit_breaker;
+use crate::engine::cooldown::cooldowns;
+use crate::engine::curve_feed::curve_feed;
+use crate::engine::latency::{latency_tracer, Stage};
+use crate::engine::migration::migrations;
+use crate::engine::position_limits::{LimitDecision, PositionLimits};
+use crate::engine::position_sizer::{PositionSizer, PositionSizerSettings};
+use crate::engine::recent_buys::{RecentBuySettings, RecentBuys};
+use crate::engine::relay_selector::{relay_selector, track_submission};
+use crate::engine::slippage::{price_impact_percent, volatility, SlippageSettings};
+use crate::engine::split_exit::{plan_chunks, SplitExitSettings};
+use crate::engine::strategy_ledger::{strategy_ledger, MANUAL_STRATEGY};
+use crate::engine::timer::trading_window_open;
+use crate::engine::tx_cache::{tx_cache, SwapTemplate};
+use crate::engine::swap::{min_out, Dex, SwapDirection};
+use crate::engine::wallet_pool::{WalletPool, WalletPoolSettings};
+use crate::engine::wsol::{WsolManager, WsolSettings};
+use crate::services::alerts::{alert, Alert};
+use crate::services::{jito, jito_leaders};
+use crate::services::spam::spam_sender;
+use crate::services::tpu::{self, TpuRoute, TpuSettings};
+
+/// Pump.fun tokens use 6 decimals
+pub const PUMP_TOKEN_DECIMALS: u32 = 6;
+
+/// A token account owned by the wallet
+#[derive(Debug, Clone)]
+pub struct WalletTokenAccount {
+    pub address: Pubkey,
+    pub mint: String,
+    /// Raw amount in base units
+    pub amount: u64,
+    pub ui_amount: f64,
+}
+
+/// A signed and submitted transaction, with the blockhash that bounds how long it can land
+struct Submission {
+    signature: String,
+    recent_blockhash: Hash,
+    last_valid_block_height: u64,
+    /// Path it went out through, the picked relay when the selector chose
+    relay: String,
+    /// Tip paid to that path in SOL
+    tip_sol: f64,
+}
+
+/// Position states an emergency exit may sell from: it goes out over a sell still in flight
+const EXIT_OVER_PENDING: &[Status] = &[Status::Bought, Status::Selling];
+
+/// A mint claimed in `TradeExecutor::splitting`, released when dropped
+struct SellClaim<'a> {
+    splitting: &'a Mutex<HashSet<String>>,
+    mint: String,
+}
+
+impl Drop for SellClaim<'_> {
+    fn drop(&mut self) {
+        self.splitting.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.mint);
+    }
+}
+
+/// When set, no new buys are started (sells still go through)
+static TRADING_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Stop opening new positions
+pub fn pause_trading() {
+    TRADING_PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Allow new positions again
+pub fn resume_trading() {
+    TRADING_PAUSED.store(false, Ordering::SeqCst);
+}
+
+/// Whether new buys are currently paused
+pub fn is_trading_paused() -> bool {
+    TRADING_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Price of one whole token in SOL from the curve's virtual reserves
+pub fn price_from_reserves(reserves: &BondingCurveReserves) -> f64 {
+    if reserves.virtual_token_reserves == 0 {
+        return 0.0;
+    }
+    (reserves.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL as f64)
+        / (reserves.virtual_token_reserves as f64 / 10f64.powi(PUMP_TOKEN_DECIMALS as i32))
+}
+
+/// Builds, sends and tracks pump.fun, PumpSwap and LaunchLab buys and sells across the trading wallets
+///
+/// Each buy picks a wallet from the wallet pool and its sells go out from the same wallet.
+/// Every trade is registered in the shared position store as `Buying`/`Selling` and handed to
+/// the confirmation tracker, which settles or rolls back the state once it lands.
+pub struct TradeExecutor {
+    app_state: AppState,
+    swap_config: SwapConfig,
+    positions: Arc<PositionStore>,
+    confirmation_tracker: Arc<ConfirmationTracker>,
+    position_limits: PositionLimits,
+    position_sizer: PositionSizer,
+    split_exit: SplitExitSettings,
+    /// Mints with a balance sell or split exit in progress
+    splitting: Mutex<HashSet<String>>,
+    recent_buys: Mutex<RecentBuys>,
+    wallets: WalletPool,
+    wsol: WsolManager,
+    launchpads: LaunchpadSettings,
+    slippage: SlippageSettings,
+    simulation: SimulationSettings,
+    tpu_route: TpuRoute,
+    logger: Logger,
+}
+
+impl TradeExecutor {
+    /// Create a new trade executor
+    pub fn new(
+        app_state: AppState,
+        swap_config: SwapConfig,
+        positions: Arc<PositionStore>,
+        logger: Logger,
+    ) -> Self {
+        let confirmation_tracker = Arc::new(ConfirmationTracker::new(
+            app_state.rpc_nonblocking_client.clone(),
+            positions.clone(),
+            logger.clone(),
+        ));
+        let position_sizer = PositionSizer::new(PositionSizerSettings::from_env(), swap_config.amount_in);
+        let wsol = WsolManager::new(app_state.wallet.pubkey(), WsolSettings::from_env());
+        let mut wallets = vec![app_state.wallet.clone()];
+        wallets.extend(app_state.wallets.iter().filter(|w| w.pubkey() != app_state.wallet.pubkey()).cloned());
+        let wallets = WalletPool::new(wallets, WalletPoolSettings::from_env());
+        Self {
+            app_state,
+            swap_config,
+            positions,
+            confirmation_tracker,
+            position_limits: PositionLimits::from_env(),
+            position_sizer,
+            split_exit: SplitExitSettings::from_env(),
+            splitting: Mutex::new(HashSet::new()),
+            recent_buys: Mutex::new(RecentBuys::new(RecentBuySettings::from_env())),
+            wallets,
+            wsol,
+            launchpads: LaunchpadSettings::from_env(),
+            slippage: SlippageSettings::from_env(),
+            simulation: SimulationSettings::from_env(),
+            tpu_route: TpuSettings::from_env().route,
+            logger,
+        }
+    }
+
+    /// Shared store the executor records positions in
+    pub fn positions(&self) -> Arc<PositionStore> {
+        self.positions.clone()
+    }
+
+    /// Trading wallets and the positions each of them holds
+    pub fn wallet_pool(&self) -> &WalletPool {
+        &self.wallets
+    }
+
+    /// Nonblocking RPC client used for trades and lookups
+    pub fn rpc_client(&self) -> Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient> {
+        self.app_state.rpc_nonblocking_client.clone()
+    }
+
+    /// SOL to spend on the next buy, sized from the wallet balance and the signal
+    pub async fn size_buy(&self, confidence: Option<f64>, volatility: Option<f64>) -> Result<f64> {
+        let wallet_balance = self.wallet_balance().await?;
+        Ok(self.position_sizer.size(wallet_balance, confidence, volatility))
+    }
+
+    /// Buy `sol_amount` SOL worth of a token on its bonding curve, or its PumpSwap pool once migrated
+    pub async fn buy(&self, mint: &str, sol_amount: f64) -> Result<String> {
+        self.buy_as(MANUAL_STRATEGY, mint, sol_amount).await
+    }
+
+    /// Buy on behalf of `strategy`, counting it against that strategy's budget
+    pub async fn buy_as(&self, strategy: &str, mint: &str, sol_amount: f64) -> Result<String> {
+        self.buy_with_slippage(strategy, mint, sol_amount, None).await
+    }
+
+    /// Slippage in basis points for a trade of `mint`, capped at `max_slippage_bps` when given
+    ///
+    /// The configured slippage is widened while the token's price swings (see `SlippageSettings`).
+    fn slippage_bps(&self, mint: &str, max_slippage_bps: Option<u64>) -> u64 {
+        let volatility = volatility().volatility_percent(mint, tokio::time::Instant::now());
+        let slippage = self.slippage.slippage_bps(self.swap_config.slippage, volatility);
+        max_slippage_bps.map(|max| max.min(slippage)).unwrap_or(slippage)
+    }
+
+    /// `buy_as` with the configured slippage capped at `max_slippage_bps`
+    pub async fn buy_with_slippage(&self, strategy: &str, mint: &str, sol_amount: f64, max_slippage_bps: Option<u64>) -> Result<String> {
+        // every buy that does not go out is recorded with why
+        let abort = |code: ReasonCode, e: anyhow::Error| {
+            record_decision(Decision::Abort, mint, code, &format!("{}: {}", strategy, e));
+            e
+        };
+        if is_trading_paused() {
+            return Err(abort(ReasonCode::TradingPaused, anyhow!("Trading is paused")));
+        }
+        // manual buys are not bound to the trading schedule
+        if strategy != MANUAL_STRATEGY && !trading_window_open() {
+            return Err(abort(ReasonCode::OutsideSchedule, anyhow!("Outside the trading schedule")));
+        }
+        if self.has_position(mint) {
+            return Err(abort(ReasonCode::AlreadyHolding, anyhow!("Already holding or trading {}", mint)));
+        }
+        self.check_cooldowns(strategy, mint).await.map_err(|e| abort(ReasonCode::CooldownActive, e))?;
+        circuit_breaker().check().map_err(|e| abort(ReasonCode::CircuitBreakerTripped, e))?;
+        balance_guard().check().map_err(|e| abort(ReasonCode::BalanceTooLow, e))?;
+
+        let wallet_index = self.wallets.select(strategy, &self.in_flight_per_wallet());
+        let wallet_balance = self.balance_of(&self.wallets.wallets()[wallet_index].pubkey()).await?;
+        let sol_amount = match self.position_limits.check(sol_amount, self.open_position_count(), wallet_balance) {
+            LimitDecision::Allow(amount) => amount,
+            LimitDecision::ScaleDown { amount_sol, reason } => {
+                self.logger.log(format!(
+                    "[LIMITS] => Scaling buy of {} down from {} to {:.4} SOL: {}",
+                    mint, sol_amount, amount_sol, reason
+                ).yellow().to_string());
+                amount_sol
+            }
+            LimitDecision::Skip(reason) => {
+                self.logger.log(format!("[LIMITS] => Skipping buy of {}: {}", mint, reason).yellow().to_string());
+                return Err(abort(ReasonCode::PositionLimitReached, anyhow!("Position limits: {}", reason)));
+            }
+        };
+
+        // claimed after the checks above, so buys they skip do not block a later one
+        let claim = self.recent_buys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .try_claim(mint, strategy, tokio::time::Instant::now());
+        if let Err(reason) = claim {
+            self.logger.log(format!("[DUPLICATE] => Skipping buy: {}", reason).yellow().to_string());
+            return Err(abort(ReasonCode::DuplicateBuy, anyhow!("Duplicate buy: {}", reason)));
+        }
+
+        let mut fees_sol = tx::estimated_fee_lamports() as f64 / LAMPORTS_PER_SOL as f64;
+        if self.swap_config.use_jito {
+            fees_sol += jito::get_tip_value().await.unwrap_or(0.0);
+        }
+        budget_manager().reserve(mint, sol_amount, fees_sol).map_err(|e| abort(ReasonCode::BudgetExhausted, e))?;
+        if let Err(e) = strategy_ledger().reserve(strategy, mint, sol_amount, fees_sol) {
+            budget_manager().release(mint);
+            return Err(abort(ReasonCode::BudgetExhausted, e));
+        }
+
+        // tagged before sending, the confirmation can come back before the send returns
+        record_decision(Decision::Buy, mint, ReasonCode::for_strategy(strategy), &format!("{}: {:.4} SOL", strategy, sol_amount));
+        let simulate = self.simulation.simulates_buy(strategy);
+        let result = self.submit_buy(mint, sol_amount, wallet_index, max_slippage_bps, simulate).await;
+        if let Err(e) = &result {
+            budget_manager().release(mint);
+            strategy_ledger().release(mint);
+            clear_trade_reason(mint, Decision::Buy);
+            record_decision(Decision::Abort, mint, ReasonCode::SubmitFailed, &format!("{}: {}", strategy, e));
+        }
+        result
+    }
+
+    /// `venue.build_buy`, patched into a cached template when there is one
+    fn build_buy_cached(&self, venue: &dyn Dex, mint: &Pubkey, wallet: &Pubkey, sol_in: u64, slippage_bps: u64) -> Result<(u64, Vec<Instruction>)> {
+        let args = venue.buy_args(sol_in, slippage_bps);
+        let now = tokio::time::Instant::now();
+        if let Some(template) = tx_cache().get(mint, wallet, venue.name(), true, now) {
+            return Ok((venue.quote(SwapDirection::Buy, sol_in), template.fill(args)));
+        }
+        let (token_amount, instructions) = venue.build_buy(wallet, sol_in, slippage_bps)?;
+        if let Some(template) = SwapTemplate::new(instructions.clone(), args, now) {
+            tx_cache().insert(*mint, *wallet, venue.name(), true, template);
+        }
+        Ok((token_amount, instructions))
+    }
+
+    /// `venue.build_sell`, patched into a cached template when there is one
+    fn build_sell_cached(&self, venue: &dyn Dex, mint: &Pubkey, wallet: &Pubkey, token_amount: u64, min_sol_out: u64) -> Result<Vec<Instruction>> {
+        let now = tokio::time::Instant::now();
+        if let Some(template) = tx_cache().get(mint, wallet, venue.name(), false, now) {
+            return Ok(template.fill((token_amount, min_sol_out)));
+        }
+        let instructions = venue.build_sell(wallet, token_amount, min_sol_out)?;
+        if let Some(template) = SwapTemplate::new(instructions.clone(), (token_amount, min_sol_out), now) {
+            tx_cache().insert(*mint, *wallet, venue.name(), false, template);
+        }
+        Ok(instructions)
+    }
+
+    fn cache_sell_template(&self, venue: &dyn Dex, mint: &Pubkey, wallet: &Pubkey) {
+        let now = tokio::time::Instant::now();
+        if tx_cache().get(mint, wallet, venue.name(), false, now).is_some() {
+            return;
+        }
+        // placeholder amounts, each sell patches in its own
+        let template = venue
+            .build_sell(wallet, 1, 2)
+            .ok()
+            .and_then(|instructions| SwapTemplate::new(instructions, (1, 2), now));
+        if let Some(template) = template {
+            tx_cache().insert(*mint, *wallet, venue.name(), false, template);
+        }
+    }
+
+    /// Build the primary wallet's buy and sell templates for `mint` ahead of its trades
+    pub async fn prebuild_templates(&self, mint: &str) -> Result<()> {
+        let mint_pubkey = Pubkey::from_str(mint)?;
+        let venue = self.venue(mint_pubkey).await?;
+        let wallet = self.wallets.primary().pubkey();
+        self.build_buy_cached(venue.as_ref(), &mint_pubkey, &wallet, LAMPORTS_PER_SOL / 10, self.swap_config.slippage)?;
+        self.cache_sell_template(venue.as_ref(), &mint_pubkey, &wallet);
+        Ok(())
+    }
+
+    /// Refuse mints and creators still cooling down after a failed buy or a losing exit
+    ///
+    /// The creator is only looked up while some creator is cooling down.
+    async fn check_cooldowns(&self, strategy: &str, mint: &str) -> Result<()> {
+        let needs_creator = cooldowns().needs_creator(strategy, mint);
+        let creator = match (needs_creator, Pubkey::from_str(mint)) {
+            (true, Ok(mint_pubkey)) => get_token_creator(self.rpc_client(), &mint_pubkey).await.ok().map(|creator| creator.to_string()),
+            _ => None,
+        };
+        let checked = cooldowns().check(mint, creator.as_deref(), strategy, tokio::time::Instant::now());
+        checked.map_err(|reason| {
+            self.logger.log(format!("[COOLDOWN] => Skipping buy: {}", reason).yellow().to_string());
+            anyhow!("Cooldown: {}", reason)
+        })
+    }
+
+    /// Buy a manually chosen token right away, skipping launch detection
+    ///
+    /// The position is registered like any other buy, so the normal exit rules manage it.
+    /// Without `sol_amount` the buy is sized by the position sizer. Returns the amount and signature.
+    pub async fn snipe(&self, mint: &str, sol_amount: Option<f64>) -> Result<(f64, String)> {
+        Pubkey::from_str(mint).map_err(|_| anyhow!("Invalid mint address: {}", mint))?;
+        let sol_amount = match sol_amount {
+            Some(amount) if amount > 0.0 => amount,
+            Some(amount) => return Err(anyhow!("Snipe amount must be positive, got {}", amount)),
+            None => self.size_buy(None, None).await?,
+        };
+        self.logger.log(format!("[SNIPE] => Manual snipe of {} for {:.4} SOL", mint, sol_amount).magenta().to_string());
+        let signature = self.buy(mint, sol_amount).await?;
+        Ok((sol_amount, signature))
+    }
+
+    /// Buy `token_amount` tokens (base units) for whatever the venue quotes them at, within slippage
+    ///
+    /// The quoted cost goes through the same limits, budget and cooldowns as any buy, so a buy the
+    /// position limits scale down receives fewer tokens. Returns the SOL spent and the signature.
+    pub async fn buy_tokens(&self, strategy: &str, mint: &str, token_amount: u64, max_slippage_bps: Option<u64>) -> Result<(f64, String)> {
+        if token_amount == 0 {
+            return Err(anyhow!("Token amount must be positive"));
+        }
+        let venue = self.venue(Pubkey::from_str(mint)?).await?;
+        let sol_in = venue
+            .quote_in(SwapDirection::Buy, token_amount)
+            .ok_or_else(|| anyhow!("{} cannot supply {} tokens of {}", venue.name(), token_amount, mint))?;
+        // a lamport of headroom, so converting through SOL does not round the cost below the quote
+        let sol_amount = (sol_in + 1) as f64 / LAMPORTS_PER_SOL as f64;
+        self.logger.log(format!(
+            "[EXACT OUT] => Buying {} tokens of {} for {:.6} SOL on {}",
+            token_amount, mint, sol_amount, venue.name()
+        ).magenta().to_string());
+        let signature = self.buy_with_slippage(strategy, mint, sol_amount, max_slippage_bps).await?;
+        Ok((sol_amount, signature))
+    }
+
+    async fn submit_buy(&self, mint: &str, sol_amount: f64, wallet_index: usize, max_slippage_bps: Option<u64>, simulate: bool) -> Result<String> {
+        let pipeline = pipeline_span(mint);
+        let build = stage_span(&pipeline, "build");
+        let start_time = tokio::time::Instant::now();
+
+        let mint_pubkey = Pubkey::from_str(mint)?;
+        let signer = self.wallets.wallets()[wallet_index].clone();
+        let wallet = signer.pubkey();
+        let venue = self.venue(mint_pubkey).instrument(build.clone()).await?;
+        let reserves = venue.reserves();
+
+        let sol_in = (sol_amount * LAMPORTS_PER_SOL as f64) as u64;
+        let impact = price_impact_percent(&reserves, sol_in);
+        if let Some(reason) = self.slippage.impact_rejection(impact) {
+            self.logger.log(format!("[SLIPPAGE] => Skipping buy of {}: {}", mint, reason).yellow().to_string());
+            return Err(anyhow!("Price impact: {}", reason));
+        }
+        let slippage_bps = self.slippage_bps(mint, max_slippage_bps);
+        if slippage_bps > self.swap_config.slippage {
+            self.logger.log(format!(
+                "[SLIPPAGE] => Widened slippage for {} to {} bps",
+                mint, slippage_bps
+            ).yellow().to_string());
+        }
+        let (token_amount, instructions) = build.in_scope(|| self.build_buy_cached(venue.as_ref(), &mint_pubkey, &wallet, sol_in, slippage_bps))?;
+        // a token account pre-created while the launch was screened saves its creation here
+        let instructions = match ata_cache().take(&wallet, &mint_pubkey, tokio::time::Instant::now()) {
+            true => without_ata_creation(instructions, &wallet, &mint_pubkey),
+            false => instructions,
+        };
+        drop(build);
+        latency_tracer().mark(mint, Stage::Build);
+
+        // claim the mint atomically, so concurrent signals for it send a single buy
+        let price = price_from_reserves(&reserves);
+        let claimed = self.positions.try_open(LiquidityPool {
+            mint: mint.to_string(),
+            buy_price: price,
+            sell_price: 0.0,
+            status: Status::Buying,
+            timestamp: Some(tokio::time::Instant::now()),
+        });
+        if !claimed {
+            latency_tracer().discard(mint);
+            return Err(anyhow!("Already holding or trading {}", mint));
+        }
+        self.wallets.assign(mint, wallet_index);
+        // PumpSwap and LaunchLab buys close the wallet's WSOL account when they are done
+        if venue.closes_wsol() && wallet == self.wallets.primary().pubkey() {
+            self.wsol.set_exists(false);
+        }
+
+        let route = self.route(true);
+        let submission = match self.send_via(route, instructions.clone(), &signer, Some(mint), simulate).instrument(stage_span(&pipeline, "send")).await {
+            Ok(submission) => submission,
+            Err(e) => {
+                latency_tracer().discard(mint);
+                self.positions.remove(mint);
+                return Err(e);
+            }
+        };
+        latency_tracer().mark(mint, Stage::Submit);
+        let signature = submission.signature.clone();
+
+        pipeline.in_scope(|| tracing::info!(
+            mint = %mint,
+            venue = venue.name(),
+            signature = %signature,
+            sol_amount,
+            token_amount,
+            latency_ms = start_time.elapsed().as_millis() as u64,
+            "buy submitted"
+        ));
+        self.track(mint, &signer, SwapDirection::Buy, instructions, &submission, sol_amount, token_amount, price, false).await?;
+        // the exits of the new position only patch amounts into this
+        self.cache_sell_template(venue.as_ref(), &mint_pubkey, &wallet);
+        Ok(signature)
+    }
+
+    /// Sell `percent` of the wallet's balance of a token
+    ///
+    /// In TWAP mode large sells are split into chunks (see `SplitExitSettings`).
+    pub async fn sell(&self, mint: &str, percent: f64) -> Result<String> {
+        self.sell_with_slippage(mint, percent, None).await
+    }
+
+    /// `sell` with the configured slippage capped at `max_slippage_bps`
+    pub async fn sell_with_slippage(&self, mint: &str, percent: f64, max_slippage_bps: Option<u64>) -> Result<String> {
+        if percent <= 0.0 || percent > 100.0 {
+            return Err(anyhow!("Sell percent must be in (0, 100], got {}", percent));
+        }
+        let (signer, balance) = self.holder(mint).await?;
+        let token_amount = (balance as f64 * percent / 100.0) as u64;
+        self.sell_held(mint, signer, token_amount, percent >= 100.0, max_slippage_bps).await
+    }
+
+    /// Sell `token_amount` base units of a token, at most the wallet's balance
+    ///
+    /// Selling the whole balance this way closes the position like a 100% sell.
+    pub async fn sell_token_amount(&self, mint: &str, token_amount: u64, max_slippage_bps: Option<u64>) -> Result<String> {
+        let (signer, balance) = self.holder(mint).await?;
+        let token_amount = token_amount.min(balance);
+        self.sell_held(mint, signer, token_amount, token_amount >= balance, max_slippage_bps).await
+    }
+
+    /// Sell `token_amount` held by `signer`, split into chunks in TWAP mode
+    async fn sell_held(&self, mint: &str, signer: WalletSigner, token_amount: u64, full_exit: bool, max_slippage_bps: Option<u64>) -> Result<String> {
+        let _claim = self.claim_sell(mint)?;
+        if token_amount == 0 {
+            return Err(anyhow!("No {} balance to sell", mint));
+        }
+
+        let price = self.current_price(mint).await?;
+        let sol_value = token_amount as f64 / 10f64.powi(PUMP_TOKEN_DECIMALS as i32) * price;
+        let slippage_bps = self.slippage_bps(mint, max_slippage_bps);
+        if !self.split_exit.should_split(sol_value) {
+            return self.sell_tokens(mint, &signer, token_amount, full_exit, slippage_bps).await;
+        }
+
+        self.sell_split(mint, &signer, token_amount, full_exit, price, slippage_bps).await
+    }
+
+    /// Claim `mint` for one sell of the held balance, failing while a split exit or another such sell runs
+    fn claim_sell(&self, mint: &str) -> Result<SellClaim<'_>> {
+        if !self.splitting.lock().unwrap_or_else(|e| e.into_inner()).insert(mint.to_string()) {
+            return Err(anyhow!("A sell of {} is already running", mint));
+        }
+        Ok(SellClaim { splitting: &self.splitting, mint: mint.to_string() })
+    }
+
+    /// Sell the whole balance of a token in one transaction, skipping TWAP splitting
+    ///
+    /// Used for emergency exits where waiting between chunks costs more than the impact.
+    /// A split exit that is still running will fail on its next chunk once the balance is gone.
+    pub async fn sell_all_now(&self, mint: &str) -> Result<String> {
+        let (signer, balance) = self.holder(mint).await?;
+        if balance == 0 {
+            return Err(anyhow!("No {} balance to sell", mint));
+        }
+        self.sell_tokens_via(self.route(false), EXIT_OVER_PENDING, mint, &signer, balance, true, self.swap_config.slippage).await
+    }
+
+    /// Sell the whole balance at `slippage_bps` over `route`, or the usual route when `None`
+    ///
+    /// For stop-loss escalation: no TWAP splitting and no volatility-based slippage.
+    pub async fn sell_escalated(&self, mint: &str, slippage_bps: u64, route: Option<&'static str>) -> Result<String> {
+        let (signer, balance) = self.holder(mint).await?;
+        if balance == 0 {
+            return Err(anyhow!("No {} balance to sell", mint));
+        }
+        let route = route.unwrap_or_else(|| self.route(false));
+        self.sell_tokens_via(route, EXIT_OVER_PENDING, mint, &signer, balance, true, slippage_bps).await
+    }
+
+    /// Tokens of `mint` held by the wallet that owns the position
+    pub async fn held_balance(&self, mint: &str) -> Result<u64> {
+        Ok(self.holder(mint).await?.1)
+    }
+
+    /// Sell as much of a token as it takes to receive `sol_amount` SOL, at least that less slippage
+    ///
+    /// The whole balance is sold when it is worth less than the target. Returns the tokens sold and the signature.
+    pub async fn sell_for_sol(&self, mint: &str, sol_amount: f64, max_slippage_bps: Option<u64>) -> Result<(u64, String)> {
+        if sol_amount <= 0.0 {
+            return Err(anyhow!("Sell proceeds must be positive, got {}", sol_amount));
+        }
+        let _claim = self.claim_sell(mint)?;
+        let (signer, balance) = self.holder(mint).await?;
+        if balance == 0 {
+            return Err(anyhow!("No {} balance to sell", mint));
+        }
+        let venue = self.venue(Pubkey::from_str(mint)?).await?;
+        let sol_out = (sol_amount * LAMPORTS_PER_SOL as f64).ceil() as u64;
+        let token_amount = venue.quote_in(SwapDirection::Sell, sol_out).unwrap_or(balance).min(balance);
+        self.logger.log(format!(
+            "[EXACT OUT] => Selling {} of {} tokens of {} for {:.6} SOL",
+            token_amount, balance, mint, sol_amount
+        ).magenta().to_string());
+        let slippage_bps = self.slippage_bps(mint, max_slippage_bps);
+        let signature = self.sell_tokens(mint, &signer, token_amount, token_amount >= balance, slippage_bps).await?;
+        Ok((token_amount, signature))
+    }
+
+    /// Wallet holding `mint` and its token balance
+    ///
+    /// When the buying wallet is unknown (e.g. restored positions), the first wallet
+    /// with a balance is used and remembered.
+    async fn holder(&self, mint: &str) -> Result<(WalletSigner, u64)> {
+        let mint_pubkey = Pubkey::from_str(mint)?;
+        if self.wallets.owner_index(mint).is_some() || self.wallets.len() == 1 {
+            let signer = self.wallets.owner(mint);
+            let balance = self.token_balance(&signer.pubkey(), &mint_pubkey).await?;
+            return Ok((signer, balance));
+        }
+        for (index, signer) in self.wallets.wallets().iter().enumerate() {
+            let balance = self.token_balance(&signer.pubkey(), &mint_pubkey).await.unwrap_or(0);
+            if balance > 0 {
+                self.wallets.assign(mint, index);
+                return Ok((signer.clone(), balance));
+            }
+        }
+        Ok((self.wallets.primary().clone(), 0))
+    }
+
+    async fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        let ata = get_associated_token_address(owner, mint);
+        Ok(self.app_state.rpc_nonblocking_client
+            .get_token_account_balance(&ata)
+            .await?
+            .amount
+            .parse::<u64>()?)
+    }
+
+    /// Sell `token_amount` in chunks, dumping the rest at once if the price collapses
+    async fn sell_split(
+        &self,
+        mint: &str,
+        signer: &WalletSigner,
+        token_amount: u64,
+        full_exit: bool,
+        start_price: f64,
+        slippage_bps: u64,
+    ) -> Result<String> {
+        let chunks = plan_chunks(token_amount, self.split_exit.chunks);
+        let count = chunks.len();
+        let mut sold = 0u64;
+        let mut signature = String::new();
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let mut amount = chunk;
+            let mut last = i + 1 == count;
+            if i > 0 {
+                tokio::time::sleep(Duration::from_millis(self.split_exit.interval_ms)).await;
+                self.sell_settled(mint).await;
+                let price = self.current_price(mint).await.unwrap_or(start_price);
+                if self.split_exit.is_collapse(start_price, price) {
+                    self.logger.log(format!(
+                        "[TWAP] => {} dropped {:.1}% during split exit, selling the remaining {} tokens now",
+                        mint, (1.0 - price / start_price) * 100.0, token_amount - sold
+                    ).red().bold().to_string());
+                    amount = token_amount - sold;
+                    last = true;
+                }
+            }
+
+            self.logger.log(format!("[TWAP] => {} chunk {}/{}: {} tokens", mint, i + 1, count, amount).cyan().to_string());
+            signature = self.sell_tokens(mint, signer, amount, full_exit && last, slippage_bps).await?;
+            sold += amount;
+            if last {
+                break;
+            }
+        }
+        Ok(signature)
+    }
+
+    /// Wait until the last sell of `mint` has settled, so the next chunk can claim the position
+    async fn sell_settled(&self, mint: &str) {
+        while self.positions.get(mint).is_some_and(|pool| pool.status == Status::Selling) {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
+    async fn sell_tokens(&self, mint: &str, signer: &WalletSigner, token_amount: u64, full_exit: bool, slippage_bps: u64) -> Result<String> {
+        self.sell_tokens_via(self.route(false), &[Status::Bought], mint, signer, token_amount, full_exit, slippage_bps).await
+    }
+
+    /// Sell over `route`, claiming a position whose status is one of `from` for the sell
+    ///
+    /// A balance without a tracked position is claimed as a new `Selling` entry.
+    #[allow(clippy::too_many_arguments)]
+    async fn sell_tokens_via(
+        &self,
+        route: &'static str,
+        from: &[Status],
+        mint: &str,
+        signer: &WalletSigner,
+        token_amount: u64,
+        full_exit: bool,
+        slippage_bps: u64,
+    ) -> Result<String> {
+        let mint_pubkey = Pubkey::from_str(mint)?;
+        let wallet = signer.pubkey();
+        let venue = self.venue(mint_pubkey).await?;
+        let reserves = venue.reserves();
+        let sol_out = venue.quote(SwapDirection::Sell, token_amount);
+        let min_sol_output = min_out(sol_out, slippage_bps);
+        let mut instructions = self.build_sell_cached(venue.as_ref(), &mint_pubkey, &wallet, token_amount, min_sol_output)?;
+
+        // claim the position atomically, so concurrent exits of the mint send a single sell;
+        // the entry keeps its open time, which the max-hold exit counts from
+        let price = price_from_reserves(&reserves);
+        let claimed = self.positions.transition(mint, from, Status::Selling)
+            || self.positions.try_open(LiquidityPool {
+                mint: mint.to_string(),
+                buy_price: 0.0,
+                sell_price: price,
+                status: Status::Selling,
+                timestamp: Some(tokio::time::Instant::now()),
+            });
+        if !claimed {
+            return Err(anyhow!("{} is being bought or already being sold", mint));
+        }
+        self.positions.update(mint, |pool| pool.sell_price = price);
+
+        // leftover WSOL in the primary wallet goes back to native SOL in the same transaction;
+        // PumpSwap and LaunchLab sells already close the WSOL account they are paid into
+        let unwrapping = match wallet == self.wallets.primary().pubkey() {
+            true if venue.closes_wsol() => {
+                self.wsol.set_exists(false);
+                false
+            }
+            true => match self.wsol.take_unwrap_for_sell() {
+                Ok(Some(unwrap)) => {
+                    instructions.push(unwrap);
+                    true
+                }
+                Ok(None) => false,
+                Err(e) => {
+                    self.positions.transition(mint, &[Status::Selling], Status::Bought);
+                    return Err(e);
+                }
+            },
+            false => false,
+        };
+
+        let submission = match self.send_via(route, instructions.clone(), signer, None, self.simulation.simulates_sell()).await {
+            Ok(submission) => submission,
+            Err(e) => {
+                self.positions.transition(mint, &[Status::Selling], Status::Bought);
+                if unwrapping {
+                    self.wsol.set_exists(true);
+                }
+                return Err(e);
+            }
+        };
+
+        let signature = submission.signature.clone();
+        self.logger.log(format!(
+            "[SELL] => {} {} tokens (min {} lamports): {}",
+            mint, token_amount, min_sol_output, signature
+        ).green().to_string());
+        let sol_amount = sol_out as f64 / LAMPORTS_PER_SOL as f64;
+        self.track(mint, signer, SwapDirection::Sell, instructions, &submission, sol_amount, token_amount, price, full_exit).await?;
+        Ok(signature)
+    }
+
+    /// Mints currently held (`Bought`)
+    pub fn open_positions(&self) -> Vec<String> {
+        self.positions.mints_with(Status::Bought)
+    }
+
+    /// Positions held or being bought, counted against MAX_OPEN_POSITIONS
+    pub fn open_position_count(&self) -> usize {
+        self.positions.count(|pool| pool.status == Status::Bought || pool.status == Status::Buying)
+    }
+
+    /// Number of trades still waiting for confirmation (`Buying`/`Selling`)
+    pub fn in_flight(&self) -> usize {
+        self.positions.count(|pool| pool.status == Status::Buying || pool.status == Status::Selling)
+    }
+
+    /// Unconfirmed trades per wallet index, for least-busy wallet selection
+    fn in_flight_per_wallet(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.wallets.len()];
+        for mint in self.positions.mints_with(Status::Buying).into_iter().chain(self.positions.mints_with(Status::Selling)) {
+            counts[self.wallets.owner_index(&mint).unwrap_or(0)] += 1;
+        }
+        counts
+    }
+
+    /// Check whether the wallet's WSOL account exists, so sells know whether to close it
+    pub async fn refresh_wsol(&self) -> Result<bool> {
+        let exists = self.app_state.rpc_nonblocking_client
+            .get_account_with_commitment(&self.wsol.account(), self.app_state.rpc_nonblocking_client.commitment())
+            .await?
+            .value
+            .is_some();
+        self.wsol.set_exists(exists);
+        Ok(exists)
+    }
+
+    /// Close the wallet's WSOL account, returning the wrapped SOL as native SOL
+    ///
+    /// Returns `None` when there is no WSOL account to close.
+    pub async fn unwrap_wsol(&self) -> Result<Option<String>> {
+        if !self.refresh_wsol().await? {
+            return Ok(None);
+        }
+        let signature = self.send(vec![self.wsol.unwrap_instruction()?], self.wallets.primary(), None, false).await?;
+        self.wsol.set_exists(false);
+        Ok(Some(signature))
+    }
+
+    /// Wrap `sol_amount` SOL into the wallet's WSOL account
+    pub async fn wrap_sol(&self, sol_amount: f64) -> Result<String> {
+        let lamports = (sol_amount * LAMPORTS_PER_SOL as f64) as u64;
+        if lamports == 0 {
+            return Err(anyhow!("Nothing to wrap"));
+        }
+        let signature = self.send(self.wsol.wrap_instructions(lamports)?, self.wallets.primary(), None, false).await?;
+        self.wsol.set_exists(true);
+        Ok(signature)
+    }
+
+    /// Send `sol_amount` SOL from the trading wallet to `to`
+    pub async fn transfer_sol(&self, to: &Pubkey, sol_amount: f64) -> Result<String> {
+        let lamports = (sol_amount * LAMPORTS_PER_SOL as f64) as u64;
+        if lamports == 0 {
+            return Err(anyhow!("Nothing to transfer"));
+        }
+        let instruction = system_instruction::transfer(&self.wallets.primary().pubkey(), to, lamports);
+        self.send(vec![instruction], self.wallets.primary(), None, false).await
+    }
+
+    /// Every SPL token account owned by the wallet
+    pub async fn token_accounts(&self) -> Result<Vec<WalletTokenAccount>> {
+        let accounts = self.app_state.rpc_nonblocking_client
+            .get_token_accounts_by_owner(&self.wallets.primary().pubkey(), TokenAccountsFilter::ProgramId(spl_token::ID))
+            .await?;
+        let mut result = Vec::with_capacity(accounts.len());
+        for keyed in accounts {
+            // the RPC returns jsonParsed token accounts
+            let data = serde_json::to_value(&keyed.account.data)?;
+            let info = &data["parsed"]["info"];
+            let (Some(mint), Some(amount)) = (
+                info["mint"].as_str(),
+                info["tokenAmount"]["amount"].as_str().and_then(|v| v.parse::<u64>().ok()),
+            ) else {
+                continue;
+            };
+            result.push(WalletTokenAccount {
+                address: Pubkey::from_str(&keyed.pubkey)?,
+                mint: mint.to_string(),
+                amount,
+                ui_amount: info["tokenAmount"]["uiAmount"].as_f64().unwrap_or(0.0),
+            });
+        }
+        Ok(result)
+    }
+
+    /// Close the given token accounts, `batch_size` per transaction, reclaiming their rent
+    ///
+    /// Returns the signatures of the close transactions that were sent.
+    pub async fn close_token_accounts(&self, accounts: &[Pubkey], batch_size: usize) -> Result<Vec<String>> {
+        let wallet = self.wallets.primary().pubkey();
+        let mut signatures = Vec::new();
+        for batch in accounts.chunks(batch_size.max(1)) {
+            let instructions = batch
+                .iter()
+                .map(|account| spl_token::instruction::close_account(&spl_token::ID, account, &wallet, &wallet, &[&wallet]))
+                .collect::<Result<Vec<_>, _>>()?;
+            signatures.push(self.send(instructions, self.wallets.primary(), None, false).await?);
+        }
+        Ok(signatures)
+    }
+
+    /// Create the primary wallet's token account for `mint` over plain RPC, waiting until it exists
+    ///
+    /// The instruction is idempotent, so an account that is already there costs only the fee.
+    pub async fn create_token_account(&self, mint: &Pubkey) -> Result<String> {
+        let wallet = self.wallets.primary().pubkey();
+        let instruction = create_associated_token_account_idempotent(&wallet, &wallet, mint, &spl_token::ID);
+        let signature = self.send_via("rpc", vec![instruction], self.wallets.primary(), None, false).await?.signature;
+        let ata = get_associated_token_address(&wallet, mint);
+        for _ in 0..20 {
+            if self.app_state.rpc_nonblocking_client.get_account(&ata).await.is_ok() {
+                return Ok(signature);
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        Err(anyhow!("token account {} not created after {}", ata, signature))
+    }
+
+    /// Current price of one whole token in SOL, on its curve or its PumpSwap pool
+    pub async fn current_price(&self, mint: &str) -> Result<f64> {
+        let venue = self.venue(Pubkey::from_str(mint)?).await?;
+        Ok(price_from_reserves(&venue.reserves()))
+    }
+
+    /// Whether `mint` has left its bonding curve and trades on its PumpSwap pool
+    pub async fn is_graduated(&self, mint: &str) -> Result<bool> {
+        Ok(self.venue(Pubkey::from_str(mint)?).await?.is_graduated())
+    }
+
+    /// SOL balance of the primary wallet
+    pub async fn wallet_balance(&self) -> Result<f64> {
+        self.balance_of(&self.wallets.primary().pubkey()).await
+    }
+
+    /// SOL balance of any wallet
+    pub async fn balance_of(&self, wallet: &Pubkey) -> Result<f64> {
+        let lamports = self.app_state.rpc_nonblocking_client.get_balance(wallet).await?;
+        Ok(lamports as f64 / LAMPORTS_PER_SOL as f64)
+    }
+
+    /// Re-bind a held `mint` whose curve completed to the pool it migrated into, returning the pool
+    ///
+    /// `None` while the mint still trades on its curve.
+    pub async fn rebind_if_migrated(&self, mint: &str) -> Result<Option<Pubkey>> {
+        let venue = self.venue(Pubkey::from_str(mint)?).await?;
+        if !venue.is_graduated() {
+            return Ok(None);
+        }
+        let pool = venue.address();
+        if migrations().migrated(mint, pool, venue.name()) {
+            let entry_price = self.positions.get(mint).map(|position| position.buy_price).unwrap_or(0.0);
+            self.logger.log(format!(
+                "[MIGRATION] => {} migrated to {} pool {}, its position sells there now (entry price {:.10} SOL kept)",
+                mint, venue.name(), pool, entry_price
+            ).green().to_string());
+            alert(Alert::debug(mint, &format!("{} migrated to its {} pool {}", mint, venue.name(), pool)));
+        }
+        Ok(Some(pool))
+    }
+
+    /// Where `mint` trades now
+    ///
+    /// Curve state comes from the curve feed when it is fresh, otherwise over RPC.
+    /// Once the curve has completed, the token's PumpSwap pool is loaded instead, and a
+    /// mint re-bound to its pool skips the curve. Mints launched on LaunchLab, or without
+    /// a pump.fun curve while LaunchLab is enabled, trade on their LaunchLab curve.
+    async fn venue(&self, mint: Pubkey) -> Result<Box<dyn Dex>> {
+        if launchpad_of(&mint.to_string()) == Some(Launchpad::LaunchLab) {
+            return self.launchlab_venue(&mint).await;
+        }
+        if migrations().pool_of(&mint.to_string()).is_some() {
+            return Ok(Box::new(PumpSwapPool::fetch(&self.app_state.rpc_nonblocking_client, &mint).await?));
+        }
+        let complete = match curve_feed().fresh(&mint.to_string()) {
+            Some(state) if !state.complete => return self.pump_fun_venue(mint, state.reserves).await,
+            Some(_) => true,
+            None => false,
+        };
+        if !complete {
+            let state = get_bonding_curve_state(
+                self.app_state.rpc_client.clone(),
+                mint,
+                program_ids().pump_fun_program,
+            ).await;
+            match state {
+                Ok((reserves, false)) => return self.pump_fun_venue(mint, reserves).await,
+                Ok(_) => {}
+                Err(_) if self.launchpads.is_enabled(Launchpad::LaunchLab) => return self.launchlab_venue(&mint).await,
+                Err(e) => return Err(e),
+            }
+        }
+        // only held mints migrate, a graduated token bought fresh simply trades on its pool
+        if self.has_position(&mint.to_string()) && migrations().completed(&mint.to_string(), tokio::time::Instant::now()) {
+            self.logger.log(format!("[MIGRATION] => Bonding curve of {} completed, moving to its PumpSwap pool", mint).yellow().to_string());
+        }
+        match PumpSwapPool::fetch(&self.app_state.rpc_nonblocking_client, &mint).await {
+            Ok(pool) => Ok(Box::new(pool)),
+            Err(e) => Err(anyhow!("{} completed its bonding curve and its PumpSwap pool is not live yet: {}", mint, e)),
+        }
+    }
+
+    /// The pump.fun curve of `mint` at `reserves`, with the creator its trades pay fees to
+    async fn pump_fun_venue(&self, mint: Pubkey, reserves: BondingCurveReserves) -> Result<Box<dyn Dex>> {
+        let creator = get_token_creator(self.rpc_client(), &mint).await?;
+        Ok(Box::new(PumpFunCurve { mint, creator, reserves }))
+    }
+
+    /// The LaunchLab curve of `mint`, while it is still open to trades
+    async fn launchlab_venue(&self, mint: &Pubkey) -> Result<Box<dyn Dex>> {
+        let pool = LaunchLabPool::fetch(&self.app_state.rpc_nonblocking_client, mint, self.launchpads.launchlab_fee_bps).await?;
+        if !pool.is_trading() {
+            return Err(anyhow!("LaunchLab curve of {} has migrated to Raydium", mint));
+        }
+        Ok(Box::new(pool))
+    }
+
+    /// Submission path of a buy (`is_buy`) or another trade: "spam", "relay", "tpu", "jito" or "rpc"
+    ///
+    /// With spam-send running every trade is broadcast through all its paths, and with relay
+    /// auto-selection running the selector picks the relay of each trade. With leader
+    /// awareness on, Jito trades go over RPC while no Jito validator is about to lead.
+    fn route(&self, is_buy: bool) -> &'static str {
+        if spam_sender().is_some() {
+            "spam"
+        } else if relay_selector().is_some() {
+            "relay"
+        } else if tpu::is_running() && self.tpu_route.covers(is_buy) {
+            "tpu"
+        } else if self.swap_config.use_jito && jito_leaders::jito_leader_upcoming() {
+            "jito"
+        } else {
+            if self.swap_config.use_jito {
+                self.logger.debug("[JITO-LEADER] => No Jito leader in the next slots, sending over RPC".to_string());
+            }
+            "rpc"
+        }
+    }
+
+    /// Quickest way out regardless of `TPU_ROUTE`: the TPU client when it runs, then Jito, then RPC
+    pub fn fastest_route(&self) -> &'static str {
+        if tpu::is_running() {
+            "tpu"
+        } else if self.swap_config.use_jito && jito_leaders::jito_leader_upcoming() {
+            "jito"
+        } else {
+            "rpc"
+        }
+    }
+
+    /// Sign with `signer` and submit, recording the sign stage for `traced_mint` when given
+    ///
+    /// With `simulate` the signed transaction is simulated first and dropped if it would fail.
+    async fn send(&self, instructions: Vec<Instruction>, signer: &WalletSigner, traced_mint: Option<&str>, simulate: bool) -> Result<String> {
+        self.send_via(self.route(traced_mint.is_some()), instructions, signer, traced_mint, simulate)
+            .await
+            .map(|submission| submission.signature)
+    }
+
+    /// `send` over the given `route`
+    async fn send_via(
+        &self,
+        route: &'static str,
+        instructions: Vec<Instruction>,
+        signer: &WalletSigner,
+        traced_mint: Option<&str>,
+        simulate: bool,
+    ) -> Result<Submission> {
+        let (recent_blockhash, last_valid_block_height) = self.app_state.rpc_nonblocking_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        if let Some(mint) = traced_mint {
+            let mut tracer = latency_tracer();
+            tracer.mark(mint, Stage::Sign);
+            tracer.set_relay(mint, route);
+        }
+        let simulate_with = simulate.then(|| self.app_state.rpc_nonblocking_client.as_ref());
+        let (relay, result) = match route {
+            "relay" => {
+                let relay_selector = relay_selector().ok_or_else(|| anyhow!("Relay selection is not running (set RELAY_AUTO_SELECT=true)"))?;
+                let (relay, result) = tx::new_signed_and_send_selected(
+                    &relay_selector,
+                    &self.app_state.rpc_nonblocking_client,
+                    recent_blockhash,
+                    signer.as_ref(),
+                    instructions,
+                    simulate_with,
+                    &self.logger,
+                ).await;
+                if let Some(mint) = traced_mint {
+                    latency_tracer().set_relay(mint, &relay.to_string());
+                }
+                (relay.to_string(), result)
+            }
+            _ => (route.to_string(), tx::new_signed_and_send_route(
+                route,
+                &self.app_state.rpc_nonblocking_client,
+                recent_blockhash,
+                signer.as_ref(),
+                instructions,
+                simulate_with,
+                &self.logger,
+            ).await),
+        };
+
+        let signature = result?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Sender returned no signature"))?;
+        // every submission that went out is watched, so the selector ranks relays by what lands
+        let tip_sol = tx::relay_tip_sol(&relay).await;
+        track_submission(self.rpc_client(), relay.clone(), Some(signature.clone()), (tip_sol * LAMPORTS_PER_SOL as f64) as u64);
+        Ok(Submission { signature, recent_blockhash, last_valid_block_height, relay, tip_sol })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn track(
+        &self,
+        mint: &str,
+        signer: &WalletSigner,
+        direction: SwapDirection,
+        instructions: Vec<Instruction>,
+        submission: &Submission,
+        sol_amount: f64,
+        token_amount: u64,
+        price: f64,
+        full_exit: bool,
+    ) -> Result<()> {
+        self.confirmation_tracker.spawn(PendingTransaction {
+            mint: mint.to_string(),
+            wallet: signer.clone(),
+            direction,
+            instructions,
+            signature: Signature::from_str(&submission.signature)?,
+            recent_blockhash: submission.recent_blockhash,
+            submitted_at: tokio::time::Instant::now(),
+            last_valid_block_height: submission.last_valid_block_height,
+            attempts: 1,
+            sol_amount,
+            token_amount,
+            price,
+            fee_sol: tx::estimated_fee_lamports() as f64 / LAMPORTS_PER_SOL as f64,
+            tip_sol: submission.tip_sol,
+            relay: submission.relay.clone(),
+            fee_boost: tx::fee_boost(),
+            full_exit,
+        });
+        Ok(())
+    }
+
+    /// Whether `mint` is held or has a trade in flight
+    pub fn has_position(&self, mint: &str) -> bool {
+        self.positions.is_active(mint)
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program};
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+
+/// Transaction template cache settings
+#[derive(Debug, Clone)]
+pub struct TxCacheSettings {
+    /// Reuse built swap instructions, patching only their amounts
+    pub enabled: bool,
+    /// Build buy and sell templates for launches while their filters run, one RPC read each
+    pub prebuild_launches: bool,
+    /// Templates kept at most before the oldest are dropped
+    pub max_entries: usize,
+    /// Templates are rebuilt after this long, in case an account of the venue changed
+    pub ttl_secs: u64,
+}
+
+impl TxCacheSettings {
+    /// Load template cache settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("TX_CACHE_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            prebuild_launches: std::env::var("TX_CACHE_PREBUILD_LAUNCHES")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            max_entries: std::env::var("TX_CACHE_MAX_ENTRIES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(500),
+            ttl_secs: std::env::var("TX_CACHE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(600),
+        }
+    }
+}
+
+/// Which swap amount a SOL transfer of the template carries
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TransferAmount {
+    First,
+    Second,
+}
+
+/// The instructions of one swap, with the accounts resolved and the amounts left to patch
+///
+/// Every venue's swap instruction is an 8 byte discriminator followed by two u64 amounts;
+/// PumpSwap and LaunchLab buys also fund the WSOL account with one of them.
+#[derive(Debug, Clone)]
+pub struct SwapTemplate {
+    instructions: Vec<Instruction>,
+    swap: usize,
+    transfer: Option<(usize, TransferAmount)>,
+    built_at: Instant,
+}
+
+impl SwapTemplate {
+    /// Template from instructions built with the swap amounts `args`, `None` when no instruction carries them
+    pub fn new(instructions: Vec<Instruction>, args: (u64, u64), now: Instant) -> Option<Self> {
+        let swap = instructions.iter().position(|ix| {
+            ix.data.len() >= 24
+                && ix.data[8..16] == args.0.to_le_bytes()
+                && ix.data[16..24] == args.1.to_le_bytes()
+        })?;
+        let transfer = instructions.iter().enumerate().find_map(|(index, ix)| {
+            let lamports = transfer_lamports(ix)?;
+            if lamports == args.0 {
+                Some((index, TransferAmount::First))
+            } else if lamports == args.1 {
+                Some((index, TransferAmount::Second))
+            } else {
+                None
+            }
+        });
+        Some(Self { instructions, swap, transfer, built_at: now })
+    }
+
+    /// The template's instructions with the swap amounts set to `args`
+    pub fn fill(&self, args: (u64, u64)) -> Vec<Instruction> {
+        let mut instructions = self.instructions.clone();
+        let swap = &mut instructions[self.swap].data;
+        swap[8..16].copy_from_slice(&args.0.to_le_bytes());
+        swap[16..24].copy_from_slice(&args.1.to_le_bytes());
+        if let Some((index, amount)) = self.transfer {
+            let lamports = match amount {
+                TransferAmount::First => args.0,
+                TransferAmount::Second => args.1,
+            };
+            instructions[index].data[4..12].copy_from_slice(&lamports.to_le_bytes());
+        }
+        instructions
+    }
+}
+
+/// Lamports of a system transfer instruction
+fn transfer_lamports(ix: &Instruction) -> Option<u64> {
+    if ix.program_id != system_program::ID || ix.data.len() != 12 || ix.data[..4] != [2, 0, 0, 0] {
+        return None;
+    }
+    Some(u64::from_le_bytes(ix.data[4..12].try_into().ok()?))
+}
+
+/// Mint, wallet, venue name and whether the template buys
+type TemplateKey = (Pubkey, Pubkey, &'static str, bool);
+
+/// Swap templates per mint, wallet, venue and side
+///
+/// The venue is part of the key, so a token that migrates gets new templates.
+#[derive(Debug)]
+pub struct TxCache {
+    settings: TxCacheSettings,
+    templates: HashMap<TemplateKey, SwapTemplate>,
+}
+
+impl TxCache {
+    pub fn new(settings: TxCacheSettings) -> Self {
+        Self { settings, templates: HashMap::new() }
+    }
+
+    pub fn settings(&self) -> &TxCacheSettings {
+        &self.settings
+    }
+
+    pub fn get(&self, mint: &Pubkey, wallet: &Pubkey, venue: &'static str, is_buy: bool, now: Instant) -> Option<&SwapTemplate> {
+        if !self.settings.enabled {
+            return None;
+        }
+        let ttl = Duration::from_secs(self.settings.ttl_secs);
+        self.templates
+            .get(&(*mint, *wallet, venue, is_buy))
+            .filter(|template| now.saturating_duration_since(template.built_at) < ttl)
+    }
+
+    pub fn insert(&mut self, mint: Pubkey, wallet: Pubkey, venue: &'static str, is_buy: bool, template: SwapTemplate) {
+        if !self.settings.enabled {
+            return;
+        }
+        if self.templates.len() >= self.settings.max_entries {
+            let oldest = self.templates.iter().min_by_key(|(_, template)| template.built_at).map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.templates.remove(&oldest);
+            }
+        }
+        self.templates.insert((mint, wallet, venue, is_buy), template);
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+static TX_CACHE: LazyLock<Mutex<TxCache>> = LazyLock::new(|| Mutex::new(TxCache::new(TxCacheSettings::from_env())));
+
+pub fn tx_cache() -> MutexGuard<'static, TxCache> {
+    TX_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Build the templates of a launch in the background, while its filters run
+pub fn prebuild(executor: &Arc<TradeExecutor>, mint: &str, logger: &Logger) {
+    {
+        let cache = tx_cache();
+        if !cache.settings().enabled || !cache.settings().prebuild_launches {
+            return;
+        }
+    }
+    let (executor, mint, logger) = (executor.clone(), mint.to_string(), logger.clone());
+    tokio::spawn(async move {
+        if let Err(e) = executor.prebuild_templates(&mint).await {
+            logger.debug(format!("[TX CACHE] => Prebuilding templates for {} failed: {}", mint, e));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::{instruction::AccountMeta, system_instruction};
+
+    fn swap(program: Pubkey, first: u64, second: u64) -> Instruction {
+        let mut data = vec![7u8; 8];
+        data.extend_from_slice(&first.to_le_bytes());
+        data.extend_from_slice(&second.to_le_bytes());
+        Instruction { program_id: program, accounts: vec![AccountMeta::new(Pubkey::new_unique(), false)], data }
+    }
+
+    /// A PumpSwap-style buy: wrap the most it may spend, then swap
+    fn buy_template(user: Pubkey, wsol: Pubkey, program: Pubkey, now: Instant) -> SwapTemplate {
+        let built = vec![system_instruction::transfer(&user, &wsol, 1_100), swap(program, 5_000, 1_100)];
+        SwapTemplate::new(built, (5_000, 1_100), now).unwrap()
+    }
+
+    fn cache() -> TxCache {
+        TxCache::new(TxCacheSettings { enabled: true, prebuild_launches: false, max_entries: 1, ttl_secs: 60 })
+    }
+
+    #[test]
+    fn test_template_patches_amounts_and_funding() {
+        let (user, wsol, program) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let template = buy_template(user, wsol, program, Instant::now());
+
+        let filled = template.fill((9_000, 2_200));
+        let fresh = vec![system_instruction::transfer(&user, &wsol, 2_200), swap(program, 9_000, 2_200)];
+        assert_eq!(filled[0], fresh[0]);
+        assert_eq!(filled[1].data, fresh[1].data);
+        assert_eq!(filled[1].accounts, template.instructions[1].accounts);
+    }
+
+    #[test]
+    fn test_template_needs_the_swap_amounts() {
+        let program = Pubkey::new_unique();
+        assert!(SwapTemplate::new(vec![swap(program, 1, 2)], (3, 4), Instant::now()).is_none());
+    }
+
+    #[test]
+    fn test_cache_keyed_by_side_and_venue() {
+        let (user, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let now = Instant::now();
+        let mut cache = cache();
+        cache.insert(mint, user, "PumpSwap", true, buy_template(user, Pubkey::new_unique(), Pubkey::new_unique(), now));
+        assert!(cache.get(&mint, &user, "PumpSwap", true, now).is_some());
+        assert!(cache.get(&mint, &user, "PumpSwap", false, now).is_none());
+        assert!(cache.get(&mint, &user, "pump.fun", true, now).is_none());
+    }
+
+    #[test]
+    fn test_cache_entries_expire() {
+        let (user, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let now = Instant::now();
+        let mut cache = cache();
+        cache.insert(mint, user, "PumpSwap", true, buy_template(user, Pubkey::new_unique(), Pubkey::new_unique(), now));
+        assert!(cache.get(&mint, &user, "PumpSwap", true, now + Duration::from_secs(61)).is_none());
+    }
+
+    #[test]
+    fn test_cache_evicts_past_max_entries() {
+        let (user, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let now = Instant::now();
+        let template = buy_template(user, Pubkey::new_unique(), Pubkey::new_unique(), now);
+        let mut cache = cache();
+        cache.insert(mint, user, "PumpSwap", true, template.clone());
+        cache.insert(Pubkey::new_unique(), user, "PumpSwap", true, template);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&mint, &user, "PumpSwap", true, now).is_none());
+    }
+}
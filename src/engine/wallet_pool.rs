@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use crate::common::signer::WalletSigner;
+
+/// How the wallet for a new buy is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletSelection {
+    /// Rotate through the wallets
+    RoundRobin,
+    /// Use the wallet assigned to the strategy in `STRATEGY_WALLETS`, the first wallet otherwise
+    PerStrategy,
+    /// Use the wallet with the fewest trades waiting for confirmation
+    LeastBusy,
+}
+
+impl WalletSelection {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "round_robin" | "round-robin" => Some(Self::RoundRobin),
+            "per_strategy" | "per-strategy" => Some(Self::PerStrategy),
+            "least_busy" | "least-busy" => Some(Self::LeastBusy),
+            _ => None,
+        }
+    }
+}
+
+/// Parse assignments like `snipe:0,copy:1` into a wallet index per strategy
+pub fn parse_strategy_wallets(value: &str) -> Result<HashMap<String, usize>, String> {
+    let mut wallets = HashMap::new();
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (strategy, index) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid strategy wallet '{}', expected strategy:index", pair))?;
+        let index = index.trim().parse::<usize>().map_err(|_| format!("Invalid wallet index in '{}'", pair))?;
+        wallets.insert(strategy.trim().to_string(), index);
+    }
+    Ok(wallets)
+}
+
+/// Multi-wallet settings
+#[derive(Debug, Clone)]
+pub struct WalletPoolSettings {
+    pub selection: WalletSelection,
+    /// Wallet index (0 = PRIVATE_KEY) per strategy, used by `per_strategy`
+    pub strategy_wallets: HashMap<String, usize>,
+}
+
+impl WalletPoolSettings {
+    /// Load multi-wallet settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            selection: std::env::var("WALLET_SELECTION")
+                .ok()
+                .and_then(|v| WalletSelection::parse(&v))
+                .unwrap_or(WalletSelection::RoundRobin),
+            strategy_wallets: parse_strategy_wallets(&std::env::var("STRATEGY_WALLETS").unwrap_or_default())
+                .unwrap_or_else(|e| {
+                    eprintln!("Ignoring STRATEGY_WALLETS: {}", e);
+                    HashMap::new()
+                }),
+        }
+    }
+}
+
+/// The trading wallets and which of them holds each position
+///
+/// The first wallet is the primary one: it pays for maintenance transactions
+/// (WSOL, account cleanup, sweeps) and holds positions whose owner is unknown.
+pub struct WalletPool {
+    wallets: Vec<WalletSigner>,
+    settings: WalletPoolSettings,
+    next: AtomicUsize,
+    /// Wallet index that bought each mint
+    owners: Mutex<HashMap<String, usize>>,
+}
+
+impl WalletPool {
+    /// Create a wallet pool; `wallets` must not be empty
+    pub fn new(wallets: Vec<WalletSigner>, settings: WalletPoolSettings) -> Self {
+        assert!(!wallets.is_empty(), "wallet pool needs at least one wallet");
+        Self {
+            wallets,
+            settings,
+            next: AtomicUsize::new(0),
+            owners: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn primary(&self) -> &WalletSigner {
+        &self.wallets[0]
+    }
+
+    pub fn wallets(&self) -> &[WalletSigner] {
+        &self.wallets
+    }
+
+    pub fn len(&self) -> usize {
+        self.wallets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+
+    /// Pick the wallet for a new buy by `strategy`
+    ///
+    /// `in_flight` gives the number of unconfirmed trades per wallet index.
+    pub fn select(&self, strategy: &str, in_flight: &[usize]) -> usize {
+        let count = self.wallets.len();
+        match self.settings.selection {
+            WalletSelection::RoundRobin => self.next.fetch_add(1, Ordering::SeqCst) % count,
+            WalletSelection::PerStrategy => self.settings.strategy_wallets
+                .get(strategy)
+                .copied()
+                .filter(|index| *index < count)
+                .unwrap_or(0),
+            WalletSelection::LeastBusy => (0..count)
+                .min_by_key(|index| in_flight.get(*index).copied().unwrap_or(0))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Record that `mint` is held by wallet `index`
+    pub fn assign(&self, mint: &str, index: usize) {
+        if let Ok(mut owners) = self.owners.lock() {
+            owners.insert(mint.to_string(), index);
+        }
+    }
+
+    /// Wallet index holding `mint`, if known
+    pub fn owner_index(&self, mint: &str) -> Option<usize> {
+        self.owners.lock().ok()?.get(mint).copied()
+    }
+
+    /// Wallet holding `mint`, the primary one when unknown
+    pub fn owner(&self, mint: &str) -> WalletSigner {
+        self.wallets[self.owner_index(mint).unwrap_or(0)].clone()
+    }
+
+    /// Index of the wallet with this public key
+    pub fn index_of(&self, pubkey: &Pubkey) -> Option<usize> {
+        self.wallets.iter().position(|wallet| wallet.pubkey() == *pubkey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::signature::Keypair;
+
+    fn pool(selection: WalletSelection) -> WalletPool {
+        WalletPool::new(
+            (0..3).map(|_| Arc::new(Keypair::new()) as WalletSigner).collect(),
+            WalletPoolSettings {
+                selection,
+                strategy_wallets: parse_strategy_wallets("copy:2, snipe:7").unwrap(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_selection_policies() {
+        let round_robin = pool(WalletSelection::RoundRobin);
+        let picks: Vec<usize> = (0..4).map(|_| round_robin.select("snipe", &[])).collect();
+        assert_eq!(picks, vec![0, 1, 2, 0]);
+
+        let per_strategy = pool(WalletSelection::PerStrategy);
+        assert_eq!(per_strategy.select("copy", &[]), 2);
+        // out of range and unassigned strategies fall back to the primary wallet
+        assert_eq!(per_strategy.select("snipe", &[]), 0);
+        assert_eq!(per_strategy.select("momentum", &[]), 0);
+
+        let least_busy = pool(WalletSelection::LeastBusy);
+        assert_eq!(least_busy.select("snipe", &[2, 0, 1]), 1);
+
+        least_busy.assign("mint", 2);
+        assert_eq!(least_busy.owner("mint").pubkey(), least_busy.wallets()[2].pubkey());
+        assert_eq!(least_busy.owner("other").pubkey(), least_busy.primary().pubkey());
+        assert!(parse_strategy_wallets("copy").is_err());
+    }
+}
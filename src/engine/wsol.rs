@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use anyhow::Result;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+/// Wrapped SOL settings
+#[derive(Debug, Clone)]
+pub struct WsolSettings {
+    /// Close the WSOL account after sells so leftover WSOL returns to native SOL
+    pub auto_unwrap: bool,
+}
+
+impl WsolSettings {
+    /// Load WSOL settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            auto_unwrap: std::env::var("AUTO_UNWRAP_WSOL")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+}
+
+const UNKNOWN: u8 = 0;
+const MISSING: u8 = 1;
+const EXISTS: u8 = 2;
+
+/// Builds wrap/unwrap instructions for the wallet's WSOL account
+///
+/// Remembers whether the account exists so wraps skip the create instruction and
+/// unwraps are only attached to a transaction when there is something to close.
+pub struct WsolManager {
+    wallet: Pubkey,
+    account: Pubkey,
+    settings: WsolSettings,
+    state: AtomicU8,
+}
+
+impl WsolManager {
+    /// Create a new WSOL manager for `wallet`
+    pub fn new(wallet: Pubkey, settings: WsolSettings) -> Self {
+        Self {
+            wallet,
+            account: get_associated_token_address(&wallet, &spl_token::native_mint::ID),
+            settings,
+            state: AtomicU8::new(UNKNOWN),
+        }
+    }
+
+    pub fn settings(&self) -> &WsolSettings {
+        &self.settings
+    }
+
+    /// Address of the wallet's WSOL associated token account
+    pub fn account(&self) -> Pubkey {
+        self.account
+    }
+
+    /// Whether the WSOL account is known to exist
+    pub fn account_exists(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == EXISTS
+    }
+
+    /// Record what the chain says about the WSOL account
+    pub fn set_exists(&self, exists: bool) {
+        self.state.store(if exists { EXISTS } else { MISSING }, Ordering::SeqCst);
+    }
+
+    /// Wrap `lamports` into the WSOL account, creating it only when it may be missing
+    pub fn wrap_instructions(&self, lamports: u64) -> Result<Vec<Instruction>> {
+        let mut instructions = Vec::with_capacity(3);
+        if !self.account_exists() {
+            instructions.push(create_associated_token_account_idempotent(
+                &self.wallet,
+                &self.wallet,
+                &spl_token::native_mint::ID,
+                &spl_token::ID,
+            ));
+        }
+        instructions.push(system_instruction::transfer(&self.wallet, &self.account, lamports));
+        instructions.push(spl_token::instruction::sync_native(&spl_token::ID, &self.account)?);
+        Ok(instructions)
+    }
+
+    /// Close the WSOL account, returning its whole balance and rent as native SOL
+    pub fn unwrap_instruction(&self) -> Result<Instruction> {
+        Ok(spl_token::instruction::close_account(
+            &spl_token::ID,
+            &self.account,
+            &self.wallet,
+            &self.wallet,
+            &[&self.wallet],
+        )?)
+    }
+
+    /// Unwrap instruction to append to a sell, if auto-unwrap is on and the account exists
+    ///
+    /// The account is marked missing right away so only one transaction tries to close it.
+    pub fn take_unwrap_for_sell(&self) -> Result<Option<Instruction>> {
+        if !self.settings.auto_unwrap {
+            return Ok(None);
+        }
+        if self.state.compare_exchange(EXISTS, MISSING, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+            return Ok(None);
+        }
+        self.unwrap_instruction().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_skipped_and_unwrap_taken_once() {
+        let manager = WsolManager::new(Pubkey::new_unique(), WsolSettings { auto_unwrap: true });
+        assert_eq!(manager.wrap_instructions(1_000).unwrap().len(), 3);
+        assert!(manager.take_unwrap_for_sell().unwrap().is_none());
+
+        manager.set_exists(true);
+        assert_eq!(manager.wrap_instructions(1_000).unwrap().len(), 2);
+        assert!(manager.take_unwrap_for_sell().unwrap().is_some());
+        assert!(manager.take_unwrap_for_sell().unwrap().is_none());
+        assert!(!manager.account_exists());
+    }
+}
@@ -2,6 +2,7 @@
 // Add the recursion limit to handle the TokenListManager
 #![recursion_limit = "256"]
 
+pub mod cli;
 pub mod common;
 pub mod core;
 pub mod dex;
@@ -12,7 +13,6 @@ pub mod services;
 pub mod tests;
 
 pub use engine::monitor::new_token_trader_pumpfun;
-pub use engine::enhanced_token_trader::start as start_enhanced_trading_system;
 pub use error::ErrorType as Error;
 
 // No duplicate module declaration needed
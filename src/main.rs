@@ -1,48 +1,128 @@
 use solana_vntr_sniper::{
-    common::{config::Config, constants::RUN_MSG},
-    engine::monitor::new_token_trader_pumpfun,
-    services::telegram::{TelegramService, TelegramFilterSettings},
-    tests::run_dev_wallet_test,
+    cli::{normalize_legacy_args, run_command, Cli, Command},
+    common::{
+        arming::initialize_arming,
+        blacklist::Blacklist,
+        config::Config,
+        constants::RUN_MSG,
+        logger::Logger,
+        telemetry::init_tracing,
+    },
+    core::position_store::PositionStore,
+    dex::launchpad::LaunchpadSettings,
+    engine::{
+        ata_cleanup::{start_ata_cleanup_system, AtaCleanupSettings},
+        balance_monitor::{start_balance_monitor_system, BalanceMonitorSettings},
+        budget::budget_manager,
+        circuit_breaker::circuit_breaker,
+        curve_feed::{start_curve_feed_system, CurveFeedSettings},
+        dev_watch::{start_dev_watch_system, DevWatchSettings},
+        execution_queue::{start_execution_queue, ExecutionQueueSettings},
+        fee_ledger::{fee_ledger, set_fee_alerts},
+        filter_stats::{set_filter_reports, start_filter_stats_system},
+        indicators::{start_indicator_system, IndicatorSettings},
+        koth::{start_koth_poller, KothSettings},
+        launch_feed::start_launch_feed_system,
+        migration::{start_migration_watch, MigrationSettings},
+        monitor::new_token_trader_pumpfun,
+        observer::{start_observer_system, ObserverSettings},
+        panic::start_panic_hotkey,
+        position_manager::{start_position_manager_system, PositionManagerSettings},
+        profit_sweep::{start_profit_sweep_system, ProfitSweepSettings},
+        relay_selector::{start_relay_selection_system, RelaySelectorSettings},
+        session_report::{set_session_reports, start_session_report_system, SessionReportSettings},
+        shadow::start_shadow_system,
+        shutdown::{graceful_shutdown, restore_positions, shutdown_signal, ShutdownSettings},
+        strategy::{start_strategy_system, StrategyRegistry},
+        strategy_ledger::strategy_ledger,
+        timer::start_timer_system,
+        token_rules::{load_token_rules, TokenRuleSettings},
+        trade_executor::TradeExecutor,
+    },
+    services::{
+        alerts::{channel_selected, set_alerts, AlertSettings, Notifier, TelegramNotifier},
+        discord::{DiscordNotifier, DiscordSettings},
+        api::{start_api_server, ApiSettings, ApiState},
+        geyser::{geyser_source, set_reconnect_alerts, GeyserSettings},
+        lookup_table::{load_lookup_table, LookupTableSettings},
+        market_cap::MarketCapUnit,
+        health::{engine_restart_requested, start_health_system, HealthSettings},
+        metrics::start_metrics_server,
+        price_feed::start_price_feed_system,
+        spam::{start_spam_sender, SpamSettings},
+        tpu::{start_tpu_client, TpuSettings},
+        jito_leaders::{start_jito_leader_system, JitoLeaderSettings},
+        leaderboard::start_leaderboard_system,
+        signals::{
+            channels::{start_channel_signal_system, ChannelSignalSettings},
+            SignalSettings,
+        },
+        telegram::{TelegramService, TelegramFilterSettings},
+    },
 };
-use std::sync::Arc;
+use anchor_client::solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::task;
 use chrono;
+use clap::Parser;
+use colored::Colorize;
 use std::env;
 
 #[tokio::main]
 async fn main() {
-    // Check if we should run the dev wallet test
-    let args: Vec<String> = env::args().collect();
-    
-    // If the "--test-dev-wallet" argument is passed, run the test and exit
-    if args.len() > 1 && args[1] == "--test-dev-wallet" {
-        println!("Running dev wallet detection test...");
-        if let Err(e) = run_dev_wallet_test().await {
-            eprintln!("Error running dev wallet test: {}", e);
-            std::process::exit(1);
+    // Structured logging (LOG_FORMAT, LOG_LEVEL, LOG_FILTER, LOG_COMPONENT_LEVELS)
+    init_tracing();
+
+    // The old --arm / --test-dev-wallet / --test-localnet flags still work
+    let cli = Cli::parse_from(normalize_legacy_args(env::args()));
+    match cli.command.unwrap_or(Command::Run) {
+        Command::Run => run().await,
+        command => {
+            if let Err(e) = run_command(command).await {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
         }
-        println!("Dev wallet test completed successfully!");
-        std::process::exit(0);
     }
+}
 
+/// Run the bot until SIGINT/SIGTERM
+async fn run() {
     // Check if enhanced mode is enabled
     let use_enhanced_mode = std::env::var("USE_ENHANCED_MODE").unwrap_or_else(|_| "false".to_string()) == "true";
 
     /* Initial Settings */
     let config = Config::new().await;
-    let config = config.lock().await;
 
     /* Running Bot */
     let run_msg = RUN_MSG;
     println!("{}", run_msg);
     
-    if use_enhanced_mode {
+    // Start the Prometheus exporter if METRICS_ENABLED=true
+    if let Err(e) = start_metrics_server(Logger::new("[METRICS] => ".magenta().bold().to_string())).await {
+        eprintln!("Failed to start metrics exporter: {}", e);
+    }
+
+    // Cached SOL/USD for market caps, from Pyth with Binance and CoinGecko as fallbacks
+    start_price_feed_system(Logger::new("[SOL/USD] => ".cyan().bold().to_string())).await;
+
+    // Observer mode scores every launch but never trades
+    let observer_settings = ObserverSettings::from_env();
+
+    if observer_settings.enabled {
+        println!("👀 Starting in OBSERVER mode - launches are scored and tracked, no trades are sent");
+    } else if use_enhanced_mode {
         println!("🚀 Starting in ENHANCED mode with time series analysis and advanced trading strategies");
     } else {
         println!("🚀 Starting in STANDARD mode with basic monitoring");
     }
 
+    // Live transactions stay impossible unless explicitly armed; observer mode never arms
+    if !observer_settings.enabled {
+        initialize_arming(&config.mode, config.app_state.wallet.as_ref(), &Logger::new("[ARMING] => ".red().bold().to_string()));
+    }
+
     // Log info about whitelist and blacklist functionality
     println!("Token list features enabled:");
     println!(" - Whitelist auto-management: Active tokens kept every review cycle");
@@ -52,6 +132,11 @@ async fn main() {
     println!(" - Lists save interval: {} minutes",
         std::env::var("SAVE_INTERVAL_MS").unwrap_or_else(|_| "600000".to_string()).parse::<u64>().unwrap_or(600000) / 60000);
 
+    // Live filter settings shared with the control API when Telegram owns them
+    let mut shared_filter_settings = None;
+    // Telegram service that takes commands once the executor exists
+    let mut telegram_control = None;
+
     // Send telegram notification with bot configuration if Telegram is enabled
     if !config.telegram_bot_token.is_empty() && !config.telegram_chat_id.is_empty() {
         // Create Telegram service with improved notification system
@@ -63,6 +148,9 @@ async fn main() {
             30 // Rate limit notifications to 1 per 30 seconds
         );
         
+        shared_filter_settings = Some(telegram_service.filter_settings_handle());
+        telegram_control = Some(telegram_service.clone());
+
         // Get filter settings
         let filter_settings = TelegramFilterSettings::from_env();
 
@@ -106,7 +194,7 @@ async fn main() {
             ├ Review Cycle: {} seconds\n\
             └ Save Interval: {} minutes\n\n\
             <b>🔹 Filters Enabled:</b>\n\
-            ├ Market Cap: {} (Range: {:.1}-{:.1}K)\n\
+            ├ Market Cap: {} (Range: {}-{})\n\
             ├ Volume: {} (Range: {:.1}-{:.1}K)\n\
             ├ Buy/Sell Count: {} (Range: {}-{})\n\
             ├ SOL Invested: {} (Min: {:.1} SOL)\n\
@@ -130,8 +218,8 @@ async fn main() {
             save_interval_minutes,
             // Filter settings
             filter_settings.market_cap_enabled,
-            filter_settings.market_cap.min,
-            filter_settings.market_cap.max,
+            MarketCapUnit::from_env().format(filter_settings.market_cap.min),
+            MarketCapUnit::from_env().format(filter_settings.market_cap.max),
             filter_settings.volume_enabled,
             filter_settings.volume.min,
             filter_settings.volume.max,
@@ -191,7 +279,7 @@ async fn main() {
                         ├ Time Exceed: {} seconds\n\
                         └ Counter Limit: {}\n\n\
                         <b>📊 Current Filters:</b>\n\
-                        ├ Market Cap: {} (Range: {:.1}-{:.1}K)\n\
+                        ├ Market Cap: {} (Range: {}-{})\n\
                         ├ Volume: {} (Range: {:.1}-{:.1}K)\n\
                         ├ Buy/Sell Count: {} (Range: {}-{})\n\
                         ├ SOL Invested: {} (Min: {:.1} SOL)\n\
@@ -199,6 +287,9 @@ async fn main() {
                         └ Dev Buy: {} (Range: {:.1}-{:.1} SOL)\n\n\
                         <b>📊 Notification Stats:</b>\n\
                         ├ Unique Tokens Notified: {}\n\n\
+                        <b>💰 Daily Budget:</b> {}\n\n\
+                        <b>🧩 Strategies:</b>\n{}\n\n\
+                        <b>⛽ Fees and Tips:</b>\n{}\n\n\
                         <i>This is an automated status update. Bot continues to monitor for token opportunities.</i>",
                         current_time,
                         start_time.elapsed().as_secs() / 60,
@@ -208,8 +299,8 @@ async fn main() {
                         counter_limit,
                         // Filter settings from the telegram service's persisted config
                         filter_settings.market_cap_enabled,
-                        filter_settings.market_cap.min,
-                        filter_settings.market_cap.max,
+                        MarketCapUnit::from_env().format(filter_settings.market_cap.min),
+                        MarketCapUnit::from_env().format(filter_settings.market_cap.max),
                         filter_settings.volume_enabled,
                         filter_settings.volume.min,
                         filter_settings.volume.max,
@@ -225,7 +316,10 @@ async fn main() {
                         filter_settings.dev_buy_bundle.min,
                         filter_settings.dev_buy_bundle.max,
                         // Show number of unique tokens that have been notified
-                        telegram_service.get_notified_tokens().len()
+                        telegram_service.get_notified_tokens().len(),
+                        budget_manager().summary(),
+                        strategy_ledger().summary(),
+                        fee_ledger().summary()
                     );
                     
                     // Send status message
@@ -241,6 +335,333 @@ async fn main() {
         });
     }
 
+    // Shared position state and executor for manual trades
+    let positions = Arc::new(PositionStore::new());
+    let trade_executor = Arc::new(TradeExecutor::new(
+        config.app_state.clone(),
+        config.swap_config.clone(),
+        positions.clone(),
+        Logger::new("[EXECUTOR] => ".green().bold().to_string()),
+    ));
+
+    // Strategy trades, risk exits and cleanups run on a bounded pool of workers, launch buys first
+    start_execution_queue(
+        ExecutionQueueSettings::from_env(),
+        Logger::new("[QUEUE] => ".green().bold().to_string()),
+    );
+
+    // Pick up positions left open by the previous run
+    let shutdown_settings = ShutdownSettings::from_env();
+    let shutdown_logger = Logger::new("[SHUTDOWN] => ".yellow().bold().to_string());
+    restore_positions(&trade_executor, &shutdown_settings, &shutdown_logger);
+
+    // Sells close the WSOL account in the same transaction once we know it exists
+    if let Err(e) = trade_executor.refresh_wsol().await {
+        eprintln!("Failed to look up the WSOL account: {}", e);
+    }
+
+    // Programs and accounts every swap touches go in a lookup table, keeping v0 transactions small
+    let lookup_table_settings = LookupTableSettings::from_env();
+    if lookup_table_settings.enabled && !observer_settings.enabled {
+        if let Err(e) = load_lookup_table(
+            &trade_executor.rpc_client(),
+            config.app_state.wallet.as_ref(),
+            &lookup_table_settings,
+            &Logger::new("[ALT] => ".cyan().bold().to_string()),
+        ).await {
+            eprintln!("Failed to load the lookup table, sending without it: {}", e);
+        }
+    }
+
+    // Direct QUIC submission to the current and upcoming leaders, available as the `tpu` relay
+    let tpu_settings = TpuSettings::from_env();
+    if tpu_settings.enabled && !observer_settings.enabled {
+        if let Err(e) = start_tpu_client(&tpu_settings, &Logger::new("[TPU] => ".cyan().bold().to_string())).await {
+            eprintln!("Failed to start the TPU client, sending without it: {}", e);
+        }
+    }
+
+    // Spam-send broadcasts every executor trade to all SPAM_RPC_ENDPOINTS and the TPU at once
+    let spam_settings = SpamSettings::from_env();
+    if spam_settings.enabled && !observer_settings.enabled {
+        if let Err(e) = start_spam_sender(&spam_settings, Logger::new("[SPAM] => ".cyan().bold().to_string())).await {
+            eprintln!("Failed to start spam-send, sending without it: {}", e);
+        }
+    }
+
+    // Relay auto-selection sends each trade through the relay that lands fastest for its tip
+    if RelaySelectorSettings::from_env().auto_select && !observer_settings.enabled {
+        start_relay_selection_system(Logger::new("[RELAY] => ".cyan().bold().to_string())).await;
+    }
+
+    // Jito only lands when a Jito validator leads, so follow the leader schedule
+    let jito_leader_settings = JitoLeaderSettings::from_env();
+    if jito_leader_settings.enabled && config.swap_config.use_jito && !observer_settings.enabled {
+        start_jito_leader_system(
+            trade_executor.rpc_client(),
+            jito_leader_settings,
+            Logger::new("[JITO-LEADER] => ".cyan().bold().to_string()),
+        ).await;
+    }
+
+    // Telegram commands (/filters, /snipe, /resume_breaker, ...)
+    if let Some(telegram_service) = &telegram_control {
+        telegram_service.set_executor(trade_executor.clone());
+        telegram_service.start_polling().await;
+    }
+
+    // Exit management (take-profit ladder, trailing stop, stop loss) for held positions
+    let _position_manager = if PositionManagerSettings::from_env().enabled {
+        Some(start_position_manager_system(
+            trade_executor.clone(),
+            Logger::new("[POSITIONS] => ".green().bold().to_string()),
+        ).await)
+    } else {
+        None
+    };
+
+    // Trading schedule: strategy buys only inside the configured windows
+    if config.timer.enabled && !observer_settings.enabled {
+        start_timer_system(
+            trade_executor.clone(),
+            config.timer.clone(),
+            Logger::new("[TIMER] => ".cyan().bold().to_string()),
+        ).await;
+    }
+
+    // Session summaries at a daily cutoff, in addition to the ones posted when a trading window closes
+    start_session_report_system(
+        SessionReportSettings::from_env(),
+        Logger::new("[SESSION] => ".cyan().bold().to_string()),
+    ).await;
+
+    // Follow-up prices of rejected tokens and the daily filter tuning report
+    start_filter_stats_system(
+        trade_executor.clone(),
+        Logger::new("[FILTERS] => ".cyan().bold().to_string()),
+    ).await;
+
+    // Paper trades of the live and the SHADOW_ filter configuration, for A/B tuning
+    start_shadow_system(
+        trade_executor.clone(),
+        Logger::new("[SHADOW] => ".cyan().bold().to_string()),
+    ).await;
+
+    // Copy targets proposed, or followed with LEADERBOARD_AUTO_ADD, from a wallet leaderboard
+    start_leaderboard_system(
+        config.copy_trading.target_wallets.clone(),
+        Logger::new("[LEADERBOARD] => ".cyan().bold().to_string()),
+    ).await;
+
+    // One gRPC source shared by the streaming systems, picked by GEYSER_VENDOR or the endpoint host
+    let geyser = geyser_source(GeyserSettings::from_env(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+        config.yellowstone_reconnect_delay,
+        config.yellowstone_max_retries,
+    ));
+    println!("gRPC provider: {}", geyser.vendor());
+    if let Some(telegram) = telegram_control.clone() {
+        let telegram = Arc::new(telegram);
+        set_reconnect_alerts(telegram.clone(), config.telegram_chat_id.clone());
+        set_fee_alerts(telegram.clone(), config.telegram_chat_id.clone());
+        set_session_reports(telegram.clone(), config.telegram_chat_id.clone());
+        set_filter_reports(telegram, config.telegram_chat_id.clone());
+    }
+
+    // Trade, stop loss and filter alerts, to Telegram and/or Discord as NOTIFY_CHANNELS selects
+    let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+    if let Some(telegram) = telegram_control.clone().filter(|_| channel_selected("telegram")) {
+        notifiers.push(Arc::new(TelegramNotifier::new(
+            Arc::new(telegram),
+            config.telegram_chat_id.clone(),
+            AlertSettings::from_env(),
+        )));
+    }
+    let discord_settings = DiscordSettings::from_env();
+    if discord_settings.is_configured() && channel_selected("discord") {
+        notifiers.push(Arc::new(DiscordNotifier::new(discord_settings)));
+    }
+    if !notifiers.is_empty() {
+        println!("Alert channels: {}", notifiers.iter().map(|notifier| notifier.name()).collect::<Vec<_>>().join(", "));
+        set_alerts(notifiers, Logger::new("[ALERTS] => ".blue().bold().to_string()));
+    }
+
+    // Push-style bonding curve prices for held mints, so exits don't wait on RPC polling
+    if CurveFeedSettings::from_env().enabled {
+        start_curve_feed_system(
+            trade_executor.clone(),
+            geyser.clone(),
+            Logger::new("[CURVES] => ".cyan().bold().to_string()),
+        ).await;
+    }
+
+    // Re-bind held mints to their PumpSwap pool once their curve completes
+    let migration_settings = MigrationSettings::from_env();
+    if migration_settings.enabled {
+        start_migration_watch(
+            trade_executor.clone(),
+            migration_settings,
+            Logger::new("[MIGRATION] => ".yellow().bold().to_string()),
+        ).await;
+    }
+
+    // 1s/5s candles with EMA/RSI for held mints, read by the position manager's exit rules
+    if IndicatorSettings::from_env().enabled {
+        start_indicator_system(
+            trade_executor.clone(),
+            geyser.clone(),
+            Logger::new("[INDICATORS] => ".blue().bold().to_string()),
+        ).await;
+    }
+
+    // Reclaim rent from token accounts emptied by full exits
+    let ata_cleanup_settings = AtaCleanupSettings::from_env();
+    if ata_cleanup_settings.enabled && !observer_settings.enabled {
+        start_ata_cleanup_system(
+            trade_executor.clone(),
+            ata_cleanup_settings,
+            Logger::new("[ATA CLEANUP] => ".green().bold().to_string()),
+        ).await;
+    }
+
+    // Low-balance alerts, and no new buys below the floor until the wallet is topped up
+    let balance_monitor_settings = BalanceMonitorSettings::from_env();
+    if balance_monitor_settings.enabled && !observer_settings.enabled {
+        start_balance_monitor_system(
+            trade_executor.clone(),
+            balance_monitor_settings,
+            telegram_control.clone().map(Arc::new),
+            config.telegram_chat_id.clone(),
+            Logger::new("[BALANCE] => ".yellow().bold().to_string()),
+        ).await;
+    }
+
+    // Move profits above the ceiling to the cold wallet
+    let profit_sweep_settings = ProfitSweepSettings::from_env();
+    if profit_sweep_settings.enabled && !observer_settings.enabled {
+        start_profit_sweep_system(
+            trade_executor.clone(),
+            profit_sweep_settings,
+            telegram_control.clone().map(Arc::new),
+            config.telegram_chat_id.clone(),
+            Logger::new("[SWEEP] => ".green().bold().to_string()),
+        ).await;
+    }
+
+    // Instant exit when the creator of a held token dumps
+    let _dev_watch = if DevWatchSettings::from_env().enabled {
+        Some(start_dev_watch_system(
+            trade_executor.clone(),
+            geyser.clone(),
+            telegram_control.clone().map(Arc::new),
+            config.telegram_chat_id.clone(),
+            Logger::new("[DEV WATCH] => ".red().bold().to_string()),
+        ).await)
+    } else {
+        None
+    };
+
+    // Per-mint and per-creator overrides applied to strategy buys and their exits
+    load_token_rules(&TokenRuleSettings::from_env(), &Logger::new("[RULES] => ".cyan().bold().to_string()));
+
+    // Start observer with daily missed winners / avoided losers reports, before the strategies that feed it
+    let _observer = if observer_settings.enabled {
+        let report_telegram = if !config.telegram_bot_token.is_empty() && !config.telegram_chat_id.is_empty() {
+            Some(Arc::new(TelegramService::new(
+                config.telegram_bot_token.clone(),
+                config.telegram_chat_id.clone(),
+                30
+            )))
+        } else {
+            None
+        };
+        Some(start_observer_system(
+            Logger::new("[OBSERVER] => ".cyan().bold().to_string()),
+            observer_settings.clone(),
+            report_telegram,
+            config.telegram_chat_id.clone(),
+        ).await)
+    } else {
+        None
+    };
+
+    // Entry strategies (snipe, copy, inverse, momentum, koth) enabled in the config
+    let strategy_registry = StrategyRegistry::from_config(&config);
+    let koth_settings = KothSettings::from_env();
+    if koth_settings.enabled && koth_settings.poll_secs > 0 {
+        start_koth_poller(koth_settings, Logger::new("[KOTH] => ".yellow().bold().to_string()));
+    }
+    // in observer mode they run as usual but their buys are only recorded
+    let strategies = if !strategy_registry.is_empty() {
+        Some(start_strategy_system(
+            trade_executor.clone(),
+            strategy_registry,
+            geyser.clone(),
+            Logger::new("[STRATEGIES] => ".magenta().bold().to_string()),
+        ).await)
+    } else {
+        None
+    };
+
+    // New launches on the LAUNCHPADS platforms, handed to the strategies' on_new_token
+    let launchpad_settings = LaunchpadSettings::from_env();
+    if let Some(registry) = strategies.clone() {
+        if !launchpad_settings.launchpads.is_empty() {
+            start_launch_feed_system(
+                trade_executor.clone(),
+                registry,
+                launchpad_settings,
+                geyser.clone(),
+                Logger::new("[LAUNCHES] => ".yellow().bold().to_string()),
+            ).await;
+        }
+    }
+
+    // Mints called in the TG_SIGNAL_CHANNELS Telegram channels
+    let channel_signal_settings = ChannelSignalSettings::from_env();
+    if channel_signal_settings.enabled && !observer_settings.enabled {
+        if let Err(e) = start_channel_signal_system(
+            trade_executor.clone(),
+            channel_signal_settings,
+            Logger::new("[TG SIGNALS] => ".magenta().bold().to_string()),
+        ).await {
+            eprintln!("Failed to start Telegram channel signals: {}", e);
+        }
+    }
+
+    // Drawdown for the circuit breaker is measured against the balance at startup
+    match config.app_state.rpc_nonblocking_client.get_balance(&config.app_state.wallet.pubkey()).await {
+        Ok(lamports) => circuit_breaker().set_starting_balance(lamports as f64 / LAMPORTS_PER_SOL as f64),
+        Err(e) => eprintln!("Failed to fetch wallet balance for circuit breaker: {}", e),
+    }
+
+    // Start the HTTP control API if API_ENABLED=true
+    let api_settings = ApiSettings::from_env();
+    if api_settings.enabled {
+        let blacklist_path = std::env::var("BLACKLIST_PATH").unwrap_or_else(|_| "blacklist.json".to_string());
+        let blacklist = Blacklist::new(&blacklist_path).unwrap_or_else(|_| Blacklist::empty(&blacklist_path));
+        let api_state = ApiState {
+            token: Arc::new(api_settings.token.clone()),
+            executor: trade_executor.clone(),
+            filter_settings: shared_filter_settings
+                .clone()
+                .unwrap_or_else(|| Arc::new(Mutex::new(TelegramFilterSettings::from_env()))),
+            blacklist: Arc::new(tokio::sync::Mutex::new(blacklist)),
+            signals: Arc::new(SignalSettings::from_env()),
+            started_at: Instant::now(),
+            logger: Logger::new("[API] => ".cyan().bold().to_string()),
+        };
+        if let Err(e) = start_api_server(api_settings, api_state).await {
+            eprintln!("Failed to start control API: {}", e);
+        }
+    }
+
+    // Typing PANIC_HOTKEY on the terminal sells everything and pauses buying
+    if !observer_settings.enabled {
+        start_panic_hotkey(trade_executor.clone(), Logger::new("[PANIC] => ".red().bold().to_string()));
+    }
+
         if let Err(e) = new_token_trader_pumpfun(
             config.yellowstone_grpc_http.clone(),
             config.yellowstone_grpc_token.clone(),
@@ -252,8 +673,8 @@ async fn main() {
             config.blacklist.clone(),
             config.time_exceed,
             config.counter_limit as u64,
-            config.min_dev_buy as u64,
-            config.max_dev_buy as u64,
+            config.min_dev_buy,
+            config.max_dev_buy,
             config.telegram_bot_token.clone(),
             config.telegram_chat_id.clone(),
             config.bundle_check,
@@ -276,8 +697,23 @@ async fn main() {
         // the new send_transaction_notification method we've implemented
     }
 
-    // Keep the main thread alive
-    loop {
-        tokio::time::sleep(Duration::from_secs(1)).await;
+    // /healthz for liveness probes, and the watchdog that restarts silent streams
+    if let Err(e) = start_health_system(
+        trade_executor.clone(),
+        HealthSettings::from_env(),
+        Logger::new("[HEALTH] => ".green().bold().to_string()),
+    ).await {
+        eprintln!("Failed to start health endpoint: {}", e);
+    }
+
+    // Run until SIGINT/SIGTERM or a watchdog restart, then settle in-flight trades and save state
+    let restart = tokio::select! {
+        _ = shutdown_signal() => false,
+        _ = engine_restart_requested() => true,
+    };
+    graceful_shutdown(trade_executor, shutdown_settings, shutdown_logger).await;
+    if restart {
+        // non-zero so the supervisor starts the engine again
+        std::process::exit(1);
     }
 }
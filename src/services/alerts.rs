@@ -0,0 +1,541 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::common::reason_code::ReasonCode;
+use crate::common::trade_log::TradeLogEntry;
+use crate::services::telegram::TelegramService;
+
+/// Telegram rejects messages longer than this
+const MAX_MESSAGE_CHARS: usize = 4096;
+/// Alerts joined into one Telegram message at most, around the size limit at typical lengths
+const TELEGRAM_ALERTS_PER_MESSAGE: usize = 20;
+/// Alerts held at most while the rate limit is reached, the oldest are dropped past it
+const MAX_QUEUED_ALERTS: usize = 200;
+
+/// How much the bot tells the chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotifyLevel {
+    /// Buys, sells, failed trades and stop losses
+    Trades,
+    /// Trades plus every token a filter rejected
+    Filters,
+    /// Everything, including migrations
+    Debug,
+}
+
+impl NotifyLevel {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "trades" | "trade" => Some(NotifyLevel::Trades),
+            "filters" | "filter" => Some(NotifyLevel::Filters),
+            "debug" => Some(NotifyLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+/// What an alert is about, each kind with its own template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    Buy,
+    Sell,
+    /// A trade that failed, was dropped or was abandoned
+    Fail,
+    StopLoss,
+    /// A copy-trading target went quiet or changed how it trades, `mint` holds the wallet
+    Target,
+    Filter,
+    Debug,
+}
+
+impl AlertKind {
+    /// Lowest level the kind is sent at
+    pub fn level(&self) -> NotifyLevel {
+        match self {
+            AlertKind::Buy | AlertKind::Sell | AlertKind::Fail | AlertKind::StopLoss | AlertKind::Target => NotifyLevel::Trades,
+            AlertKind::Filter => NotifyLevel::Filters,
+            AlertKind::Debug => NotifyLevel::Debug,
+        }
+    }
+}
+
+/// Alert level, templates and batching settings
+#[derive(Debug, Clone)]
+pub struct AlertSettings {
+    pub level: NotifyLevel,
+    pub buy_template: String,
+    pub sell_template: String,
+    pub fail_template: String,
+    pub stop_loss_template: String,
+    pub target_template: String,
+    pub filter_template: String,
+    pub debug_template: String,
+    /// Alerts arriving within this window of the first are sent as one message, in milliseconds
+    pub batch_window_ms: u64,
+    /// Messages sent at most per minute (0 = unlimited)
+    pub max_messages_per_minute: u32,
+}
+
+const DEFAULT_BUY_TEMPLATE: &str = "✅ <b>BUY</b> <code>{mint}</code>\n🏷 {code}\n💰 {sol} SOL at {price} SOL\n🔗 <a href=\"{solscan}\">Solscan</a> | <a href=\"{pumpfun}\">pump.fun</a>";
+const DEFAULT_SELL_TEMPLATE: &str = "🟥 <b>SELL</b> <code>{mint}</code>\n🏷 {code}\n💰 {sol} SOL at {price} SOL\n📊 PnL: {pnl}\n🔗 <a href=\"{solscan}\">Solscan</a> | <a href=\"{pumpfun}\">pump.fun</a>";
+const DEFAULT_FAIL_TEMPLATE: &str = "⚠️ <b>{side} {status}</b> <code>{mint}</code> ({code})\n{reason}\n🔗 <a href=\"{solscan}\">Solscan</a>";
+const DEFAULT_STOP_LOSS_TEMPLATE: &str = "🛑 <b>STOP LOSS</b> <code>{mint}</code>\n{reason}\n🔗 <a href=\"{pumpfun}\">pump.fun</a>";
+const DEFAULT_TARGET_TEMPLATE: &str = "👁 <b>TARGET</b> <code>{mint}</code>\n{reason}\n🔗 <a href=\"{solscan}\">Solscan</a>";
+const DEFAULT_FILTER_TEMPLATE: &str = "🚫 Skipped <code>{mint}</code> ({filter}): {reason}";
+const DEFAULT_DEBUG_TEMPLATE: &str = "🔧 {reason}";
+
+fn template_env(key: &str, default: &str) -> String {
+    std::env::var(key)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        // .env files cannot hold real newlines
+        .map(|v| v.replace("\\n", "\n"))
+        .unwrap_or_else(|| default.to_string())
+}
+
+impl AlertSettings {
+    /// Load alert settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            level: std::env::var("TELEGRAM_NOTIFY_LEVEL")
+                .ok()
+                .and_then(|v| NotifyLevel::parse(&v))
+                .unwrap_or(NotifyLevel::Trades),
+            buy_template: template_env("TELEGRAM_TEMPLATE_BUY", DEFAULT_BUY_TEMPLATE),
+            sell_template: template_env("TELEGRAM_TEMPLATE_SELL", DEFAULT_SELL_TEMPLATE),
+            fail_template: template_env("TELEGRAM_TEMPLATE_FAIL", DEFAULT_FAIL_TEMPLATE),
+            stop_loss_template: template_env("TELEGRAM_TEMPLATE_STOP_LOSS", DEFAULT_STOP_LOSS_TEMPLATE),
+            target_template: template_env("TELEGRAM_TEMPLATE_TARGET", DEFAULT_TARGET_TEMPLATE),
+            filter_template: template_env("TELEGRAM_TEMPLATE_FILTER", DEFAULT_FILTER_TEMPLATE),
+            debug_template: template_env("TELEGRAM_TEMPLATE_DEBUG", DEFAULT_DEBUG_TEMPLATE),
+            batch_window_ms: std::env::var("TELEGRAM_BATCH_WINDOW_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3000),
+            max_messages_per_minute: std::env::var("TELEGRAM_MAX_MESSAGES_PER_MINUTE")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(20),
+        }
+    }
+
+    pub fn template(&self, kind: AlertKind) -> &str {
+        match kind {
+            AlertKind::Buy => &self.buy_template,
+            AlertKind::Sell => &self.sell_template,
+            AlertKind::Fail => &self.fail_template,
+            AlertKind::StopLoss => &self.stop_loss_template,
+            AlertKind::Target => &self.target_template,
+            AlertKind::Filter => &self.filter_template,
+            AlertKind::Debug => &self.debug_template,
+        }
+    }
+
+    pub fn is_enabled(&self, kind: AlertKind) -> bool {
+        kind.level() <= self.level
+    }
+}
+
+/// One notification, rendered through the template of its kind
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub mint: String,
+    pub side: Option<String>,
+    pub status: Option<String>,
+    pub sol: Option<f64>,
+    pub price: Option<f64>,
+    /// Realized PnL of a sell against the average entry, in percent
+    pub pnl_percent: Option<f64>,
+    pub signature: Option<String>,
+    pub filter: Option<String>,
+    /// Machine-readable reason of the decision behind the alert
+    pub code: Option<ReasonCode>,
+    pub reason: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Alert {
+    fn new(kind: AlertKind, mint: &str) -> Self {
+        Self {
+            kind,
+            mint: mint.to_string(),
+            side: None,
+            status: None,
+            sol: None,
+            price: None,
+            pnl_percent: None,
+            signature: None,
+            filter: None,
+            code: None,
+            reason: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// A trade that reached a final status
+    pub fn trade(trade: &TradeLogEntry, pnl_percent: Option<f64>) -> Self {
+        let kind = match (trade.status.as_str(), trade.side.as_str()) {
+            ("confirmed", "buy") => AlertKind::Buy,
+            ("confirmed", _) => AlertKind::Sell,
+            _ => AlertKind::Fail,
+        };
+        Self {
+            side: Some(trade.side.clone()),
+            status: Some(trade.status.clone()),
+            sol: Some(trade.sol_amount),
+            price: Some(trade.price),
+            pnl_percent,
+            signature: Some(trade.signature.clone()),
+            reason: trade.reason.clone(),
+            code: trade.reason_code,
+            ..Self::new(kind, &trade.mint)
+        }
+    }
+
+    pub fn stop_loss(mint: &str, reason: &str) -> Self {
+        Self {
+            side: Some("sell".to_string()),
+            reason: Some(reason.to_string()),
+            code: Some(ReasonCode::StopLossTriggered),
+            ..Self::new(AlertKind::StopLoss, mint)
+        }
+    }
+
+    /// Copy-trading target `wallet` went quiet or changed how it trades
+    pub fn target(wallet: &str, reason: &str) -> Self {
+        Self {
+            reason: Some(reason.to_string()),
+            ..Self::new(AlertKind::Target, wallet)
+        }
+    }
+
+    /// A token rejected by `filter`
+    pub fn filter(mint: &str, filter: &str, reason: &str) -> Self {
+        Self {
+            filter: Some(filter.to_string()),
+            code: Some(ReasonCode::for_filter(filter)),
+            reason: Some(reason.to_string()),
+            ..Self::new(AlertKind::Filter, mint)
+        }
+    }
+
+    pub fn debug(mint: &str, message: &str) -> Self {
+        Self {
+            reason: Some(message.to_string()),
+            ..Self::new(AlertKind::Debug, mint)
+        }
+    }
+
+    /// Solscan link to the transaction, or to the token (the wallet of a target alert) when there is none
+    pub fn solscan_url(&self) -> String {
+        match &self.signature {
+            Some(signature) if !signature.is_empty() => format!("https://solscan.io/tx/{}", signature),
+            _ if self.kind == AlertKind::Target => format!("https://solscan.io/account/{}", self.mint),
+            _ => format!("https://solscan.io/token/{}", self.mint),
+        }
+    }
+
+    pub fn pumpfun_url(&self) -> String {
+        format!("https://pump.fun/coin/{}", self.mint)
+    }
+
+    /// Fill the placeholders of `template`, `-` for values the alert does not carry
+    ///
+    /// Placeholders: `{mint}`, `{side}`, `{status}`, `{sol}`, `{price}`, `{pnl}`, `{signature}`,
+    /// `{filter}`, `{code}`, `{reason}`, `{solscan}` and `{pumpfun}`.
+    pub fn render(&self, template: &str) -> String {
+        let missing = || "-".to_string();
+        let fields = [
+            ("{mint}", self.mint.clone()),
+            ("{side}", self.side.as_deref().map(str::to_uppercase).unwrap_or_else(missing)),
+            ("{status}", self.status.clone().unwrap_or_else(missing)),
+            ("{sol}", self.sol.map(|sol| format!("{:.4}", sol)).unwrap_or_else(missing)),
+            ("{price}", self.price.map(|price| format!("{:.10}", price)).unwrap_or_else(missing)),
+            ("{pnl}", self.pnl_percent.map(|pnl| format!("{:+.2}%", pnl)).unwrap_or_else(missing)),
+            ("{signature}", self.signature.clone().unwrap_or_else(missing)),
+            ("{filter}", self.filter.clone().unwrap_or_else(missing)),
+            ("{code}", self.code.map(|code| code.to_string()).unwrap_or_else(missing)),
+            ("{reason}", self.reason.as_deref().map(escape_html).unwrap_or_else(missing)),
+            ("{solscan}", self.solscan_url()),
+            ("{pumpfun}", self.pumpfun_url()),
+        ];
+        fields.iter().fold(template.to_string(), |text, (placeholder, value)| text.replace(placeholder, value))
+    }
+}
+
+/// Reasons come from RPC errors and token metadata, keep them from breaking the HTML parse mode
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Alerts waiting to go out on one channel, merged into as few messages as its rate limit needs
+#[derive(Debug, Default)]
+pub struct AlertBatch {
+    queued: VecDeque<Alert>,
+    first_queued: Option<Instant>,
+    sent: VecDeque<Instant>,
+    dropped: u64,
+}
+
+impl AlertBatch {
+    pub fn push(&mut self, alert: Alert, now: Instant) {
+        if self.queued.len() >= MAX_QUEUED_ALERTS {
+            self.queued.pop_front();
+            self.dropped += 1;
+        }
+        self.queued.push_back(alert);
+        self.first_queued.get_or_insert(now);
+    }
+
+    pub fn len(&self) -> usize {
+        self.queued.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty()
+    }
+
+    /// Alerts for the next message and how many were dropped since the last one
+    ///
+    /// Nothing is returned before the batch window has passed or while the rate limit is
+    /// reached. At most `max_alerts` go into one message, the rest wait for the next.
+    pub fn flush(&mut self, window: Duration, max_per_minute: u32, max_alerts: usize, now: Instant) -> Option<(Vec<Alert>, u64)> {
+        let first_queued = self.first_queued?;
+        if now.duration_since(first_queued) < window {
+            return None;
+        }
+        while self.sent.front().map(|at| now.duration_since(*at) >= Duration::from_secs(60)).unwrap_or(false) {
+            self.sent.pop_front();
+        }
+        if max_per_minute > 0 && self.sent.len() >= max_per_minute as usize {
+            return None;
+        }
+
+        let take = self.queued.len().min(max_alerts.max(1));
+        let alerts: Vec<Alert> = self.queued.drain(..take).collect();
+        if self.queued.is_empty() {
+            self.first_queued = None;
+        }
+        self.sent.push_back(now);
+        Some((alerts, std::mem::take(&mut self.dropped)))
+    }
+}
+
+/// Join rendered alerts into as few messages of at most `max_chars` as they fit in
+///
+/// A single alert over the limit is cut rather than rejected.
+pub fn join_messages(rendered: &[String], max_chars: usize) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for text in rendered {
+        let text: String = text.chars().take(max_chars).collect();
+        if !current.is_empty() && current.chars().count() + 2 + text.chars().count() > max_chars {
+            messages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(&text);
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+/// A channel alerts are delivered to
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Whether alerts of `kind` go to this channel at its level
+    fn is_enabled(&self, kind: AlertKind) -> bool;
+
+    /// Alerts arriving within this window of the first are sent together
+    fn batch_window(&self) -> Duration;
+
+    /// Messages sent at most per minute (0 = unlimited)
+    fn max_messages_per_minute(&self) -> u32;
+
+    /// Alerts one message carries at most
+    fn max_alerts_per_message(&self) -> usize;
+
+    /// Deliver `alerts` as one message, noting `dropped` alerts lost to a burst
+    fn send<'a>(&'a self, alerts: &'a [Alert], dropped: u64) -> BoxFuture<'a, Result<()>>;
+}
+
+/// Alerts rendered through the templates into a Telegram chat
+pub struct TelegramNotifier {
+    telegram: Arc<TelegramService>,
+    chat_id: String,
+    settings: AlertSettings,
+}
+
+impl TelegramNotifier {
+    pub fn new(telegram: Arc<TelegramService>, chat_id: String, settings: AlertSettings) -> Self {
+        Self { telegram, chat_id, settings }
+    }
+}
+
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn is_enabled(&self, kind: AlertKind) -> bool {
+        self.settings.is_enabled(kind)
+    }
+
+    fn batch_window(&self) -> Duration {
+        Duration::from_millis(self.settings.batch_window_ms)
+    }
+
+    fn max_messages_per_minute(&self) -> u32 {
+        self.settings.max_messages_per_minute
+    }
+
+    fn max_alerts_per_message(&self) -> usize {
+        TELEGRAM_ALERTS_PER_MESSAGE
+    }
+
+    fn send<'a>(&'a self, alerts: &'a [Alert], dropped: u64) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut rendered = Vec::with_capacity(alerts.len() + 1);
+            if dropped > 0 {
+                rendered.push(format!("⏳ {} alerts dropped during a burst", dropped));
+            }
+            rendered.extend(alerts.iter().map(|alert| alert.render(self.settings.template(alert.kind))));
+            for message in join_messages(&rendered, MAX_MESSAGE_CHARS) {
+                self.telegram.send_message(&self.chat_id, &message, "HTML").await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Whether `NOTIFY_CHANNELS`, e.g. `telegram,discord`, sends alerts to `name`; every configured channel when unset
+pub fn channel_selected(name: &str) -> bool {
+    match std::env::var("NOTIFY_CHANNELS") {
+        Ok(channels) if !channels.trim().is_empty() => channels
+            .split(',')
+            .any(|channel| channel.trim().eq_ignore_ascii_case(name)),
+        _ => true,
+    }
+}
+
+struct Channel {
+    notifier: Arc<dyn Notifier>,
+    batch: Mutex<AlertBatch>,
+}
+
+impl Channel {
+    fn next_batch(&self) -> Option<(Vec<Alert>, u64)> {
+        self.batch.lock().unwrap_or_else(|e| e.into_inner()).flush(
+            self.notifier.batch_window(),
+            self.notifier.max_messages_per_minute(),
+            self.notifier.max_alerts_per_message(),
+            Instant::now(),
+        )
+    }
+}
+
+static CHANNELS: LazyLock<Mutex<Vec<Arc<Channel>>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Send trade, stop loss, filter and debug alerts to `notifiers`, each batched and rate limited on its own
+pub fn set_alerts(notifiers: Vec<Arc<dyn Notifier>>, logger: Logger) {
+    let channels: Vec<Arc<Channel>> = notifiers
+        .into_iter()
+        .map(|notifier| Arc::new(Channel { notifier, batch: Mutex::new(AlertBatch::default()) }))
+        .collect();
+    *CHANNELS.lock().unwrap_or_else(|e| e.into_inner()) = channels.clone();
+    for channel in channels {
+        let logger = logger.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(250));
+            loop {
+                interval.tick().await;
+                let Some((alerts, dropped)) = channel.next_batch() else {
+                    continue;
+                };
+                if let Err(e) = channel.notifier.send(&alerts, dropped).await {
+                    logger.error(format!("Failed to send {} alerts to {}: {}", alerts.len(), channel.notifier.name(), e));
+                }
+            }
+        });
+    }
+}
+
+/// Queue `alert` on every channel that has its level enabled
+pub fn alert(alert: Alert) {
+    let channels = CHANNELS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let now = Instant::now();
+    for channel in channels.iter().filter(|channel| channel.notifier.is_enabled(alert.kind)) {
+        channel.batch.lock().unwrap_or_else(|e| e.into_inner()).push(alert.clone(), now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_and_fills_placeholders() {
+        let alert = Alert::stop_loss("Mint111", "price <20% of entry");
+        assert_eq!(
+            alert.render("{side} {mint} {pnl} {reason} {pumpfun}"),
+            "SELL Mint111 - price &lt;20% of entry https://pump.fun/coin/Mint111"
+        );
+        assert_eq!(alert.render("{code}"), "StopLossTriggered");
+        assert_eq!(Alert::debug("Mint111", "note").render("{code}"), "-");
+    }
+
+    #[test]
+    fn test_notify_level_filters_kinds() {
+        let settings = AlertSettings { level: NotifyLevel::Trades, ..AlertSettings::from_env() };
+        assert!(settings.is_enabled(AlertKind::StopLoss));
+        assert!(!settings.is_enabled(AlertKind::Filter));
+        assert_eq!(NotifyLevel::parse("Debug"), Some(NotifyLevel::Debug));
+    }
+
+    #[test]
+    fn test_batch_flushes_after_window() {
+        let window = Duration::from_secs(2);
+        let now = Instant::now();
+        let mut batch = AlertBatch::default();
+        batch.push(Alert::debug("one", "one"), now);
+        batch.push(Alert::debug("two", "two"), now + Duration::from_secs(1));
+        assert_eq!(batch.flush(window, 2, 10, now + Duration::from_secs(1)), None);
+        let (alerts, dropped) = batch.flush(window, 2, 10, now + window).unwrap();
+        assert_eq!((alerts.len(), dropped), (2, 0));
+    }
+
+    #[test]
+    fn test_batch_holds_past_rate_limit() {
+        let window = Duration::from_secs(2);
+        let now = Instant::now();
+        let mut batch = AlertBatch::default();
+        batch.push(Alert::debug("one", "one"), now);
+        assert!(batch.flush(window, 2, 10, now + window).is_some());
+        batch.push(Alert::debug("two", "two"), now + window);
+        assert!(batch.flush(window, 2, 10, now + Duration::from_secs(4)).is_some());
+        // two messages this minute already
+        batch.push(Alert::debug("three", "three"), now + Duration::from_secs(5));
+        assert_eq!(batch.flush(window, 2, 10, now + Duration::from_secs(10)), None);
+        // the first message leaves the minute
+        let (alerts, _) = batch.flush(window, 2, 10, now + Duration::from_secs(62)).unwrap();
+        assert_eq!(alerts[0].mint, "three");
+    }
+
+    #[test]
+    fn test_join_messages_splits_at_limit() {
+        let messages = join_messages(&["x".repeat(3000), "y".repeat(3000), "z".repeat(5000)], 4096);
+        assert_eq!(messages.iter().map(|m| m.len()).collect::<Vec<_>>(), vec![3000, 3000, 4096]);
+        assert_eq!(join_messages(&["a".to_string(), "b".to_string()], 4096), vec!["a\n\nb"]);
+    }
+}
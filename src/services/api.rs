@@ -0,0 +1,483 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use anyhow::{anyhow, Result};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use subtle::ConstantTimeEq;
+
+use crate::common::arming::is_armed;
+use crate::common::blacklist::Blacklist;
+use crate::common::config::Config;
+use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::balance_monitor::balance_guard;
+use crate::engine::budget::budget_manager;
+use crate::engine::circuit_breaker::circuit_breaker;
+use crate::engine::copy_trade::target_watch;
+use crate::engine::execution_queue::queued_tasks;
+use crate::engine::latency::latency_tracer;
+use crate::engine::panic::panic_sell;
+use crate::engine::position_marks::position_marks;
+use crate::engine::shadow::{shadow_book, shadow_overrides, FilterConfig};
+use crate::engine::slot_sync::slot_sync;
+use crate::engine::strategy_ledger::{strategy_ledger, MANUAL_STRATEGY};
+use crate::engine::fee_ledger::fee_ledger;
+use crate::engine::filter_stats::filter_stats;
+use crate::engine::token_rules::{load_token_rules, TokenRuleSettings};
+use crate::engine::trade_executor::{is_trading_paused, pause_trading, resume_trading, TradeExecutor};
+use crate::services::leaderboard::leaderboard;
+use crate::services::price_feed::sol_usd;
+use crate::services::signals::{Signal, SignalSettings, SignalSide, SIGNAL_STRATEGY};
+use crate::services::telegram::TelegramFilterSettings;
+
+/// HTTP control API settings
+#[derive(Debug, Clone)]
+pub struct ApiSettings {
+    /// Whether the control API is served
+    pub enabled: bool,
+    /// Address the API binds to
+    pub bind_address: String,
+    /// Port the API listens on
+    pub port: u16,
+    /// Bearer token required on every request
+    pub token: String,
+}
+
+impl ApiSettings {
+    /// Load API settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("API_ENABLED").unwrap_or_default() == "true",
+            bind_address: std::env::var("API_BIND_ADDRESS").unwrap_or_else(|_| "127.0.0.1".to_string()),
+            port: std::env::var("API_PORT")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(8787),
+            token: std::env::var("API_TOKEN").unwrap_or_default(),
+        }
+    }
+}
+
+/// Shared state handed to every API handler
+#[derive(Clone)]
+pub struct ApiState {
+    pub token: Arc<String>,
+    pub executor: Arc<TradeExecutor>,
+    pub filter_settings: Arc<Mutex<TelegramFilterSettings>>,
+    pub blacklist: Arc<tokio::sync::Mutex<Blacklist>>,
+    pub signals: Arc<SignalSettings>,
+    pub started_at: Instant,
+    pub logger: Logger,
+}
+
+type ApiResult = Result<Json<Value>, (StatusCode, Json<Value>)>;
+
+fn api_error(status: StatusCode, message: impl ToString) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message.to_string() })))
+}
+
+fn bearer(headers: &HeaderMap) -> &str {
+    headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default()
+}
+
+/// Whether a presented token matches the configured one, compared in constant time
+///
+/// An empty configured token never matches.
+fn token_matches(provided: &str, expected: &str) -> bool {
+    !provided.is_empty() && !expected.is_empty() && bool::from(provided.as_bytes().ct_eq(expected.as_bytes()))
+}
+
+/// Reject requests without the configured `Authorization: Bearer <API_TOKEN>` header
+fn authorize(state: &ApiState, headers: &HeaderMap) -> Result<(), (StatusCode, Json<Value>)> {
+    if !token_matches(bearer(headers), &state.token) {
+        return Err(api_error(StatusCode::UNAUTHORIZED, "invalid or missing API token"));
+    }
+    Ok(())
+}
+
+/// Like `authorize`, also accepting `SIGNAL_TOKEN`
+fn authorize_signal(state: &ApiState, headers: &HeaderMap) -> Result<(), (StatusCode, Json<Value>)> {
+    if token_matches(bearer(headers), &state.signals.token) {
+        return Ok(());
+    }
+    authorize(state, headers)
+}
+
+/// Landing record of same-block copies, with the share that made the target's block
+fn same_block_status() -> Value {
+    let sync = slot_sync();
+    json!({
+        "landings": sync.stats(),
+        "same_block_percent": sync.stats().same_block_percent(),
+    })
+}
+
+async fn status(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let open_positions = state.executor.positions().len();
+    Ok(Json(json!({
+        "armed": is_armed(),
+        "paused": is_trading_paused(),
+        "uptime_secs": state.started_at.elapsed().as_secs(),
+        "open_positions": open_positions,
+        "budget": budget_manager().status(),
+        "circuit_breaker": circuit_breaker().status(),
+        "balance": balance_guard().status(),
+        "strategies": strategy_ledger().report(),
+        "fees": fee_ledger().report(),
+        "latency": latency_tracer().report(),
+        "queued_tasks": queued_tasks(),
+        "same_block": same_block_status(),
+        "sol_usd": sol_usd(),
+    })))
+}
+
+async fn positions(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let positions: Vec<Value> = state.executor.positions()
+        .snapshot()
+        .iter()
+        .map(|pool| {
+            let mark = position_marks().get(&pool.mint);
+            json!({
+                "mint": pool.mint,
+                "buy_price": pool.buy_price,
+                "sell_price": pool.sell_price,
+                "status": format!("{:?}", pool.status),
+                "age_secs": pool.timestamp.map(|t| t.elapsed().as_secs()),
+                "last_price": mark.map(|mark| mark.last_price),
+                "unrealized_pnl_percent": mark.map(|mark| mark.unrealized_pnl_percent()),
+                "unrealized_pnl_sol": mark.and_then(|mark| mark.unrealized_pnl_sol()),
+                "max_drawdown_percent": mark.map(|mark| mark.max_drawdown_percent),
+                "marked_secs_ago": mark.map(|mark| mark.updated_at.elapsed().as_secs()),
+            })
+        })
+        .collect();
+    Ok(Json(json!(positions)))
+}
+
+async fn stats(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let stats = filter_stats();
+    Ok(Json(json!({
+        "follow_up_secs": stats.settings().follow_up_secs,
+        "winner_percent": stats.settings().winner_percent,
+        "filters": stats.report(chrono::Utc::now()),
+    })))
+}
+
+async fn targets(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let watch = target_watch();
+    let activity: Vec<Value> = watch
+        .activity(tokio::time::Instant::now())
+        .into_iter()
+        .map(|(wallet, activity, ago)| json!({
+            "wallet": wallet,
+            "paused": watch.is_paused(&wallet),
+            "last_trade_secs_ago": ago.map(|ago| ago.as_secs()),
+            "activity": activity,
+        }))
+        .collect();
+    Ok(Json(json!({ "targets": activity, "paused": watch.paused() })))
+}
+
+async fn resume_target(State(state): State<ApiState>, headers: HeaderMap, Path(wallet): Path<String>) -> ApiResult {
+    authorize(&state, &headers)?;
+    let resumed = target_watch().resume(&wallet);
+    Ok(Json(json!({ "wallet": wallet, "resumed": resumed })))
+}
+
+async fn get_leaderboard(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    Ok(Json(json!(*leaderboard())))
+}
+
+async fn shadow(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let book = shadow_book();
+    let overrides: serde_json::Map<String, Value> = shadow_overrides()
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+    Ok(Json(json!({
+        "enabled": book.settings().enabled,
+        "overrides": overrides,
+        "live": book.stats(FilterConfig::Live),
+        "shadow": book.stats(FilterConfig::Shadow),
+        "agreement": book.agreement(),
+    })))
+}
+
+async fn get_config(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let settings = state.filter_settings
+        .lock()
+        .map_err(|_| api_error(StatusCode::INTERNAL_SERVER_ERROR, "config lock poisoned"))?
+        .clone();
+    Ok(Json(serde_json::to_value(settings).unwrap_or_default()))
+}
+
+/// Merge a partial JSON object into the filter settings and persist them
+async fn patch_config(State(state): State<ApiState>, headers: HeaderMap, Json(patch): Json<Value>) -> ApiResult {
+    authorize(&state, &headers)?;
+    let mut settings = state.filter_settings
+        .lock()
+        .map_err(|_| api_error(StatusCode::INTERNAL_SERVER_ERROR, "config lock poisoned"))?;
+
+    let mut current = serde_json::to_value(&*settings)
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    merge_json(&mut current, patch);
+    let updated: TelegramFilterSettings = serde_json::from_value(current.clone())
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, format!("invalid config: {}", e)))?;
+
+    *settings = updated;
+    if let Err(e) = settings.save_to_file() {
+        state.logger.error(format!("Failed to persist config patch: {}", e));
+    }
+    state.logger.log("Config patched via API".yellow().to_string());
+    Ok(Json(current))
+}
+
+/// Re-read the environment into a new config snapshot; running systems pick it up on their next read
+async fn reload_config(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    Config::reload().await;
+    load_token_rules(&TokenRuleSettings::from_env(), &state.logger);
+    state.logger.log("Config reloaded via API".yellow().to_string());
+    Ok(Json(json!({ "reloaded": true })))
+}
+
+async fn pause(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    pause_trading();
+    state.logger.log("⏸️  Trading paused via API".yellow().to_string());
+    Ok(Json(json!({ "paused": true })))
+}
+
+async fn resume(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    resume_trading();
+    state.logger.log("▶️  Trading resumed via API".green().to_string());
+    Ok(Json(json!({ "paused": false })))
+}
+
+/// Pause buying, drop queued buys and sell everything at any price
+async fn panic(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    state.logger.log("🚨 Panic sell via API".red().bold().to_string());
+    let report = panic_sell(&state.executor, &state.logger).await;
+    Ok(Json(json!(report)))
+}
+
+async fn reset_breaker(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    circuit_breaker().reset();
+    state.logger.log("Circuit breaker reset via API".green().to_string());
+    Ok(Json(json!({ "circuit_breaker": circuit_breaker().status() })))
+}
+
+#[derive(Deserialize)]
+struct BuyRequest {
+    mint: String,
+    amount_sol: Option<f64>,
+    /// Tokens to receive (base units) instead of SOL to spend
+    amount_tokens: Option<u64>,
+}
+
+async fn buy(State(state): State<ApiState>, headers: HeaderMap, Json(request): Json<BuyRequest>) -> ApiResult {
+    authorize(&state, &headers)?;
+    if let Some(token_amount) = request.amount_tokens {
+        let (amount, signature) = state.executor
+            .buy_tokens(MANUAL_STRATEGY, &request.mint, token_amount, None)
+            .await
+            .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+        return Ok(Json(json!({
+            "mint": request.mint,
+            "amount_sol": amount,
+            "amount_tokens": token_amount,
+            "signature": signature,
+        })));
+    }
+    let amount = match request.amount_sol {
+        Some(amount) => amount,
+        None => state.executor
+            .size_buy(None, None)
+            .await
+            .map_err(|e| api_error(StatusCode::BAD_GATEWAY, e))?,
+    };
+    let signature = state.executor
+        .buy(&request.mint, amount)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    Ok(Json(json!({ "mint": request.mint, "amount_sol": amount, "signature": signature })))
+}
+
+#[derive(Deserialize)]
+struct SellRequest {
+    mint: String,
+    percent: Option<f64>,
+    /// SOL proceeds to receive instead of a share of the balance
+    amount_sol: Option<f64>,
+}
+
+async fn sell(State(state): State<ApiState>, headers: HeaderMap, Json(request): Json<SellRequest>) -> ApiResult {
+    authorize(&state, &headers)?;
+    record_decision(Decision::Sell, &request.mint, ReasonCode::ManualSell, "api");
+    if let Some(amount) = request.amount_sol {
+        let (token_amount, signature) = state.executor
+            .sell_for_sol(&request.mint, amount, None)
+            .await
+            .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+        return Ok(Json(json!({
+            "mint": request.mint,
+            "amount_sol": amount,
+            "amount_tokens": token_amount,
+            "signature": signature,
+        })));
+    }
+    let percent = request.percent.unwrap_or(100.0);
+    let signature = state.executor
+        .sell(&request.mint, percent)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+    Ok(Json(json!({ "mint": request.mint, "percent": percent, "signature": signature })))
+}
+
+/// Trade on an external signal through the same limits, budget and relays as the strategies
+async fn signal(State(state): State<ApiState>, headers: HeaderMap, Json(signal): Json<Signal>) -> ApiResult {
+    if !state.signals.enabled {
+        return Err(api_error(StatusCode::NOT_FOUND, "signal endpoint is disabled"));
+    }
+    authorize_signal(&state, &headers)?;
+    signal.validate(&state.signals).map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+
+    match signal.side {
+        SignalSide::Buy => {
+            if state.blacklist.lock().await.is_blacklisted(&signal.mint) {
+                return Err(api_error(StatusCode::FORBIDDEN, format!("{} is blacklisted", signal.mint)));
+            }
+            let amount = match signal.amount {
+                Some(amount) => amount,
+                None => state.executor
+                    .size_buy(None, None)
+                    .await
+                    .map_err(|e| api_error(StatusCode::BAD_GATEWAY, e))?
+                    .min(state.signals.max_buy_sol),
+            };
+            state.logger.log(format!(
+                "Signal from {}: buying {} for {:.4} SOL", signal.source(), signal.mint, amount
+            ).magenta().to_string());
+            let signature = state.executor
+                .buy_with_slippage(SIGNAL_STRATEGY, &signal.mint, amount, signal.max_slippage)
+                .await
+                .map_err(|e| api_error(StatusCode::CONFLICT, e))?;
+            Ok(Json(json!({ "mint": signal.mint, "side": "buy", "amount_sol": amount, "signature": signature })))
+        }
+        SignalSide::Sell => {
+            let percent = signal.amount.unwrap_or(100.0);
+            state.logger.log(format!(
+                "Signal from {}: selling {:.1}% of {}", signal.source(), percent, signal.mint
+            ).yellow().to_string());
+            let signature = state.executor
+                .sell_with_slippage(&signal.mint, percent, signal.max_slippage)
+                .await
+                .map_err(|e| api_error(StatusCode::CONFLICT, e))?;
+            Ok(Json(json!({ "mint": signal.mint, "side": "sell", "percent": percent, "signature": signature })))
+        }
+    }
+}
+
+async fn get_blacklist(State(state): State<ApiState>, headers: HeaderMap) -> ApiResult {
+    authorize(&state, &headers)?;
+    let blacklist = state.blacklist.lock().await;
+    Ok(Json(json!(blacklist.get_addresses())))
+}
+
+#[derive(Deserialize)]
+struct BlacklistRequest {
+    address: String,
+}
+
+async fn add_blacklist(State(state): State<ApiState>, headers: HeaderMap, Json(request): Json<BlacklistRequest>) -> ApiResult {
+    authorize(&state, &headers)?;
+    let mut blacklist = state.blacklist.lock().await;
+    let added = blacklist.add_address(&request.address);
+    blacklist.save().map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(Json(json!({ "address": request.address, "added": added })))
+}
+
+async fn remove_blacklist(State(state): State<ApiState>, headers: HeaderMap, Path(address): Path<String>) -> ApiResult {
+    authorize(&state, &headers)?;
+    let mut blacklist = state.blacklist.lock().await;
+    let removed = blacklist.remove_address(&address);
+    blacklist.save().map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(Json(json!({ "address": address, "removed": removed })))
+}
+
+/// Recursively merge `patch` into `target`, replacing non-object values
+fn merge_json(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+/// Build the control API router
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/status", get(status))
+        .route("/positions", get(positions))
+        .route("/stats", get(stats))
+        .route("/shadow", get(shadow))
+        .route("/leaderboard", get(get_leaderboard))
+        .route("/targets", get(targets))
+        .route("/targets/:wallet/resume", post(resume_target))
+        .route("/config", get(get_config).patch(patch_config))
+        .route("/config/reload", post(reload_config))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .route("/panic", post(panic))
+        .route("/breaker/reset", post(reset_breaker))
+        .route("/buy", post(buy))
+        .route("/sell", post(sell))
+        .route("/signal", post(signal))
+        .route("/blacklist", get(get_blacklist).post(add_blacklist))
+        .route("/blacklist/:address", delete(remove_blacklist))
+        .with_state(state)
+}
+
+/// Serve the control API in the background
+pub async fn start_api_server(settings: ApiSettings, state: ApiState) -> Result<()> {
+    if settings.token.is_empty() {
+        return Err(anyhow!("API_ENABLED=true requires API_TOKEN to be set"));
+    }
+
+    let addr: SocketAddr = format!("{}:{}", settings.bind_address, settings.port).parse()?;
+    let logger = state.logger.clone();
+    logger.log(format!("🌐 Control API listening on http://{}", addr).green().to_string());
+
+    let app = router(state);
+    tokio::spawn(async move {
+        if let Err(e) = axum::Server::bind(&addr).serve(app.into_make_service()).await {
+            logger.error(format!("Control API stopped: {}", e));
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::anyhow;
+use futures_util::StreamExt;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::SubscribeUpdate;
+
+use crate::services::geyser::UpdateStream;
+use crate::services::metrics::metrics;
+
+/// Stream backpressure settings
+#[derive(Debug, Clone)]
+pub struct BackpressureSettings {
+    pub enabled: bool,
+    /// Updates queued at most between a stream and its consumer
+    pub capacity: usize,
+    /// Bulk updates queued longer than this are dropped unprocessed, in milliseconds (0 = never)
+    pub max_lag_ms: u64,
+}
+
+impl BackpressureSettings {
+    /// Load stream backpressure settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("STREAM_BACKPRESSURE_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            capacity: std::env::var("STREAM_QUEUE_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(4096),
+            max_lag_ms: std::env::var("STREAM_MAX_LAG_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(2000),
+        }
+    }
+}
+
+/// How an update is treated when its consumer falls behind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventClass {
+    /// Never dropped: pings, stream errors and updates of held positions or followed wallets
+    Critical,
+    /// Dropped oldest first when the queue is full, and once it waited longer than the lag limit
+    Bulk,
+}
+
+/// FIFO with a soft capacity that sheds bulk items first
+pub struct BoundedQueue<T> {
+    capacity: usize,
+    max_lag: Option<Duration>,
+    items: VecDeque<(T, EventClass, Instant)>,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize, max_lag: Option<Duration>) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            max_lag,
+            items: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Queue `item`, returning whether a bulk item was dropped to keep the queue bounded
+    ///
+    /// A full queue drops its oldest bulk item. When only critical items are queued, an
+    /// incoming bulk item is dropped instead and a critical one goes over the capacity.
+    pub fn push(&mut self, item: T, class: EventClass, now: Instant) -> bool {
+        let mut dropped = false;
+        if self.items.len() >= self.capacity {
+            match self.items.iter().position(|(_, queued, _)| *queued == EventClass::Bulk) {
+                Some(index) => {
+                    self.items.remove(index);
+                    dropped = true;
+                }
+                None if class == EventClass::Bulk => return true,
+                None => {}
+            }
+        }
+        self.items.push_back((item, class, now));
+        dropped
+    }
+
+    /// Next item to process and how many stale bulk items were skipped to reach it
+    pub fn pop(&mut self, now: Instant) -> (Option<T>, u64) {
+        let mut stale = 0;
+        while let Some((item, class, queued_at)) = self.items.pop_front() {
+            let too_old = self.max_lag.map(|lag| now.duration_since(queued_at) > lag).unwrap_or(false);
+            if class == EventClass::Bulk && too_old {
+                stale += 1;
+                continue;
+            }
+            return (Some(item), stale);
+        }
+        (None, stale)
+    }
+}
+
+struct Shared {
+    queue: Mutex<BoundedQueue<anyhow::Result<SubscribeUpdate>>>,
+    ready: Notify,
+}
+
+/// Stops the reader once the consumer drops its end
+struct Reader(JoinHandle<()>);
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Read `stream` on its own task into a bounded queue, consumed through the returned stream
+///
+/// The connection is drained as fast as it delivers however slow the consumer is, so the
+/// server never backs up; `classify` decides which updates may be dropped when it does.
+/// Drops and queue depth are exported per `name`. Errors end the stream as before.
+pub fn buffered(
+    name: &'static str,
+    stream: UpdateStream,
+    settings: &BackpressureSettings,
+    classify: fn(&SubscribeUpdate) -> EventClass,
+) -> UpdateStream {
+    if !settings.enabled {
+        return stream;
+    }
+    let max_lag = (settings.max_lag_ms > 0).then(|| Duration::from_millis(settings.max_lag_ms));
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(BoundedQueue::new(settings.capacity, max_lag)),
+        ready: Notify::new(),
+    });
+
+    let reader_shared = shared.clone();
+    let reader = Reader(tokio::spawn(async move {
+        let mut stream = stream;
+        loop {
+            let (item, class) = match stream.next().await {
+                Some(Ok(update)) => {
+                    let class = classify(&update);
+                    (Ok(update), class)
+                }
+                Some(Err(e)) => (Err(e), EventClass::Critical),
+                None => (Err(anyhow!("Stream closed")), EventClass::Critical),
+            };
+            let done = item.is_err();
+            {
+                let mut queue = reader_shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+                if queue.push(item, class, Instant::now()) {
+                    metrics().stream_dropped.with_label_values(&[name, "full"]).inc();
+                }
+                metrics().stream_queued.with_label_values(&[name]).set(queue.len() as i64);
+            }
+            reader_shared.ready.notify_one();
+            if done {
+                return;
+            }
+        }
+    }));
+
+    Box::pin(futures_util::stream::unfold((shared, reader), move |(shared, reader)| async move {
+        loop {
+            let (item, stale) = {
+                let mut queue = shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+                let popped = queue.pop(Instant::now());
+                metrics().stream_queued.with_label_values(&[name]).set(queue.len() as i64);
+                popped
+            };
+            if stale > 0 {
+                metrics().stream_dropped.with_label_values(&[name, "stale"]).inc_by(stale);
+            }
+            if let Some(item) = item {
+                return Some((item, (shared, reader)));
+            }
+            shared.ready.notified().await;
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_shed_first_and_critical_kept() {
+        let now = Instant::now();
+        let mut queue = BoundedQueue::new(2, Some(Duration::from_millis(500)));
+        assert!(!queue.push("bulk 1", EventClass::Bulk, now));
+        assert!(!queue.push("ping", EventClass::Critical, now));
+        // the oldest bulk item makes room
+        assert!(queue.push("held trade", EventClass::Critical, now));
+        // nothing bulk left: bulk is refused, critical goes over the capacity
+        assert!(queue.push("bulk 2", EventClass::Bulk, now));
+        assert!(!queue.push("target trade", EventClass::Critical, now));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(now), (Some("ping"), 0));
+
+        let mut queue = BoundedQueue::new(10, Some(Duration::from_millis(500)));
+        queue.push("stale", EventClass::Bulk, now);
+        queue.push("old but critical", EventClass::Critical, now);
+        queue.push("fresh", EventClass::Bulk, now + Duration::from_millis(400));
+        let later = now + Duration::from_millis(600);
+        assert_eq!(queue.pop(later), (Some("old but critical"), 1));
+        assert_eq!(queue.pop(later), (Some("fresh"), 0));
+        assert_eq!(queue.pop(later), (None, 0));
+    }
+}
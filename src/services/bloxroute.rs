@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use anchor_client::solana_sdk::{signature::Signature, transaction::VersionedTransaction};
+use std::{str::FromStr, sync::LazyLock};
+
+pub static BLOXROUTE_URL: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("BLOXROUTE_URL").unwrap_or_else(|_| "https://ny.solana.dex.blxrbdn.com".to_string())
+});
+/// Same `AUTH_HEADER` used by `BloxRouteConfig`
+pub static BLOXROUTE_AUTH_HEADER: LazyLock<String> =
+    LazyLock::new(|| std::env::var("AUTH_HEADER").unwrap_or_default());
+
+/// Whether bloXroute credentials are configured
+pub fn is_configured() -> bool {
+    !BLOXROUTE_AUTH_HEADER.is_empty()
+}
+
+/// Minimal client for the bloXroute Trader API submit endpoint
+pub struct BloxrouteClient {
+    client: reqwest::Client,
+    base_url: String,
+    auth_header: String,
+}
+
+impl BloxrouteClient {
+    pub fn new(base_url: &str, auth_header: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_header: auth_header.to_string(),
+        }
+    }
+
+    /// Submit a signed transaction, returning its signature
+    pub async fn send_transaction(&self, txn: &VersionedTransaction) -> Result<Signature> {
+        let serialized = bincode::serialize(txn)?;
+        let content = base64::encode(serialized);
+
+        let response: Value = self.client
+            .post(format!("{}/api/v2/submit", self.base_url))
+            .header("Authorization", &self.auth_header)
+            .json(&json!({
+                "transaction": { "content": content },
+                "skipPreFlight": true,
+                "frontRunningProtection": false,
+            }))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response.get("signature").and_then(|s| s.as_str()) {
+            Some(signature) => Ok(Signature::from_str(signature)?),
+            None => Err(anyhow!("bloXroute submit failed: {}", response)),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Stream dedup settings
+#[derive(Debug, Clone)]
+pub struct DedupSettings {
+    pub enabled: bool,
+    /// How long a signature is remembered after it was first seen
+    pub ttl_secs: u64,
+    /// Signatures remembered at most; the oldest are dropped first
+    pub capacity: usize,
+}
+
+impl DedupSettings {
+    /// Load stream dedup settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("STREAM_DEDUP_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase() == "true",
+            ttl_secs: std::env::var("STREAM_DEDUP_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60),
+            capacity: std::env::var("STREAM_DEDUP_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(20_000),
+        }
+    }
+}
+
+/// Transaction signatures recently handled by one consumer of the stream
+///
+/// The same transaction arrives more than once when it is streamed over redundant
+/// connections or replayed after a reconnect; only its first copy should trigger
+/// anything. Entries expire after the TTL and the oldest go first when full.
+pub struct SignatureCache {
+    settings: DedupSettings,
+    order: VecDeque<([u8; 64], Instant)>,
+    seen: HashSet<[u8; 64]>,
+}
+
+impl SignatureCache {
+    pub fn new(settings: DedupSettings) -> Self {
+        Self {
+            settings,
+            order: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Record `signature`, returning false when it was already seen within the TTL
+    ///
+    /// Malformed signatures are never treated as duplicates.
+    pub fn first_seen(&mut self, signature: &[u8], now: Instant) -> bool {
+        if !self.settings.enabled {
+            return true;
+        }
+        let Ok(signature) = <[u8; 64]>::try_from(signature) else {
+            return true;
+        };
+        let ttl = Duration::from_secs(self.settings.ttl_secs);
+        while let Some((oldest, at)) = self.order.front() {
+            if now.duration_since(*at) < ttl && self.order.len() < self.settings.capacity.max(1) {
+                break;
+            }
+            self.seen.remove(oldest);
+            self.order.pop_front();
+        }
+        if !self.seen.insert(signature) {
+            return false;
+        }
+        self.order.push_back((signature, now));
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> SignatureCache {
+        SignatureCache::new(DedupSettings { enabled: true, ttl_secs: 60, capacity: 2 })
+    }
+
+    #[test]
+    fn test_repeat_signature_is_dropped() {
+        let mut cache = cache();
+        let now = Instant::now();
+        assert!(cache.first_seen(&[1; 64], now));
+        assert!(!cache.first_seen(&[1; 64], now));
+        assert!(cache.first_seen(&[2; 64], now));
+    }
+
+    #[test]
+    fn test_malformed_signature_is_never_a_duplicate() {
+        let mut cache = cache();
+        let now = Instant::now();
+        assert!(cache.first_seen(&[1; 32], now));
+        assert!(cache.first_seen(&[1; 32], now));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_full_cache_evicts_oldest() {
+        let mut cache = cache();
+        let now = Instant::now();
+        cache.first_seen(&[1; 64], now);
+        cache.first_seen(&[2; 64], now);
+        assert!(cache.first_seen(&[3; 64], now));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.first_seen(&[1; 64], now));
+    }
+
+    #[test]
+    fn test_signature_expires_after_ttl() {
+        let mut cache = cache();
+        let now = Instant::now();
+        cache.first_seen(&[1; 64], now);
+        let later = now + Duration::from_secs(61);
+        assert!(cache.first_seen(&[1; 64], later));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_disabled_cache_passes_everything() {
+        let mut cache = SignatureCache::new(DedupSettings { enabled: false, ttl_secs: 60, capacity: 2 });
+        let now = Instant::now();
+        assert!(cache.first_seen(&[1; 64], now));
+        assert!(cache.first_seen(&[1; 64], now));
+    }
+}
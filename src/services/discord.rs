@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use crate::services::alerts::{Alert, AlertKind, Notifier, NotifyLevel};
+use crate::services::pump_api::{pump_api, PumpCoin};
+
+/// Discord accepts at most this many embeds per message
+const MAX_EMBEDS: usize = 10;
+/// Coin pages cached at most for embed titles before the cache starts over
+const MAX_CACHED_COINS: usize = 1000;
+
+/// Discord webhook notifier settings
+#[derive(Debug, Clone)]
+pub struct DiscordSettings {
+    /// Webhook URL from the channel's integrations; empty turns Discord alerts off
+    pub webhook_url: String,
+    pub level: NotifyLevel,
+    /// Name the messages are posted under, empty for the webhook's own
+    pub username: String,
+    /// Alerts arriving within this window of the first are sent as one message, in milliseconds
+    pub batch_window_ms: u64,
+    /// Messages sent at most per minute (0 = unlimited), Discord allows 30 per webhook
+    pub max_messages_per_minute: u32,
+}
+
+impl DiscordSettings {
+    /// Load Discord settings from environment variables
+    ///
+    /// The level falls back to `TELEGRAM_NOTIFY_LEVEL`, so both channels match unless told otherwise.
+    pub fn from_env() -> Self {
+        Self {
+            webhook_url: std::env::var("DISCORD_WEBHOOK_URL").unwrap_or_default().trim().to_string(),
+            level: std::env::var("DISCORD_NOTIFY_LEVEL")
+                .or_else(|_| std::env::var("TELEGRAM_NOTIFY_LEVEL"))
+                .ok()
+                .and_then(|v| NotifyLevel::parse(&v))
+                .unwrap_or(NotifyLevel::Trades),
+            username: std::env::var("DISCORD_USERNAME").unwrap_or_default(),
+            batch_window_ms: std::env::var("DISCORD_BATCH_WINDOW_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3000),
+            max_messages_per_minute: std::env::var("DISCORD_MAX_MESSAGES_PER_MINUTE")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(25),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        !self.webhook_url.is_empty()
+    }
+}
+
+fn color(kind: AlertKind) -> u32 {
+    match kind {
+        AlertKind::Buy => 0x2ecc71,
+        AlertKind::Sell => 0xe67e22,
+        AlertKind::Fail | AlertKind::StopLoss => 0xe74c3c,
+        AlertKind::Target => 0xf1c40f,
+        AlertKind::Filter => 0x95a5a6,
+        AlertKind::Debug => 0x3498db,
+    }
+}
+
+fn heading(kind: AlertKind) -> &'static str {
+    match kind {
+        AlertKind::Buy => "✅ BUY",
+        AlertKind::Sell => "🟥 SELL",
+        AlertKind::Fail => "⚠️ FAILED",
+        AlertKind::StopLoss => "🛑 STOP LOSS",
+        AlertKind::Target => "👁 TARGET",
+        AlertKind::Filter => "🚫 SKIPPED",
+        AlertKind::Debug => "🔧 DEBUG",
+    }
+}
+
+/// Rich embed for `alert`, titled with the token name when its coin page is known
+pub fn embed(alert: &Alert, coin: Option<&PumpCoin>) -> Value {
+    let token = match coin {
+        Some(coin) if !coin.name.is_empty() => format!("{} ({})", coin.name, coin.symbol),
+        _ => alert.mint.clone(),
+    };
+    let label = if alert.kind == AlertKind::Target { "Wallet" } else { "Mint" };
+    let mut fields = vec![json!({ "name": label, "value": format!("`{}`", alert.mint) })];
+    if let Some(sol) = alert.sol {
+        fields.push(json!({ "name": "Amount", "value": format!("{:.4} SOL", sol), "inline": true }));
+    }
+    if let Some(price) = alert.price {
+        let name = if alert.kind == AlertKind::Buy { "Buy price" } else { "Price" };
+        fields.push(json!({ "name": name, "value": format!("{:.10} SOL", price), "inline": true }));
+    }
+    if let Some(pnl) = alert.pnl_percent {
+        fields.push(json!({ "name": "PnL", "value": format!("{:+.2}%", pnl), "inline": true }));
+    }
+    if let Some(filter) = &alert.filter {
+        fields.push(json!({ "name": "Filter", "value": filter, "inline": true }));
+    }
+    if let Some(code) = alert.code {
+        fields.push(json!({ "name": "Reason", "value": format!("`{}`", code), "inline": true }));
+    }
+    fields.push(json!({
+        "name": "Links",
+        "value": format!("[Solscan]({}) | [pump.fun]({})", alert.solscan_url(), alert.pumpfun_url()),
+    }));
+
+    let mut title = format!("{} {}", heading(alert.kind), token);
+    if alert.kind == AlertKind::Fail {
+        if let (Some(side), Some(status)) = (&alert.side, &alert.status) {
+            title = format!("⚠️ {} {} {}", side.to_uppercase(), status, token);
+        }
+    }
+    let mut embed = json!({
+        "title": title,
+        "url": alert.pumpfun_url(),
+        "color": color(alert.kind),
+        "fields": fields,
+        "timestamp": alert.timestamp.to_rfc3339(),
+    });
+    if let Some(reason) = &alert.reason {
+        embed["description"] = json!(reason);
+    }
+    if let Some(image) = coin.and_then(|coin| coin.image_uri.as_ref()).filter(|image| !image.is_empty()) {
+        embed["thumbnail"] = json!({ "url": image });
+    }
+    embed
+}
+
+/// Alerts posted as embeds to a Discord channel webhook
+pub struct DiscordNotifier {
+    settings: DiscordSettings,
+    client: reqwest::Client,
+    coins: Mutex<HashMap<String, Option<PumpCoin>>>,
+}
+
+impl DiscordNotifier {
+    pub fn new(settings: DiscordSettings) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            settings,
+            client,
+            coins: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Coin page of a traded mint, looked up once; filter and debug alerts skip it so a launch storm costs no API calls
+    async fn coin(&self, alert: &Alert) -> Option<PumpCoin> {
+        if matches!(alert.kind, AlertKind::Target | AlertKind::Filter | AlertKind::Debug) {
+            return None;
+        }
+        if let Some(coin) = self.coins.lock().unwrap_or_else(|e| e.into_inner()).get(&alert.mint) {
+            return coin.clone();
+        }
+        let coin = pump_api().coin(&alert.mint).await.ok().flatten();
+        let mut coins = self.coins.lock().unwrap_or_else(|e| e.into_inner());
+        if coins.len() >= MAX_CACHED_COINS {
+            coins.clear();
+        }
+        coins.insert(alert.mint.clone(), coin.clone());
+        coin
+    }
+
+    async fn post(&self, body: &Value) -> Result<()> {
+        for _ in 0..2 {
+            let response = self.client.post(&self.settings.webhook_url).json(body).send().await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                response.error_for_status()?;
+                return Ok(());
+            }
+            let retry_after = response.json::<Value>().await.ok()
+                .and_then(|body| body.get("retry_after").and_then(Value::as_f64))
+                .unwrap_or(1.0);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after.clamp(0.1, 30.0))).await;
+        }
+        Err(anyhow!("rate limited by Discord"))
+    }
+}
+
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    fn is_enabled(&self, kind: AlertKind) -> bool {
+        kind.level() <= self.settings.level
+    }
+
+    fn batch_window(&self) -> Duration {
+        Duration::from_millis(self.settings.batch_window_ms)
+    }
+
+    fn max_messages_per_minute(&self) -> u32 {
+        self.settings.max_messages_per_minute
+    }
+
+    fn max_alerts_per_message(&self) -> usize {
+        MAX_EMBEDS
+    }
+
+    fn send<'a>(&'a self, alerts: &'a [Alert], dropped: u64) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut embeds = Vec::with_capacity(alerts.len());
+            for alert in alerts {
+                let coin = self.coin(alert).await;
+                embeds.push(embed(alert, coin.as_ref()));
+            }
+            let mut body = json!({ "embeds": embeds });
+            if dropped > 0 {
+                body["content"] = json!(format!("⏳ {} alerts dropped during a burst", dropped));
+            }
+            if !self.settings.username.is_empty() {
+                body["username"] = json!(self.settings.username);
+            }
+            self.post(&body).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::trade_log::TradeLogEntry;
+    use chrono::Utc;
+
+    #[test]
+    fn test_trade_embed() {
+        let trade = TradeLogEntry {
+            timestamp: Utc::now(),
+            mint: "Mint111".to_string(),
+            side: "buy".to_string(),
+            status: "confirmed".to_string(),
+            signature: "sig".to_string(),
+            sol_amount: 0.25,
+            token_amount: 1000,
+            price: 0.00000003,
+            fee_sol: 0.000005,
+            tip_sol: 0.0,
+            attempts: 1,
+            latency_ms: 400,
+            reason: None,
+            reason_code: None,
+        };
+        let coin = PumpCoin {
+            mint: "Mint111".to_string(),
+            name: "Pepe".to_string(),
+            symbol: "PEPE".to_string(),
+            image_uri: Some("https://example.com/pepe.png".to_string()),
+            ..PumpCoin::default()
+        };
+        let embed = embed(&Alert::trade(&trade, None), Some(&coin));
+        assert_eq!(embed["title"], "✅ BUY Pepe (PEPE)");
+        assert_eq!(embed["url"], "https://pump.fun/coin/Mint111");
+        assert_eq!(embed["thumbnail"]["url"], "https://example.com/pepe.png");
+        let fields = embed["fields"].as_array().unwrap();
+        assert!(fields.iter().any(|field| field["name"] == "Buy price" && field["value"] == "0.0000000300 SOL"));
+        assert!(fields.iter().any(|field| field["value"] == "[Solscan](https://solscan.io/tx/sig) | [pump.fun](https://pump.fun/coin/Mint111)"));
+
+        let failed = embed(&Alert::trade(&TradeLogEntry { status: "dropped".to_string(), ..trade }, None), None);
+        assert_eq!(failed["title"], "⚠️ BUY dropped Mint111");
+        assert!(failed.get("thumbnail").is_none());
+    }
+}
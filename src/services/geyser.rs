@@ -0,0 +1,497 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
+use anyhow::{anyhow, Result};
+use futures::channel::mpsc;
+use futures_util::future::BoxFuture;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use rand::Rng;
+use tokio::sync::{watch, Semaphore};
+use tokio::time::Instant;
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::prelude::{SubscribeRequest, SubscribeUpdate};
+
+use crate::common::logger::Logger;
+use crate::services::health::{stream_health, update_lag, update_slot};
+use crate::services::metrics::metrics;
+use crate::services::telegram::TelegramService;
+
+/// Sends filter updates and pings on an open subscription
+pub type SubscribeSink = Pin<Box<dyn Sink<SubscribeRequest, Error = anyhow::Error> + Send>>;
+/// Updates received on an open subscription
+pub type UpdateStream = Pin<Box<dyn Stream<Item = Result<SubscribeUpdate>> + Send>>;
+
+/// gRPC provider behind YELLOWSTONE_GRPC_HTTP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeyserVendor {
+    /// Any Yellowstone (Dragon's Mouth) compatible endpoint
+    Yellowstone,
+    /// Helius LaserStream
+    Helius,
+    /// Triton One
+    Triton,
+}
+
+impl GeyserVendor {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "yellowstone" | "generic" => Some(Self::Yellowstone),
+            "helius" | "laserstream" => Some(Self::Helius),
+            "triton" => Some(Self::Triton),
+            _ => None,
+        }
+    }
+
+    /// Guess the vendor from the endpoint host
+    pub fn detect(endpoint: &str) -> Self {
+        let host = url::Url::parse(endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        if host.ends_with("helius-rpc.com") || host.ends_with("helius.xyz") {
+            Self::Helius
+        } else if host.ends_with("rpcpool.com") || host.ends_with("triton.one") {
+            Self::Triton
+        } else {
+            Self::Yellowstone
+        }
+    }
+}
+
+impl std::fmt::Display for GeyserVendor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeyserVendor::Yellowstone => write!(f, "Yellowstone"),
+            GeyserVendor::Helius => write!(f, "Helius LaserStream"),
+            GeyserVendor::Triton => write!(f, "Triton"),
+        }
+    }
+}
+
+/// gRPC source settings
+#[derive(Debug, Clone)]
+pub struct GeyserSettings {
+    pub vendor: GeyserVendor,
+    pub endpoint: String,
+    pub token: Option<String>,
+    /// First reconnect delay, in seconds
+    pub reconnect_delay_secs: u64,
+    /// Longest reconnect delay the backoff grows to, in seconds
+    pub max_backoff_secs: u64,
+    /// Consecutive failed reconnects before alerting and switching to the fallback (0 = never)
+    pub max_retries: u32,
+    /// Endpoint used once `max_retries` is exhausted on the primary one
+    pub fallback_endpoint: Option<String>,
+    pub fallback_token: Option<String>,
+    /// Concurrent streams allowed by the plan (0 = unlimited)
+    pub max_streams: usize,
+    /// Accounts allowed in one subscription filter (0 = unlimited)
+    pub max_filter_accounts: usize,
+}
+
+impl GeyserSettings {
+    /// Load gRPC source settings for the configured endpoint and token
+    ///
+    /// The vendor comes from GEYSER_VENDOR, or the endpoint host when unset.
+    pub fn from_env(endpoint: String, token: String, reconnect_delay_secs: u64, max_retries: u32) -> Self {
+        let vendor = std::env::var("GEYSER_VENDOR")
+            .ok()
+            .and_then(|v| GeyserVendor::parse(&v))
+            .unwrap_or_else(|| GeyserVendor::detect(&endpoint));
+        let (endpoint, token) = split_credentials(vendor, &endpoint, &token);
+        let fallback = std::env::var("GEYSER_FALLBACK_ENDPOINT")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|fallback| {
+                let token = std::env::var("GEYSER_FALLBACK_TOKEN").unwrap_or_default();
+                split_credentials(GeyserVendor::detect(&fallback), fallback.trim(), &token)
+            });
+        let (fallback_endpoint, fallback_token) = match fallback {
+            Some((endpoint, token)) => (Some(endpoint), token),
+            None => (None, None),
+        };
+        Self {
+            vendor,
+            endpoint,
+            token,
+            reconnect_delay_secs,
+            max_backoff_secs: std::env::var("GEYSER_MAX_BACKOFF_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60),
+            max_retries,
+            fallback_endpoint,
+            fallback_token,
+            max_streams: std::env::var("GEYSER_MAX_STREAMS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0),
+            max_filter_accounts: std::env::var("GEYSER_MAX_FILTER_ACCOUNTS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Move a token embedded in the endpoint into the x-token header
+///
+/// Helius hands out URLs with `?api-key=...` and Triton with the token as the path;
+/// both expect it as `x-token` on the gRPC connection.
+pub fn split_credentials(vendor: GeyserVendor, endpoint: &str, token: &str) -> (String, Option<String>) {
+    let token = Some(token.trim().to_string()).filter(|t| !t.is_empty());
+    if token.is_some() {
+        return (endpoint.to_string(), token);
+    }
+    let Ok(mut url) = url::Url::parse(endpoint) else {
+        return (endpoint.to_string(), None);
+    };
+    match vendor {
+        GeyserVendor::Helius => {
+            let key = url.query_pairs().find(|(name, _)| name == "api-key").map(|(_, value)| value.to_string());
+            if key.is_some() {
+                url.set_query(None);
+                return (url.to_string().trim_end_matches('/').to_string(), key);
+            }
+        }
+        GeyserVendor::Triton => {
+            let path = url.path().trim_matches('/').to_string();
+            if !path.is_empty() && !path.contains('/') {
+                url.set_path("");
+                return (url.to_string().trim_end_matches('/').to_string(), Some(path));
+            }
+        }
+        GeyserVendor::Yellowstone => {}
+    }
+    (endpoint.to_string(), None)
+}
+
+/// An open subscription; dropping it closes the stream and frees its slot
+pub struct GeyserSubscription {
+    pub sink: SubscribeSink,
+    pub stream: UpdateStream,
+}
+
+/// A Yellowstone-compatible gRPC provider
+pub trait GeyserSource: Send + Sync {
+    fn vendor(&self) -> GeyserVendor;
+
+    fn settings(&self) -> &GeyserSettings;
+
+    /// Connect and open a subscription stream
+    fn subscribe(&self) -> BoxFuture<'_, Result<GeyserSubscription>>;
+
+    /// Delay before the next reconnect, counting it as a failure
+    fn reconnect_delay(&self) -> Duration;
+
+    /// Reconnects since a stream last delivered an update
+    fn consecutive_failures(&self) -> u32;
+
+    /// Switch to the fallback endpoint, returning false when there is none or it is already in use
+    fn fail_over(&self) -> bool;
+
+    /// Cap an account filter to the plan's limit, keeping the first entries
+    fn limit_accounts(&self, accounts: &[String]) -> Vec<String> {
+        match self.settings().max_filter_accounts {
+            0 => accounts.to_vec(),
+            max => accounts.iter().take(max).cloned().collect(),
+        }
+    }
+}
+
+/// Exponential backoff from `base_secs`, capped at `max_secs`, with "equal jitter"
+///
+/// `jitter` in [0, 1) picks a delay between half and all of the backoff, so
+/// streams that dropped together do not all reconnect at the same moment.
+pub fn backoff_delay(base_secs: u64, max_secs: u64, failures: u32, jitter: f64) -> Duration {
+    let base = Duration::from_secs(base_secs.max(1));
+    let backoff = base.saturating_mul(1u32 << failures.min(16)).min(Duration::from_secs(max_secs.max(base_secs).max(1)));
+    backoff.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+}
+
+/// Connection state shared by the vendor implementations
+struct Connection {
+    settings: GeyserSettings,
+    streams: Option<Arc<Semaphore>>,
+    /// Reconnects since the last stream delivered an update
+    failures: Arc<AtomicU32>,
+    /// New streams go to the fallback endpoint
+    on_fallback: AtomicBool,
+}
+
+impl Connection {
+    fn new(settings: GeyserSettings) -> Self {
+        Self {
+            streams: (settings.max_streams > 0).then(|| Arc::new(Semaphore::new(settings.max_streams))),
+            settings,
+            failures: Arc::new(AtomicU32::new(0)),
+            on_fallback: AtomicBool::new(false),
+        }
+    }
+
+    async fn open(&self, keepalive: Option<Duration>) -> Result<GeyserSubscription> {
+        let permit = match &self.streams {
+            Some(streams) => Some(streams.clone().try_acquire_owned().map_err(|_| {
+                anyhow!("{} stream limit of {} reached (GEYSER_MAX_STREAMS)", self.settings.vendor, self.settings.max_streams)
+            })?),
+            None => None,
+        };
+
+        let (endpoint, token) = match &self.settings.fallback_endpoint {
+            Some(fallback) if self.on_fallback.load(Ordering::Relaxed) => (fallback, &self.settings.fallback_token),
+            _ => (&self.settings.endpoint, &self.settings.token),
+        };
+        let mut builder = GeyserGrpcClient::build_from_shared(endpoint.clone())?
+            .x_token(token.clone())?
+            .tls_config(ClientTlsConfig::new().with_native_roots())?
+            .connect_timeout(Duration::from_secs(10));
+        if let Some(interval) = keepalive {
+            builder = builder.http2_keep_alive_interval(interval).keep_alive_while_idle(true);
+        }
+        let mut client = builder.connect().await?;
+        // the raw tonic call keeps the stream independent of the client's lifetime
+        let (sink, requests) = mpsc::unbounded::<SubscribeRequest>();
+        let stream = client.geyser.subscribe(requests).await?.into_inner();
+
+        let failures = self.failures.clone();
+        let mut restart = STREAM_RESTARTS.subscribe();
+        let stream = stream.map(move |update| {
+            // the permit lives as long as the stream
+            let _ = &permit;
+            match update {
+                Ok(update) => {
+                    failures.store(0, Ordering::Relaxed);
+                    stream_health().record_update(update_slot(&update), Instant::now());
+                    if let Some(lag) = update_lag(&update, SystemTime::now()) {
+                        metrics().grpc_lag_seconds.observe(lag.as_secs_f64());
+                    }
+                    Ok(update)
+                }
+                Err(status) => Err(anyhow!("Stream error: {}", status)),
+            }
+        });
+        // a watchdog restart ends the stream, so its consumer reconnects
+        let stream = stream.take_until(async move {
+            let _ = restart.changed().await;
+        });
+        Ok(GeyserSubscription {
+            sink: Box::pin(sink.sink_map_err(|e| anyhow!("Failed to send subscribe request: {}", e))),
+            stream: Box::pin(stream),
+        })
+    }
+
+    /// Jittered exponential backoff from the configured delay
+    fn backoff_delay(&self) -> Duration {
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed);
+        backoff_delay(
+            self.settings.reconnect_delay_secs,
+            self.settings.max_backoff_secs,
+            failures,
+            rand::thread_rng().gen::<f64>(),
+        )
+    }
+
+    fn consecutive_failures(&self) -> u32 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    fn fail_over(&self) -> bool {
+        if self.settings.fallback_endpoint.is_none() || self.on_fallback.swap(true, Ordering::Relaxed) {
+            return false;
+        }
+        self.failures.store(0, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Bumped by the watchdog to end every open stream
+static STREAM_RESTARTS: LazyLock<watch::Sender<u64>> = LazyLock::new(|| watch::channel(0).0);
+
+/// End every open stream; each consumer reconnects as after a dropped connection
+pub fn restart_streams() {
+    STREAM_RESTARTS.send_modify(|restarts| *restarts += 1);
+}
+
+/// Generic Yellowstone endpoint: optional x-token
+pub struct YellowstoneSource(Connection);
+
+impl GeyserSource for YellowstoneSource {
+    fn vendor(&self) -> GeyserVendor {
+        GeyserVendor::Yellowstone
+    }
+
+    fn settings(&self) -> &GeyserSettings {
+        &self.0.settings
+    }
+
+    fn subscribe(&self) -> BoxFuture<'_, Result<GeyserSubscription>> {
+        Box::pin(self.0.open(None))
+    }
+
+    fn reconnect_delay(&self) -> Duration {
+        self.0.backoff_delay()
+    }
+
+    fn consecutive_failures(&self) -> u32 {
+        self.0.consecutive_failures()
+    }
+
+    fn fail_over(&self) -> bool {
+        self.0.fail_over()
+    }
+}
+
+/// Helius LaserStream: the API key is mandatory
+pub struct HeliusSource(Connection);
+
+impl GeyserSource for HeliusSource {
+    fn vendor(&self) -> GeyserVendor {
+        GeyserVendor::Helius
+    }
+
+    fn settings(&self) -> &GeyserSettings {
+        &self.0.settings
+    }
+
+    fn subscribe(&self) -> BoxFuture<'_, Result<GeyserSubscription>> {
+        Box::pin(async move {
+            if self.0.settings.token.is_none() {
+                return Err(anyhow!("Helius LaserStream needs the API key in YELLOWSTONE_GRPC_TOKEN"));
+            }
+            self.0.open(Some(Duration::from_secs(30))).await
+        })
+    }
+
+    fn reconnect_delay(&self) -> Duration {
+        self.0.backoff_delay()
+    }
+
+    fn consecutive_failures(&self) -> u32 {
+        self.0.consecutive_failures()
+    }
+
+    fn fail_over(&self) -> bool {
+        self.0.fail_over()
+    }
+}
+
+/// Triton: its load balancers drop idle streams, so HTTP/2 keepalive pings run every 10s
+pub struct TritonSource(Connection);
+
+impl GeyserSource for TritonSource {
+    fn vendor(&self) -> GeyserVendor {
+        GeyserVendor::Triton
+    }
+
+    fn settings(&self) -> &GeyserSettings {
+        &self.0.settings
+    }
+
+    fn subscribe(&self) -> BoxFuture<'_, Result<GeyserSubscription>> {
+        Box::pin(async move {
+            if self.0.settings.token.is_none() {
+                return Err(anyhow!("Triton needs the access token in YELLOWSTONE_GRPC_TOKEN"));
+            }
+            self.0.open(Some(Duration::from_secs(10))).await
+        })
+    }
+
+    fn reconnect_delay(&self) -> Duration {
+        self.0.backoff_delay()
+    }
+
+    fn consecutive_failures(&self) -> u32 {
+        self.0.consecutive_failures()
+    }
+
+    fn fail_over(&self) -> bool {
+        self.0.fail_over()
+    }
+}
+
+/// Build the source for the configured vendor
+pub fn geyser_source(settings: GeyserSettings) -> Arc<dyn GeyserSource> {
+    match settings.vendor {
+        GeyserVendor::Yellowstone => Arc::new(YellowstoneSource(Connection::new(settings))),
+        GeyserVendor::Helius => Arc::new(HeliusSource(Connection::new(settings))),
+        GeyserVendor::Triton => Arc::new(TritonSource(Connection::new(settings))),
+    }
+}
+
+static RECONNECT_ALERTS: LazyLock<Mutex<Option<(Arc<TelegramService>, String)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Send an alert to `chat_id` whenever a source runs out of reconnect retries
+pub fn set_reconnect_alerts(telegram: Arc<TelegramService>, chat_id: String) {
+    *RECONNECT_ALERTS.lock().unwrap_or_else(|e| e.into_inner()) = Some((telegram, chat_id));
+}
+
+/// Wait before reconnecting a stream that ended with `result`
+///
+/// Each failure backs off further. Once `max_retries` reconnects in a row have failed,
+/// a Telegram alert goes out and new streams switch to the fallback endpoint when one
+/// is configured. Every stream sends its filters again when it reconnects.
+pub async fn wait_to_reconnect(source: &dyn GeyserSource, stream: &str, result: Result<()>, logger: &Logger) {
+    let Err(e) = result else {
+        tokio::time::sleep(Duration::from_secs(source.settings().reconnect_delay_secs.max(1))).await;
+        return;
+    };
+    let delay = source.reconnect_delay();
+    let failures = source.consecutive_failures();
+    logger.error(format!(
+        "{} stream failed: {}, reconnecting in {:.1}s (failure {})",
+        stream, e, delay.as_secs_f64(), failures
+    ));
+
+    let max_retries = source.settings().max_retries;
+    if max_retries > 0 && failures == max_retries {
+        let switched = source.fail_over();
+        let message = match switched {
+            true => format!("⚠️ {} gRPC failed {} reconnects in a row, switching to the fallback endpoint", source.vendor(), failures),
+            false => format!("⚠️ {} gRPC failed {} reconnects in a row, still retrying", source.vendor(), failures),
+        };
+        logger.error(message.clone());
+        let alerts = RECONNECT_ALERTS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some((telegram, chat_id)) = alerts {
+            if let Err(e) = telegram.send_message(&chat_id, &message, "HTML").await {
+                logger.error(format!("Failed to send reconnect alert: {}", e));
+            }
+        }
+    }
+    tokio::time::sleep(delay).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_to_the_cap_with_jitter() {
+        assert_eq!(backoff_delay(2, 60, 0, 0.0), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2, 60, 3, 1.0), Duration::from_secs(16));
+        assert_eq!(backoff_delay(2, 60, 3, 0.0), Duration::from_secs(8));
+        assert_eq!(backoff_delay(2, 60, 30, 1.0), Duration::from_secs(60));
+        assert_eq!(backoff_delay(0, 0, 0, 1.0), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_vendor_detection_and_embedded_tokens() {
+        assert_eq!(GeyserVendor::detect("https://laserstream-mainnet-ewr.helius-rpc.com"), GeyserVendor::Helius);
+        assert_eq!(GeyserVendor::detect("https://example.rpcpool.com"), GeyserVendor::Triton);
+        assert_eq!(GeyserVendor::detect("http://127.0.0.1:10000"), GeyserVendor::Yellowstone);
+
+        assert_eq!(
+            split_credentials(GeyserVendor::Helius, "https://laserstream.helius-rpc.com?api-key=abc", ""),
+            ("https://laserstream.helius-rpc.com".to_string(), Some("abc".to_string()))
+        );
+        assert_eq!(
+            split_credentials(GeyserVendor::Triton, "https://example.rpcpool.com/tok3n", ""),
+            ("https://example.rpcpool.com".to_string(), Some("tok3n".to_string()))
+        );
+        // an explicit token wins and the endpoint is left alone
+        assert_eq!(
+            split_credentials(GeyserVendor::Triton, "https://example.rpcpool.com/tok3n", "explicit"),
+            ("https://example.rpcpool.com/tok3n".to_string(), Some("explicit".to_string()))
+        );
+    }
+}
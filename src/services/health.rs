@@ -0,0 +1,332 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use colored::Colorize;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{subscribe_update::UpdateOneof, SubscribeUpdate};
+
+use crate::common::logger::Logger;
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::geyser::restart_streams;
+
+/// Health endpoint and watchdog settings
+#[derive(Debug, Clone)]
+pub struct HealthSettings {
+    /// Whether `/healthz` is served
+    pub enabled: bool,
+    /// Address the endpoint binds to
+    pub bind_address: String,
+    /// Port the endpoint listens on
+    pub port: u16,
+    /// Streamed slots this far behind the RPC slot report unhealthy
+    pub max_slot_lag: u64,
+    /// Stream silence that restarts the gRPC streams, in seconds (0 = watchdog off)
+    pub stream_silence_secs: u64,
+    /// Stream restarts without an update before the whole engine restarts (0 = never)
+    pub max_stream_restarts: u32,
+}
+
+impl HealthSettings {
+    /// Load health settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("HEALTH_ENABLED").unwrap_or_default() == "true",
+            bind_address: std::env::var("HEALTH_BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0".to_string()),
+            port: std::env::var("HEALTH_PORT")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(8080),
+            max_slot_lag: std::env::var("HEALTH_MAX_SLOT_LAG")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(150),
+            stream_silence_secs: std::env::var("WATCHDOG_STREAM_SILENCE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(120),
+            max_stream_restarts: std::env::var("WATCHDOG_MAX_STREAM_RESTARTS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(3),
+        }
+    }
+}
+
+/// What the watchdog does about a silent stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    RestartStreams,
+    RestartEngine,
+}
+
+/// When the gRPC streams and confirmed trades were last seen
+#[derive(Debug, Default)]
+pub struct StreamHealth {
+    last_update: Option<Instant>,
+    last_slot: u64,
+    last_trade: Option<Instant>,
+    /// Watchdog restarts since the last update
+    restarts: u32,
+}
+
+impl StreamHealth {
+    /// Any stream delivered an update, pings included
+    pub fn record_update(&mut self, slot: Option<u64>, now: Instant) {
+        self.last_update = Some(now);
+        self.restarts = 0;
+        if let Some(slot) = slot {
+            self.last_slot = self.last_slot.max(slot);
+        }
+    }
+
+    pub fn trade_confirmed(&mut self, now: Instant) {
+        self.last_trade = Some(now);
+    }
+
+    /// Time since the last update, `None` before the first one
+    pub fn silence(&self, now: Instant) -> Option<Duration> {
+        self.last_update.map(|at| now.duration_since(at))
+    }
+
+    pub fn last_slot(&self) -> u64 {
+        self.last_slot
+    }
+
+    pub fn last_trade_age(&self, now: Instant) -> Option<Duration> {
+        self.last_trade.map(|at| now.duration_since(at))
+    }
+
+    /// Check the silence; each restart gets another full silence period to reconnect
+    ///
+    /// The watchdog only arms once a stream has delivered something.
+    pub fn watchdog(&mut self, settings: &HealthSettings, now: Instant) -> Option<WatchdogAction> {
+        if settings.stream_silence_secs == 0 {
+            return None;
+        }
+        let silence = self.silence(now)?;
+        let allowed = Duration::from_secs(settings.stream_silence_secs * (self.restarts as u64 + 1));
+        if silence < allowed {
+            return None;
+        }
+        if settings.max_stream_restarts > 0 && self.restarts >= settings.max_stream_restarts {
+            return Some(WatchdogAction::RestartEngine);
+        }
+        self.restarts += 1;
+        Some(WatchdogAction::RestartStreams)
+    }
+}
+
+/// Slot an update refers to, if it carries one
+pub fn update_slot(update: &SubscribeUpdate) -> Option<u64> {
+    match update.update_oneof.as_ref()? {
+        UpdateOneof::Transaction(tx) => Some(tx.slot),
+        UpdateOneof::Account(account) => Some(account.slot),
+        UpdateOneof::Slot(slot) => Some(slot.slot),
+        UpdateOneof::BlockMeta(meta) => Some(meta.slot),
+        _ => None,
+    }
+}
+
+/// How long ago the geyser server created an update, `None` when it carries no timestamp
+pub fn update_lag(update: &SubscribeUpdate, now: SystemTime) -> Option<Duration> {
+    let created = update.created_at.as_ref()?;
+    let created = UNIX_EPOCH + Duration::new(u64::try_from(created.seconds).ok()?, u32::try_from(created.nanos).ok()?);
+    now.duration_since(created).ok()
+}
+
+static STREAM_HEALTH: LazyLock<Mutex<StreamHealth>> = LazyLock::new(|| Mutex::new(StreamHealth::default()));
+static ENGINE_RESTART: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+/// Shared stream health, fed by every gRPC stream and the confirmation tracker
+pub fn stream_health() -> MutexGuard<'static, StreamHealth> {
+    STREAM_HEALTH.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Resolves once the watchdog wants the engine restarted
+pub async fn engine_restart_requested() {
+    ENGINE_RESTART.notified().await
+}
+
+#[derive(Clone)]
+struct HealthState {
+    executor: Arc<TradeExecutor>,
+    settings: HealthSettings,
+}
+
+async fn healthz(State(state): State<HealthState>) -> (StatusCode, Json<Value>) {
+    let now = Instant::now();
+    let (silence, streamed_slot, last_trade) = {
+        let health = stream_health();
+        (health.silence(now), health.last_slot(), health.last_trade_age(now))
+    };
+    // a stream that never delivered anything is not connected
+    let silence_limit = Duration::from_secs(state.settings.stream_silence_secs.max(60));
+    let stream_ok = silence.map(|silence| silence < silence_limit).unwrap_or(false);
+
+    let started = Instant::now();
+    let rpc_slot = state.executor.rpc_client().get_slot().await;
+    let rpc_latency_ms = started.elapsed().as_millis() as u64;
+    let slot_lag = rpc_slot.as_ref().ok().map(|slot| slot.saturating_sub(streamed_slot));
+    let slot_ok = slot_lag.map(|lag| lag <= state.settings.max_slot_lag).unwrap_or(false);
+    let balance = state.executor.wallet_balance().await.ok();
+
+    let healthy = stream_ok && rpc_slot.is_ok() && slot_ok;
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(json!({
+        "healthy": healthy,
+        "stream": {
+            "connected": stream_ok,
+            "last_update_secs": silence.map(|silence| silence.as_secs()),
+        },
+        "rpc": {
+            "ok": rpc_slot.is_ok(),
+            "latency_ms": rpc_latency_ms,
+            "error": rpc_slot.as_ref().err().map(|e| e.to_string()),
+        },
+        "slot": {
+            "rpc": rpc_slot.ok(),
+            "streamed": streamed_slot,
+            "lag": slot_lag,
+        },
+        "wallet_balance_sol": balance,
+        "last_trade_secs": last_trade.map(|age| age.as_secs()),
+    })))
+}
+
+/// Serve `/healthz` for liveness probes and run the stream watchdog
+///
+/// A stream silent for `WATCHDOG_STREAM_SILENCE_SECS` has every gRPC stream reconnected.
+/// After `WATCHDOG_MAX_STREAM_RESTARTS` such restarts without an update the engine shuts down
+/// gracefully and exits non-zero, so Docker or Kubernetes starts it again.
+pub async fn start_health_system(executor: Arc<TradeExecutor>, settings: HealthSettings, logger: Logger) -> Result<()> {
+    if settings.stream_silence_secs > 0 {
+        let watchdog_settings = settings.clone();
+        let watchdog_logger = logger.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let action = stream_health().watchdog(&watchdog_settings, Instant::now());
+                match action {
+                    Some(WatchdogAction::RestartStreams) => {
+                        watchdog_logger.error(format!(
+                            "No stream update for {}s, restarting the gRPC streams",
+                            watchdog_settings.stream_silence_secs
+                        ));
+                        restart_streams();
+                    }
+                    Some(WatchdogAction::RestartEngine) => {
+                        watchdog_logger.error("Streams still silent after restarting them, restarting the engine".red().bold().to_string());
+                        ENGINE_RESTART.notify_one();
+                        return;
+                    }
+                    None => {}
+                }
+            }
+        });
+    }
+
+    if !settings.enabled {
+        return Ok(());
+    }
+    let addr: SocketAddr = format!("{}:{}", settings.bind_address, settings.port).parse()?;
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .with_state(HealthState { executor, settings });
+
+    logger.log(format!("🩺 Health endpoint listening on http://{}/healthz", addr).green().to_string());
+    tokio::spawn(async move {
+        if let Err(e) = axum::Server::bind(&addr).serve(app.into_make_service()).await {
+            logger.error(format!("Health endpoint stopped: {}", e));
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> HealthSettings {
+        HealthSettings {
+            enabled: false,
+            bind_address: String::new(),
+            port: 0,
+            max_slot_lag: 150,
+            stream_silence_secs: 60,
+            max_stream_restarts: 2,
+        }
+    }
+
+    fn secs(start: Instant, secs: u64) -> Instant {
+        start + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_watchdog_idle_before_first_update() {
+        let mut health = StreamHealth::default();
+        assert_eq!(health.watchdog(&settings(), secs(Instant::now(), 600)), None);
+    }
+
+    #[test]
+    fn test_watchdog_restarts_streams_after_silence() {
+        let mut health = StreamHealth::default();
+        let start = Instant::now();
+        health.record_update(Some(100), start);
+        assert_eq!(health.watchdog(&settings(), secs(start, 59)), None);
+        assert_eq!(health.watchdog(&settings(), secs(start, 60)), Some(WatchdogAction::RestartStreams));
+        // the reconnect gets another silence period
+        assert_eq!(health.watchdog(&settings(), secs(start, 90)), None);
+    }
+
+    #[test]
+    fn test_watchdog_escalates_to_engine_restart() {
+        let mut health = StreamHealth::default();
+        let start = Instant::now();
+        health.record_update(Some(100), start);
+        assert_eq!(health.watchdog(&settings(), secs(start, 60)), Some(WatchdogAction::RestartStreams));
+        assert_eq!(health.watchdog(&settings(), secs(start, 120)), Some(WatchdogAction::RestartStreams));
+        assert_eq!(health.watchdog(&settings(), secs(start, 180)), Some(WatchdogAction::RestartEngine));
+    }
+
+    #[test]
+    fn test_update_resets_restart_count() {
+        let mut health = StreamHealth::default();
+        let start = Instant::now();
+        health.record_update(Some(100), start);
+        health.watchdog(&settings(), secs(start, 60));
+        health.watchdog(&settings(), secs(start, 120));
+
+        health.record_update(Some(101), secs(start, 181));
+        assert_eq!(health.watchdog(&settings(), secs(start, 241)), Some(WatchdogAction::RestartStreams));
+    }
+
+    #[test]
+    fn test_last_slot_ignores_older_slots() {
+        let mut health = StreamHealth::default();
+        let start = Instant::now();
+        health.record_update(Some(100), start);
+        health.record_update(Some(90), secs(start, 1));
+        assert_eq!(health.last_slot(), 100);
+    }
+
+    #[test]
+    fn test_update_lag_from_created_at() {
+        let mut update = SubscribeUpdate::default();
+        let now = UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+        assert_eq!(update_lag(&update, now), None);
+
+        update.created_at = Some(Default::default());
+        if let Some(created) = update.created_at.as_mut() {
+            created.seconds = 1_700_000_000;
+            created.nanos = 100_000_000;
+        }
+        assert_eq!(update_lag(&update, now), Some(Duration::from_millis(150)));
+        // a server clock ahead of ours is no lag to report
+        assert_eq!(update_lag(&update, UNIX_EPOCH + Duration::from_secs(1_600_000_000)), None);
+    }
+}
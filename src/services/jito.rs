@@ -7,7 +7,7 @@ use anchor_client::solana_sdk::pubkey::Pubkey;
 use std::{future::Future, str::FromStr, sync::LazyLock, time::Duration};
 use tokio::time::{sleep, Instant};
 
-use crate::common::config::import_env_var;
+use crate::common::{config::import_env_var, logger::Logger};
 
 pub static BLOCK_ENGINE_URL: LazyLock<String> =
     LazyLock::new(|| import_env_var("JITO_BLOCK_ENGINE_URL"));
@@ -160,14 +160,130 @@ pub fn new_progress_bar() -> ProgressBar {
     progress_bar
 }
 
+/// Maximum number of transactions the block engine accepts in one bundle
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Status returned by `getInflightBundleStatuses`
+#[derive(Deserialize, Debug)]
+pub struct InflightBundleStatus {
+    pub bundle_id: String,
+    /// One of Invalid, Pending, Failed, Landed
+    pub status: String,
+    pub landed_slot: Option<u64>,
+    /// Error detail some block engines attach to a failed bundle
+    #[serde(default)]
+    pub err: Option<Value>,
+}
+
+/// Final result of a submitted bundle
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleOutcome {
+    /// All transactions in the bundle landed in `slot`
+    Landed { bundle_id: String, slot: u64, signatures: Vec<String> },
+    /// The block engine reported the bundle as failed, with the error it or a transaction gave
+    Failed { bundle_id: String, reason: String },
+    /// No final status was seen before the timeout
+    TimedOut { bundle_id: String },
+}
+
+/// Builds a multi-transaction Jito bundle
+///
+/// Transactions are kept in submission order: an optional ATA-create transaction,
+/// then the swap transaction(s), then the tip transaction last so the tip is only
+/// paid if everything before it succeeds.
+pub struct BundleBuilder<'a> {
+    payer: &'a dyn Signer,
+    recent_blockhash: Hash,
+    setup: Option<Vec<Instruction>>,
+    bodies: Vec<Vec<Instruction>>,
+    tip_lamports: u64,
+}
+
+impl<'a> BundleBuilder<'a> {
+    /// Start a new bundle paid for and signed by `payer`
+    pub fn new(payer: &'a dyn Signer, recent_blockhash: Hash) -> Self {
+        Self {
+            payer,
+            recent_blockhash,
+            setup: None,
+            bodies: Vec::new(),
+            tip_lamports: 0,
+        }
+    }
+
+    /// Add a separate setup transaction (e.g. ATA creation) at the front of the bundle
+    pub fn with_setup(mut self, instructions: Vec<Instruction>) -> Self {
+        if !instructions.is_empty() {
+            self.setup = Some(instructions);
+        }
+        self
+    }
+
+    /// Add a transaction built from the given instructions
+    pub fn add_transaction(mut self, instructions: Vec<Instruction>) -> Self {
+        self.bodies.push(instructions);
+        self
+    }
+
+    /// Set the tip paid by the trailing tip transaction
+    pub fn with_tip(mut self, tip_lamports: u64) -> Self {
+        self.tip_lamports = tip_lamports;
+        self
+    }
+
+    /// Sign every transaction and return them in bundle order
+    pub fn build(self) -> Result<Vec<VersionedTransaction>> {
+        if self.bodies.is_empty() {
+            return Err(anyhow!("jito: bundle has no transactions"));
+        }
+
+        let mut groups: Vec<Vec<Instruction>> = Vec::new();
+        if let Some(setup) = self.setup {
+            groups.push(setup);
+        }
+        groups.extend(self.bodies);
+
+        if self.tip_lamports > 0 {
+            let (tip_account, _) = get_tip_account()?;
+            groups.push(vec![system_instruction::transfer(
+                &self.payer.pubkey(),
+                &tip_account,
+                self.tip_lamports,
+            )]);
+        }
+
+        if groups.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(anyhow!(
+                "jito: bundle has {} transactions, max is {}",
+                groups.len(),
+                MAX_BUNDLE_TRANSACTIONS
+            ));
+        }
+
+        let lookup_tables = lookup_tables();
+        groups
+            .iter()
+            .map(|instructions| signed_versioned_transaction(instructions, self.payer, self.recent_blockhash, &lookup_tables))
+            .collect()
+    }
+}
+
 use crate::error::ClientError;
 use bincode;
 use bs64;
 use reqwest;
 use serde_json::json;
 use anchor_client::solana_sdk::{
-    commitment_config::CommitmentConfig, signature::Signature, transaction::Transaction,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+    transaction::VersionedTransaction,
 };
+use crate::common::signer::signed_versioned_transaction;
+use crate::services::lookup_table::lookup_tables;
 
 pub const MAX_RETRIES: u8 = 3;
 pub const RETRY_DELAY: Duration = Duration::from_millis(200);
@@ -207,13 +323,9 @@ impl JitoClient {
         }
     }
 
-    pub fn new(endpoint : &str) -> self {
-        
-    }
-
     pub async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
     ) -> Result<Signature, ClientError> {
         let wire_transaction = bincode::serialize(transaction).map_err(|e| {
             ClientError::Parse(
@@ -244,6 +356,113 @@ impl JitoClient {
         Err(ClientError::Other("Max retries exceeded".to_string()))
     }
 
+    /// Submit an ordered list of signed transactions as one atomic bundle
+    ///
+    /// The client must be created against the `/api/v1/bundles` endpoint.
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, ClientError> {
+        let mut encoded_txs = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let wire_transaction = bincode::serialize(transaction).map_err(|e| {
+                ClientError::Parse(
+                    "Transaction serialization failed".to_string(),
+                    e.to_string(),
+                )
+            })?;
+            encoded_txs.push(bs64::encode(&wire_transaction));
+        }
+
+        let params = json!([encoded_txs, { "encoding": self.config.encoding }]);
+        let response = self.send_request("sendBundle", params).await?;
+
+        response["result"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                ClientError::Jito(
+                    "Invalid sendBundle response".to_string(),
+                    "Missing bundle id".to_string(),
+                )
+            })
+    }
+
+    /// Fetch the in-flight status of a bundle from the block engine
+    pub async fn get_inflight_bundle_status(&self, bundle_id: &str) -> Result<Option<InflightBundleStatus>, ClientError> {
+        let response = self
+            .send_request("getInflightBundleStatuses", json!([[bundle_id]]))
+            .await?;
+
+        match response["result"]["value"].as_array().and_then(|v| v.first()) {
+            Some(status) => Ok(Some(serde_json::from_value(status.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Error of a bundle's transactions from `getBundleStatuses`, if one failed on chain
+    pub async fn get_bundle_error(&self, bundle_id: &str) -> Result<Option<String>, ClientError> {
+        let response = self
+            .send_request("getBundleStatuses", json!([[bundle_id]]))
+            .await?;
+
+        let err = &response["result"]["value"][0]["err"];
+        if err.is_null() || err.get("Ok").is_some() {
+            return Ok(None);
+        }
+        Ok(Some(err.to_string()))
+    }
+
+    /// Poll a bundle until it lands, fails or the timeout expires
+    ///
+    /// `Invalid` only means the block engine has not seen the bundle yet, so polling goes on.
+    pub async fn wait_for_bundle_outcome(
+        &self,
+        bundle_id: &str,
+        signatures: Vec<String>,
+        interval: Duration,
+        timeout: Duration,
+        logger: &Logger,
+    ) -> BundleOutcome {
+        let start_time = Instant::now();
+
+        loop {
+            match self.get_inflight_bundle_status(bundle_id).await {
+                Ok(Some(status)) => match status.status.as_str() {
+                    "Landed" => {
+                        return BundleOutcome::Landed {
+                            bundle_id: bundle_id.to_string(),
+                            slot: status.landed_slot.unwrap_or_default(),
+                            signatures,
+                        }
+                    }
+                    "Failed" => {
+                        let reason = match status.err {
+                            Some(err) => err.to_string(),
+                            None => match self.get_bundle_error(bundle_id).await {
+                                Ok(Some(err)) => err,
+                                Ok(None) => "dropped by every block engine region".to_string(),
+                                Err(e) => format!("no error detail ({})", e),
+                            },
+                        };
+                        return BundleOutcome::Failed {
+                            bundle_id: bundle_id.to_string(),
+                            reason,
+                        };
+                    }
+                    _ => {}
+                },
+                Ok(None) => {}
+                Err(e) => logger.error(format!("Failed to get status of bundle {}: {}", bundle_id, e)),
+            }
+
+            if start_time.elapsed() > timeout {
+                return BundleOutcome::TimedOut {
+                    bundle_id: bundle_id.to_string(),
+                };
+            }
+
+            sleep(interval).await;
+        }
+    }
+
     async fn try_send_transaction(&self, encoded_tx: &str) -> Result<String, ClientError> {
         let params = json!([
             encoded_tx,
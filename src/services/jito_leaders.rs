@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde_json::{json, Value};
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::services::jito::BLOCK_ENGINE_URL;
+
+/// Average slot time, used to extrapolate the current slot between polls
+const SLOT_MS: u64 = 400;
+/// Leader slots fetched per refresh, about half an hour
+const SCHEDULE_SLOTS: u64 = 4000;
+
+/// Leader-schedule aware Jito settings
+#[derive(Debug, Clone)]
+pub struct JitoLeaderSettings {
+    /// Only submit to Jito when a Jito-running validator leads one of the next slots
+    pub enabled: bool,
+    /// Upcoming slots checked for a Jito leader, the current one included
+    pub lookahead_slots: u64,
+    /// Validator list telling which vote accounts run the Jito client
+    pub validators_url: String,
+    /// Block engine region URLs; the one answering fastest is used (empty = JITO_BLOCK_ENGINE_URL)
+    pub region_urls: Vec<String>,
+    /// How often the schedule, the Jito validator set and region latencies are reloaded (seconds)
+    pub refresh_secs: u64,
+}
+
+impl JitoLeaderSettings {
+    /// Load leader-aware Jito settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("JITO_LEADER_AWARE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            lookahead_slots: std::env::var("JITO_LEADER_LOOKAHEAD_SLOTS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(4),
+            validators_url: std::env::var("JITO_VALIDATORS_URL")
+                .unwrap_or_else(|_| "https://kobe.mainnet.jito.network/api/v1/validators".to_string()),
+            region_urls: std::env::var("JITO_REGION_URLS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .collect(),
+            refresh_secs: std::env::var("JITO_LEADER_REFRESH_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(300),
+        }
+    }
+}
+
+/// Vote accounts marked as running the Jito client in a validators API response
+fn parse_jito_vote_accounts(body: &Value) -> HashSet<String> {
+    body.get("validators")
+        .and_then(Value::as_array)
+        .map(|validators| {
+            validators
+                .iter()
+                .filter(|validator| validator.get("running_jito").and_then(Value::as_bool).unwrap_or(false))
+                .filter_map(|validator| validator.get("vote_account").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Slot leaders from `first_slot` on, and which of them run Jito
+#[derive(Debug, Clone, Default)]
+pub struct LeaderWindow {
+    pub first_slot: u64,
+    pub leaders: Vec<Pubkey>,
+    pub jito_identities: HashSet<Pubkey>,
+}
+
+impl LeaderWindow {
+    /// Whether a Jito validator leads one of the `lookahead` slots from `slot`, `None` outside the window
+    pub fn jito_leader_within(&self, slot: u64, lookahead: u64) -> Option<bool> {
+        let start = slot.checked_sub(self.first_slot)? as usize;
+        if start >= self.leaders.len() {
+            return None;
+        }
+        let end = (start + lookahead.max(1) as usize).min(self.leaders.len());
+        Some(self.leaders[start..end].iter().any(|leader| self.jito_identities.contains(leader)))
+    }
+}
+
+struct LeaderState {
+    window: LeaderWindow,
+    lookahead_slots: u64,
+    /// Last polled slot and when
+    observed_slot: u64,
+    observed_at: Instant,
+}
+
+static LEADER_STATE: LazyLock<RwLock<Option<LeaderState>>> = LazyLock::new(|| RwLock::new(None));
+static BLOCK_ENGINE: LazyLock<RwLock<Option<String>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Whether Jito can land a submission now
+///
+/// True while leader awareness is off or the schedule is not loaded, so Jito is never
+/// blocked for lack of data.
+pub fn jito_leader_upcoming() -> bool {
+    let state = LEADER_STATE.read().unwrap_or_else(|e| e.into_inner());
+    let Some(state) = state.as_ref() else {
+        return true;
+    };
+    let slot = state.observed_slot + state.observed_at.elapsed().as_millis() as u64 / SLOT_MS;
+    state.window.jito_leader_within(slot, state.lookahead_slots).unwrap_or(true)
+}
+
+/// Block engine to submit to: the fastest region, or `JITO_BLOCK_ENGINE_URL`
+pub fn block_engine_url() -> String {
+    BLOCK_ENGINE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| BLOCK_ENGINE_URL.clone())
+}
+
+/// Node identities of the validators running Jito
+async fn jito_identities(rpc_client: &RpcClient, client: &reqwest::Client, validators_url: &str) -> Result<HashSet<Pubkey>> {
+    let body: Value = client.get(validators_url).send().await?.error_for_status()?.json().await?;
+    let vote_accounts = parse_jito_vote_accounts(&body);
+    if vote_accounts.is_empty() {
+        return Err(anyhow!("No Jito validators in the {} response", validators_url));
+    }
+    let status = rpc_client.get_vote_accounts().await?;
+    Ok(status
+        .current
+        .iter()
+        .chain(status.delinquent.iter())
+        .filter(|account| vote_accounts.contains(&account.vote_pubkey))
+        .filter_map(|account| Pubkey::from_str(&account.node_pubkey).ok())
+        .collect())
+}
+
+/// The region answering a `getTipAccounts` call fastest
+async fn fastest_region(client: &reqwest::Client, urls: &[String]) -> Option<(String, Duration)> {
+    let mut fastest: Option<(String, Duration)> = None;
+    for url in urls {
+        let started = Instant::now();
+        let response = client
+            .post(format!("{}/api/v1/bundles", url))
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "getTipAccounts", "params": []}))
+            .timeout(Duration::from_secs(3))
+            .send()
+            .await;
+        if !response.map(|r| r.status().is_success()).unwrap_or(false) {
+            continue;
+        }
+        let elapsed = started.elapsed();
+        if fastest.as_ref().map(|(_, best)| elapsed < *best).unwrap_or(true) {
+            fastest = Some((url.clone(), elapsed));
+        }
+    }
+    fastest
+}
+
+async fn refresh(rpc_client: &RpcClient, client: &reqwest::Client, settings: &JitoLeaderSettings, logger: &Logger) -> Result<()> {
+    let identities = jito_identities(rpc_client, client, &settings.validators_url).await?;
+    let slot = rpc_client.get_slot_with_commitment(CommitmentConfig::processed()).await?;
+    let leaders = rpc_client.get_slot_leaders(slot, SCHEDULE_SLOTS).await?;
+    let jito_slots = leaders.iter().filter(|leader| identities.contains(leader)).count();
+    logger.debug(format!(
+        "{} Jito validators lead {}/{} of the next slots",
+        identities.len(), jito_slots, leaders.len()
+    ));
+    *LEADER_STATE.write().unwrap_or_else(|e| e.into_inner()) = Some(LeaderState {
+        window: LeaderWindow { first_slot: slot, leaders, jito_identities: identities },
+        lookahead_slots: settings.lookahead_slots,
+        observed_slot: slot,
+        observed_at: Instant::now(),
+    });
+
+    if settings.region_urls.len() > 1 {
+        if let Some((url, latency)) = fastest_region(client, &settings.region_urls).await {
+            let mut current = BLOCK_ENGINE.write().unwrap_or_else(|e| e.into_inner());
+            if current.as_deref() != Some(url.as_str()) {
+                logger.log(format!("Submitting to block engine {} ({:?})", url, latency));
+            }
+            *current = Some(url);
+        }
+    } else if let Some(url) = settings.region_urls.first() {
+        *BLOCK_ENGINE.write().unwrap_or_else(|e| e.into_inner()) = Some(url.clone());
+    }
+    Ok(())
+}
+
+/// Load the leader schedule and keep it, the Jito validator set and the current slot fresh
+pub async fn start_jito_leader_system(rpc_client: std::sync::Arc<RpcClient>, settings: JitoLeaderSettings, logger: Logger) {
+    let client = reqwest::Client::new();
+    match refresh(&rpc_client, &client, &settings, &logger).await {
+        Ok(()) => logger.log(format!(
+            "Jito submissions wait for a Jito leader within {} slots",
+            settings.lookahead_slots.max(1)
+        ).green().to_string()),
+        Err(e) => logger.error(format!("Failed to load the leader schedule, Jito is used unconditionally until it loads: {}", e)),
+    }
+    tokio::spawn(async move {
+        let refresh_every = Duration::from_secs(settings.refresh_secs.max(30));
+        let mut refreshed_at = Instant::now();
+        let mut interval = tokio::time::interval(Duration::from_secs(2));
+        loop {
+            interval.tick().await;
+            // reload before the window runs out, or on schedule
+            let window_end = LEADER_STATE
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+                .map(|state| state.window.first_slot + state.window.leaders.len() as u64);
+            let slot = rpc_client.get_slot_with_commitment(CommitmentConfig::processed()).await.ok();
+            if let (Some(slot), Some(state)) = (slot, LEADER_STATE.write().unwrap_or_else(|e| e.into_inner()).as_mut()) {
+                state.observed_slot = slot;
+                state.observed_at = Instant::now();
+            }
+            let near_end = matches!((slot, window_end), (Some(slot), Some(end)) if slot + 150 >= end);
+            if refreshed_at.elapsed() >= refresh_every || near_end || window_end.is_none() {
+                refreshed_at = Instant::now();
+                if let Err(e) = refresh(&rpc_client, &client, &settings, &logger).await {
+                    logger.error(format!("Failed to refresh the leader schedule: {}", e));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jito_leader_within() {
+        let jito = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let window = LeaderWindow {
+            first_slot: 100,
+            leaders: vec![other, other, other, other, jito, jito, jito, jito],
+            jito_identities: HashSet::from([jito]),
+        };
+        assert_eq!(window.jito_leader_within(100, 4), Some(false));
+        assert_eq!(window.jito_leader_within(101, 4), Some(true));
+        assert_eq!(window.jito_leader_within(106, 4), Some(true));
+        assert_eq!(window.jito_leader_within(99, 4), None);
+        assert_eq!(window.jito_leader_within(108, 4), None);
+
+        let body = json!({"validators": [
+            {"vote_account": "vote1", "running_jito": true},
+            {"vote_account": "vote2", "running_jito": false},
+            {"vote_account": "vote3"},
+        ]});
+        assert_eq!(parse_jito_vote_accounts(&body), HashSet::from(["vote1".to_string()]));
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::common::logger::Logger;
+use crate::services::alerts::{alert, Alert};
+
+/// Leaderboard a wallet ranking is pulled from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaderboardProvider {
+    Gmgn,
+    SolanaTracker,
+}
+
+impl LeaderboardProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "gmgn" => Some(LeaderboardProvider::Gmgn),
+            "solanatracker" | "solana_tracker" => Some(LeaderboardProvider::SolanaTracker),
+            _ => None,
+        }
+    }
+
+    fn default_url(&self) -> &'static str {
+        match self {
+            LeaderboardProvider::Gmgn => "https://gmgn.ai/defi/quotation/v1/rank/sol/wallets/7d?orderby=pnl_7d&direction=desc",
+            LeaderboardProvider::SolanaTracker => "https://data.solanatracker.io/top-traders/all?sortBy=total",
+        }
+    }
+}
+
+/// Wallet leaderboard importer settings
+#[derive(Debug, Clone)]
+pub struct LeaderboardSettings {
+    pub enabled: bool,
+    pub provider: LeaderboardProvider,
+    /// Ranking endpoint, the provider's default when empty
+    pub url: String,
+    /// Sent as `x-api-key`, Solana Tracker requires one
+    pub api_key: String,
+    pub interval_secs: u64,
+    /// Trades a wallet made in the ranked period at least
+    pub min_trades: u64,
+    /// Share of winning trades at least, in percent
+    pub min_win_rate_percent: f64,
+    /// Wallets proposed per refresh at most
+    pub max_targets: usize,
+    /// Follow the proposed wallets right away instead of only listing them
+    pub auto_add: bool,
+}
+
+impl LeaderboardSettings {
+    /// Load leaderboard settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("LEADERBOARD_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            provider: std::env::var("LEADERBOARD_PROVIDER")
+                .ok()
+                .and_then(|v| LeaderboardProvider::parse(&v))
+                .unwrap_or(LeaderboardProvider::Gmgn),
+            url: std::env::var("LEADERBOARD_URL").unwrap_or_default().trim().to_string(),
+            api_key: std::env::var("LEADERBOARD_API_KEY").unwrap_or_default(),
+            interval_secs: std::env::var("LEADERBOARD_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3600),
+            min_trades: std::env::var("LEADERBOARD_MIN_TRADES")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(20),
+            min_win_rate_percent: std::env::var("LEADERBOARD_MIN_WIN_RATE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(55.0),
+            max_targets: std::env::var("LEADERBOARD_MAX_TARGETS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(10),
+            auto_add: std::env::var("LEADERBOARD_AUTO_ADD")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+
+    fn url(&self) -> &str {
+        if self.url.is_empty() { self.provider.default_url() } else { &self.url }
+    }
+}
+
+/// One ranked wallet
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedWallet {
+    pub address: String,
+    pub trades: u64,
+    pub win_rate_percent: f64,
+    /// Realized profit over the ranked period as the provider reports it, in USD
+    pub pnl_usd: Option<f64>,
+}
+
+fn number(value: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter()
+        .filter_map(|key| value.get(*key))
+        .find_map(|field| field.as_f64().or_else(|| field.as_str().and_then(|text| text.parse::<f64>().ok())))
+}
+
+/// Ranked wallets of a GMGN `rank` response: `data.rank[]` with 7 day stats, win rate as a fraction
+pub fn parse_gmgn(body: &Value) -> Vec<RankedWallet> {
+    let rows = body.pointer("/data/rank").and_then(Value::as_array).cloned().unwrap_or_default();
+    rows.iter()
+        .filter_map(|row| {
+            let address = row.get("wallet_address").or_else(|| row.get("address"))?.as_str()?.to_string();
+            let trades = number(row, &["txs_7d", "txs"])
+                .or_else(|| Some(number(row, &["buy_7d", "buy"])? + number(row, &["sell_7d", "sell"])?))
+                .unwrap_or(0.0);
+            Some(RankedWallet {
+                address,
+                trades: trades as u64,
+                win_rate_percent: number(row, &["winrate_7d", "winrate"]).unwrap_or(0.0) * 100.0,
+                pnl_usd: number(row, &["realized_profit_7d", "realized_profit"]),
+            })
+        })
+        .collect()
+}
+
+/// Ranked wallets of a Solana Tracker `top-traders` response: `wallets[]` with a `summary`, win rate in percent
+pub fn parse_solana_tracker(body: &Value) -> Vec<RankedWallet> {
+    let rows = body.get("wallets").and_then(Value::as_array).cloned().unwrap_or_default();
+    rows.iter()
+        .filter_map(|row| {
+            let address = row.get("wallet")?.as_str()?.to_string();
+            let summary = row.get("summary").unwrap_or(row);
+            let trades = number(summary, &["totalTrades"])
+                .or_else(|| Some(number(summary, &["totalWins"])? + number(summary, &["totalLosses"])?))
+                .unwrap_or(0.0);
+            Some(RankedWallet {
+                address,
+                trades: trades as u64,
+                win_rate_percent: number(summary, &["winPercentage"]).unwrap_or(0.0),
+                pnl_usd: number(summary, &["realized", "total"]),
+            })
+        })
+        .collect()
+}
+
+/// Wallets meeting the trade count and win rate criteria, in ranking order, minus the ones already followed
+pub fn select(wallets: &[RankedWallet], settings: &LeaderboardSettings, followed: &HashSet<String>) -> Vec<RankedWallet> {
+    wallets
+        .iter()
+        .filter(|wallet| wallet.trades >= settings.min_trades && wallet.win_rate_percent >= settings.min_win_rate_percent)
+        .filter(|wallet| !followed.contains(&wallet.address))
+        .take(settings.max_targets)
+        .cloned()
+        .collect()
+}
+
+/// Latest proposals and the wallets imported as targets
+#[derive(Debug, Default, Serialize)]
+pub struct Leaderboard {
+    pub proposed: Vec<RankedWallet>,
+    /// Followed by the copy strategy next to `TARGET_WALLETS`, with `LEADERBOARD_AUTO_ADD`
+    pub imported: Vec<String>,
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
+
+static LEADERBOARD: LazyLock<Mutex<Leaderboard>> = LazyLock::new(|| Mutex::new(Leaderboard::default()));
+
+pub fn leaderboard() -> MutexGuard<'static, Leaderboard> {
+    LEADERBOARD.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Wallets the copy strategy follows on top of its configured targets
+pub fn imported_targets() -> Vec<String> {
+    leaderboard().imported.clone()
+}
+
+async fn fetch(client: &reqwest::Client, settings: &LeaderboardSettings) -> Result<Vec<RankedWallet>> {
+    let mut request = client.get(settings.url()).header("accept", "application/json");
+    if !settings.api_key.is_empty() {
+        request = request.header("x-api-key", &settings.api_key);
+    }
+    let body: Value = request.send().await?.error_for_status()?.json().await?;
+    let wallets = match settings.provider {
+        LeaderboardProvider::Gmgn => parse_gmgn(&body),
+        LeaderboardProvider::SolanaTracker => parse_solana_tracker(&body),
+    };
+    if wallets.is_empty() {
+        return Err(anyhow!("no wallets in the leaderboard response"));
+    }
+    Ok(wallets)
+}
+
+/// Refresh the leaderboard every `LEADERBOARD_INTERVAL_SECS` and propose, or import, the best wallets
+///
+/// `followed` are the configured `TARGET_WALLETS`, never proposed again.
+pub async fn start_leaderboard_system(followed: Vec<String>, logger: Logger) {
+    let settings = LeaderboardSettings::from_env();
+    if !settings.enabled {
+        return;
+    }
+    let followed: HashSet<String> = followed.into_iter().collect();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+    logger.log(format!(
+        "Leaderboard importer: {:?} every {}s, auto-add {}",
+        settings.provider, settings.interval_secs, settings.auto_add
+    ));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.interval_secs.max(60)));
+        loop {
+            interval.tick().await;
+            let wallets = match fetch(&client, &settings).await {
+                Ok(wallets) => wallets,
+                Err(e) => {
+                    logger.error(format!("Leaderboard refresh failed: {}", e));
+                    continue;
+                }
+            };
+            let proposed = select(&wallets, &settings, &followed);
+            let added: Vec<String> = {
+                let mut board = leaderboard();
+                let added = proposed
+                    .iter()
+                    .filter(|wallet| !board.proposed.iter().any(|known| known.address == wallet.address))
+                    .map(|wallet| wallet.address.clone())
+                    .collect();
+                if settings.auto_add {
+                    board.imported = proposed.iter().map(|wallet| wallet.address.clone()).collect();
+                }
+                board.proposed = proposed.clone();
+                board.refreshed_at = Some(Utc::now());
+                added
+            };
+            for wallet in proposed.iter().filter(|wallet| added.contains(&wallet.address)) {
+                let verb = if settings.auto_add { "Following" } else { "Proposed copy target" };
+                let message = format!(
+                    "{} {}: {} trades, {:.1}% wins, PnL {}",
+                    verb,
+                    wallet.address,
+                    wallet.trades,
+                    wallet.win_rate_percent,
+                    wallet.pnl_usd.map(|pnl| format!("${:.0}", pnl)).unwrap_or_else(|| "-".to_string())
+                );
+                logger.log(message.cyan().to_string());
+                alert(Alert::debug(&wallet.address, &message));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_select_wallets() {
+        let gmgn = json!({ "data": { "rank": [
+            { "wallet_address": "A", "buy_7d": 30, "sell_7d": 25, "winrate_7d": 0.62, "realized_profit_7d": 12000.5 },
+            { "wallet_address": "B", "txs_7d": 8, "winrate_7d": 0.9 },
+            { "wallet_address": "C", "txs_7d": 100, "winrate_7d": "0.40" },
+        ] } });
+        let wallets = parse_gmgn(&gmgn);
+        assert_eq!(wallets[0].trades, 55);
+        assert!((wallets[0].win_rate_percent - 62.0).abs() < 1e-9);
+        assert!((wallets[2].win_rate_percent - 40.0).abs() < 1e-9);
+
+        let tracker = json!({ "wallets": [
+            { "wallet": "D", "summary": { "totalWins": 40, "totalLosses": 10, "winPercentage": 80.0, "realized": 500.0 } },
+        ] });
+        let tracked = parse_solana_tracker(&tracker);
+        assert_eq!(tracked[0], RankedWallet { address: "D".to_string(), trades: 50, win_rate_percent: 80.0, pnl_usd: Some(500.0) });
+
+        let mut settings = LeaderboardSettings::from_env();
+        settings.min_trades = 20;
+        settings.min_win_rate_percent = 55.0;
+        settings.max_targets = 10;
+        let all: Vec<RankedWallet> = wallets.into_iter().chain(tracked).collect();
+        let chosen: Vec<String> = select(&all, &settings, &HashSet::new()).into_iter().map(|w| w.address).collect();
+        assert_eq!(chosen, vec!["A", "D"]);
+        let followed = HashSet::from(["A".to_string()]);
+        assert_eq!(select(&all, &settings, &followed)[0].address, "D");
+    }
+}
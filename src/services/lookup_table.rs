@@ -0,0 +1,173 @@
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{
+    address_lookup_table::{
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::AddressLookupTable,
+        AddressLookupTableAccount,
+    },
+    commitment_config::CommitmentConfig,
+    compute_budget,
+    pubkey::Pubkey,
+    signer::Signer,
+    system_program,
+    sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::common::{arming::ensure_armed, logger::Logger, signer::signed_transaction};
+use crate::dex::program_ids::program_ids;
+
+/// Addresses added per extend transaction, keeping it well under the size limit
+const EXTEND_CHUNK: usize = 20;
+
+/// Address lookup table settings
+#[derive(Debug, Clone)]
+pub struct LookupTableSettings {
+    /// Compile swaps against the lookup table
+    pub enabled: bool,
+    /// Existing table owned by the wallet; a new one is created when empty
+    pub address: Option<Pubkey>,
+}
+
+impl LookupTableSettings {
+    /// Load lookup table settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("USE_LOOKUP_TABLE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            address: std::env::var("LOOKUP_TABLE_ADDRESS")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .and_then(|v| Pubkey::from_str(v.trim()).ok()),
+        }
+    }
+}
+
+/// Accounts nearly every swap of `wallet` touches: programs, fee and config accounts, the WSOL mint
+///
+/// Jito tip accounts are left out, tips must reference them directly.
+pub fn frequent_accounts(wallet: &Pubkey) -> Vec<Pubkey> {
+    let ids = program_ids();
+    vec![
+        system_program::ID,
+        compute_budget::ID,
+        sysvar::rent::ID,
+        spl_token::ID,
+        spl_token_2022::ID,
+        spl_associated_token_account::ID,
+        spl_token::native_mint::ID,
+        get_associated_token_address(wallet, &spl_token::native_mint::ID),
+        ids.pump_fun_program,
+        ids.pump_fun_global,
+        ids.pump_fun_fee_recipient,
+        ids.pump_fun_event_authority,
+        ids.pump_swap_program,
+        ids.pump_swap_global_config,
+        ids.pump_swap_event_authority,
+        ids.launchlab_program,
+        ids.launchlab_global_config,
+        ids.launchlab_platform_config,
+    ]
+}
+
+/// Accounts of `wanted` the table does not hold yet, in order and without repeats
+pub fn missing_accounts(table: &[Pubkey], wanted: &[Pubkey]) -> Vec<Pubkey> {
+    let mut missing: Vec<Pubkey> = Vec::new();
+    for account in wanted {
+        if !table.contains(account) && !missing.contains(account) {
+            missing.push(*account);
+        }
+    }
+    missing
+}
+
+/// Lookup tables transactions are compiled against
+static LOOKUP_TABLES: LazyLock<RwLock<Vec<AddressLookupTableAccount>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Lookup tables for the transaction builder, empty until one is loaded
+pub fn lookup_tables() -> Vec<AddressLookupTableAccount> {
+    LOOKUP_TABLES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+async fn fetch(rpc_client: &RpcClient, address: &Pubkey) -> Result<AddressLookupTableAccount> {
+    let account = rpc_client.get_account(address).await?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| anyhow!("{} is not a lookup table: {}", address, e))?;
+    Ok(AddressLookupTableAccount { key: *address, addresses: table.addresses.to_vec() })
+}
+
+async fn send(rpc_client: &RpcClient, payer: &dyn Signer, instruction: anchor_client::solana_sdk::instruction::Instruction) -> Result<()> {
+    let recent_blockhash = rpc_client.get_latest_blockhash().await?;
+    let txn = signed_transaction(&[instruction], payer, recent_blockhash)?;
+    rpc_client.send_and_confirm_transaction(&txn).await?;
+    Ok(())
+}
+
+/// Load the wallet's lookup table, creating it and adding missing accounts as needed
+///
+/// Extended addresses can only be used from the next slot, so this waits one slot
+/// before the table is handed to the transaction builder.
+pub async fn load_lookup_table(
+    rpc_client: &RpcClient,
+    payer: &dyn Signer,
+    settings: &LookupTableSettings,
+    logger: &Logger,
+) -> Result<Pubkey> {
+    let authority = payer.pubkey();
+    let address = match settings.address {
+        Some(address) => address,
+        None => {
+            ensure_armed()?;
+            let recent_slot = rpc_client.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+            let (instruction, address) = create_lookup_table(authority, authority, recent_slot);
+            send(rpc_client, payer, instruction).await?;
+            logger.log(format!("Created lookup table {}, set LOOKUP_TABLE_ADDRESS to reuse it", address).green().to_string());
+            address
+        }
+    };
+
+    let table = fetch(rpc_client, &address).await?;
+    let missing = missing_accounts(&table.addresses, &frequent_accounts(&authority));
+    if !missing.is_empty() {
+        ensure_armed()?;
+        for chunk in missing.chunks(EXTEND_CHUNK) {
+            send(rpc_client, payer, extend_lookup_table(address, authority, Some(authority), chunk.to_vec())).await?;
+        }
+        logger.log(format!("Added {} accounts to lookup table {}", missing.len(), address));
+        let extended_at = rpc_client.get_slot().await?;
+        while rpc_client.get_slot().await? <= extended_at {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
+    let table = fetch(rpc_client, &address).await?;
+    logger.log(format!("Compiling swaps against lookup table {} ({} accounts)", address, table.addresses.len()));
+    *LOOKUP_TABLES.write().unwrap_or_else(|e| e.into_inner()) = vec![table];
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_accounts() {
+        let wallet = Pubkey::new_unique();
+        let wanted = frequent_accounts(&wallet);
+        assert!(wanted.contains(&program_ids().pump_fun_program));
+        assert!(wanted.contains(&get_associated_token_address(&wallet, &spl_token::native_mint::ID)));
+
+        let held = vec![wanted[0], wanted[3], Pubkey::new_unique()];
+        let missing = missing_accounts(&held, &wanted);
+        assert_eq!(missing.len(), wanted.len() - 2);
+        assert_eq!(missing[0], wanted[1]);
+        assert!(missing_accounts(&wanted, &wanted).is_empty());
+        assert_eq!(missing_accounts(&[], &[wanted[1], wanted[1]]), vec![wanted[1]]);
+    }
+}
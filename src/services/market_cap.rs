@@ -0,0 +1,182 @@
+use anyhow::Result;
+
+use crate::common::reason_code::ReasonCode;
+use crate::dex::pump_fun::{BondingCurveReserves, INITIAL_VIRTUAL_SOL_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES, TOKEN_TOTAL_SUPPLY};
+use crate::engine::trade_executor::{price_from_reserves, TradeExecutor, PUMP_TOKEN_DECIMALS};
+use crate::services::price_feed::sol_usd;
+
+/// Unit of `MIN_MARKET_CAP`/`MAX_MARKET_CAP` and of every market cap handed to the filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketCapUnit {
+    Usd,
+    /// Thousands of USD, so the default 8-15 range reads $8K-$15K
+    UsdThousands,
+    Sol,
+}
+
+impl MarketCapUnit {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "usd" => Some(MarketCapUnit::Usd),
+            "usd_thousands" | "kusd" => Some(MarketCapUnit::UsdThousands),
+            "sol" => Some(MarketCapUnit::Sol),
+            _ => None,
+        }
+    }
+
+    /// Unit set by `MARKET_CAP_UNIT`, thousands of USD by default
+    pub fn from_env() -> Self {
+        std::env::var("MARKET_CAP_UNIT")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or(MarketCapUnit::UsdThousands)
+    }
+
+    /// A market cap in this unit for display, like `$12.50K` or `42.10 SOL`
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            MarketCapUnit::Usd => format!("${:.0}", value),
+            MarketCapUnit::UsdThousands => format!("${:.2}K", value),
+            MarketCapUnit::Sol => format!("{:.2} SOL", value),
+        }
+    }
+
+    /// Why a market cap in this unit is outside `min`-`max`, `None` when it is inside
+    ///
+    /// An unknown market cap fails, like any filter missing its data.
+    pub fn rejection(&self, market_cap: Option<f64>, min: f64, max: f64) -> Option<(ReasonCode, String)> {
+        let Some(market_cap) = market_cap else {
+            return Some((ReasonCode::FilterMarketCapUnknown, "market cap unknown".to_string()));
+        };
+        let code = if market_cap < min {
+            ReasonCode::FilterMarketCapTooLow
+        } else if market_cap > max {
+            ReasonCode::FilterMarketCapTooHigh
+        } else {
+            return None;
+        };
+        Some((code, format!(
+            "market cap {} not in range {}-{}",
+            self.format(market_cap), self.format(min), self.format(max)
+        )))
+    }
+}
+
+/// Market cap of a token; the SOL figure is always known, the USD one once the SOL/USD price is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketCap {
+    pub sol: f64,
+    pub usd: Option<f64>,
+}
+
+impl MarketCap {
+    /// From the SOL price of one whole token and the number of whole tokens minted
+    pub fn from_price(price_sol: f64, supply_tokens: f64, sol_usd: Option<f64>) -> Self {
+        let sol = price_sol * supply_tokens;
+        Self { sol, usd: sol_usd.map(|price| sol * price) }
+    }
+
+    /// From bonding curve or PumpSwap pool reserves, over the fixed pump.fun supply
+    pub fn from_reserves(reserves: &BondingCurveReserves, sol_usd: Option<f64>) -> Self {
+        Self::from_price(price_from_reserves(reserves), whole_tokens(TOKEN_TOTAL_SUPPLY), sol_usd)
+    }
+
+    /// This market cap in `unit`; USD units are `None` until the SOL/USD price is known
+    pub fn in_unit(&self, unit: MarketCapUnit) -> Option<f64> {
+        match unit {
+            MarketCapUnit::Usd => self.usd,
+            MarketCapUnit::UsdThousands => self.usd.map(|usd| usd / 1000.0),
+            MarketCapUnit::Sol => Some(self.sol),
+        }
+    }
+
+    /// This market cap in `MARKET_CAP_UNIT`, the unit the filter thresholds are in
+    pub fn in_configured_unit(&self) -> Option<f64> {
+        self.in_unit(MarketCapUnit::from_env())
+    }
+}
+
+/// Whole tokens in `raw` base units of a pump.fun token
+pub fn whole_tokens(raw: u64) -> f64 {
+    raw as f64 / 10f64.powi(PUMP_TOKEN_DECIMALS as i32)
+}
+
+/// Curve reserves right after a pump.fun launch, the dev buy included
+pub fn launch_reserves(dev_buy_lamports: u64, dev_buy_tokens: u64) -> BondingCurveReserves {
+    BondingCurveReserves {
+        virtual_token_reserves: INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(dev_buy_tokens),
+        virtual_sol_reserves: INITIAL_VIRTUAL_SOL_RESERVES + dev_buy_lamports,
+    }
+}
+
+/// Market cap in `MARKET_CAP_UNIT` of `supply_tokens` whole tokens at `price_sol`, priced with the cached SOL/USD feed
+pub fn market_cap(price_sol: f64, supply_tokens: f64) -> Option<f64> {
+    MarketCap::from_price(price_sol, supply_tokens, sol_usd()).in_configured_unit()
+}
+
+/// Current market cap of `mint`, read from its bonding curve or PumpSwap pool
+pub async fn fetch_market_cap(executor: &TradeExecutor, mint: &str) -> Result<MarketCap> {
+    let price = executor.current_price(mint).await?;
+    Ok(MarketCap::from_price(price, whole_tokens(TOKEN_TOTAL_SUPPLY), sol_usd()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_market_cap() {
+        // a fresh curve prices 1B tokens at 30 SOL / 1.073B tokens
+        let launch = MarketCap::from_reserves(&launch_reserves(0, 0), Some(150.0));
+        assert!((launch.sol - 27.959).abs() < 0.001);
+        assert!((launch.usd.unwrap() - 4193.85).abs() < 0.01);
+        assert_eq!(whole_tokens(TOKEN_TOTAL_SUPPLY), 1_000_000_000.0);
+    }
+
+    #[test]
+    fn test_dev_buy_raises_market_cap() {
+        let launch = MarketCap::from_reserves(&launch_reserves(0, 0), Some(150.0));
+        let bought = MarketCap::from_reserves(&launch_reserves(1_000_000_000, 34_612_903_225_806), Some(150.0));
+        assert!(bought.sol > launch.sol);
+    }
+
+    #[test]
+    fn test_market_cap_in_each_unit() {
+        let launch = MarketCap::from_reserves(&launch_reserves(0, 0), Some(150.0));
+        assert!((launch.in_unit(MarketCapUnit::UsdThousands).unwrap() - 4.194).abs() < 0.001);
+        assert_eq!(launch.in_unit(MarketCapUnit::Sol), Some(launch.sol));
+    }
+
+    #[test]
+    fn test_usd_unknown_without_sol_price() {
+        let unpriced = MarketCap::from_price(0.00000003, 1_000_000_000.0, None);
+        assert!((unpriced.sol - 30.0).abs() < 1e-9);
+        assert_eq!(unpriced.in_unit(MarketCapUnit::Usd), None);
+        assert_eq!(unpriced.in_unit(MarketCapUnit::UsdThousands), None);
+    }
+
+    #[test]
+    fn test_parse_and_format_units() {
+        let unit = MarketCapUnit::parse("USD_THOUSANDS").unwrap();
+        assert_eq!(unit, MarketCapUnit::UsdThousands);
+        assert_eq!(unit.format(12.5), "$12.50K");
+        assert_eq!(MarketCapUnit::parse("eur"), None);
+    }
+
+    #[test]
+    fn test_range_rejection_codes() {
+        let unit = MarketCapUnit::UsdThousands;
+        assert_eq!(unit.rejection(Some(10.0), 8.0, 15.0), None);
+        assert_eq!(unit.rejection(Some(4.19), 8.0, 15.0).unwrap().0, ReasonCode::FilterMarketCapTooLow);
+        assert_eq!(unit.rejection(Some(20.0), 8.0, 15.0).unwrap().0, ReasonCode::FilterMarketCapTooHigh);
+        assert_eq!(unit.rejection(None, 8.0, 15.0).unwrap().0, ReasonCode::FilterMarketCapUnknown);
+    }
+
+    #[test]
+    fn test_rejection_message_uses_unit() {
+        assert_eq!(
+            MarketCapUnit::Sol.rejection(Some(90.0), 30.0, 80.0).unwrap().1,
+            "market cap 90.00 SOL not in range 30.00 SOL-80.00 SOL"
+        );
+    }
+}
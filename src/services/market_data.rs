@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::common::config::AdvancedFilterSettings;
+
+/// An API serving liquidity, volume and price data of traded tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketDataProvider {
+    Birdeye,
+    DexScreener,
+}
+
+impl MarketDataProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "birdeye" => Some(MarketDataProvider::Birdeye),
+            "dexscreener" => Some(MarketDataProvider::DexScreener),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarketDataProvider::Birdeye => "Birdeye",
+            MarketDataProvider::DexScreener => "DexScreener",
+        }
+    }
+}
+
+/// Market data client settings
+#[derive(Debug, Clone)]
+pub struct MarketDataSettings {
+    pub provider: MarketDataProvider,
+    /// Required for Birdeye
+    pub birdeye_api_key: String,
+    /// How long an answer, including "not listed", is reused for a mint
+    pub cache_secs: u64,
+    /// Requests sent per second at most
+    pub requests_per_sec: f64,
+}
+
+impl MarketDataSettings {
+    /// Load market data client settings from environment variables
+    pub fn from_env() -> Self {
+        let provider = std::env::var("MARKET_DATA_PROVIDER").unwrap_or_else(|_| "dexscreener".to_string());
+        Self {
+            provider: MarketDataProvider::parse(&provider).unwrap_or_else(|| {
+                eprintln!("Ignoring MARKET_DATA_PROVIDER: unknown provider '{}'", provider);
+                MarketDataProvider::DexScreener
+            }),
+            birdeye_api_key: std::env::var("BIRDEYE_API_KEY").unwrap_or_default(),
+            cache_secs: std::env::var("MARKET_DATA_CACHE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60),
+            requests_per_sec: std::env::var("MARKET_DATA_REQUESTS_PER_SEC")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+/// Liquidity, volume and price change of a token's main pool
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketData {
+    pub price_usd: Option<f64>,
+    pub liquidity_usd: Option<f64>,
+    pub volume_24h_usd: Option<f64>,
+    pub price_change_1h: Option<f64>,
+    pub price_change_24h: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+}
+
+impl MarketData {
+    /// Check against the market data filters, returning why the token fails
+    ///
+    /// Missing figures fail the filters they are needed for.
+    pub fn check(&self, filters: &AdvancedFilterSettings) -> Result<(), String> {
+        let liquidity = self.liquidity_usd.ok_or("no liquidity data")?;
+        if liquidity < filters.min_liquidity_usd {
+            return Err(format!("liquidity ${:.0} below ${:.0}", liquidity, filters.min_liquidity_usd));
+        }
+        if filters.min_volume_24h_usd > 0.0 {
+            let volume = self.volume_24h_usd.ok_or("no volume data")?;
+            if volume < filters.min_volume_24h_usd {
+                return Err(format!("24h volume ${:.0} below ${:.0}", volume, filters.min_volume_24h_usd));
+            }
+        }
+        if let Some(change) = self.price_change_1h {
+            if change < filters.min_price_change_1h || change > filters.max_price_change_1h {
+                return Err(format!(
+                    "1h price change {:.1}% not in range {}-{}%",
+                    change, filters.min_price_change_1h, filters.max_price_change_1h
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Numbers come as JSON numbers or strings depending on the API and field
+fn number(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Data of the most liquid Solana pair in a DexScreener `latest/dex/tokens` response
+fn parse_dexscreener(body: &Value) -> Option<MarketData> {
+    let pair = body.get("pairs")?
+        .as_array()?
+        .iter()
+        .filter(|pair| pair.get("chainId").and_then(Value::as_str) == Some("solana"))
+        .max_by(|a, b| {
+            let liquidity = |pair: &Value| number(pair.pointer("/liquidity/usd")).unwrap_or(0.0);
+            liquidity(a).total_cmp(&liquidity(b))
+        })?;
+    Some(MarketData {
+        price_usd: number(pair.get("priceUsd")),
+        liquidity_usd: number(pair.pointer("/liquidity/usd")),
+        volume_24h_usd: number(pair.pointer("/volume/h24")),
+        price_change_1h: number(pair.pointer("/priceChange/h1")),
+        price_change_24h: number(pair.pointer("/priceChange/h24")),
+        market_cap_usd: number(pair.get("marketCap")).or_else(|| number(pair.get("fdv"))),
+    })
+}
+
+/// Data in a Birdeye `defi/token_overview` response
+fn parse_birdeye(body: &Value) -> Option<MarketData> {
+    let data = body.get("data").filter(|data| data.is_object())?;
+    Some(MarketData {
+        price_usd: number(data.get("price")),
+        liquidity_usd: number(data.get("liquidity")),
+        volume_24h_usd: number(data.get("v24hUSD")),
+        price_change_1h: number(data.get("priceChange1hPercent")),
+        price_change_24h: number(data.get("priceChange24hPercent")),
+        market_cap_usd: number(data.get("marketCap")).or_else(|| number(data.get("mc"))),
+    })
+}
+
+/// Rate-limited, cached Birdeye or DexScreener lookups
+pub struct MarketDataClient {
+    settings: MarketDataSettings,
+    client: reqwest::Client,
+    cache: Mutex<HashMap<String, (Option<MarketData>, Instant)>>,
+    /// Earliest time the next request may go out
+    next_slot: tokio::sync::Mutex<Instant>,
+}
+
+impl MarketDataClient {
+    pub fn new(settings: MarketDataSettings) -> Self {
+        Self {
+            settings,
+            client: reqwest::Client::new(),
+            cache: Mutex::new(HashMap::new()),
+            next_slot: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    fn cached(&self, mint: &str) -> Option<Option<MarketData>> {
+        let ttl = Duration::from_secs(self.settings.cache_secs);
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get(mint).filter(|(_, at)| at.elapsed() < ttl).map(|(data, _)| data.clone())
+    }
+
+    /// Wait for the next free request slot
+    async fn throttle(&self) {
+        let interval = Duration::from_secs_f64(1.0 / self.settings.requests_per_sec.max(0.01));
+        let mut next_slot = self.next_slot.lock().await;
+        let now = Instant::now();
+        if *next_slot > now {
+            tokio::time::sleep_until(*next_slot).await;
+        }
+        *next_slot = (*next_slot).max(now) + interval;
+    }
+
+    async fn fetch(&self, mint: &str) -> Result<Option<MarketData>> {
+        self.throttle().await;
+        let body: Value = match self.settings.provider {
+            MarketDataProvider::DexScreener => self.client
+                .get(format!("https://api.dexscreener.com/latest/dex/tokens/{}", mint))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?,
+            MarketDataProvider::Birdeye => {
+                if self.settings.birdeye_api_key.is_empty() {
+                    return Err(anyhow!("BIRDEYE_API_KEY is not set"));
+                }
+                self.client
+                    .get("https://public-api.birdeye.so/defi/token_overview")
+                    .query(&[("address", mint)])
+                    .header("X-API-KEY", &self.settings.birdeye_api_key)
+                    .header("x-chain", "solana")
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?
+            }
+        };
+        Ok(match self.settings.provider {
+            MarketDataProvider::DexScreener => parse_dexscreener(&body),
+            MarketDataProvider::Birdeye => parse_birdeye(&body),
+        })
+    }
+
+    /// Market data of `mint`, or `None` when the provider does not list it
+    pub async fn get(&self, mint: &str) -> Result<Option<MarketData>> {
+        if let Some(data) = self.cached(mint) {
+            return Ok(data);
+        }
+        let data = self.fetch(mint).await?;
+        let ttl = Duration::from_secs(self.settings.cache_secs);
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.retain(|_, (_, at)| at.elapsed() < ttl);
+        cache.insert(mint.to_string(), (data.clone(), Instant::now()));
+        Ok(data)
+    }
+
+    pub fn provider(&self) -> MarketDataProvider {
+        self.settings.provider
+    }
+}
+
+static MARKET_DATA: LazyLock<MarketDataClient> = LazyLock::new(|| MarketDataClient::new(MarketDataSettings::from_env()));
+
+/// Get the process-wide market data client
+pub fn market_data() -> &'static MarketDataClient {
+    &MARKET_DATA
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_check_market_data() {
+        let dexscreener = json!({"pairs": [
+            {"chainId": "solana", "dexId": "pumpfun", "priceUsd": "0.0001", "liquidity": {"usd": 900.0}},
+            {"chainId": "solana", "dexId": "pumpswap", "priceUsd": "0.00012", "liquidity": {"usd": 45000.5},
+             "volume": {"h24": 120000}, "priceChange": {"h1": -3.5, "h24": 40}, "marketCap": 120000},
+        ]});
+        let data = parse_dexscreener(&dexscreener).unwrap();
+        assert_eq!(data.liquidity_usd, Some(45000.5));
+        assert_eq!(data.price_change_1h, Some(-3.5));
+        assert_eq!(parse_dexscreener(&json!({"pairs": null})), None);
+
+        let birdeye = json!({"success": true, "data": {"price": 0.00012, "liquidity": 45000.5, "v24hUSD": 120000, "priceChange1hPercent": -3.5, "mc": 120000}});
+        assert_eq!(parse_birdeye(&birdeye).unwrap().market_cap_usd, Some(120000.0));
+
+        let mut filters = AdvancedFilterSettings::default();
+        assert!(data.check(&filters).is_ok());
+        filters.min_liquidity_usd = 50_000.0;
+        assert!(data.check(&filters).is_err());
+        filters.min_liquidity_usd = 0.0;
+        filters.min_price_change_1h = 0.0;
+        assert!(data.check(&filters).is_err());
+        assert!(MarketData::default().check(&AdvancedFilterSettings::default()).is_err());
+    }
+}
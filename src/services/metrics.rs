@@ -0,0 +1,181 @@
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+use anyhow::Result;
+use axum::{routing::get, Router};
+use colored::Colorize;
+use prometheus::{
+    Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::common::logger::Logger;
+
+/// Metrics exporter settings
+#[derive(Debug, Clone)]
+pub struct MetricsSettings {
+    /// Whether the `/metrics` endpoint is served
+    pub enabled: bool,
+    /// Address the exporter binds to
+    pub bind_address: String,
+    /// Port the exporter listens on
+    pub port: u16,
+}
+
+impl MetricsSettings {
+    /// Load metrics settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("METRICS_ENABLED").unwrap_or_default() == "true",
+            bind_address: std::env::var("METRICS_BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0".to_string()),
+            port: std::env::var("METRICS_PORT")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(9184),
+        }
+    }
+}
+
+/// All Prometheus metrics exported by the bot
+pub struct Metrics {
+    pub registry: Registry,
+    /// Launch and trade events received from the stream
+    pub events_processed: IntCounter,
+    /// Tokens that passed every filter
+    pub filters_passed: IntCounter,
+    /// Tokens rejected by a filter, labelled by filter name
+    pub filters_rejected: IntCounterVec,
+    /// Buy submissions, labelled by result (confirmed/failed/abandoned)
+    pub buys: IntCounterVec,
+    /// Sell submissions, labelled by result (confirmed/failed/abandoned)
+    pub sells: IntCounterVec,
+    /// Time from submission to confirmation
+    pub landing_latency_seconds: Histogram,
+    /// Delay between the geyser server creating a streamed update and when we received it
+    pub grpc_lag_seconds: Histogram,
+    /// Submissions per relay, labelled by relay and whether it landed
+    pub relay_submissions: IntCounterVec,
+    /// Realized PnL in SOL since start
+    pub realized_pnl_sol: Gauge,
+    /// Trade tasks dropped by the full execution queue, labelled by priority
+    pub execution_shed: IntCounterVec,
+    /// Transactions dropped because their pre-send simulation failed
+    pub simulation_aborts: IntCounter,
+    /// Stream updates dropped under load, labelled by stream and reason (full/stale)
+    pub stream_dropped: IntCounterVec,
+    /// Stream updates waiting for their consumer, labelled by stream
+    pub stream_queued: IntGaugeVec,
+}
+
+impl Metrics {
+    fn new() -> Result<Self> {
+        let registry = Registry::new_custom(Some("vntr".to_string()), None)?;
+
+        let events_processed = IntCounter::new("events_processed_total", "Stream events processed")?;
+        let filters_passed = IntCounter::new("filters_passed_total", "Tokens that passed all filters")?;
+        let filters_rejected = IntCounterVec::new(
+            Opts::new("filters_rejected_total", "Tokens rejected by a filter"),
+            &["filter"],
+        )?;
+        let buys = IntCounterVec::new(Opts::new("buys_total", "Buy submissions by result"), &["result"])?;
+        let sells = IntCounterVec::new(Opts::new("sells_total", "Sell submissions by result"), &["result"])?;
+        let landing_latency_seconds = Histogram::with_opts(
+            HistogramOpts::new("landing_latency_seconds", "Time from submission to confirmation")
+                .buckets(vec![0.2, 0.4, 0.8, 1.2, 2.0, 3.0, 5.0, 10.0, 20.0, 30.0]),
+        )?;
+        let grpc_lag_seconds = Histogram::with_opts(
+            HistogramOpts::new("grpc_lag_seconds", "Delay between the server creating an update and receiving it")
+                .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0]),
+        )?;
+        let relay_submissions = IntCounterVec::new(
+            Opts::new("relay_submissions_total", "Submissions per relay"),
+            &["relay", "landed"],
+        )?;
+        let realized_pnl_sol = Gauge::new("realized_pnl_sol", "Realized PnL in SOL since start")?;
+        let execution_shed = IntCounterVec::new(
+            Opts::new("execution_shed_total", "Trade tasks dropped by the full execution queue"),
+            &["priority"],
+        )?;
+
+        let simulation_aborts = IntCounter::new(
+            "simulation_aborts_total",
+            "Transactions dropped because their pre-send simulation failed",
+        )?;
+        let stream_dropped = IntCounterVec::new(
+            Opts::new("stream_dropped_total", "Stream updates dropped under load"),
+            &["stream", "reason"],
+        )?;
+        let stream_queued = IntGaugeVec::new(
+            Opts::new("stream_queued", "Stream updates waiting for their consumer"),
+            &["stream"],
+        )?;
+
+        registry.register(Box::new(events_processed.clone()))?;
+        registry.register(Box::new(filters_passed.clone()))?;
+        registry.register(Box::new(filters_rejected.clone()))?;
+        registry.register(Box::new(buys.clone()))?;
+        registry.register(Box::new(sells.clone()))?;
+        registry.register(Box::new(landing_latency_seconds.clone()))?;
+        registry.register(Box::new(grpc_lag_seconds.clone()))?;
+        registry.register(Box::new(relay_submissions.clone()))?;
+        registry.register(Box::new(realized_pnl_sol.clone()))?;
+        registry.register(Box::new(execution_shed.clone()))?;
+        registry.register(Box::new(simulation_aborts.clone()))?;
+        registry.register(Box::new(stream_dropped.clone()))?;
+        registry.register(Box::new(stream_queued.clone()))?;
+
+        Ok(Self {
+            registry,
+            events_processed,
+            filters_passed,
+            filters_rejected,
+            buys,
+            sells,
+            landing_latency_seconds,
+            grpc_lag_seconds,
+            relay_submissions,
+            realized_pnl_sol,
+            execution_shed,
+            simulation_aborts,
+            stream_dropped,
+            stream_queued,
+        })
+    }
+
+    /// Encode every registered metric in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        let encoder = TextEncoder::new();
+        if let Err(e) = encoder.encode(&self.registry.gather(), &mut buffer) {
+            return format!("# failed to encode metrics: {}\n", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Process-wide metrics, recorded from anywhere in the bot
+pub static METRICS: LazyLock<Metrics> =
+    LazyLock::new(|| Metrics::new().expect("metrics registry should initialize"));
+
+/// Get the process-wide metrics
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+/// Serve `/metrics` for Prometheus scraping in the background
+pub async fn start_metrics_server(logger: Logger) -> Result<()> {
+    let settings = MetricsSettings::from_env();
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let addr: SocketAddr = format!("{}:{}", settings.bind_address, settings.port).parse()?;
+    let app = Router::new().route("/metrics", get(|| async { metrics().render() }));
+
+    logger.log(format!("📈 Metrics exporter listening on http://{}/metrics", addr).green().to_string());
+    tokio::spawn(async move {
+        if let Err(e) = axum::Server::bind(&addr).serve(app.into_make_service()).await {
+            logger.error(format!("Metrics server stopped: {}", e));
+        }
+    });
+
+    Ok(())
+}
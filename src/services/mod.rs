@@ -1,4 +1,26 @@
 pub mod jito;
+pub mod jito_leaders;
 pub mod nozomi;
 pub mod zeroslot;
 pub mod telegram;
+pub mod spam;
+pub mod tpu;
+pub mod subscription;
+pub mod dedup;
+pub mod bloxroute;
+pub mod geyser;
+pub mod lookup_table;
+pub mod market_data;
+pub mod health;
+pub mod metrics;
+pub mod price_feed;
+pub mod market_cap;
+pub mod pump_api;
+pub mod signals;
+pub mod token_metadata;
+pub mod webhook;
+pub mod api;
+pub mod backpressure;
+pub mod alerts;
+pub mod discord;
+pub mod leaderboard;
@@ -1,8 +1,104 @@
 use crate::common::config::import_env_var;
+use crate::error::ClientError;
 use anyhow::{anyhow, Result};
 use rand::{seq::IteratorRandom, thread_rng};
-use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde_json::{json, Value};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
 use std::{str::FromStr, sync::LazyLock};
-usd std::{str::new_price, sync::LazyLock}
 
 pub static NOZOMI_URL: LazyLock<String> = LazyLock::new(|| import_env_var("NOZOMI_URL"));
+
+pub fn get_tip_account() -> Result<Pubkey> {
+    let accounts = [
+        "TEMPaMeCRFAS9EKF53Jd6KpHxgL47uWLcpFArU1Fanq".to_string(),
+        "noz3jAjPiHuBPqiSPkkugaJDkJscPuRhYnSpbi8UvC4".to_string(),
+        "noz3str9KXfpKknefHji8L1mPgimezaiUyCHYMDv1GE".to_string(),
+        "noz6uoYCDijhu1V7cutCpwxNiSovEwLdRHPwmgCGDNo".to_string(),
+        "noz9EPNcT7WH6Sou3sr3GGjHQYVkN3DNirpbvDkv9YJ".to_string(),
+        "nozc5yT15LazbLTFVZzoNZCwjh3yUtW86LoUyqsBu4L".to_string(),
+        "nozFrhfnNGoyqwVuwPAW4aaGqempx4PU6g6D9CJMv7Z".to_string(),
+        "nozievPk7HyK1Rqy1MPJwVQ7qQg2QoJGyP71oeDwbsu".to_string(),
+    ];
+    let mut rng = thread_rng();
+    match accounts.iter().choose(&mut rng) {
+        Some(acc) => Ok(Pubkey::from_str(acc).inspect_err(|err| {
+            println!("nozomi: failed to parse Pubkey: {:?}", err);
+        })?),
+        None => Err(anyhow!("nozomi: no tip accounts available")),
+    }
+}
+
+// unit sol, NOZOMI_TIP_VALUE is in lamports
+pub async fn get_tip_value() -> Result<f64> {
+    match std::env::var("NOZOMI_TIP_VALUE") {
+        Ok(tip_value) => match u64::from_str(&tip_value) {
+            Ok(lamports) => Ok(lamports as f64 / 1_000_000_000.0),
+            Err(_) => Err(anyhow!("Invalid NOZOMI_TIP_VALUE in environment variable: '{}'", tip_value)),
+        },
+        Err(_) => Err(anyhow!("NOZOMI_TIP_VALUE environment variable not set")),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NozomiClient {
+    endpoint: String,
+    client: reqwest::Client,
+}
+
+impl NozomiClient {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, ClientError> {
+        let wire_transaction = bincode::serialize(transaction).map_err(|e| {
+            ClientError::Parse(
+                "Transaction serialization failed".to_string(),
+                e.to_string(),
+            )
+        })?;
+
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [
+                bs64::encode(&wire_transaction),
+                { "encoding": "base64" }
+            ]
+        });
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| ClientError::Send("Request failed".to_string(), e.to_string()))?;
+
+        let response_data: Value = response
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        if let Some(error) = response_data.get("error") {
+            return Err(ClientError::Send("Nozomi error".to_string(), error.to_string()));
+        }
+
+        let signature = response_data["result"].as_str().ok_or_else(|| {
+            ClientError::Parse(
+                "Invalid response format".to_string(),
+                "Missing result field".to_string(),
+            )
+        })?;
+        Signature::from_str(signature)
+            .map_err(|e| ClientError::Parse("Invalid signature".to_string(), e.to_string()))
+    }
+}
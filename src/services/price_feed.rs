@@ -0,0 +1,252 @@
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::common::config::create_coingecko_proxy;
+use crate::common::logger::Logger;
+
+/// Pyth SOL/USD price feed id
+const PYTH_SOL_USD_FEED: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+/// A source of the SOL/USD price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceProvider {
+    Pyth,
+    Binance,
+    CoinGecko,
+}
+
+impl PriceProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "pyth" => Some(PriceProvider::Pyth),
+            "binance" => Some(PriceProvider::Binance),
+            "coingecko" => Some(PriceProvider::CoinGecko),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PriceProvider::Pyth => "Pyth",
+            PriceProvider::Binance => "Binance",
+            PriceProvider::CoinGecko => "CoinGecko",
+        }
+    }
+}
+
+/// Parse a provider list like `pyth,binance,coingecko`, in fallback order
+pub fn parse_providers(value: &str) -> Result<Vec<PriceProvider>, String> {
+    let mut providers = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let provider = PriceProvider::parse(name).ok_or_else(|| format!("Unknown price provider '{}'", name))?;
+        if !providers.contains(&provider) {
+            providers.push(provider);
+        }
+    }
+    Ok(providers)
+}
+
+/// SOL/USD price feed settings
+#[derive(Debug, Clone)]
+pub struct PriceFeedSettings {
+    /// Providers tried in order until one answers
+    pub providers: Vec<PriceProvider>,
+    /// How long a fetched price is served before it counts as stale
+    pub ttl_secs: u64,
+    pub refresh_secs: u64,
+    /// Time one provider gets to answer
+    pub timeout_ms: u64,
+    pub pyth_url: String,
+}
+
+impl PriceFeedSettings {
+    /// Load SOL/USD price feed settings from environment variables
+    pub fn from_env() -> Self {
+        let providers = std::env::var("SOL_PRICE_PROVIDERS").unwrap_or_else(|_| "pyth,binance,coingecko".to_string());
+        Self {
+            providers: parse_providers(&providers).unwrap_or_else(|e| {
+                eprintln!("Ignoring SOL_PRICE_PROVIDERS: {}", e);
+                vec![PriceProvider::Pyth, PriceProvider::Binance, PriceProvider::CoinGecko]
+            }),
+            ttl_secs: std::env::var("SOL_PRICE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(120),
+            refresh_secs: std::env::var("SOL_PRICE_REFRESH_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(15),
+            timeout_ms: std::env::var("SOL_PRICE_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3000),
+            pyth_url: std::env::var("PYTH_HERMES_URL").unwrap_or_else(|_| "https://hermes.pyth.network".to_string()),
+        }
+    }
+}
+
+/// Price from a Hermes `updates/price/latest` response, scaled by its exponent
+fn parse_pyth(body: &Value) -> Option<f64> {
+    let price = body.get("parsed")?.get(0)?.get("price")?;
+    let mantissa: f64 = price.get("price")?.as_str()?.parse().ok()?;
+    let expo = price.get("expo")?.as_i64()?;
+    Some(mantissa * 10f64.powi(expo as i32))
+}
+
+/// Price from a Binance `ticker/price` response
+fn parse_binance(body: &Value) -> Option<f64> {
+    body.get("price")?.as_str()?.parse().ok()
+}
+
+/// Reject answers no market could have produced
+fn sane(price: f64) -> Option<f64> {
+    (price.is_finite() && price > 0.0).then_some(price)
+}
+
+struct CachedPrice {
+    usd: f64,
+    provider: PriceProvider,
+    fetched_at: Instant,
+}
+
+/// Cached SOL/USD price, refreshed in the background from the first provider that answers
+pub struct PriceFeed {
+    settings: PriceFeedSettings,
+    client: reqwest::Client,
+    cached: RwLock<Option<CachedPrice>>,
+}
+
+impl PriceFeed {
+    pub fn new(settings: PriceFeedSettings) -> Self {
+        Self {
+            settings,
+            client: reqwest::Client::new(),
+            cached: RwLock::new(None),
+        }
+    }
+
+    async fn fetch_from(&self, provider: PriceProvider) -> Result<f64> {
+        let price = match provider {
+            PriceProvider::Pyth => {
+                let url = format!(
+                    "{}/v2/updates/price/latest?ids[]={}",
+                    self.settings.pyth_url.trim_end_matches('/'), PYTH_SOL_USD_FEED
+                );
+                let body: Value = self.client.get(url).send().await?.error_for_status()?.json().await?;
+                parse_pyth(&body)
+            }
+            PriceProvider::Binance => {
+                let body: Value = self.client
+                    .get("https://api.binance.com/api/v3/ticker/price?symbol=SOLUSDT")
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                parse_binance(&body)
+            }
+            PriceProvider::CoinGecko => Some(create_coingecko_proxy().await?),
+        };
+        price.and_then(sane).ok_or_else(|| anyhow!("unexpected {} response", provider.name()))
+    }
+
+    /// Fetch from the providers in order and cache the first price, returning it
+    pub async fn refresh(&self) -> Result<f64> {
+        let timeout = Duration::from_millis(self.settings.timeout_ms.max(100));
+        let mut errors = Vec::new();
+        for provider in &self.settings.providers {
+            match tokio::time::timeout(timeout, self.fetch_from(*provider)).await {
+                Ok(Ok(usd)) => {
+                    *self.cached.write().unwrap_or_else(|e| e.into_inner()) = Some(CachedPrice {
+                        usd,
+                        provider: *provider,
+                        fetched_at: Instant::now(),
+                    });
+                    return Ok(usd);
+                }
+                Ok(Err(e)) => errors.push(format!("{}: {}", provider.name(), e)),
+                Err(_) => errors.push(format!("{}: timed out", provider.name())),
+            }
+        }
+        Err(anyhow!("No SOL/USD price ({})", errors.join("; ")))
+    }
+
+    /// Cached price, if one was fetched within the TTL
+    pub fn sol_usd(&self) -> Option<f64> {
+        let cached = self.cached.read().unwrap_or_else(|e| e.into_inner());
+        cached.as_ref()
+            .filter(|cached| cached.fetched_at.elapsed() < Duration::from_secs(self.settings.ttl_secs))
+            .map(|cached| cached.usd)
+    }
+
+    /// Provider of the cached price
+    pub fn provider(&self) -> Option<PriceProvider> {
+        self.cached.read().unwrap_or_else(|e| e.into_inner()).as_ref().map(|cached| cached.provider)
+    }
+}
+
+static PRICE_FEED: LazyLock<PriceFeed> = LazyLock::new(|| PriceFeed::new(PriceFeedSettings::from_env()));
+
+/// Get the process-wide SOL/USD price feed
+pub fn price_feed() -> &'static PriceFeed {
+    &PRICE_FEED
+}
+
+/// Latest SOL/USD price, or `None` while no provider has answered within the TTL
+pub fn sol_usd() -> Option<f64> {
+    price_feed().sol_usd()
+}
+
+/// Keep the SOL/USD price fresh in the background
+pub async fn start_price_feed_system(logger: Logger) {
+    let feed = price_feed();
+    match feed.refresh().await {
+        Ok(usd) => logger.log(format!(
+            "SOL/USD {:.2} from {}",
+            usd, feed.provider().map(|provider| provider.name()).unwrap_or("?")
+        ).green().to_string()),
+        Err(e) => logger.error(e.to_string()),
+    }
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(feed.settings.refresh_secs.max(1)));
+        interval.tick().await;
+        let mut failing = false;
+        loop {
+            interval.tick().await;
+            match feed.refresh().await {
+                Ok(_) => failing = false,
+                // log once per outage, the cached price keeps being served until the TTL
+                Err(e) if !failing => {
+                    failing = true;
+                    logger.error(e.to_string());
+                }
+                Err(_) => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_provider_responses() {
+        let pyth = json!({"parsed": [{"id": PYTH_SOL_USD_FEED, "price": {"price": "15234000000", "conf": "1000", "expo": -8}}]});
+        assert!((parse_pyth(&pyth).unwrap() - 152.34).abs() < 1e-9);
+        assert_eq!(parse_binance(&json!({"symbol": "SOLUSDT", "price": "151.20000000"})), Some(151.2));
+        assert_eq!(parse_binance(&json!({"code": -1121, "msg": "Invalid symbol."})), None);
+        assert_eq!(sane(0.0), None);
+
+        assert_eq!(
+            parse_providers("binance, pyth,binance").unwrap(),
+            vec![PriceProvider::Binance, PriceProvider::Pyth]
+        );
+        assert!(parse_providers("pyth,kraken").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+use anyhow::Result;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// pump.fun frontend API settings
+#[derive(Debug, Clone)]
+pub struct PumpApiSettings {
+    pub base_url: String,
+    /// Time one request gets to answer
+    pub timeout_ms: u64,
+    /// Lookups of a coin the API has not indexed yet, fresh launches take a moment
+    pub attempts: u32,
+    pub retry_ms: u64,
+}
+
+impl PumpApiSettings {
+    /// Load pump.fun API settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            base_url: std::env::var("PUMP_API_URL").unwrap_or_else(|_| "https://frontend-api-v3.pump.fun".to_string()),
+            timeout_ms: std::env::var("PUMP_API_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(2000),
+            attempts: std::env::var("PUMP_API_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(3),
+            retry_ms: std::env::var("PUMP_API_RETRY_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(500),
+        }
+    }
+}
+
+/// Coin page data served by the pump.fun frontend API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PumpCoin {
+    pub mint: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    pub image_uri: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+    #[serde(default)]
+    pub reply_count: u64,
+    /// Unix milliseconds the coin became king of the hill, if it ever did
+    pub king_of_the_hill_timestamp: Option<i64>,
+    pub usd_market_cap: Option<f64>,
+    #[serde(default)]
+    pub complete: bool,
+}
+
+fn present(value: &Option<String>) -> bool {
+    value.as_deref().map(|value| !value.trim().is_empty()).unwrap_or(false)
+}
+
+impl PumpCoin {
+    /// Twitter, Telegram and website links set on the coin page
+    pub fn social_count(&self) -> usize {
+        [&self.twitter, &self.telegram, &self.website].into_iter().filter(|link| present(link)).count()
+    }
+
+    pub fn has_image(&self) -> bool {
+        present(&self.image_uri)
+    }
+
+    pub fn is_king_of_the_hill(&self) -> bool {
+        self.king_of_the_hill_timestamp.is_some()
+    }
+}
+
+/// Client for the coin pages of the pump.fun frontend API
+pub struct PumpApiClient {
+    settings: PumpApiSettings,
+    client: reqwest::Client,
+}
+
+impl PumpApiClient {
+    pub fn new(settings: PumpApiSettings) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(settings.timeout_ms.max(100)))
+            .build()
+            .unwrap_or_default();
+        Self { settings, client }
+    }
+
+    /// Coin page of `mint`, or `None` when the API does not know it
+    pub async fn coin(&self, mint: &str) -> Result<Option<PumpCoin>> {
+        let url = format!("{}/coins/{}", self.settings.base_url.trim_end_matches('/'), mint);
+        let response = self.client.get(url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND || response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        let body = response.error_for_status()?.text().await?;
+        // unknown coins are also answered with an empty body
+        if body.trim().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&body)?))
+    }
+
+    /// The coin currently crowned king of the hill
+    pub async fn king_of_the_hill(&self) -> Result<Option<PumpCoin>> {
+        let url = format!("{}/coins/king-of-the-hill?includeNsfw=false", self.settings.base_url.trim_end_matches('/'));
+        let body = self.client.get(url).send().await?.error_for_status()?.text().await?;
+        if body.trim().is_empty() || body.trim() == "null" {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&body)?))
+    }
+
+    /// Coin page of a fresh launch, retried until the API has indexed it
+    pub async fn coin_when_indexed(&self, mint: &str) -> Result<Option<PumpCoin>> {
+        let attempts = self.settings.attempts.max(1);
+        for attempt in 1..=attempts {
+            match self.coin(mint).await {
+                Ok(Some(coin)) => return Ok(Some(coin)),
+                Ok(None) if attempt == attempts => return Ok(None),
+                Err(e) if attempt == attempts => return Err(e),
+                _ => tokio::time::sleep(Duration::from_millis(self.settings.retry_ms)).await,
+            }
+        }
+        Ok(None)
+    }
+}
+
+static PUMP_API: LazyLock<PumpApiClient> = LazyLock::new(|| PumpApiClient::new(PumpApiSettings::from_env()));
+
+/// Get the process-wide pump.fun API client
+pub fn pump_api() -> &'static PumpApiClient {
+    &PUMP_API
+}
+
+/// Coin page filter settings
+#[derive(Debug, Clone)]
+pub struct CoinPageFilter {
+    /// Skip coins without a Twitter, Telegram or website link
+    pub require_socials: bool,
+    /// Skip coins with fewer replies than this (0 = no minimum)
+    pub min_reply_count: u64,
+    /// Skip coins without an image
+    pub require_image: bool,
+}
+
+impl CoinPageFilter {
+    /// Load coin page filter settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            require_socials: std::env::var("REQUIRE_SOCIALS")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            min_reply_count: std::env::var("MIN_REPLY_COUNT")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0),
+            require_image: std::env::var("REQUIRE_IMAGE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.require_socials || self.min_reply_count > 0 || self.require_image
+    }
+
+    /// Reason to skip a coin, if its page fails the filter
+    pub fn rejection(&self, coin: &PumpCoin) -> Option<String> {
+        if self.require_socials && coin.social_count() == 0 {
+            return Some("no social links".to_string());
+        }
+        if coin.reply_count < self.min_reply_count {
+            return Some(format!("{} replies, minimum is {}", coin.reply_count, self.min_reply_count));
+        }
+        if self.require_image && !coin.has_image() {
+            return Some("no image".to_string());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coin_page_filter() {
+        let coin: PumpCoin = serde_json::from_str(r#"{
+            "mint": "Mint1111111111111111111111111111111111pump",
+            "name": "Coin", "symbol": "COIN",
+            "image_uri": "https://ipfs.io/ipfs/Qm",
+            "twitter": "", "telegram": null, "website": "https://coin.fun",
+            "reply_count": 4, "king_of_the_hill_timestamp": null,
+            "usd_market_cap": 6500.5, "complete": false, "nsfw": false
+        }"#).unwrap();
+        assert_eq!(coin.social_count(), 1);
+        assert!(coin.has_image());
+        assert!(!coin.is_king_of_the_hill());
+
+        let mut filter = CoinPageFilter { require_socials: true, min_reply_count: 0, require_image: true };
+        assert_eq!(filter.rejection(&coin), None);
+        filter.min_reply_count = 5;
+        assert_eq!(filter.rejection(&coin), Some("4 replies, minimum is 5".to_string()));
+        let bare = PumpCoin { reply_count: 10, ..Default::default() };
+        assert_eq!(filter.rejection(&bare), Some("no social links".to_string()));
+    }
+}
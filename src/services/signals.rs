@@ -0,0 +1,104 @@
+use std::str::FromStr;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::Deserialize;
+
+pub mod channels;
+
+/// Strategy name external signals are budgeted and attributed under
+pub const SIGNAL_STRATEGY: &str = "signal";
+
+/// Inbound signal endpoint settings
+#[derive(Debug, Clone)]
+pub struct SignalSettings {
+    /// Whether `POST /signal` is served by the control API
+    pub enabled: bool,
+    /// Bearer token accepted on `/signal` only, so signal sources never hold the full API token
+    pub token: String,
+    /// Largest buy a signal may ask for, in SOL
+    pub max_buy_sol: f64,
+}
+
+impl SignalSettings {
+    /// Load signal endpoint settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("SIGNAL_API_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            token: std::env::var("SIGNAL_TOKEN").unwrap_or_default(),
+            max_buy_sol: std::env::var("SIGNAL_MAX_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.5),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalSide {
+    Buy,
+    Sell,
+}
+
+/// A trade requested by an external alpha source
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Signal {
+    pub mint: String,
+    pub side: SignalSide,
+    /// SOL to spend on a buy (position sizer when absent), or percent of the holding to sell (100 when absent)
+    pub amount: Option<f64>,
+    /// Slippage cap in basis points; the configured `SLIPPAGE` applies when it is lower
+    pub max_slippage: Option<u64>,
+    /// Free-form name of the sender, for logs
+    pub source: Option<String>,
+}
+
+impl Signal {
+    /// Reject malformed signals and buys above the configured cap
+    pub fn validate(&self, settings: &SignalSettings) -> Result<(), String> {
+        Pubkey::from_str(&self.mint).map_err(|_| format!("invalid mint address '{}'", self.mint))?;
+        let Some(amount) = self.amount else {
+            return Ok(());
+        };
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("amount must be positive, got {}", amount));
+        }
+        match self.side {
+            SignalSide::Buy if amount > settings.max_buy_sol => {
+                Err(format!("buy of {} SOL above SIGNAL_MAX_BUY_SOL {}", amount, settings.max_buy_sol))
+            }
+            SignalSide::Sell if amount > 100.0 => Err(format!("sell percent must be at most 100, got {}", amount)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        self.source.as_deref().unwrap_or("unknown")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_signal() {
+        let settings = SignalSettings { enabled: true, token: "t".to_string(), max_buy_sol: 0.5 };
+        let mut signal: Signal = serde_json::from_str(
+            r#"{"mint": "So11111111111111111111111111111111111111112", "side": "buy", "amount": 0.2, "max_slippage": 300}"#
+        ).unwrap();
+        assert_eq!(signal.validate(&settings), Ok(()));
+        assert_eq!(signal.source(), "unknown");
+
+        signal.amount = Some(1.0);
+        assert!(signal.validate(&settings).is_err());
+        signal.side = SignalSide::Sell;
+        assert_eq!(signal.validate(&settings), Ok(()));
+        signal.amount = Some(150.0);
+        assert!(signal.validate(&settings).is_err());
+        signal.mint = "not-a-mint".to_string();
+        assert!(signal.validate(&settings).is_err());
+        assert!(serde_json::from_str::<Signal>(r#"{"mint": "m", "side": "hold"}"#).is_err());
+    }
+}
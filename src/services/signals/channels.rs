@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use colored::Colorize;
+use regex::Regex;
+use tokio::time::Instant;
+
+use crate::common::logger::Logger;
+use crate::engine::execution_queue::{submit, Priority};
+use crate::engine::filter_stats::record_rejection;
+use crate::engine::strategy::{execute_action, StrategyAction, TaggedAction};
+use crate::engine::trade_executor::TradeExecutor;
+use crate::services::alerts::{alert, Alert};
+use crate::services::pump_api::{pump_api, CoinPageFilter};
+
+/// Strategy name channel signals are budgeted and attributed under
+pub const CHANNEL_STRATEGY: &str = "channel";
+
+/// Base58 words the length of a Solana address
+static MINT_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b[1-9A-HJ-NP-Za-km-z]{32,44}\b").unwrap());
+
+/// Addresses posted in calls that are never the called token
+const IGNORED_MINTS: &[&str] = &[
+    "So11111111111111111111111111111111111111112",
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+];
+
+/// A watched channel and how much its calls are trusted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelWeight {
+    /// Username without `@`, lowercased, or the numeric chat id
+    pub channel: String,
+    /// Trust weight (0.0-1.0), used as the buy's confidence
+    pub weight: f64,
+}
+
+/// Parse a channel list like `alpha_calls:1.0,@degen:0.5`; a missing weight is 1.0
+pub fn parse_channels(value: &str) -> Result<Vec<ChannelWeight>, String> {
+    let mut channels = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (channel, weight) = match entry.split_once(':') {
+            Some((channel, weight)) => {
+                let weight = weight.trim().parse::<f64>().map_err(|_| format!("Invalid weight in '{}'", entry))?;
+                (channel, weight)
+            }
+            None => (entry, 1.0),
+        };
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(format!("Weight of '{}' must be between 0 and 1", channel.trim()));
+        }
+        let channel = channel.trim().trim_start_matches('@').to_lowercase();
+        if channel.is_empty() {
+            return Err(format!("Missing channel in '{}'", entry));
+        }
+        channels.push(ChannelWeight { channel, weight });
+    }
+    Ok(channels)
+}
+
+/// Valid mint addresses in a message, in order of appearance
+pub fn extract_mints(text: &str) -> Vec<String> {
+    let mut mints: Vec<String> = Vec::new();
+    for found in MINT_PATTERN.find_iter(text) {
+        let mint = found.as_str();
+        if IGNORED_MINTS.contains(&mint) || Pubkey::from_str(mint).is_err() || mints.iter().any(|m| m == mint) {
+            continue;
+        }
+        mints.push(mint.to_string());
+    }
+    mints
+}
+
+/// Telegram channel signal settings
+#[derive(Debug, Clone)]
+pub struct ChannelSignalSettings {
+    pub enabled: bool,
+    /// Telegram application credentials from my.telegram.org
+    pub api_id: i32,
+    pub api_hash: String,
+    /// Phone number of the listening account, used on the first login
+    pub phone: String,
+    /// File the logged-in session is kept in
+    pub session_path: String,
+    pub channels: Vec<ChannelWeight>,
+    /// Calls of a mint already acted on are ignored for this long, in seconds
+    pub cooldown_secs: u64,
+}
+
+impl ChannelSignalSettings {
+    /// Load Telegram channel signal settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("TG_SIGNALS_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            api_id: std::env::var("TG_API_ID")
+                .ok()
+                .and_then(|v| v.parse::<i32>().ok())
+                .unwrap_or(0),
+            api_hash: std::env::var("TG_API_HASH").unwrap_or_default(),
+            phone: std::env::var("TG_PHONE").unwrap_or_default(),
+            session_path: std::env::var("TG_SESSION_PATH").unwrap_or_else(|_| "telegram.session".to_string()),
+            channels: parse_channels(&std::env::var("TG_SIGNAL_CHANNELS").unwrap_or_default()).unwrap_or_else(|e| {
+                eprintln!("Ignoring TG_SIGNAL_CHANNELS: {}", e);
+                Vec::new()
+            }),
+            cooldown_secs: std::env::var("TG_SIGNAL_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(600),
+        }
+    }
+}
+
+/// A mint called in a watched channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelCall {
+    pub mint: String,
+    pub channel: String,
+    pub weight: f64,
+}
+
+/// Turns channel messages into calls, once per mint per cooldown
+pub struct ChannelSignals {
+    settings: ChannelSignalSettings,
+    called: HashMap<String, Instant>,
+}
+
+impl ChannelSignals {
+    pub fn new(settings: ChannelSignalSettings) -> Self {
+        Self { settings, called: HashMap::new() }
+    }
+
+    /// Trust weight of a channel, `None` if it is not watched
+    pub fn weight(&self, channel: &str) -> Option<f64> {
+        let channel = channel.trim_start_matches('@').to_lowercase();
+        self.settings.channels.iter().find(|watched| watched.channel == channel).map(|watched| watched.weight)
+    }
+
+    /// Calls in a message posted to `channel`
+    pub fn on_message(&mut self, channel: &str, text: &str, now: Instant) -> Vec<ChannelCall> {
+        let Some(weight) = self.weight(channel) else {
+            return Vec::new();
+        };
+        let cooldown = Duration::from_secs(self.settings.cooldown_secs);
+        self.called.retain(|_, at| now.duration_since(*at) < cooldown);
+        let mut calls = Vec::new();
+        for mint in extract_mints(text) {
+            if self.called.contains_key(&mint) {
+                continue;
+            }
+            self.called.insert(mint.clone(), now);
+            calls.push(ChannelCall { mint, channel: channel.to_string(), weight });
+        }
+        calls
+    }
+}
+
+/// Run a call through the coin page filter, then buy it like any strategy signal
+async fn act_on_call(executor: Arc<TradeExecutor>, coin_page: CoinPageFilter, call: ChannelCall, logger: Logger) {
+    if coin_page.is_enabled() {
+        let reason = match pump_api().coin(&call.mint).await {
+            Ok(Some(coin)) => coin_page.rejection(&coin),
+            Ok(None) => Some("coin page not found".to_string()),
+            Err(e) => Some(format!("coin page lookup failed: {}", e)),
+        };
+        if let Some(reason) = reason {
+            logger.log(format!("Skipping {} from {}: {}", call.mint, call.channel, reason).yellow().to_string());
+            record_rejection("coin_page", &call.mint);
+            alert(Alert::filter(&call.mint, "coin_page", &reason));
+            return;
+        }
+    }
+    let action = TaggedAction {
+        strategy: CHANNEL_STRATEGY,
+        action: StrategyAction::Buy {
+            mint: call.mint,
+            sol_amount: None,
+            confidence: Some(call.weight),
+            reason: format!("called in {} (weight {:.2})", call.channel, call.weight),
+        },
+    };
+    submit(Priority::Buy, "channel buy", execute_action(executor, action, logger));
+}
+
+/// Listen to the configured channels with a Telegram user account and buy the mints they call
+#[cfg(feature = "telegram-client")]
+pub async fn start_channel_signal_system(executor: Arc<TradeExecutor>, settings: ChannelSignalSettings, logger: Logger) -> Result<()> {
+    use grammers_client::Update;
+
+    let client = client::connect(&settings).await?;
+    logger.log(format!("Watching {} Telegram channels for calls", settings.channels.len()).green().to_string());
+    let coin_page = CoinPageFilter::from_env();
+    let mut signals = ChannelSignals::new(settings);
+    tokio::spawn(async move {
+        loop {
+            let update = match client.next_update().await {
+                Ok(update) => update,
+                Err(e) => {
+                    logger.error(format!("Telegram updates stopped: {}", e));
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            let Update::NewMessage(message) = update else {
+                continue;
+            };
+            let chat = message.chat();
+            // channels are matched by username, or by id when they have none
+            let channel = chat.username().map(str::to_string).unwrap_or_else(|| chat.id().to_string());
+            for call in signals.on_message(&channel, message.text(), Instant::now()) {
+                logger.log(format!("{} called {}", call.channel, call.mint).magenta().to_string());
+                tokio::spawn(act_on_call(executor.clone(), coin_page.clone(), call, logger.clone()));
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(feature = "telegram-client"))]
+pub async fn start_channel_signal_system(_executor: Arc<TradeExecutor>, _settings: ChannelSignalSettings, _logger: Logger) -> Result<()> {
+    Err(anyhow::anyhow!("TG_SIGNALS_ENABLED=true needs a build with --features telegram-client"))
+}
+
+#[cfg(feature = "telegram-client")]
+mod client {
+    use anyhow::{anyhow, Result};
+    use grammers_client::{Client, Config, SignInError};
+    use grammers_session::Session;
+
+    use super::ChannelSignalSettings;
+
+    /// Connect with the saved session, logging in interactively on the first run
+    pub async fn connect(settings: &ChannelSignalSettings) -> Result<Client> {
+        if settings.api_id == 0 || settings.api_hash.is_empty() {
+            return Err(anyhow!("TG_API_ID and TG_API_HASH are required for channel signals"));
+        }
+        let client = Client::connect(Config {
+            session: Session::load_file_or_create(&settings.session_path)?,
+            api_id: settings.api_id,
+            api_hash: settings.api_hash.clone(),
+            params: Default::default(),
+        }).await?;
+        if client.is_authorized().await? {
+            return Ok(client);
+        }
+        if settings.phone.is_empty() {
+            return Err(anyhow!("TG_PHONE is required for the first Telegram login"));
+        }
+        let token = client.request_login_code(&settings.phone).await?;
+        let code = rpassword::prompt_password("Telegram login code: ")?;
+        match client.sign_in(&token, code.trim()).await {
+            Ok(_) => {}
+            Err(SignInError::PasswordRequired(password_token)) => {
+                let password = rpassword::prompt_password("Telegram two-step password: ")?;
+                client.check_password(password_token, password.trim()).await?;
+            }
+            Err(e) => return Err(anyhow!("Telegram login failed: {}", e)),
+        }
+        client.session().save_to_file(&settings.session_path)?;
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+
+    fn call_text() -> String {
+        format!("🚀 New call!\nCA: {}\nhttps://pump.fun/coin/{}\npaired with So11111111111111111111111111111111111111112", MINT, MINT)
+    }
+
+    fn signals() -> ChannelSignals {
+        ChannelSignals::new(ChannelSignalSettings {
+            enabled: true,
+            api_id: 1,
+            api_hash: String::new(),
+            phone: String::new(),
+            session_path: String::new(),
+            channels: parse_channels("@Alpha_Calls:0.9, degen:0.4,-1001234567890").unwrap(),
+            cooldown_secs: 60,
+        })
+    }
+
+    #[test]
+    fn test_parse_channels_and_weights() {
+        let channels = parse_channels("@Alpha_Calls:0.9, degen:0.4,-1001234567890").unwrap();
+        assert_eq!(channels[0], ChannelWeight { channel: "alpha_calls".to_string(), weight: 0.9 });
+        assert_eq!(channels[2].weight, 1.0);
+        assert!(parse_channels("alpha:1.5").is_err());
+    }
+
+    #[test]
+    fn test_extract_mints_skips_wsol_and_duplicates() {
+        assert_eq!(extract_mints(&call_text()), vec![MINT.to_string()]);
+        assert!(extract_mints("no address here, just 0OIl words").is_empty());
+    }
+
+    #[test]
+    fn test_calls_only_from_watched_channels() {
+        let mut signals = signals();
+        let now = Instant::now();
+        assert!(signals.on_message("unknown", &call_text(), now).is_empty());
+        let calls = signals.on_message("Alpha_Calls", &call_text(), now);
+        assert_eq!(calls, vec![ChannelCall { mint: MINT.to_string(), channel: "Alpha_Calls".to_string(), weight: 0.9 }]);
+    }
+
+    #[test]
+    fn test_repeated_call_waits_for_cooldown() {
+        let mut signals = signals();
+        let now = Instant::now();
+        signals.on_message("Alpha_Calls", &call_text(), now);
+        assert!(signals.on_message("degen", &call_text(), now + Duration::from_secs(10)).is_empty());
+        assert_eq!(signals.on_message("degen", &call_text(), now + Duration::from_secs(61)).len(), 1);
+    }
+}
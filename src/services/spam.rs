@@ -0,0 +1,215 @@
+use std::sync::{Arc, OnceLock};
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use tokio::time::Instant;
+use anchor_client::solana_client::{
+    nonblocking::{rpc_client::RpcClient, tpu_client::TpuClient},
+    rpc_config::RpcSendTransactionConfig,
+    tpu_client::TpuClientConfig,
+};
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use crate::common::logger::Logger;
+
+/// Settings for broadcasting one signed transaction through many paths at once
+#[derive(Debug, Clone, Default)]
+pub struct SpamSettings {
+    /// Whether spam-send mode is enabled
+    pub enabled: bool,
+    /// Extra sendTransaction endpoints to broadcast to
+    pub rpc_endpoints: Vec<String>,
+    /// Whether to also push the transaction to the leader TPU over QUIC
+    pub use_tpu: bool,
+}
+
+impl SpamSettings {
+    /// Load spam-send settings from environment variables
+    pub fn from_env() -> Self {
+        let rpc_endpoints = std::env::var("SPAM_RPC_ENDPOINTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        Self {
+            enabled: std::env::var("SPAM_SEND_ENABLED").unwrap_or_default() == "true",
+            rpc_endpoints,
+            use_tpu: std::env::var("SPAM_USE_TPU").unwrap_or_default() == "true",
+        }
+    }
+}
+
+/// Outcome of sending through a single path
+#[derive(Debug, Clone)]
+pub struct PathResult {
+    /// Endpoint URL or "tpu"
+    pub path: String,
+    /// Whether the path accepted the transaction
+    pub accepted: bool,
+    /// Error message if the path rejected it
+    pub error: Option<String>,
+}
+
+/// Broadcasts an identical signed transaction to several RPCs and the TPU
+///
+/// Because every path carries the same bytes there is only ever one signature, so
+/// duplicate "already processed" responses are counted as accepted rather than as
+/// failures, and callers track a single signature downstream.
+pub struct SpamSender {
+    rpc_clients: Vec<(String, Arc<RpcClient>)>,
+    tpu_client: Option<Arc<TpuClient>>,
+    logger: Logger,
+}
+
+impl SpamSender {
+    /// Create a spam sender from settings, connecting the TPU client if requested
+    pub async fn new(settings: &SpamSettings, logger: Logger) -> Result<Self> {
+        let rpc_clients = settings
+            .rpc_endpoints
+            .iter()
+            .map(|url| {
+                (
+                    url.clone(),
+                    Arc::new(RpcClient::new_with_commitment(url.clone(), CommitmentConfig::processed())),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let tpu_client = if settings.use_tpu {
+            let rpc_http = std::env::var("RPC_HTTP").unwrap_or_default();
+            let rpc_wss = std::env::var("RPC_WSS").unwrap_or_default();
+            let rpc_client = Arc::new(RpcClient::new_with_commitment(rpc_http, CommitmentConfig::processed()));
+            match TpuClient::new("spam-sender", rpc_client, &rpc_wss, TpuClientConfig::default()).await {
+                Ok(client) => Some(Arc::new(client)),
+                Err(e) => {
+                    logger.error(format!("Failed to start TPU client, continuing with RPCs only: {}", e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if rpc_clients.is_empty() && tpu_client.is_none() {
+            return Err(anyhow!("Spam-send enabled but no SPAM_RPC_ENDPOINTS or TPU path configured"));
+        }
+
+        logger.log(format!(
+            "Spam-send ready: {} RPC endpoints, TPU {}",
+            rpc_clients.len(),
+            if tpu_client.is_some() { "enabled" } else { "disabled" }
+        ));
+
+        Ok(Self {
+            rpc_clients,
+            tpu_client,
+            logger,
+        })
+    }
+
+    /// Send the same signed transaction through every configured path concurrently
+    pub async fn broadcast(&self, txn: &VersionedTransaction) -> Result<(Signature, Vec<PathResult>)> {
+        let start_time = Instant::now();
+        let signature = *txn
+            .signatures
+            .first()
+            .ok_or_else(|| anyhow!("Transaction is not signed"))?;
+
+        let mut handles = Vec::new();
+        for (url, client) in &self.rpc_clients {
+            let url = url.clone();
+            let client = client.clone();
+            let txn = txn.clone();
+            handles.push(tokio::spawn(async move {
+                let result = client
+                    .send_transaction_with_config(
+                        &txn,
+                        RpcSendTransactionConfig {
+                            skip_preflight: true,
+                            max_retries: Some(0),
+                            ..RpcSendTransactionConfig::default()
+                        },
+                    )
+                    .await;
+                match result {
+                    Ok(_) => PathResult { path: url, accepted: true, error: None },
+                    Err(e) if is_duplicate_error(&e.to_string()) => PathResult { path: url, accepted: true, error: None },
+                    Err(e) => PathResult { path: url, accepted: false, error: Some(e.to_string()) },
+                }
+            }));
+        }
+
+        if let Some(tpu_client) = &self.tpu_client {
+            let tpu_client = tpu_client.clone();
+            let wire_transaction = bincode::serialize(txn)?;
+            handles.push(tokio::spawn(async move {
+                let accepted = tpu_client.send_wire_transaction(wire_transaction).await;
+                PathResult {
+                    path: "tpu".to_string(),
+                    accepted,
+                    error: if accepted { None } else { Some("no leader accepted the transaction".to_string()) },
+                }
+            }));
+        }
+
+        let mut results = Vec::new();
+        for handle in futures::future::join_all(handles).await {
+            match handle {
+                Ok(result) => results.push(result),
+                Err(e) => results.push(PathResult {
+                    path: "unknown".to_string(),
+                    accepted: false,
+                    error: Some(format!("Task failed: {:?}", e)),
+                }),
+            }
+        }
+
+        let accepted = results.iter().filter(|r| r.accepted).count();
+        self.logger.log(
+            format!(
+                "[TXN-ELLAPSED(SPAM)]: {:?} - {} accepted by {}/{} paths",
+                start_time.elapsed(),
+                signature,
+                accepted,
+                results.len()
+            )
+            .yellow()
+            .to_string(),
+        );
+
+        if accepted == 0 {
+            let errors: Vec<String> = results
+                .iter()
+                .filter_map(|r| r.error.as_ref().map(|e| format!("{}: {}", r.path, e)))
+                .collect();
+            return Err(anyhow!("All spam paths failed: {:?}", errors));
+        }
+
+        Ok((signature, results))
+    }
+}
+
+static SPAM_SENDER: OnceLock<Arc<SpamSender>> = OnceLock::new();
+
+/// Running spam sender, if `SPAM_SEND_ENABLED` started one
+pub fn spam_sender() -> Option<Arc<SpamSender>> {
+    SPAM_SENDER.get().cloned()
+}
+
+/// Connect the spam sender, after which executor trades are broadcast through every path
+pub async fn start_spam_sender(settings: &SpamSettings, logger: Logger) -> Result<()> {
+    let sender = SpamSender::new(settings, logger).await?;
+    SPAM_SENDER.set(Arc::new(sender)).map_err(|_| anyhow!("Spam sender already started"))?;
+    Ok(())
+}
+
+/// Whether an RPC error just means another path already delivered the same transaction
+pub fn is_duplicate_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("already been processed") || message.contains("alreadyprocessed")
+}
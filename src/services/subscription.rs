@@ -0,0 +1,119 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestAccountsDataSlice, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterTransactions, SubscribeRequestPing,
+};
+
+/// Bonding curve bytes the bot reads: after the 8-byte discriminator, the five
+/// reserve/supply u64s and the `complete` flag
+pub const CURVE_DATA_SLICE_OFFSET: u64 = 8;
+pub const CURVE_DATA_SLICE_LENGTH: u64 = 41;
+
+/// Builds subscribe requests that filter on the server, so only the updates a
+/// stream actually reads are sent and decoded
+///
+/// Sending a new request on an open stream replaces all of its filters.
+pub struct SubscriptionBuilder {
+    request: SubscribeRequest,
+}
+
+impl Default for SubscriptionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubscriptionBuilder {
+    /// Empty request at processed commitment
+    pub fn new() -> Self {
+        Self {
+            request: SubscribeRequest {
+                commitment: Some(CommitmentLevel::Processed as i32),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Successful non-vote transactions touching any of `accounts` and all of `required`
+    ///
+    /// Skipped when both lists are empty, which would otherwise match every transaction.
+    pub fn transactions(mut self, name: &str, accounts: &[String], required: &[Pubkey]) -> Self {
+        if accounts.is_empty() && required.is_empty() {
+            return self;
+        }
+        self.request.transactions.insert(name.to_string(), SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: Some(false),
+            account_include: accounts.to_vec(),
+            account_required: required.iter().map(Pubkey::to_string).collect(),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Every successful transaction of `program`
+    pub fn program_transactions(self, name: &str, program: &Pubkey) -> Self {
+        self.transactions(name, &[], std::slice::from_ref(program))
+    }
+
+    /// Updates of the given accounts; skipped when `accounts` is empty
+    pub fn accounts(mut self, name: &str, accounts: &[String]) -> Self {
+        if accounts.is_empty() {
+            return self;
+        }
+        self.request.accounts.insert(name.to_string(), SubscribeRequestFilterAccounts {
+            account: accounts.to_vec(),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Only send `length` bytes from `offset` of each account's data
+    pub fn data_slice(mut self, offset: u64, length: u64) -> Self {
+        self.request.accounts_data_slice.push(SubscribeRequestAccountsDataSlice { offset, length });
+        self
+    }
+
+    /// Bonding curve accounts, trimmed to the reserve fields
+    pub fn curve_accounts(self, name: &str, curves: &[String]) -> Self {
+        if curves.is_empty() {
+            return self;
+        }
+        self.accounts(name, curves).data_slice(CURVE_DATA_SLICE_OFFSET, CURVE_DATA_SLICE_LENGTH)
+    }
+
+    pub fn build(self) -> SubscribeRequest {
+        self.request
+    }
+}
+
+/// Reply to a server ping so the stream is not dropped as idle
+pub fn ping_request() -> SubscribeRequest {
+    SubscribeRequest {
+        ping: Some(SubscribeRequestPing { id: 1 }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_are_narrow_and_empty_lists_are_skipped() {
+        let program = Pubkey::new_unique();
+        let request = SubscriptionBuilder::new()
+            .transactions("wallets", &["wallet".to_string()], &[program])
+            .transactions("nothing", &[], &[])
+            .curve_accounts("curves", &["curve".to_string()])
+            .build();
+
+        assert_eq!(request.transactions.len(), 1);
+        let filter = &request.transactions["wallets"];
+        assert_eq!(filter.failed, Some(false));
+        assert_eq!(filter.account_required, vec![program.to_string()]);
+        assert_eq!(request.accounts["curves"].account, vec!["curve".to_string()]);
+        assert_eq!(request.accounts_data_slice.len(), 1);
+        assert_eq!(request.accounts_data_slice[0].length, CURVE_DATA_SLICE_LENGTH);
+    }
+}
@@ -1,7 +1,22 @@
 use std::sync::{Arc, Mutex};
+use anchor_client::solana_sdk::native_token::LAMPORTS_PER_SOL;
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 use crate::common::logger::Logger;
+use crate::common::reason_code::{record_decision, Decision, ReasonCode};
+use crate::engine::circuit_breaker::circuit_breaker;
+use crate::engine::confidence::{ConfidenceInputs, ConfidenceScorer, ConfidenceSettings};
+use crate::engine::filter_stats::{filter_stats, record_rejection, record_rejection_as, report_message};
+use crate::engine::panic::panic_sell;
+use crate::engine::position_manager::{sell, SellAmount};
+use crate::engine::position_marks::position_marks;
+use crate::engine::token_age::TokenAgeFilter;
+use crate::engine::trade_executor::{resume_trading, TradeExecutor};
+use crate::services::alerts::escape_html;
+use crate::services::market_cap::MarketCapUnit;
+use crate::services::metrics::metrics;
+use crate::services::pump_api::{CoinPageFilter, PumpCoin};
+use crate::services::token_metadata::{MetadataFilter, TokenMetadata};
 use colored::Colorize;
 use anyhow::{Result, anyhow};
 use tokio::time::Duration;
@@ -18,12 +33,16 @@ const CONFIG_FILE_NAME: &str = "telegram_config.json";
 // Telegram filter settings
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TelegramFilterSettings {
+    // In MARKET_CAP_UNIT, thousands of USD by default
     pub market_cap: FilterRange<f64>,
     pub volume: FilterRange<f64>,
     pub buy_sell_count: FilterRange<i32>,
     pub sol_invested: f64,
     pub launcher_sol_balance: FilterRange<f64>,
     pub dev_buy_bundle: FilterRange<f64>,
+    // Bonding curve completion in percent; defaulted so older saved configs still load
+    #[serde(default = "default_curve_progress")]
+    pub curve_progress: FilterRange<f64>,
     // Track enabled/disabled status for each filter
     pub market_cap_enabled: bool,
     pub volume_enabled: bool,
@@ -31,6 +50,12 @@ pub struct TelegramFilterSettings {
     pub sol_invested_enabled: bool,
     pub launcher_sol_balance_enabled: bool,
     pub dev_buy_bundle_enabled: bool,
+    #[serde(default)]
+    pub curve_progress_enabled: bool,
+}
+
+fn default_curve_progress() -> FilterRange<f64> {
+    FilterRange { min: 0.0, max: 100.0 }
 }
 
 // Range filter for numeric values
@@ -40,6 +65,20 @@ pub struct FilterRange<T> {
     pub max: T,
 }
 
+/// SOL amount in lamports, rounded to the nearest lamport
+///
+/// Plain truncation turns e.g. 0.3 SOL into 299_999_999 lamports.
+pub fn sol_to_lamports(sol: f64) -> u64 {
+    (sol.max(0.0) * LAMPORTS_PER_SOL as f64).round() as u64
+}
+
+impl FilterRange<f64> {
+    /// Whether `lamports` falls inside this range of SOL amounts, bounds included
+    pub fn contains_lamports(&self, lamports: u64) -> bool {
+        sol_to_lamports(self.min) <= lamports && lamports <= sol_to_lamports(self.max)
+    }
+}
+
 // Load settings from environment variables
 impl TelegramFilterSettings {
     pub fn from_env() -> Self {
@@ -95,6 +134,10 @@ impl TelegramFilterSettings {
                 min: parse_f64_env("MIN_DEV_BUY", 5.0),
                 max: parse_f64_env("MAX_DEV_BUY", 30.0),
             },
+            curve_progress: FilterRange {
+                min: parse_f64_env("MIN_CURVE_PROGRESS", 0.0),
+                max: parse_f64_env("MAX_CURVE_PROGRESS", 100.0),
+            },
             // Set all filters to true by default
             market_cap_enabled: parse_bool_env("MARKET_CAP_ENABLED", true),
             volume_enabled: parse_bool_env("VOLUME_ENABLED", true), 
@@ -102,6 +145,7 @@ impl TelegramFilterSettings {
             sol_invested_enabled: parse_bool_env("SOL_INVESTED_ENABLED", true),
             launcher_sol_balance_enabled: parse_bool_env("LAUNCHER_SOL_ENABLED", true),
             dev_buy_bundle_enabled: parse_bool_env("DEV_BUY_ENABLED", true),
+            curve_progress_enabled: parse_bool_env("CURVE_PROGRESS_ENABLED", false),
         }
     }
     
@@ -148,17 +192,27 @@ impl Default for TelegramFilterSettings {
 }
 
 // Token information for filtered notifications
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct TokenInfo {
     pub address: String,
     pub name: Option<String>,
     pub symbol: Option<String>,
+    /// In `MARKET_CAP_UNIT`, the unit of the market cap filter thresholds
     pub market_cap: Option<f64>,
     pub volume: Option<f64>,
     pub buy_sell_count: Option<i32>,
     pub dev_buy_amount: Option<f64>,
+    /// Dev buy decoded from the launch transaction, in lamports
+    pub dev_buy_lamports: Option<u64>,
+    /// Tokens the dev received in the launch transaction, in raw units
+    pub dev_buy_tokens: Option<u64>,
     pub launcher_sol_balance: Option<f64>,
     pub bundle_check: Option<bool>,
+    pub bundled_percent: Option<f64>,
+    pub insider_check: Option<bool>,
+    pub insider_percent: Option<f64>,
+    pub curve_progress: Option<f64>,
+    pub token_age_secs: Option<u64>,
     pub ath: Option<f64>,
     // New fields to match screenshot
     pub buy_ratio: Option<i32>,
@@ -169,6 +223,12 @@ pub struct TokenInfo {
     pub token_price: Option<f64>,
     pub dev_wallet: Option<String>,
     pub sol_balance: Option<f64>,
+    /// pump.fun coin page, when it was fetched
+    pub pump_coin: Option<PumpCoin>,
+    /// Metadata URI set at launch
+    pub uri: Option<String>,
+    /// Metadata the URI resolved to, when it was fetched
+    pub metadata: Option<TokenMetadata>,
 }
 
 // Message to be sent to Telegram
@@ -256,6 +316,7 @@ pub struct TelegramService {
     last_notification_time: Instant,
     notification_interval: Duration,
     notified_tokens: Arc<Mutex<HashSet<String>>>, // Track tokens for which we've sent notifications
+    executor: Arc<Mutex<Option<Arc<TradeExecutor>>>>, // Set once trading starts, used by /snipe
 }
 
 impl TelegramService {
@@ -287,7 +348,130 @@ impl TelegramService {
             last_notification_time: Instant::now(),
             notification_interval: Duration::from_secs(notification_interval_secs),
             notified_tokens: Arc::new(Mutex::new(HashSet::new())), // Initialize empty set of notified tokens
+            executor: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    // Executor used by trading commands such as /snipe
+    pub fn set_executor(&self, executor: Arc<TradeExecutor>) {
+        *self.executor.lock().unwrap() = Some(executor);
+    }
+
+    // Handle "/snipe <mint> [amount_sol]"
+    async fn handle_snipe(&self, chat_id: &str, text: &str) -> Result<()> {
+        let mut parts = text.split_whitespace().skip(1);
+        let Some(mint) = parts.next() else {
+            return self.send_message(chat_id, "Usage: <code>/snipe &lt;mint&gt; [amount_sol]</code>", "HTML").await;
+        };
+        let amount = match parts.next().map(|v| v.parse::<f64>()) {
+            Some(Ok(amount)) => Some(amount),
+            Some(Err(_)) => return self.send_message(chat_id, "❌ Amount must be a number of SOL", "HTML").await,
+            None => None,
+        };
+        let executor = self.executor.lock().unwrap().clone();
+        let Some(executor) = executor else {
+            return self.send_message(chat_id, "❌ Trading is not running", "HTML").await;
+        };
+
+        let msg = match executor.snipe(mint, amount).await {
+            Ok((sol_amount, signature)) => format!(
+                "<b>🎯 Manual snipe sent</b>\n\nToken: <code>{}</code>\nAmount: {:.4} SOL\n🔗 <a href=\"https://solscan.io/tx/{}\">View Transaction</a>",
+                mint, sol_amount, signature
+            ),
+            Err(e) => format!("<b>❌ Snipe failed</b>\n\n<code>{}</code>: {}", mint, e),
+        };
+        self.send_message(chat_id, &msg, "HTML").await
+    }
+
+    // Handle "/sell <mint> [25% | 1000000 tokens | all]", selling everything without an amount
+    async fn handle_sell(&self, chat_id: &str, text: &str) -> Result<()> {
+        let mut parts = text.split_whitespace().skip(1);
+        let Some(mint) = parts.next() else {
+            return self.send_message(chat_id, "Usage: <code>/sell &lt;mint&gt; [25% | 1000000 tokens | all]</code>", "HTML").await;
+        };
+        let rest = parts.collect::<Vec<_>>().join(" ");
+        let amount = match rest.is_empty() {
+            true => SellAmount::All,
+            false => match SellAmount::parse(&rest) {
+                Ok(amount) => amount,
+                Err(e) => return self.send_message(chat_id, &format!("❌ {}", escape_html(&e)), "HTML").await,
+            },
+        };
+        let executor = self.executor.lock().unwrap().clone();
+        let Some(executor) = executor else {
+            return self.send_message(chat_id, "❌ Trading is not running", "HTML").await;
+        };
+
+        record_decision(Decision::Sell, mint, ReasonCode::ManualSell, "telegram");
+        let msg = match sell(&executor, mint, amount).await {
+            Ok(signature) => format!(
+                "<b>🟥 Manual sell sent</b>\n\nToken: <code>{}</code>\nAmount: {}\n🔗 <a href=\"https://solscan.io/tx/{}\">View Transaction</a>",
+                mint, amount, signature
+            ),
+            Err(e) => format!("<b>❌ Sell failed</b>\n\n<code>{}</code>: {}", mint, e),
+        };
+        self.send_message(chat_id, &msg, "HTML").await
+    }
+
+    // Handle "/panic": pause buying, drop queued buys and sell every position at any price
+    async fn handle_panic(&self, chat_id: &str) -> Result<()> {
+        let executor = self.executor.lock().unwrap().clone();
+        let Some(executor) = executor else {
+            return self.send_message(chat_id, "❌ Trading is not running", "HTML").await;
+        };
+        self.send_message(chat_id, "<b>🚨 Panic sell</b>\n\nPausing trading and selling everything...", "HTML").await?;
+
+        let report = panic_sell(&executor, &self.logger).await;
+        let mut msg = format!("<b>🚨 Panic sell done</b>\n\n{}\n", escape_html(&report.summary()));
+        for sell in &report.sells {
+            match (&sell.signature, &sell.error) {
+                (Some(signature), _) => msg.push_str(&format!(
+                    "\n✅ <code>{}</code> <a href=\"https://solscan.io/tx/{}\">tx</a>", sell.mint, signature
+                )),
+                (None, error) => msg.push_str(&format!(
+                    "\n❌ <code>{}</code>: {}", sell.mint, escape_html(error.as_deref().unwrap_or("-"))
+                )),
+            }
+        }
+        for mint in &report.untracked {
+            msg.push_str(&format!("\n⚠️ <code>{}</code> not sold, no tracked position", mint));
+        }
+        msg.push_str("\n\nSend /resume to start buying again once things look normal.");
+        self.send_message(chat_id, &msg, "HTML").await
+    }
+
+    async fn handle_positions(&self, chat_id: &str) -> Result<()> {
+        let marks = position_marks().snapshot();
+        if marks.is_empty() {
+            return self.send_message(chat_id, "No open positions", "HTML").await;
+        }
+        let mut msg = format!("<b>📊 Open positions ({})</b>\n", marks.len());
+        for (mint, mark) in marks {
+            let pnl_sol = mark.unrealized_pnl_sol().map(|pnl| format!(" ({:+.4} SOL)", pnl)).unwrap_or_default();
+            msg.push_str(&format!(
+                "\n<code>{}</code>\n{} {:+.1}%{} · max drawdown {:.1}% · marked {}s ago\n",
+                mint,
+                if mark.unrealized_pnl_percent() >= 0.0 { "🟢" } else { "🔴" },
+                mark.unrealized_pnl_percent(),
+                pnl_sol,
+                mark.max_drawdown_percent,
+                mark.updated_at.elapsed().as_secs()
+            ));
         }
+        self.send_message(chat_id, &msg, "HTML").await
+    }
+
+    async fn handle_stats(&self, chat_id: &str) -> Result<()> {
+        let msg = {
+            let stats = filter_stats();
+            report_message(&stats.report(chrono::Utc::now()), stats.settings().winner_percent)
+        };
+        self.send_message(chat_id, &msg, "HTML").await
+    }
+
+    // Shared handle to the live filter settings, for other control surfaces
+    pub fn filter_settings_handle(&self) -> Arc<Mutex<TelegramFilterSettings>> {
+        self.filter_settings.clone()
     }
 
     // Public method to get a clone of the current filter settings
@@ -362,9 +546,28 @@ impl TelegramService {
             "".to_string()
         };
         
+        // Bundled supply at launch
+        let bundle_info = if let Some(bundled) = token.bundled_percent {
+            format!("📦 <b>Bundled Supply:</b> {:.1}%", bundled)
+        } else {
+            "".to_string()
+        };
+        
+        // Replies, king of the hill and socials from the pump.fun coin page
+        let coin_page_info = if let Some(coin) = &token.pump_coin {
+            format!(
+                "💬 <b>Replies:</b> {} | <b>Socials:</b> {}{}",
+                coin.reply_count,
+                coin.social_count(),
+                if coin.is_king_of_the_hill() { " | 👑 King of the Hill" } else { "" }
+            )
+        } else {
+            "".to_string()
+        };
+        
         // Market cap information
         let market_cap_info = if let Some(market_cap) = token.market_cap {
-            format!("📊 <b>Market Cap:</b> {:.2}K", market_cap)
+            format!("📊 <b>Market Cap:</b> {}", MarketCapUnit::from_env().format(market_cap))
         } else {
             "".to_string()
         };
@@ -379,6 +582,8 @@ impl TelegramService {
             {}\n\
             {}\n\
             {}\n\
+            {}\n\
+            {}\n\
             {}\n\n\
             ⚠️ <b>This token passes all filter criteria:</b>\n\
             ├ Market Cap: {}\n\
@@ -395,6 +600,8 @@ impl TelegramService {
             dev_buy_info,
             launcher_sol_info,
             dev_wallet_info,
+            bundle_info,
+            coin_page_info,
             self.filter_settings.lock().unwrap().market_cap_enabled,
             self.filter_settings.lock().unwrap().volume_enabled,
             self.filter_settings.lock().unwrap().buy_sell_count_enabled,
@@ -473,7 +680,7 @@ impl TelegramService {
             let message_text = format!(
                 "⚙️ <b>Token Filter Settings</b>\n\n\
                 ❄️ <b>Monitoring Market Cap:</b> {}\n\
-                📈 Min MC: {}K  📉 Max MC: {}K\n\n\
+                📈 Min MC: {}  📉 Max MC: {}\n\n\
                 🔥 <b>Monitoring Volumes:</b> {}\n\
                 📈 Min: {}K  📉 Max: {}K\n\n\
                 ☀️ <b>Monitoring Number of Buy/Sell:</b> {}\n\
@@ -484,9 +691,12 @@ impl TelegramService {
                 📈 Min: {} Sol  📉 Max: {} Sol\n\n\
                 ❄️ <b>Dev Buy & Bundle Check:</b> {}\n\
                 📈 Min: {} Sol  📉 Max: {} Sol\n\
-                🔄 <b>Bundle Check Required:</b> {}",
+                🔄 <b>Bundle Check Required:</b> {}\n\n\
+                📊 <b>Monitoring Curve Progress:</b> {}\n\
+                📈 Min: {}%  📉 Max: {}%",
                 if settings.market_cap_enabled { "✅" } else { "❌" },
-                settings.market_cap.min, settings.market_cap.max,
+                MarketCapUnit::from_env().format(settings.market_cap.min),
+                MarketCapUnit::from_env().format(settings.market_cap.max),
                 if settings.volume_enabled { "✅" } else { "❌" },
                 settings.volume.min, settings.volume.max,
                 if settings.buy_sell_count_enabled { "✅" } else { "❌" },
@@ -501,7 +711,9 @@ impl TelegramService {
                     if bundle_check.to_lowercase() == "true" { "✅" } else { "❌" }
                 } else {
                     "❌"
-                }
+                },
+                if settings.curve_progress_enabled { "✅" } else { "❌" },
+                settings.curve_progress.min, settings.curve_progress.max
             );
             
             let keyboard_data = vec![
@@ -547,6 +759,13 @@ impl TelegramService {
                         callback_data: "toggle_dev_buy".to_string(),
                     },
                 ],
+                // Curve Progress row
+                vec![
+                    InlineKeyboardButton {
+                        text: format!("Curve Progress: {}", if settings.curve_progress_enabled { "✅" } else { "❌" }),
+                        callback_data: "toggle_curve_progress".to_string(),
+                    },
+                ],
             ];
             
             (message_text, keyboard_data)
@@ -582,6 +801,7 @@ impl TelegramService {
         if let Ok(notified_tokens) = self.notified_tokens.lock() {
             if notified_tokens.contains(&token.address) {
                 // Skip this token as we've already notified about it
+                metrics().filters_rejected.with_label_values(&["already_notified"]).inc();
                 return false;
             }
         }
@@ -599,6 +819,7 @@ impl TelegramService {
                         filter_settings.buy_sell_count.min,
                         filter_settings.buy_sell_count.max
                     ).yellow().to_string());
+                    record_rejection("buy_sell_count", &token.address);
                     return false;
                 }
             }
@@ -609,6 +830,7 @@ impl TelegramService {
                     "Token {} failed buy/sell count filter: no data available", 
                     token.address
                 ).yellow().to_string());
+                record_rejection("buy_sell_count", &token.address);
                 return false;
             }
         }
@@ -622,25 +844,20 @@ impl TelegramService {
         // Token age is stored in token_tracker, so we can't access it directly here
         // We'll rely on token_tracker ensuring this check before sending for notification
         
-        // Apply market cap filter
+        // Apply market cap filter, thresholds and token market cap both in MARKET_CAP_UNIT
         if filter_settings.market_cap_enabled {
-            if let Some(market_cap) = token.market_cap {
-                if market_cap < filter_settings.market_cap.min || market_cap > filter_settings.market_cap.max {
-                    self.logger.log(format!(
-                        "Token {} failed market cap filter: {:.2} not in range {}-{}", 
-                        token.address,
-                        market_cap,
-                        filter_settings.market_cap.min,
-                        filter_settings.market_cap.max
-                    ).yellow().to_string());
-                    return false;
-                }
-            } else {
-                // No market cap data available and this filter is enabled
+            let rejection = MarketCapUnit::from_env().rejection(
+                token.market_cap,
+                filter_settings.market_cap.min,
+                filter_settings.market_cap.max,
+            );
+            if let Some((code, reason)) = rejection {
                 self.logger.log(format!(
-                    "Token {} failed market cap filter: no data available", 
-                    token.address
+                    "Token {} failed market cap filter: {}",
+                    token.address,
+                    reason
                 ).yellow().to_string());
+                record_rejection_as("market_cap", &token.address, code);
                 return false;
             }
         }
@@ -656,6 +873,7 @@ impl TelegramService {
                         filter_settings.volume.min,
                         filter_settings.volume.max
                     ).yellow().to_string());
+                    record_rejection("volume", &token.address);
                     return false;
                 }
             } else {
@@ -664,6 +882,7 @@ impl TelegramService {
                     "Token {} failed volume filter: no data available", 
                     token.address
                 ).yellow().to_string());
+                record_rejection("volume", &token.address);
                 return false;
             }
         }
@@ -680,6 +899,7 @@ impl TelegramService {
                         filter_settings.launcher_sol_balance.min,
                         filter_settings.launcher_sol_balance.max
                     ).yellow().to_string());
+                    record_rejection("launcher_sol_balance", &token.address);
                     return false;
                 }
             } else {
@@ -688,22 +908,23 @@ impl TelegramService {
                     "Token {} failed launcher SOL filter: no data available", 
                     token.address
                 ).yellow().to_string());
+                record_rejection("launcher_sol_balance", &token.address);
                 return false;
             }
         }
         
-        // Apply dev buy amount filter
+        // Apply dev buy amount filter, in lamports so the bounds are exact
         if filter_settings.dev_buy_bundle_enabled {
-            if let Some(dev_buy) = token.dev_buy_amount {
-                if dev_buy < filter_settings.dev_buy_bundle.min || 
-                   dev_buy > filter_settings.dev_buy_bundle.max {
+            if let Some(dev_buy) = token.dev_buy_lamports.or_else(|| token.dev_buy_amount.map(sol_to_lamports)) {
+                if !filter_settings.dev_buy_bundle.contains_lamports(dev_buy) {
                     self.logger.log(format!(
-                        "Token {} failed dev buy filter: {:.2} SOL not in range {}-{}", 
+                        "Token {} failed dev buy filter: {:.9} SOL not in range {}-{}", 
                         token.address,
-                        dev_buy,
+                        dev_buy as f64 / LAMPORTS_PER_SOL as f64,
                         filter_settings.dev_buy_bundle.min,
                         filter_settings.dev_buy_bundle.max
                     ).yellow().to_string());
+                    record_rejection("dev_buy", &token.address);
                     return false;
                 }
             } else {
@@ -712,10 +933,137 @@ impl TelegramService {
                     "Token {} failed dev buy filter: no data available", 
                     token.address
                 ).yellow().to_string());
+                record_rejection("dev_buy", &token.address);
                 return false;
             }
         }
         
+        // Apply bonding curve progress filter
+        if filter_settings.curve_progress_enabled {
+            if let Some(progress) = token.curve_progress {
+                if progress < filter_settings.curve_progress.min ||
+                   progress > filter_settings.curve_progress.max {
+                    self.logger.log(format!(
+                        "Token {} failed curve progress filter: {:.1}% not in range {}-{}%", 
+                        token.address,
+                        progress,
+                        filter_settings.curve_progress.min,
+                        filter_settings.curve_progress.max
+                    ).yellow().to_string());
+                    record_rejection("curve_progress", &token.address);
+                    return false;
+                }
+            } else {
+                // No curve data available and this filter is enabled
+                self.logger.log(format!(
+                    "Token {} failed curve progress filter: no data available", 
+                    token.address
+                ).yellow().to_string());
+                record_rejection("curve_progress", &token.address);
+                return false;
+            }
+        }
+        
+        // Apply token age filter (MIN_TOKEN_AGE_SECS / MAX_TOKEN_AGE_SECS)
+        let age_filter = TokenAgeFilter::from_env();
+        if age_filter.is_enabled() {
+            if let Some(age) = token.token_age_secs {
+                if let Some(reason) = age_filter.rejection(age) {
+                    self.logger.log(format!(
+                        "Token {} failed token age filter: {}", 
+                        token.address,
+                        reason
+                    ).yellow().to_string());
+                    record_rejection("token_age", &token.address);
+                    return false;
+                }
+            } else {
+                // No creation time available and this filter is enabled
+                self.logger.log(format!(
+                    "Token {} failed token age filter: no data available", 
+                    token.address
+                ).yellow().to_string());
+                record_rejection("token_age", &token.address);
+                return false;
+            }
+        }
+        
+        // Reject launches the bundle analyzer flagged (BUNDLE_CHECK)
+        let bundle_check_enabled = std::env::var("BUNDLE_CHECK")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(true);
+        if bundle_check_enabled && token.bundle_check == Some(false) {
+            self.logger.log(format!(
+                "Token {} failed bundle check: {:.1}% of supply bundled at launch",
+                token.address,
+                token.bundled_percent.unwrap_or(0.0)
+            ).yellow().to_string());
+            record_rejection("bundle", &token.address);
+            return false;
+        }
+        
+        // Reject launches whose early buyers look like insiders (INSIDER_CHECK_ENABLED)
+        let insider_check_enabled = std::env::var("INSIDER_CHECK_ENABLED")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(true);
+        if insider_check_enabled && token.insider_check == Some(false) {
+            self.logger.log(format!(
+                "Token {} failed insider check: {:.0}% of early buyers look like insiders",
+                token.address,
+                token.insider_percent.unwrap_or(0.0)
+            ).yellow().to_string());
+            record_rejection("insiders", &token.address);
+            return false;
+        }
+        
+        // Reject coins whose pump.fun page fails REQUIRE_SOCIALS / MIN_REPLY_COUNT / REQUIRE_IMAGE
+        let coin_page_filter = CoinPageFilter::from_env();
+        if coin_page_filter.is_enabled() {
+            let rejection = match &token.pump_coin {
+                Some(coin) => coin_page_filter.rejection(coin),
+                None => Some("no data available".to_string()),
+            };
+            if let Some(reason) = rejection {
+                self.logger.log(format!(
+                    "Token {} failed coin page filter: {}",
+                    token.address,
+                    reason
+                ).yellow().to_string());
+                record_rejection("coin_page", &token.address);
+                return false;
+            }
+        }
+        
+        // Reject launches with unresolvable metadata or missing links (REQUIRE_METADATA / REQUIRE_TWITTER / ...)
+        let metadata_filter = MetadataFilter::from_env();
+        if metadata_filter.is_enabled() {
+            if let Some(reason) = metadata_filter.rejection(token.metadata.as_ref()) {
+                self.logger.log(format!(
+                    "Token {} failed metadata filter: {}",
+                    token.address,
+                    reason
+                ).yellow().to_string());
+                record_rejection("metadata", &token.address);
+                return false;
+            }
+        }
+        
+        // Gate on the combined confidence score (MIN_BUY_CONFIDENCE)
+        let scorer = ConfidenceScorer::new(ConfidenceSettings::from_env());
+        if scorer.settings().enabled {
+            if let Some(score) = scorer.score(&ConfidenceInputs::from_token_info(token)) {
+                if let Some(reason) = scorer.rejection(&score) {
+                    self.logger.log(format!(
+                        "Token {} failed confidence filter: {}", 
+                        token.address,
+                        reason
+                    ).yellow().to_string());
+                    record_rejection("confidence", &token.address);
+                    return false;
+                }
+            }
+        }
+        
         // Apply SOL invested filter
         if filter_settings.sol_invested_enabled {
             if let Some(volume) = token.volume {
@@ -726,6 +1074,7 @@ impl TelegramService {
                         volume,
                         filter_settings.sol_invested
                     ).yellow().to_string());
+                    record_rejection("sol_invested", &token.address);
                     return false;
                 }
             } else {
@@ -734,6 +1083,7 @@ impl TelegramService {
                     "Token {} failed SOL invested filter: no data available", 
                     token.address
                 ).yellow().to_string());
+                record_rejection("sol_invested", &token.address);
                 return false;
             }
         }
@@ -758,6 +1108,7 @@ impl TelegramService {
                     volume,
                     limit_buy_amount
                 ).yellow().to_string());
+                record_rejection("limit_volume", &token.address);
                 return false;
             }
         }
@@ -767,6 +1118,7 @@ impl TelegramService {
             "Token {} passes all filters", 
             token.address
         ).green().to_string());
+        metrics().filters_passed.inc();
         
         true
     }
@@ -800,6 +1152,10 @@ impl TelegramService {
                 settings.dev_buy_bundle_enabled = !settings.dev_buy_bundle_enabled;
                 (settings.dev_buy_bundle_enabled, "DEV_BUY_ENABLED")
             },
+            "curve_progress" => {
+                settings.curve_progress_enabled = !settings.curve_progress_enabled;
+                (settings.curve_progress_enabled, "CURVE_PROGRESS_ENABLED")
+            },
             _ => return Err(anyhow!("Unknown filter: {}", filter_name)),
         };
         
@@ -844,6 +1200,10 @@ impl TelegramService {
                 let enabled = self.toggle_filter("dev_buy")?;
                 format!("Dev Buy & Bundle Check filter {}", if enabled { "enabled" } else { "disabled" })
             },
+            "toggle_curve_progress" => {
+                let enabled = self.toggle_filter("curve_progress")?;
+                format!("Curve Progress filter {}", if enabled { "enabled" } else { "disabled" })
+            },
             "stop_all" => {
                 // Use a block to ensure the mutex guard is dropped before the await point
                 {
@@ -854,6 +1214,7 @@ impl TelegramService {
                     settings.sol_invested_enabled = false;
                     settings.launcher_sol_balance_enabled = false;
                     settings.dev_buy_bundle_enabled = false;
+                    settings.curve_progress_enabled = false;
                     
                     // Save the settings to file for persistence
                     if let Err(e) = settings.save_to_file() {
@@ -957,6 +1318,45 @@ impl TelegramService {
                                                                 eprintln!("Error sending config path: {}", e);
                                                             }
                                                         },
+                                                        "/resume_breaker" => {
+                                                            circuit_breaker().reset();
+                                                            let msg = "<b>▶️ Circuit breaker reset</b>\n\nBuying is enabled again.";
+                                                            if let Err(e) = service.send_message(&chat_id, msg, "HTML").await {
+                                                                eprintln!("Error sending breaker reset confirmation: {}", e);
+                                                            }
+                                                        },
+                                                        "/positions" => {
+                                                            if let Err(e) = service.handle_positions(&chat_id).await {
+                                                                eprintln!("Error sending positions: {}", e);
+                                                            }
+                                                        },
+                                                        "/stats" => {
+                                                            if let Err(e) = service.handle_stats(&chat_id).await {
+                                                                eprintln!("Error sending filter stats: {}", e);
+                                                            }
+                                                        },
+                                                        "/resume" => {
+                                                            resume_trading();
+                                                            let msg = "<b>▶️ Trading resumed</b>\n\nNew buys are allowed again.";
+                                                            if let Err(e) = service.send_message(&chat_id, msg, "HTML").await {
+                                                                eprintln!("Error sending resume confirmation: {}", e);
+                                                            }
+                                                        },
+                                                        "/panic" => {
+                                                            if let Err(e) = service.handle_panic(&chat_id).await {
+                                                                eprintln!("Error handling panic command: {}", e);
+                                                            }
+                                                        },
+                                                        cmd if cmd.starts_with("/sell") => {
+                                                            if let Err(e) = service.handle_sell(&chat_id, cmd).await {
+                                                                eprintln!("Error handling sell command: {}", e);
+                                                            }
+                                                        },
+                                                        cmd if cmd.starts_with("/snipe") => {
+                                                            if let Err(e) = service.handle_snipe(&chat_id, cmd).await {
+                                                                eprintln!("Error handling snipe command: {}", e);
+                                                            }
+                                                        },
                                                         _ => {}
                                                     }
                                                 }
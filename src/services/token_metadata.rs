@@ -0,0 +1,175 @@
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Off-chain metadata a launch's URI points to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+impl TokenMetadata {
+    /// Parse metadata JSON, with links at the top level (pump.fun) or under `extensions` (Metaplex)
+    pub fn parse(body: &Value) -> Option<Self> {
+        if !body.is_object() {
+            return None;
+        }
+        let extensions = body.get("extensions").cloned().unwrap_or(Value::Null);
+        let link = |key: &str| text(body, key).or_else(|| text(&extensions, key));
+        Some(Self {
+            name: text(body, "name"),
+            symbol: text(body, "symbol"),
+            description: text(body, "description"),
+            image: text(body, "image"),
+            twitter: link("twitter"),
+            telegram: link("telegram"),
+            website: link("website"),
+        })
+    }
+}
+
+/// Host of a link, accepting links written without a scheme
+fn host(link: &str) -> Option<String> {
+    let rest = link.split_once("://").map(|(_, rest)| rest).unwrap_or(link);
+    let host = rest.split(['/', '?', '#']).next()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+    (host.contains('.') && !host.contains(' ')).then_some(host)
+}
+
+/// Twitter link pointing at twitter.com or x.com
+fn is_twitter(link: &str) -> bool {
+    matches!(host(link).as_deref(), Some("twitter.com" | "x.com"))
+}
+
+/// Telegram link pointing at t.me or telegram.me
+fn is_telegram(link: &str) -> bool {
+    matches!(host(link).as_deref(), Some("t.me" | "telegram.me"))
+}
+
+/// Metadata quality filter settings
+#[derive(Debug, Clone)]
+pub struct MetadataFilter {
+    /// Skip launches whose metadata URI does not resolve to JSON
+    pub require_metadata: bool,
+    pub require_twitter: bool,
+    pub require_telegram: bool,
+    pub require_website: bool,
+    /// Gateway `ipfs://` URIs are fetched through
+    pub ipfs_gateway: String,
+    /// Time the URI gets to answer
+    pub timeout_ms: u64,
+}
+
+impl MetadataFilter {
+    /// Load metadata filter settings from environment variables
+    pub fn from_env() -> Self {
+        let flag = |key: &str| std::env::var(key)
+            .unwrap_or_else(|_| "false".to_string())
+            .to_lowercase() == "true";
+        Self {
+            require_metadata: flag("REQUIRE_METADATA"),
+            require_twitter: flag("REQUIRE_TWITTER"),
+            require_telegram: flag("REQUIRE_TELEGRAM"),
+            require_website: flag("REQUIRE_WEBSITE"),
+            ipfs_gateway: std::env::var("IPFS_GATEWAY").unwrap_or_else(|_| "https://ipfs.io/ipfs/".to_string()),
+            timeout_ms: std::env::var("METADATA_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(2000),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.require_metadata || self.require_twitter || self.require_telegram || self.require_website
+    }
+
+    /// Reason to skip a launch, if its metadata fails the filter
+    ///
+    /// `None` metadata means the URI did not resolve.
+    pub fn rejection(&self, metadata: Option<&TokenMetadata>) -> Option<String> {
+        let Some(metadata) = metadata else {
+            return Some("metadata URI did not resolve".to_string());
+        };
+        if self.require_twitter && !metadata.twitter.as_deref().is_some_and(is_twitter) {
+            return Some("no Twitter link".to_string());
+        }
+        if self.require_telegram && !metadata.telegram.as_deref().is_some_and(is_telegram) {
+            return Some("no Telegram link".to_string());
+        }
+        if self.require_website && metadata.website.as_deref().and_then(host).is_none() {
+            return Some("no website".to_string());
+        }
+        None
+    }
+
+    /// HTTP URL to fetch `uri` from
+    fn resolve(&self, uri: &str) -> String {
+        match uri.strip_prefix("ipfs://") {
+            Some(cid) => format!("{}/{}", self.ipfs_gateway.trim_end_matches('/'), cid.trim_start_matches("ipfs/")),
+            None => uri.to_string(),
+        }
+    }
+
+    /// Fetch and parse the metadata `uri` points to
+    pub async fn fetch(&self, client: &reqwest::Client, uri: &str) -> Result<TokenMetadata> {
+        if uri.trim().is_empty() {
+            return Err(anyhow!("empty metadata URI"));
+        }
+        let body: Value = client
+            .get(self.resolve(uri.trim()))
+            .timeout(Duration::from_millis(self.timeout_ms.max(100)))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        TokenMetadata::parse(&body).ok_or_else(|| anyhow!("metadata is not a JSON object"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_metadata_filter() {
+        let metadata = TokenMetadata::parse(&json!({
+            "name": "Coin", "symbol": "COIN", "image": "https://ipfs.io/ipfs/Qm",
+            "twitter": "https://x.com/coin", "telegram": "", "website": "coin.fun",
+        })).unwrap();
+        let mut filter = MetadataFilter {
+            require_metadata: true,
+            require_twitter: true,
+            require_telegram: false,
+            require_website: true,
+            ipfs_gateway: "https://ipfs.io/ipfs/".to_string(),
+            timeout_ms: 2000,
+        };
+        assert_eq!(filter.rejection(Some(&metadata)), None);
+        assert!(filter.rejection(None).is_some());
+        filter.require_telegram = true;
+        assert_eq!(filter.rejection(Some(&metadata)), Some("no Telegram link".to_string()));
+
+        let metaplex = TokenMetadata::parse(&json!({"name": "Coin", "extensions": {"twitter": "https://twitter.com/coin", "telegram": "t.me/coin"}})).unwrap();
+        assert_eq!(metaplex.telegram.as_deref(), Some("t.me/coin"));
+        assert!(is_telegram("t.me/coin"));
+        assert!(!is_twitter("https://notx.com/coin"));
+        assert_eq!(filter.resolve("ipfs://Qm123"), "https://ipfs.io/ipfs/Qm123");
+    }
+}
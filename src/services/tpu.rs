@@ -0,0 +1,117 @@
+use std::sync::{Arc, OnceLock};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use anchor_client::solana_client::{
+    nonblocking::{rpc_client::RpcClient, tpu_client::TpuClient},
+    tpu_client::TpuClientConfig,
+};
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, transaction::VersionedTransaction};
+
+use crate::common::logger::Logger;
+
+/// Executor trades sent over the TPU path instead of Jito or RPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TpuRoute {
+    /// Only the relay selector and the spam racer use it
+    None,
+    Buys,
+    All,
+}
+
+impl TpuRoute {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "buys" => Some(Self::Buys),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// Whether a buy (`is_buy`) or another trade goes over the TPU path
+    pub fn covers(&self, is_buy: bool) -> bool {
+        match self {
+            Self::None => false,
+            Self::Buys => is_buy,
+            Self::All => true,
+        }
+    }
+}
+
+/// Direct TPU submission settings
+#[derive(Debug, Clone)]
+pub struct TpuSettings {
+    /// Connect a TPU client at startup, making `tpu` available as a relay
+    pub enabled: bool,
+    /// Upcoming leader slots each transaction is sent to, the current leader included
+    pub fanout_slots: u64,
+    pub route: TpuRoute,
+}
+
+impl TpuSettings {
+    /// Load TPU settings from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("TPU_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase() == "true",
+            fanout_slots: std::env::var("TPU_FANOUT_SLOTS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(4),
+            route: std::env::var("TPU_ROUTE")
+                .ok()
+                .and_then(|v| TpuRoute::parse(&v))
+                .unwrap_or(TpuRoute::None),
+        }
+    }
+}
+
+static TPU_CLIENT: OnceLock<Arc<TpuClient>> = OnceLock::new();
+
+/// Connected TPU client, if the TPU path was started
+pub fn tpu_client() -> Option<Arc<TpuClient>> {
+    TPU_CLIENT.get().cloned()
+}
+
+/// Whether transactions can be pushed to the leaders directly
+pub fn is_running() -> bool {
+    TPU_CLIENT.get().is_some()
+}
+
+/// Connect the TPU client, which follows the leader schedule over the RPC websocket
+pub async fn start_tpu_client(settings: &TpuSettings, logger: &Logger) -> Result<()> {
+    let rpc_http = std::env::var("RPC_HTTP").unwrap_or_default();
+    let rpc_wss = std::env::var("RPC_WSS").unwrap_or_default();
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(rpc_http, CommitmentConfig::processed()));
+    let config = TpuClientConfig { fanout_slots: settings.fanout_slots.max(1) };
+    let client = TpuClient::new("vntr-tpu", rpc_client, &rpc_wss, config).await?;
+    TPU_CLIENT.set(Arc::new(client)).map_err(|_| anyhow!("TPU client already started"))?;
+    logger.log(format!("TPU path ready, sending to the next {} leader slots over QUIC", settings.fanout_slots.max(1)).green().to_string());
+    Ok(())
+}
+
+/// Push a signed transaction to the current and upcoming leaders
+pub async fn send_transaction(txn: &VersionedTransaction) -> Result<()> {
+    let client = tpu_client().ok_or_else(|| anyhow!("TPU path is not running (set TPU_ENABLED=true)"))?;
+    let wire_transaction = bincode::serialize(txn)?;
+    if !client.send_wire_transaction(wire_transaction).await {
+        return Err(anyhow!("No leader accepted the transaction over QUIC"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert_eq!(TpuRoute::parse(" Buys "), Some(TpuRoute::Buys));
+        assert_eq!(TpuRoute::parse("leaders"), None);
+        assert!(TpuRoute::Buys.covers(true));
+        assert!(!TpuRoute::Buys.covers(false));
+        assert!(TpuRoute::All.covers(false));
+        assert!(!TpuRoute::None.covers(true));
+    }
+}
@@ -0,0 +1,293 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::common::logger::Logger;
+use crate::common::reason_code::ReasonCode;
+use crate::common::trade_log::TradeLogEntry;
+
+/// A trade event the webhook can be sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    Buy,
+    Sell,
+    /// A buy or sell failed on-chain or was never confirmed
+    Fail,
+    StopLoss,
+}
+
+impl WebhookEventKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "buy" => Some(WebhookEventKind::Buy),
+            "sell" => Some(WebhookEventKind::Sell),
+            "fail" => Some(WebhookEventKind::Fail),
+            "stop_loss" => Some(WebhookEventKind::StopLoss),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEventKind::Buy => "buy",
+            WebhookEventKind::Sell => "sell",
+            WebhookEventKind::Fail => "fail",
+            WebhookEventKind::StopLoss => "stop_loss",
+        }
+    }
+}
+
+/// Parse an event list like `buy,sell,fail,stop_loss`
+pub fn parse_events(value: &str) -> Result<Vec<WebhookEventKind>, String> {
+    let mut events = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let event = WebhookEventKind::parse(name).ok_or_else(|| format!("Unknown webhook event '{}'", name))?;
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// Outbound webhook settings
+#[derive(Debug, Clone)]
+pub struct WebhookSettings {
+    /// Endpoint events are POSTed to; empty turns the webhook off
+    pub url: String,
+    /// Key of the HMAC-SHA256 signature header; empty sends unsigned requests
+    pub secret: String,
+    pub events: Vec<WebhookEventKind>,
+    /// Time one delivery attempt gets to answer
+    pub timeout_ms: u64,
+    /// Delivery attempts before an event is dropped
+    pub attempts: u32,
+}
+
+impl WebhookSettings {
+    /// Load outbound webhook settings from environment variables
+    pub fn from_env() -> Self {
+        let events = std::env::var("WEBHOOK_EVENTS").unwrap_or_else(|_| "buy,sell,fail,stop_loss".to_string());
+        Self {
+            url: std::env::var("WEBHOOK_URL").unwrap_or_default(),
+            secret: std::env::var("WEBHOOK_SECRET").unwrap_or_default(),
+            events: parse_events(&events).unwrap_or_else(|e| {
+                eprintln!("Ignoring WEBHOOK_EVENTS: {}", e);
+                vec![WebhookEventKind::Buy, WebhookEventKind::Sell, WebhookEventKind::Fail, WebhookEventKind::StopLoss]
+            }),
+            timeout_ms: std::env::var("WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(3000),
+            attempts: std::env::var("WEBHOOK_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(3),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.url.trim().is_empty()
+    }
+}
+
+/// JSON body of one webhook request
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub timestamp: DateTime<Utc>,
+    pub mint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sol_amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
+}
+
+impl WebhookEvent {
+    /// Event for a settled trade: confirmed buys and sells, everything else is a failure
+    pub fn from_trade(trade: &TradeLogEntry) -> Self {
+        let event = match (trade.status.as_str(), trade.side.as_str()) {
+            ("confirmed", "buy") => WebhookEventKind::Buy,
+            ("confirmed", _) => WebhookEventKind::Sell,
+            _ => WebhookEventKind::Fail,
+        };
+        Self {
+            event,
+            timestamp: trade.timestamp,
+            mint: trade.mint.clone(),
+            side: Some(trade.side.clone()),
+            signature: Some(trade.signature.clone()),
+            sol_amount: Some(trade.sol_amount),
+            token_amount: Some(trade.token_amount),
+            price: Some(trade.price),
+            reason: trade.reason.clone().or_else(|| (event == WebhookEventKind::Fail).then(|| trade.status.clone())),
+            reason_code: trade.reason_code,
+        }
+    }
+
+    /// Event for a stop loss that fired, sent before its sell is submitted
+    pub fn stop_loss(mint: &str, reason: &str) -> Self {
+        Self {
+            event: WebhookEventKind::StopLoss,
+            timestamp: Utc::now(),
+            mint: mint.to_string(),
+            side: Some("sell".to_string()),
+            signature: None,
+            sol_amount: None,
+            token_amount: None,
+            price: None,
+            reason: Some(reason.to_string()),
+            reason_code: Some(ReasonCode::StopLossTriggered),
+        }
+    }
+}
+
+/// Hex HMAC-SHA256 of `{timestamp}.{body}`, so a captured request cannot be replayed with a new timestamp
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Delivers trade events to a user-configured HTTP endpoint
+pub struct Webhook {
+    settings: WebhookSettings,
+    client: reqwest::Client,
+    logger: Logger,
+}
+
+impl Webhook {
+    pub fn new(settings: WebhookSettings) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(settings.timeout_ms.max(100)))
+            .build()
+            .unwrap_or_default();
+        Self {
+            settings,
+            client,
+            logger: Logger::new("[WEBHOOK] => ".blue().bold().to_string()),
+        }
+    }
+
+    pub fn settings(&self) -> &WebhookSettings {
+        &self.settings
+    }
+
+    /// POST the event in the background, retrying with backoff
+    pub fn notify(&'static self, event: WebhookEvent) {
+        if !self.settings.is_enabled() || !self.settings.events.contains(&event.event) {
+            return;
+        }
+        tokio::spawn(async move {
+            let body = match serde_json::to_string(&event) {
+                Ok(body) => body,
+                Err(e) => {
+                    self.logger.error(format!("Failed to serialize {} event: {}", event.event.name(), e));
+                    return;
+                }
+            };
+            let attempts = self.settings.attempts.max(1);
+            for attempt in 1..=attempts {
+                match self.deliver(&body).await {
+                    Ok(()) => return,
+                    Err(e) if attempt == attempts => self.logger.error(format!(
+                        "Dropped {} event for {} after {} attempts: {}",
+                        event.event.name(), event.mint, attempts, e
+                    )),
+                    Err(_) => tokio::time::sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1))).await,
+                }
+            }
+        });
+    }
+
+    async fn deliver(&self, body: &str) -> anyhow::Result<()> {
+        let timestamp = Utc::now().timestamp();
+        let mut request = self.client
+            .post(&self.settings.url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .body(body.to_string());
+        if !self.settings.secret.is_empty() {
+            request = request.header("X-Webhook-Signature", format!("sha256={}", sign(&self.settings.secret, timestamp, body)));
+        }
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+static WEBHOOK: LazyLock<Webhook> = LazyLock::new(|| Webhook::new(WebhookSettings::from_env()));
+
+/// Get the process-wide outbound webhook
+pub fn webhook() -> &'static Webhook {
+    &WEBHOOK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abandoned_sell() -> TradeLogEntry {
+        TradeLogEntry {
+            timestamp: Utc::now(),
+            mint: "mint".to_string(),
+            side: "sell".to_string(),
+            status: "abandoned".to_string(),
+            signature: "sig".to_string(),
+            sol_amount: 0.5,
+            token_amount: 1000,
+            price: 0.0005,
+            fee_sol: 0.000005,
+            tip_sol: 0.0,
+            attempts: 3,
+            latency_ms: 900,
+            reason: None,
+            reason_code: Some(ReasonCode::StopLossTriggered),
+        }
+    }
+
+    #[test]
+    fn test_parse_events_dedups_and_rejects_unknown() {
+        assert_eq!(
+            parse_events("sell, stop_loss,sell").unwrap(),
+            vec![WebhookEventKind::Sell, WebhookEventKind::StopLoss]
+        );
+        assert!(parse_events("buy,rug").is_err());
+    }
+
+    #[test]
+    fn test_signature_is_hmac_of_timestamp_and_body() {
+        assert_eq!(
+            sign("secret", 1700000000, r#"{"event":"buy"}"#),
+            "0bec03b7cd10e80e80109833dd93187514e2b5f14e53d9572c5fa36d3e4a0f9d"
+        );
+    }
+
+    #[test]
+    fn test_abandoned_trade_is_a_fail_event() {
+        let event = WebhookEvent::from_trade(&abandoned_sell());
+        assert_eq!(event.event, WebhookEventKind::Fail);
+        assert_eq!(event.reason.as_deref(), Some("abandoned"));
+        assert_eq!(serde_json::to_value(&event).unwrap()["reason_code"], "StopLossTriggered");
+    }
+
+    #[test]
+    fn test_stop_loss_event_omits_signature() {
+        let json = serde_json::to_value(WebhookEvent::stop_loss("mint", "stop loss at -31.0%")).unwrap();
+        assert_eq!(json["event"], "stop_loss");
+        assert!(json.get("signature").is_none());
+    }
+}
@@ -2,9 +2,104 @@ use crate::error::ClientError;
 use anyhow::{anyhow, Result};
 use rand::{seq::IteratorRandom, thread_rng};
 use serde_json::{json, Value};
-use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
 use std::{str::FromStr, sync::LazyLock};
 
 use crate::common::config::import_env_var;
 
 pub static ZERO_SLOT_URL: LazyLock<String> = LazyLock::new(|| import_env_var("ZERO_SLOT_URL"));
+
+pub fn get_tip_account() -> Result<Pubkey> {
+    let accounts = [
+        "6fQaVhYZA4w3MBSXjJ81Vf6W1EDYeUPXpgVQ6UQyU1Av".to_string(),
+        "4HiwLEP2Bzqj3hM2ENxJuzhcPCdsafwiet3oGkMkuQY4".to_string(),
+        "7toBU3inhmrARGngC7z6SjyP85HgGMmCTEwGNRAcYnEK".to_string(),
+        "8mR3wB1nh4D6J9RUCugxUpc6ya8w38LPxZ3ZjcBhgzws".to_string(),
+        "6SiVU5WEwqfFapRuYCndomztEwDjvS5xgtEof3PLEGm9".to_string(),
+        "TpdxgNJBWZRL8UXF5mrEsyWxDWx9HQexA9P1eTWQ42p".to_string(),
+        "D8f3WkQu6dCF33cZxuAsrKHrGsqGP2yvAHf8mX6RXnwf".to_string(),
+        "GQPFicsy3P3NDxXKmx2nCiWCJdZhWtdQqpgB9cqzEc8K".to_string(),
+    ];
+    let mut rng = thread_rng();
+    match accounts.iter().choose(&mut rng) {
+        Some(acc) => Ok(Pubkey::from_str(acc).inspect_err(|err| {
+            println!("zeroslot: failed to parse Pubkey: {:?}", err);
+        })?),
+        None => Err(anyhow!("zeroslot: no tip accounts available")),
+    }
+}
+
+// unit sol, ZERO_SLOT_TIP_VALUE is in lamports
+pub async fn get_tip_value() -> Result<f64> {
+    match std::env::var("ZERO_SLOT_TIP_VALUE") {
+        Ok(tip_value) => match u64::from_str(&tip_value) {
+            Ok(lamports) => Ok(lamports as f64 / 1_000_000_000.0),
+            Err(_) => Err(anyhow!("Invalid ZERO_SLOT_TIP_VALUE in environment variable: '{}'", tip_value)),
+        },
+        Err(_) => Err(anyhow!("ZERO_SLOT_TIP_VALUE environment variable not set")),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ZeroSlotClient {
+    endpoint: String,
+    client: reqwest::Client,
+}
+
+impl ZeroSlotClient {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, ClientError> {
+        let wire_transaction = bincode::serialize(transaction).map_err(|e| {
+            ClientError::Parse(
+                "Transaction serialization failed".to_string(),
+                e.to_string(),
+            )
+        })?;
+
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [
+                bs64::encode(&wire_transaction),
+                { "encoding": "base64" }
+            ]
+        });
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| ClientError::Send("Request failed".to_string(), e.to_string()))?;
+
+        let response_data: Value = response
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        if let Some(error) = response_data.get("error") {
+            return Err(ClientError::Send("ZeroSlot error".to_string(), error.to_string()));
+        }
+
+        let signature = response_data["result"].as_str().ok_or_else(|| {
+            ClientError::Parse(
+                "Invalid response format".to_string(),
+                "Missing result field".to_string(),
+            )
+        })?;
+        Signature::from_str(signature)
+            .map_err(|e| ClientError::Parse("Invalid signature".to_string(), e.to_string()))
+    }
+}
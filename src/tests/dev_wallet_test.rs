@@ -1,5 +1,7 @@
 use std::sync::Arc;
-use crate::engine::token_tracker::{TokenTracker, StreamEvent};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use crate::dex::pump_fun::TOKEN_TOTAL_SUPPLY;
+use crate::engine::token_tracker::ExtendedTokenInfo;
 use crate::common::logger::Logger;
 use crate::services::telegram::TelegramService;
 use colored::Colorize;
@@ -9,10 +11,9 @@ use std::env;
 /// Runs a test of the dev wallet identification and notification deduplication features
 /// 
 /// This function will:
-/// 1. Create a token tracker
-/// 2. Add a test token to the tracker
-/// 3. Simulate transactions for the token
-/// 4. Try to identify the dev wallet
+/// 1. Create a test token with the creator recorded as its dev wallet
+/// 2. Simulate transactions for the token
+/// 3. Report the dev wallet
 /// 5. Send a notification via Telegram (if credentials are available)
 /// 6. Test notification deduplication by trying to send another notification for the same token
 pub async fn run_dev_wallet_test() -> Result<(), Box<dyn std::error::Error>> {
@@ -33,49 +34,21 @@ pub async fn run_dev_wallet_test() -> Result<(), Box<dyn std::error::Error>> {
         None
     };
     
-    // Initialize token tracker with default parameters
-    let token_tracker = Arc::new(TokenTracker::new(
-        logger.clone(),
-        10.0,  // price_check_threshold
-        3600,  // time_threshold_secs
-        1.0,   // min_market_cap
-        1000.0, // max_market_cap
-        1.0,   // min_volume
-        1000.0, // max_volume
-        5,     // min_buy_sell_count
-        1000,  // max_buy_sell_count
-        0.1,   // min_launcher_sol_balance
-        100.0  // max_launcher_sol_balance
-    ));
-    
-    // Create a simulated token mint event
+    // Create a simulated token launch, the creator is the dev wallet
     let test_token_mint = format!("TestToken{}", chrono::Utc::now().timestamp());
-    let _stream_event = StreamEvent::TokenMint {
-        token_mint: test_token_mint.clone(),
-        dev_buy_amount: 10.0,         // Dev bought 10 SOL worth
-        launcher_sol_balance: 5.0,    // Launcher has 5 SOL
-        token_price: 0.001,           // Initial token price 
-        bundle_check: true,           // Bundle check passed
-    };
-    
-    logger.log(format!("Simulating token mint event for token: {}", test_token_mint).cyan().to_string());
-    
-    // Add the token to the tracker
-    let _token = token_tracker.add_token(
+    let mut token = ExtendedTokenInfo::new(
         test_token_mint.clone(),
-        0.001, // Initial price 
-        5.0,   // Launcher SOL
-        Some(10.0), // Dev buy amount 
-        Some(5.0),  // Launcher SOL balance
-        Some(true), // Bundle check
         Some("TEST".to_string()), // Token name
         Some("TST".to_string()),  // Token symbol
-    ).await?;
-    
-    logger.log("Token added to tracker".green().to_string());
+        0.001,                    // Initial token price
+        TOKEN_TOTAL_SUPPLY,
+        Some(10.0),               // Dev bought 10 SOL worth
+        Some(5.0),                // Launcher has 5 SOL
+        Some(true),               // Bundle check passed
+        Some(Pubkey::new_unique().to_string()),
+    );
     
-    // Wait a moment for processing
-    tokio::time::sleep(Duration::from_secs(1)).await;
+    logger.log(format!("Simulating token mint event for token: {}", test_token_mint).cyan().to_string());
     
     // Simulate token transactions to increase buy/sell count
     for i in 0..10 {
@@ -89,64 +62,44 @@ pub async fn run_dev_wallet_test() -> Result<(), Box<dyn std::error::Error>> {
             amount
         ).cyan().to_string());
         
-        token_tracker.update_token(
-            &test_token_mint,
-            amount,
-            is_buy
-        ).await?;
+        token.update_price(token.current_token_price * if is_buy { 1.01 } else { 0.99 }, is_buy);
         
         // Short delay between transactions
         tokio::time::sleep(Duration::from_millis(200)).await;
     }
     
-    // Get the token information from the tracker
-    if let Some(token_info) = token_tracker.get_token(&test_token_mint).await {
-        logger.log(format!(
-            "Token {} has {} buys and {} sells",
-            test_token_mint,
-            token_info.buy_count,
-            token_info.sell_count
-        ).green().to_string());
+    logger.log(format!(
+        "Token {} has {} buys and {} sells",
+        test_token_mint,
+        token.buy_tx_num,
+        token.sell_tx_num
+    ).green().to_string());
+    
+    logger.log(format!(
+        "Extended token info: dev wallet = {}",
+        token.dev_wallet.as_ref().unwrap_or(&"Not identified".to_string())
+    ).green().to_string());
+    
+    // Send notification via Telegram if available
+    if let Some(telegram) = &telegram_service {
+        logger.log("Sending Telegram notification...".to_string());
+        
+        let telegram_token_info = token.to_telegram_token_info();
         
-        // Get extended token info
-        let extended_tokens = token_tracker.get_extended_tokens_map_arc();
-        let extended_token_info = {
-            let guard = extended_tokens.lock().unwrap();
-            guard.get(&test_token_mint).map(|token| token.clone_without_task())
+        match telegram.send_token_notification(&telegram_token_info).await {
+            Ok(_) => logger.log("Telegram notification sent successfully".green().to_string()),
+            Err(e) => logger.log(format!("Failed to send Telegram notification: {}", e).red().to_string()),
         };
         
-        if let Some(ext_info) = extended_token_info {
-            logger.log(format!(
-                "Extended token info: dev wallet = {}",
-                ext_info.dev_wallet.as_ref().unwrap_or(&"Not identified".to_string())
-            ).green().to_string());
-            
-            // Send notification via Telegram if available
-            if let Some(telegram) = &telegram_service {
-                logger.log("Sending Telegram notification...".to_string());
-                
-                let telegram_token_info = ext_info.to_telegram_token_info();
-                
-                match telegram.send_token_notification(&telegram_token_info).await {
-                    Ok(_) => logger.log("Telegram notification sent successfully".green().to_string()),
-                    Err(e) => logger.log(format!("Failed to send Telegram notification: {}", e).red().to_string()),
-                };
-                
-                // Wait a moment for the notification to be processed
-                tokio::time::sleep(Duration::from_secs(2)).await;
-                
-                // Try to send another notification for the same token - this should be prevented
-                logger.log("Attempting to send another notification for the same token...".yellow().to_string());
-                match telegram.send_token_notification(&telegram_token_info).await {
-                    Ok(_) => logger.log("Second notification sent - duplicate prevention failed!".red().to_string()),
-                    Err(e) => logger.log(format!("Second notification properly blocked: {}", e).green().to_string()),
-                };
-            }
-        } else {
-            logger.log("Extended token info not found".red().to_string());
-        }
-    } else {
-        logger.log("Token not found in tracker".red().to_string());
+        // Wait a moment for the notification to be processed
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        
+        // Try to send another notification for the same token - this should be prevented
+        logger.log("Attempting to send another notification for the same token...".yellow().to_string());
+        match telegram.send_token_notification(&telegram_token_info).await {
+            Ok(_) => logger.log("Second notification sent - duplicate prevention failed!".red().to_string()),
+            Err(e) => logger.log(format!("Second notification properly blocked: {}", e).green().to_string()),
+        };
     }
     
     logger.log("Test script completed".green().to_string());
@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use colored::Colorize;
+use spl_associated_token_account::get_associated_token_address;
+use tokio::time::{Duration, Instant};
+
+use crate::common::arming::arm_for_localnet;
+use crate::common::config::{AppState, SwapConfig};
+use crate::common::logger::Logger;
+use crate::core::position_store::PositionStore;
+use crate::dex::program_ids::program_ids;
+use crate::dex::pump_fun::{creator_vault_pda, get_token_creator};
+use crate::engine::shutdown::{wait_for_in_flight, ShutdownSettings};
+use crate::engine::swap::{SwapDirection, SwapInType};
+use crate::engine::trade_executor::TradeExecutor;
+
+const LOCALNET_RPC: &str = "http://127.0.0.1:8899";
+
+/// Fixture files produced by `./localnet.sh dump`
+struct Fixtures {
+    dir: PathBuf,
+    mint: Pubkey,
+}
+
+impl Fixtures {
+    fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let dir = PathBuf::from(std::env::var("LOCALNET_FIXTURES_DIR").unwrap_or_else(|_| "fixtures".to_string()));
+        let mint = std::fs::read_to_string(dir.join("mint.txt"))
+            .map_err(|_| format!("Missing {}/mint.txt, run ./localnet.sh dump first", dir.display()))?;
+        Ok(Self {
+            mint: Pubkey::from_str(mint.trim())?,
+            dir,
+        })
+    }
+
+    /// Every `<address>.json` account dump in the fixture directory
+    fn account_files(&self) -> Result<Vec<(String, PathBuf)>, Box<dyn std::error::Error>> {
+        let mut accounts = Vec::new();
+        for entry in std::fs::read_dir(self.dir.join("accounts"))? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                let address = path.file_stem().unwrap().to_string_lossy().to_string();
+                accounts.push((address, path));
+            }
+        }
+        Ok(accounts)
+    }
+}
+
+/// A solana-test-validator child process that is killed when dropped
+struct LocalValidator {
+    child: Child,
+}
+
+impl LocalValidator {
+    fn start(fixtures: &Fixtures, ledger: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut command = Command::new("solana-test-validator");
+        command
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(ledger)
+            .arg("--bpf-program")
+            .arg(program_ids().pump_fun_program.to_string())
+            .arg(fixtures.dir.join("pump.so"));
+
+        for (address, path) in fixtures.account_files()? {
+            command.arg("--account").arg(address).arg(path);
+        }
+
+        let child = command.stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+        Ok(Self { child })
+    }
+}
+
+impl Drop for LocalValidator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Runs a real buy and sell through the `TradeExecutor` against a local validator loaded with pump.fun fixtures
+///
+/// This function will:
+/// 1. Start solana-test-validator with the dumped pump.fun program and accounts
+/// 2. Fund a fresh wallet, and the creator vault if it was not dumped, via airdrop
+/// 3. Buy the fixture mint through the executor and check the position and token balance
+/// 4. Sell the full balance back through the executor and check the SOL returned
+///
+/// The trades take the bot's own route, from venue lookup and instruction building to
+/// confirmation tracking, so any account-layout regression shows up here as a failed
+/// transaction instead of on mainnet.
+pub async fn run_localnet_test() -> Result<(), Box<dyn std::error::Error>> {
+    let logger = Logger::new("[TEST LOCALNET] => ".green().bold().to_string());
+    let fixtures = Fixtures::load()?;
+    let ledger = std::env::temp_dir().join("vntr-localnet-ledger");
+
+    logger.log(format!("Starting local validator with fixtures from {}", fixtures.dir.display()));
+    let _validator = LocalValidator::start(&fixtures, &ledger)?;
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        LOCALNET_RPC.to_string(),
+        CommitmentConfig::confirmed(),
+    ));
+    wait_for_validator(&rpc_client, Duration::from_secs(60)).await?;
+    logger.log("Local validator is up".green().to_string());
+
+    // Fund a throwaway wallet
+    let wallet = Keypair::new();
+    airdrop(&rpc_client, &wallet.pubkey(), 10 * LAMPORTS_PER_SOL).await?;
+
+    // Creator fees go to a system account that must stay rent-exempt, as it is on mainnet
+    let creator = get_token_creator(rpc_client.clone(), &fixtures.mint).await?;
+    let creator_vault = creator_vault_pda(&creator);
+    if rpc_client.get_balance(&creator_vault).await? == 0 {
+        let rent = rpc_client.get_minimum_balance_for_rent_exemption(0).await?;
+        airdrop(&rpc_client, &creator_vault, rent).await?;
+    }
+
+    arm_for_localnet(LOCALNET_RPC)?;
+    let wallet = Arc::new(wallet);
+    let executor = TradeExecutor::new(
+        AppState {
+            rpc_client: Arc::new(anchor_client::solana_client::rpc_client::RpcClient::new_with_commitment(
+                LOCALNET_RPC.to_string(),
+                CommitmentConfig::confirmed(),
+            )),
+            rpc_nonblocking_client: rpc_client.clone(),
+            wallet: wallet.clone(),
+            wallets: Vec::new(),
+        },
+        SwapConfig {
+            swap_direction: SwapDirection::Buy,
+            in_type: SwapInType::Qty,
+            amount_in: 0.1,
+            slippage: 2_000, // 20%
+            use_jito: false,
+        },
+        Arc::new(PositionStore::new()),
+        Logger::new("[EXECUTOR] => ".green().bold().to_string()),
+    );
+    let settle = ShutdownSettings::from_env();
+    let mint = fixtures.mint.to_string();
+    let user_ata = get_associated_token_address(&wallet.pubkey(), &fixtures.mint);
+
+    // Buy
+    let signature = executor.buy(&mint, 0.1).await?;
+    wait_for_in_flight(&executor, &settle, &logger).await;
+    if !executor.open_positions().contains(&mint) {
+        return Err(format!("Buy {} did not open a position", signature).into());
+    }
+    let held = token_balance(&rpc_client, &user_ata).await;
+    if held == 0 {
+        return Err(format!("Buy {} landed but no tokens were received", signature).into());
+    }
+    logger.log(format!("BUY ok: received {} tokens in {}", held, signature).green().to_string());
+
+    // Sell everything back
+    let sol_before = rpc_client.get_balance(&wallet.pubkey()).await?;
+    let signature = executor.sell(&mint, 100.0).await?;
+    wait_for_in_flight(&executor, &settle, &logger).await;
+
+    let sol_after = rpc_client.get_balance(&wallet.pubkey()).await?;
+    let remaining = token_balance(&rpc_client, &user_ata).await;
+    if remaining != 0 || sol_after <= sol_before || executor.has_position(&mint) {
+        return Err(format!(
+            "Sell {} did not settle: {} tokens left, SOL {} -> {}",
+            signature, remaining, sol_before, sol_after
+        ).into());
+    }
+    logger.log(format!("SELL ok: received {} lamports in {}", sol_after - sol_before, signature).green().to_string());
+
+    logger.log("Localnet test completed".green().to_string());
+    Ok(())
+}
+
+async fn airdrop(rpc_client: &RpcClient, to: &Pubkey, lamports: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let signature = rpc_client.request_airdrop(to, lamports).await?;
+    rpc_client.poll_for_signature(&signature).await?;
+    Ok(())
+}
+
+/// Token balance of an account, 0 once the account is closed
+async fn token_balance(rpc_client: &RpcClient, account: &Pubkey) -> u64 {
+    match rpc_client.get_token_account_balance(account).await {
+        Ok(balance) => balance.amount.parse::<u64>().unwrap_or(0),
+        Err(_) => 0,
+    }
+}
+
+async fn wait_for_validator(rpc_client: &RpcClient, timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Instant::now();
+    while start_time.elapsed() < timeout {
+        if rpc_client.get_health().await.is_ok() {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    Err("Timed out waiting for solana-test-validator".into())
+}
@@ -1,4 +1,6 @@
 pub mod dev_wallet_test;
+pub mod localnet_test;
 
 // Export test functions if needed
-pub use dev_wallet_test::run_dev_wallet_test; 
\ No newline at end of file
+pub use dev_wallet_test::run_dev_wallet_test;
+pub use localnet_test::run_localnet_test; 
\ No newline at end of file